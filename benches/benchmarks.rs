@@ -1,8 +1,9 @@
 //! Performance benchmarks for palette generation.
 //!
 //! Measures the hot paths:
-//! - Full palette generation
-//! - HellwigJmh color space conversions
+//! - Full palette generation, for several representative configs
+//! - HellwigJmh color space conversions (scalar and batched; build with
+//!   `--features simd` to measure the vectorized path)
 //! - APCA contrast calculations
 //! - Accent solver COBYLA optimization
 //! - Gamut mapping operations
@@ -10,13 +11,20 @@
 
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 use palette::Srgb;
-use themalingadingdong::accent_solver::optimize_accents;
+use themalingadingdong::accent_solver::{
+    clear_solution_cache, optimize_accents, optimize_accents_in,
+};
 use themalingadingdong::apca::apca_contrast;
-use themalingadingdong::config::AccentOptSettings;
+use themalingadingdong::config::{AccentOptSettings, CommentContrast};
 use themalingadingdong::curves::{CurveType, InterpolationConfig};
-use themalingadingdong::gamut_map::{gamut_map, max_colorfulness_at};
+use themalingadingdong::extended_accents::{ExtendedAccentMode, ExtendedAccents};
+use themalingadingdong::gamut_map::{
+    Gamut, clear_gamut_cache, gamut_map, max_colorfulness_at, max_colorfulness_in,
+    max_colorfulness_many,
+};
 use themalingadingdong::generate::{GenerateConfig, generate};
-use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::hellwig::{HellwigJmh, batch_from_srgb, batch_into_srgb};
+use themalingadingdong::hue_spacing::HueSpacing;
 use themalingadingdong::interpolation::interpolate_with_curves;
 
 /// Benchmark full palette generation with default config.
@@ -24,10 +32,59 @@ fn bench_palette_generation(c: &mut Criterion) {
     let config = GenerateConfig::default();
 
     c.bench_function("palette_generation", |b| {
-        b.iter(|| generate(black_box(&config)))
+        b.iter(|| {
+            clear_solution_cache();
+            generate(black_box(&config))
+        })
     });
 }
 
+/// Benchmark palette generation for configs that exercise other paths:
+/// a light variant, Display P3 accents, hue spacing with comment contrast,
+/// and derived extended accents.
+fn bench_generation_configs(c: &mut Criterion) {
+    let light = GenerateConfig {
+        background: Srgb::new(250, 248, 240),
+        foreground: Srgb::new(40, 40, 48),
+        ..GenerateConfig::default()
+    };
+    let display_p3 = GenerateConfig {
+        gamut: Gamut::DisplayP3,
+        ..GenerateConfig::default()
+    };
+    let spaced = GenerateConfig {
+        hue_spacing: Some(HueSpacing::default()),
+        comment_contrast: Some(CommentContrast {
+            min: 30.0,
+            max: 45.0,
+        }),
+        ..GenerateConfig::default()
+    };
+    let derived = GenerateConfig {
+        extended_accents: ExtendedAccents {
+            mode: ExtendedAccentMode::Darker,
+            amount: None,
+        },
+        ..GenerateConfig::default()
+    };
+
+    let mut group = c.benchmark_group("generation");
+    for (name, config) in [
+        ("light", light),
+        ("display_p3", display_p3),
+        ("hue_spacing", spaced),
+        ("derived_extended", derived),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                clear_solution_cache();
+                generate(black_box(&config))
+            })
+        });
+    }
+    group.finish();
+}
+
 /// Benchmark HellwigJmh forward conversion (sRGB -> JMh) for 256 colors.
 fn bench_hellwig_from_srgb(c: &mut Criterion) {
     // Generate 256 test colors spanning the color space
@@ -71,6 +128,40 @@ fn bench_hellwig_into_srgb(c: &mut Criterion) {
     });
 }
 
+/// Benchmark batched HellwigJmh conversions in both directions for 256 colors.
+fn bench_hellwig_batch(c: &mut Criterion) {
+    let colors: Vec<Srgb<f32>> = (0u8..=255)
+        .map(|i: u8| Srgb::new(i, i.wrapping_mul(97), i.wrapping_mul(193)).into_format())
+        .collect();
+    let mut jmh_colors = vec![HellwigJmh::new(0.0, 0.0, 0.0); colors.len()];
+    batch_from_srgb(&colors, &mut jmh_colors);
+
+    c.bench_function("hellwig_batch_from_srgb_256", |b| {
+        let mut out = vec![HellwigJmh::new(0.0, 0.0, 0.0); colors.len()];
+        b.iter(|| batch_from_srgb(black_box(&colors), &mut out))
+    });
+
+    c.bench_function("hellwig_batch_into_srgb_256", |b| {
+        let mut out = vec![Srgb::new(0.0, 0.0, 0.0); jmh_colors.len()];
+        b.iter(|| batch_into_srgb(black_box(&jmh_colors), &mut out))
+    });
+}
+
+/// Benchmark a HellwigJmh round trip (sRGB -> JMh -> sRGB) for 256 colors.
+fn bench_hellwig_round_trip(c: &mut Criterion) {
+    let colors: Vec<Srgb<u8>> = (0u8..=255)
+        .map(|i: u8| Srgb::new(i, i.wrapping_mul(97), i.wrapping_mul(193)))
+        .collect();
+
+    c.bench_function("hellwig_round_trip_256", |b| {
+        b.iter(|| {
+            for color in &colors {
+                black_box(HellwigJmh::from_srgb_u8(black_box(*color)).into_srgb_u8());
+            }
+        })
+    });
+}
+
 /// Benchmark APCA contrast calculation for 256 foreground colors against a fixed background.
 fn bench_apca_contrast(c: &mut Criterion) {
     let background = Srgb::new(26u8, 26, 46); // Default dark background
@@ -141,6 +232,40 @@ fn bench_max_colorfulness_at(c: &mut Criterion) {
     });
 }
 
+/// Benchmark gamut boundary searches with a cold cache, so every lookup
+/// runs the bisection.
+fn bench_max_colorfulness_uncached(c: &mut Criterion) {
+    let test_params: Vec<(f32, f32)> = (0u8..=255)
+        .map(|i: u8| {
+            let j = 10.0 + (i as f32 / 255.0) * 80.0;
+            let h = (i.wrapping_mul(193) as f32 / 255.0) * 360.0;
+            (j, h)
+        })
+        .collect();
+
+    for (name, gamut) in [
+        ("max_colorfulness_uncached_srgb_256", Gamut::Srgb),
+        ("max_colorfulness_uncached_p3_256", Gamut::DisplayP3),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                clear_gamut_cache();
+                for &(j, hue) in &test_params {
+                    black_box(max_colorfulness_in(j, hue, gamut));
+                }
+            })
+        });
+    }
+
+    c.bench_function("max_colorfulness_many_uncached_p3_256", |b| {
+        let mut out = vec![0.0; test_params.len()];
+        b.iter(|| {
+            clear_gamut_cache();
+            max_colorfulness_many(black_box(&test_params), Gamut::DisplayP3, &mut out);
+        })
+    });
+}
+
 /// Benchmark interpolation with B-spline curve (8 steps).
 fn bench_interpolate_bspline_8(c: &mut Criterion) {
     let start = Srgb::new(0.1f32, 0.1, 0.12);
@@ -181,18 +306,51 @@ fn bench_accent_solver(c: &mut Criterion) {
     let min_contrast = 60.0;
 
     c.bench_function("accent_solver_8_hues", |b| {
-        b.iter(|| black_box(optimize_accents(background, &hues, &settings, min_contrast)))
+        b.iter(|| {
+            clear_solution_cache();
+            black_box(optimize_accents(background, &hues, &settings, min_contrast))
+        })
+    });
+
+    let light_background = Srgb::new(250u8, 248, 240);
+    c.bench_function("accent_solver_8_hues_light", |b| {
+        b.iter(|| {
+            clear_solution_cache();
+            black_box(optimize_accents(
+                light_background,
+                &hues,
+                &settings,
+                min_contrast,
+            ))
+        })
+    });
+
+    c.bench_function("accent_solver_8_hues_p3", |b| {
+        b.iter(|| {
+            clear_solution_cache();
+            black_box(optimize_accents_in(
+                background,
+                &hues,
+                &settings,
+                min_contrast,
+                Gamut::DisplayP3,
+            ))
+        })
     });
 }
 
 criterion_group!(
     benches,
     bench_palette_generation,
+    bench_generation_configs,
     bench_hellwig_from_srgb,
     bench_hellwig_into_srgb,
+    bench_hellwig_batch,
+    bench_hellwig_round_trip,
     bench_apca_contrast,
     bench_gamut_map,
     bench_max_colorfulness_at,
+    bench_max_colorfulness_uncached,
     bench_accent_solver,
     bench_interpolate_bspline_8,
 );
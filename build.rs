@@ -6,6 +6,13 @@
 //! - ECCENTRICITY_CAM16_LUT: CAM16 eccentricity
 //! - HK_HUE_LUT: Helmholtz-Kohlrausch hue dependency
 //! - CUSP_LUT: Gamut boundary cusps (J', M) per hue
+//! - BAKED_CUSP_LUTS: cusps for the extra viewing conditions listed in
+//!   THEMALINGADINGDONG_LUT_CONDITIONS, e.g. `dark,4,d65;dim,20,d50`
+//!   (surround, adapting luminance in cd/m², white point per set)
+//!
+//! Cusp tables are the slow part. Each is computed with its hues spread over
+//! all cores and cached in src/generated/cache/ under a hash of its
+//! parameters, so rebuilds and other profiles reuse it.
 //!
 //! Also generates man page via clap_mangen.
 
@@ -14,10 +21,16 @@ use std::fs;
 use std::io::Write;
 use std::path::Path;
 
-use palette::cam16::{BakedParameters, Cam16Jmh, Parameters, StaticWp};
-use palette::convert::IntoColorUnclamped;
-use palette::white_point::D65;
-use palette::{Srgb, Xyz};
+use palette::cam16::{BakedParameters, Cam16Jmh, Parameters, StaticWp, Surround as Cam16Surround};
+use palette::convert::{FromColorUnclamped, IntoColorUnclamped};
+use palette::white_point::{A, Any, D50, D55, D65, D75, F2, F11, WhitePoint};
+use palette::{LinSrgb, Srgb, Xyz};
+
+/// Environment variable listing extra viewing conditions to bake cusps for.
+const LUT_CONDITIONS_VAR: &str = "THEMALINGADINGDONG_LUT_CONDITIONS";
+
+/// Bumped when the cusp search changes, so cached tables are recomputed.
+const CUSP_ALGORITHM_VERSION: u32 = 1;
 
 #[path = "src/cli_args.rs"]
 mod cli_args;
@@ -25,6 +38,7 @@ mod cli_args;
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=src/cli_args.rs");
+    println!("cargo:rerun-if-env-changed={LUT_CONDITIONS_VAR}");
 
     let out_dir = Path::new("src/generated");
     fs::create_dir_all(out_dir).expect("Failed to create src/generated directory");
@@ -33,7 +47,7 @@ fn main() {
     generate_eccentricity_lut(out_dir);
     generate_eccentricity_cam16_lut(out_dir);
     generate_hk_hue_lut(out_dir);
-    generate_cusp_lut(out_dir);
+    generate_cusp_luts(out_dir);
     generate_mod_rs(out_dir);
     generate_man_page();
 }
@@ -174,6 +188,34 @@ fn generate_mod_rs(out_dir: &Path) {
     writeln!(file, "include!(\"eccentricity_cam16_lut.rs\");").unwrap();
     writeln!(file, "include!(\"hk_hue_lut.rs\");").unwrap();
     writeln!(file, "include!(\"cusp_lut.rs\");").unwrap();
+    writeln!(file).unwrap();
+    writeln!(
+        file,
+        "/// Cusp table baked at build time for one set of viewing conditions."
+    )
+    .unwrap();
+    writeln!(file, "pub struct BakedCuspLut {{").unwrap();
+    writeln!(file, "    /// \"dark\", \"dim\", or \"average\"").unwrap();
+    writeln!(file, "    pub surround: &'static str,").unwrap();
+    writeln!(file, "    /// Adapting luminance in cd/m²").unwrap();
+    writeln!(file, "    pub adapting_luminance: f32,").unwrap();
+    writeln!(file, "    /// White point, as named in `[viewing]`").unwrap();
+    writeln!(file, "    pub white_point: &'static str,").unwrap();
+    writeln!(
+        file,
+        "    /// Hash of the parameters the table was computed for"
+    )
+    .unwrap();
+    writeln!(file, "    pub hash: &'static str,").unwrap();
+    writeln!(
+        file,
+        "    /// (J'_cusp, M_cusp) for hue angles 0..360 degrees"
+    )
+    .unwrap();
+    writeln!(file, "    pub cusps: [(f32, f32); 360],").unwrap();
+    writeln!(file, "}}").unwrap();
+    writeln!(file).unwrap();
+    writeln!(file, "include!(\"baked_cusp_luts.rs\");").unwrap();
 }
 
 // LUT computation functions (copied from hellwig.rs to avoid dependencies)
@@ -208,14 +250,140 @@ fn hue_angle_dependency(hue_rad: f64) -> f64 {
 
 // Cusp LUT generation
 
-/// Default viewing parameters for CAM16 (same as runtime)
-fn default_params() -> BakedParameters<StaticWp<D65>, f64> {
-    let adapting_luminance = 64.0 / PI * 0.2;
-    Parameters::default_static_wp(adapting_luminance).bake()
+/// Viewing conditions a cusp table is computed for.
+struct LutConditions {
+    /// "dark", "dim", or "average"
+    surround: &'static str,
+    /// Adapting luminance in cd/m²
+    adapting_luminance: f64,
+    /// White point name, as in `[viewing]`
+    white_point: &'static str,
+}
+
+impl LutConditions {
+    /// The sRGB reference conditions CUSP_LUT is made for.
+    fn reference() -> Self {
+        Self {
+            surround: "average",
+            adapting_luminance: 64.0 / PI * 0.2,
+            white_point: "d65",
+        }
+    }
+
+    fn is_reference(&self) -> bool {
+        self.surround == "average"
+            && self.white_point == "d65"
+            && (self.adapting_luminance - 64.0 / PI * 0.2).abs() < 1e-6
+    }
+
+    /// FNV-1a hash of the parameters and the search version, as 16 hex digits.
+    fn hash(&self) -> String {
+        let key = format!(
+            "v{CUSP_ALGORITHM_VERSION} {} {:.6} {}",
+            self.surround, self.adapting_luminance, self.white_point
+        );
+        let hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+        format!("{hash:016x}")
+    }
+}
+
+/// Parse LUT_CONDITIONS_VAR: `surround,adapting_luminance,white_point` sets
+/// separated by `;`.
+fn parse_lut_conditions(spec: &str) -> Vec<LutConditions> {
+    spec.split(';')
+        .map(str::trim)
+        .filter(|set| !set.is_empty())
+        .map(|set| {
+            let fields: Vec<&str> = set.split(',').map(str::trim).collect();
+            let [surround, adapting_luminance, white_point] = fields[..] else {
+                panic!("{LUT_CONDITIONS_VAR}: expected surround,adapting_luminance,white_point (got '{set}')");
+            };
+            let surround = ["dark", "dim", "average"]
+                .into_iter()
+                .find(|s| s.eq_ignore_ascii_case(surround))
+                .unwrap_or_else(|| panic!("{LUT_CONDITIONS_VAR}: unknown surround '{surround}'"));
+            let adapting_luminance: f64 = adapting_luminance
+                .parse()
+                .ok()
+                .filter(|l| *l > 0.0 && *l <= 10_000.0)
+                .unwrap_or_else(|| {
+                    panic!("{LUT_CONDITIONS_VAR}: adapting luminance must be in (0, 10000] (got '{adapting_luminance}')")
+                });
+            let white_point = ["d65", "d50", "d55", "d75", "a", "f2", "f11"]
+                .into_iter()
+                .find(|w| w.eq_ignore_ascii_case(white_point))
+                .unwrap_or_else(|| panic!("{LUT_CONDITIONS_VAR}: unknown white point '{white_point}'"));
+            LutConditions {
+                surround,
+                adapting_luminance,
+                white_point,
+            }
+        })
+        .collect()
+}
+
+/// CAM16 parameters for one set of conditions. The reference conditions
+/// keep the static D65 parameters CUSP_LUT has always been made with.
+enum CuspModel {
+    Reference(BakedParameters<StaticWp<D65>, f64>),
+    Custom(BakedParameters<Xyz<Any, f64>, f64>),
+}
+
+impl CuspModel {
+    fn new(conditions: &LutConditions) -> Self {
+        if conditions.is_reference() {
+            return Self::Reference(
+                Parameters::default_static_wp(conditions.adapting_luminance).bake(),
+            );
+        }
+        // The white at the brightest level sRGB can show it (as the runtime
+        // scales it)
+        let white: Xyz<Any, f64> = match conditions.white_point {
+            "d50" => D50::get_xyz(),
+            "d55" => D55::get_xyz(),
+            "d75" => D75::get_xyz(),
+            "a" => A::get_xyz(),
+            "f2" => F2::get_xyz(),
+            "f11" => F11::get_xyz(),
+            _ => D65::get_xyz(),
+        };
+        let rgb = LinSrgb::from_color_unclamped(Xyz::<D65, f64>::new(white.x, white.y, white.z));
+        let white = white / rgb.red.max(rgb.green).max(rgb.blue);
+
+        let mut params = Parameters::default_dynamic_wp(white, conditions.adapting_luminance);
+        params.surround = match conditions.surround {
+            "dark" => Cam16Surround::Dark,
+            "dim" => Cam16Surround::Dim,
+            _ => Cam16Surround::Average,
+        };
+        Self::Custom(params.bake())
+    }
+
+    fn to_xyz(&self, cam16: Cam16Jmh<f64>) -> Xyz<D65, f64> {
+        match self {
+            Self::Reference(params) => cam16.into_xyz(*params),
+            Self::Custom(params) => cam16.into_xyz(*params).with_white_point(),
+        }
+    }
+
+    /// Upper end of the colorfulness search (matching the runtime's)
+    fn max_m(&self) -> f64 {
+        match self {
+            Self::Reference(_) => 120.0,
+            Self::Custom(_) => 150.0,
+        }
+    }
 }
 
 /// Convert HellwigJmh to sRGB (f64 precision for build-time accuracy)
-fn hellwig_to_srgb(lightness: f64, colorfulness: f64, hue_deg: f64) -> (f64, f64, f64) {
+fn hellwig_to_srgb(
+    model: &CuspModel,
+    lightness: f64,
+    colorfulness: f64,
+    hue_deg: f64,
+) -> (f64, f64, f64) {
     let hue_rad = hue_deg * PI / 180.0;
 
     // Reverse HK effect
@@ -229,27 +397,26 @@ fn hellwig_to_srgb(lightness: f64, colorfulness: f64, hue_deg: f64) -> (f64, f64
 
     // Convert via CAM16 -> XYZ -> sRGB (unclamped to detect out-of-gamut)
     let cam16 = Cam16Jmh::new(lightness_base, colorfulness_cam16, hue_deg);
-    let params = default_params();
-    let xyz: Xyz<D65, f64> = cam16.into_xyz(params);
+    let xyz = model.to_xyz(cam16);
     let srgb: Srgb<f64> = Srgb::from_linear(xyz.into_color_unclamped());
 
     (srgb.red, srgb.green, srgb.blue)
 }
 
 /// Check if HellwigJmh color is within sRGB gamut
-fn is_in_gamut(lightness: f64, colorfulness: f64, hue_deg: f64) -> bool {
-    let (r, g, b) = hellwig_to_srgb(lightness, colorfulness, hue_deg);
+fn is_in_gamut(model: &CuspModel, lightness: f64, colorfulness: f64, hue_deg: f64) -> bool {
+    let (r, g, b) = hellwig_to_srgb(model, lightness, colorfulness, hue_deg);
     (0.0..=1.0).contains(&r) && (0.0..=1.0).contains(&g) && (0.0..=1.0).contains(&b)
 }
 
 /// Find maximum in-gamut colorfulness at given (J', h) using binary search
-fn find_max_m_at_jh(j: f64, hue_deg: f64) -> f64 {
+fn find_max_m_at_jh(model: &CuspModel, j: f64, hue_deg: f64) -> f64 {
     let mut lo = 0.0;
-    let mut hi = 120.0;
+    let mut hi = model.max_m();
 
     while hi - lo > 0.01 {
         let mid = (lo + hi) / 2.0;
-        if is_in_gamut(j, mid, hue_deg) {
+        if is_in_gamut(model, j, mid, hue_deg) {
             lo = mid;
         } else {
             hi = mid;
@@ -259,14 +426,14 @@ fn find_max_m_at_jh(j: f64, hue_deg: f64) -> f64 {
 }
 
 /// Compute cusp (J', M) for a given hue
-fn compute_cusp(hue_deg: f64) -> (f64, f64) {
+fn compute_cusp(model: &CuspModel, hue_deg: f64) -> (f64, f64) {
     let mut best_j = 50.0;
     let mut best_m = 0.0;
 
     // Coarse search: J' from 10 to 95 in steps of 1
     for j_int in 10..=95 {
         let j = j_int as f64;
-        let m_max = find_max_m_at_jh(j, hue_deg);
+        let m_max = find_max_m_at_jh(model, j, hue_deg);
         if m_max > best_m {
             best_m = m_max;
             best_j = j;
@@ -280,7 +447,7 @@ fn compute_cusp(hue_deg: f64) -> (f64, f64) {
     let steps = 40;
     for i in 0..=steps {
         let j = j_lo + (j_hi - j_lo) * (i as f64 / steps as f64);
-        let m_max = find_max_m_at_jh(j, hue_deg);
+        let m_max = find_max_m_at_jh(model, j, hue_deg);
         if m_max > best_m {
             best_m = m_max;
             best_j = j;
@@ -290,8 +457,54 @@ fn compute_cusp(hue_deg: f64) -> (f64, f64) {
     (best_j, best_m)
 }
 
-/// Generate CUSP_LUT: 360-entry [(f32, f32); 360] for gamut cusp points
-fn generate_cusp_lut(out_dir: &Path) {
+/// Cusp table rows ("J' M", formatted as written to the LUT) for
+/// `conditions`, from the cache when an earlier build made them.
+fn cusp_rows(cache_dir: &Path, conditions: &LutConditions) -> Vec<String> {
+    let cache = cache_dir.join(format!("cusps-{}.txt", conditions.hash()));
+    if let Ok(cached) = fs::read_to_string(&cache) {
+        let rows: Vec<String> = cached.lines().map(str::to_string).collect();
+        if rows.len() == 360 {
+            return rows;
+        }
+    }
+
+    // Spread the hues over the available cores
+    let model = CuspModel::new(conditions);
+    let threads = std::thread::available_parallelism().map_or(4, |n| n.get());
+    let hues: Vec<u32> = (0..360).collect();
+    let rows: Vec<String> = std::thread::scope(|scope| {
+        let workers: Vec<_> = hues
+            .chunks(360_usize.div_ceil(threads))
+            .map(|chunk| {
+                let model = &model;
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|hue| {
+                            let (j, m) = compute_cusp(model, f64::from(*hue));
+                            format!("{:.6}, {:.6}", j as f32, m as f32)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("cusp worker panicked"))
+            .collect()
+    });
+
+    fs::create_dir_all(cache_dir).expect("Failed to create LUT cache directory");
+    fs::write(&cache, rows.join("\n")).expect("Failed to write cached cusp table");
+    rows
+}
+
+/// Generate CUSP_LUT (360-entry [(f32, f32); 360] gamut cusps for the
+/// reference conditions) and BAKED_CUSP_LUTS for the extra conditions.
+fn generate_cusp_luts(out_dir: &Path) {
+    let cache_dir = out_dir.join("cache");
+    let reference = LutConditions::reference();
+
     let path = out_dir.join("cusp_lut.rs");
     let mut file = fs::File::create(&path).expect("Failed to create cusp_lut.rs");
 
@@ -306,12 +519,55 @@ fn generate_cusp_lut(out_dir: &Path) {
     )
     .unwrap();
     writeln!(file, "pub static CUSP_LUT: [(f32, f32); 360] = [").unwrap();
-
-    for hue_deg in 0..360 {
-        let (j_cusp, m_cusp) = compute_cusp(hue_deg as f64);
-        writeln!(file, "    ({:.6}, {:.6}),", j_cusp as f32, m_cusp as f32).unwrap();
+    for row in cusp_rows(&cache_dir, &reference) {
+        writeln!(file, "    ({row}),").unwrap();
     }
+    writeln!(file, "];").unwrap();
+    writeln!(file).unwrap();
+    writeln!(file, "/// Parameter hash of CUSP_LUT").unwrap();
+    writeln!(
+        file,
+        "pub static CUSP_LUT_HASH: &str = \"{}\";",
+        reference.hash()
+    )
+    .unwrap();
 
+    let spec = std::env::var(LUT_CONDITIONS_VAR).unwrap_or_default();
+    let path = out_dir.join("baked_cusp_luts.rs");
+    let mut file = fs::File::create(&path).expect("Failed to create baked_cusp_luts.rs");
+
+    writeln!(file, "// Cusp LUTs for extra viewing conditions.").unwrap();
+    writeln!(file, "//").unwrap();
+    writeln!(file, "// Generated by build.rs - do not edit manually.").unwrap();
+    writeln!(file).unwrap();
+    writeln!(file, "#[allow(clippy::excessive_precision)]").unwrap();
+    writeln!(
+        file,
+        "/// Cusp tables for the conditions in {LUT_CONDITIONS_VAR} at build time"
+    )
+    .unwrap();
+    writeln!(file, "pub static BAKED_CUSP_LUTS: &[BakedCuspLut] = &[").unwrap();
+    for conditions in parse_lut_conditions(&spec) {
+        if conditions.is_reference() {
+            continue;
+        }
+        writeln!(file, "    BakedCuspLut {{").unwrap();
+        writeln!(file, "        surround: {:?},", conditions.surround).unwrap();
+        writeln!(
+            file,
+            "        adapting_luminance: {:?},",
+            conditions.adapting_luminance as f32
+        )
+        .unwrap();
+        writeln!(file, "        white_point: {:?},", conditions.white_point).unwrap();
+        writeln!(file, "        hash: {:?},", conditions.hash()).unwrap();
+        writeln!(file, "        cusps: [").unwrap();
+        for row in cusp_rows(&cache_dir, &conditions) {
+            writeln!(file, "            ({row}),").unwrap();
+        }
+        writeln!(file, "        ],").unwrap();
+        writeln!(file, "    }},").unwrap();
+    }
     writeln!(file, "];").unwrap();
 }
 
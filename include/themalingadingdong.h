@@ -0,0 +1,118 @@
+#ifndef THEMALINGADINGDONG_H
+#define THEMALINGADINGDONG_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Number of palette slots (base00-base17).
+#define TMD_SLOTS 24
+
+// Outcome of an FFI call.
+typedef enum TmdStatus {
+  TMD_STATUS_OK = 0,
+  // A required pointer argument was null
+  TMD_STATUS_NULL_ARGUMENT = 1,
+  // A string argument was not valid UTF-8
+  TMD_STATUS_INVALID_UTF8 = 2,
+  // Colors or configuration could not be parsed or were out of range
+  TMD_STATUS_INVALID_CONFIG = 3,
+  // The generator panicked; the library is still usable
+  TMD_STATUS_PANIC = 4,
+} TmdStatus;
+
+// A generated palette.
+typedef struct TmdPalette {
+  // base00-base17 as NUL-terminated lowercase hex, without `#`
+  char colors[TMD_SLOTS][7];
+  // Whether the scheme is a light variant
+  bool is_light;
+  // Number of generation warnings (unmet contrast targets and the like)
+  uint32_t warning_count;
+} TmdPalette;
+
+// One contrast check of a foreground slot on a background slot.
+typedef struct TmdCheck {
+  // Foreground slot index (0-23, base00-base17)
+  uint8_t foreground;
+  // Background slot index (0-23)
+  uint8_t background;
+  // APCA contrast (Lc, signed)
+  double contrast;
+  // Minimum |Lc| the check requires
+  double min_contrast;
+  // WCAG 2.x contrast ratio (1-21)
+  double wcag_ratio;
+  bool passes;
+  // Whether the check must pass for the scheme to be valid (the others
+  // are informational)
+  bool required;
+} TmdCheck;
+
+// Validation results; free with `tmd_validation_free`.
+typedef struct TmdValidation {
+  // Required checks first, then informational ones
+  struct TmdCheck *checks;
+  size_t len;
+  // Number of required checks
+  size_t required;
+  // Number of required checks that pass
+  size_t required_passed;
+} TmdValidation;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Generate a palette from background and foreground colors (any CSS
+// format) with the default settings.
+//
+// # Safety
+//
+// `background` and `foreground` must be NUL-terminated strings and `out`
+// must point to a writable `TmdPalette`.
+enum TmdStatus tmd_generate(const char *background,
+                            const char *foreground,
+                            struct TmdPalette *out);
+
+// Generate a palette from a configuration in the config file's TOML format.
+//
+// # Safety
+//
+// `config_toml` must be a NUL-terminated string and `out` must point to a
+// writable `TmdPalette`.
+enum TmdStatus tmd_generate_config(const char *config_toml, struct TmdPalette *out);
+
+// Check a palette's contrast with the default thresholds (APCA).
+//
+// On success `out` owns an array that must be released with
+// `tmd_validation_free`.
+//
+// # Safety
+//
+// `palette` must point to a `TmdPalette` whose colors are NUL-terminated,
+// and `out` to a writable `TmdValidation`.
+enum TmdStatus tmd_validate(const struct TmdPalette *palette, struct TmdValidation *out);
+
+// Release the checks of a `TmdValidation` filled by `tmd_validate`.
+// Freeing an empty or already freed result does nothing.
+//
+// # Safety
+//
+// `validation` must be null or point to a `TmdValidation` filled by
+// `tmd_validate` and not modified since.
+void tmd_validation_free(struct TmdValidation *validation);
+
+// Message for the last failed call on this thread, or null if none failed.
+// Valid until the next failing call on the same thread.
+const char *tmd_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* THEMALINGADINGDONG_H */
@@ -0,0 +1,203 @@
+//! Accent order matched to a reference scheme (`--match-order`).
+//!
+//! The solver gives each accent slot the hue it starts with, so base08 is
+//! red and base0D blue whatever a template expects. Given a reference
+//! scheme (gruvbox, say), the accents are solved as usual and then
+//! reassigned to base08-base0F in the permutation with the smallest total
+//! ΔE to the reference, slot by slot, so the result reads like the
+//! reference while every accent keeps the contrast it was solved for.
+//!
+//! The order is applied as hue overrides, so a saved config or embedded
+//! provenance reproduces it. Pinned, anchored, and hue-linked accents keep
+//! their slots.
+
+use std::fmt;
+
+use palette::Srgb;
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+use crate::config::{ConfigError, HueOverrides, ThemeConfig};
+use crate::contrast_matrix::slot_name;
+use crate::generate::generate_for_variant;
+use crate::hellwig::HellwigJmh;
+
+/// Accent slots base08-base0F.
+const ACCENTS: usize = 8;
+
+/// A permutation of base08-base0F.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccentOrder {
+    /// For each slot, the accent (by its original index, 0 for base08) it
+    /// takes
+    pub order: [usize; ACCENTS],
+    /// Total ΔE to the reference in this order
+    pub delta_e: f32,
+    /// Total ΔE to the reference in the original order
+    pub original_delta_e: f32,
+}
+
+impl AccentOrder {
+    /// Whether every accent stays in its slot.
+    pub fn is_identity(&self) -> bool {
+        self.order.iter().enumerate().all(|(slot, &i)| slot == i)
+    }
+
+    /// `values` (one per accent, base08 first) in this order.
+    pub fn apply<T: Copy>(&self, values: [T; ACCENTS]) -> [T; ACCENTS] {
+        self.order.map(|i| values[i])
+    }
+}
+
+impl fmt::Display for AccentOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let moves: Vec<String> = self
+            .order
+            .iter()
+            .enumerate()
+            .filter(|(slot, i)| slot != *i)
+            .map(|(slot, &i)| format!("{} <- {}", slot_name(8 + slot), slot_name(8 + i)))
+            .collect();
+        if moves.is_empty() {
+            write!(f, "original order")?;
+        } else {
+            write!(f, "{}", moves.join(", "))?;
+        }
+        write!(
+            f,
+            " (total delta E {:.1}, was {:.1})",
+            self.delta_e, self.original_delta_e
+        )
+    }
+}
+
+/// The order minimizing the total of `cost[slot][accent]`, with `fixed`
+/// accents kept in their own slots.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::accent_order::best_order;
+///
+/// // Accents 0 and 1 each fit the other's slot better
+/// let mut cost = [[10.0; 8]; 8];
+/// for i in 0..8 {
+///     cost[i][i] = 1.0;
+/// }
+/// cost[0][1] = 0.0;
+/// cost[1][0] = 0.0;
+/// let order = best_order(&cost, &[false; 8]);
+/// assert_eq!(order.order, [1, 0, 2, 3, 4, 5, 6, 7]);
+/// assert_eq!((order.delta_e, order.original_delta_e), (6.0, 8.0));
+///
+/// // Fixing accent 0 keeps both where they are
+/// let fixed = [true, false, false, false, false, false, false, false];
+/// assert!(best_order(&cost, &fixed).is_identity());
+/// ```
+pub fn best_order(cost: &[[f32; ACCENTS]; ACCENTS], fixed: &[bool; ACCENTS]) -> AccentOrder {
+    // best[mask]: least cost filling the first popcount(mask) slots with
+    // the accents in mask, and the accent the last of those slots took
+    let masks = 1usize << ACCENTS;
+    let mut best: Vec<(f32, usize)> = vec![(f32::INFINITY, 0); masks];
+    best[0] = (0.0, 0);
+    for mask in 0..masks {
+        let (so_far, _) = best[mask];
+        if !so_far.is_finite() {
+            continue;
+        }
+        let slot = mask.count_ones() as usize;
+        if slot == ACCENTS {
+            continue;
+        }
+        for accent in 0..ACCENTS {
+            let allowed = if fixed[slot] {
+                accent == slot
+            } else {
+                !fixed[accent]
+            };
+            let next = mask | (1 << accent);
+            if !allowed || next == mask {
+                continue;
+            }
+            let total = so_far + cost[slot][accent];
+            if total < best[next].0 {
+                best[next] = (total, accent);
+            }
+        }
+    }
+
+    let mut order = [0; ACCENTS];
+    let mut mask = masks - 1;
+    for slot in (0..ACCENTS).rev() {
+        let accent = best[mask].1;
+        order[slot] = accent;
+        mask &= !(1 << accent);
+    }
+    AccentOrder {
+        order,
+        delta_e: best[masks - 1].0,
+        original_delta_e: (0..ACCENTS).map(|i| cost[i][i]).sum(),
+    }
+}
+
+/// base08-base0F of `scheme` in HellwigJmh.
+fn accents(scheme: &Base16Scheme) -> Result<[HellwigJmh; ACCENTS], ConfigError> {
+    let mut jmh = [HellwigJmh::new(0.0, 0.0, 0.0); ACCENTS];
+    for (i, accent) in jmh.iter_mut().enumerate() {
+        let slot = slot_name(8 + i);
+        let color = scheme.palette.get(slot).ok_or_else(|| {
+            ConfigError::InvalidValue(format!("reference scheme '{}' has no {slot}", scheme.name))
+        })?;
+        *accent = HellwigJmh::from_srgb_u8(Srgb::new(color.rgb.0, color.rgb.1, color.rgb.2));
+    }
+    Ok(jmh)
+}
+
+/// Reorder `config`'s accents to match `reference`, and return the order.
+///
+/// Each of `variants` is generated, and one order is chosen for all of
+/// them by their summed ΔE. The hues the first variant's accents were
+/// solved at are then written to `colors.hue_overrides` in that order.
+pub fn match_accent_order(
+    config: &mut ThemeConfig,
+    reference: &Base16Scheme,
+    variants: &[Option<SchemeVariant>],
+) -> Result<AccentOrder, ConfigError> {
+    let target = accents(reference)?;
+    let generate_config = config.to_generate_config()?;
+    let links = config.hue_links()?;
+
+    let mut cost = [[0.0; ACCENTS]; ACCENTS];
+    let mut hues = None;
+    for variant in variants {
+        let result = generate_for_variant(&generate_config, variant.clone());
+        let generated = accents(&result.scheme)?;
+        for (slot, row) in cost.iter_mut().enumerate() {
+            for (accent, cell) in row.iter_mut().enumerate() {
+                *cell += generated[accent].delta_e(&target[slot]);
+            }
+        }
+        hues.get_or_insert_with(|| {
+            std::array::from_fn(|i| {
+                result
+                    .base_accent_results
+                    .get(i)
+                    .map_or(generated[i].hue, |r| r.hue)
+            })
+        });
+    }
+    let Some(hues) = hues else {
+        return Ok(best_order(&cost, &[true; ACCENTS]));
+    };
+
+    let fixed: [bool; ACCENTS] = std::array::from_fn(|i| {
+        generate_config.pins.contains_key(slot_name(8 + i))
+            || generate_config.accent_opt.anchor(i).is_some()
+            || links.links[i].is_some()
+            || links.links.iter().flatten().any(|link| link.source == i)
+    });
+    let order = best_order(&cost, &fixed);
+    if !order.is_identity() {
+        config.colors.hue_overrides = Some(HueOverrides::from_array(order.apply(hues).map(Some)));
+    }
+    Ok(order)
+}
@@ -1,21 +1,101 @@
-//! COBYLA-based accent color optimization.
+//! Accent color optimization (COBYLA by default).
 //!
 //! Optimizes (J', M) pairs for accent colors using constrained optimization
 //! to balance lightness uniformity against colorfulness preservation.
+//!
+//! `AccentOptSettings::solver` swaps COBYLA for a Nelder-Mead simplex or a
+//! refined grid search, both run on the objective plus a penalty for
+//! violated constraints. A solver that fails falls back to the grid.
+//!
+//! Hues are solved independently unless `AccentOptSettings::joint` or
+//! `AccentOptSettings::cvd` is set; then each hue is re-solved in turn
+//! against the others, with a penalty for lightness away from the group's
+//! (joint) and one for pairs that collapse under simulated color vision
+//! deficiency (CVD), in that order. Semantic pairs
+//! (`AccentOptSettings::semantic`) are then re-solved against each other,
+//! with their J' box widened so they can split in lightness.
+//!
+//! An accent with an anchor (`AccentOptSettings::anchors`) takes its brand
+//! color's hue, and its J'/M box is re-centered on the brand color with a
+//! hard constraint keeping it within the anchor's ΔE tolerance.
+//!
+//! Contrast is APCA Lc unless another [`ContrastModel`] is passed to
+//! [`optimize_accents_with`]; `min_contrast` is then in that model's units.
+//!
+//! Independent per-hue solutions are memoized across calls, keyed by the
+//! background luminance, the hue, a hash of everything else the solve
+//! depends on, and the minimum contrast, with least-recently-used eviction.
+//! Editing one hue override in the TUI therefore re-solves only that hue;
+//! joint and CVD passes still run on every call, since they depend on all
+//! the accents.
 
-use std::time::Instant;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
 
-use argmin::core::{CostFunction, Error, Executor, State};
+use argmin::core::{CostFunction, Error};
+#[cfg(not(target_arch = "wasm32"))]
+use argmin::core::{Executor, State};
+#[cfg(not(target_arch = "wasm32"))]
 use cobyla::CobylaSolver;
 use palette::Srgb;
+#[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 use tracing::{debug, info, warn};
+// std's Instant panics on wasm32-unknown-unknown
+use web_time::Instant;
 
-use crate::apca::{contrast_from_luminances, srgb_f32_to_luminance, srgb_to_luminance};
-use crate::config::AccentOptSettings;
-use crate::gamut_map::{cusp_at_hue, gamut_map, max_colorfulness_at};
+use crate::apca::{extended_srgb_to_luminance, srgb_f32_to_luminance, srgb_to_luminance};
+use crate::config::{AccentOptSettings, JointOptimization, SolverMethod, SolverSettings};
+use crate::contrast::{Apca, ContrastModel};
+use crate::cvd::{CvdKind, CvdSeparation, simulate};
+use crate::gamut_map::{
+    Gamut, cusp_at_hue, gamut_map, gamut_map_to, max_colorfulness_in, max_colorfulness_many,
+};
 use crate::hellwig::HellwigJmh;
 use crate::interpolation::srgb_to_u8;
+use crate::semantic_pairs::{SemanticSeparation, Separation};
+use crate::viewing::{active_model, with_model};
+
+/// Lc headroom above the minimum contrast, at the worst point of the J'/M box,
+/// beyond which contrast is treated as saturated.
+///
+/// On near-black backgrounds APCA saturates and the whole box clears the
+/// target, so the contrast term is flat; the solver then maximizes uniformity
+/// subject to a hard contrast floor instead of the weighted blend.
+pub const SATURATION_HEADROOM: f64 = 15.0;
+
+/// Re-solve passes over all hues when CVD separation is enabled.
+const CVD_PASSES: usize = 2;
+
+/// Re-solve passes over the hues in semantic pairs.
+const SEMANTIC_PASSES: usize = 3;
+
+/// Weight of a semantic pair's squared shortfall, relative to the
+/// uniformity term; above 1 so telling the pair apart wins over evenness.
+const SEMANTIC_WEIGHT: f64 = 10.0;
+
+/// Separation score a semantic pair is pushed to beyond the passing 1, so
+/// 8-bit rounding does not undo it.
+const SEMANTIC_TARGET: f32 = 1.05;
+
+/// Weight of the shortfall below `preferred_contrast`, relative to the
+/// uniformity term it is added to; below 1 so uniformity keeps priority once
+/// the minimum is met.
+pub const PREFERRED_WEIGHT: f64 = 0.25;
+
+/// Most re-solve passes over all hues in joint optimization.
+const JOINT_PASSES: usize = 4;
+
+/// Largest J' change in a joint pass at which the hues count as settled.
+const JOINT_TOLERANCE: f32 = 0.01;
+
+/// ΔE past an anchor's tolerance still accepted, for 8-bit rounding.
+const ANCHOR_SLACK: f32 = 0.5;
+
+/// Independent per-hue solutions kept across calls.
+const SOLUTION_CACHE_CAPACITY: usize = 256;
 
 /// Result for a single hue optimization.
 #[derive(Debug, Clone)]
@@ -28,11 +108,13 @@ pub struct HueOptResult {
     pub m: f32,
     /// Original colorfulness before gamut mapping
     pub original_m: f32,
-    /// Final gamut-mapped sRGB color
+    /// Final color in the target gamut
+    pub jmh: HellwigJmh,
+    /// Final color mapped into sRGB (identical to `jmh` for an sRGB target)
     pub color: Srgb<f32>,
     /// Post-gamut-mapping lightness
     pub post_clamp_j: f32,
-    /// APCA contrast against background
+    /// Contrast against background (APCA Lc unless solved with another model)
     pub achieved_contrast: f64,
     /// Whether all constraints were satisfied
     pub met_constraints: bool,
@@ -46,6 +128,12 @@ pub struct HueOptResult {
     pub m_lower_bound: f32,
     /// The M upper bound for this optimization
     pub m_upper_bound: f32,
+    /// Whether contrast was saturated (uniformity optimized under a contrast floor)
+    pub saturated: bool,
+    /// Smallest simulated-CVD ΔE to another accent, when separation is enabled
+    pub cvd_delta_e: Option<f32>,
+    /// ΔE from the anchor's brand color, when the accent is anchored
+    pub anchor_delta_e: Option<f32>,
 }
 
 /// Result for all accent hues.
@@ -55,16 +143,79 @@ pub struct AccentOptResult {
     pub hue_results: Vec<HueOptResult>,
     /// Total optimization time in milliseconds
     pub elapsed_ms: u64,
+    /// Hues whose independent solution was reused from an earlier call
+    pub cached_hues: usize,
 }
 
-/// Cost function for COBYLA optimization of a single hue.
+/// What an independent solve of one hue depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SolutionKey {
+    bg_lum: u64,
+    hue: u32,
+    /// Hash of the settings, anchor, gamut, contrast model, and viewing model
+    settings: u64,
+    min_contrast: u64,
+}
+
+/// Independent per-hue solutions with least-recently-used eviction.
+#[derive(Default)]
+struct SolutionCache {
+    entries: HashMap<SolutionKey, (HueOptResult, u64)>,
+    /// Bumped on every access; entries remember when they were last used
+    clock: u64,
+}
+
+impl SolutionCache {
+    fn get(&mut self, key: &SolutionKey) -> Option<HueOptResult> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(key).map(|(result, used)| {
+            *used = clock;
+            result.clone()
+        })
+    }
+
+    fn insert(&mut self, key: SolutionKey, result: HueOptResult) {
+        if self.entries.len() >= SOLUTION_CACHE_CAPACITY
+            && !self.entries.contains_key(&key)
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| *key)
+        {
+            self.entries.remove(&oldest);
+        }
+        self.clock += 1;
+        self.entries.insert(key, (result, self.clock));
+    }
+}
+
+static SOLUTIONS: LazyLock<Mutex<SolutionCache>> = LazyLock::new(Mutex::default);
+
+/// Forget every memoized per-hue solution, so the next call solves from
+/// scratch (e.g. to benchmark the solver).
+pub fn clear_solution_cache() {
+    let mut cache = SOLUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+    *cache = SolutionCache::default();
+}
+
+/// Cost function for the optimization of a single hue.
 ///
 /// Minimizes weighted combination of contrast gap and uniformity while enforcing:
 /// - Box constraints on J' and M (hard constraints)
 /// - Gamut constraint: M <= max achievable at J, hue
-struct AccentProblem {
+///
+/// Contrast above the minimum costs nothing, unless a preferred contrast is
+/// set: then the shortfall below it is added to the uniformity term at
+/// `PREFERRED_WEIGHT`.
+///
+/// When `saturated`, minimizes uniformity alone with contrast as a hard floor.
+struct AccentProblem<'a> {
     /// Pre-computed background luminance (shared across all hues)
     bg_lum: f64,
+    /// How contrast is measured
+    model: &'a dyn ContrastModel,
     /// Fixed hue for this optimization
     hue: f32,
     /// Target lightness
@@ -81,17 +232,235 @@ struct AccentProblem {
     contrast_weight: f32,
     /// Minimum contrast requirement
     min_contrast: f64,
+    /// Contrast worth reaching beyond the minimum, when above it
+    preferred_contrast: Option<f64>,
+    /// Contrast headroom exceeds `SATURATION_HEADROOM` across the box
+    saturated: bool,
+    /// Gamut the accent must fit in
+    gamut: Gamut,
+    /// Terms coupling the accent to the others
+    penalties: Penalties,
+    /// Brand color the accent must stay near
+    anchor: Option<Anchor>,
+}
+
+/// What every hue is solved against.
+#[derive(Clone, Copy)]
+struct SolveContext<'a> {
+    /// Pre-computed background luminance
+    bg_lum: f64,
+    model: &'a dyn ContrastModel,
+    min_contrast: f64,
+    gamut: Gamut,
+}
+
+/// A brand color in HellwigJmh and how far an accent may move from it.
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    jmh: HellwigJmh,
+    tolerance: f32,
+}
+
+/// Settings and anchor for the `index`-th accent.
+///
+/// An anchored accent's J'/M box is re-centered on its brand color and
+/// sized to the tolerance, so the objective pulls it toward the brand color.
+fn hue_settings(
+    settings: &AccentOptSettings,
+    index: usize,
+) -> (Cow<'_, AccentOptSettings>, Option<Anchor>) {
+    let anchor = settings.anchor(index).and_then(|a| {
+        Some(Anchor {
+            jmh: a.jmh()?,
+            tolerance: a.tolerance,
+        })
+    });
+    match anchor {
+        Some(anchor) => (
+            Cow::Owned(AccentOptSettings {
+                target_j: anchor.jmh.lightness,
+                target_m: anchor.jmh.colorfulness,
+                delta_j: anchor.tolerance,
+                delta_m: anchor.tolerance,
+                ..settings.clone()
+            }),
+            Some(anchor),
+        ),
+        None => (Cow::Borrowed(settings), None),
+    }
+}
+
+/// Another accent as seen with normal vision and under each deficiency.
+struct Neighbor {
+    hue: f32,
+    normal: HellwigJmh,
+    simulated: [HellwigJmh; 3],
+}
+
+impl Neighbor {
+    fn new(color: Srgb<u8>, hue: f32) -> Self {
+        Self {
+            hue,
+            normal: HellwigJmh::from_srgb_u8(color),
+            simulated: CvdKind::ALL.map(|kind| HellwigJmh::from_srgb_u8(simulate(color, kind))),
+        }
+    }
+}
+
+/// A pair that falls short of the separation target.
+struct Shortfall {
+    kind: CvdKind,
+    other_hue: f32,
+    delta_e: f32,
+    target: f32,
+}
+
+/// Terms coupling a hue to the other accents, when re-solved against them.
+#[derive(Default)]
+struct Penalties {
+    /// Separation under simulated CVD
+    cvd: Option<CvdPenalty>,
+    /// Lightness uniformity across the group
+    group: Option<GroupPenalty>,
+    /// Separation from the hue's semantic partners
+    semantic: Option<SemanticPenalty>,
+}
+
+/// A hue's share of the group J' variance term, with the other accents
+/// fixed.
+///
+/// The joint objective adds `group_weight * n * Var(J') / delta_j²` to the
+/// accents' own objectives; as a function of one accent's J', that is
+/// `group_weight * (n - 1) / n * ((J' - mean of the others) / delta_j)²`
+/// plus terms the accent does not affect.
+#[derive(Debug, Clone, Copy)]
+struct GroupPenalty {
+    weight: f64,
+    others_mean: f64,
+}
+
+impl GroupPenalty {
+    fn new(group_weight: f32, others: &[f32]) -> Self {
+        let n = (others.len() + 1) as f64;
+        Self {
+            weight: f64::from(group_weight) * (n - 1.0) / n,
+            others_mean: others.iter().map(|&j| f64::from(j)).sum::<f64>() / (n - 1.0),
+        }
+    }
+
+    fn at(&self, j: f64, delta_j: f32) -> f64 {
+        self.weight * ((j - self.others_mean) / f64::from(delta_j)).powi(2)
+    }
 }
 
-impl AccentProblem {
+/// Penalty for simulated-CVD collisions with fixed neighboring accents.
+struct CvdPenalty {
+    settings: CvdSeparation,
+    neighbors: Vec<Neighbor>,
+}
+
+impl CvdPenalty {
+    /// Weighted squared shortfall below the separation target, summed over
+    /// neighbors and deficiencies.
+    fn at(&self, color: Srgb<u8>) -> f64 {
+        let min = self.settings.min_delta_e;
+        self.pairs(color)
+            .map(|(kind, _, delta_e, target)| {
+                let gap = ((target - delta_e) / min).max(0.0) as f64;
+                self.settings.weight(kind) as f64 * gap * gap
+            })
+            .sum()
+    }
+
+    /// Smallest simulated ΔE to a neighbor under a weighted deficiency.
+    fn closest(&self, color: Srgb<u8>) -> Option<f32> {
+        self.pairs(color)
+            .map(|(_, _, delta_e, _)| delta_e)
+            .min_by(f32::total_cmp)
+    }
+
+    /// The pair furthest below its target, if any is.
+    fn worst_shortfall(&self, color: Srgb<u8>) -> Option<Shortfall> {
+        self.pairs(color)
+            .filter(|(_, _, delta_e, target)| delta_e < target)
+            .max_by(|a, b| (a.3 - a.2).total_cmp(&(b.3 - b.2)))
+            .map(|(kind, other_hue, delta_e, target)| Shortfall {
+                kind,
+                other_hue,
+                delta_e,
+                target,
+            })
+    }
+
+    /// (kind, neighbor hue, simulated ΔE, target ΔE) for every neighbor and
+    /// weighted deficiency.
+    ///
+    /// The target is capped at the normal-vision ΔE, so pairs that are close
+    /// by design are not pushed apart.
+    fn pairs(&self, color: Srgb<u8>) -> impl Iterator<Item = (CvdKind, f32, f32, f32)> + '_ {
+        let normal = HellwigJmh::from_srgb_u8(color);
+        let simulated = CvdKind::ALL.map(|kind| HellwigJmh::from_srgb_u8(simulate(color, kind)));
+        self.neighbors.iter().flat_map(move |n| {
+            let target = self.settings.min_delta_e.min(normal.delta_e(&n.normal));
+            CvdKind::ALL
+                .into_iter()
+                .zip(simulated)
+                .zip(n.simulated)
+                .filter(|((kind, _), _)| self.settings.weight(*kind) > 0.0)
+                .map(move |((kind, a), b)| (kind, n.hue, a.delta_e(&b), target))
+        })
+    }
+}
+
+/// Penalty for a hue too close to its fixed semantic partners.
+struct SemanticPenalty {
+    min_hue: f32,
+    min_lightness: f32,
+    /// (hue, color) of each partner
+    partners: Vec<(f32, Srgb<u8>)>,
+}
+
+impl SemanticPenalty {
+    /// Weighted squared shortfall of the separation score below
+    /// `SEMANTIC_TARGET`, summed over partners and visions.
+    fn at(&self, color: Srgb<u8>) -> f64 {
+        self.scores(color)
+            .map(|(_, _, score)| {
+                let gap = f64::from((SEMANTIC_TARGET - score).max(0.0));
+                SEMANTIC_WEIGHT * gap * gap
+            })
+            .sum()
+    }
+
+    /// (partner hue, vision, separation score) for every partner and vision.
+    fn scores(&self, color: Srgb<u8>) -> impl Iterator<Item = (f32, Option<CvdKind>, f32)> + '_ {
+        self.partners.iter().flat_map(move |&(hue, partner)| {
+            std::iter::once(None)
+                .chain(CvdKind::ALL.map(Some))
+                .map(move |vision| {
+                    let score = Separation::between(color, partner, vision)
+                        .score(self.min_hue, self.min_lightness);
+                    (hue, vision, score)
+                })
+        })
+    }
+}
+
+/// The sRGB color shown for (J', M, h).
+fn display_color(j: f32, m: f32, hue: f32) -> Srgb<u8> {
+    srgb_to_u8(gamut_map(HellwigJmh::new(j, m, hue)).into_srgb())
+}
+
+impl AccentProblem<'_> {
     /// Compute contrast for given (J', M) after gamut mapping.
     #[inline]
     fn contrast_at(&self, j: f64, m: f64) -> f64 {
         let color = HellwigJmh::new(j as f32, m as f32, self.hue);
-        let mapped = gamut_map(color);
-        let srgb = mapped.into_srgb();
-        let fg_lum = srgb_f32_to_luminance(srgb);
-        contrast_from_luminances(fg_lum, self.bg_lum).abs()
+        let mapped = gamut_map_to(color, self.gamut);
+        let fg_lum = luminance_in(mapped, self.gamut);
+        self.model
+            .contrast_from_luminances(fg_lum, self.bg_lum)
+            .abs()
     }
 
     /// Compute weighted objective distance from targets.
@@ -101,9 +470,33 @@ impl AccentProblem {
         let m_term = ((m - self.target_m as f64) / self.delta_m as f64).powi(2);
         w * j_term + (1.0 - w) * m_term
     }
+
+    /// Objective plus `VIOLATION_PENALTY` per unit of constraint violation,
+    /// for the solvers without constraint handling.
+    fn merit(&self, p: [f64; 2]) -> Result<f64, Error> {
+        let output = self.cost(&p.to_vec())?;
+        let violation: f64 = output[1..].iter().map(|c| (-c).max(0.0)).sum();
+        Ok(output[0] + VIOLATION_PENALTY * violation)
+    }
+
+    /// Lowest contrast over a 3x3 grid spanning the J'/M box.
+    fn min_box_contrast(&self) -> f64 {
+        let j_lo = (self.target_j - self.delta_j).max(0.0) as f64;
+        let j_hi = (self.target_j + self.delta_j).min(100.0) as f64;
+        let m_lo = (self.target_m - self.delta_m).max(0.0) as f64;
+        let m_hi = (self.target_m + self.delta_m) as f64;
+
+        let mut min = f64::INFINITY;
+        for j in [j_lo, (j_lo + j_hi) / 2.0, j_hi] {
+            for m in [m_lo, (m_lo + m_hi) / 2.0, m_hi] {
+                min = min.min(self.contrast_at(j, m));
+            }
+        }
+        min
+    }
 }
 
-impl CostFunction for AccentProblem {
+impl CostFunction for AccentProblem<'_> {
     type Param = Vec<f64>;
     type Output = Vec<f64>;
 
@@ -120,9 +513,41 @@ impl CostFunction for AccentProblem {
             .max(0.0)
             .powi(2);
 
-        // OBJECTIVE: weighted combination (contrast_weight controls priority)
+        // Preference term: shortfall below the preferred contrast, 1 at the
+        // minimum and 0 at or above the preferred level
+        let preference = self
+            .preferred_contrast
+            .filter(|preferred| *preferred > self.min_contrast)
+            .map_or(0.0, |preferred| {
+                ((preferred - contrast) / (preferred - self.min_contrast))
+                    .clamp(0.0, 1.0)
+                    .powi(2)
+            });
+        let uniformity = uniformity + PREFERRED_WEIGHT * preference;
+
+        // OBJECTIVE: weighted combination (contrast_weight controls priority),
+        // or pure uniformity when contrast is saturated (floor enforced below)
         let cw = self.contrast_weight as f64;
-        let objective = cw * contrast_gap + (1.0 - cw) * uniformity;
+        let mut objective = if self.saturated {
+            uniformity
+        } else {
+            cw * contrast_gap + (1.0 - cw) * uniformity
+        };
+
+        // Lightness away from the rest of the group
+        if let Some(ref group) = self.penalties.group {
+            objective += group.at(j, self.delta_j);
+        }
+
+        // Separation from the other accents under simulated CVD
+        if let Some(ref cvd) = self.penalties.cvd {
+            objective += cvd.at(display_color(j as f32, m as f32, self.hue));
+        }
+
+        // Separation from semantic partners, with and without CVD
+        if let Some(ref semantic) = self.penalties.semantic {
+            objective += semantic.at(display_color(j as f32, m as f32, self.hue));
+        }
 
         // HARD CONSTRAINTS (COBYLA treats positive values as satisfied)
         // J box constraints
@@ -134,22 +559,52 @@ impl CostFunction for AccentProblem {
         let m_upper = (self.target_m + self.delta_m) as f64 - m;
 
         // Gamut constraint: M must be <= max achievable at this J'
-        let m_max = max_colorfulness_at(j as f32, self.hue) as f64;
+        let m_max = max_colorfulness_in(j as f32, self.hue, self.gamut) as f64;
         let gamut_constraint = m_max - m;
 
-        Ok(vec![
+        let mut output = vec![
             objective,
             j_lower,
             j_upper,
             m_lower,
             m_upper,
             gamut_constraint,
-        ])
+        ];
+        if self.saturated {
+            output.push(contrast - self.min_contrast);
+        }
+        if let Some(anchor) = self.anchor {
+            let mapped = gamut_map_to(HellwigJmh::new(j as f32, m as f32, self.hue), self.gamut);
+            output.push((anchor.tolerance - mapped.delta_e(&anchor.jmh)) as f64);
+        }
+        Ok(output)
+    }
+}
+
+// Lets COBYLA borrow the problem, which the grid fallback reuses
+impl CostFunction for &AccentProblem<'_> {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    fn cost(&self, params: &Self::Param) -> Result<Self::Output, Error> {
+        (*self).cost(params)
+    }
+}
+
+/// APCA luminance of a color that lies inside `gamut`.
+///
+/// sRGB colors use the LUT path; wide-gamut colors are measured from their
+/// extended-range sRGB coordinates so they are not clipped first.
+fn luminance_in(color: HellwigJmh, gamut: Gamut) -> f64 {
+    if gamut.is_srgb() {
+        srgb_f32_to_luminance(color.into_srgb())
+    } else {
+        extended_srgb_to_luminance(color.into_srgb_unclamped())
     }
 }
 
 /// Find feasible starting point for optimization using cusp data.
-fn initial_guess(hue: f32, settings: &AccentOptSettings) -> (f64, f64) {
+fn initial_guess(hue: f32, settings: &AccentOptSettings, gamut: Gamut) -> (f64, f64) {
     let cusp = cusp_at_hue(hue);
 
     // Start at target J' if feasible, otherwise use cusp J'
@@ -160,7 +615,7 @@ fn initial_guess(hue: f32, settings: &AccentOptSettings) -> (f64, f64) {
     };
 
     // Start at target M if in gamut, otherwise scale down (0.95 to stay close to boundary)
-    let m_max = max_colorfulness_at(j, hue);
+    let m_max = max_colorfulness_in(j, hue, gamut);
     let m = settings.target_m.min(m_max * 0.95);
 
     (j as f64, m as f64)
@@ -171,23 +626,23 @@ fn initial_guess(hue: f32, settings: &AccentOptSettings) -> (f64, f64) {
 /// Returns (is_feasible, max_achievable_m) where:
 /// - is_feasible: true if gamut allows M >= target_m - delta_m
 /// - max_achievable_m: maximum M achievable within J bounds
-fn check_m_feasibility(hue: f32, settings: &AccentOptSettings) -> (bool, f32) {
+fn check_m_feasibility(hue: f32, settings: &AccentOptSettings, gamut: Gamut) -> (bool, f32) {
     let j_min = settings.target_j - settings.delta_j;
     let j_max = settings.target_j + settings.delta_j;
     let m_required = (settings.target_m - settings.delta_m).max(0.0);
 
     // Find max achievable M within J bounds (sample at 1-unit intervals)
-    let mut max_m = 0.0f32;
     let j_start = j_min.max(0.0) as i32;
     let j_end = j_max.min(100.0) as i32;
-    for j in j_start..=j_end {
-        max_m = max_m.max(max_colorfulness_at(j as f32, hue));
-    }
+    let points: Vec<(f32, f32)> = (j_start..=j_end).map(|j| (j as f32, hue)).collect();
+    let mut boundaries = vec![0.0; points.len()];
+    max_colorfulness_many(&points, gamut, &mut boundaries);
+    let max_m = boundaries.into_iter().fold(0.0f32, f32::max);
 
     (max_m >= m_required, max_m)
 }
 
-/// Optimize accent colors for all hues using COBYLA.
+/// Optimize accent colors for all hues with the configured solver.
 ///
 /// Pre-computes background luminance once and runs per-hue optimization.
 /// Returns best-effort results even when constraints are infeasible.
@@ -217,39 +672,410 @@ pub fn optimize_accents(
     hues: &[f32],
     settings: &AccentOptSettings,
     min_contrast: f64,
+) -> AccentOptResult {
+    optimize_accents_in(background, hues, settings, min_contrast, Gamut::Srgb)
+}
+
+/// Optimize accent colors for all hues against a target gamut.
+///
+/// Same as `optimize_accents`, but colorfulness is bounded by `gamut`
+/// instead of sRGB. Each result's `jmh` lies in `gamut`; its `color` is the
+/// sRGB fallback.
+pub fn optimize_accents_in(
+    background: Srgb<u8>,
+    hues: &[f32],
+    settings: &AccentOptSettings,
+    min_contrast: f64,
+    gamut: Gamut,
+) -> AccentOptResult {
+    optimize_accents_with(background, hues, settings, min_contrast, gamut, &Apca)
+}
+
+/// Optimize accent colors for all hues, measuring contrast with `model`.
+///
+/// Same as `optimize_accents_in`; `min_contrast` and each result's
+/// `achieved_contrast` are in `model`'s units.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::accent_solver::optimize_accents_with;
+/// use themalingadingdong::config::AccentOptSettings;
+/// use themalingadingdong::contrast::Michelson;
+/// use themalingadingdong::gamut_map::Gamut;
+///
+/// let bg = Srgb::new(26u8, 26, 46);
+/// let hues = [25.0, 60.0, 120.0, 180.0, 240.0, 285.0, 320.0, 350.0];
+/// let settings = AccentOptSettings::default();
+/// let result = optimize_accents_with(bg, &hues, &settings, 70.0, Gamut::Srgb, &Michelson);
+/// assert!(result.hue_results.iter().all(|r| r.achieved_contrast >= 69.5));
+/// ```
+pub fn optimize_accents_with(
+    background: Srgb<u8>,
+    hues: &[f32],
+    settings: &AccentOptSettings,
+    min_contrast: f64,
+    gamut: Gamut,
+    model: &dyn ContrastModel,
 ) -> AccentOptResult {
     let start = Instant::now();
 
     // Pre-compute background luminance ONCE for all hues
     let bg_lum = srgb_to_luminance(background);
 
-    // Parallel optimization across hues (typically 8 hues, scales well on multi-core)
-    let hue_results: Vec<HueOptResult> = hues
-        .par_iter()
-        .map(|&hue| optimize_single_hue(bg_lum, hue, settings, min_contrast))
-        .collect();
+    // Parallel optimization across hues (typically 8 hues, scales well on multi-core),
+    // with the caller's viewing conditions carried into the workers. wasm32
+    // has no threads, so hues are solved in turn there.
+    let viewing = active_model();
+    let solve = |(i, &hue): (usize, &f32)| {
+        with_model(viewing.clone(), || {
+            let (settings, anchor) = hue_settings(settings, i);
+            let hue = anchor.map_or(hue, |a| a.jmh.hue);
+
+            let mut hasher = DefaultHasher::new();
+            format!(
+                "{settings:?} {anchor:?} {gamut:?} {model:?} {:?}",
+                viewing.as_ref().map(|v| v.conditions)
+            )
+            .hash(&mut hasher);
+            let key = SolutionKey {
+                bg_lum: bg_lum.to_bits(),
+                hue: hue.to_bits(),
+                settings: hasher.finish(),
+                min_contrast: min_contrast.to_bits(),
+            };
+            let cached = SOLUTIONS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&key);
+            if let Some(result) = cached {
+                return (result, true);
+            }
+
+            let context = SolveContext {
+                bg_lum,
+                model,
+                min_contrast,
+                gamut,
+            };
+            let result =
+                optimize_single_hue(&context, hue, &settings, Penalties::default(), anchor);
+            SOLUTIONS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, result.clone());
+            (result, false)
+        })
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    let solved: Vec<(HueOptResult, bool)> = hues.par_iter().enumerate().map(solve).collect();
+    #[cfg(target_arch = "wasm32")]
+    let solved: Vec<(HueOptResult, bool)> = hues.iter().enumerate().map(solve).collect();
+    let cached_hues = solved.iter().filter(|(_, cached)| *cached).count();
+    let mut hue_results: Vec<HueOptResult> = solved.into_iter().map(|(r, _)| r).collect();
+
+    if let Some(joint) = settings.joint
+        && hues.len() > 1
+    {
+        let context = SolveContext {
+            bg_lum,
+            model,
+            min_contrast,
+            gamut,
+        };
+        optimize_jointly(&mut hue_results, &context, settings, &joint);
+    }
+
+    if let Some(cvd) = settings.cvd
+        && hues.len() > 1
+    {
+        let context = SolveContext {
+            bg_lum,
+            model,
+            min_contrast,
+            gamut,
+        };
+        separate_for_cvd(&mut hue_results, &context, settings, &cvd);
+    }
+
+    if let Some(ref semantic) = settings.semantic
+        && hues.len() > 1
+    {
+        let context = SolveContext {
+            bg_lum,
+            model,
+            min_contrast,
+            gamut,
+        };
+        separate_semantic_pairs(&mut hue_results, &context, settings, semantic);
+    }
 
     let elapsed_ms = start.elapsed().as_millis() as u64;
     info!(
         hues = hues.len(),
-        elapsed_ms, "Accent optimization complete"
+        cached_hues, elapsed_ms, "Accent optimization complete"
     );
 
     AccentOptResult {
         hue_results,
         elapsed_ms,
+        cached_hues,
     }
 }
 
-/// Optimize a single hue using COBYLA.
+/// Re-solve each hue against the others with the group J' variance term.
+///
+/// Block coordinate descent on the joint objective: every hue is
+/// re-optimized in turn with the other accents held fixed, until a pass
+/// moves no J' by more than `JOINT_TOLERANCE` or after `JOINT_PASSES`
+/// passes. A re-solve that breaks constraints the independent solve met is
+/// discarded.
+fn optimize_jointly(
+    results: &mut [HueOptResult],
+    context: &SolveContext,
+    settings: &AccentOptSettings,
+    joint: &JointOptimization,
+) {
+    let before = lightness_spread(results);
+    for pass in 0..JOINT_PASSES {
+        let mut largest_move = 0.0f32;
+        for i in 0..results.len() {
+            let others: Vec<f32> = results
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k != i)
+                .map(|(_, r)| r.j)
+                .collect();
+            let penalties = Penalties {
+                group: Some(GroupPenalty::new(joint.group_weight, &others)),
+                ..Default::default()
+            };
+            let hue = results[i].hue;
+            let (settings, anchor) = hue_settings(settings, i);
+            let resolved = optimize_single_hue(context, hue, &settings, penalties, anchor);
+            // The group term never costs a hue its box or contrast constraints
+            if resolved.met_constraints || !results[i].met_constraints {
+                largest_move = largest_move.max((resolved.j - results[i].j).abs());
+                results[i] = resolved;
+            }
+        }
+        debug!(pass, largest_move, "Joint optimization pass complete");
+        if largest_move < JOINT_TOLERANCE {
+            break;
+        }
+    }
+    debug!(
+        before,
+        after = lightness_spread(results),
+        "Joint optimization evened J' spread"
+    );
+}
+
+/// Standard deviation of J' across the results.
+fn lightness_spread(results: &[HueOptResult]) -> f32 {
+    let n = results.len() as f32;
+    let mean = results.iter().map(|r| r.j).sum::<f32>() / n;
+    (results.iter().map(|r| (r.j - mean).powi(2)).sum::<f32>() / n).sqrt()
+}
+
+/// Re-solve each hue against the others with the CVD separation penalty.
+///
+/// Coordinate descent: every hue is re-optimized in turn with the other
+/// accents held fixed, for `CVD_PASSES` passes. A re-solve that breaks
+/// constraints the independent solve met is discarded.
+fn separate_for_cvd(
+    results: &mut [HueOptResult],
+    context: &SolveContext,
+    settings: &AccentOptSettings,
+    cvd: &CvdSeparation,
+) {
+    let penalty_for = |results: &[HueOptResult], i: usize| CvdPenalty {
+        settings: *cvd,
+        neighbors: results
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| *k != i)
+            .map(|(_, r)| Neighbor::new(srgb_to_u8(r.color), r.hue))
+            .collect(),
+    };
+
+    for pass in 0..CVD_PASSES {
+        for i in 0..results.len() {
+            let penalty = penalty_for(results, i);
+            let hue = results[i].hue;
+            let (settings, anchor) = hue_settings(settings, i);
+            let penalties = Penalties {
+                cvd: Some(penalty),
+                ..Default::default()
+            };
+            let resolved = optimize_single_hue(context, hue, &settings, penalties, anchor);
+            // Separation never costs a hue its box or contrast constraints
+            if resolved.met_constraints || !results[i].met_constraints {
+                results[i] = resolved;
+            }
+        }
+        debug!(pass, "CVD separation pass complete");
+    }
+
+    for i in 0..results.len() {
+        let penalty = penalty_for(results, i);
+        let result = &mut results[i];
+        let color = srgb_to_u8(result.color);
+        result.cvd_delta_e = penalty.closest(color);
+        if result.warning.is_none()
+            && let Some(short) = penalty.worst_shortfall(color)
+        {
+            warn!(
+                hue = result.hue,
+                other = short.other_hue,
+                kind = %short.kind,
+                delta_e = short.delta_e,
+                "CVD separation below target"
+            );
+            result.warning = Some(format!(
+                "Hue {:.0}: {} ΔE {:.1} to hue {:.0} < {:.1}",
+                result.hue, short.kind, short.delta_e, short.other_hue, short.target
+            ));
+        }
+    }
+}
+
+/// Re-solve the hues of each semantic pair against their partners.
+///
+/// Coordinate descent like `separate_for_cvd`, over the paired hues only,
+/// for `SEMANTIC_PASSES` passes. An unanchored hue's J' box is widened by
+/// `min_lightness` so a pair that hue cannot tell apart can split in
+/// lightness. Pairs still too close get a warning.
+fn separate_semantic_pairs(
+    results: &mut [HueOptResult],
+    context: &SolveContext,
+    settings: &AccentOptSettings,
+    semantic: &SemanticSeparation,
+) {
+    let penalty_for = |results: &[HueOptResult], i: usize| SemanticPenalty {
+        min_hue: semantic.min_hue,
+        min_lightness: semantic.min_lightness,
+        partners: semantic
+            .pairs
+            .iter()
+            .filter_map(|&(a, b)| match i {
+                _ if i == a => Some(b),
+                _ if i == b => Some(a),
+                _ => None,
+            })
+            .filter_map(|k| results.get(k))
+            .map(|r| (r.hue, srgb_to_u8(r.color)))
+            .collect(),
+    };
+    let mut paired: Vec<usize> = semantic
+        .pairs
+        .iter()
+        .flat_map(|&(a, b)| [a, b])
+        .filter(|&i| i < results.len())
+        .collect();
+    paired.sort_unstable();
+    paired.dedup();
+
+    for pass in 0..SEMANTIC_PASSES {
+        for &i in &paired {
+            let penalty = penalty_for(results, i);
+            if penalty.at(srgb_to_u8(results[i].color)) == 0.0 {
+                continue;
+            }
+            let hue = results[i].hue;
+            let (settings, anchor) = hue_settings(settings, i);
+            let mut settings = settings.into_owned();
+            if anchor.is_none() {
+                settings.delta_j += semantic.min_lightness;
+            }
+            // The penalty is piecewise flat in 8-bit color, which local
+            // solvers stall on
+            settings.solver.method = SolverMethod::Grid;
+            let penalties = Penalties {
+                semantic: Some(penalty),
+                ..Default::default()
+            };
+            let resolved = optimize_single_hue(context, hue, &settings, penalties, anchor);
+            // Separation never costs a hue its contrast or anchor
+            if resolved.met_constraints || !results[i].met_constraints {
+                results[i] = resolved;
+            }
+        }
+        debug!(pass, "Semantic pair separation pass complete");
+    }
+
+    for &i in &paired {
+        let penalty = penalty_for(results, i);
+        let result = &mut results[i];
+        if result.warning.is_none()
+            && let Some((other_hue, vision, _)) = penalty
+                .scores(srgb_to_u8(result.color))
+                .filter(|(_, _, score)| *score < 1.0)
+                .min_by(|a, b| a.2.total_cmp(&b.2))
+        {
+            let vision = vision.map_or_else(|| "normal vision".to_string(), |k| k.to_string());
+            warn!(
+                hue = result.hue,
+                other = other_hue,
+                vision,
+                "semantic pair not distinguishable"
+            );
+            result.warning = Some(format!(
+                "Hue {:.0}: too close to semantic partner hue {:.0} under {vision}",
+                result.hue, other_hue
+            ));
+        }
+    }
+}
+
+/// Optimize a single hue, then check it against its anchor.
 fn optimize_single_hue(
-    bg_lum: f64,
+    context: &SolveContext,
     hue: f32,
     settings: &AccentOptSettings,
-    min_contrast: f64,
+    penalties: Penalties,
+    anchor: Option<Anchor>,
+) -> HueOptResult {
+    let mut result = solve_hue(context, hue, settings, penalties, anchor);
+    if let Some(anchor) = anchor {
+        let delta_e = result.jmh.delta_e(&anchor.jmh);
+        result.anchor_delta_e = Some(delta_e);
+        if delta_e > anchor.tolerance + ANCHOR_SLACK {
+            warn!(
+                hue,
+                delta_e,
+                tolerance = anchor.tolerance,
+                "accent left its anchor"
+            );
+            result.met_constraints = false;
+            result.warning.get_or_insert_with(|| {
+                format!(
+                    "Hue {:.0}: ΔE {:.1} from its anchor > {:.1}",
+                    hue, delta_e, anchor.tolerance
+                )
+            });
+        }
+    }
+    result
+}
+
+/// Optimize a single hue with the configured solver.
+fn solve_hue(
+    context: &SolveContext,
+    hue: f32,
+    settings: &AccentOptSettings,
+    penalties: Penalties,
+    anchor: Option<Anchor>,
 ) -> HueOptResult {
+    let SolveContext {
+        bg_lum,
+        model,
+        min_contrast,
+        gamut,
+    } = *context;
     // Check M feasibility before optimization
-    let (is_m_feasible, max_achievable_m) = check_m_feasibility(hue, settings);
+    let (is_m_feasible, max_achievable_m) = check_m_feasibility(hue, settings, gamut);
     let m_lower = (settings.target_m - settings.delta_m).max(0.0);
 
     if !is_m_feasible {
@@ -260,11 +1086,10 @@ fn optimize_single_hue(
         );
         // Use best possible values: target J and max achievable M
         return build_hue_result(
-            bg_lum,
+            context,
             hue,
             settings.target_j,
             max_achievable_m,
-            min_contrast,
             settings,
             Some(format!(
                 "Hue {:.0}: gamut limit {:.1} < M bound {:.1}",
@@ -273,7 +1098,7 @@ fn optimize_single_hue(
         );
     }
 
-    let (j_init, m_init) = initial_guess(hue, settings);
+    let (j_init, m_init) = initial_guess(hue, settings, gamut);
 
     debug!(
         hue,
@@ -284,11 +1109,13 @@ fn optimize_single_hue(
         delta_j = settings.delta_j,
         delta_m = settings.delta_m,
         min_contrast,
-        "Starting COBYLA optimization"
+        method = %settings.solver.method,
+        "Starting optimization"
     );
 
-    let problem = AccentProblem {
+    let mut problem = AccentProblem {
         bg_lum,
+        model,
         hue,
         target_j: settings.target_j,
         target_m: settings.target_m,
@@ -297,70 +1124,293 @@ fn optimize_single_hue(
         j_weight: settings.j_weight,
         contrast_weight: settings.contrast_weight,
         min_contrast,
+        preferred_contrast: settings.preferred_contrast,
+        saturated: false,
+        gamut,
+        penalties,
+        anchor,
     };
 
+    // Switch objectives when the whole box clears the target by a wide margin
+    let headroom = problem.min_box_contrast() - min_contrast;
+    problem.saturated = headroom > SATURATION_HEADROOM;
+    let saturated = problem.saturated;
+    if saturated {
+        debug!(hue, headroom, "Contrast saturated, optimizing uniformity");
+    }
+
     // Check initial contrast to understand feasibility
     let init_contrast = problem.contrast_at(j_init, m_init);
     debug!(hue, init_contrast, "Initial guess contrast");
 
-    let solver = CobylaSolver::new(vec![j_init, m_init]);
-
-    let result = Executor::new(problem, solver)
-        .configure(|mut state| {
-            state.rhoend = 1e-6; // Tighter convergence tolerance
-            state.max_iters(200).iprint(0) // Higher limit as safety net
-        })
-        .run();
-
-    match result {
-        Ok(res) => {
-            let fallback = vec![j_init, m_init];
-            let best = res.state.get_best_param().unwrap_or(&fallback);
-            let j = best[0] as f32;
-            let m = best[1] as f32;
+    let solver = settings.solver;
+    match minimize(&problem, [j_init, m_init], &solver) {
+        Ok([j, m]) => {
+            let j = j as f32;
+            let m = m as f32;
 
-            debug!(hue, j, m, "COBYLA converged");
-            build_hue_result(bg_lum, hue, j, m, min_contrast, settings, None)
+            debug!(hue, j, m, method = %solver.method, "Solver converged");
+            HueOptResult {
+                saturated,
+                ..build_hue_result(context, hue, j, m, settings, None)
+            }
+        }
+        Err(e) if solver.method != SolverMethod::Grid => {
+            warn!(
+                hue,
+                error = %e,
+                method = %solver.method,
+                "Solver failed, falling back to grid search"
+            );
+            let [j, m] = grid_search(
+                &problem,
+                [j_init, m_init],
+                &SolverSettings {
+                    method: SolverMethod::Grid,
+                    ..SolverSettings::default()
+                },
+            )
+            .unwrap_or([j_init, m_init]);
+            HueOptResult {
+                saturated,
+                ..build_hue_result(
+                    context,
+                    hue,
+                    j as f32,
+                    m as f32,
+                    settings,
+                    Some(format!("{} failed, used grid search: {}", solver.method, e)),
+                )
+            }
         }
         Err(e) => {
-            warn!(hue, error = %e, "COBYLA optimization failed, using initial guess");
+            warn!(hue, error = %e, "Grid search failed, using initial guess");
             let j = j_init as f32;
             let m = m_init as f32;
 
             build_hue_result(
-                bg_lum,
+                context,
                 hue,
                 j,
                 m,
-                min_contrast,
                 settings,
-                Some(format!("COBYLA failed: {}", e)),
+                Some(format!("grid search failed: {}", e)),
             )
         }
     }
 }
 
+/// Search the J'/M box from `start` with the configured method, returning
+/// the best (J', M) found.
+fn minimize(
+    problem: &AccentProblem<'_>,
+    start: [f64; 2],
+    solver: &SolverSettings,
+) -> Result<[f64; 2], Error> {
+    match solver.method {
+        SolverMethod::Cobyla => cobyla(problem, start, solver),
+        SolverMethod::NelderMead => nelder_mead(problem, start, solver),
+        SolverMethod::Grid => grid_search(problem, start, solver),
+    }
+}
+
+/// Run COBYLA from `start`.
+#[cfg(not(target_arch = "wasm32"))]
+fn cobyla(
+    problem: &AccentProblem<'_>,
+    start: [f64; 2],
+    solver: &SolverSettings,
+) -> Result<[f64; 2], Error> {
+    let cobyla = CobylaSolver::new(start.to_vec());
+    let rhoend = solver.rhoend();
+    let max_iters = solver.max_iters();
+    let res = Executor::new(problem, cobyla)
+        .configure(|mut state| {
+            state.rhoend = rhoend;
+            state.max_iters(max_iters).iprint(0)
+        })
+        .run()?;
+    Ok(res
+        .state
+        .get_best_param()
+        .map_or(start, |best| [best[0], best[1]]))
+}
+
+/// Penalty per unit of constraint violation in the penalized merit.
+const VIOLATION_PENALTY: f64 = 1e3;
+
+/// Compass search from `start` on the penalized merit.
+///
+/// The `cobyla` crate does not build for wasm32-unknown-unknown, so the web
+/// build uses this instead. Palettes match the COBYLA ones to within a
+/// couple of 8-bit sRGB steps per channel.
+#[cfg(target_arch = "wasm32")]
+fn cobyla(
+    problem: &AccentProblem<'_>,
+    start: [f64; 2],
+    _solver: &SolverSettings,
+) -> Result<[f64; 2], Error> {
+    let mut best = start;
+    let mut best_merit = problem.merit(best)?;
+    let mut step = f64::from(problem.delta_j.max(problem.delta_m)) / 2.0;
+    let mut evals = 0;
+    while step > 1e-4 && evals < 2000 {
+        let mut improved = false;
+        for (dj, dm) in [(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)] {
+            let candidate = [best[0] + dj * step, best[1] + dm * step];
+            let value = problem.merit(candidate)?;
+            evals += 1;
+            if value < best_merit {
+                (best, best_merit, improved) = (candidate, value, true);
+                break;
+            }
+        }
+        if !improved {
+            step /= 2.0;
+        }
+    }
+    Ok(best)
+}
+
+/// Nelder-Mead simplex search from `start` on the penalized merit.
+///
+/// Stops once the simplex is smaller than `rhoend` in both J' and M, or
+/// after `max_iters` iterations.
+fn nelder_mead(
+    problem: &AccentProblem<'_>,
+    start: [f64; 2],
+    solver: &SolverSettings,
+) -> Result<[f64; 2], Error> {
+    let along =
+        |p: [f64; 2], q: [f64; 2], t: f64| [p[0] + t * (q[0] - p[0]), p[1] + t * (q[1] - p[1])];
+    let step_j = f64::from(problem.delta_j.max(0.5)) / 2.0;
+    let step_m = f64::from(problem.delta_m.max(0.5)) / 2.0;
+    let vertex = |p: [f64; 2]| problem.merit(p).map(|value| (p, value));
+    let mut simplex = [
+        vertex(start)?,
+        vertex([start[0] + step_j, start[1]])?,
+        vertex([start[0], start[1] + step_m])?,
+    ];
+
+    for _ in 0..solver.max_iters() {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let [(best, best_value), (_, second_value), (worst, worst_value)] = simplex;
+        let size = simplex[1..]
+            .iter()
+            .map(|(p, _)| (p[0] - best[0]).abs().max((p[1] - best[1]).abs()))
+            .fold(0.0, f64::max);
+        if size < solver.rhoend() {
+            break;
+        }
+
+        // Reflect the worst vertex through the centroid of the other two
+        let centroid = along(simplex[0].0, simplex[1].0, 0.5);
+        let reflected = along(worst, centroid, 2.0);
+        let reflected_value = problem.merit(reflected)?;
+        simplex[2] = if reflected_value < best_value {
+            let expanded = along(worst, centroid, 3.0);
+            let expanded_value = problem.merit(expanded)?;
+            if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            }
+        } else if reflected_value < second_value {
+            (reflected, reflected_value)
+        } else {
+            let contracted = along(worst, centroid, 0.5);
+            let contracted_value = problem.merit(contracted)?;
+            if contracted_value < worst_value {
+                (contracted, contracted_value)
+            } else {
+                // Shrink toward the best vertex
+                for v in &mut simplex[1..] {
+                    *v = vertex(along(best, v.0, 0.5))?;
+                }
+                continue;
+            }
+        };
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    Ok(simplex[0].0)
+}
+
+/// Points per side of each grid level.
+const GRID_POINTS: i32 = 5;
+
+/// Grid search over the J'/M box on the penalized merit, refined around the
+/// best point.
+///
+/// The first level spans the whole box; each later one is centered on the
+/// best point so far at half the spacing, until the spacing drops below
+/// `rhoend` or `max_iters` levels have run.
+fn grid_search(
+    problem: &AccentProblem<'_>,
+    start: [f64; 2],
+    solver: &SolverSettings,
+) -> Result<[f64; 2], Error> {
+    let mut best = start;
+    let mut best_value = problem.merit(start)?;
+    let mut center = [f64::from(problem.target_j), f64::from(problem.target_m)];
+    let half = f64::from(GRID_POINTS / 2);
+    let mut spacing = [
+        f64::from(problem.delta_j.max(0.5)) / half,
+        f64::from(problem.delta_m.max(0.5)) / half,
+    ];
+
+    for _ in 0..solver.max_iters() {
+        if spacing[0].max(spacing[1]) < solver.rhoend() {
+            break;
+        }
+        for a in 0..GRID_POINTS {
+            for b in 0..GRID_POINTS {
+                let p = [
+                    center[0] + (f64::from(a) - half) * spacing[0],
+                    center[1] + (f64::from(b) - half) * spacing[1],
+                ];
+                let value = problem.merit(p)?;
+                if value < best_value {
+                    (best, best_value) = (p, value);
+                }
+            }
+        }
+        center = best;
+        spacing = spacing.map(|s| s / 2.0);
+    }
+    Ok(best)
+}
+
 /// Build HueOptResult from optimized (J', M) values.
 fn build_hue_result(
-    bg_lum: f64,
+    context: &SolveContext,
     hue: f32,
     j: f32,
     m: f32,
-    min_contrast: f64,
     settings: &AccentOptSettings,
     mut warning: Option<String>,
 ) -> HueOptResult {
+    let SolveContext {
+        bg_lum,
+        model,
+        min_contrast,
+        gamut,
+    } = *context;
+
     // Store original M before gamut mapping
     let original_m = m;
 
-    // Apply gamut mapping
+    // Apply gamut mapping; wide-gamut results also get an sRGB fallback
     let color = HellwigJmh::new(j, m, hue);
-    let mapped = gamut_map(color);
-    let srgb = mapped.into_srgb();
+    let mapped = gamut_map_to(color, gamut);
+    let srgb = gamut_map(mapped).into_srgb();
 
-    // Compute actual contrast
-    let fg_lum = srgb_to_luminance(srgb_to_u8(srgb));
-    let achieved_contrast = contrast_from_luminances(fg_lum, bg_lum).abs();
+    // Compute actual contrast (quantized like the hex output for sRGB)
+    let fg_lum = if gamut.is_srgb() {
+        srgb_to_luminance(srgb_to_u8(srgb))
+    } else {
+        luminance_in(mapped, gamut)
+    };
+    let achieved_contrast = model.contrast_from_luminances(fg_lum, bg_lum).abs();
 
     // Compute bounds
     let j_lower = settings.target_j - settings.delta_j;
@@ -411,8 +1461,11 @@ fn build_hue_result(
             "Contrast below minimum within bounds"
         );
         warning = Some(format!(
-            "Hue {:.0}: Lc {:.1} < {:.1} (best within bounds)",
-            hue, achieved_contrast, min_contrast
+            "Hue {:.0}: {} {:.1} < {:.1} (best within bounds)",
+            hue,
+            model.label(),
+            achieved_contrast,
+            min_contrast
         ));
     }
 
@@ -421,6 +1474,7 @@ fn build_hue_result(
         j,
         m: mapped.colorfulness,
         original_m,
+        jmh: mapped,
         color: srgb,
         post_clamp_j: mapped.lightness,
         achieved_contrast,
@@ -430,5 +1484,8 @@ fn build_hue_result(
         m_in_bounds,
         m_lower_bound: m_lower,
         m_upper_bound: m_upper,
+        saturated: false,
+        cvd_delta_e: None,
+        anchor_delta_e: None,
     }
 }
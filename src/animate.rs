@@ -0,0 +1,190 @@
+//! Animated transitions between two schemes (`animate`).
+//!
+//! Every slot is interpolated in HellwigJmh, J' and M linearly and hue
+//! along the shorter arc, so intermediate frames keep even perceived
+//! lightness steps instead of passing through the muddy midpoints of an
+//! sRGB blend. A slot that is nearly gray at one end takes its hue from the
+//! other end, so it doesn't swing through unrelated hues on the way. The
+//! first and last frames are the input colors exactly.
+//!
+//! Frames are written as scheme files, or as one CSS `@keyframes` rule over
+//! the scheme's custom properties.
+
+use std::fmt::Write;
+
+use color_eyre::eyre::{Result, bail};
+use palette::Srgb;
+use tinted_builder::{Base16Scheme, Color};
+
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::{lerp, lerp_hue, srgb_to_hex};
+use crate::metadata::detect_variant;
+
+/// Colorfulness below which a color's hue is treated as undefined.
+const ACHROMATIC_M: f32 = 2.0;
+
+/// Interpolate `steps` frames from `from` to `to`, both ends included.
+///
+/// Both schemes must have the same palette slots (two Base16 or two Base24
+/// schemes). Frames take their system and author from `from`, and their
+/// variant from their own base00/base05 lightness.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::animate::interpolate_schemes;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let night = generate(&GenerateConfig::default()).scheme;
+/// let day = generate(&GenerateConfig {
+///     background: Srgb::new(250, 248, 240),
+///     foreground: Srgb::new(40, 40, 48),
+///     ..Default::default()
+/// })
+/// .scheme;
+///
+/// let frames = interpolate_schemes(&night, &day, 5).unwrap();
+/// assert_eq!(frames.len(), 5);
+/// assert_eq!(frames[0].palette["base00"].rgb, night.palette["base00"].rgb);
+/// assert_eq!(frames[4].palette["base00"].rgb, day.palette["base00"].rgb);
+/// ```
+pub fn interpolate_schemes(
+    from: &Base16Scheme,
+    to: &Base16Scheme,
+    steps: usize,
+) -> Result<Vec<Base16Scheme>> {
+    if steps < 2 {
+        bail!("an animation needs at least 2 frames (got {steps})");
+    }
+    for (scheme, other) in [(from, to), (to, from)] {
+        let mut missing: Vec<&str> = scheme
+            .palette
+            .keys()
+            .filter(|slot| !other.palette.contains_key(*slot))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            missing.sort_unstable();
+            bail!(
+                "{} has {} but {} does not",
+                scheme.name,
+                missing.join(", "),
+                other.name
+            );
+        }
+    }
+
+    let width = steps.to_string().len();
+    let frames = (0..steps)
+        .map(|i| {
+            let t = i as f32 / (steps - 1) as f32;
+            let palette = from
+                .palette
+                .iter()
+                .map(|(slot, start)| {
+                    let color = match i {
+                        0 => start.clone(),
+                        _ if i == steps - 1 => to.palette[slot].clone(),
+                        _ => interpolate_color(start, &to.palette[slot], t),
+                    };
+                    (slot.clone(), color)
+                })
+                .collect();
+            let mut frame = Base16Scheme {
+                name: format!("{} to {} ({} of {steps})", from.name, to.name, i + 1),
+                slug: format!("{}-to-{}-{:0width$}", from.slug, to.slug, i + 1),
+                description: Some(format!(
+                    "Frame {} of {steps} from {} to {}",
+                    i + 1,
+                    from.name,
+                    to.name
+                )),
+                palette,
+                ..from.clone()
+            };
+            if let Some(variant) = detect_variant(&frame) {
+                frame.variant = variant;
+            }
+            frame
+        })
+        .collect();
+    Ok(frames)
+}
+
+/// The color a fraction `t` of the way from `start` to `end`.
+fn interpolate_color(start: &Color, end: &Color, t: f32) -> Color {
+    let srgb = |color: &Color| {
+        let (r, g, b) = color.rgb;
+        Srgb::new(r, g, b)
+    };
+    let mixed = mix_srgb(srgb(start), srgb(end), t);
+    Color::new(srgb_to_hex(mixed)).expect("valid hex")
+}
+
+/// Mix two colors in HellwigJmh, `t` of the way from `start` to `end`;
+/// a nearly gray end takes the other end's hue.
+pub(crate) fn mix_srgb(start: Srgb<u8>, end: Srgb<u8>, t: f32) -> Srgb<u8> {
+    let (a, b) = (
+        HellwigJmh::from_srgb_u8(start),
+        HellwigJmh::from_srgb_u8(end),
+    );
+    let (hue_a, hue_b) = match (a.colorfulness < ACHROMATIC_M, b.colorfulness < ACHROMATIC_M) {
+        (true, false) => (b.hue, b.hue),
+        (false, true) => (a.hue, a.hue),
+        _ => (a.hue, b.hue),
+    };
+    HellwigJmh::new(
+        lerp(a.lightness, b.lightness, t),
+        lerp(a.colorfulness, b.colorfulness, t),
+        lerp_hue(hue_a, hue_b, t),
+    )
+    .into_srgb_u8_gamut_mapped()
+}
+
+/// A CSS animation named `name` stepping the `--baseXX` custom properties
+/// through `frames`, evenly spaced.
+///
+/// The properties are registered with `@property` as colors, since browsers
+/// only animate custom properties that declare a type. Apply it with e.g.
+/// `animation: <name> 10s forwards` on `:root`.
+pub fn css_keyframes(frames: &[Base16Scheme], name: &str) -> String {
+    let Some(first) = frames.first() else {
+        return String::new();
+    };
+    let mut slots: Vec<&String> = first.palette.keys().collect();
+    slots.sort_by_key(|slot| slot.to_lowercase());
+    let hex = |frame: &Base16Scheme, slot: &str| {
+        let (r, g, b) = frame.palette[slot].rgb;
+        format!("#{r:02x}{g:02x}{b:02x}")
+    };
+
+    let mut css = String::new();
+    let _ = writeln!(css, "/* {name}: {} frames */", frames.len());
+    for slot in &slots {
+        let _ = writeln!(
+            css,
+            "@property --{} {{\n  syntax: \"<color>\";\n  inherits: true;\n  initial-value: {};\n}}",
+            slot.to_lowercase(),
+            hex(first, slot)
+        );
+    }
+    let _ = writeln!(css, "\n@keyframes {name} {{");
+    let last = frames.len().saturating_sub(1).max(1);
+    for (i, frame) in frames.iter().enumerate() {
+        let percent = i as f64 * 100.0 / last as f64;
+        let _ = writeln!(css, "  {} {{", format_percent(percent));
+        for slot in &slots {
+            let _ = writeln!(css, "    --{}: {};", slot.to_lowercase(), hex(frame, slot));
+        }
+        css.push_str("  }\n");
+    }
+    css.push_str("}\n");
+    css
+}
+
+/// A keyframe selector: `25%`, or `33.333%` between whole percents.
+fn format_percent(percent: f64) -> String {
+    let text = format!("{percent:.3}");
+    format!("{}%", text.trim_end_matches('0').trim_end_matches('.'))
+}
@@ -0,0 +1,139 @@
+//! Per-slot diagnostic comments in YAML output (`--annotate`).
+//!
+//! Each palette entry gets a trailing comment with the numbers behind it:
+//!
+//! ```yaml
+//! palette:
+//!   base00: 1d2021  # J' 10.5 M 2.3 h 217.0
+//!   base08: ffbfb7  # Lc 75.1 | J' 80.1 M 19.7 h 25.2 | relaxed: M reduced to fit gamut
+//!   base09: ffc49b  # Lc 76.2 | J' 80.0 M 25.2 h 55.1 | constraints met
+//! ```
+//!
+//! Lc is the APCA contrast of the slot as text on base00. Accents add the
+//! solver's outcome: `constraints met`, or which constraints it had to give
+//! up on.
+
+use palette::Srgb;
+use tinted_builder::Base16Scheme;
+
+use crate::apca::apca_contrast;
+use crate::contrast_matrix::SLOTS;
+use crate::hellwig::HellwigJmh;
+use crate::report::{AccentDiagnostics, SolverReport};
+
+/// Largest distance from the accents' target J' that isn't reported.
+const J_TOLERANCE: f32 = 0.5;
+
+/// The numbers behind one palette slot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotNote {
+    pub slot: &'static str,
+    /// APCA contrast on base00 (absent for base00 itself)
+    pub lc: Option<f64>,
+    pub jmh: HellwigJmh,
+    /// Constraints the solver gave up on; `None` for slots it didn't solve
+    pub relaxed: Option<Vec<String>>,
+}
+
+impl SlotNote {
+    /// The note as comment text, without the `#`.
+    pub fn comment(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(lc) = self.lc {
+            parts.push(format!("Lc {:.1}", lc.abs()));
+        }
+        parts.push(format!(
+            "J' {:.1} M {:.1} h {:.1}",
+            self.jmh.lightness, self.jmh.colorfulness, self.jmh.hue
+        ));
+        match self.relaxed.as_deref() {
+            Some([]) => parts.push("constraints met".to_string()),
+            Some(relaxed) => parts.push(format!("relaxed: {}", relaxed.join(", "))),
+            None => {}
+        }
+        parts.join(" | ")
+    }
+}
+
+/// Constraints the solver gave up on for one accent.
+fn relaxed(accent: &AccentDiagnostics) -> Vec<String> {
+    let mut relaxed = Vec::new();
+    if !accent.met_minimum {
+        relaxed.push("below contrast floor".to_string());
+    }
+    if accent.j_deviation.abs() > J_TOLERANCE {
+        relaxed.push(format!("J' {:+.1} from target", accent.j_deviation));
+    }
+    if accent.gamut_mapped {
+        relaxed.push("M reduced to fit gamut".to_string());
+    }
+    if !accent.m_in_bounds {
+        relaxed.push("M outside bounds".to_string());
+    }
+    relaxed
+}
+
+/// Notes for every slot of `scheme`, in slot order, with the solver's
+/// outcome for the accents when `solver` is given.
+pub fn slot_notes(scheme: &Base16Scheme, solver: Option<&SolverReport>) -> Vec<SlotNote> {
+    let rgb = |slot: &str| {
+        scheme
+            .palette
+            .get(slot)
+            .map(|c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+    };
+    let background = rgb("base00");
+    SLOTS
+        .iter()
+        .filter_map(|&slot| {
+            let color = rgb(slot)?;
+            let accent = solver.and_then(|s| s.accents.iter().find(|a| a.slot == slot));
+            Some(SlotNote {
+                slot,
+                lc: background
+                    .filter(|_| slot != "base00")
+                    .map(|bg| apca_contrast(color, bg)),
+                jmh: HellwigJmh::from_srgb_u8(color),
+                relaxed: accent.map(relaxed),
+            })
+        })
+        .collect()
+}
+
+/// `yaml` (a scheme serialized as YAML) with each palette entry followed by
+/// its note from `notes`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::annotate::{annotate_yaml, slot_notes};
+/// use themalingadingdong::generate::{GenerateConfig, generate_for_variant};
+///
+/// let result = generate_for_variant(&GenerateConfig::default(), None);
+/// let yaml = serde_yaml::to_string(&result.scheme).unwrap();
+/// let annotated = annotate_yaml(&yaml, &slot_notes(&result.scheme, None));
+///
+/// let base05 = annotated.lines().find(|l| l.contains("base05:")).unwrap();
+/// assert!(base05.contains("  # Lc "));
+/// // Comments don't change the scheme
+/// let read_back: serde_yaml::Value = serde_yaml::from_str(&annotated).unwrap();
+/// assert_eq!(read_back, serde_yaml::from_str::<serde_yaml::Value>(&yaml).unwrap());
+/// ```
+pub fn annotate_yaml(yaml: &str, notes: &[SlotNote]) -> String {
+    let mut out = String::with_capacity(yaml.len() * 2);
+    let mut in_palette = false;
+    for line in yaml.lines() {
+        out.push_str(line);
+        if !line.starts_with([' ', '#']) && !line.is_empty() {
+            in_palette = line == "palette:";
+        } else if in_palette
+            && let Some((key, _)) = line.trim_start().split_once(':')
+            && let Some(note) = notes.iter().find(|n| n.slot == key)
+        {
+            out.push_str("  # ");
+            out.push_str(&note.comment());
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,310 @@
+//! Terminal (ANSI) meaning of the accent slots.
+//!
+//! Terminals show base16 themes through ANSI colors 0-15, and programs pick
+//! those colors by meaning: `git diff` marks removed lines red, `ls` colors
+//! directories blue, test runners print failures red and passes green. The
+//! base16 convention fixes which accent backs which ANSI color; this module
+//! spells that mapping out and checks that each accent's hue still reads as
+//! its color. A hue override that turns base08 teal keeps the scheme valid,
+//! but every red diff marker becomes teal with it.
+//!
+//! Hues are checked against a band per named accent (`[ansi.bands]`); the
+//! defaults cover the six accents behind ANSI 1-6 and 9-14.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use palette::Srgb;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use tinted_builder::Base16Scheme;
+
+use crate::config::ConfigError;
+use crate::hellwig::HellwigJmh;
+
+/// Colorfulness below which an accent is too gray to read as any hue.
+const NEUTRAL_M: f32 = 5.0;
+
+/// An accent slot with its color name and the ANSI colors it backs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NamedAccent {
+    /// Palette slot (base08-base0F)
+    pub slot: &'static str,
+    /// Color name the slot stands for, as used in `[ansi.bands]`
+    pub name: &'static str,
+    /// ANSI color (1-6) the slot is used for
+    pub normal: Option<u8>,
+    /// Bright ANSI color (9-14), taken from this Base24 slot when present
+    pub bright: Option<(u8, &'static str)>,
+}
+
+const fn accent(
+    slot: &'static str,
+    name: &'static str,
+    normal: Option<u8>,
+    bright: Option<(u8, &'static str)>,
+) -> NamedAccent {
+    NamedAccent {
+        slot,
+        name,
+        normal,
+        bright,
+    }
+}
+
+/// base08-base0F in slot order. base09 and base0F have no ANSI color.
+pub const ACCENTS: [NamedAccent; 8] = [
+    accent("base08", "red", Some(1), Some((9, "base12"))),
+    accent("base09", "orange", None, None),
+    accent("base0A", "yellow", Some(3), Some((11, "base13"))),
+    accent("base0B", "green", Some(2), Some((10, "base14"))),
+    accent("base0C", "cyan", Some(6), Some((14, "base15"))),
+    accent("base0D", "blue", Some(4), Some((12, "base16"))),
+    accent("base0E", "purple", Some(5), Some((13, "base17"))),
+    accent("base0F", "magenta", None, None),
+];
+
+/// Named accent for a slot (base08-base0F).
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::ansi::named_accent;
+///
+/// let green = named_accent("base0B").unwrap();
+/// assert_eq!(green.name, "green");
+/// assert_eq!(green.normal, Some(2));
+/// assert_eq!(green.bright, Some((10, "base14")));
+/// assert!(named_accent("base05").is_none());
+/// ```
+pub fn named_accent(slot: &str) -> Option<&'static NamedAccent> {
+    ACCENTS.iter().find(|a| a.slot == slot)
+}
+
+/// Slots ANSI colors 0-15 take their value from, with the Base24 bright
+/// accent preferred for 9-14.
+const ANSI_SLOTS: [(&str, Option<&str>); 16] = [
+    ("base00", None),
+    ("base08", None),
+    ("base0B", None),
+    ("base0A", None),
+    ("base0D", None),
+    ("base0E", None),
+    ("base0C", None),
+    ("base05", None),
+    ("base03", None),
+    ("base08", Some("base12")),
+    ("base0B", Some("base14")),
+    ("base0A", Some("base13")),
+    ("base0D", Some("base16")),
+    ("base0E", Some("base17")),
+    ("base0C", Some("base15")),
+    ("base07", None),
+];
+
+/// Slot each ANSI color (0-15) takes its value from: the base16 terminal
+/// mapping, using the Base24 bright accents for 9-14 when the scheme has them.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::ansi::ansi_slots;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let slots = ansi_slots(&scheme);
+/// assert_eq!(slots[1], "base08");
+/// assert_eq!(slots[9], "base12");
+/// ```
+pub fn ansi_slots(scheme: &Base16Scheme) -> [&'static str; 16] {
+    ANSI_SLOTS.map(|(base, bright)| {
+        bright
+            .filter(|b| scheme.palette.contains_key(*b))
+            .unwrap_or(base)
+    })
+}
+
+/// A range of hues, running counterclockwise from `from` to `to` (degrees).
+/// `from` greater than `to` wraps through 0°.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HueBand {
+    pub from: f32,
+    pub to: f32,
+}
+
+impl HueBand {
+    pub const fn new(from: f32, to: f32) -> Self {
+        Self { from, to }
+    }
+
+    /// Whether `hue` lies in the band.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::ansi::HueBand;
+    ///
+    /// let red = HueBand::new(350.0, 50.0);
+    /// assert!(red.contains(10.0));
+    /// assert!(red.contains(355.0));
+    /// assert!(!red.contains(190.0));
+    /// ```
+    pub fn contains(&self, hue: f32) -> bool {
+        let width = (self.to - self.from).rem_euclid(360.0);
+        (hue - self.from).rem_euclid(360.0) <= width
+    }
+}
+
+/// Hue bands the ANSI accents keep by default.
+const DEFAULT_BANDS: [(&str, HueBand); 6] = [
+    ("red", HueBand::new(350.0, 50.0)),
+    ("yellow", HueBand::new(65.0, 115.0)),
+    ("green", HueBand::new(115.0, 170.0)),
+    ("cyan", HueBand::new(160.0, 215.0)),
+    ("blue", HueBand::new(220.0, 275.0)),
+    ("purple", HueBand::new(265.0, 330.0)),
+];
+
+/// ANSI mapping settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnsiConfig {
+    /// Hue bands by accent name, replacing the default for that accent.
+    /// Accents without a default band (orange, magenta) are only checked
+    /// when given one here.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub bands: BTreeMap<String, HueBand>,
+}
+
+impl AnsiConfig {
+    /// Whether these are the default bands.
+    pub fn is_default(&self) -> bool {
+        self.bands.is_empty()
+    }
+
+    /// Band the accent called `name` must stay within, if any.
+    pub fn band(&self, name: &str) -> Option<HueBand> {
+        self.bands.get(name).copied().or_else(|| {
+            DEFAULT_BANDS
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, band)| *band)
+        })
+    }
+
+    /// Check that bands name real accents and use hues in 0-360.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (name, band) in &self.bands {
+            if !ACCENTS.iter().any(|a| a.name == name) {
+                let names: Vec<_> = ACCENTS.iter().map(|a| a.name).collect();
+                return Err(ConfigError::InvalidValue(format!(
+                    "ansi.bands: '{name}' is not an accent (expected one of {})",
+                    names.join(", ")
+                )));
+            }
+            for (key, hue) in [("from", band.from), ("to", band.to)] {
+                if !(0.0..=360.0).contains(&hue) {
+                    return Err(ConfigError::InvalidValue(format!(
+                        "ansi.bands.{name}.{key} must be between 0 and 360 degrees (got {hue})"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Warnings for accents whose hue has left their band, so terminal output
+/// that relies on the color's meaning no longer reads as intended.
+///
+/// Hues are measured in the active viewing conditions. Accents too gray to
+/// have a recognizable hue are skipped.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::ansi::{AnsiConfig, semantic_warnings};
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let mut config = GenerateConfig::default();
+/// config.hue_overrides[0] = Some(190.0); // base08 turned teal
+/// let scheme = generate(&config).scheme;
+///
+/// let warnings = semantic_warnings(&scheme, &AnsiConfig::default());
+/// assert_eq!(warnings.len(), 1);
+/// assert!(warnings[0].starts_with("base08 (red, ANSI 1/9)"));
+/// ```
+pub fn semantic_warnings(scheme: &Base16Scheme, config: &AnsiConfig) -> Vec<String> {
+    out_of_band(scheme, config)
+        .into_iter()
+        .map(|(accent, band, jmh)| {
+            let ansi = match (accent.normal, accent.bright) {
+                (Some(normal), Some((bright, _))) => format!(", ANSI {normal}/{bright}"),
+                _ => String::new(),
+            };
+            format!(
+                "{} ({}{ansi}): hue {:.0}° is outside {:.0}°-{:.0}°; terminal output colored {} will not look {}",
+                accent.slot, accent.name, jmh.hue, band.from, band.to, accent.name, accent.name
+            )
+        })
+        .collect()
+}
+
+/// Accents whose hue has left their band, with the band and the accent's
+/// color. Accents too gray to have a recognizable hue are skipped.
+pub fn out_of_band(
+    scheme: &Base16Scheme,
+    config: &AnsiConfig,
+) -> Vec<(NamedAccent, HueBand, HellwigJmh)> {
+    ACCENTS
+        .iter()
+        .filter_map(|accent| {
+            let band = config.band(accent.name)?;
+            let (r, g, b) = scheme.palette.get(accent.slot)?.rgb;
+            let jmh = HellwigJmh::from_srgb_u8(Srgb::new(r, g, b));
+            if jmh.colorfulness < NEUTRAL_M || band.contains(jmh.hue) {
+                return None;
+            }
+            Some((*accent, band, jmh))
+        })
+        .collect()
+}
+
+/// The ANSI color mapping of a scheme, serialized as an `index: slot` map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnsiMap {
+    pub slots: [&'static str; 16],
+}
+
+impl AnsiMap {
+    pub fn new(scheme: &Base16Scheme) -> Self {
+        Self {
+            slots: ansi_slots(scheme),
+        }
+    }
+
+    /// CSS custom properties mapping each ANSI color to its slot variable,
+    /// both named with `prefix`.
+    pub fn css(&self, prefix: &str) -> String {
+        let mut css = String::from("\n:root {\n");
+        for (index, slot) in self.slots.iter().enumerate() {
+            let _ = writeln!(
+                css,
+                "  --{prefix}ansi-{index}: var(--{prefix}{});",
+                slot.to_lowercase()
+            );
+        }
+        css.push_str("}\n");
+        css
+    }
+}
+
+impl Serialize for AnsiMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.slots.len()))?;
+        for (index, slot) in self.slots.iter().enumerate() {
+            map.serialize_entry(&index, slot)?;
+        }
+        map.end()
+    }
+}
@@ -76,6 +76,28 @@ pub fn srgb_f32_to_luminance(color: Srgb<f32>) -> f64 {
     }
 }
 
+/// Convert an extended-range sRGB color to APCA luminance (Y).
+///
+/// Channels outside 0.0-1.0 (wide-gamut colors expressed in sRGB primaries)
+/// are linearized with a sign-preserving power instead of being clamped.
+pub fn extended_srgb_to_luminance(color: Srgb<f32>) -> f64 {
+    let linearize = |c: f32| {
+        let c = c as f64;
+        c.signum() * c.abs().powf(2.4)
+    };
+    let y = (COEF_R * linearize(color.red)
+        + COEF_G * linearize(color.green)
+        + COEF_B * linearize(color.blue))
+    .max(0.0);
+
+    // Low-luminance soft clamp
+    if y < LOW_Y_THRESHOLD {
+        y + (LOW_Y_THRESHOLD - y).powf(LOW_Y_EXPONENT)
+    } else {
+        y
+    }
+}
+
 /// Calculate APCA contrast (Lc) between foreground and background colors.
 ///
 /// Returns the Lc value:
@@ -143,6 +165,69 @@ pub fn contrast_from_luminances(y_fg: f64, y_bg: f64) -> f64 {
     }
 }
 
+/// Font lookup sentinel: no text of this weight at this Lc.
+const PROHIBITED: f32 = 999.0;
+
+/// Font lookup sentinel: non-text elements (dividers, icons) only.
+const NON_TEXT: f32 = 777.0;
+
+/// APCA-W3 font lookup table: minimum font size (px) by |Lc| in steps of 5
+/// (rows, Lc 0-105) and font weight 100-900 (columns).
+#[rustfmt::skip]
+const FONT_LOOKUP: [[f32; 9]; 22] = [
+    [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0], // 0
+    [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0], // 5
+    [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0], // 10
+    [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0], // 15
+    [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0], // 20
+    [777.0, 777.0, 777.0, 120.0, 120.0, 108.0, 96.0, 96.0, 96.0],    // 25
+    [777.0, 777.0, 120.0, 108.0, 108.0, 96.0, 72.0, 72.0, 72.0],     // 30
+    [777.0, 120.0, 108.0, 96.0, 72.0, 60.0, 48.0, 48.0, 48.0],       // 35
+    [120.0, 108.0, 96.0, 60.0, 48.0, 42.0, 32.0, 32.0, 32.0],        // 40
+    [108.0, 96.0, 72.0, 42.0, 32.0, 28.0, 24.0, 24.0, 24.0],         // 45
+    [96.0, 72.0, 60.0, 32.0, 28.0, 24.0, 21.0, 21.0, 21.0],          // 50
+    [80.0, 60.0, 48.0, 28.0, 24.0, 21.0, 18.0, 18.0, 18.0],          // 55
+    [72.0, 48.0, 42.0, 24.0, 21.0, 18.0, 16.0, 16.0, 18.0],          // 60
+    [68.0, 46.0, 32.0, 21.75, 19.0, 17.0, 15.0, 16.0, 18.0],         // 65
+    [64.0, 44.0, 28.0, 19.5, 18.0, 16.0, 14.5, 16.0, 18.0],          // 70
+    [60.0, 42.0, 24.0, 18.0, 16.0, 15.0, 14.0, 16.0, 18.0],          // 75
+    [56.0, 38.25, 23.0, 17.25, 15.81, 14.81, 14.0, 16.0, 18.0],      // 80
+    [52.0, 34.5, 22.0, 16.5, 15.625, 14.625, 14.0, 16.0, 18.0],      // 85
+    [48.0, 32.0, 21.0, 16.0, 15.5, 14.5, 14.0, 16.0, 18.0],          // 90
+    [45.0, 28.0, 19.5, 15.5, 15.0, 14.0, 13.5, 16.0, 18.0],          // 95
+    [42.0, 26.5, 18.5, 15.0, 14.5, 13.5, 13.0, 16.0, 18.0],          // 100
+    [39.0, 25.0, 18.0, 14.5, 14.0, 13.0, 12.0, 16.0, 18.0],          // 105
+];
+
+/// Smallest font size (px) APCA allows for text of `weight` at `lc`, or
+/// `None` when `lc` is too low for text of that weight.
+///
+/// Lc is rounded down to the table's step of 5 and `weight` to the nearest
+/// hundred (clamped to 100-900); only the magnitude of `lc` counts.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::apca::min_font_size;
+///
+/// assert_eq!(min_font_size(-75.0, 400), Some(18.0));
+/// // Lc 62 reads as Lc 60
+/// assert_eq!(min_font_size(62.0, 700), Some(16.0));
+/// assert_eq!(min_font_size(20.0, 900), None);
+/// ```
+pub fn min_font_size(lc: f64, weight: u16) -> Option<f32> {
+    let row = ((lc.abs() / 5.0).floor() as usize).min(FONT_LOOKUP.len() - 1);
+    let column = (usize::from(weight.clamp(100, 900)) + 50) / 100 - 1;
+    let size = FONT_LOOKUP[row][column];
+    (size != PROHIBITED && size != NON_TEXT).then_some(size)
+}
+
+/// Whether `lc` is enough for non-text elements such as dividers and icons.
+pub fn allows_non_text(lc: f64) -> bool {
+    let row = ((lc.abs() / 5.0).floor() as usize).min(FONT_LOOKUP.len() - 1);
+    FONT_LOOKUP[row].iter().any(|size| *size != PROHIBITED)
+}
+
 /// APCA contrast thresholds for different use cases.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Threshold {
@@ -165,4 +250,35 @@ pub mod thresholds {
         min_lc: 60.0,
         description: "Content text",
     };
+
+    /// Large or bold text (headlines, status bar labels) - Lc 45
+    pub const LARGE_TEXT: Threshold = Threshold {
+        min_lc: 45.0,
+        description: "Large text",
+    };
+
+    /// Spot-readable text (placeholders, selected text) - Lc 30
+    pub const SPOT_TEXT: Threshold = Threshold {
+        min_lc: 30.0,
+        description: "Spot-readable text",
+    };
+
+    /// Every threshold, most demanding first.
+    pub const ALL: [Threshold; 4] = [BODY_TEXT_MIN, CONTENT_TEXT, LARGE_TEXT, SPOT_TEXT];
+}
+
+/// The most demanding threshold `lc` meets, or `None` below spot-readable
+/// text. Only the magnitude counts, so polarity doesn't matter.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::apca::{text_usage, thresholds};
+///
+/// assert_eq!(text_usage(-80.0), Some(thresholds::BODY_TEXT_MIN));
+/// assert_eq!(text_usage(50.0), Some(thresholds::LARGE_TEXT));
+/// assert_eq!(text_usage(12.0), None);
+/// ```
+pub fn text_usage(lc: f64) -> Option<Threshold> {
+    thresholds::ALL.into_iter().find(|t| lc.abs() >= t.min_lc)
 }
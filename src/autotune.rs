@@ -0,0 +1,174 @@
+//! Contrast-target autotuning ("find max feasible contrast").
+//!
+//! Raising `min_contrast` eventually pushes accents out of their J'/M box or
+//! out of gamut, and where that happens depends on the background, the
+//! targets, and the gamut. `max_feasible_contrast` bisects for the highest
+//! base and extended contrast floors that every accent still meets within
+//! its bounds.
+
+use tinted_builder::SchemeVariant;
+use tracing::debug;
+
+use crate::generate::{GenerateConfig, GenerationResult, generate_for_variant};
+use crate::interpolation::AccentResult;
+use crate::linked::generate_linked;
+
+/// Width of the final bisection bracket, in Lc.
+pub const CONTRAST_TOLERANCE: f64 = 0.5;
+
+/// Upper end of the search (APCA tops out around Lc 106-108).
+const MAX_LC: f64 = 108.0;
+
+/// Highest contrast floors the accents can meet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContrastCeiling {
+    /// Highest feasible `min_contrast` (base08-base0F), or `None` if the
+    /// accents leave their bounds even at Lc 0
+    pub min_contrast: Option<f64>,
+    /// Highest feasible `extended_min_contrast` (base10-base17), searched
+    /// with `min_contrast` already raised
+    pub extended_min_contrast: Option<f64>,
+}
+
+impl ContrastCeiling {
+    /// `config` with both floors set to their ceilings (unchanged where no
+    /// floor is feasible).
+    pub fn apply(&self, config: &GenerateConfig) -> GenerateConfig {
+        GenerateConfig {
+            min_contrast: self.min_contrast.unwrap_or(config.min_contrast),
+            extended_min_contrast: self
+                .extended_min_contrast
+                .unwrap_or(config.extended_min_contrast),
+            ..config.clone()
+        }
+    }
+}
+
+/// Find the highest `min_contrast` and `extended_min_contrast` for which
+/// every accent meets the floor with its J'/M (after gamut mapping) inside
+/// the configured bounds, for each of `variants` (`None` for auto).
+///
+/// Pinned accents are skipped, since the floor cannot move them. Values are
+/// within `CONTRAST_TOLERANCE` of the true ceiling, rounded down to 0.1.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::autotune::max_feasible_contrast;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let config = GenerateConfig::default();
+/// let ceiling = max_feasible_contrast(&config, &[None]);
+/// let min_contrast = ceiling.min_contrast.unwrap();
+///
+/// let tuned = generate(&ceiling.apply(&config));
+/// assert!(tuned.base_accent_results.iter().all(|r| r.met_minimum));
+/// assert!(min_contrast > 30.0);
+/// ```
+pub fn max_feasible_contrast(
+    config: &GenerateConfig,
+    variants: &[Option<SchemeVariant>],
+) -> ContrastCeiling {
+    ceiling(config, |config| {
+        variants
+            .iter()
+            .map(|variant| generate_for_variant(config, variant.clone()))
+            .collect()
+    })
+}
+
+/// Like `max_feasible_contrast`, for a pair generated by `generate_linked`:
+/// the floors hold for both variants, each within its own (mirrored) bounds.
+pub fn max_feasible_linked_contrast(config: &GenerateConfig) -> ContrastCeiling {
+    ceiling(config, |config| {
+        let linked = generate_linked(config);
+        vec![linked.dark, linked.light]
+    })
+}
+
+/// Bisect both floors, accepting one when every result of `generate` stays
+/// within bounds.
+fn ceiling(
+    config: &GenerateConfig,
+    generate: impl Fn(&GenerateConfig) -> Vec<GenerationResult>,
+) -> ContrastCeiling {
+    let feasible = |config: &GenerateConfig, extended: bool| {
+        generate(config).iter().all(|result| {
+            if extended {
+                within_bounds(&result.extended_accent_results, config, |i| {
+                    format!("base1{i:X}")
+                })
+            } else {
+                within_bounds(&result.base_accent_results, config, |i| {
+                    format!("base0{:X}", 8 + i)
+                })
+            }
+        })
+    };
+
+    let min_contrast = bisect(|lc| {
+        feasible(
+            &GenerateConfig {
+                min_contrast: lc,
+                ..config.clone()
+            },
+            false,
+        )
+    });
+    let base = GenerateConfig {
+        min_contrast: min_contrast.unwrap_or(config.min_contrast),
+        ..config.clone()
+    };
+    let extended_min_contrast = bisect(|lc| {
+        feasible(
+            &GenerateConfig {
+                extended_min_contrast: lc,
+                ..base.clone()
+            },
+            true,
+        )
+    });
+
+    ContrastCeiling {
+        min_contrast,
+        extended_min_contrast,
+    }
+}
+
+/// Whether every unpinned accent met its floor without leaving its bounds.
+///
+/// Gamut mapping alone is fine (saturated reds are mapped at any contrast);
+/// it only counts once it pulls M below the lower bound.
+fn within_bounds(
+    results: &[AccentResult],
+    config: &GenerateConfig,
+    slot: impl Fn(usize) -> String,
+) -> bool {
+    results
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !config.pins.contains_key(&slot(*i)))
+        .all(|(_, r)| r.met_minimum && r.m_in_bounds)
+}
+
+/// Highest Lc in [0, `MAX_LC`] where `feasible` holds, assuming it holds
+/// below some threshold and fails above it.
+fn bisect(mut feasible: impl FnMut(f64) -> bool) -> Option<f64> {
+    if !feasible(0.0) {
+        return None;
+    }
+    if feasible(MAX_LC) {
+        return Some(MAX_LC);
+    }
+    let (mut lo, mut hi) = (0.0, MAX_LC);
+    while hi - lo > CONTRAST_TOLERANCE {
+        let mid = (lo + hi) / 2.0;
+        if feasible(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        debug!(lo, hi, "contrast bisection");
+    }
+    Some((lo * 10.0).floor() / 10.0)
+}
@@ -0,0 +1,338 @@
+//! Batch generation of many schemes from a single manifest file.
+//!
+//! A manifest lists named configurations using the same layout as a
+//! `ThemeConfig` TOML file. Each entry is layered over optional shared
+//! defaults: `ThemeConfig defaults < [defaults] < [[scheme]] entry`.
+//!
+//! ```toml
+//! output_dir = "themes"
+//!
+//! [defaults.contrast]
+//! minimum = 70.0
+//!
+//! [[scheme]]
+//! theme.name = "Gruvbox Dark"
+//! colors.background = "#1d2021"
+//! colors.foreground = "#ebdbb2"
+//!
+//! [[scheme]]
+//! theme.name = "Gruvbox Light"
+//! theme.variant = "light"
+//! colors.background = "#fbf1c7"
+//! colors.foreground = "#3c3836"
+//! colors.hue_overrides.base08 = 30.0
+//! ```
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::{Result, WrapErr, bail, eyre};
+use figment::Figment;
+use figment::providers::Serialized;
+use serde::{Deserialize, Serialize};
+use tinted_builder::{Base16Scheme, SchemeVariant};
+use tracing::info;
+
+use crate::ansi::AnsiMap;
+use crate::cli::OutputFormat;
+use crate::config::{ThemeConfig, validate_config};
+use crate::generate::generate_for_variant;
+use crate::jobs::{millis, run_jobs};
+use crate::metadata;
+use crate::roles::{SemanticRoles, derive_roles};
+use crate::surfaces::SurfaceRamp;
+use crate::validation::{terminal_attribute_warnings, validation_warnings};
+
+/// Parsed batch manifest.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BatchManifest {
+    /// Directory to write schemes into (relative to the manifest file)
+    pub output_dir: Option<PathBuf>,
+    /// Settings shared by every scheme (same layout as a config file)
+    pub defaults: toml::Table,
+    /// Per-scheme settings (same layout as a config file)
+    pub scheme: Vec<toml::Table>,
+}
+
+impl BatchManifest {
+    /// Load a manifest from a TOML file.
+    ///
+    /// A relative `output_dir` is resolved against the manifest's directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        let mut manifest: Self = toml::from_str(&content)
+            .wrap_err_with(|| format!("Failed to parse manifest {}", path.display()))?;
+
+        if let Some(ref dir) = manifest.output_dir
+            && dir.is_relative()
+        {
+            let base = path.parent().unwrap_or(Path::new(""));
+            manifest.output_dir = Some(base.join(dir));
+        }
+
+        Ok(manifest)
+    }
+
+    /// Resolve every entry into a full `ThemeConfig`.
+    ///
+    /// Entries are validated individually; the first invalid entry aborts.
+    pub fn resolve(&self) -> Result<Vec<ThemeConfig>> {
+        self.scheme
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let config: ThemeConfig = Figment::new()
+                    .merge(Serialized::defaults(ThemeConfig::default()))
+                    .merge(Serialized::defaults(&self.defaults))
+                    .merge(Serialized::defaults(entry))
+                    .extract()
+                    .map_err(|e| eyre!("scheme #{}: {}", i + 1, e))?;
+                validate_config(&config)
+                    .map_err(|e| eyre!("scheme #{} ({}): {}", i + 1, config.theme.name, e))?;
+                Ok(config)
+            })
+            .collect()
+    }
+}
+
+/// Outcome of generating one manifest entry.
+#[derive(Debug)]
+pub struct BatchOutcome {
+    /// The generated scheme
+    pub scheme: Base16Scheme,
+    /// Where the scheme was written
+    pub path: PathBuf,
+    /// Generation and validation warnings
+    pub warnings: Vec<String>,
+    /// Time spent generating and rendering the scheme
+    pub elapsed: Duration,
+}
+
+/// A generated scheme rendered in its output format and awaiting the
+/// duplicate slug check, with its warnings and start time.
+struct RenderedScheme {
+    scheme: Base16Scheme,
+    content: String,
+    warnings: Vec<String>,
+    started: Instant,
+}
+
+/// Map a `theme.variant` hint onto a forced variant.
+pub(crate) fn forced_variant(hint: Option<&str>) -> Result<Option<SchemeVariant>> {
+    match hint.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("auto") => Ok(None),
+        Some("dark") => Ok(Some(SchemeVariant::Dark)),
+        Some("light") => Ok(Some(SchemeVariant::Light)),
+        Some(other) => bail!(
+            "Unknown variant '{}' (expected dark, light, or auto)",
+            other
+        ),
+    }
+}
+
+/// Progress event emitted while a batch runs (see `run_batch_with_progress`).
+///
+/// Serializes as one JSON object per event, tagged by `event`, e.g.
+/// `{"event":"finished","index":0,"total":2,"scheme":"Gruvbox Dark",...}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum BatchEvent {
+    /// Generation of an entry began
+    Started {
+        index: usize,
+        total: usize,
+        scheme: String,
+    },
+    /// An entry was generated and written
+    Finished {
+        index: usize,
+        total: usize,
+        scheme: String,
+        path: PathBuf,
+        warnings: usize,
+        duration_ms: u64,
+    },
+    /// An entry could not be generated or written
+    Failed {
+        index: usize,
+        total: usize,
+        scheme: String,
+        error: String,
+        duration_ms: u64,
+    },
+}
+
+/// Generate every scheme in the manifest in parallel and write them to `output_dir`.
+///
+/// Entries run as [`crate::jobs`], each rendered to text on its worker, so
+/// only the finished output waits for the rest. Files are named
+/// `<slug>.<ext>`. Fails before writing anything if two
+/// entries would produce the same slug. Metadata issues are reported as
+/// warnings, or normalized first when `fix_metadata` is set. Variables in
+/// CSS, SCSS, and Tailwind output are named with `prefix`.
+pub fn run_batch(
+    manifest: &BatchManifest,
+    output_dir: &Path,
+    format: OutputFormat,
+    prefix: &str,
+    fix_metadata: bool,
+) -> Result<Vec<BatchOutcome>> {
+    run_batch_with_progress(manifest, output_dir, format, prefix, fix_metadata, &|_| {})
+}
+
+/// `run_batch`, reporting each entry's progress to `progress`.
+///
+/// `progress` is called from worker threads as entries start, and again
+/// when each is written or fails.
+pub fn run_batch_with_progress(
+    manifest: &BatchManifest,
+    output_dir: &Path,
+    format: OutputFormat,
+    prefix: &str,
+    fix_metadata: bool,
+    progress: &(dyn Fn(&BatchEvent) + Sync),
+) -> Result<Vec<BatchOutcome>> {
+    let configs = manifest.resolve()?;
+    let total = configs.len();
+    let failed = |index: usize, scheme: &str, error: &color_eyre::Report, started: Instant| {
+        progress(&BatchEvent::Failed {
+            index,
+            total,
+            scheme: scheme.to_string(),
+            error: format!("{error:#}"),
+            duration_ms: millis(started.elapsed()),
+        });
+    };
+
+    let mut rendered: Vec<Option<(RenderedScheme, Duration)>> =
+        std::iter::repeat_with(|| None).take(total).collect();
+    run_jobs(
+        configs,
+        |index, theme_config| {
+            let started = Instant::now();
+            let name = &theme_config.theme.name;
+            progress(&BatchEvent::Started {
+                index,
+                total,
+                scheme: name.clone(),
+            });
+            render_entry(&theme_config, format, prefix, fix_metadata, started)
+                .inspect_err(|e| failed(index, name, e, started))
+        },
+        |finished| -> Result<()> {
+            rendered[finished.index] = Some((finished.output?, finished.elapsed));
+            Ok(())
+        },
+    )?;
+    let rendered: Vec<(RenderedScheme, Duration)> = rendered.into_iter().flatten().collect();
+
+    let mut seen = HashSet::new();
+    for (entry, _) in &rendered {
+        if !seen.insert(entry.scheme.slug.as_str()) {
+            bail!("Duplicate scheme slug '{}' in manifest", entry.scheme.slug);
+        }
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .wrap_err_with(|| format!("Failed to create {}", output_dir.display()))?;
+
+    let outcomes = rendered
+        .into_iter()
+        .enumerate()
+        .map(|(index, (entry, elapsed))| {
+            let RenderedScheme {
+                scheme,
+                content,
+                warnings,
+                started,
+            } = entry;
+            let path = output_dir.join(format!("{}.{}", scheme.slug, format.extension()));
+            std::fs::write(&path, content)
+                .wrap_err_with(|| format!("Failed to write to {}", path.display()))
+                .inspect_err(|e| failed(index, &scheme.name, e, started))?;
+            info!(path = %path.display(), "wrote scheme");
+            progress(&BatchEvent::Finished {
+                index,
+                total,
+                scheme: scheme.name.clone(),
+                path: path.clone(),
+                warnings: warnings.len(),
+                duration_ms: millis(started.elapsed()),
+            });
+            Ok(BatchOutcome {
+                scheme,
+                path,
+                warnings,
+                elapsed,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    info!(count = outcomes.len(), "batch complete");
+    Ok(outcomes)
+}
+
+/// Generate, validate, and render one manifest entry.
+fn render_entry(
+    theme_config: &ThemeConfig,
+    format: OutputFormat,
+    prefix: &str,
+    fix_metadata: bool,
+    started: Instant,
+) -> Result<RenderedScheme> {
+    let name = &theme_config.theme.name;
+    let config = theme_config
+        .to_generate_config()
+        .map_err(|e| eyre!("{}: {}", name, e))?;
+    let variant =
+        forced_variant(theme_config.theme.variant.as_deref()).wrap_err_with(|| name.clone())?;
+
+    let mut result = generate_for_variant(&config, variant);
+    if fix_metadata {
+        metadata::fix_metadata(&mut result.scheme);
+    }
+    let mut warnings = result.warnings;
+    warnings.extend(
+        metadata::check_generated_metadata(&result.scheme, fix_metadata || config.author.is_some())
+            .iter()
+            .map(|issue| format!("metadata: {issue}")),
+    );
+    warnings.extend(validation_warnings(
+        &result.scheme,
+        theme_config.contrast.standard,
+        &theme_config.validation,
+    ));
+    if let Some(attrs) = theme_config.contrast.terminal {
+        warnings.extend(terminal_attribute_warnings(&result.scheme, &attrs));
+    }
+    let roles = theme_config
+        .roles
+        .as_ref()
+        .map(|c| derive_roles(&result.scheme, c));
+    warnings.extend(roles.iter().flat_map(SemanticRoles::warnings));
+    let ansi = theme_config
+        .ansi
+        .is_some()
+        .then(|| AnsiMap::new(&result.scheme));
+    let surfaces = theme_config
+        .surfaces
+        .as_ref()
+        .map(|c| SurfaceRamp::new(&result.scheme, c.steps));
+    let content = format.serialize_prefixed(
+        &result.scheme,
+        Some(&result.wide_palette),
+        roles.as_ref(),
+        ansi.as_ref(),
+        surfaces.as_ref(),
+        prefix,
+    )?;
+    Ok(RenderedScheme {
+        scheme: result.scheme,
+        content,
+        warnings,
+        started,
+    })
+}
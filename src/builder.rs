@@ -0,0 +1,388 @@
+//! Fluent builder API for embedding the generator in other programs.
+//!
+//! `ThemeBuilder` wraps `GenerateConfig`, runs generation and validation in
+//! one call, and returns a `GeneratedTheme` with typed palette accessors, so
+//! callers do not need to know about the individual pipeline modules.
+//!
+//! # Example
+//!
+//! ```
+//! use themalingadingdong::builder::ThemeBuilder;
+//!
+//! let theme = ThemeBuilder::new()
+//!     .name("Midnight")
+//!     .background("#1a1a2e")
+//!     .foreground("#eaeaea")
+//!     .min_contrast(75.0)
+//!     .hue_override(0, 0.0)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(theme.hex("base00").as_deref(), Some("1a1a2e"));
+//! assert_eq!(theme.accents().len(), 8);
+//! assert!(theme.is_dark());
+//! ```
+
+use std::sync::Arc;
+
+use palette::Srgb;
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+use crate::ansi::AnsiConfig;
+use crate::config::{AccentOptSettings, CommentContrast, ConfigError};
+use crate::contrast::ContrastModel;
+use crate::curves::InterpolationConfig;
+use crate::gamut_map::Gamut;
+use crate::generate::{GenerateConfig, generate_for_variant, parse_color};
+use crate::hue_spacing::HueSpacing;
+use crate::interpolation::srgb_to_hex;
+use crate::ramp::LightnessRamp;
+use crate::roles::is_slot;
+use crate::validation::{
+    ContrastStandard, ValidationConfig, ValidationResults, validate_with_model,
+};
+use crate::viewing::ViewingConditions;
+use crate::wide_gamut::WidePalette;
+
+/// Fluent builder for generating a validated theme.
+///
+/// Setters never fail; invalid input (unparseable colors, out-of-range hue
+/// indices) is reported by `build`.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeBuilder {
+    config: GenerateConfig,
+    background: Option<String>,
+    foreground: Option<String>,
+    hue_overrides: Vec<(usize, Option<f32>)>,
+    pins: Vec<(String, String)>,
+    variant: Option<SchemeVariant>,
+    standard: ContrastStandard,
+    thresholds: ValidationConfig,
+}
+
+impl ThemeBuilder {
+    /// Start from the `GenerateConfig` defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from an existing generator configuration.
+    pub fn from_config(config: GenerateConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Background color (base00) in any CSS format.
+    pub fn background(mut self, color: impl Into<String>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    /// Foreground color (base07) in any CSS format.
+    pub fn foreground(mut self, color: impl Into<String>) -> Self {
+        self.foreground = Some(color.into());
+        self
+    }
+
+    /// Scheme name.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.config.name = name.into();
+        self
+    }
+
+    /// Scheme author.
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.config.author = Some(author.into());
+        self
+    }
+
+    /// Scheme description, in place of the generated summary.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.config.description = Some(description.into());
+        self
+    }
+
+    /// Space the accent hues evenly by perceived difference, moving each at
+    /// most `max_shift` degrees from its default or override.
+    pub fn perceptual_hues(mut self, max_shift: f32) -> Self {
+        self.config.hue_spacing = Some(HueSpacing { max_shift });
+        self
+    }
+
+    /// Minimum APCA contrast for base08-base0F (Lc).
+    pub fn min_contrast(mut self, lc: f64) -> Self {
+        self.config.min_contrast = lc;
+        self
+    }
+
+    /// Extend the UI ramp `j` J' past the foreground (base06/base07 beyond it).
+    pub fn foreground_headroom(mut self, j: f32) -> Self {
+        self.config.foreground_headroom = j;
+        self
+    }
+
+    /// Minimum APCA contrast for base10-base17 (Lc).
+    pub fn extended_min_contrast(mut self, lc: f64) -> Self {
+        self.config.extended_min_contrast = lc;
+        self
+    }
+
+    /// Maximum per-hue lightness adjustment (J' units).
+    pub fn max_lightness_adjustment(mut self, j: f32) -> Self {
+        self.config.max_lightness_adjustment = j;
+        self
+    }
+
+    /// Pin base03 (comments) to an APCA range against base00.
+    pub fn comment_contrast(mut self, min: f64, max: f64) -> Self {
+        self.config.comment_contrast = Some(CommentContrast { min, max });
+        self
+    }
+
+    /// Target APCA contrast of base01-base06 against base00 (see `ramp`).
+    pub fn ramp(mut self, ramp: LightnessRamp) -> Self {
+        self.config.ramp = ramp;
+        self
+    }
+
+    /// Override the hue of accent `index` (0 = base08 ... 7 = base0F), in degrees.
+    pub fn hue_override(mut self, index: usize, hue: f32) -> Self {
+        self.hue_overrides.push((index, Some(hue)));
+        self
+    }
+
+    /// Restore the default hue of accent `index`.
+    pub fn clear_hue_override(mut self, index: usize) -> Self {
+        self.hue_overrides.push((index, None));
+        self
+    }
+
+    /// Hold `slot` (e.g. `"base0D"`) at exactly `color` (any CSS format).
+    pub fn pin(mut self, slot: impl Into<String>, color: impl Into<String>) -> Self {
+        self.pins.push((slot.into(), color.into()));
+        self
+    }
+
+    /// Interpolation curves for the UI ramp.
+    pub fn interpolation(mut self, interpolation: InterpolationConfig) -> Self {
+        self.config.interpolation = interpolation;
+        self
+    }
+
+    /// Optimization settings for base08-base0F.
+    pub fn accent_opt(mut self, settings: AccentOptSettings) -> Self {
+        self.config.accent_opt = settings;
+        self
+    }
+
+    /// Optimization settings for base10-base17.
+    pub fn extended_accent_opt(mut self, settings: AccentOptSettings) -> Self {
+        self.config.extended_accent_opt = settings;
+        self
+    }
+
+    /// Gamut to optimize accents for (see `GeneratedTheme::wide_palette`).
+    pub fn gamut(mut self, gamut: Gamut) -> Self {
+        self.config.gamut = gamut;
+        self
+    }
+
+    /// Viewing conditions to optimize for (see `crate::viewing`).
+    pub fn viewing(mut self, viewing: ViewingConditions) -> Self {
+        self.config.viewing = viewing;
+        self
+    }
+
+    /// Hue bands the accents must stay within (see `crate::ansi`).
+    pub fn ansi(mut self, ansi: AnsiConfig) -> Self {
+        self.config.ansi = ansi;
+        self
+    }
+
+    /// Force a dark or light variant instead of detecting it from the background.
+    pub fn variant(mut self, variant: SchemeVariant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+
+    /// Measure accent contrast and validate with `model` instead of APCA
+    /// (see `crate::contrast`); contrast floors and validation thresholds
+    /// are then in its units.
+    pub fn contrast_model(mut self, model: impl ContrastModel + 'static) -> Self {
+        self.config.contrast_model = Arc::new(model);
+        self
+    }
+
+    /// Contrast standard used to judge validation results.
+    pub fn contrast_standard(mut self, standard: ContrastStandard) -> Self {
+        self.standard = standard;
+        self
+    }
+
+    /// Accent thresholds per background and polarity used for validation.
+    pub fn validation_thresholds(mut self, thresholds: ValidationConfig) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Resolve the generator configuration without generating.
+    pub fn to_config(&self) -> Result<GenerateConfig, ConfigError> {
+        let mut config = self.config.clone();
+        if let Some(ref bg) = self.background {
+            config.background = parse_color(bg).map_err(ConfigError::InvalidColor)?;
+        }
+        if let Some(ref fg) = self.foreground {
+            config.foreground = parse_color(fg).map_err(ConfigError::InvalidColor)?;
+        }
+        for &(index, hue) in &self.hue_overrides {
+            let slot = config.hue_overrides.get_mut(index).ok_or_else(|| {
+                ConfigError::InvalidValue(format!("hue override index {index} out of range (0-7)"))
+            })?;
+            *slot = hue;
+        }
+        for (slot, color) in &self.pins {
+            if !is_slot(slot) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "pin: '{slot}' is not a slot (expected base00-base17)"
+                )));
+            }
+            let color = parse_color(color).map_err(ConfigError::InvalidColor)?;
+            config.pins.insert(slot.clone(), color);
+        }
+        if let Some(ref comment) = config.comment_contrast {
+            comment.validate()?;
+        }
+        if let Some(ref spacing) = config.hue_spacing {
+            spacing.validate()?;
+        }
+        config.ramp.validate()?;
+        config.viewing.validate()?;
+        config.ansi.validate()?;
+        Ok(config)
+    }
+
+    /// Generate and validate the theme.
+    pub fn build(&self) -> Result<GeneratedTheme, ConfigError> {
+        let config = self.to_config()?;
+        self.thresholds.validate()?;
+        let result = generate_for_variant(&config, self.variant.clone());
+        let validation = validate_with_model(
+            &result.scheme,
+            &result.base_accent_results,
+            &result.extended_accent_results,
+            self.standard,
+            &self.thresholds,
+            &*config.contrast_model,
+        );
+        Ok(GeneratedTheme {
+            scheme: result.scheme,
+            warnings: result.warnings,
+            wide_palette: result.wide_palette,
+            validation,
+            config,
+        })
+    }
+}
+
+/// A generated and validated theme.
+#[derive(Debug, Clone)]
+pub struct GeneratedTheme {
+    scheme: Base16Scheme,
+    warnings: Vec<String>,
+    wide_palette: WidePalette,
+    validation: ValidationResults,
+    config: GenerateConfig,
+}
+
+impl GeneratedTheme {
+    /// Color of a palette slot ("base00"-"base17", either hex case).
+    pub fn color(&self, slot: &str) -> Option<Srgb<u8>> {
+        let color = self.scheme.palette.get(slot).or_else(|| {
+            let digits = slot.get(4..)?;
+            self.scheme
+                .palette
+                .get(&format!("base{}", digits.to_uppercase()))
+        })?;
+        let (r, g, b) = color.rgb;
+        Some(Srgb::new(r, g, b))
+    }
+
+    /// Hex value of a palette slot (no `#`).
+    pub fn hex(&self, slot: &str) -> Option<String> {
+        self.color(slot).map(srgb_to_hex)
+    }
+
+    /// UI ramp base00-base07, background to foreground.
+    pub fn ui(&self) -> [Srgb<u8>; 8] {
+        self.slots(0)
+    }
+
+    /// Accents base08-base0F.
+    pub fn accents(&self) -> [Srgb<u8>; 8] {
+        self.slots(8)
+    }
+
+    /// Extended accents base10-base17.
+    pub fn extended_accents(&self) -> [Srgb<u8>; 8] {
+        self.slots(16)
+    }
+
+    /// Background color (base00) of the generated variant.
+    pub fn background(&self) -> Srgb<u8> {
+        self.ui()[0]
+    }
+
+    /// Foreground color (base07) of the generated variant.
+    pub fn foreground(&self) -> Srgb<u8> {
+        self.ui()[7]
+    }
+
+    /// Whether the generated variant is dark.
+    pub fn is_dark(&self) -> bool {
+        matches!(self.scheme.variant, SchemeVariant::Dark)
+    }
+
+    /// Whether every required check passes.
+    pub fn is_valid(&self) -> bool {
+        self.validation.passes()
+    }
+
+    /// Generation warnings (hues that missed their contrast floor).
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Every slot in the configured gamut; the hex palette is its sRGB fallback.
+    pub fn wide_palette(&self) -> &WidePalette {
+        &self.wide_palette
+    }
+
+    /// Contrast validation results.
+    pub fn validation(&self) -> &ValidationResults {
+        &self.validation
+    }
+
+    /// Configuration the theme was generated from.
+    pub fn config(&self) -> &GenerateConfig {
+        &self.config
+    }
+
+    /// The generated tinted-theming scheme.
+    pub fn scheme(&self) -> &Base16Scheme {
+        &self.scheme
+    }
+
+    /// Consume the theme, returning the scheme.
+    pub fn into_scheme(self) -> Base16Scheme {
+        self.scheme
+    }
+
+    fn slots(&self, start: usize) -> [Srgb<u8>; 8] {
+        std::array::from_fn(|i| {
+            self.color(&format!("base{:02X}", start + i))
+                .expect("generated schemes have every Base24 slot")
+        })
+    }
+}
@@ -2,10 +2,35 @@
 
 pub use crate::cli_args::*;
 
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde::Serialize;
+use tinted_builder::Base16Scheme;
+
+use crate::ansi::{AnsiConfig, AnsiMap};
 use crate::config::{
-    AccentOptSettings, ColorConfig, ContrastConfig, HueOverrides, ThemeConfig, ThemeMetadata,
+    AccentAnchor, AccentOptSettings, CommentContrast, ConfigOverrides, HueOverrides,
+    SolverSettings, TerminalAttributes, ThemeConfig,
 };
 use crate::curves::{CurveConfig, CurveType, InterpolationConfig};
+use crate::daemon::DaemonConfig;
+use crate::display_sim::DisplaySimulation;
+use crate::extended_accents::ExtendedAccentMode;
+use crate::gamut_map::Gamut;
+use crate::harmony::{Harmony, HarmonyConfig};
+use crate::hue_spacing::HueSpacing;
+use crate::merge::Side;
+use crate::roles::{RolesConfig, SemanticRoles, roles_css};
+use crate::surfaces::{SurfaceRamp, SurfacesConfig};
+use crate::term_color::ColorDepth;
+use crate::validation::ContrastStandard;
+use crate::viewing::{Surround, WhitePoint};
+use crate::web_vars::{to_scss, to_tailwind};
+use crate::wide_gamut::{WidePalette, to_css};
+use crate::xterm256::Quantization;
+use crate::yaml_patch::patch_scheme_yaml;
 
 impl From<CurveTypeArg> for CurveType {
     fn from(arg: CurveTypeArg) -> Self {
@@ -20,6 +45,225 @@ impl From<CurveTypeArg> for CurveType {
     }
 }
 
+impl From<ContrastStandardArg> for ContrastStandard {
+    fn from(arg: ContrastStandardArg) -> Self {
+        match arg {
+            ContrastStandardArg::Apca => ContrastStandard::Apca,
+            ContrastStandardArg::Wcag => ContrastStandard::Wcag,
+            ContrastStandardArg::Both => ContrastStandard::Both,
+        }
+    }
+}
+
+impl From<ExtendedAccentsArg> for ExtendedAccentMode {
+    fn from(arg: ExtendedAccentsArg) -> Self {
+        match arg {
+            ExtendedAccentsArg::Independent => ExtendedAccentMode::Independent,
+            ExtendedAccentsArg::Darker => ExtendedAccentMode::Darker,
+            ExtendedAccentsArg::Desaturated => ExtendedAccentMode::Desaturated,
+            ExtendedAccentsArg::Paired => ExtendedAccentMode::Paired,
+        }
+    }
+}
+
+impl From<HarmonyArg> for Harmony {
+    fn from(arg: HarmonyArg) -> Self {
+        match arg {
+            HarmonyArg::Analogous => Harmony::Analogous,
+            HarmonyArg::Complementary => Harmony::Complementary,
+            HarmonyArg::SplitComplementary => Harmony::SplitComplementary,
+            HarmonyArg::Triadic => Harmony::Triadic,
+            HarmonyArg::Tetradic => Harmony::Tetradic,
+        }
+    }
+}
+
+impl From<GamutArg> for Gamut {
+    fn from(arg: GamutArg) -> Self {
+        match arg {
+            GamutArg::Srgb => Gamut::Srgb,
+            GamutArg::DisplayP3 => Gamut::DisplayP3,
+            GamutArg::Rec2020 => Gamut::Rec2020,
+        }
+    }
+}
+
+impl From<SimulateArg> for DisplaySimulation {
+    fn from(arg: SimulateArg) -> Self {
+        match arg {
+            SimulateArg::Dim => DisplaySimulation::Dim,
+            SimulateArg::Bright => DisplaySimulation::Bright,
+            SimulateArg::SrgbGamma24 => DisplaySimulation::SrgbGamma24,
+        }
+    }
+}
+
+impl From<MergeSideArg> for Side {
+    fn from(arg: MergeSideArg) -> Self {
+        match arg {
+            MergeSideArg::A => Side::A,
+            MergeSideArg::B => Side::B,
+        }
+    }
+}
+
+impl From<QuantizationArg> for Quantization {
+    fn from(arg: QuantizationArg) -> Self {
+        match arg {
+            QuantizationArg::Nearest => Quantization::Nearest,
+            QuantizationArg::Joint => Quantization::Joint,
+        }
+    }
+}
+
+impl From<ColorDepthArg> for ColorDepth {
+    fn from(arg: ColorDepthArg) -> Self {
+        match arg {
+            ColorDepthArg::Truecolor => ColorDepth::TrueColor,
+            ColorDepthArg::Ansi256 => ColorDepth::Ansi256,
+            ColorDepthArg::Ansi16 => ColorDepth::Ansi16,
+        }
+    }
+}
+
+impl From<SurroundArg> for Surround {
+    fn from(arg: SurroundArg) -> Self {
+        match arg {
+            SurroundArg::Dark => Surround::Dark,
+            SurroundArg::Dim => Surround::Dim,
+            SurroundArg::Average => Surround::Average,
+        }
+    }
+}
+
+impl From<WhitePointArg> for WhitePoint {
+    fn from(arg: WhitePointArg) -> Self {
+        match arg {
+            WhitePointArg::D65 => WhitePoint::D65,
+            WhitePointArg::D50 => WhitePoint::D50,
+            WhitePointArg::D55 => WhitePoint::D55,
+            WhitePointArg::D75 => WhitePoint::D75,
+            WhitePointArg::A => WhitePoint::A,
+            WhitePointArg::F2 => WhitePoint::F2,
+            WhitePointArg::F11 => WhitePoint::F11,
+        }
+    }
+}
+
+impl OutputFormat {
+    /// File extension for this format (without the leading dot).
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Json => "json",
+            OutputFormat::Css => "css",
+            OutputFormat::Scss => "scss",
+            OutputFormat::Tailwind => "js",
+        }
+    }
+
+    /// `scheme` as plain YAML edited into `source`, the YAML it was
+    /// imported from, keeping that file's comments and layout (see
+    /// [`patch_scheme_yaml`]). `None` for other formats, or when `source`
+    /// can't be edited and the scheme should be serialized instead.
+    pub fn patch_source(self, scheme: &Base16Scheme, source: &str) -> Option<String> {
+        match self {
+            OutputFormat::Yaml => patch_scheme_yaml(source, scheme),
+            _ => None,
+        }
+    }
+
+    /// Serialize a scheme in this format.
+    pub fn serialize(self, scheme: &Base16Scheme) -> Result<String> {
+        self.serialize_wide(scheme, None)
+    }
+
+    /// Serialize a scheme, including wide-gamut values where the format
+    /// supports them (CSS only; the other formats carry the sRGB hex values).
+    pub fn serialize_wide(
+        self,
+        scheme: &Base16Scheme,
+        wide: Option<&WidePalette>,
+    ) -> Result<String> {
+        self.serialize_with(scheme, wide, None, None, None)
+    }
+
+    /// Serialize a scheme with wide-gamut values and, when given, `roles`,
+    /// `ansi`, and `surfaces` sections (`:root` blocks of variables in CSS).
+    pub fn serialize_with(
+        self,
+        scheme: &Base16Scheme,
+        wide: Option<&WidePalette>,
+        roles: Option<&SemanticRoles>,
+        ansi: Option<&AnsiMap>,
+        surfaces: Option<&SurfaceRamp>,
+    ) -> Result<String> {
+        self.serialize_prefixed(scheme, wide, roles, ansi, surfaces, "")
+    }
+
+    /// `serialize_with`, naming the variables of CSS, SCSS, and Tailwind
+    /// output with `prefix` (`--var-prefix`; YAML and JSON ignore it).
+    pub fn serialize_prefixed(
+        self,
+        scheme: &Base16Scheme,
+        wide: Option<&WidePalette>,
+        roles: Option<&SemanticRoles>,
+        ansi: Option<&AnsiMap>,
+        surfaces: Option<&SurfaceRamp>,
+        prefix: &str,
+    ) -> Result<String> {
+        let with_sections = WithSections {
+            scheme,
+            roles,
+            ansi,
+            surfaces,
+        };
+        let plain = roles.is_none() && ansi.is_none() && surfaces.is_none();
+        match self {
+            OutputFormat::Yaml if plain => {
+                serde_yaml::to_string(scheme).wrap_err("Failed to serialize scheme to YAML")
+            }
+            OutputFormat::Yaml => {
+                serde_yaml::to_string(&with_sections).wrap_err("Failed to serialize scheme to YAML")
+            }
+            OutputFormat::Json if plain => {
+                serde_json::to_string_pretty(scheme).wrap_err("Failed to serialize scheme to JSON")
+            }
+            OutputFormat::Json => serde_json::to_string_pretty(&with_sections)
+                .wrap_err("Failed to serialize scheme to JSON"),
+            OutputFormat::Css => {
+                let mut css = to_css(scheme, wide, prefix);
+                if let Some(roles) = roles {
+                    css += &roles_css(roles, prefix);
+                }
+                if let Some(ansi) = ansi {
+                    css += &ansi.css(prefix);
+                }
+                if let Some(surfaces) = surfaces {
+                    css += &surfaces.css(prefix);
+                }
+                Ok(css)
+            }
+            OutputFormat::Scss => Ok(to_scss(scheme, roles, ansi, surfaces, prefix)),
+            OutputFormat::Tailwind => Ok(to_tailwind(scheme, roles, ansi, surfaces, prefix)),
+        }
+    }
+}
+
+/// A scheme followed by its semantic roles, ANSI mapping, and extended
+/// surface ramp, serialized as one document.
+#[derive(Serialize)]
+struct WithSections<'a> {
+    #[serde(flatten)]
+    scheme: &'a Base16Scheme,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<&'a SemanticRoles>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ansi: Option<&'a AnsiMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    surfaces: Option<&'a SurfaceRamp>,
+}
+
 impl Cli {
     /// Build the hue overrides array from CLI flags.
     pub fn hue_overrides(&self) -> [Option<f32>; 8] {
@@ -35,6 +279,102 @@ impl Cli {
         ]
     }
 
+    /// Build the comment contrast range from CLI flags.
+    ///
+    /// Returns `None` unless at least one bound is given; the other bound
+    /// falls back to the `CommentContrast` default.
+    pub fn comment_contrast(&self) -> Option<CommentContrast> {
+        if self.comment_min_contrast.is_none() && self.comment_max_contrast.is_none() {
+            return None;
+        }
+        let defaults = CommentContrast::default();
+        Some(CommentContrast {
+            min: self.comment_min_contrast.unwrap_or(defaults.min),
+            max: self.comment_max_contrast.unwrap_or(defaults.max),
+        })
+    }
+
+    /// Build the terminal attribute model from CLI flags.
+    ///
+    /// Returns `None` unless `--check-attributes` or a multiplier is given;
+    /// unset values fall back to the `TerminalAttributes` defaults.
+    pub fn terminal_attributes(&self) -> Option<TerminalAttributes> {
+        if !self.check_attributes && self.dim_multiplier.is_none() && self.bold_multiplier.is_none()
+        {
+            return None;
+        }
+        let defaults = TerminalAttributes::default();
+        Some(TerminalAttributes {
+            dim: self.dim_multiplier.unwrap_or(defaults.dim),
+            bold: self.bold_multiplier.unwrap_or(defaults.bold),
+            dim_min_contrast: defaults.dim_min_contrast,
+        })
+    }
+
+    /// Build the hue spacing settings from CLI flags.
+    ///
+    /// Returns `None` unless `--perceptual-hues` or `--hue-max-shift` is given.
+    pub fn hue_spacing(&self) -> Option<HueSpacing> {
+        if !self.perceptual_hues && self.hue_max_shift.is_none() {
+            return None;
+        }
+        Some(HueSpacing {
+            max_shift: self
+                .hue_max_shift
+                .unwrap_or(HueSpacing::default().max_shift),
+        })
+    }
+
+    /// Semantic roles to emit: the config's `[roles]` table, or the
+    /// defaults when only `--roles` is given.
+    pub fn roles_config(&self, config: &ThemeConfig) -> Option<RolesConfig> {
+        config
+            .roles
+            .clone()
+            .or_else(|| self.roles.then(RolesConfig::default))
+    }
+
+    /// ANSI hue bands to emit a mapping for: the config's `[ansi]` table,
+    /// or the defaults when only `--ansi-map` is given.
+    pub fn ansi_config(&self, config: &ThemeConfig) -> Option<AnsiConfig> {
+        config
+            .ansi
+            .clone()
+            .or_else(|| self.ansi_map.then(AnsiConfig::default))
+    }
+
+    /// Extended surface ramp to emit: `--surface-steps`, or else the config's
+    /// `[surfaces]` table.
+    pub fn surfaces_config(&self, config: &ThemeConfig) -> Option<SurfacesConfig> {
+        self.surface_steps
+            .map(|steps| SurfacesConfig {
+                steps: steps.into(),
+            })
+            .or_else(|| config.surfaces.clone())
+    }
+
+    /// Colors to draw previews with: `--color-depth`, or else the detected
+    /// depth when drawing to a terminal. Piped output keeps 24-bit colors,
+    /// since whatever shows it later is unknown.
+    pub fn color_depth(&self, is_terminal: bool) -> ColorDepth {
+        match self.color_depth {
+            Some(arg) => arg.into(),
+            None if is_terminal => ColorDepth::detect(),
+            None => ColorDepth::TrueColor,
+        }
+    }
+
+    /// Daemon outputs and terminals: the config's `[daemon]` table, with its
+    /// relative outputs resolved against `config_dir`, plus `--output` and
+    /// `--osc-tty`.
+    pub fn daemon_config(&self, config: &ThemeConfig, config_dir: &Path) -> DaemonConfig {
+        let mut daemon = config.daemon.clone().unwrap_or_default();
+        daemon.resolve_against(config_dir);
+        daemon.outputs.extend(self.output.clone());
+        daemon.terminals.extend(self.osc_tty.iter().cloned());
+        daemon
+    }
+
     /// Build InterpolationConfig from CLI arguments, using defaults where not specified.
     pub fn interpolation_config(&self) -> InterpolationConfig {
         let defaults = InterpolationConfig::default();
@@ -48,6 +388,7 @@ impl Cli {
                     .lightness_strength
                     .unwrap_or(defaults.lightness.strength),
                 control_points: None,
+                stops: None,
             },
             chroma: CurveConfig {
                 curve_type: self
@@ -56,6 +397,7 @@ impl Cli {
                     .unwrap_or(defaults.chroma.curve_type),
                 strength: defaults.chroma.strength,
                 control_points: None,
+                stops: None,
             },
             hue: CurveConfig {
                 curve_type: self
@@ -64,6 +406,7 @@ impl Cli {
                     .unwrap_or(defaults.hue.curve_type),
                 strength: defaults.hue.strength,
                 control_points: None,
+                stops: None,
             },
         }
     }
@@ -78,92 +421,107 @@ impl Cli {
             delta_m: self.delta_m.unwrap_or(defaults.delta_m),
             j_weight: self.j_weight.unwrap_or(defaults.j_weight),
             contrast_weight: self.contrast_weight.unwrap_or(defaults.contrast_weight),
+            preferred_contrast: None,
+            cvd: None,
+            anchors: self.anchors(false),
+            solver: SolverSettings::default(),
+            joint: None,
+            semantic: None,
         }
     }
 
-    /// Convert flat CLI args to nested ThemeConfig for Figment merging.
-    ///
-    /// Only fields that are explicitly set on the CLI will be included in the
-    /// returned config (via `skip_serializing_if`), allowing proper layering
-    /// with TOML file settings.
-    pub fn to_config_overrides(&self) -> ThemeConfig {
-        let defaults = ThemeConfig::default();
-
-        // Build hue overrides only if any hue is specified
-        let hue_overrides = {
-            let arr = self.hue_overrides();
-            if arr.iter().any(|h| h.is_some()) {
-                Some(HueOverrides::from_array(arr))
-            } else {
-                None
-            }
-        };
-
-        // Build interpolation config, using defaults for unspecified curves
-        let curves = InterpolationConfig {
-            lightness: CurveConfig {
-                curve_type: self
-                    .lightness_curve
-                    .map(Into::into)
-                    .unwrap_or(defaults.curves.lightness.curve_type),
-                strength: self
-                    .lightness_strength
-                    .unwrap_or(defaults.curves.lightness.strength),
-                control_points: None,
-            },
-            chroma: CurveConfig {
-                curve_type: self
-                    .chroma_curve
-                    .map(Into::into)
-                    .unwrap_or(defaults.curves.chroma.curve_type),
-                strength: defaults.curves.chroma.strength,
-                control_points: None,
-            },
-            hue: CurveConfig {
-                curve_type: self
-                    .hue_curve
-                    .map(Into::into)
-                    .unwrap_or(defaults.curves.hue.curve_type),
-                strength: defaults.curves.hue.strength,
-                control_points: None,
-            },
-        };
+    /// `--anchor` values for the extended accents (base1x slots) or for the
+    /// base accents (everything else, so bad slots fail validation there).
+    fn anchors(&self, extended: bool) -> BTreeMap<String, AccentAnchor> {
+        self.anchor
+            .iter()
+            .filter(|(slot, _)| slot.starts_with("base1") == extended)
+            .map(|(slot, color)| (slot.clone(), AccentAnchor::new(color)))
+            .collect()
+    }
 
-        // Build optimization settings, using defaults for unspecified values
-        let optimization = AccentOptSettings {
-            target_j: self.target_j.unwrap_or(defaults.optimization.target_j),
-            target_m: self.target_m.unwrap_or(defaults.optimization.target_m),
-            delta_j: self.delta_j.unwrap_or(defaults.optimization.delta_j),
-            delta_m: self.delta_m.unwrap_or(defaults.optimization.delta_m),
-            j_weight: self.j_weight.unwrap_or(defaults.optimization.j_weight),
-            contrast_weight: self
-                .contrast_weight
-                .unwrap_or(defaults.optimization.contrast_weight),
-        };
+    /// Collect the config values set explicitly on the CLI for Figment
+    /// merging.
+    ///
+    /// Flags that weren't given are left out, so they never mask the TOML
+    /// file or profile, while a given flag wins even at its default value.
+    pub fn to_config_overrides(&self) -> ConfigOverrides {
+        let hues = self.hue_overrides();
+        let hue_overrides = hues
+            .iter()
+            .any(Option::is_some)
+            .then(|| HueOverrides::from_array(hues));
+        let nonempty =
+            |anchors: BTreeMap<String, AccentAnchor>| (!anchors.is_empty()).then_some(anchors);
 
-        ThemeConfig {
-            theme: ThemeMetadata {
-                name: self.name.clone().unwrap_or_default(),
-                author: self.author.clone(),
-                variant: None,
-            },
-            colors: ColorConfig {
-                background: self.background.clone(),
-                foreground: self.foreground.clone(),
-                hue_overrides,
-            },
-            curves,
-            contrast: ContrastConfig {
-                minimum: self.min_contrast.unwrap_or(defaults.contrast.minimum),
-                extended_minimum: self
-                    .extended_min_contrast
-                    .unwrap_or(defaults.contrast.extended_minimum),
-                max_adjustment: self
-                    .max_lightness_adjustment
-                    .unwrap_or(defaults.contrast.max_adjustment),
-            },
-            extended_optimization: defaults.extended_optimization.clone(),
-            optimization,
-        }
+        let mut overrides = ConfigOverrides::default();
+        overrides
+            .set("theme.name", self.name.as_ref())
+            .set("theme.author", self.author.as_ref())
+            .set("theme.description", self.description.as_ref())
+            .set("colors.background", self.background.as_ref())
+            .set("colors.foreground", self.foreground.as_ref())
+            .set("colors.hue_overrides", hue_overrides)
+            .set("colors.foreground_headroom", self.foreground_headroom)
+            .set(
+                "colors.adaptive_hues",
+                self.no_adaptive_hues.then_some(false),
+            )
+            .set("colors.hue_spacing", self.hue_spacing())
+            .set("colors.gamut", self.gamut.map(Gamut::from))
+            .set(
+                "colors.pins",
+                (!self.pin.is_empty())
+                    .then(|| self.pin.iter().cloned().collect::<BTreeMap<_, _>>()),
+            )
+            .set(
+                "colors.harmony",
+                self.harmony.map(|rule| HarmonyConfig {
+                    rule: rule.into(),
+                    seed_hue: self.seed_hue,
+                }),
+            )
+            .set(
+                "curves.lightness.type",
+                self.lightness_curve.map(CurveType::from),
+            )
+            .set("curves.lightness.strength", self.lightness_strength)
+            .set("curves.chroma.type", self.chroma_curve.map(CurveType::from))
+            .set("curves.hue.type", self.hue_curve.map(CurveType::from))
+            .set("contrast.minimum", self.min_contrast)
+            .set("contrast.extended_minimum", self.extended_min_contrast)
+            .set("contrast.max_adjustment", self.max_lightness_adjustment)
+            .set("contrast.comment", self.comment_contrast())
+            .set("contrast.terminal", self.terminal_attributes())
+            .set(
+                "contrast.standard",
+                self.contrast_standard.map(ContrastStandard::from),
+            )
+            .set("optimization.target_j", self.target_j)
+            .set("optimization.target_m", self.target_m)
+            .set("optimization.delta_j", self.delta_j)
+            .set("optimization.delta_m", self.delta_m)
+            .set("optimization.j_weight", self.j_weight)
+            .set("optimization.contrast_weight", self.contrast_weight)
+            .set("optimization.anchors", nonempty(self.anchors(false)))
+            .set(
+                "extended_optimization.anchors",
+                nonempty(self.anchors(true)),
+            )
+            .set(
+                "extended_accents.mode",
+                self.extended_accents.map(ExtendedAccentMode::from),
+            )
+            .set("extended_accents.amount", self.extended_amount)
+            .set(
+                "viewing.white_point",
+                self.white_point.map(WhitePoint::from),
+            )
+            .set("viewing.adapting_luminance", self.adapting_luminance)
+            .set("viewing.surround", self.surround.map(Surround::from));
+        // `--roles` and `--ansi-map` must not replace a [roles] or [ansi]
+        // table, and `--surface-steps` only overrides [surfaces]; see
+        // `roles_config`, `ansi_config`, and `surfaces_config`
+        overrides
     }
 }
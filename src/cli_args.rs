@@ -7,11 +7,12 @@
 use std::path::PathBuf;
 
 use clap::builder::ArgPredicate;
-use clap::{Parser, ValueEnum};
-use serde::Serialize;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
 
 /// Output variant selection.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum VariantArg {
     /// Auto-detect from background luminance
     #[default]
@@ -22,16 +23,168 @@ pub enum VariantArg {
     Light,
     /// Generate both variants (requires --output)
     Both,
+    /// Generate both variants designed together: shared hues, mirrored
+    /// accent lightness (requires --output)
+    Linked,
 }
 
 /// Output format selection.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     /// YAML format (default, tinted-theming compatible)
     #[default]
     Yaml,
     /// JSON format (tinted-theming compatible)
     Json,
+    /// CSS custom properties, with `color()` values for wide gamuts
+    Css,
+    /// SCSS variables
+    Scss,
+    /// Tailwind config with the palette as theme colors
+    Tailwind,
+}
+
+/// CLI-compatible contrast standard enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum ContrastStandardArg {
+    /// APCA Lc thresholds
+    Apca,
+    /// WCAG 2.x contrast ratio (level AA)
+    Wcag,
+    /// Both APCA and WCAG 2.x
+    Both,
+}
+
+/// CLI-compatible gamut enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum GamutArg {
+    /// sRGB
+    Srgb,
+    /// Display-P3
+    DisplayP3,
+    /// Rec.2020
+    Rec2020,
+}
+
+/// CLI-compatible preview display simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum SimulateArg {
+    /// Display turned down in a dark room
+    Dim,
+    /// Glare from bright ambient light
+    Bright,
+    /// sRGB content on a pure gamma 2.4 display
+    #[value(name = "srgb-gamma-2.4")]
+    SrgbGamma24,
+}
+
+/// One of the schemes given to `merge`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum MergeSideArg {
+    /// The first scheme
+    A,
+    /// The second scheme
+    B,
+}
+
+/// CLI-compatible terminal color depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum ColorDepthArg {
+    /// 24-bit color
+    Truecolor,
+    /// The xterm 256-color palette
+    #[value(name = "256")]
+    Ansi256,
+    /// The 16 ANSI colors
+    #[value(name = "16")]
+    Ansi16,
+}
+
+/// CLI-compatible viewing surround.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum SurroundArg {
+    /// Dark room
+    Dark,
+    /// Dimly lit room
+    Dim,
+    /// Lit office or daylight
+    Average,
+}
+
+/// CLI-compatible adopted white point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum WhitePointArg {
+    /// Daylight, 6504 K
+    D65,
+    /// Horizon light, 5003 K
+    D50,
+    /// Mid-morning daylight, 5503 K
+    D55,
+    /// North sky daylight, 7504 K
+    D75,
+    /// Incandescent light, 2856 K
+    A,
+    /// Cool white fluorescent, 4230 K
+    F2,
+    /// Narrow-band white fluorescent, 4000 K
+    F11,
+}
+
+/// CLI-compatible extended accent derivation mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum ExtendedAccentsArg {
+    /// Optimize base10-base17 independently
+    Independent,
+    /// Darker variants of base08-base0F
+    Darker,
+    /// Desaturated variants of base08-base0F
+    Desaturated,
+    /// base08-base0F offset toward the background, solved jointly with them
+    Paired,
+}
+
+/// CLI-compatible color-harmony rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum HarmonyArg {
+    /// Neighbors of the seed hue
+    Analogous,
+    /// The seed hue and its opposite
+    Complementary,
+    /// The seed hue and the two hues beside its opposite
+    SplitComplementary,
+    /// Three hues 120° apart
+    Triadic,
+    /// Four hues 90° apart
+    Tetradic,
+}
+
+/// How to emit the xterm-256 approximation table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Xterm256Mode {
+    /// Append a comment block to the YAML output
+    Comment,
+    /// Write a separate `<output>.xterm256.json` file (requires --output)
+    Json,
+}
+
+/// CLI-compatible xterm-256 quantization.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum QuantizationArg {
+    /// Map each slot to its nearest index independently
+    #[default]
+    Nearest,
+    /// Pick indices for all slots together, preserving their luminance order
+    Joint,
+}
+
+/// Extra reports printed to stderr after generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportKind {
+    /// APCA contrast between every pair of slots, marking validated pairs below threshold
+    ContrastMatrix,
+    /// Standalone HTML accessibility report, written next to the output
+    Html,
 }
 
 /// CLI-compatible curve type enum.
@@ -52,35 +205,334 @@ pub enum CurveTypeArg {
     Sigmoid,
 }
 
+/// Standalone utilities that run instead of palette generation.
+/// Check a color argument: anything `csscolorparser` accepts, or a CSS
+/// `color()` function, whose components are checked when the config is built.
+fn css_color_arg(s: &str) -> Result<String, String> {
+    let trimmed = s.trim();
+    let is_color_function = trimmed
+        .get(..6)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("color("))
+        && trimmed.ends_with(')');
+    if is_color_function {
+        return Ok(s.to_string());
+    }
+    s.parse::<csscolorparser::Color>()
+        .map(|_| s.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Parse a `SLOT=COLOR` argument.
+fn slot_color_arg(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .filter(|(_, c)| css_color_arg(c).is_ok())
+        .map(|(slot, c)| (slot.to_string(), c.to_string()))
+        .ok_or_else(|| format!("expected SLOT=COLOR (e.g. base0D=#82aaff), got '{s}'"))
+}
+
+/// Check a variable-name prefix: letters, digits, `-`, and `_`.
+fn var_prefix_arg(s: &str) -> Result<String, String> {
+    if s.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(s.to_string())
+    } else {
+        Err(format!(
+            "expected letters, digits, '-', or '_' (e.g. theme-), got '{s}'"
+        ))
+    }
+}
+
+/// Parse a time of day as `HH:MM`.
+fn time_arg(s: &str) -> Result<String, String> {
+    let valid = s.split_once(':').is_some_and(|(h, m)| {
+        h.len() == 2
+            && m.len() == 2
+            && h.parse::<u8>().is_ok_and(|h| h < 24)
+            && m.parse::<u8>().is_ok_and(|m| m < 60)
+    });
+    if valid {
+        Ok(s.to_string())
+    } else {
+        Err(format!("expected a time as HH:MM (e.g. 18:30), got '{s}'"))
+    }
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Convert a color between sRGB hex, OKLCH, and HellwigJmh
+    Convert {
+        /// Color in any CSS format (hex, rgb(), oklch(), ...) or HellwigJmh as "jmh(J' M h)"
+        color: String,
+    },
+    /// Print the palette, sample output, and ANSI mapping with truecolor escapes
+    ///
+    /// Uses the same options as generation (or --input to preview an
+    /// existing scheme), e.g. `themalingadingdong -b "#1d2021" -f "#ebdbb2" preview`
+    Preview,
+    /// Render tinted-theming template repositories with the generated scheme
+    ///
+    /// Each directory needs a `templates/config.yaml`; outputs are written
+    /// inside it (or under --output-dir) as the config describes, e.g.
+    /// `themalingadingdong --config theme.toml render ~/src/base16-vim`
+    Render {
+        /// Template repository directories
+        #[arg(value_name = "DIR")]
+        templates: Vec<PathBuf>,
+        /// Also render every template repository matching this glob (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        template_glob: Vec<String>,
+        /// Write outputs under DIR/<repository name> instead of into each repository
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+    },
+    /// Regenerate a scheme written with --emit-provenance and check that
+    /// the output is byte-identical
+    Reproduce {
+        /// Scheme file with embedded provenance
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+    /// Score schemes on contrast margin, accent uniformity, hue spacing, and
+    /// colorblind separability, and print them ranked as JSON
+    ///
+    /// Scores the given scheme files, or the scheme the generation options
+    /// produce when none are given, e.g. `themalingadingdong score a.yaml b.yaml`
+    Score {
+        /// Scheme files to compare
+        #[arg(value_name = "FILE")]
+        files: Vec<PathBuf>,
+    },
+    /// Compare the accents of a dark and a light variant in HellwigJmh and
+    /// flag those whose hue or colorfulness drift apart
+    ///
+    /// Compares the two scheme files, or the dark and light variants the
+    /// generation options produce when none are given (linked with
+    /// `--variant linked`); thresholds come from `[validation.variants]`.
+    /// Exits with an error if any slot diverges, e.g.
+    /// `themalingadingdong match-variants night.yaml day.yaml`
+    MatchVariants {
+        /// Dark variant scheme file
+        #[arg(value_name = "DARK", requires = "light")]
+        dark: Option<PathBuf>,
+        /// Light variant scheme file
+        #[arg(value_name = "LIGHT")]
+        light: Option<PathBuf>,
+        /// Print the comparison as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Interpolate between two scheme files in HellwigJmh, for animated
+    /// theme transitions
+    ///
+    /// Writes one scheme per frame (in --format) into --output-dir, or a CSS
+    /// @keyframes animation with --keyframes, e.g.
+    /// `themalingadingdong animate night.yaml day.yaml --steps 8 --output-dir frames`
+    Animate {
+        /// Scheme to start from
+        #[arg(value_name = "FROM")]
+        from: PathBuf,
+        /// Scheme to end at
+        #[arg(value_name = "TO")]
+        to: PathBuf,
+        /// Number of frames, including both schemes
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u16).range(2..))]
+        steps: u16,
+        /// Directory for the frame files
+        #[arg(
+            long,
+            value_name = "DIR",
+            default_value = ".",
+            conflicts_with = "keyframes"
+        )]
+        output_dir: PathBuf,
+        /// Print a CSS @keyframes animation of the --baseXX properties instead
+        /// of writing frame files (to --output if given)
+        #[arg(long)]
+        keyframes: bool,
+    },
+    /// Generate palettes stepping from the light variant to the dark one,
+    /// for switching themes through the day
+    ///
+    /// Writes each step (in --format) and a `schedule.toml` manifest into
+    /// --output-dir, e.g.
+    /// `themalingadingdong --config theme.toml schedule --times 12:00,17:00,19:00,21:00`
+    Schedule {
+        /// Number of palettes, including the light and dark variants
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u16).range(2..))]
+        steps: u16,
+        /// Time of day each palette starts, lightest first (sets the number
+        /// of steps)
+        #[arg(
+            long,
+            value_name = "HH:MM",
+            value_delimiter = ',',
+            value_parser = time_arg,
+            conflicts_with = "steps"
+        )]
+        times: Vec<String>,
+        /// Directory for the palettes and manifest
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        output_dir: PathBuf,
+    },
+    /// Check scheme files against the tinted-theming schemes repository's
+    /// CI rules: required keys, palette slots, hex formats, name/author/
+    /// variant metadata, and a file name matching the slug
+    ///
+    /// Exits with an error if any file would be rejected, e.g.
+    /// `themalingadingdong publish-check base24/my-theme-dark.yaml`
+    PublishCheck {
+        /// Scheme files to check
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Lint scheme files for duplicate colors, flat or out-of-order
+    /// surfaces, accents outside their ANSI hue bands, and low contrast
+    ///
+    /// Prints each finding with its code and, where one exists, the color
+    /// that fixes it; exits with an error if any remain, e.g.
+    /// `themalingadingdong lint my-theme.yaml --fix`
+    Lint {
+        /// Scheme files to lint
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<PathBuf>,
+        /// Apply the fixes, rewriting each file (keeping the original as
+        /// FILE.bak) or printing the result with --dry-run
+        #[arg(long)]
+        fix: bool,
+        /// Print the findings as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compose a scheme from two others: surfaces from one, accents from the
+    /// other, plus single slots picked from either
+    ///
+    /// The result is revalidated, with a warning for every required pair the
+    /// merge broke. Prints it in --format (or writes --output), e.g.
+    /// `themalingadingdong merge gruvbox.yaml nord.yaml --pick base0D=a`
+    Merge {
+        /// Scheme A
+        #[arg(value_name = "A")]
+        a: PathBuf,
+        /// Scheme B
+        #[arg(value_name = "B")]
+        b: PathBuf,
+        /// Scheme to take base00-base07 from
+        #[arg(long, value_enum, default_value = "a")]
+        surfaces: MergeSideArg,
+        /// Scheme to take base08-base0F from
+        #[arg(long, value_enum, default_value = "b")]
+        accents: MergeSideArg,
+        /// Scheme to take base10-base17 from (defaults to --accents)
+        #[arg(long, value_enum)]
+        extended_accents: Option<MergeSideArg>,
+        /// Take one slot from a scheme, e.g. `base0D=a` (repeatable)
+        #[arg(long, value_name = "SLOT=SCHEME")]
+        pick: Vec<String>,
+    },
+    /// Print a JSON Schema for config files, for editor completion and
+    /// validation, e.g. `themalingadingdong config-schema > theme.schema.json`
+    ConfigSchema,
+    /// Upgrade a config file written for an older config version and
+    /// report the changes
+    ///
+    /// Rewrites FILE in place (keeping the original as FILE.bak), writes to
+    /// --output instead, or prints the result with --dry-run, e.g.
+    /// `themalingadingdong migrate-config theme.toml`
+    MigrateConfig {
+        /// Config file to upgrade
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+        /// Write the upgraded config here instead of rewriting FILE
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Print the upgraded config instead of writing it
+        #[arg(long, conflicts_with = "output")]
+        dry_run: bool,
+    },
+}
+
 /// Base24 palette generator using HellwigJmh color space with APCA validation.
 #[derive(Parser, Debug, Serialize)]
 #[command(name = "themalingadingdong")]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Cli {
-    /// Background color (base00) in any CSS format (hex, rgb(), oklch(), hsl(), named)
+    #[command(subcommand)]
+    #[serde(skip)]
+    pub command: Option<Command>,
+
+    /// Background color (base00) in any CSS format (hex, rgb(), oklch(), hsl(), color(), named)
     #[arg(
         short,
         long,
         default_value_if("interactive", "true", "#000000"),
         default_value_if("input", ArgPredicate::IsPresent, "#000000"),
-        required_unless_present_any = ["interactive", "config", "completions", "input"],
-        value_parser = |s: &str| s.parse::<csscolorparser::Color>().map(|_| s.to_string()).map_err(|e| e.to_string())
+        required_unless_present_any = ["interactive", "config", "completions", "input", "from_image", "batch", "replay", "resume", "print_lut_info", "print_keys"],
+        value_parser = css_color_arg
     )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub background: Option<String>,
 
-    /// Foreground color (base07) in any CSS format (hex, rgb(), oklch(), hsl(), named)
+    /// Foreground color (base07) in any CSS format (hex, rgb(), oklch(), hsl(), color(), named)
     #[arg(
         short,
         long,
         default_value_if("interactive", "true", "#FFFFFF"),
         default_value_if("input", ArgPredicate::IsPresent, "#FFFFFF"),
-        required_unless_present_any = ["interactive", "config", "completions", "input"],
-        value_parser = |s: &str| s.parse::<csscolorparser::Color>().map(|_| s.to_string()).map_err(|e| e.to_string())
+        required_unless_present_any = ["interactive", "config", "completions", "input", "from_image", "batch", "replay", "resume", "print_lut_info", "print_keys"],
+        value_parser = css_color_arg
     )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub foreground: Option<String>,
 
+    /// Extend the UI ramp this many J' past the foreground, so base06/base07
+    /// come out brighter than a soft, base05-style foreground
+    #[arg(long, value_name = "J")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground_headroom: Option<f32>,
+
+    /// Nudge accent hues apart so they are evenly spaced perceptually
+    /// rather than in degrees
+    #[arg(long)]
+    #[serde(skip)]
+    pub perceptual_hues: bool,
+
+    /// Furthest a hue may move from its default or override under
+    /// perceptual spacing, in degrees (implies --perceptual-hues)
+    #[arg(long, value_name = "DEGREES")]
+    #[serde(skip)]
+    pub hue_max_shift: Option<f32>,
+
+    /// Place the accent hues by a color-harmony rule instead of the
+    /// defaults (explicit --hue-* values still apply)
+    #[arg(long, value_enum, value_name = "RULE")]
+    #[serde(skip)]
+    pub harmony: Option<HarmonyArg>,
+
+    /// Hue the harmony is anchored on, in degrees (default 25, base08's
+    /// default)
+    #[arg(long, value_name = "DEGREES", requires = "harmony")]
+    #[serde(skip)]
+    pub seed_hue: Option<f32>,
+
+    /// Keep the default accent hues even when the background is tinted
+    /// (by default they move out of the background's hue)
+    #[arg(long)]
+    #[serde(skip)]
+    pub no_adaptive_hues: bool,
+
+    /// Hold a slot at an exact color, e.g. `--pin base0D=#82aaff` (repeatable)
+    #[arg(long, value_name = "SLOT=COLOR", value_parser = slot_color_arg)]
+    #[serde(skip)]
+    pub pin: Vec<(String, String)>,
+
+    /// Keep an accent within ΔE 5 of a brand color while contrast is
+    /// solved, e.g. `--anchor base0D=#0969da` (repeatable)
+    #[arg(long, value_name = "SLOT=COLOR", value_parser = slot_color_arg)]
+    #[serde(skip)]
+    pub anchor: Vec<(String, String)>,
+
     /// Minimum APCA contrast for accent colors (floor, not exact target)
     /// Colors will achieve at least this contrast while maintaining uniform lightness.
     #[arg(long)]
@@ -92,12 +544,60 @@ pub struct Cli {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_min_contrast: Option<f64>,
 
+    /// How to derive extended accents base10-base17 from base08-base0F
+    #[arg(long, value_enum, value_name = "MODE")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_accents: Option<ExtendedAccentsArg>,
+
+    /// J' offset (darker, paired) or percent colorfulness to remove (desaturated)
+    #[arg(long, value_name = "AMOUNT")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_amount: Option<f32>,
+
     /// Maximum per-hue lightness adjustment (0-10 J' units, default 2.0)
     /// Small adjustments help difficult hues reach minimum contrast.
     #[arg(long)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_lightness_adjustment: Option<f32>,
 
+    /// Lowest APCA contrast for base03 (comments) on base00.
+    /// Enables comment contrast tuning; upper bound defaults to 55.
+    #[arg(long, value_name = "LC")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_min_contrast: Option<f64>,
+
+    /// Highest APCA contrast for base03 (comments) on base00.
+    /// Enables comment contrast tuning; lower bound defaults to 40.
+    #[arg(long, value_name = "LC")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_max_contrast: Option<f64>,
+
+    /// Contrast standard validation must satisfy (both metrics are always reported)
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contrast_standard: Option<ContrastStandardArg>,
+
+    /// Gamut to optimize accents for (wide gamuts need --format css to use the extra range)
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gamut: Option<GamutArg>,
+
+    /// Surround the theme will be viewed in: dark, dim, or average (default)
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surround: Option<SurroundArg>,
+
+    /// Adapting luminance of the viewing environment in cd/m², roughly
+    /// ambient lux / π × 0.2 (default 4.07, a 64 lux room)
+    #[arg(long, value_name = "CD_M2")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adapting_luminance: Option<f32>,
+
+    /// White point the viewer is adapted to (default d65)
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub white_point: Option<WhitePointArg>,
+
     // Individual hue overrides (base08-base0F)
     // Default values come from DEFAULT_BASE16_HUES lookup table
     /// Override hue for base08 (Red). Default: 25 degrees
@@ -145,7 +645,8 @@ pub struct Cli {
         long,
         default_value_if("interactive", "true", "My Theme"),
         default_value_if("input", ArgPredicate::IsPresent, "Imported Theme"),
-        required_unless_present_any = ["interactive", "config", "completions", "input"]
+        default_value_if("from_image", ArgPredicate::IsPresent, "Wallpaper"),
+        required_unless_present_any = ["interactive", "config", "completions", "input", "from_image", "batch", "replay", "resume", "print_lut_info", "print_keys"]
     )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -155,22 +656,98 @@ pub struct Cli {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
 
-    /// Output file (stdout if not specified, required for --variant both)
+    /// Scheme description (default: a summary of the generation parameters)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Output file (stdout if not specified, required for --variant both).
+    /// With --batch, the output directory.
     #[arg(short, long)]
     #[serde(skip)]
     pub output: Option<std::path::PathBuf>,
 
-    /// Output format: yaml or json (tinted-theming compatible)
+    /// Output format: yaml or json (tinted-theming compatible), or css, scss,
+    /// or tailwind for web projects
     #[arg(long, value_enum, default_value_t = OutputFormat::Yaml)]
     #[serde(skip)]
     pub format: OutputFormat,
 
-    /// Import Base16/Base24 scheme file for editing (implies --interactive)
+    /// Prefix for variable names in css, scss, and tailwind output
+    /// (e.g. `theme-` for `--theme-base00`)
+    #[arg(long, value_name = "PREFIX", default_value = "", value_parser = var_prefix_arg)]
+    #[serde(skip)]
+    pub var_prefix: String,
+
+    /// Import Base16/Base24 scheme or Alacritty/Kitty/Xresources/VS Code theme (implies --interactive)
     #[arg(long, value_name = "FILE")]
     #[serde(skip)]
     pub input: Option<PathBuf>,
 
-    /// Output variant: auto (detect from background), dark, light, or both
+    /// Take the background, foreground, and accent hues from the dominant
+    /// colors of an image (PNG or PPM; other formats through ImageMagick).
+    /// Explicit -b/-f and --hue-* values still apply
+    #[arg(long, value_name = "IMAGE", conflicts_with_all = ["input", "harmony"])]
+    #[serde(skip)]
+    pub from_image: Option<PathBuf>,
+
+    /// Fit generator parameters to the --input scheme and output them as a config
+    #[arg(long, requires = "input")]
+    #[serde(skip)]
+    pub fit: bool,
+
+    /// Also emit the nearest xterm-256 index (and delta E) for each slot
+    #[arg(long, value_enum, value_name = "MODE")]
+    #[serde(skip)]
+    pub xterm256: Option<Xterm256Mode>,
+
+    /// How --xterm256 maps slots to indices: nearest per slot, or joint to
+    /// keep the slots' luminance order and distinct slots apart
+    #[arg(long, value_enum, default_value = "nearest", requires = "xterm256")]
+    #[serde(skip)]
+    pub xterm256_quantize: QuantizationArg,
+
+    /// Comment each YAML palette entry with its achieved contrast (Lc on
+    /// base00), J'/M/h, and which solver constraints were relaxed
+    #[arg(long)]
+    #[serde(skip)]
+    pub annotate: bool,
+
+    /// Embed the effective settings, version, and lookup-table checksums in
+    /// the output so `reproduce` can regenerate it
+    #[arg(long)]
+    #[serde(skip)]
+    pub emit_provenance: bool,
+
+    /// Write one copy of each scheme per `[displays.<name>]` config table,
+    /// calibrated for that display's gamma and white point, as
+    /// `<output>-<name>.<ext>`
+    #[arg(
+        long,
+        requires = "output",
+        conflicts_with_all = ["emit_provenance", "batch", "daemon", "input"]
+    )]
+    #[serde(skip)]
+    pub per_display: bool,
+
+    /// Print an extra report to stderr (contrast-matrix: 24x24 APCA table), or
+    /// write one to `<output>.report.html` (html; `<slug>.report.html` without
+    /// --output)
+    #[arg(long, value_enum, value_name = "REPORT")]
+    #[serde(skip)]
+    pub report: Option<ReportKind>,
+
+    /// Write the generated schemes with their full validation results,
+    /// warnings, and solver diagnostics to stdout as one JSON document
+    /// (combine with `--config -` to read a JSON config from stdin)
+    #[arg(
+        long,
+        conflicts_with_all = ["output", "dry_run", "watch", "daemon", "interactive", "batch", "xterm256", "emit_provenance"]
+    )]
+    #[serde(skip)]
+    pub json_report: bool,
+
+    /// Output variant: auto (detect from background), dark, light, both, or linked
     #[arg(long, value_enum, default_value_t = VariantArg::Auto)]
     #[serde(skip)]
     pub variant: VariantArg,
@@ -180,11 +757,111 @@ pub struct Cli {
     #[serde(skip)]
     pub no_adjust: bool,
 
+    /// Raise --min-contrast and --extended-min-contrast to the highest values
+    /// every accent can meet within its J'/M bounds
+    #[arg(long)]
+    #[serde(skip)]
+    pub max_contrast: bool,
+
+    /// Sample N random parameter sets within the config's [explore] bounds,
+    /// list the best-scoring, and generate the one picked with --explore-pick
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    #[serde(skip)]
+    pub explore: Option<u16>,
+
+    /// Seed for --explore, to sample the same candidates again (random by
+    /// default; the seed used is printed)
+    #[arg(long, value_name = "SEED", requires = "explore")]
+    #[serde(skip)]
+    pub explore_seed: Option<u64>,
+
+    /// Rank of the --explore candidate to generate (1 is the best)
+    #[arg(
+        long,
+        value_name = "RANK",
+        default_value_t = 1,
+        requires = "explore",
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    #[serde(skip)]
+    pub explore_pick: u16,
+
+    /// Reassign the solved accents to base08-base0F in the order closest
+    /// (by total delta E) to this reference scheme, so the result reads
+    /// like it. Pinned, anchored, and hue-linked accents stay put
+    #[arg(long, value_name = "SCHEME", conflicts_with = "input")]
+    #[serde(skip)]
+    pub match_order: Option<PathBuf>,
+
+    /// Flag accent pairs that become hard to distinguish under colorblindness simulation
+    #[arg(long)]
+    #[serde(skip)]
+    pub check_cvd: bool,
+
+    /// Minimum delta E between simulated accent pairs (used by --check-cvd and the TUI overlay)
+    #[arg(long, value_name = "DELTA_E", default_value_t = 5.0)]
+    #[serde(skip)]
+    pub cvd_threshold: f32,
+
+    /// Add a semantic `roles` section (error, warning, selection_bg, ...) to the output
+    #[arg(long)]
+    #[serde(skip)]
+    pub roles: bool,
+
+    /// Add an `ansi` section mapping ANSI colors 0-15 to their slots to the output
+    #[arg(long)]
+    #[serde(skip)]
+    pub ansi_map: bool,
+
+    /// Add a `surfaces` section of N shades interpolated from base00 to base07 to the output
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(2..=64))]
+    #[serde(skip)]
+    pub surface_steps: Option<u8>,
+
+    /// Show the preview (`preview` subcommand or TUI) as it would appear on a
+    /// dimmed display, in bright ambient light, or on a gamma 2.4 display;
+    /// generated output is unchanged
+    #[arg(long, value_enum, value_name = "MODE")]
+    #[serde(skip)]
+    pub simulate: Option<SimulateArg>,
+
+    /// Colors the terminal can show, for the `preview` subcommand and the TUI;
+    /// detected from COLORTERM and TERM when not given
+    #[arg(long, value_enum, value_name = "DEPTH")]
+    #[serde(skip)]
+    pub color_depth: Option<ColorDepthArg>,
+
+    /// Print how long each generation stage took to stderr
+    #[arg(long)]
+    #[serde(skip)]
+    pub timings: bool,
+
+    /// Check accents on base00 as dim and bold terminal text
+    #[arg(long)]
+    #[serde(skip)]
+    pub check_attributes: bool,
+
+    /// Luminance multiplier terminals apply to dim text (implies --check-attributes)
+    #[arg(long, value_name = "FACTOR")]
+    #[serde(skip)]
+    pub dim_multiplier: Option<f64>,
+
+    /// Luminance multiplier terminals apply to bold-as-bright text (implies --check-attributes)
+    #[arg(long, value_name = "FACTOR")]
+    #[serde(skip)]
+    pub bold_multiplier: Option<f64>,
+
     /// Launch interactive TUI for previewing and editing the palette
     #[arg(short, long)]
     #[serde(skip)]
     pub interactive: bool,
 
+    /// Render the TUI's palette and preview panels to an SVG or PNG image
+    /// instead of launching it (PNG needs rsvg-convert, resvg, or ImageMagick)
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    #[serde(skip)]
+    pub screenshot: Option<PathBuf>,
+
     // Curve configuration
     /// Lightness interpolation curve type
     #[arg(long, value_enum)]
@@ -206,11 +883,85 @@ pub struct Cli {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hue_curve: Option<CurveTypeArg>,
 
-    /// Load configuration from TOML file
+    /// Generate every scheme listed in a TOML manifest
+    #[arg(long, value_name = "MANIFEST", conflicts_with_all = ["interactive", "input"])]
+    #[serde(skip)]
+    pub batch: Option<PathBuf>,
+
+    /// Report --batch progress as NDJSON events on stderr instead of text
+    #[arg(long, requires = "batch")]
+    #[serde(skip)]
+    pub progress_json: bool,
+
+    /// Record parameter changes made in the TUI to a session log
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "interactive",
+        conflicts_with = "replay"
+    )]
+    #[serde(skip)]
+    pub record: Option<PathBuf>,
+
+    /// Replay a recorded session log in the TUI
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["input", "batch", "config"])]
+    #[serde(skip)]
+    pub replay: Option<PathBuf>,
+
+    /// Replay speed multiplier (2.0 = twice as fast as recorded)
+    #[arg(
+        long,
+        value_name = "FACTOR",
+        default_value_t = 1.0,
+        requires = "replay"
+    )]
+    #[serde(skip)]
+    pub replay_speed: f64,
+
+    /// Replay one change per keypress (n) instead of following recorded timing
+    #[arg(long, requires = "replay", conflicts_with = "replay_speed")]
+    #[serde(skip)]
+    pub replay_step: bool,
+
+    /// Reopen the TUI with the parameters it had when last closed (implies --interactive)
+    #[arg(long, conflicts_with_all = ["input", "batch", "config", "replay"])]
+    #[serde(skip)]
+    pub resume: bool,
+
+    /// Load configuration from TOML file, or JSON from stdin with `-`
     #[arg(long, value_name = "FILE")]
     #[serde(skip)]
     pub config: Option<PathBuf>,
 
+    /// Layer the named [profile.<NAME>] table of the --config file over its shared settings
+    #[arg(long, value_name = "NAME", requires = "config")]
+    #[serde(skip)]
+    pub profile: Option<String>,
+
+    /// Regenerate the output whenever the --config file changes
+    #[arg(
+        long,
+        requires_all = ["config", "output"],
+        conflicts_with_all = ["interactive", "input", "batch", "replay"]
+    )]
+    #[serde(skip)]
+    pub watch: bool,
+
+    /// Run in the background: regenerate on --config changes, write every
+    /// [daemon] output, and recolor the [daemon] terminals
+    #[arg(
+        long,
+        requires = "config",
+        conflicts_with_all = ["interactive", "input", "batch", "replay", "watch", "dry_run"]
+    )]
+    #[serde(skip)]
+    pub daemon: bool,
+
+    /// Terminal device to recolor with OSC sequences in --daemon mode (repeatable)
+    #[arg(long, value_name = "TTY", requires = "daemon")]
+    #[serde(skip)]
+    pub osc_tty: Vec<PathBuf>,
+
     /// Save current configuration to TOML file
     #[arg(long, value_name = "FILE")]
     #[serde(skip)]
@@ -262,8 +1013,26 @@ pub struct Cli {
     #[serde(skip)]
     pub completions: Option<clap_complete::Shell>,
 
+    /// Print which lookup tables this binary was built with and exit
+    #[arg(long)]
+    #[serde(skip)]
+    pub print_lut_info: bool,
+
+    /// Print the interactive editor's key bindings as a [keys] table and exit
+    ///
+    /// Includes the rebindings of --config, so the output can be pasted
+    /// back into a configuration file and edited.
+    #[arg(long)]
+    #[serde(skip)]
+    pub print_keys: bool,
+
     /// Preview validation results without generating output
     #[arg(long)]
     #[serde(skip)]
     pub dry_run: bool,
+
+    /// Normalize scheme metadata (name, slug, author whitespace, variant) before output
+    #[arg(long)]
+    #[serde(skip)]
+    pub fix_metadata: bool,
 }
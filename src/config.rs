@@ -1,16 +1,47 @@
 //! TOML configuration file support for theme generation.
 //!
 //! Uses Figment for hierarchical configuration with layered overrides:
-//! `defaults < TOML file < CLI args`
+//! `defaults < TOML file < [profile.<name>] < CLI args`
+//!
+//! String values in the file may read environment variables and start from
+//! `~` (see [`crate::env_expand`]).
 
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 use figment::Figment;
-use figment::providers::{Format, Serialized, Toml};
+use figment::providers::{Format, Json, Serialized, Toml};
+use figment::value::Dict;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
+use crate::ansi::AnsiConfig;
+use crate::config_schema::{Diagnostic, check_config};
 use crate::curves::InterpolationConfig;
+use crate::cvd::CvdSeparation;
+use crate::daemon::DaemonConfig;
+use crate::display_profile::DisplayProfile;
+use crate::env_expand::{Expanded, restore_unexpanded};
+use crate::explore::ExploreConfig;
+use crate::extended_accents::ExtendedAccents;
+use crate::gamut_map::Gamut;
 use crate::generate::{GenerateConfig, parse_color};
+use crate::harmony::HarmonyConfig;
+use crate::hellwig::HellwigJmh;
+use crate::hooks::HooksConfig;
+use crate::hue_links::HueLinks;
+use crate::hue_spacing::HueSpacing;
+use crate::import::extends_config;
+use crate::interpolation::srgb_to_hex;
+use crate::keys::KeysConfig;
+use crate::migrate::{CONFIG_VERSION, migrate_config};
+use crate::ramp::LightnessRamp;
+use crate::roles::{RolesConfig, is_slot};
+use crate::semantic_pairs::SemanticSeparation;
+use crate::surfaces::SurfacesConfig;
+use crate::validation::{ContrastStandard, ValidationConfig};
+use crate::viewing::ViewingConditions;
 
 /// Error type for configuration operations.
 #[derive(Debug)]
@@ -23,6 +54,15 @@ pub enum ConfigError {
     InvalidColor(String),
     /// Missing required field
     MissingField(&'static str),
+    /// Field value outside its valid range
+    InvalidValue(String),
+    /// Problems found in a config file, with their positions
+    Diagnostics {
+        path: PathBuf,
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// Several validation errors, reported together
+    Multiple(Vec<ConfigError>),
 }
 
 impl std::fmt::Display for ConfigError {
@@ -32,6 +72,18 @@ impl std::fmt::Display for ConfigError {
             Self::Figment(e) => write!(f, "Configuration error: {}", e),
             Self::InvalidColor(s) => write!(f, "{}", s),
             Self::MissingField(field) => write!(f, "Missing required field: {}", field),
+            Self::InvalidValue(s) => write!(f, "{}", s),
+            Self::Diagnostics { path, diagnostics } => {
+                let lines: Vec<String> = diagnostics
+                    .iter()
+                    .map(|d| format!("{}:{d}", path.display()))
+                    .collect();
+                f.write_str(&lines.join("\n"))
+            }
+            Self::Multiple(errors) => {
+                let lines: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                f.write_str(&lines.join("\n"))
+            }
         }
     }
 }
@@ -50,45 +102,344 @@ impl From<figment::Error> for ConfigError {
     }
 }
 
+/// `config_path` that reads a JSON config from standard input.
+pub const STDIN_CONFIG: &str = "-";
+
 /// Load configuration with Figment layering.
 ///
 /// Priority: defaults < TOML file < CLI overrides
 pub fn load_config(
     config_path: Option<&Path>,
-    cli_overrides: &ThemeConfig,
+    cli_overrides: &ConfigOverrides,
+) -> Result<ThemeConfig, ConfigError> {
+    load_config_with_profile(config_path, None, cli_overrides)
+}
+
+/// Load configuration, layering a named `[profile.<name>]` table from the
+/// TOML file over the file's shared settings.
+///
+/// Priority: defaults < TOML file < profile < CLI overrides
+///
+/// A `config_path` of [`STDIN_CONFIG`] reads the config as JSON from
+/// standard input instead, with the same layout as the TOML file.
+///
+/// ```toml
+/// [colors]
+/// background = "#1d2021"
+/// foreground = "#ebdbb2"
+///
+/// [profile.high-contrast.contrast]
+/// minimum = 90.0
+/// ```
+pub fn load_config_with_profile(
+    config_path: Option<&Path>,
+    profile: Option<&str>,
+    cli_overrides: &ConfigOverrides,
 ) -> Result<ThemeConfig, ConfigError> {
-    let mut figment = Figment::new().merge(Serialized::defaults(ThemeConfig::default()));
+    let mut figment = Figment::new();
+    // The same layers before environment expansion, for `save`
+    let mut unexpanded = Figment::new();
 
-    if let Some(path) = config_path {
-        figment = figment.merge(Toml::file(path));
+    match (config_path, profile) {
+        (Some(path), profile) => {
+            let (file, raw) = if path == Path::new(STDIN_CONFIG) {
+                let source = std::io::read_to_string(std::io::stdin())?;
+                (
+                    Figment::from(Expanded(Json::string(&source))),
+                    Figment::from(Json::string(&source)),
+                )
+            } else {
+                let migrated = check_file(path)?;
+                let toml = || match migrated {
+                    Some(ref migrated) => Toml::string(migrated),
+                    None => Toml::file(path),
+                };
+                (Figment::from(Expanded(toml())), Figment::from(toml()))
+            };
+            figment = figment.merge(file.clone());
+            unexpanded = unexpanded.merge(raw.clone());
+            if let Some(name) = profile {
+                figment = figment.merge(profile_layer(&file, name, path)?);
+                unexpanded = unexpanded.merge(profile_layer(&raw, name, path)?);
+            }
+        }
+        (None, Some(name)) => {
+            return Err(ConfigError::InvalidValue(format!(
+                "profile '{name}' requires a config file"
+            )));
+        }
+        (None, None) => {}
     }
 
-    figment = figment.merge(Serialized::defaults(cli_overrides));
-    Ok(figment.extract()?)
+    figment = figment.merge(cli_overrides.0.clone());
+
+    // An `extends` scheme takes the place of the defaults under everything
+    // else
+    let base = match figment.extract_inner::<String>("extends") {
+        Ok(extends) => {
+            let config_dir = config_path
+                .filter(|path| *path != Path::new(STDIN_CONFIG))
+                .and_then(Path::parent)
+                // A bare `x.toml` has an empty parent, the working directory
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or(Path::new("."));
+            let gallery_dir = figment
+                .extract_inner::<PathBuf>("preview.gallery_dir")
+                .ok()
+                .map(|dir| config_dir.join(dir));
+            extends_config(&extends, config_dir, gallery_dir.as_deref())
+                .map_err(|e| ConfigError::InvalidValue(format!("{e:#}")))?
+        }
+        Err(_) => ThemeConfig::default(),
+    };
+    let mut config: ThemeConfig = Figment::new()
+        .merge(Serialized::defaults(base))
+        .merge(figment)
+        .extract()?;
+    config.unexpanded = unexpanded.extract().unwrap_or_default();
+    Ok(config)
+}
+
+/// Report every schema problem in the config file at `path` at once.
+///
+/// A file from an older config version whose keys changed is upgraded first
+/// and the upgraded TOML returned; positions in its diagnostics then refer to
+/// that TOML. A missing file is left to Figment, which treats it as empty.
+fn check_file(path: &Path) -> Result<Option<String>, ConfigError> {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let migrated = match migrate_config(&source) {
+        Ok(migrated) if !migrated.changes.is_empty() => {
+            warn!(
+                path = %path.display(),
+                from = migrated.from_version,
+                to = CONFIG_VERSION,
+                "upgraded old config version; run migrate-config to update the file"
+            );
+            Some(migrated.to_toml())
+        }
+        // Syntax errors are reported with positions below, and a file that
+        // only lacks its version reads the same either way
+        _ => None,
+    };
+    let diagnostics = check_config(migrated.as_deref().unwrap_or(&source));
+    if diagnostics.is_empty() {
+        Ok(migrated)
+    } else {
+        let path = match migrated {
+            Some(_) => PathBuf::from(format!("{} (upgraded)", path.display())),
+            None => path.to_path_buf(),
+        };
+        Err(ConfigError::Diagnostics { path, diagnostics })
+    }
+}
+
+/// Values given explicitly on the command line, layered over the config
+/// file and profile by [`load_config_with_profile`].
+///
+/// Only keys that were set override anything, so a flag given with its
+/// default value still wins over the file.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides(Figment);
+
+impl ConfigOverrides {
+    /// Override the value at a dotted config `key`, e.g. `contrast.minimum`.
+    ///
+    /// `None` leaves the key to the file, so unset flags pass straight in.
+    pub fn set<T: Serialize>(&mut self, key: &str, value: Option<T>) -> &mut Self {
+        if let Some(value) = value {
+            self.0 = std::mem::take(&mut self.0).merge(Serialized::default(key, value));
+        }
+        self
+    }
+}
+
+/// The `[profile.<name>]` table of `file`, focused so it layers like a config file.
+fn profile_layer(file: &Figment, name: &str, path: &Path) -> Result<Figment, ConfigError> {
+    if name.is_empty() || name.contains('.') {
+        return Err(ConfigError::InvalidValue(format!(
+            "invalid profile name '{name}'"
+        )));
+    }
+    let key = format!("profile.{name}");
+    if file.find_value(&key).is_err() {
+        let available: Vec<String> = file
+            .find_value("profile")
+            .ok()
+            .and_then(|v| v.into_dict())
+            .map(|d| d.into_keys().collect())
+            .unwrap_or_default();
+        let available = if available.is_empty() {
+            "none defined".to_string()
+        } else {
+            format!("available: {}", available.join(", "))
+        };
+        return Err(ConfigError::InvalidValue(format!(
+            "profile '{name}' not found in {} ({available})",
+            path.display()
+        )));
+    }
+    Ok(file.focus(&key))
 }
 
 /// Validate that required fields are present and valid in the configuration.
+///
+/// Every problem is reported: a single error is returned as is, several as
+/// `ConfigError::Multiple`.
 pub fn validate_config(config: &ThemeConfig) -> Result<(), ConfigError> {
-    // Validate colors first - this gives the most actionable error message
-    if let Some(ref bg) = config.colors.background {
-        parse_color(bg).map_err(ConfigError::InvalidColor)?;
-    } else {
-        return Err(ConfigError::MissingField("colors.background"));
+    let mut errors = Vec::new();
+    let mut check = |result: Result<(), ConfigError>| {
+        if let Err(e) = result {
+            errors.push(e);
+        }
+    };
+
+    // Colors first - these give the most actionable error messages
+    check(match config.colors.background {
+        Some(ref bg) => parse_color(bg).map(drop).map_err(ConfigError::InvalidColor),
+        None => Err(ConfigError::MissingField("colors.background")),
+    });
+    check(match config.colors.foreground {
+        Some(ref fg) => parse_color(fg).map(drop).map_err(ConfigError::InvalidColor),
+        None => Err(ConfigError::MissingField("colors.foreground")),
+    });
+
+    if let Some(ref comment) = config.contrast.comment {
+        check(comment.validate());
     }
 
-    if let Some(ref fg) = config.colors.foreground {
-        parse_color(fg).map_err(ConfigError::InvalidColor)?;
-    } else {
-        return Err(ConfigError::MissingField("colors.foreground"));
+    if let Some(ref terminal) = config.contrast.terminal {
+        check(terminal.validate());
+    }
+
+    if let Some(ref spacing) = config.colors.hue_spacing {
+        check(spacing.validate());
+    }
+
+    check(config.ramp.validate());
+
+    for (slot, color) in config.colors.pins.iter().flatten() {
+        check(if is_slot(slot) {
+            parse_color(color)
+                .map(drop)
+                .map_err(ConfigError::InvalidColor)
+        } else {
+            Err(ConfigError::InvalidValue(format!(
+                "colors.pins: '{slot}' is not a slot (expected base00-base17)"
+            )))
+        });
     }
 
-    Ok(())
+    if let Some(ref harmony) = config.colors.harmony {
+        check(harmony.validate());
+    }
+
+    if let Some(ref links) = config.colors.hue_links {
+        check(HueLinks::parse(links).map(drop));
+    }
+
+    for cvd in [&config.optimization.cvd, &config.extended_optimization.cvd]
+        .into_iter()
+        .flatten()
+    {
+        check(cvd.validate());
+    }
+    for joint in [
+        &config.optimization.joint,
+        &config.extended_optimization.joint,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        check(joint.validate());
+    }
+
+    let base_slots: Vec<String> = (8..16).map(|i| format!("base0{i:X}")).collect();
+    let extended_slots: Vec<String> = (0..8).map(|i| format!("base1{i:X}")).collect();
+    check(
+        config
+            .optimization
+            .validate_anchors("optimization", &base_slots),
+    );
+    check(
+        config
+            .extended_optimization
+            .validate_anchors("extended_optimization", &extended_slots),
+    );
+    check(config.optimization.solver.validate("optimization"));
+    check(
+        config
+            .optimization
+            .validate_preferred_contrast("optimization", config.contrast.minimum),
+    );
+    check(
+        config
+            .extended_optimization
+            .validate_preferred_contrast("extended_optimization", config.contrast.extended_minimum),
+    );
+    check(
+        config
+            .extended_optimization
+            .solver
+            .validate("extended_optimization"),
+    );
+
+    check(config.extended_accents.validate());
+    check(config.validation.validate());
+    check(config.viewing.validate());
+
+    if let Some(ref roles) = config.roles {
+        check(roles.validate());
+    }
+
+    if let Some(ref ansi) = config.ansi {
+        check(ansi.validate());
+    }
+
+    if let Some(ref surfaces) = config.surfaces {
+        check(surfaces.validate());
+    }
+
+    if let Some(ref daemon) = config.daemon {
+        check(daemon.validate());
+    }
+
+    check(config.hooks.validate());
+    check(config.keys.validate());
+    check(config.explore.validate());
+
+    for (name, display) in &config.displays {
+        check(display.validate(name));
+    }
+
+    if let Some(headroom) = config.colors.foreground_headroom
+        && !(0.0..=100.0).contains(&headroom)
+    {
+        check(Err(ConfigError::InvalidValue(format!(
+            "colors.foreground_headroom must be between 0 and 100 J' (got {headroom})"
+        ))));
+    }
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(ConfigError::Multiple(errors)),
+    }
 }
 
 /// Root configuration structure for TOML files.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ThemeConfig {
+    /// Config layout version the file was written for (see `crate::migrate`)
+    pub config_version: u32,
+    /// Scheme (a path or a slug) this config starts from; see
+    /// [`extends_config`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
     /// Theme metadata
     pub theme: ThemeMetadata,
     /// Color settings
@@ -97,25 +448,84 @@ pub struct ThemeConfig {
     pub curves: InterpolationConfig,
     /// Contrast settings
     pub contrast: ContrastConfig,
+    /// Accent thresholds per background and per polarity
+    #[serde(skip_serializing_if = "ValidationConfig::is_default")]
+    pub validation: ValidationConfig,
     /// Accent optimization settings for main accents (base08-0F)
     pub optimization: AccentOptSettings,
     /// Accent optimization settings for extended accents (base10-17)
     pub extended_optimization: AccentOptSettings,
+    /// How extended accents are derived from the base accents
+    #[serde(skip_serializing_if = "ExtendedAccents::is_default")]
+    pub extended_accents: ExtendedAccents,
+    /// Target contrast steps for the UI surfaces (base01-base06)
+    #[serde(skip_serializing_if = "LightnessRamp::is_empty")]
+    pub ramp: LightnessRamp,
+    /// TUI preview settings
+    #[serde(skip_serializing_if = "PreviewConfig::is_empty")]
+    pub preview: PreviewConfig,
+    /// Semantic color roles; when set, a `roles` section is added to the output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<RolesConfig>,
+    /// Outputs and terminals for `--daemon`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daemon: Option<DaemonConfig>,
+    /// Viewing conditions the theme is optimized for
+    #[serde(skip_serializing_if = "ViewingConditions::is_default")]
+    pub viewing: ViewingConditions,
+    /// ANSI hue bands; when set, an `ansi` section is added to the output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ansi: Option<AnsiConfig>,
+    /// Extended surface ramp; when set, a `surfaces` section is added to the output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surfaces: Option<SurfacesConfig>,
+    /// Displays `--per-display` writes calibrated copies for, by name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub displays: BTreeMap<String, DisplayProfile>,
+    /// Commands run after generation
+    #[serde(skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
+    /// Interactive editor key bindings
+    #[serde(skip_serializing_if = "KeysConfig::is_empty")]
+    pub keys: KeysConfig,
+    /// Bounds for `--explore` and the TUI's shuffle
+    #[serde(skip_serializing_if = "ExploreConfig::is_default")]
+    pub explore: ExploreConfig,
+    /// The config file's values as written, before environment expansion;
+    /// [`save`](Self::save) writes them back where the value is unchanged
+    #[serde(skip)]
+    pub unexpanded: Dict,
 }
 
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
+            config_version: CONFIG_VERSION,
+            extends: None,
             theme: ThemeMetadata::default(),
             colors: ColorConfig::default(),
             curves: InterpolationConfig::default(),
             contrast: ContrastConfig::default(),
+            validation: ValidationConfig::default(),
             optimization: AccentOptSettings::default(),
             extended_optimization: AccentOptSettings {
                 target_j: 70.0,
                 target_m: 35.0,
                 ..AccentOptSettings::default()
             },
+            extended_accents: ExtendedAccents::default(),
+            ramp: LightnessRamp::default(),
+            preview: PreviewConfig::default(),
+            roles: None,
+            daemon: None,
+            viewing: ViewingConditions::default(),
+            ansi: None,
+            surfaces: None,
+            displays: BTreeMap::new(),
+            hooks: HooksConfig::default(),
+            keys: KeysConfig::default(),
+            explore: ExploreConfig::default(),
+            unexpanded: Dict::new(),
         }
     }
 }
@@ -130,6 +540,9 @@ pub struct ThemeMetadata {
     /// Author of the theme
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
+    /// Description of the theme (summarized from the parameters if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     /// Variant hint (dark, light, auto)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variant: Option<String>,
@@ -148,6 +561,28 @@ pub struct ColorConfig {
     /// Hue overrides for accent colors
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hue_overrides: Option<HueOverrides>,
+    /// J' to extend the UI ramp past the foreground (base06/base07 brighter than it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground_headroom: Option<f32>,
+    /// Move default accent hues away from a tinted background's hue
+    /// (default true; ignored when any hue is overridden)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adaptive_hues: Option<bool>,
+    /// Nudge accent hues apart for even perceptual spacing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hue_spacing: Option<HueSpacing>,
+    /// Gamut accents are optimized for: "srgb", "display-p3", or "rec2020"
+    #[serde(skip_serializing_if = "Gamut::is_srgb")]
+    pub gamut: Gamut,
+    /// Slots held at an exact color, e.g. `base0D = "#82aaff"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pins: Option<BTreeMap<String, String>>,
+    /// Accent hues from a color-harmony rule instead of the defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub harmony: Option<HarmonyConfig>,
+    /// Accent hues held at an offset from another, e.g. `base0F = "base08 + 30"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hue_links: Option<BTreeMap<String, String>>,
 }
 
 /// Hue overrides for individual accent colors.
@@ -225,6 +660,21 @@ pub struct ContrastConfig {
     /// Small adjustments help difficult hues reach minimum contrast while
     /// keeping colors near-uniform.
     pub max_adjustment: f32,
+    /// Target APCA contrast range for base03 (comments).
+    /// When set, base03 is solved against base00 instead of taken from the curve.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<CommentContrast>,
+    /// How terminals render dim and bold text.
+    /// When set, accents on base00 are also checked as dim and bold text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<TerminalAttributes>,
+    /// Contrast metric(s) validation must satisfy: "apca", "wcag", or "both".
+    /// Both metrics are always reported.
+    #[serde(
+        alias = "contrast_standard",
+        skip_serializing_if = "ContrastStandard::is_default"
+    )]
+    pub standard: ContrastStandard,
 }
 
 impl Default for ContrastConfig {
@@ -233,12 +683,128 @@ impl Default for ContrastConfig {
             minimum: 75.0,
             extended_minimum: 60.0,
             max_adjustment: 2.0,
+            comment: None,
+            terminal: None,
+            standard: ContrastStandard::default(),
         }
     }
 }
 
-/// Accent color optimization settings for COBYLA solver.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Target APCA contrast range for comment text (base03 on base00).
+///
+/// Comments should be readable but subdued, so base03 is placed inside
+/// `[min, max]` rather than at a single exact contrast.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommentContrast {
+    /// Lowest acceptable contrast (Lc) - keeps comments readable
+    pub min: f64,
+    /// Highest acceptable contrast (Lc) - keeps comments subdued
+    pub max: f64,
+}
+
+impl Default for CommentContrast {
+    fn default() -> Self {
+        Self {
+            min: 40.0,
+            max: 55.0,
+        }
+    }
+}
+
+impl CommentContrast {
+    /// Check that the range is non-empty and within APCA's usable Lc range.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(15.0..=90.0).contains(&self.min) || !(15.0..=90.0).contains(&self.max) {
+            return Err(ConfigError::InvalidValue(format!(
+                "contrast.comment: Lc range {:.0}-{:.0} must lie within 15-90",
+                self.min, self.max
+            )));
+        }
+        if self.min > self.max {
+            return Err(ConfigError::InvalidValue(format!(
+                "contrast.comment: min ({:.0}) must not exceed max ({:.0})",
+                self.min, self.max
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Luminance multipliers modelling terminal `dim` and bold-as-bright rendering.
+///
+/// Terminals commonly render `dim` text by scaling the foreground down and
+/// bold text by brightening it; both shift contrast against the background.
+/// Multipliers are applied to linear-light RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TerminalAttributes {
+    /// Luminance multiplier for `dim` text (0-1)
+    pub dim: f64,
+    /// Luminance multiplier for bold text rendered as bright (at least 1)
+    pub bold: f64,
+    /// Lowest readable contrast (Lc) for dim accent text on base00
+    pub dim_min_contrast: f64,
+}
+
+impl Default for TerminalAttributes {
+    fn default() -> Self {
+        Self {
+            dim: 0.5,
+            bold: 1.25,
+            dim_min_contrast: 45.0,
+        }
+    }
+}
+
+impl TerminalAttributes {
+    /// Check that the multipliers dim and brighten, and the Lc floor is usable.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.dim > 0.0 && self.dim <= 1.0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "contrast.terminal.dim must be in (0, 1] (got {})",
+                self.dim
+            )));
+        }
+        if !(1.0..=4.0).contains(&self.bold) {
+            return Err(ConfigError::InvalidValue(format!(
+                "contrast.terminal.bold must be between 1 and 4 (got {})",
+                self.bold
+            )));
+        }
+        if !(15.0..=90.0).contains(&self.dim_min_contrast) {
+            return Err(ConfigError::InvalidValue(format!(
+                "contrast.terminal.dim_min_contrast must lie within 15-90 (got {:.0})",
+                self.dim_min_contrast
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// TUI preview settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreviewConfig {
+    /// Directory of source files shown in the code preview alongside the
+    /// built-in samples. The language is inferred from each file's extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippets_dir: Option<PathBuf>,
+    /// Directory of scheme files listed by the scheme gallery, e.g. a
+    /// tinted-theming `schemes` checkout. Subdirectories are included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gallery_dir: Option<PathBuf>,
+}
+
+impl PreviewConfig {
+    /// Whether no preview setting is set.
+    pub fn is_empty(&self) -> bool {
+        self.snippets_dir.is_none() && self.gallery_dir.is_none()
+    }
+}
+
+/// Accent color optimization settings for the accent solver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AccentOptSettings {
     /// Target lightness (J') for accent colors
@@ -253,6 +819,117 @@ pub struct AccentOptSettings {
     pub j_weight: f32,
     /// Weight for contrast vs uniformity (0.0=uniformity, 1.0=contrast)
     pub contrast_weight: f32,
+    /// Contrast worth reaching beyond the minimum, in the same units; below
+    /// it, a shortfall is weighed against uniformity (off when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_contrast: Option<f64>,
+    /// Simulated-CVD separation between accents (off when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvd: Option<CvdSeparation>,
+    /// Accents held near a brand color, keyed by slot (base08-base0F in
+    /// `[optimization]`, base10-base17 in `[extended_optimization]`)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub anchors: BTreeMap<String, AccentAnchor>,
+    /// Search method for each accent's J'/M box, and its tuning
+    #[serde(skip_serializing_if = "SolverSettings::is_default")]
+    pub solver: SolverSettings,
+    /// Solve the accents together for even lightness (off when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joint: Option<JointOptimization>,
+    /// Semantic pairs to keep apart, from `[validation.semantic]`
+    /// (see [`SemanticPairs::apply`](crate::semantic_pairs::SemanticPairs::apply))
+    #[serde(skip)]
+    pub semantic: Option<SemanticSeparation>,
+}
+
+impl AccentOptSettings {
+    /// Anchor of the `index`-th accent solved with these settings
+    /// (base08 or base10 for 0, through base0F or base17 for 7).
+    pub fn anchor(&self, index: usize) -> Option<&AccentAnchor> {
+        self.anchors
+            .get(&format!("base0{:X}", 8 + index))
+            .or_else(|| self.anchors.get(&format!("base1{index:X}")))
+    }
+
+    /// Check that the `section` table's preferred contrast is above the
+    /// `minimum` its accents are held to.
+    fn validate_preferred_contrast(&self, section: &str, minimum: f64) -> Result<(), ConfigError> {
+        match self.preferred_contrast {
+            Some(preferred) if !(preferred > minimum && preferred.is_finite()) => {
+                Err(ConfigError::InvalidValue(format!(
+                    "{section}.preferred_contrast must be above the minimum contrast {minimum} (got {preferred})"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the anchors of the `section` table, whose accents are `slots`.
+    fn validate_anchors(&self, section: &str, slots: &[String]) -> Result<(), ConfigError> {
+        for (slot, anchor) in &self.anchors {
+            if !slots.contains(slot) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "{section}.anchors: '{slot}' is not one of {}-{}",
+                    slots[0],
+                    slots[slots.len() - 1]
+                )));
+            }
+            anchor.validate(&format!("{section}.anchors.{slot}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Default ΔE an anchored accent may move from its brand color.
+pub const DEFAULT_ANCHOR_TOLERANCE: f32 = 5.0;
+
+/// A brand color an accent must stay close to.
+///
+/// The solver keeps the accent's hue at the brand color's and searches J'
+/// and M within `tolerance` of it for the contrast floor, instead of the
+/// shared target box:
+///
+/// ```toml
+/// [optimization.anchors]
+/// base0D = { color = "#0969da", tolerance = 4.0 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccentAnchor {
+    /// Brand color (any CSS color format)
+    pub color: String,
+    /// Largest HellwigJmh ΔE the accent may move from `color`
+    #[serde(default = "default_anchor_tolerance")]
+    pub tolerance: f32,
+}
+
+fn default_anchor_tolerance() -> f32 {
+    DEFAULT_ANCHOR_TOLERANCE
+}
+
+impl AccentAnchor {
+    /// Anchor to `color` with the default tolerance.
+    pub fn new(color: impl Into<String>) -> Self {
+        Self {
+            color: color.into(),
+            tolerance: DEFAULT_ANCHOR_TOLERANCE,
+        }
+    }
+
+    /// The brand color in HellwigJmh, if `color` parses.
+    pub fn jmh(&self) -> Option<HellwigJmh> {
+        parse_color(&self.color).ok().map(HellwigJmh::from_srgb_u8)
+    }
+
+    fn validate(&self, key: &str) -> Result<(), ConfigError> {
+        parse_color(&self.color).map_err(|e| ConfigError::InvalidColor(format!("{key}: {e}")))?;
+        if !(self.tolerance > 0.0 && self.tolerance <= 50.0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "{key}.tolerance must be in (0, 50] (got {})",
+                self.tolerance
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl Default for AccentOptSettings {
@@ -264,19 +941,186 @@ impl Default for AccentOptSettings {
             delta_m: 6.0,
             j_weight: 0.75,
             contrast_weight: 0.8,
+            preferred_contrast: None,
+            cvd: None,
+            anchors: BTreeMap::new(),
+            solver: SolverSettings::default(),
+            joint: None,
+            semantic: None,
         }
     }
 }
 
+/// Joint optimization of all accents in a group (the `[optimization.joint]`
+/// and `[extended_optimization.joint]` tables).
+///
+/// Solved one at a time, each accent settles near the target J' on its own,
+/// which can leave the set uneven. Jointly, the objective also penalizes the
+/// variance of J' across the accents, so they trade some distance from the
+/// target for consistent lightness as a group:
+///
+/// ```toml
+/// [optimization.joint]
+/// group_weight = 2.0
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JointOptimization {
+    /// Weight of the J' variance term, relative to one accent's objective
+    pub group_weight: f32,
+}
+
+impl Default for JointOptimization {
+    fn default() -> Self {
+        Self { group_weight: 1.0 }
+    }
+}
+
+impl JointOptimization {
+    /// Check that the weight is a non-negative number.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.group_weight >= 0.0 && self.group_weight.is_finite()) {
+            return Err(ConfigError::InvalidValue(format!(
+                "joint.group_weight must be a non-negative number (got {})",
+                self.group_weight
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Method searching each accent's J'/M box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SolverMethod {
+    /// Constrained optimization by linear approximations
+    #[default]
+    Cobyla,
+    /// Simplex search on the objective plus a constraint penalty
+    NelderMead,
+    /// Grid over the box, refined around the best point
+    Grid,
+}
+
+impl SolverMethod {
+    /// Name as written in `[optimization.solver]`.
+    pub fn name(self) -> &'static str {
+        match self {
+            SolverMethod::Cobyla => "cobyla",
+            SolverMethod::NelderMead => "nelder-mead",
+            SolverMethod::Grid => "grid",
+        }
+    }
+}
+
+impl fmt::Display for SolverMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Accent solver selection and tuning (`[optimization.solver]`).
+///
+/// COBYLA handles most configurations; the other methods are an escape
+/// hatch for ones where it stalls:
+///
+/// ```toml
+/// [optimization.solver]
+/// method = "nelder-mead"
+/// max_iters = 800
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SolverSettings {
+    pub method: SolverMethod,
+    /// Iteration limit (refinement levels for the grid; default per method)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_iters: Option<u64>,
+    /// Final step size: COBYLA's trust region, the Nelder-Mead simplex, or
+    /// the grid spacing (default per method)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rhoend: Option<f64>,
+}
+
+impl SolverSettings {
+    /// Whether these are the default settings (COBYLA, untuned).
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Iteration limit, or the method's default.
+    pub fn max_iters(&self) -> u64 {
+        self.max_iters.unwrap_or(match self.method {
+            SolverMethod::Cobyla => 200,
+            SolverMethod::NelderMead => 400,
+            SolverMethod::Grid => 16,
+        })
+    }
+
+    /// Final step size, or the method's default.
+    pub fn rhoend(&self) -> f64 {
+        self.rhoend.unwrap_or(match self.method {
+            SolverMethod::Cobyla | SolverMethod::NelderMead => 1e-6,
+            SolverMethod::Grid => 1e-3,
+        })
+    }
+
+    /// Check the tuning of the `section` table's solver.
+    fn validate(&self, section: &str) -> Result<(), ConfigError> {
+        if self.max_iters == Some(0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "{section}.solver.max_iters must be at least 1"
+            )));
+        }
+        if let Some(rhoend) = self.rhoend
+            && !(rhoend > 0.0 && rhoend.is_finite())
+        {
+            return Err(ConfigError::InvalidValue(format!(
+                "{section}.solver.rhoend must be a positive number (got {rhoend})"
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl ThemeConfig {
     /// Save configuration to a TOML file.
+    ///
+    /// Values still equal to what the file's `$VAR` and `~` references
+    /// expanded to are written as the references, so the saved file stays
+    /// portable.
     pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
-        let content = toml::to_string_pretty(self)
+        let mut content = toml::to_string_pretty(self)
             .map_err(|e| ConfigError::Figment(Box::new(figment::Error::from(e.to_string()))))?;
+        if !self.unexpanded.is_empty() {
+            let mut document: toml_edit::DocumentMut = content
+                .parse()
+                .map_err(|e| ConfigError::InvalidValue(format!("failed to write config: {e}")))?;
+            restore_unexpanded(document.as_table_mut(), &self.unexpanded);
+            content = document.to_string();
+        }
         std::fs::write(path, content)?;
         Ok(())
     }
 
+    /// `overrides` with every unset hue taken from `[colors.harmony]`.
+    pub fn with_harmony(&self, mut overrides: [Option<f32>; 8]) -> [Option<f32>; 8] {
+        if let Some(harmony) = self.colors.harmony {
+            for (hue, harmonic) in overrides.iter_mut().zip(harmony.hues()) {
+                hue.get_or_insert(harmonic);
+            }
+        }
+        overrides
+    }
+
+    /// Parsed `[colors.hue_links]` (empty when unset).
+    pub fn hue_links(&self) -> Result<HueLinks, ConfigError> {
+        self.colors
+            .hue_links
+            .as_ref()
+            .map_or(Ok(HueLinks::default()), HueLinks::parse)
+    }
+
     /// Convert to GenerateConfig.
     ///
     /// Uses defaults for any unspecified values.
@@ -301,32 +1145,58 @@ impl ThemeConfig {
             .as_ref()
             .map(|h| h.to_array())
             .unwrap_or([None; 8]);
+        let hue_overrides = self.hue_links()?.resolve(self.with_harmony(hue_overrides));
 
         Ok(GenerateConfig {
             background,
             foreground,
+            foreground_headroom: self.colors.foreground_headroom.unwrap_or(0.0),
             hue_overrides,
+            adaptive_hues: self.colors.adaptive_hues.unwrap_or(true),
+            hue_spacing: self.colors.hue_spacing,
             min_contrast: self.contrast.minimum,
             extended_min_contrast: self.contrast.extended_minimum,
+            contrast_model: defaults.contrast_model,
             max_lightness_adjustment: self.contrast.max_adjustment,
+            comment_contrast: self.contrast.comment,
+            ramp: self.ramp,
             name: if self.theme.name.is_empty() {
                 defaults.name
             } else {
                 self.theme.name.clone()
             },
             author: self.theme.author.clone(),
+            description: self.theme.description.clone(),
             interpolation: self.curves.clone(),
-            accent_opt: self.optimization.clone(),
-            extended_accent_opt: self.extended_optimization.clone(),
+            accent_opt: self.validation.semantic.apply(&self.optimization, 0),
+            extended_accent_opt: self
+                .validation
+                .semantic
+                .apply(&self.extended_optimization, 1),
+            extended_accents: self.extended_accents,
+            gamut: self.colors.gamut,
+            pins: self
+                .colors
+                .pins
+                .iter()
+                .flatten()
+                .map(|(slot, color)| Ok((slot.clone(), parse_color(color)?)))
+                .collect::<Result<_, String>>()
+                .map_err(ConfigError::InvalidColor)?,
+            viewing: self.viewing,
+            ansi: self.ansi.clone().unwrap_or_default(),
         })
     }
 
     /// Create from a GenerateConfig.
     pub fn from_generate_config(config: &GenerateConfig) -> Self {
         Self {
+            config_version: CONFIG_VERSION,
+            extends: None,
             theme: ThemeMetadata {
                 name: config.name.clone(),
                 author: config.author.clone(),
+                description: config.description.clone(),
                 variant: None,
             },
             colors: ColorConfig {
@@ -339,15 +1209,46 @@ impl ThemeConfig {
                     config.foreground.red, config.foreground.green, config.foreground.blue
                 )),
                 hue_overrides: Some(HueOverrides::from_array(config.hue_overrides)),
+                foreground_headroom: (config.foreground_headroom > 0.0)
+                    .then_some(config.foreground_headroom),
+                adaptive_hues: (!config.adaptive_hues).then_some(false),
+                hue_spacing: config.hue_spacing,
+                gamut: config.gamut,
+                pins: (!config.pins.is_empty()).then(|| {
+                    config
+                        .pins
+                        .iter()
+                        .map(|(slot, color)| (slot.clone(), format!("#{}", srgb_to_hex(*color))))
+                        .collect()
+                }),
+                harmony: None,
+                hue_links: None,
             },
             curves: config.interpolation.clone(),
             contrast: ContrastConfig {
                 minimum: config.min_contrast,
                 extended_minimum: config.extended_min_contrast,
                 max_adjustment: config.max_lightness_adjustment,
+                comment: config.comment_contrast,
+                terminal: None,
+                standard: ContrastStandard::default(),
             },
+            validation: ValidationConfig::default(),
             optimization: config.accent_opt.clone(),
             extended_optimization: config.extended_accent_opt.clone(),
+            extended_accents: config.extended_accents,
+            ramp: config.ramp,
+            preview: PreviewConfig::default(),
+            roles: None,
+            daemon: None,
+            viewing: config.viewing,
+            ansi: (!config.ansi.is_default()).then(|| config.ansi.clone()),
+            surfaces: None,
+            displays: BTreeMap::new(),
+            hooks: HooksConfig::default(),
+            keys: KeysConfig::default(),
+            explore: ExploreConfig::default(),
+            unexpanded: Dict::new(),
         }
     }
 }
@@ -0,0 +1,1404 @@
+//! Config file schema: located diagnostics and JSON Schema export.
+//!
+//! Serde skips unknown keys and reports only the first bad value, without a
+//! position. `check_config` walks the parsed TOML against a static
+//! description of every table and reports all unknown keys (with the
+//! nearest valid name), type mismatches, out-of-range numbers, and invalid
+//! colors, each at its line and column. The same description is exported
+//! as a JSON Schema for editor completion and validation.
+//!
+//! Checks that relate several keys (comment `min` not above `max`,
+//! increasing ramp targets, ...) stay in `validate_config`.
+
+use std::fmt;
+
+use serde_json::{Map, Value, json};
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+use crate::curves::MANUAL_STOPS;
+use crate::env_expand::{expand, is_verbatim};
+use crate::generate::parse_color;
+use crate::hue_links::{accent_index, parse_link};
+use crate::keys::{ACTIONS, parse_key};
+use crate::migrate::CONFIG_VERSION;
+use crate::roles::is_slot;
+use crate::semantic_pairs::parse_semantic_pair;
+use crate::validation::parse_pair_rule;
+
+/// Pattern of a Base24 slot name (base00-base17, uppercase hex digits).
+const SLOT_PATTERN: &str = "^base(0[0-9A-F]|1[0-7])$";
+
+/// Pattern of a base accent slot name (base08-base0F, either case).
+const ACCENT_PATTERN: &str = "^base0[89A-Fa-f]$";
+
+/// Pattern of a hue link such as `base08 + 30`.
+const LINK_PATTERN: &str = r"^\s*base0[89A-Fa-f]\s*([+-]\s*[0-9]+(\.[0-9]+)?\s*°?)?\s*$";
+
+/// Pattern of a validation pair rule such as `base08 on base02 >= 45`.
+const PAIR_RULE_PATTERN: &str = r"^\s*[Bb][Aa][Ss][Ee][01][0-9A-Fa-f]\s+on\s+[Bb][Aa][Ss][Ee][01][0-9A-Fa-f]\s*(>=|≥)\s*[0-9]+(\.[0-9]+)?\s*$";
+
+/// Pattern of a semantic pair such as `base0B/base08`.
+const SEMANTIC_PAIR_PATTERN: &str =
+    r"^\s*[Bb][Aa][Ss][Ee](0[89A-Fa-f]|1[0-7])\s*/\s*[Bb][Aa][Ss][Ee](0[89A-Fa-f]|1[0-7])\s*$";
+
+/// A problem found in a config file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column (in characters)
+    pub column: usize,
+    /// Dotted key the problem belongs to (empty for syntax errors)
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        if !self.key.is_empty() {
+            write!(f, "{}: ", self.key)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// A key of a config table.
+struct Field {
+    name: &'static str,
+    /// Other accepted spellings (serde aliases)
+    aliases: &'static [&'static str],
+    kind: Kind,
+    doc: &'static str,
+}
+
+/// Accepted values for a key.
+enum Kind {
+    Table(&'static [Field]),
+    /// `[profile.<name>]` tables holding config sections
+    Profiles,
+    /// Tables of these fields keyed by a user-chosen name
+    Named(&'static [Field]),
+    /// Slot names mapped to colors
+    Pins,
+    /// Slot names mapped to anchor tables
+    Anchors,
+    /// Accent names mapped to hue links
+    HueLinks,
+    /// Editor action names mapped to a key or an array of keys
+    KeyBindings,
+    Number(Bounds),
+    Bool,
+    Text,
+    Path,
+    /// Any CSS color
+    Color,
+    Enum(&'static [&'static str]),
+    /// Array of these names
+    Enums(&'static [&'static str]),
+    /// Array of slot names
+    Slots,
+    /// Array of rules like `base08 on base02 >= 45`
+    PairRules,
+    /// Array of accent pairs like `base0B/base08`
+    SemanticPairs,
+    Paths,
+    /// Array of `[t, value]` pairs
+    ControlPoints,
+    /// Array of one number from 0 to 1 per surface step
+    Stops,
+}
+
+/// Allowed range of a number.
+struct Bounds {
+    min: f64,
+    /// Whether `min` itself is excluded
+    exclusive_min: bool,
+    max: Option<f64>,
+}
+
+const fn range(min: f64, max: f64) -> Kind {
+    Kind::Number(Bounds {
+        min,
+        exclusive_min: false,
+        max: Some(max),
+    })
+}
+
+const fn above(min: f64, max: Option<f64>) -> Kind {
+    Kind::Number(Bounds {
+        min,
+        exclusive_min: true,
+        max,
+    })
+}
+
+const fn field(name: &'static str, kind: Kind, doc: &'static str) -> Field {
+    Field {
+        name,
+        aliases: &[],
+        kind,
+        doc,
+    }
+}
+
+const CURVE_TYPES: &[&str] = &[
+    "linear",
+    "smoothstep",
+    "smootherstep",
+    "smooth_start",
+    "smooth_end",
+    "sigmoid",
+    "b_spline",
+    "cubic_bezier",
+    "manual",
+];
+
+const THEME: &[Field] = &[
+    field("name", Kind::Text, "Name of the theme"),
+    field("author", Kind::Text, "Author of the theme"),
+    field(
+        "description",
+        Kind::Text,
+        "Description of the theme (summarized from the parameters if unset)",
+    ),
+    field("variant", Kind::Text, "Variant hint (dark, light, auto)"),
+];
+
+const HUE_OVERRIDES: &[Field] = &[
+    field("base08", range(0.0, 360.0), "base08 (Red) hue in degrees"),
+    field(
+        "base09",
+        range(0.0, 360.0),
+        "base09 (Orange) hue in degrees",
+    ),
+    field(
+        "base0a",
+        range(0.0, 360.0),
+        "base0A (Yellow) hue in degrees",
+    ),
+    field("base0b", range(0.0, 360.0), "base0B (Green) hue in degrees"),
+    field("base0c", range(0.0, 360.0), "base0C (Cyan) hue in degrees"),
+    field("base0d", range(0.0, 360.0), "base0D (Blue) hue in degrees"),
+    field(
+        "base0e",
+        range(0.0, 360.0),
+        "base0E (Purple) hue in degrees",
+    ),
+    field(
+        "base0f",
+        range(0.0, 360.0),
+        "base0F (Magenta) hue in degrees",
+    ),
+];
+
+const HUE_SPACING: &[Field] = &[field(
+    "max_shift",
+    range(0.0, 45.0),
+    "Largest hue shift per accent, in degrees",
+)];
+
+const HARMONY: &[Field] = &[
+    field(
+        "rule",
+        Kind::Enum(&[
+            "analogous",
+            "complementary",
+            "split-complementary",
+            "triadic",
+            "tetradic",
+        ]),
+        "Color-harmony rule placing the accent hues",
+    ),
+    field(
+        "seed_hue",
+        range(0.0, 360.0),
+        "Hue the rule is anchored on, in degrees",
+    ),
+];
+
+const COLORS: &[Field] = &[
+    field(
+        "background",
+        Kind::Color,
+        "Background color (any CSS color format)",
+    ),
+    field(
+        "foreground",
+        Kind::Color,
+        "Foreground color (any CSS color format)",
+    ),
+    field(
+        "hue_overrides",
+        Kind::Table(HUE_OVERRIDES),
+        "Hue overrides for accent colors",
+    ),
+    field(
+        "foreground_headroom",
+        range(0.0, 100.0),
+        "J' to extend the UI ramp past the foreground",
+    ),
+    field(
+        "adaptive_hues",
+        Kind::Bool,
+        "Move default accent hues away from a tinted background's hue",
+    ),
+    field(
+        "hue_spacing",
+        Kind::Table(HUE_SPACING),
+        "Nudge accent hues apart for even perceptual spacing",
+    ),
+    field(
+        "gamut",
+        Kind::Enum(&["srgb", "display-p3", "p3", "rec2020"]),
+        "Gamut accents are optimized for",
+    ),
+    field("pins", Kind::Pins, "Slots held at an exact color"),
+    field(
+        "harmony",
+        Kind::Table(HARMONY),
+        "Accent hues from a color-harmony rule",
+    ),
+    field(
+        "hue_links",
+        Kind::HueLinks,
+        "Accent hues held at an offset from another accent's",
+    ),
+];
+
+const CURVE: &[Field] = &[
+    field("type", Kind::Enum(CURVE_TYPES), "Curve shape"),
+    field("strength", above(0.0, Some(10.0)), "Curve strength"),
+    field(
+        "control_points",
+        Kind::ControlPoints,
+        "[t, value] control points for b_spline curves, or the two [x, y] handles of a cubic_bezier",
+    ),
+    field(
+        "stops",
+        Kind::Stops,
+        "t of base00-base07, in order, for manual curves",
+    ),
+];
+
+const CURVES: &[Field] = &[
+    field("lightness", Kind::Table(CURVE), "Lightness (J') curve"),
+    field("chroma", Kind::Table(CURVE), "Colorfulness (M) curve"),
+    field("hue", Kind::Table(CURVE), "Hue curve"),
+];
+
+const COMMENT: &[Field] = &[
+    field(
+        "min",
+        range(15.0, 90.0),
+        "Lowest acceptable comment contrast (Lc)",
+    ),
+    field(
+        "max",
+        range(15.0, 90.0),
+        "Highest acceptable comment contrast (Lc)",
+    ),
+];
+
+const TERMINAL: &[Field] = &[
+    field(
+        "dim",
+        above(0.0, Some(1.0)),
+        "Luminance multiplier for dim text",
+    ),
+    field(
+        "bold",
+        range(1.0, 4.0),
+        "Luminance multiplier for bold text rendered as bright",
+    ),
+    field(
+        "dim_min_contrast",
+        range(15.0, 90.0),
+        "Lowest readable contrast (Lc) for dim accent text on base00",
+    ),
+];
+
+const CONTRAST: &[Field] = &[
+    Field {
+        name: "minimum",
+        aliases: &["target"],
+        kind: range(0.0, 108.0),
+        doc: "Minimum APCA contrast for accent colors (Lc)",
+    },
+    Field {
+        name: "extended_minimum",
+        aliases: &["extended"],
+        kind: range(0.0, 108.0),
+        doc: "Minimum APCA contrast for extended accent colors (Lc)",
+    },
+    field(
+        "max_adjustment",
+        range(0.0, 10.0),
+        "Maximum per-hue lightness adjustment (J')",
+    ),
+    field(
+        "comment",
+        Kind::Table(COMMENT),
+        "Target APCA contrast range for base03 (comments)",
+    ),
+    field(
+        "terminal",
+        Kind::Table(TERMINAL),
+        "How terminals render dim and bold text",
+    ),
+    Field {
+        name: "standard",
+        aliases: &["contrast_standard"],
+        kind: Kind::Enum(&["apca", "wcag", "both"]),
+        doc: "Contrast metric(s) validation must satisfy",
+    },
+];
+
+const VALIDATION: &[Field] = &[
+    field("base00", range(0.0, 108.0), "Minimum accent Lc on base00"),
+    field("base01", range(0.0, 108.0), "Minimum accent Lc on base01"),
+    field("base02", range(0.0, 108.0), "Minimum accent Lc on base02"),
+    field(
+        "light_on_dark",
+        range(-50.0, 50.0),
+        "Lc added to every threshold for light text on a dark background",
+    ),
+    field(
+        "dark_on_light",
+        range(-50.0, 50.0),
+        "Lc added to every threshold for dark text on a light background",
+    ),
+    field(
+        "pairs",
+        Kind::PairRules,
+        "Extra required pairs, e.g. \"base08 on base02 >= 45\"",
+    ),
+    field(
+        "semantic",
+        Kind::Table(SEMANTIC),
+        "Accent pairs that must stay distinguishable",
+    ),
+    field(
+        "variants",
+        Kind::Table(VARIANTS),
+        "How far accents may drift between the dark and light variants",
+    ),
+];
+
+const VARIANTS: &[Field] = &[
+    field(
+        "max_hue_shift",
+        above(0.0, Some(180.0)),
+        "Hue difference (degrees) allowed between variants",
+    ),
+    field(
+        "max_colorfulness_shift",
+        above(0.0, Some(100.0)),
+        "Colorfulness (M) difference allowed between variants",
+    ),
+];
+
+const SEMANTIC: &[Field] = &[
+    field(
+        "pairs",
+        Kind::SemanticPairs,
+        "Pairs as \"base0B/base08\", checked with and without simulated CVD",
+    ),
+    field(
+        "min_hue",
+        above(0.0, Some(180.0)),
+        "Hue difference (degrees) that tells a pair apart",
+    ),
+    field(
+        "min_lightness",
+        above(0.0, Some(50.0)),
+        "J' difference that tells a pair apart",
+    ),
+];
+
+const CVD: &[Field] = &[
+    field(
+        "min_delta_e",
+        above(0.0, Some(100.0)),
+        "Simulated ΔE each pair should keep",
+    ),
+    field(
+        "protanopia",
+        range(0.0, f64::INFINITY),
+        "Penalty weight under protanopia (0 disables)",
+    ),
+    field(
+        "deuteranopia",
+        range(0.0, f64::INFINITY),
+        "Penalty weight under deuteranopia (0 disables)",
+    ),
+    field(
+        "tritanopia",
+        range(0.0, f64::INFINITY),
+        "Penalty weight under tritanopia (0 disables)",
+    ),
+];
+
+const ANCHOR: &[Field] = &[
+    field("color", Kind::Color, "Brand color (any CSS color format)"),
+    field(
+        "tolerance",
+        above(0.0, Some(50.0)),
+        "Largest ΔE the accent may move from the brand color (default 5)",
+    ),
+];
+
+const SOLVER: &[Field] = &[
+    field(
+        "method",
+        Kind::Enum(&["cobyla", "nelder-mead", "grid"]),
+        "Method searching each accent's J'/M box",
+    ),
+    field(
+        "max_iters",
+        range(1.0, f64::INFINITY),
+        "Iteration limit (refinement levels for grid; default per method)",
+    ),
+    field(
+        "rhoend",
+        above(0.0, None),
+        "Final step size (default per method)",
+    ),
+];
+
+const JOINT: &[Field] = &[field(
+    "group_weight",
+    range(0.0, f64::INFINITY),
+    "Weight of the J' variance across accents",
+)];
+
+const OPTIMIZATION: &[Field] = &[
+    field("target_j", range(0.0, 100.0), "Target lightness (J')"),
+    field("target_m", range(0.0, 150.0), "Target colorfulness (M)"),
+    field(
+        "delta_j",
+        range(0.0, 100.0),
+        "Maximum deviation from target_j",
+    ),
+    field(
+        "delta_m",
+        range(0.0, 150.0),
+        "Maximum deviation from target_m",
+    ),
+    field(
+        "j_weight",
+        range(0.0, 1.0),
+        "Weight for J vs M uniformity (0 = M priority, 1 = J priority)",
+    ),
+    field(
+        "contrast_weight",
+        range(0.0, 1.0),
+        "Weight for contrast vs uniformity (0 = uniformity, 1 = contrast)",
+    ),
+    field(
+        "preferred_contrast",
+        above(0.0, None),
+        "Contrast worth reaching beyond the minimum, weighed against uniformity",
+    ),
+    field(
+        "cvd",
+        Kind::Table(CVD),
+        "Simulated-CVD separation between accents",
+    ),
+    field(
+        "anchors",
+        Kind::Anchors,
+        "Accents held near a brand color, keyed by slot",
+    ),
+    field(
+        "solver",
+        Kind::Table(SOLVER),
+        "Accent solver selection and tuning",
+    ),
+    field(
+        "joint",
+        Kind::Table(JOINT),
+        "Solve the accents together for even lightness",
+    ),
+];
+
+const EXTENDED_ACCENTS: &[Field] = &[
+    field(
+        "mode",
+        Kind::Enum(&["independent", "darker", "desaturated", "paired"]),
+        "How base10-base17 are derived",
+    ),
+    field(
+        "amount",
+        range(0.0, 100.0),
+        "J' offset, or percent colorfulness to remove",
+    ),
+];
+
+const RAMP: &[Field] = &[
+    field(
+        "base01",
+        above(0.0, Some(106.0)),
+        "Target Lc of base01 on base00",
+    ),
+    field(
+        "base02",
+        above(0.0, Some(106.0)),
+        "Target Lc of base02 on base00",
+    ),
+    field(
+        "base03",
+        above(0.0, Some(106.0)),
+        "Target Lc of base03 on base00",
+    ),
+    field(
+        "base04",
+        above(0.0, Some(106.0)),
+        "Target Lc of base04 on base00",
+    ),
+    field(
+        "base05",
+        above(0.0, Some(106.0)),
+        "Target Lc of base05 on base00",
+    ),
+    field(
+        "base06",
+        above(0.0, Some(106.0)),
+        "Target Lc of base06 on base00",
+    ),
+];
+
+const PREVIEW: &[Field] = &[
+    field(
+        "snippets_dir",
+        Kind::Path,
+        "Directory of source files shown in the code preview",
+    ),
+    field(
+        "gallery_dir",
+        Kind::Path,
+        "Directory of scheme files listed by the scheme gallery",
+    ),
+];
+
+const ROLES: &[Field] = &[
+    field("error", Kind::Slots, "Candidate slots for errors"),
+    field("warning", Kind::Slots, "Candidate slots for warnings"),
+    field("success", Kind::Slots, "Candidate slots for success"),
+    field("info", Kind::Slots, "Candidate slots for information"),
+    field("diff_added", Kind::Slots, "Candidate slots for added lines"),
+    field(
+        "diff_removed",
+        Kind::Slots,
+        "Candidate slots for removed lines",
+    ),
+    field(
+        "selection_bg",
+        Kind::Slots,
+        "Candidate slots for the selection background",
+    ),
+    field("cursor", Kind::Slots, "Candidate slots for the cursor"),
+    field(
+        "text_min_contrast",
+        range(0.0, 106.0),
+        "Minimum APCA contrast (Lc) for text roles on base00",
+    ),
+    field(
+        "selection_min_contrast",
+        range(0.0, 106.0),
+        "Minimum APCA contrast (Lc) for base05 text on the selection background",
+    ),
+    field(
+        "cursor_min_contrast",
+        range(0.0, 106.0),
+        "Minimum APCA contrast (Lc) for the cursor against base00",
+    ),
+];
+
+const SURFACES: &[Field] = &[field(
+    "steps",
+    range(2.0, 64.0),
+    "Number of shades from base00 to base07, both included",
+)];
+
+const HOOKS: &[Field] = &[field(
+    "post_generate",
+    Kind::Text,
+    "Shell command run after each scheme file is written",
+)];
+
+const EXPLORE: &[Field] = &[
+    field(
+        "hue_jitter",
+        range(0.0, 180.0),
+        "Furthest an accent hue moves either way, in degrees",
+    ),
+    field(
+        "min_target_m",
+        above(0.0, None),
+        "Lowest target colorfulness (M) for base08-base0F",
+    ),
+    field(
+        "max_target_m",
+        above(0.0, None),
+        "Highest target colorfulness (M) for base08-base0F",
+    ),
+    field(
+        "curve_types",
+        Kind::Enums(&[
+            "linear",
+            "smoothstep",
+            "smootherstep",
+            "smooth_start",
+            "smooth_end",
+            "sigmoid",
+        ]),
+        "Curves the lightness and chroma interpolation are drawn from",
+    ),
+    field(
+        "count",
+        range(1.0, f64::INFINITY),
+        "Candidates sampled by the interactive editor's shuffle",
+    ),
+];
+
+const DAEMON: &[Field] = &[
+    field(
+        "outputs",
+        Kind::Paths,
+        "Output files; the format is chosen by extension",
+    ),
+    field(
+        "terminals",
+        Kind::Paths,
+        "Terminal devices to recolor with OSC sequences",
+    ),
+];
+
+const VIEWING: &[Field] = &[
+    field(
+        "white_point",
+        Kind::Enum(&["d65", "d50", "d55", "d75", "a", "f2", "f11"]),
+        "White point the viewer is adapted to",
+    ),
+    field(
+        "adapting_luminance",
+        above(0.0, Some(10_000.0)),
+        "Adapting luminance L_A in cd/m²",
+    ),
+    field(
+        "surround",
+        Kind::Enum(&["dark", "dim", "average"]),
+        "Brightness of the area around the display",
+    ),
+];
+
+const HUE_BAND: &[Field] = &[
+    field("from", range(0.0, 360.0), "Start of the band in degrees"),
+    field(
+        "to",
+        range(0.0, 360.0),
+        "End of the band in degrees, counterclockwise from `from`",
+    ),
+];
+
+const ANSI_BANDS: &[Field] = &[
+    field(
+        "red",
+        Kind::Table(HUE_BAND),
+        "Hue band of base08 (ANSI 1/9)",
+    ),
+    field("orange", Kind::Table(HUE_BAND), "Hue band of base09"),
+    field(
+        "yellow",
+        Kind::Table(HUE_BAND),
+        "Hue band of base0A (ANSI 3/11)",
+    ),
+    field(
+        "green",
+        Kind::Table(HUE_BAND),
+        "Hue band of base0B (ANSI 2/10)",
+    ),
+    field(
+        "cyan",
+        Kind::Table(HUE_BAND),
+        "Hue band of base0C (ANSI 6/14)",
+    ),
+    field(
+        "blue",
+        Kind::Table(HUE_BAND),
+        "Hue band of base0D (ANSI 4/12)",
+    ),
+    field(
+        "purple",
+        Kind::Table(HUE_BAND),
+        "Hue band of base0E (ANSI 5/13)",
+    ),
+    field("magenta", Kind::Table(HUE_BAND), "Hue band of base0F"),
+];
+
+const ANSI: &[Field] = &[field(
+    "bands",
+    Kind::Table(ANSI_BANDS),
+    "Hue band each named accent must stay within",
+)];
+
+const DISPLAY: &[Field] = &[
+    field(
+        "gamma",
+        range(1.0, 3.0),
+        "Exponent of the display's tone curve",
+    ),
+    field(
+        "white_point",
+        Kind::Enum(&["d65", "d50", "d55", "d75", "a", "f2", "f11"]),
+        "White point the display is calibrated to",
+    ),
+];
+
+/// Top-level keys; `config_version` must stay first and `profile` last
+/// (see `sections`).
+const ROOT: &[Field] = &[
+    field(
+        "config_version",
+        range(1.0, CONFIG_VERSION as f64),
+        "Config layout version the file was written for",
+    ),
+    field(
+        "extends",
+        Kind::Text,
+        "Scheme file or slug this config starts from",
+    ),
+    field("theme", Kind::Table(THEME), "Theme metadata"),
+    field("colors", Kind::Table(COLORS), "Color settings"),
+    field(
+        "curves",
+        Kind::Table(CURVES),
+        "Interpolation curve settings",
+    ),
+    field("contrast", Kind::Table(CONTRAST), "Contrast settings"),
+    field(
+        "validation",
+        Kind::Table(VALIDATION),
+        "Accent thresholds per background and per polarity",
+    ),
+    field(
+        "optimization",
+        Kind::Table(OPTIMIZATION),
+        "Accent optimization settings for base08-base0F",
+    ),
+    field(
+        "extended_optimization",
+        Kind::Table(OPTIMIZATION),
+        "Accent optimization settings for base10-base17",
+    ),
+    field(
+        "extended_accents",
+        Kind::Table(EXTENDED_ACCENTS),
+        "How extended accents are derived from the base accents",
+    ),
+    field(
+        "ramp",
+        Kind::Table(RAMP),
+        "Target contrast steps for base01-base06",
+    ),
+    field("preview", Kind::Table(PREVIEW), "TUI preview settings"),
+    field("roles", Kind::Table(ROLES), "Semantic color roles"),
+    field(
+        "daemon",
+        Kind::Table(DAEMON),
+        "Outputs and terminals for --daemon",
+    ),
+    field(
+        "viewing",
+        Kind::Table(VIEWING),
+        "Viewing conditions the theme is optimized for",
+    ),
+    field(
+        "ansi",
+        Kind::Table(ANSI),
+        "ANSI hue bands; adds an ansi section to the output",
+    ),
+    field(
+        "surfaces",
+        Kind::Table(SURFACES),
+        "Extended surface ramp; adds a surfaces section to the output",
+    ),
+    field(
+        "displays",
+        Kind::Named(DISPLAY),
+        "Display calibrations --per-display writes copies for, by name",
+    ),
+    field("hooks", Kind::Table(HOOKS), "Commands run after generation"),
+    field(
+        "keys",
+        Kind::KeyBindings,
+        "Interactive editor key bindings, by action",
+    ),
+    field(
+        "explore",
+        Kind::Table(EXPLORE),
+        "Bounds for --explore and the interactive editor's shuffle",
+    ),
+    field(
+        "profile",
+        Kind::Profiles,
+        "Named profiles layered over the shared settings with --profile",
+    ),
+];
+
+/// Tables a profile may override (everything but `config_version` and
+/// `profile`).
+fn sections() -> &'static [Field] {
+    &ROOT[1..ROOT.len() - 1]
+}
+
+/// Check a config file's TOML source against the schema.
+///
+/// Returns every problem found, in source order; an empty list means the
+/// file is well-formed (cross-key rules are left to `validate_config`).
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::config_schema::check_config;
+///
+/// let diagnostics = check_config("[contrast]\nminimun = 80\nmax_adjustment = 40\n");
+/// assert_eq!(diagnostics.len(), 2);
+/// assert_eq!(diagnostics[0].line, 2);
+/// assert!(diagnostics[0].message.contains("did you mean 'minimum'"));
+/// assert_eq!(diagnostics[1].key, "contrast.max_adjustment");
+/// ```
+pub fn check_config(source: &str) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        source,
+        diagnostics: Vec::new(),
+    };
+    let (root, errors) = DeTable::parse_recoverable(source);
+    if errors.is_empty() {
+        checker.table(root.get_ref(), "", ROOT);
+    }
+    // A partial parse would report keys it lost track of, so syntax errors
+    // are reported alone
+    for error in errors {
+        let offset = error.span().map_or(0, |span| span.start);
+        checker.report(offset, "", error.message().trim_end());
+    }
+    checker.diagnostics.sort_by_key(|d| (d.line, d.column));
+    checker.diagnostics
+}
+
+struct Checker<'s> {
+    source: &'s str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, offset: usize, key: &str, message: impl Into<String>) {
+        let before = &self.source[..offset.min(self.source.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.diagnostics.push(Diagnostic {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            key: key.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn table(&mut self, table: &DeTable, path: &str, fields: &[Field]) {
+        for (key, value) in table.iter() {
+            let name: &str = key.get_ref();
+            let key_path = join(path, name);
+            match fields
+                .iter()
+                .find(|f| f.name == name || f.aliases.contains(&name))
+            {
+                Some(field) => self.value(value, &key_path, &field.kind),
+                None => {
+                    let names = fields
+                        .iter()
+                        .flat_map(|f| std::iter::once(f.name).chain(f.aliases.iter().copied()));
+                    let message = match suggest(name, names) {
+                        Some(s) => format!("unknown key (did you mean '{s}'?)"),
+                        None => "unknown key".to_string(),
+                    };
+                    self.report(key.span().start, &key_path, message);
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, value: &Spanned<DeValue>, path: &str, kind: &Kind) {
+        let offset = value.span().start;
+        match (kind, value.get_ref()) {
+            (Kind::Table(fields), DeValue::Table(table)) => self.table(table, path, fields),
+            (Kind::Profiles, DeValue::Table(profiles)) => {
+                for (name, profile) in profiles.iter() {
+                    let profile_path = join(path, name.get_ref());
+                    match profile.get_ref() {
+                        DeValue::Table(table) => self.table(table, &profile_path, sections()),
+                        other => self.mismatch(profile.span().start, &profile_path, kind, other),
+                    }
+                }
+            }
+            (Kind::Named(fields), DeValue::Table(entries)) => {
+                for (name, entry) in entries.iter() {
+                    let entry_path = join(path, name.get_ref());
+                    match entry.get_ref() {
+                        DeValue::Table(table) => self.table(table, &entry_path, fields),
+                        other => self.mismatch(entry.span().start, &entry_path, kind, other),
+                    }
+                }
+            }
+            (Kind::Pins | Kind::Anchors, DeValue::Table(slots)) => {
+                let entry = match kind {
+                    Kind::Anchors => &Kind::Table(ANCHOR),
+                    _ => &Kind::Color,
+                };
+                for (slot, value) in slots.iter() {
+                    let slot_path = join(path, slot.get_ref());
+                    if is_slot(slot.get_ref()) {
+                        self.value(value, &slot_path, entry);
+                    } else {
+                        self.report(
+                            slot.span().start,
+                            &slot_path,
+                            format!(
+                                "'{}' is not a slot (expected base00-base17)",
+                                slot.get_ref()
+                            ),
+                        );
+                    }
+                }
+            }
+            (Kind::HueLinks, DeValue::Table(links)) => {
+                for (slot, link) in links.iter() {
+                    let slot_path = join(path, slot.get_ref());
+                    if accent_index(slot.get_ref()).is_none() {
+                        self.report(
+                            slot.span().start,
+                            &slot_path,
+                            format!(
+                                "'{}' is not an accent (expected base08-base0F)",
+                                slot.get_ref()
+                            ),
+                        );
+                        continue;
+                    }
+                    match link.get_ref() {
+                        DeValue::String(expr) => {
+                            if let Err(e) = parse_link(expr) {
+                                self.report(link.span().start, &slot_path, e);
+                            }
+                        }
+                        other => self.mismatch(link.span().start, &slot_path, &Kind::Text, other),
+                    }
+                }
+            }
+            (Kind::KeyBindings, DeValue::Table(bindings)) => {
+                for (action, keys) in bindings.iter() {
+                    let action_path = join(path, action.get_ref());
+                    if !ACTIONS.contains(&action.get_ref().as_ref()) {
+                        let message = match suggest(action.get_ref(), ACTIONS.iter().copied()) {
+                            Some(s) => format!("unknown action (did you mean '{s}'?)"),
+                            None => "unknown action".to_string(),
+                        };
+                        self.report(action.span().start, &action_path, message);
+                        continue;
+                    }
+                    match keys.get_ref() {
+                        DeValue::String(_) => self.key(keys, &action_path),
+                        DeValue::Array(items) => {
+                            for item in items {
+                                self.key(item, &action_path);
+                            }
+                        }
+                        other => self.report(
+                            keys.span().start,
+                            &action_path,
+                            format!(
+                                "expected a key or an array of keys, found {}",
+                                type_name(other)
+                            ),
+                        ),
+                    }
+                }
+            }
+            (Kind::Number(bounds), DeValue::Integer(_) | DeValue::Float(_)) => {
+                if let Some(n) = number(value.get_ref())
+                    && let Some(expected) = bounds.violation(n)
+                {
+                    self.report(offset, path, format!("{expected} (got {n})"));
+                }
+            }
+            (Kind::Bool, DeValue::Boolean(_)) => {}
+            (Kind::Text | Kind::Path, DeValue::String(text)) => {
+                self.expand(offset, path, text);
+            }
+            (Kind::Color, DeValue::String(color)) => {
+                if let Some(color) = self.expand(offset, path, color)
+                    && let Err(e) = parse_color(&color)
+                {
+                    self.report(offset, path, e);
+                }
+            }
+            (Kind::Enum(values), DeValue::String(s)) => self.choice(offset, path, values, s),
+            (Kind::Stops, DeValue::Array(items)) => {
+                if items.len() != MANUAL_STOPS {
+                    self.report(
+                        offset,
+                        path,
+                        format!("expected {MANUAL_STOPS} stops (got {})", items.len()),
+                    );
+                }
+                for item in items {
+                    self.element(item, path, kind);
+                }
+            }
+            (
+                Kind::Enums(_)
+                | Kind::Slots
+                | Kind::PairRules
+                | Kind::SemanticPairs
+                | Kind::Paths
+                | Kind::ControlPoints,
+                DeValue::Array(items),
+            ) => {
+                for item in items {
+                    self.element(item, path, kind);
+                }
+            }
+            (kind, other) => self.mismatch(offset, path, kind, other),
+        }
+    }
+
+    /// `text` with environment variables expanded, as it will be loaded;
+    /// reports and returns `None` if it can't be.
+    fn expand(&mut self, offset: usize, path: &str, text: &str) -> Option<String> {
+        if is_verbatim(path) {
+            return Some(text.to_string());
+        }
+        expand(text)
+            .inspect_err(|e| self.report(offset, path, e.clone()))
+            .ok()
+    }
+
+    /// Check that `s` is one of `values`.
+    fn choice(&mut self, offset: usize, path: &str, values: &[&str], s: &str) {
+        if !values.contains(&s) {
+            let expected = values.join(", ");
+            let message = match suggest(s, values.iter().copied()) {
+                Some(v) => format!("'{s}' is not one of {expected} (did you mean '{v}'?)"),
+                None => format!("'{s}' is not one of {expected}"),
+            };
+            self.report(offset, path, message);
+        }
+    }
+
+    /// Check one element of an array-valued key.
+    fn element(&mut self, item: &Spanned<DeValue>, path: &str, kind: &Kind) {
+        let offset = item.span().start;
+        match (kind, item.get_ref()) {
+            (Kind::Enums(values), DeValue::String(s)) => self.choice(offset, path, values, s),
+            (Kind::Slots, DeValue::String(slot)) => {
+                if !is_slot(slot) {
+                    self.report(
+                        offset,
+                        path,
+                        format!("'{slot}' is not a slot (expected base00-base17)"),
+                    );
+                }
+            }
+            (Kind::PairRules, DeValue::String(rule)) => {
+                if let Err(e) = parse_pair_rule(rule) {
+                    self.report(offset, path, e);
+                }
+            }
+            (Kind::SemanticPairs, DeValue::String(pair)) => {
+                if let Err(e) = parse_semantic_pair(pair) {
+                    self.report(offset, path, e);
+                }
+            }
+            (Kind::Paths, DeValue::String(text)) => {
+                self.expand(offset, path, text);
+            }
+            (Kind::ControlPoints, DeValue::Array(pair))
+                if pair.len() == 2 && pair.iter().all(|v| number(v.get_ref()).is_some()) => {}
+            (Kind::ControlPoints, _) => self.report(offset, path, "expected a [t, value] pair"),
+            (Kind::Stops, value) if number(value).is_some_and(|n| (0.0..=1.0).contains(&n)) => {}
+            (Kind::Stops, _) => self.report(offset, path, "expected a number from 0 to 1"),
+            (_, other) => self.report(
+                offset,
+                path,
+                format!("expected a string, found {}", type_name(other)),
+            ),
+        }
+    }
+
+    /// Check one key of a `[keys]` entry.
+    fn key(&mut self, item: &Spanned<DeValue>, path: &str) {
+        match item.get_ref() {
+            DeValue::String(key) => {
+                if let Err(e) = parse_key(key) {
+                    self.report(item.span().start, path, e);
+                }
+            }
+            other => self.report(
+                item.span().start,
+                path,
+                format!("expected a string, found {}", type_name(other)),
+            ),
+        }
+    }
+
+    fn mismatch(&mut self, offset: usize, path: &str, kind: &Kind, found: &DeValue) {
+        self.report(
+            offset,
+            path,
+            format!("expected {}, found {}", kind.describe(), type_name(found)),
+        );
+    }
+}
+
+impl Kind {
+    fn describe(&self) -> &'static str {
+        match self {
+            Self::Table(_)
+            | Self::Profiles
+            | Self::Named(_)
+            | Self::Pins
+            | Self::Anchors
+            | Self::HueLinks
+            | Self::KeyBindings => "a table",
+            Self::Number(_) => "a number",
+            Self::Bool => "a boolean",
+            Self::Text | Self::Path | Self::Color | Self::Enum(_) => "a string",
+            Self::Enums(_) | Self::Slots | Self::PairRules | Self::SemanticPairs | Self::Paths => {
+                "an array of strings"
+            }
+            Self::ControlPoints => "an array of [t, value] pairs",
+            Self::Stops => "an array of numbers",
+        }
+    }
+}
+
+impl Bounds {
+    /// The range `n` should be in, if it is outside it.
+    fn violation(&self, n: f64) -> Option<String> {
+        let above_min = if self.exclusive_min {
+            n > self.min
+        } else {
+            n >= self.min
+        };
+        match self.max {
+            Some(max) if max.is_finite() => (!above_min || n > max).then(|| {
+                if self.exclusive_min {
+                    format!("must be in ({}, {max}]", self.min)
+                } else {
+                    format!("must be between {} and {max}", self.min)
+                }
+            }),
+            _ => (!above_min || !n.is_finite()).then(|| {
+                if self.exclusive_min {
+                    format!("must be greater than {}", self.min)
+                } else {
+                    format!("must be at least {}", self.min)
+                }
+            }),
+        }
+    }
+}
+
+/// Numeric value of an integer or float.
+fn number(value: &DeValue) -> Option<f64> {
+    match value {
+        DeValue::Integer(i) => i64::from_str_radix(i.as_str(), i.radix())
+            .ok()
+            .map(|n| n as f64),
+        DeValue::Float(f) => f.as_str().parse().ok(),
+        _ => None,
+    }
+}
+
+fn type_name(value: &DeValue) -> &'static str {
+    match value {
+        DeValue::String(_) => "a string",
+        DeValue::Integer(_) | DeValue::Float(_) => "a number",
+        DeValue::Boolean(_) => "a boolean",
+        DeValue::Datetime(_) => "a datetime",
+        DeValue::Array(_) => "an array",
+        DeValue::Table(_) => "a table",
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// The candidate closest to `input`, if it is a plausible typo.
+fn suggest<'a>(input: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let input = input.to_ascii_lowercase();
+    let limit = (input.len() / 3).max(2);
+    candidates
+        .map(|c| (edit_distance(&input, &c.to_ascii_lowercase()), c))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// JSON Schema (draft 2020-12) for config files, for editor completion
+/// and validation (e.g. with taplo or Even Better TOML).
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::config_schema::json_schema;
+///
+/// let schema = json_schema();
+/// let minimum = &schema["properties"]["contrast"]["properties"]["minimum"];
+/// assert_eq!(minimum["maximum"], 108.0);
+/// assert_eq!(schema["additionalProperties"], false);
+/// ```
+pub fn json_schema() -> Value {
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".into(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    schema.insert("title".into(), json!("themalingadingdong config"));
+    if let Value::Object(root) = table_schema(ROOT) {
+        schema.extend(root);
+    }
+    Value::Object(schema)
+}
+
+fn table_schema(fields: &[Field]) -> Value {
+    let mut properties = Map::new();
+    for field in fields {
+        let mut schema = kind_schema(&field.kind);
+        schema["description"] = json!(field.doc);
+        for alias in field.aliases {
+            let mut alias_schema = kind_schema(&field.kind);
+            alias_schema["description"] = json!(format!("Alias of `{}`", field.name));
+            properties.insert(alias.to_string(), alias_schema);
+        }
+        properties.insert(field.name.to_string(), schema);
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+fn kind_schema(kind: &Kind) -> Value {
+    match kind {
+        Kind::Table(fields) => table_schema(fields),
+        Kind::Profiles => json!({
+            "type": "object",
+            "additionalProperties": table_schema(sections()),
+        }),
+        Kind::Named(fields) => json!({
+            "type": "object",
+            "additionalProperties": table_schema(fields),
+        }),
+        Kind::Pins => json!({
+            "type": "object",
+            "propertyNames": { "pattern": SLOT_PATTERN },
+            "additionalProperties": { "type": "string" },
+        }),
+        Kind::HueLinks => json!({
+            "type": "object",
+            "propertyNames": { "pattern": ACCENT_PATTERN },
+            "additionalProperties": { "type": "string", "pattern": LINK_PATTERN },
+        }),
+        Kind::KeyBindings => json!({
+            "type": "object",
+            "propertyNames": { "enum": ACTIONS },
+            "additionalProperties": {
+                "oneOf": [
+                    { "type": "string" },
+                    { "type": "array", "items": { "type": "string" } },
+                ],
+            },
+        }),
+        Kind::Anchors => {
+            let mut anchor = table_schema(ANCHOR);
+            anchor["required"] = json!(["color"]);
+            json!({
+                "type": "object",
+                "propertyNames": { "pattern": SLOT_PATTERN },
+                "additionalProperties": anchor,
+            })
+        }
+        Kind::Number(bounds) => {
+            let mut schema = json!({ "type": "number" });
+            let min_key = if bounds.exclusive_min {
+                "exclusiveMinimum"
+            } else {
+                "minimum"
+            };
+            schema[min_key] = json!(bounds.min);
+            if let Some(max) = bounds.max.filter(|max| max.is_finite()) {
+                schema["maximum"] = json!(max);
+            }
+            schema
+        }
+        Kind::Bool => json!({ "type": "boolean" }),
+        Kind::Text | Kind::Path | Kind::Color => json!({ "type": "string" }),
+        Kind::Enum(values) => json!({ "type": "string", "enum": values }),
+        Kind::Enums(values) => json!({
+            "type": "array",
+            "items": { "type": "string", "enum": values },
+        }),
+        Kind::Slots => json!({
+            "type": "array",
+            "items": { "type": "string", "pattern": SLOT_PATTERN },
+        }),
+        Kind::PairRules => json!({
+            "type": "array",
+            "items": { "type": "string", "pattern": PAIR_RULE_PATTERN },
+        }),
+        Kind::SemanticPairs => json!({
+            "type": "array",
+            "items": { "type": "string", "pattern": SEMANTIC_PAIR_PATTERN },
+        }),
+        Kind::Paths => json!({ "type": "array", "items": { "type": "string" } }),
+        Kind::ControlPoints => json!({
+            "type": "array",
+            "items": {
+                "type": "array",
+                "items": { "type": "number" },
+                "minItems": 2,
+                "maxItems": 2,
+            },
+        }),
+        Kind::Stops => json!({
+            "type": "array",
+            "items": { "type": "number", "minimum": 0, "maximum": 1 },
+            "minItems": MANUAL_STOPS,
+            "maxItems": MANUAL_STOPS,
+        }),
+    }
+}
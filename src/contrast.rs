@@ -0,0 +1,167 @@
+//! Pluggable contrast models.
+//!
+//! The accent solver and validation measure contrast through a
+//! [`ContrastModel`], APCA unless told otherwise. Michelson and Weber
+//! contrast are included for comparison, and any other formula can be
+//! plugged in by implementing the trait.
+//!
+//! Every model works from the same screen luminances: relative luminance
+//! with APCA's soft clamp near black, which stands in for display flare.
+//! Results are signed like APCA Lc (positive for dark text on a light
+//! background) and scaled so thresholds read like Lc: Michelson and Weber
+//! are percentages.
+
+use std::fmt;
+use std::sync::{Arc, LazyLock};
+
+use palette::Srgb;
+
+use crate::apca::{apca_contrast, contrast_from_luminances, srgb_to_luminance};
+
+/// A formula for the contrast of text on a background.
+///
+/// # Example
+///
+/// A custom model, plugged into generation:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use themalingadingdong::contrast::ContrastModel;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// /// Luminance difference in percent
+/// #[derive(Debug)]
+/// struct Difference;
+///
+/// impl ContrastModel for Difference {
+///     fn name(&self) -> &str {
+///         "difference"
+///     }
+///
+///     fn contrast_from_luminances(&self, y_fg: f64, y_bg: f64) -> f64 {
+///         (y_bg - y_fg) * 100.0
+///     }
+/// }
+///
+/// let config = GenerateConfig {
+///     contrast_model: Arc::new(Difference),
+///     min_contrast: 30.0,
+///     extended_min_contrast: 25.0,
+///     ..Default::default()
+/// };
+/// let result = generate(&config);
+/// assert!(result.base_accent_results.iter().all(|r| r.achieved_contrast > 25.0));
+/// ```
+pub trait ContrastModel: fmt::Debug + Send + Sync {
+    /// Name shown in reports.
+    fn name(&self) -> &str;
+
+    /// Prefix for contrast values in warnings, e.g. `Lc` in "Lc 52.1 < 60.0".
+    fn label(&self) -> &str {
+        self.name()
+    }
+
+    /// Signed contrast of a foreground on a background, given their screen
+    /// luminances (see [`srgb_to_luminance`]).
+    fn contrast_from_luminances(&self, y_fg: f64, y_bg: f64) -> f64;
+
+    /// Signed contrast of `fg` on `bg`.
+    fn contrast(&self, fg: Srgb<u8>, bg: Srgb<u8>) -> f64 {
+        self.contrast_from_luminances(srgb_to_luminance(fg), srgb_to_luminance(bg))
+    }
+}
+
+/// The default model (APCA), shared so configs built from it compare equal
+/// in [`GenerationCache`](crate::generate::GenerationCache) keys.
+pub fn default_model() -> Arc<dyn ContrastModel> {
+    static DEFAULT: LazyLock<Arc<dyn ContrastModel>> = LazyLock::new(|| Arc::new(Apca));
+    Arc::clone(&DEFAULT)
+}
+
+/// APCA Lc (WCAG 3.0 draft), the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Apca;
+
+impl ContrastModel for Apca {
+    fn name(&self) -> &str {
+        "APCA"
+    }
+
+    fn label(&self) -> &str {
+        "Lc"
+    }
+
+    fn contrast_from_luminances(&self, y_fg: f64, y_bg: f64) -> f64 {
+        contrast_from_luminances(y_fg, y_bg)
+    }
+
+    fn contrast(&self, fg: Srgb<u8>, bg: Srgb<u8>) -> f64 {
+        apca_contrast(fg, bg)
+    }
+}
+
+/// Michelson contrast, `(Ybg - Yfg) / (Ybg + Yfg)`, in percent (0-100).
+///
+/// Symmetric in polarity: swapping text and background only flips the sign.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::contrast::{ContrastModel, Michelson};
+///
+/// let black = Srgb::new(0u8, 0, 0);
+/// let white = Srgb::new(255u8, 255, 255);
+/// let dark_on_light = Michelson.contrast(black, white);
+/// assert!(dark_on_light > 90.0 && dark_on_light <= 100.0);
+/// assert_eq!(Michelson.contrast(white, black), -dark_on_light);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Michelson;
+
+impl ContrastModel for Michelson {
+    fn name(&self) -> &str {
+        "Michelson"
+    }
+
+    fn contrast_from_luminances(&self, y_fg: f64, y_bg: f64) -> f64 {
+        let sum = y_bg + y_fg;
+        if sum <= 0.0 {
+            return 0.0;
+        }
+        (y_bg - y_fg) / sum * 100.0
+    }
+}
+
+/// Weber contrast against the background, `(Ybg - Yfg) / Ybg`, in percent.
+///
+/// Dark text on a light background tops out at 100; light text on a dark
+/// background is unbounded, since the background luminance is the divisor.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::contrast::{ContrastModel, Weber};
+///
+/// let black = Srgb::new(0u8, 0, 0);
+/// let white = Srgb::new(255u8, 255, 255);
+/// assert!(Weber.contrast(black, white) <= 100.0);
+/// assert!(Weber.contrast(white, black) < -1000.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Weber;
+
+impl ContrastModel for Weber {
+    fn name(&self) -> &str {
+        "Weber"
+    }
+
+    fn contrast_from_luminances(&self, y_fg: f64, y_bg: f64) -> f64 {
+        if y_bg <= 0.0 {
+            return 0.0;
+        }
+        (y_bg - y_fg) / y_bg * 100.0
+    }
+}
@@ -0,0 +1,192 @@
+//! APCA contrast between every pair of palette slots.
+//!
+//! Validation only checks the pairs a scheme is designed around (text on
+//! base00/base01, accents on base00-base02). Templates combine slots more
+//! freely, e.g. base03 on base01 status bars or accents on base03, so the
+//! matrix reports all 24×24 combinations and marks the validated pairs that
+//! fall below their threshold.
+
+use std::fmt::Write;
+
+use float_cmp::approx_eq;
+use palette::Srgb;
+use tinted_builder::Base16Scheme;
+
+use crate::apca::apca_contrast;
+use crate::validation::{ValidationConfig, accent_validation_pairs, ui_validation_pairs};
+
+/// Every Base24 slot, in matrix order.
+pub const SLOTS: [&str; 24] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07", "base08",
+    "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F", "base10", "base11",
+    "base12", "base13", "base14", "base15", "base16", "base17",
+];
+
+/// Slot name for palette index 0-23 (base00-base17).
+pub fn slot_name(index: usize) -> &'static str {
+    SLOTS[index]
+}
+
+/// Signed APCA Lc for each foreground (row) on each background (column).
+#[derive(Debug, Clone)]
+pub struct ContrastMatrix {
+    /// Slots present in the scheme, in [`SLOTS`] order
+    pub slots: Vec<&'static str>,
+    values: Vec<Vec<f64>>,
+}
+
+/// A validated pair whose contrast is below its threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixFailure {
+    pub foreground: &'static str,
+    pub background: &'static str,
+    /// APCA contrast magnitude
+    pub lc: f64,
+    /// Minimum Lc for the pair
+    pub min_lc: f64,
+}
+
+impl ContrastMatrix {
+    /// Compute the matrix for every slot present in `scheme`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::contrast_matrix::ContrastMatrix;
+    /// use themalingadingdong::generate::{GenerateConfig, generate};
+    ///
+    /// let scheme = generate(&GenerateConfig::default()).scheme;
+    /// let matrix = ContrastMatrix::new(&scheme);
+    ///
+    /// assert_eq!(matrix.slots.len(), 24);
+    /// assert_eq!(matrix.get("base05", "base05"), Some(0.0));
+    /// assert!(matrix.get("base07", "base00").unwrap().abs() > 75.0);
+    /// ```
+    pub fn new(scheme: &Base16Scheme) -> Self {
+        let colors: Vec<(&'static str, Srgb<u8>)> = SLOTS
+            .iter()
+            .filter_map(|slot| {
+                let c = scheme.palette.get(*slot)?;
+                Some((*slot, Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2)))
+            })
+            .collect();
+        let values = colors
+            .iter()
+            .map(|(_, fg)| {
+                colors
+                    .iter()
+                    .map(|(_, bg)| apca_contrast(*fg, *bg))
+                    .collect()
+            })
+            .collect();
+        Self {
+            slots: colors.into_iter().map(|(slot, _)| slot).collect(),
+            values,
+        }
+    }
+
+    fn index(&self, slot: &str) -> Option<usize> {
+        self.slots.iter().position(|s| *s == slot)
+    }
+
+    /// Signed Lc of `fg` text on `bg`, if both slots are present.
+    pub fn get(&self, fg: &str, bg: &str) -> Option<f64> {
+        Some(self.values[self.index(fg)?][self.index(bg)?])
+    }
+
+    /// Minimum Lc validation requires for `fg` on `bg` with the default
+    /// thresholds, if the pair is validated.
+    pub fn threshold(fg: &str, bg: &str) -> Option<f64> {
+        ui_validation_pairs()
+            .into_iter()
+            .chain(accent_validation_pairs(&ValidationConfig::default()))
+            .find(|p| p.foreground == fg && p.background == bg)
+            .map(|p| p.threshold.min_lc)
+    }
+
+    /// The failure for `fg` on `bg`, if it is a validated pair below its
+    /// threshold (with the same 0.5 Lc tolerance as validation).
+    pub fn failure(&self, fg: &str, bg: &str) -> Option<MatrixFailure> {
+        let lc = self.get(fg, bg)?.abs();
+        let min_lc = Self::threshold(fg, bg)?;
+        if lc > min_lc || approx_eq!(f64, lc, min_lc, epsilon = 0.5) {
+            return None;
+        }
+        let foreground = self.slots[self.index(fg)?];
+        let background = self.slots[self.index(bg)?];
+        Some(MatrixFailure {
+            foreground,
+            background,
+            lc,
+            min_lc,
+        })
+    }
+
+    /// Whether `fg` on `bg` is a validated pair below its threshold.
+    pub fn is_below_threshold(&self, fg: &str, bg: &str) -> bool {
+        self.failure(fg, bg).is_some()
+    }
+
+    /// Validated pairs below their threshold, in row-major order.
+    pub fn failures(&self) -> Vec<MatrixFailure> {
+        self.slots
+            .iter()
+            .flat_map(|fg| self.slots.iter().map(move |bg| (fg, bg)))
+            .filter_map(|(fg, bg)| self.failure(fg, bg))
+            .collect()
+    }
+}
+
+/// Render the matrix as a plain-text table of Lc magnitudes.
+///
+/// Rows are foregrounds and columns backgrounds, labelled by slot suffix
+/// (`00`-`17`). Validated pairs below their threshold are marked with `!`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::contrast_matrix::{ContrastMatrix, render_table};
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let table = render_table(&ContrastMatrix::new(&scheme));
+///
+/// assert!(table.lines().any(|l| l.starts_with("base0D")));
+/// ```
+pub fn render_table(matrix: &ContrastMatrix) -> String {
+    let mut out = String::new();
+    out.push_str("Contrast matrix (APCA |Lc|, rows = foreground, columns = background):\n");
+    let mut header = String::from("fg\\bg ");
+    for bg in &matrix.slots {
+        let _ = write!(header, "{:>4} ", &bg[4..]);
+    }
+    let _ = writeln!(out, "{}", header.trim_end());
+    for &fg in &matrix.slots {
+        let mut row = String::from(fg);
+        for &bg in &matrix.slots {
+            let lc = matrix.get(fg, bg).unwrap_or_default().abs();
+            let mark = if matrix.is_below_threshold(fg, bg) {
+                '!'
+            } else {
+                ' '
+            };
+            let _ = write!(row, "{lc:>4.0}{mark}");
+        }
+        let _ = writeln!(out, "{}", row.trim_end());
+    }
+
+    let failures = matrix.failures();
+    if failures.is_empty() {
+        out.push_str("All validated pairs meet their thresholds.\n");
+    } else {
+        let _ = writeln!(out, "! {} validated pairs below threshold:", failures.len());
+        for f in &failures {
+            let _ = writeln!(
+                out,
+                "  {} on {}: Lc {:.1} < {:.0}",
+                f.foreground, f.background, f.lc, f.min_lc
+            );
+        }
+    }
+    out
+}
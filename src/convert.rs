@@ -0,0 +1,164 @@
+//! Single-color conversions between sRGB hex, OKLCH, and HellwigJmh.
+//!
+//! Backs the `convert` subcommand: parse one color, report it in each
+//! notation along with which gamuts contain it and how much colorfulness
+//! each gamut allows at its lightness and hue.
+
+use std::fmt;
+
+use palette::convert::IntoColorUnclamped;
+use palette::{Oklch, Srgb};
+
+use crate::css_color::parse_linear;
+use crate::gamut_map::{Gamut, max_colorfulness_in};
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::srgb_to_hex;
+
+/// Gamuts reported on, narrowest first.
+const GAMUTS: [Gamut; 3] = [Gamut::Srgb, Gamut::DisplayP3, Gamut::Rec2020];
+
+/// OKLCH chroma below which the hue is reported as 0.
+const ACHROMATIC_CHROMA: f32 = 1e-4;
+
+/// One color expressed in every supported notation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorReport {
+    /// The color in HellwigJmh
+    pub jmh: HellwigJmh,
+    /// sRGB hex, gamut-mapped when the color lies outside sRGB
+    pub hex: String,
+    /// The color in OKLCH (unclamped, so wide-gamut colors survive)
+    pub oklch: Oklch<f32>,
+    /// Whether each gamut contains the color
+    pub in_gamut: [(Gamut, bool); 3],
+    /// Largest M each gamut allows at this J' and hue
+    pub max_colorfulness: [(Gamut, f32); 3],
+}
+
+impl ColorReport {
+    /// Build the report for a HellwigJmh color.
+    pub fn new(jmh: HellwigJmh) -> Self {
+        let oklch: Oklch<f32> = jmh.into_linear_srgb_unclamped().into_color_unclamped();
+        Self {
+            jmh,
+            hex: format!("#{}", srgb_to_hex(jmh.into_srgb_u8_gamut_mapped())),
+            oklch,
+            in_gamut: GAMUTS.map(|g| (g, g.contains(jmh))),
+            max_colorfulness: GAMUTS.map(|g| (g, max_colorfulness_in(jmh.lightness, jmh.hue, g))),
+        }
+    }
+
+    /// Build the report for an sRGB color (channels may exceed 0.0-1.0).
+    ///
+    /// In-range channels are reported exactly rather than round-tripped
+    /// through HellwigJmh, which can land a hair outside the gamut.
+    pub fn from_srgb(srgb: Srgb<f32>) -> Self {
+        let mut report = Self::new(HellwigJmh::from_srgb(srgb));
+        let in_range = [srgb.red, srgb.green, srgb.blue]
+            .iter()
+            .all(|c| (0.0..=1.0).contains(c));
+        if in_range {
+            report.hex = format!("#{}", srgb_to_hex(srgb.into_format()));
+            report.in_gamut = GAMUTS.map(|g| (g, true));
+        }
+        report
+    }
+
+    /// Whether the color is representable in sRGB (i.e. `hex` is exact).
+    pub fn in_srgb(&self) -> bool {
+        self.in_gamut[0].1
+    }
+}
+
+impl fmt::Display for ColorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mapped = if self.in_srgb() {
+            ""
+        } else {
+            " (gamut-mapped)"
+        };
+        writeln!(f, "hex     {}{mapped}", self.hex)?;
+        // Hue is meaningless (rounding noise) for achromatic colors
+        let oklch_hue = if self.oklch.chroma < ACHROMATIC_CHROMA {
+            0.0
+        } else {
+            self.oklch.hue.into_positive_degrees()
+        };
+        writeln!(
+            f,
+            "oklch   oklch({:.2}% {:.4} {:.2})",
+            self.oklch.l * 100.0,
+            self.oklch.chroma,
+            oklch_hue
+        )?;
+        writeln!(
+            f,
+            "jmh     jmh({:.2} {:.2} {:.2})",
+            self.jmh.lightness, self.jmh.colorfulness, self.jmh.hue
+        )?;
+        let gamuts: Vec<String> = self
+            .in_gamut
+            .iter()
+            .map(|(g, inside)| format!("{g} {}", if *inside { "yes" } else { "no" }))
+            .collect();
+        writeln!(f, "gamut   {}", gamuts.join(", "))?;
+        let limits: Vec<String> = self
+            .max_colorfulness
+            .iter()
+            .map(|(g, m)| format!("{g} {m:.1}"))
+            .collect();
+        writeln!(
+            f,
+            "max M   {} (at J' {:.1}, h {:.1})",
+            limits.join(", "),
+            self.jmh.lightness,
+            self.jmh.hue
+        )
+    }
+}
+
+/// Parse and describe a color given in any CSS format (hex, rgb(),
+/// oklch(), color(), ...) or as HellwigJmh written `jmh(J' M h)`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::convert::convert_color;
+///
+/// let red = convert_color("#ff0000").unwrap();
+/// assert_eq!(red.hex, "#ff0000");
+/// assert!(red.in_srgb());
+///
+/// let green = convert_color("jmh(60 90 150)").unwrap();
+/// assert!(!green.in_srgb());
+/// assert_eq!(green.jmh.hue, 150.0);
+/// ```
+pub fn convert_color(input: &str) -> Result<ColorReport, String> {
+    let trimmed = input.trim();
+    if let Some(args) = trimmed
+        .get(..4)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("jmh("))
+        .and_then(|_| trimmed[4..].strip_suffix(')'))
+    {
+        return parse_jmh_args(args).map(ColorReport::new).ok_or_else(|| {
+            format!("Invalid color '{input}': expected jmh(J' M h) with three numbers")
+        });
+    }
+
+    let linear = parse_linear(trimmed)?;
+    Ok(ColorReport::from_srgb(Srgb::from_linear(linear)))
+}
+
+fn parse_jmh_args(args: &str) -> Option<HellwigJmh> {
+    let values: Vec<f32> = args
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let [j, m, h] = values[..] else {
+        return None;
+    };
+    (j.is_finite() && m.is_finite() && h.is_finite() && m >= 0.0)
+        .then(|| HellwigJmh::new(j, m, h.rem_euclid(360.0)))
+}
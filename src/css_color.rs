@@ -0,0 +1,140 @@
+//! CSS color parsing for color inputs (`--background`, config colors, pins).
+//!
+//! Accepts everything CSS Color 4 lets you write in a stylesheet: hex,
+//! named colors (`rebeccapurple`), `rgb()`, `hsl()`, `hwb()`, `lab()`,
+//! `lch()`, `oklab()`, `oklch()`, and `color()` with the `srgb`,
+//! `srgb-linear`, `display-p3`, `rec2020` and `xyz-d65` spaces. Alpha is
+//! ignored.
+//!
+//! Colors outside the target gamut are gamut mapped in Hellwig JMh (keeping
+//! lightness and hue) rather than clipped per channel, which would shift the
+//! hue of a saturated `oklch()` or Display-P3 input.
+
+use csscolorparser::Color as CssColor;
+use palette::convert::IntoColorUnclamped;
+use palette::white_point::D65;
+use palette::{LinSrgb, Srgb, Xyz};
+
+use crate::gamut_map::{Gamut, gamut_map, gamut_map_to};
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::srgb_to_u8;
+
+/// Slack for treating linear channels as inside sRGB (float round-off).
+const SRGB_TOLERANCE: f32 = 1e-5;
+
+/// Parse a CSS color into unclamped linear sRGB.
+///
+/// Wide-gamut colors have channels outside 0-1.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::css_color::parse_linear;
+///
+/// let p3_green = parse_linear("color(display-p3 0 1 0)").unwrap();
+/// assert!(p3_green.red < 0.0 && p3_green.green > 1.0);
+/// ```
+pub fn parse_linear(input: &str) -> Result<LinSrgb<f32>, String> {
+    let trimmed = input.trim();
+    if let Some(args) = strip_function(trimmed, "color") {
+        return parse_color_function(args).map_err(|e| format!("Invalid color '{input}': {e}"));
+    }
+    let css: CssColor = trimmed
+        .parse()
+        .map_err(|e| format!("Invalid color '{input}': {e}"))?;
+    let [r, g, b, _a] = css.to_linear_rgba();
+    Ok(LinSrgb::new(r, g, b))
+}
+
+/// Parse a CSS color and map it into `gamut`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::css_color::parse_color_in;
+/// use themalingadingdong::gamut_map::Gamut;
+///
+/// // Display-P3 red keeps its colorfulness in P3 but not in sRGB
+/// let p3 = parse_color_in("color(display-p3 1 0 0)", Gamut::DisplayP3).unwrap();
+/// let srgb = parse_color_in("color(display-p3 1 0 0)", Gamut::Srgb).unwrap();
+/// assert!(p3.colorfulness > srgb.colorfulness);
+/// assert!((p3.hue - srgb.hue).abs() < 1.0);
+/// ```
+pub fn parse_color_in(input: &str, gamut: Gamut) -> Result<HellwigJmh, String> {
+    let linear = parse_linear(input)?;
+    Ok(gamut_map_to(HellwigJmh::from_linear_srgb(linear), gamut))
+}
+
+/// Parse a CSS color into 8-bit sRGB.
+///
+/// Colors inside sRGB convert exactly; colors outside are gamut mapped.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::css_color::parse_srgb;
+///
+/// assert_eq!(parse_srgb("rebeccapurple").unwrap(), Srgb::new(102u8, 51, 153));
+/// assert_eq!(parse_srgb("hsl(0 100% 50%)").unwrap(), Srgb::new(255u8, 0, 0));
+/// assert_eq!(parse_srgb("color(srgb 1 0.5 0)").unwrap(), Srgb::new(255u8, 128, 0));
+/// ```
+pub fn parse_srgb(input: &str) -> Result<Srgb<u8>, String> {
+    let linear = parse_linear(input)?;
+    let in_srgb = [linear.red, linear.green, linear.blue]
+        .iter()
+        .all(|c| (-SRGB_TOLERANCE..=1.0 + SRGB_TOLERANCE).contains(c));
+    let srgb = if in_srgb {
+        Srgb::from_linear(LinSrgb::new(
+            linear.red.clamp(0.0, 1.0),
+            linear.green.clamp(0.0, 1.0),
+            linear.blue.clamp(0.0, 1.0),
+        ))
+    } else {
+        gamut_map(HellwigJmh::from_linear_srgb(linear)).into_srgb()
+    };
+    Ok(srgb_to_u8(srgb))
+}
+
+/// The argument list of `name(...)`, matched case-insensitively.
+fn strip_function<'a>(input: &'a str, name: &str) -> Option<&'a str> {
+    let open = input.find('(')?;
+    if !input[..open].trim_end().eq_ignore_ascii_case(name) {
+        return None;
+    }
+    input[open + 1..].strip_suffix(')')
+}
+
+/// Parse the arguments of `color(<space> c1 c2 c3 [/ alpha])`.
+fn parse_color_function(args: &str) -> Result<LinSrgb<f32>, String> {
+    let components = args.split('/').next().unwrap_or_default();
+    let mut tokens = components.split_whitespace();
+    let space = tokens
+        .next()
+        .ok_or("color() needs a color space")?
+        .to_ascii_lowercase();
+    let values = tokens
+        .map(|token| {
+            if token.eq_ignore_ascii_case("none") {
+                Ok(0.0)
+            } else if let Some(percent) = token.strip_suffix('%') {
+                percent.parse::<f32>().map(|p| p / 100.0)
+            } else {
+                token.parse::<f32>()
+            }
+            .map_err(|_| format!("invalid component '{token}'"))
+        })
+        .collect::<Result<Vec<f32>, String>>()?;
+    let [a, b, c] = values[..] else {
+        return Err(format!("color() needs 3 components, got {}", values.len()));
+    };
+
+    match space.as_str() {
+        "srgb" => Ok(Gamut::Srgb.decode([a, b, c])),
+        "srgb-linear" => Ok(LinSrgb::new(a, b, c)),
+        "display-p3" => Ok(Gamut::DisplayP3.decode([a, b, c])),
+        "rec2020" => Ok(Gamut::Rec2020.decode([a, b, c])),
+        "xyz" | "xyz-d65" => Ok(Xyz::<D65, f32>::new(a, b, c).into_color_unclamped()),
+        other => Err(format!("unsupported color space '{other}'")),
+    }
+}
@@ -22,6 +22,10 @@ pub enum CurveType {
     Sigmoid,
     /// Custom B-spline with control points
     BSpline,
+    /// CSS-style cubic Bézier easing through two control points
+    CubicBezier,
+    /// Hand-placed output t for each surface step
+    Manual,
 }
 
 impl CurveType {
@@ -34,20 +38,24 @@ impl CurveType {
             Self::SmoothStart => Self::SmoothEnd,
             Self::SmoothEnd => Self::Sigmoid,
             Self::Sigmoid => Self::BSpline,
-            Self::BSpline => Self::Linear,
+            Self::BSpline => Self::CubicBezier,
+            Self::CubicBezier => Self::Manual,
+            Self::Manual => Self::Linear,
         }
     }
 
     /// Get the previous curve type in sequence.
     pub fn prev(self) -> Self {
         match self {
-            Self::Linear => Self::BSpline,
+            Self::Linear => Self::Manual,
             Self::Smoothstep => Self::Linear,
             Self::Smootherstep => Self::Smoothstep,
             Self::SmoothStart => Self::Smootherstep,
             Self::SmoothEnd => Self::SmoothStart,
             Self::Sigmoid => Self::SmoothEnd,
             Self::BSpline => Self::Sigmoid,
+            Self::CubicBezier => Self::BSpline,
+            Self::Manual => Self::CubicBezier,
         }
     }
 
@@ -61,6 +69,8 @@ impl CurveType {
             Self::SmoothEnd => "Ease Out",
             Self::Sigmoid => "Sigmoid",
             Self::BSpline => "B-Spline",
+            Self::CubicBezier => "Bézier",
+            Self::Manual => "Manual",
         }
     }
 
@@ -70,8 +80,15 @@ impl CurveType {
     }
 }
 
+/// Bézier control points used when a `cubic_bezier` curve has none: CSS's
+/// `ease`.
+pub const DEFAULT_BEZIER_POINTS: [(f32, f32); 2] = [(0.25, 0.1), (0.25, 1.0)];
+
+/// Stops of a `manual` curve: one per surface step, base00-base07.
+pub const MANUAL_STOPS: usize = 8;
+
 /// Configuration for a single interpolation curve.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct CurveConfig {
     /// The curve type to use
@@ -79,8 +96,66 @@ pub struct CurveConfig {
     pub curve_type: CurveType,
     /// Strength/steepness parameter (for sigmoid, 0.1-5.0)
     pub strength: f32,
-    /// Custom control points for B-spline (t, value pairs)
+    /// Custom control points: (t, value) pairs for B-spline, or the two
+    /// (x, y) handles of a cubic Bézier
     pub control_points: Option<Vec<(f32, f32)>>,
+    /// Output t of each surface step for a manual curve (eight values,
+    /// 0.0-1.0)
+    pub stops: Option<Vec<f32>>,
+}
+
+impl CurveConfig {
+    /// The two Bézier handles of a `cubic_bezier` curve, clamped to 0.0-1.0
+    /// (the defaults when fewer than two are set).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::curves::{CurveConfig, CurveType, DEFAULT_BEZIER_POINTS};
+    ///
+    /// let mut curve = CurveConfig {
+    ///     curve_type: CurveType::CubicBezier,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(curve.bezier_points(), DEFAULT_BEZIER_POINTS);
+    /// curve.control_points = Some(vec![(0.4, -0.5), (0.6, 1.0)]);
+    /// assert_eq!(curve.bezier_points(), [(0.4, 0.0), (0.6, 1.0)]);
+    /// ```
+    pub fn bezier_points(&self) -> [(f32, f32); 2] {
+        match self.control_points.as_deref() {
+            Some([p1, p2, ..]) => [*p1, *p2].map(|(x, y)| (x.clamp(0.0, 1.0), y.clamp(0.0, 1.0))),
+            _ => DEFAULT_BEZIER_POINTS,
+        }
+    }
+
+    /// The stops of a `manual` curve, clamped to 0.0-1.0 (evenly spaced
+    /// unless exactly [`MANUAL_STOPS`] are set).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::curves::{
+    ///     CurveConfig, CurveType, compute_sample_positions, evaluate_curve,
+    /// };
+    ///
+    /// let stops = vec![0.0, 0.05, 0.1, 0.2, 0.45, 0.7, 0.9, 1.0];
+    /// let curve = CurveConfig {
+    ///     curve_type: CurveType::Manual,
+    ///     stops: Some(stops.clone()),
+    ///     ..Default::default()
+    /// };
+    /// // Each surface step lands on its stop, and steps between are linear
+    /// assert_eq!(compute_sample_positions(8, &curve), stops);
+    /// assert!((evaluate_curve(&curve, 0.5) - 0.325).abs() < 1e-6);
+    /// ```
+    pub fn manual_stops(&self) -> [f32; MANUAL_STOPS] {
+        match self.stops.as_deref() {
+            Some(stops) if stops.len() == MANUAL_STOPS => {
+                std::array::from_fn(|i| stops[i].clamp(0.0, 1.0))
+            }
+            _ => std::array::from_fn(|i| i as f32 / (MANUAL_STOPS - 1) as f32),
+        }
+    }
 }
 
 impl Default for CurveConfig {
@@ -89,12 +164,13 @@ impl Default for CurveConfig {
             curve_type: CurveType::Linear,
             strength: 1.0,
             control_points: None,
+            stops: None,
         }
     }
 }
 
 /// Complete interpolation configuration with separate curves for L/C/H.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct InterpolationConfig {
     /// Curve for lightness interpolation
@@ -132,6 +208,54 @@ pub fn evaluate_curve(config: &CurveConfig, t: f32) -> f32 {
         CurveType::SmoothEnd => smooth_end(t),
         CurveType::Sigmoid => sigmoid(t, config.strength),
         CurveType::BSpline => evaluate_bspline(config, t),
+        CurveType::CubicBezier => cubic_bezier(config.bezier_points(), t),
+        CurveType::Manual => manual(config.manual_stops(), t),
+    }
+}
+
+/// Bisection iterations for `invert` (resolution 2^-30 of the domain).
+const INVERT_ITERATIONS: usize = 30;
+
+/// Invert a curve: find the t (0.0 to 1.0) at which `evaluate_curve` returns `y`.
+///
+/// Linear and ease-in/out curves are inverted in closed form; the rest are
+/// solved by bisection, which assumes the curve is non-decreasing. For a
+/// B-spline whose control points double back, one of the matching t values
+/// is returned. `y` outside 0.0-1.0 is clamped.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::curves::{CurveConfig, CurveType, evaluate_curve, invert};
+///
+/// let curve = CurveConfig {
+///     curve_type: CurveType::Smoothstep,
+///     ..Default::default()
+/// };
+/// let t = invert(&curve, 0.8);
+/// assert!((evaluate_curve(&curve, t) - 0.8).abs() < 1e-5);
+/// ```
+pub fn invert(config: &CurveConfig, y: f32) -> f32 {
+    let y = y.clamp(0.0, 1.0);
+
+    match config.curve_type {
+        CurveType::Linear => y,
+        CurveType::SmoothStart => y.sqrt(),
+        CurveType::SmoothEnd => 1.0 - (1.0 - y).sqrt(),
+        // Endpoints are exact; bisection would stop where f32 rounding saturates
+        _ if y <= 0.0 || y >= 1.0 => y,
+        _ => {
+            let (mut lo, mut hi) = (0.0f32, 1.0f32);
+            for _ in 0..INVERT_ITERATIONS {
+                let mid = (lo + hi) / 2.0;
+                if evaluate_curve(config, mid) < y {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            (lo + hi) / 2.0
+        }
     }
 }
 
@@ -202,6 +326,35 @@ fn evaluate_bspline(config: &CurveConfig, t: f32) -> f32 {
     }
 }
 
+/// Cubic Bézier from (0, 0) to (1, 1) through handles `p1` and `p2`, at x = `t`.
+///
+/// Handles within the unit square keep x non-decreasing, so the curve
+/// parameter for `t` is found by bisection.
+fn cubic_bezier([p1, p2]: [(f32, f32); 2], t: f32) -> f32 {
+    let bezier = |a: f32, b: f32, s: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+    };
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..INVERT_ITERATIONS {
+        let mid = (lo + hi) / 2.0;
+        if bezier(p1.0, p2.0, mid) < t {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    bezier(p1.1, p2.1, (lo + hi) / 2.0)
+}
+
+/// Piecewise-linear curve through `stops`, evenly spaced over t.
+fn manual(stops: [f32; MANUAL_STOPS], t: f32) -> f32 {
+    let position = t * (MANUAL_STOPS - 1) as f32;
+    let i = (position.floor() as usize).min(MANUAL_STOPS - 2);
+    let frac = position - i as f32;
+    stops[i] + (stops[i + 1] - stops[i]) * frac
+}
+
 /// Compute sample positions based on curve configuration.
 /// Returns the output t values for each step (where colors will be sampled).
 pub fn compute_sample_positions(steps: usize, curve: &CurveConfig) -> Vec<f32> {
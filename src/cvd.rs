@@ -0,0 +1,286 @@
+//! Color vision deficiency (CVD) simulation and validation.
+//!
+//! Simulates dichromacy with the Machado et al. (2009) matrices at full
+//! severity, applied in linear sRGB. The validation pass flags accent pairs
+//! that are distinct for normal vision but collapse under simulation;
+//! `CvdSeparation` asks the accent solver to avoid such pairs in the first
+//! place.
+
+use std::fmt;
+
+use palette::{LinSrgb, Srgb};
+use serde::{Deserialize, Serialize};
+use tinted_builder::{Base16Scheme, Color};
+
+use crate::config::ConfigError;
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::srgb_to_hex;
+
+/// Default ΔE below which simulated accent pairs are flagged.
+pub const DEFAULT_CVD_THRESHOLD: f32 = 5.0;
+
+/// Accent groups checked pairwise (pairs never span groups).
+const ACCENT_GROUPS: [[&str; 8]; 2] = [
+    [
+        "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+    ],
+    [
+        "base10", "base11", "base12", "base13", "base14", "base15", "base16", "base17",
+    ],
+];
+
+/// Dichromatic color vision deficiencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CvdKind {
+    /// Missing L cones (red-blind)
+    Protanopia,
+    /// Missing M cones (green-blind)
+    Deuteranopia,
+    /// Missing S cones (blue-blind)
+    Tritanopia,
+}
+
+impl CvdKind {
+    /// All simulated deficiencies.
+    pub const ALL: [CvdKind; 3] = [
+        CvdKind::Protanopia,
+        CvdKind::Deuteranopia,
+        CvdKind::Tritanopia,
+    ];
+
+    /// Lowercase display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            CvdKind::Protanopia => "protanopia",
+            CvdKind::Deuteranopia => "deuteranopia",
+            CvdKind::Tritanopia => "tritanopia",
+        }
+    }
+
+    /// Machado 2009 simulation matrix (severity 1.0, linear sRGB).
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            CvdKind::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            CvdKind::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            CvdKind::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+}
+
+/// Simulated-CVD separation the accent solver enforces between accents
+/// (the `[optimization.cvd]` and `[extended_optimization.cvd]` tables).
+///
+/// Each accent is penalized for every other accent in its group whose
+/// simulated ΔE falls below `min_delta_e`, scaled by the weight of the
+/// deficiency. Pairs already closer than that for normal vision only need to
+/// keep their normal separation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CvdSeparation {
+    /// Simulated ΔE each pair should keep
+    pub min_delta_e: f32,
+    /// Penalty weight under protanopia (0 disables)
+    pub protanopia: f32,
+    /// Penalty weight under deuteranopia (0 disables)
+    pub deuteranopia: f32,
+    /// Penalty weight under tritanopia (0 disables)
+    pub tritanopia: f32,
+}
+
+impl Default for CvdSeparation {
+    fn default() -> Self {
+        Self {
+            min_delta_e: 2.0 * DEFAULT_CVD_THRESHOLD,
+            protanopia: 1.0,
+            deuteranopia: 1.0,
+            tritanopia: 1.0,
+        }
+    }
+}
+
+impl CvdSeparation {
+    /// Penalty weight for `kind`.
+    pub fn weight(&self, kind: CvdKind) -> f32 {
+        match kind {
+            CvdKind::Protanopia => self.protanopia,
+            CvdKind::Deuteranopia => self.deuteranopia,
+            CvdKind::Tritanopia => self.tritanopia,
+        }
+    }
+
+    /// Check that the target is positive and the weights are non-negative.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.min_delta_e > 0.0 && self.min_delta_e <= 100.0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "cvd.min_delta_e must be in (0, 100] (got {})",
+                self.min_delta_e
+            )));
+        }
+        for kind in CvdKind::ALL {
+            let weight = self.weight(kind);
+            if !(weight >= 0.0 && weight.is_finite()) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "cvd.{kind} must be a non-negative weight (got {weight})"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CvdKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Simulate how a color appears under the given deficiency.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::cvd::{CvdKind, simulate};
+///
+/// // Neutral grays are unaffected
+/// let gray = Srgb::new(128u8, 128, 128);
+/// let sim = simulate(gray, CvdKind::Deuteranopia);
+/// assert!((sim.red as i16 - 128).abs() <= 1);
+/// ```
+pub fn simulate(color: Srgb<u8>, kind: CvdKind) -> Srgb<u8> {
+    let lin: LinSrgb<f32> = color.into_format::<f32>().into_linear();
+    let rgb = [lin.red, lin.green, lin.blue];
+    let m = kind.matrix();
+    let apply =
+        |row: [f32; 3]| (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).clamp(0.0, 1.0);
+    let out = LinSrgb::new(apply(m[0]), apply(m[1]), apply(m[2]));
+    Srgb::<f32>::from_linear(out).into_format()
+}
+
+/// Simulate every color in a scheme.
+pub fn simulate_scheme(scheme: &Base16Scheme, kind: CvdKind) -> Base16Scheme {
+    let palette = scheme
+        .palette
+        .iter()
+        .map(|(name, color)| {
+            let (r, g, b) = color.rgb;
+            let sim = simulate(Srgb::new(r, g, b), kind);
+            (
+                name.clone(),
+                Color::new(srgb_to_hex(sim)).expect("valid hex"),
+            )
+        })
+        .collect();
+    Base16Scheme {
+        palette,
+        ..scheme.clone()
+    }
+}
+
+/// An accent pair that becomes hard to tell apart under a deficiency.
+#[derive(Debug, Clone)]
+pub struct CvdConflict {
+    /// Deficiency under which the pair collapses
+    pub kind: CvdKind,
+    /// First accent slot
+    pub first: &'static str,
+    /// Second accent slot
+    pub second: &'static str,
+    /// ΔE between the simulated colors
+    pub delta_e: f32,
+    /// ΔE between the original colors
+    pub normal_delta_e: f32,
+}
+
+impl fmt::Display for CvdConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} vs {} ({}): ΔE {:.1} (normal {:.1})",
+            self.first, self.second, self.kind, self.delta_e, self.normal_delta_e
+        )
+    }
+}
+
+/// Flag accent pairs whose simulated ΔE falls below `threshold`.
+///
+/// Pairs that are already closer than `threshold` for normal vision are not
+/// reported, since the deficiency does not cause the confusion.
+pub fn check_cvd(scheme: &Base16Scheme, threshold: f32) -> Vec<CvdConflict> {
+    let mut conflicts = Vec::new();
+    for (first, second, a, b) in accent_pairs(scheme) {
+        let normal_delta_e = HellwigJmh::from_srgb_u8(a).delta_e(&HellwigJmh::from_srgb_u8(b));
+        if normal_delta_e < threshold {
+            continue;
+        }
+        for kind in CvdKind::ALL {
+            let delta_e = simulated_delta_e(a, b, kind);
+            if delta_e < threshold {
+                conflicts.push(CvdConflict {
+                    kind,
+                    first,
+                    second,
+                    delta_e,
+                    normal_delta_e,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// Simulated ΔE of every accent pair within a group, under each
+/// deficiency.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::cvd::cvd_separations;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// // 28 pairs in each of the two accent groups, three deficiencies
+/// assert_eq!(cvd_separations(&scheme).len(), 2 * 28 * 3);
+/// ```
+pub fn cvd_separations(scheme: &Base16Scheme) -> Vec<f32> {
+    accent_pairs(scheme)
+        .flat_map(|(_, _, a, b)| CvdKind::ALL.map(|kind| simulated_delta_e(a, b, kind)))
+        .collect()
+}
+
+/// ΔE between two colors as seen with `kind`.
+fn simulated_delta_e(a: Srgb<u8>, b: Srgb<u8>, kind: CvdKind) -> f32 {
+    HellwigJmh::from_srgb_u8(simulate(a, kind))
+        .delta_e(&HellwigJmh::from_srgb_u8(simulate(b, kind)))
+}
+
+/// Every pair of accents within an accent group that the scheme defines.
+fn accent_pairs(
+    scheme: &Base16Scheme,
+) -> impl Iterator<Item = (&'static str, &'static str, Srgb<u8>, Srgb<u8>)> + '_ {
+    let color = |name: &str| {
+        scheme.palette.get(name).map(|c| {
+            let (r, g, b) = c.rgb;
+            Srgb::new(r, g, b)
+        })
+    };
+    ACCENT_GROUPS.iter().flat_map(move |group| {
+        group.iter().enumerate().flat_map(move |(i, &first)| {
+            group[i + 1..]
+                .iter()
+                .filter_map(move |&second| Some((first, second, color(first)?, color(second)?)))
+        })
+    })
+}
@@ -0,0 +1,229 @@
+//! Long-running `--daemon` mode: regenerate on config changes, write every
+//! configured output, and recolor attached terminals.
+//!
+//! The daemon is configured from the `[daemon]` table of the watched config
+//! file, so one file describes both the theme and where it goes:
+//!
+//! ```toml
+//! [colors]
+//! background = "#1d2021"
+//! foreground = "#ebdbb2"
+//!
+//! [daemon]
+//! outputs = ["themes/current.yaml", "themes/current.css"]
+//! terminals = ["/dev/pts/3"]
+//! ```
+
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+use tinted_builder::Base16Scheme;
+use tracing::{info, warn};
+
+use crate::ansi::{AnsiMap, ansi_slots};
+use crate::batch::forced_variant;
+use crate::cli::OutputFormat;
+use crate::config::{ConfigError, ThemeConfig};
+use crate::generate::generate_for_variant;
+use crate::roles::{SemanticRoles, derive_roles};
+use crate::surfaces::SurfaceRamp;
+use crate::validation::{terminal_attribute_warnings, validation_warnings};
+
+/// Where the daemon writes each regenerated scheme.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonConfig {
+    /// Output files; the format is chosen by extension (.yaml, .yml, .json, .css)
+    pub outputs: Vec<PathBuf>,
+    /// Terminal devices (e.g. `/dev/pts/3`) to recolor with OSC sequences
+    pub terminals: Vec<PathBuf>,
+}
+
+impl DaemonConfig {
+    /// Check that every output has a recognized extension.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for path in &self.outputs {
+            format_for(path)?;
+        }
+        Ok(())
+    }
+
+    /// Resolve relative output paths against `base` (the config file's directory).
+    pub fn resolve_against(&mut self, base: &Path) {
+        for path in &mut self.outputs {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        }
+    }
+}
+
+/// Output format for `path`, chosen by its extension.
+///
+/// # Example
+///
+/// ```
+/// use std::path::Path;
+/// use themalingadingdong::cli::OutputFormat;
+/// use themalingadingdong::daemon::format_for;
+///
+/// assert_eq!(format_for(Path::new("scheme.yml")).unwrap(), OutputFormat::Yaml);
+/// assert_eq!(format_for(Path::new("theme.css")).unwrap(), OutputFormat::Css);
+/// assert_eq!(format_for(Path::new("tailwind.theme.js")).unwrap(), OutputFormat::Tailwind);
+/// assert!(format_for(Path::new("theme.txt")).is_err());
+/// ```
+pub fn format_for(path: &Path) -> Result<OutputFormat, ConfigError> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml" | "yml") => Ok(OutputFormat::Yaml),
+        Some("json") => Ok(OutputFormat::Json),
+        Some("css") => Ok(OutputFormat::Css),
+        Some("scss") => Ok(OutputFormat::Scss),
+        Some("js") => Ok(OutputFormat::Tailwind),
+        _ => Err(ConfigError::InvalidValue(format!(
+            "daemon.outputs: cannot infer a format for '{}' (expected .yaml, .yml, .json, .css, .scss, or .js)",
+            path.display()
+        ))),
+    }
+}
+
+/// OSC escape sequences that set a terminal's 16 ANSI colors (OSC 4) and its
+/// default foreground, background, and cursor colors (OSC 10, 11, 12).
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::daemon::osc_sequences;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let scheme = generate(&GenerateConfig {
+///     background: Srgb::new(0, 0, 0),
+///     foreground: Srgb::new(255, 255, 255),
+///     ..Default::default()
+/// })
+/// .scheme;
+///
+/// let osc = osc_sequences(&scheme);
+/// assert!(osc.starts_with("\x1b]4;0;rgb:00/00/00\x1b\\"));
+/// assert!(osc.contains("\x1b]11;rgb:00/00/00\x1b\\"));
+/// ```
+pub fn osc_sequences(scheme: &Base16Scheme) -> String {
+    let rgb = |slot: &str| {
+        scheme
+            .palette
+            .get(slot)
+            .map(|c| format!("rgb:{:02x}/{:02x}/{:02x}", c.rgb.0, c.rgb.1, c.rgb.2))
+    };
+
+    let mut osc = String::new();
+    for (index, slot) in ansi_slots(scheme).iter().enumerate() {
+        if let Some(color) = rgb(slot) {
+            let _ = write!(osc, "\x1b]4;{index};{color}\x1b\\");
+        }
+    }
+    for (code, slot) in [(10, "base05"), (11, "base00"), (12, "base05")] {
+        if let Some(color) = rgb(slot) {
+            let _ = write!(osc, "\x1b]{code};{color}\x1b\\");
+        }
+    }
+    osc
+}
+
+/// Result of one daemon rebuild.
+#[derive(Debug)]
+pub struct DaemonOutcome {
+    /// The generated scheme
+    pub scheme: Base16Scheme,
+    /// Files written
+    pub written: Vec<PathBuf>,
+    /// Terminals recolored
+    pub applied: Vec<PathBuf>,
+    /// Generation, validation, and terminal warnings
+    pub warnings: Vec<String>,
+}
+
+/// Generate `theme_config`, write it to every output, and push OSC sequences
+/// to every terminal.
+///
+/// Output write failures abort the rebuild; a terminal that cannot be
+/// written (e.g. it was closed) is reported as a warning and skipped.
+pub fn rebuild(theme_config: &ThemeConfig, daemon: &DaemonConfig) -> Result<DaemonOutcome> {
+    let config = theme_config
+        .to_generate_config()
+        .map_err(|e| eyre!("Invalid configuration: {}", e))?;
+    let variant = forced_variant(theme_config.theme.variant.as_deref())?;
+
+    let result = generate_for_variant(&config, variant);
+    let scheme = result.scheme;
+    let mut warnings = result.warnings;
+    warnings.extend(validation_warnings(
+        &scheme,
+        theme_config.contrast.standard,
+        &theme_config.validation,
+    ));
+    if let Some(attrs) = theme_config.contrast.terminal {
+        warnings.extend(terminal_attribute_warnings(&scheme, &attrs));
+    }
+    let roles = theme_config
+        .roles
+        .as_ref()
+        .map(|c| derive_roles(&scheme, c));
+    warnings.extend(roles.iter().flat_map(SemanticRoles::warnings));
+    let ansi = theme_config.ansi.is_some().then(|| AnsiMap::new(&scheme));
+    let surfaces = theme_config
+        .surfaces
+        .as_ref()
+        .map(|c| SurfaceRamp::new(&scheme, c.steps));
+
+    let mut written = Vec::with_capacity(daemon.outputs.len());
+    for path in &daemon.outputs {
+        let content = format_for(path)
+            .map_err(|e| eyre!("{}", e))?
+            .serialize_with(
+                &scheme,
+                Some(&result.wide_palette),
+                roles.as_ref(),
+                ansi.as_ref(),
+                surfaces.as_ref(),
+            )?;
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, content)
+            .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+        info!(path = %path.display(), "wrote scheme");
+        written.push(path.clone());
+    }
+
+    let mut applied = Vec::new();
+    if !daemon.terminals.is_empty() {
+        let osc = osc_sequences(&scheme);
+        for tty in &daemon.terminals {
+            match OpenOptions::new()
+                .write(true)
+                .open(tty)
+                .and_then(|mut f| f.write_all(osc.as_bytes()))
+            {
+                Ok(()) => applied.push(tty.clone()),
+                Err(e) => {
+                    warn!(terminal = %tty.display(), error = %e, "failed to apply colors");
+                    warnings.push(format!("terminal {}: {e}", tty.display()));
+                }
+            }
+        }
+    }
+
+    Ok(DaemonOutcome {
+        scheme,
+        written,
+        applied,
+        warnings,
+    })
+}
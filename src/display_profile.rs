@@ -0,0 +1,134 @@
+//! Scheme copies calibrated for particular displays.
+//!
+//! A scheme's hex values assume an sRGB display: the sRGB tone curve and a
+//! D65 white. A monitor calibrated to a plain power curve or another white
+//! point shows them shifted. A `DisplayProfile` describes such a display by
+//! the gamma and white point from its ICC profile, and re-encodes a scheme
+//! so the display emits the colors an sRGB display would (`--per-display`
+//! writes one copy per `[displays.<name>]` table).
+//!
+//! Primaries are assumed to be sRGB's. White point compensation scales the
+//! linear channels so the display's white comes out at D65, giving up some
+//! peak brightness on the channels it cuts back.
+
+use std::ops::RangeInclusive;
+
+use palette::{LinSrgb, Srgb};
+use serde::{Deserialize, Serialize};
+use tinted_builder::{Base16Scheme, Color};
+
+use crate::config::ConfigError;
+use crate::interpolation::srgb_to_hex;
+use crate::metadata::slugify;
+use crate::viewing::WhitePoint;
+
+/// Gamma of a display profile that does not set one.
+pub const DEFAULT_DISPLAY_GAMMA: f32 = 2.2;
+
+/// Accepted display gammas.
+const GAMMA_RANGE: RangeInclusive<f32> = 1.0..=3.0;
+
+/// How a display is calibrated (a `[displays.<name>]` table).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayProfile {
+    /// Exponent of the display's tone curve
+    pub gamma: f32,
+    /// White point the display is calibrated to
+    pub white_point: WhitePoint,
+}
+
+impl Default for DisplayProfile {
+    fn default() -> Self {
+        Self {
+            gamma: DEFAULT_DISPLAY_GAMMA,
+            white_point: WhitePoint::D65,
+        }
+    }
+}
+
+impl DisplayProfile {
+    /// Check that the gamma is plausible and `name` (the profile's key)
+    /// can be used in file names.
+    pub fn validate(&self, name: &str) -> Result<(), ConfigError> {
+        if name.is_empty() || slugify(name) != name {
+            return Err(ConfigError::InvalidValue(format!(
+                "display name '{name}' must be lowercase letters, digits, and hyphens"
+            )));
+        }
+        if !GAMMA_RANGE.contains(&self.gamma) {
+            return Err(ConfigError::InvalidValue(format!(
+                "displays.{name}.gamma must be in [{}, {}] (got {})",
+                GAMMA_RANGE.start(),
+                GAMMA_RANGE.end(),
+                self.gamma
+            )));
+        }
+        Ok(())
+    }
+
+    /// The value to send this display so it shows `color` as an sRGB
+    /// display would.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use palette::Srgb;
+    /// use themalingadingdong::display_profile::DisplayProfile;
+    /// use themalingadingdong::viewing::WhitePoint;
+    ///
+    /// let white = Srgb::new(255u8, 255, 255);
+    /// // A D65 display needs no white point correction
+    /// assert_eq!(DisplayProfile::default().calibrate(white), white);
+    ///
+    /// // A warm (D50) display has its red cut back to show a D65 white
+    /// let warm = DisplayProfile {
+    ///     white_point: WhitePoint::D50,
+    ///     ..Default::default()
+    /// };
+    /// let corrected = warm.calibrate(white);
+    /// assert!(corrected.red < corrected.blue);
+    /// ```
+    pub fn calibrate(&self, color: Srgb<u8>) -> Srgb<u8> {
+        let white = self.white_point.linear_srgb();
+        let floor = white.red.min(white.green).min(white.blue);
+        let linear: LinSrgb<f32> = color.into_format::<f32>().into_linear();
+        let encode = |c: f32, w: f32| (c * floor / w).clamp(0.0, 1.0).powf(1.0 / self.gamma);
+        Srgb::new(
+            encode(linear.red, white.red),
+            encode(linear.green, white.green),
+            encode(linear.blue, white.blue),
+        )
+        .into_format()
+    }
+
+    /// Calibrate every color in a scheme for the display called `name`,
+    /// adding the name to the slug and description.
+    pub fn calibrate_scheme(&self, scheme: &Base16Scheme, name: &str) -> Base16Scheme {
+        let palette = scheme
+            .palette
+            .iter()
+            .map(|(slot, color)| {
+                let (r, g, b) = color.rgb;
+                let calibrated = self.calibrate(Srgb::new(r, g, b));
+                (
+                    slot.clone(),
+                    Color::new(srgb_to_hex(calibrated)).expect("valid hex"),
+                )
+            })
+            .collect();
+        let calibration = format!(
+            "calibrated for {name} (gamma {}, {:?} white)",
+            self.gamma, self.white_point
+        );
+        Base16Scheme {
+            slug: format!("{}-{name}", scheme.slug),
+            description: Some(match &scheme.description {
+                Some(description) => format!("{description}; {calibration}"),
+                None => calibration,
+            }),
+            palette,
+            ..scheme.clone()
+        }
+    }
+}
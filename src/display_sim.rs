@@ -0,0 +1,131 @@
+//! Preview transforms approximating how a scheme reads on other displays.
+//!
+//! These only change what previews show (the `preview` subcommand and the
+//! TUI); generated schemes are never affected. Each transform works on linear
+//! sRGB:
+//!
+//! - `Dim`: a display turned down in a dark room. Output drops to 40% and
+//!   the darkest 1% of the range crushes to black.
+//! - `Bright`: a display in bright ambient light. Reflected light adds 5% of
+//!   white to every color, lifting blacks and compressing contrast.
+//! - `SrgbGamma24`: an sRGB signal shown on a display with a pure 2.4 power
+//!   curve, which darkens shadows.
+
+use std::fmt;
+
+use palette::{LinSrgb, Srgb};
+use tinted_builder::{Base16Scheme, Color};
+
+use crate::interpolation::srgb_to_hex;
+
+/// Fraction of full output a dimmed display reaches.
+const DIM_PEAK: f32 = 0.4;
+
+/// Linear level a dimmed display crushes to black.
+const DIM_BLACK_CRUSH: f32 = 0.01;
+
+/// Ambient light reflected off a display in bright surroundings, as a
+/// fraction of its white.
+const BRIGHT_REFLECTANCE: f32 = 0.05;
+
+/// A simulated display or viewing environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplaySimulation {
+    /// Dimmed display in a dark room
+    Dim,
+    /// Glare from bright ambient light
+    Bright,
+    /// sRGB content on a pure gamma 2.4 display
+    SrgbGamma24,
+}
+
+impl DisplaySimulation {
+    /// All simulations, in TUI cycling order.
+    pub const ALL: [DisplaySimulation; 3] = [
+        DisplaySimulation::Dim,
+        DisplaySimulation::Bright,
+        DisplaySimulation::SrgbGamma24,
+    ];
+
+    /// Name as accepted by `--simulate`.
+    pub fn name(self) -> &'static str {
+        match self {
+            DisplaySimulation::Dim => "dim",
+            DisplaySimulation::Bright => "bright",
+            DisplaySimulation::SrgbGamma24 => "srgb-gamma-2.4",
+        }
+    }
+
+    /// The simulation after `current` when cycling (None = off).
+    pub fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::ALL[0]),
+            Some(sim) => {
+                let index = Self::ALL.iter().position(|s| *s == sim).unwrap_or(0);
+                Self::ALL.get(index + 1).copied()
+            }
+        }
+    }
+
+    /// How `color` appears under this simulation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use palette::Srgb;
+    /// use themalingadingdong::display_sim::DisplaySimulation;
+    ///
+    /// let black = Srgb::new(0u8, 0, 0);
+    /// // Glare lifts black
+    /// assert!(DisplaySimulation::Bright.apply(black).red > 0);
+    /// // White stays white on a gamma 2.4 display
+    /// let white = Srgb::new(255u8, 255, 255);
+    /// assert_eq!(DisplaySimulation::SrgbGamma24.apply(white), white);
+    /// ```
+    pub fn apply(self, color: Srgb<u8>) -> Srgb<u8> {
+        let encoded: Srgb<f32> = color.into_format();
+        let linear: LinSrgb<f32> = match self {
+            DisplaySimulation::SrgbGamma24 => LinSrgb::new(
+                encoded.red.powf(2.4),
+                encoded.green.powf(2.4),
+                encoded.blue.powf(2.4),
+            ),
+            _ => encoded.into_linear(),
+        };
+        let map = |c: f32| match self {
+            DisplaySimulation::Dim => {
+                ((c - DIM_BLACK_CRUSH) / (1.0 - DIM_BLACK_CRUSH)).max(0.0) * DIM_PEAK
+            }
+            DisplaySimulation::Bright => (c + BRIGHT_REFLECTANCE) / (1.0 + BRIGHT_REFLECTANCE),
+            DisplaySimulation::SrgbGamma24 => c,
+        };
+        let out = LinSrgb::new(map(linear.red), map(linear.green), map(linear.blue));
+        Srgb::<f32>::from_linear(out).into_format()
+    }
+
+    /// Apply the simulation to every color in a scheme.
+    pub fn apply_scheme(self, scheme: &Base16Scheme) -> Base16Scheme {
+        let palette = scheme
+            .palette
+            .iter()
+            .map(|(name, color)| {
+                let (r, g, b) = color.rgb;
+                let sim = self.apply(Srgb::new(r, g, b));
+                (
+                    name.clone(),
+                    Color::new(srgb_to_hex(sim)).expect("valid hex"),
+                )
+            })
+            .collect();
+        Base16Scheme {
+            palette,
+            ..scheme.clone()
+        }
+    }
+}
+
+impl fmt::Display for DisplaySimulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,213 @@
+//! Environment variable and `~` expansion in config values.
+//!
+//! String values in a config file may read environment variables as `$NAME`
+//! or `${NAME}`, with `${NAME:-fallback}` standing in when the variable is
+//! unset or empty; `$$` is a literal `$`. A value starting with `~/` (or
+//! just `~`) starts from the home directory instead:
+//!
+//! ```toml
+//! [colors]
+//! background = "${TERM_BG:-#1d2021}"
+//!
+//! [preview]
+//! gallery_dir = "~/src/schemes"
+//! ```
+//!
+//! Values are expanded as the file is layered, so profiles and `extends`
+//! see the expanded values and CLI arguments still override them. The
+//! `[hooks]` table is left as written: its commands run through the shell,
+//! which expands them with the hook's own variables. Saving a loaded config
+//! writes the references back (see [`restore_unexpanded`]).
+
+use figment::value::{Dict, Map, Value};
+use figment::{Metadata, Profile, Provider};
+use toml_edit::{Item, TableLike};
+
+/// Tables whose values are passed on verbatim.
+const VERBATIM_TABLES: [&str; 1] = ["hooks"];
+
+/// Whether the value at the dotted `key` is left unexpanded.
+pub fn is_verbatim(key: &str) -> bool {
+    key.split('.').any(|part| VERBATIM_TABLES.contains(&part))
+}
+
+/// Expand `text` from the process environment.
+pub fn expand(text: &str) -> Result<String, String> {
+    expand_with(text, |name| std::env::var(name).ok())
+}
+
+/// Expand `text`, reading variables (and `HOME` for `~`) with `lookup`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::env_expand::expand_with;
+///
+/// let env = |name: &str| match name {
+///     "HOME" => Some("/home/me".to_string()),
+///     "TERM_BG" => Some("#282828".to_string()),
+///     _ => None,
+/// };
+/// assert_eq!(expand_with("$TERM_BG", env).unwrap(), "#282828");
+/// assert_eq!(expand_with("${TERM_FG:-#ebdbb2}", env).unwrap(), "#ebdbb2");
+/// assert_eq!(expand_with("~/themes/${TERM_BG}.yaml", env).unwrap(), "/home/me/themes/#282828.yaml");
+/// assert_eq!(expand_with("costs $$5", env).unwrap(), "costs $5");
+/// assert!(expand_with("$TERM_FG", env).is_err());
+/// ```
+pub fn expand_with(text: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = match text.strip_prefix('~') {
+        Some(after) if after.is_empty() || after.starts_with('/') => {
+            out.push_str(&lookup("HOME").ok_or("'~' needs HOME to be set")?);
+            after
+        }
+        _ => text,
+    };
+
+    while let Some(i) = rest.find('$') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            out.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            let end = after
+                .find('}')
+                .ok_or_else(|| format!("unclosed '${{' in '{text}'"))?;
+            let (name, fallback) = match after[..end].split_once(":-") {
+                Some((name, fallback)) => (name, Some(fallback)),
+                None => (&after[..end], None),
+            };
+            if !is_name(name) {
+                return Err(format!("invalid variable name '{name}' in '{text}'"));
+            }
+            match (lookup(name), fallback) {
+                (Some(value), Some(fallback)) if value.is_empty() => out.push_str(fallback),
+                (Some(value), _) => out.push_str(&value),
+                (None, Some(fallback)) => out.push_str(fallback),
+                (None, None) => return Err(unset(name)),
+            }
+            rest = &after[end + 1..];
+        } else {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let name = &rest[..len];
+            // A '$' not followed by a name is kept as is
+            if !is_name(name) {
+                out.push('$');
+                continue;
+            }
+            out.push_str(&lookup(name).ok_or_else(|| unset(name))?);
+            rest = &rest[len..];
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn unset(name: &str) -> String {
+    format!("environment variable {name} is not set (use ${{{name}:-fallback}} for a default)")
+}
+
+/// A provider with every string value of another expanded from the
+/// environment.
+pub struct Expanded<P>(pub P);
+
+impl<P: Provider> Provider for Expanded<P> {
+    fn metadata(&self) -> Metadata {
+        self.0.metadata()
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
+        let mut data = self.0.data()?;
+        for dict in data.values_mut() {
+            expand_dict(dict, "").map_err(figment::Error::from)?;
+        }
+        Ok(data)
+    }
+}
+
+fn expand_dict(dict: &mut Dict, path: &str) -> Result<(), String> {
+    for (key, value) in dict.iter_mut() {
+        let path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        if !is_verbatim(&path) {
+            expand_value(value, &path)?;
+        }
+    }
+    Ok(())
+}
+
+fn expand_value(value: &mut Value, path: &str) -> Result<(), String> {
+    match value {
+        Value::String(_, text) => {
+            *text = expand(text).map_err(|e| format!("{path}: {e}"))?;
+        }
+        Value::Dict(_, dict) => expand_dict(dict, path)?,
+        Value::Array(_, items) => {
+            for item in items {
+                expand_value(item, path)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Put back the unexpanded text of the strings in `raw` that `table` still
+/// holds the expansion of.
+///
+/// `raw` is a config layer as written and `table` a serialized config; a
+/// value that was changed since it was expanded is kept as it is.
+pub fn restore_unexpanded(table: &mut dyn TableLike, raw: &Dict) {
+    for (key, raw) in raw {
+        if let Some(item) = table.get_mut(key) {
+            restore_item(item, raw);
+        }
+    }
+}
+
+fn restore_item(item: &mut Item, raw: &Value) {
+    match (item, raw) {
+        (Item::Value(value), raw) => restore_value(value, raw),
+        (Item::Table(table), Value::Dict(_, raw)) => restore_unexpanded(table, raw),
+        (Item::ArrayOfTables(tables), Value::Array(_, raws)) => {
+            for (table, raw) in tables.iter_mut().zip(raws) {
+                if let Value::Dict(_, raw) = raw {
+                    restore_unexpanded(table, raw);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn restore_value(value: &mut toml_edit::Value, raw: &Value) {
+    match (value, raw) {
+        (toml_edit::Value::String(text), Value::String(_, raw))
+            if text.value() != raw && expand(raw).as_deref() == Ok(text.value().as_str()) =>
+        {
+            let decor = text.decor().clone();
+            *text = toml_edit::Formatted::new(raw.clone());
+            *text.decor_mut() = decor;
+        }
+        (toml_edit::Value::Array(values), Value::Array(_, raws)) => {
+            for (value, raw) in values.iter_mut().zip(raws) {
+                restore_value(value, raw);
+            }
+        }
+        (toml_edit::Value::InlineTable(table), Value::Dict(_, raw)) => {
+            restore_unexpanded(table, raw);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,281 @@
+//! Random exploration around a palette's parameters (`--explore`).
+//!
+//! Each candidate jitters the accent hues, draws a target colorfulness for
+//! base08-base0F, and picks lightness and chroma curves, all within the
+//! bounds of the `[explore]` table:
+//!
+//! ```toml
+//! [explore]
+//! hue_jitter = 15
+//! min_target_m = 15
+//! max_target_m = 35
+//! curve_types = ["linear", "smoothstep", "sigmoid"]
+//! ```
+//!
+//! Candidates are generated in parallel as [`crate::jobs`] and ranked by
+//! their [`crate::score`] total, best first, so the top few can be picked
+//! as a new starting point.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tinted_builder::Base16Scheme;
+use web_time::SystemTime;
+
+use crate::config::{ConfigError, HueOverrides, ThemeConfig};
+use crate::curves::CurveType;
+use crate::generate::{GenerateConfig, generate};
+use crate::interpolation::DEFAULT_BASE16_HUES;
+use crate::jobs::map_jobs;
+use crate::score::{SchemeScore, ScoreWeights, score_scheme_with};
+use crate::validation::ValidationConfig;
+
+/// Default furthest an accent hue moves, in degrees.
+pub const DEFAULT_HUE_JITTER: f32 = 15.0;
+
+/// Candidates listed for selection, best first.
+pub const EXPLORE_SHOWN: usize = 5;
+
+/// Default number of candidates the TUI's shuffle key samples.
+pub const DEFAULT_EXPLORE_COUNT: usize = 24;
+
+/// Bounds candidates are sampled within.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExploreConfig {
+    /// Furthest an accent hue moves either way, in degrees
+    pub hue_jitter: f32,
+    /// Lowest target colorfulness (M) for base08-base0F
+    pub min_target_m: f32,
+    /// Highest target colorfulness (M) for base08-base0F
+    pub max_target_m: f32,
+    /// Curves the lightness and chroma interpolation are drawn from
+    pub curve_types: Vec<CurveType>,
+    /// Candidates sampled by the TUI's shuffle key
+    pub count: usize,
+}
+
+impl Default for ExploreConfig {
+    fn default() -> Self {
+        Self {
+            hue_jitter: DEFAULT_HUE_JITTER,
+            min_target_m: 15.0,
+            max_target_m: 35.0,
+            curve_types: vec![
+                CurveType::Linear,
+                CurveType::Smoothstep,
+                CurveType::Smootherstep,
+                CurveType::SmoothStart,
+                CurveType::SmoothEnd,
+                CurveType::Sigmoid,
+            ],
+            count: DEFAULT_EXPLORE_COUNT,
+        }
+    }
+}
+
+impl ExploreConfig {
+    /// Whether these are the default bounds, for skipping the table when
+    /// serializing.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that the bounds are in range and can be sampled.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(0.0..=180.0).contains(&self.hue_jitter) {
+            return Err(ConfigError::InvalidValue(format!(
+                "explore.hue_jitter must be between 0 and 180 degrees (got {})",
+                self.hue_jitter
+            )));
+        }
+        if !(self.min_target_m > 0.0 && self.min_target_m <= self.max_target_m) {
+            return Err(ConfigError::InvalidValue(format!(
+                "explore.min_target_m must be above 0 and at most max_target_m (got {} and {})",
+                self.min_target_m, self.max_target_m
+            )));
+        }
+        if self.curve_types.is_empty() {
+            return Err(ConfigError::InvalidValue(
+                "explore.curve_types must list at least one curve".to_string(),
+            ));
+        }
+        if self.curve_types.contains(&CurveType::BSpline) {
+            return Err(ConfigError::InvalidValue(
+                "explore.curve_types cannot include b_spline (it needs control points)".to_string(),
+            ));
+        }
+        if self.count == 0 {
+            return Err(ConfigError::InvalidValue(
+                "explore.count must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of one sampled candidate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExploreParams {
+    /// Hues of base08-base0F, in degrees
+    pub hues: [f32; 8],
+    /// Target colorfulness (M) for base08-base0F
+    pub target_m: f32,
+    pub lightness_curve: CurveType,
+    pub chroma_curve: CurveType,
+}
+
+impl ExploreParams {
+    /// Apply these parameters to a generation config.
+    pub fn apply(&self, config: &mut GenerateConfig) {
+        config.hue_overrides = self.hues.map(Some);
+        config.accent_opt.target_m = self.target_m;
+        config.interpolation.lightness.curve_type = self.lightness_curve;
+        config.interpolation.chroma.curve_type = self.chroma_curve;
+    }
+
+    /// Apply these parameters to a config file's settings.
+    pub fn apply_to_config(&self, config: &mut ThemeConfig) {
+        config.colors.hue_overrides = Some(HueOverrides::from_array(self.hues.map(Some)));
+        config.optimization.target_m = self.target_m;
+        config.curves.lightness.curve_type = self.lightness_curve;
+        config.curves.chroma.curve_type = self.chroma_curve;
+    }
+}
+
+impl fmt::Display for ExploreParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "M {:.1}, lightness {}, chroma {}, hues",
+            self.target_m,
+            self.lightness_curve.display_name(),
+            self.chroma_curve.display_name()
+        )?;
+        for hue in self.hues {
+            write!(f, " {hue:.0}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A generated candidate and its score.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub params: ExploreParams,
+    pub scheme: Base16Scheme,
+    pub score: SchemeScore,
+    /// Time taken to generate and score the candidate
+    pub elapsed: Duration,
+}
+
+/// SplitMix64; good enough to sample parameters and reproducible from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [lo, hi].
+    fn range(&mut self, lo: f32, hi: f32) -> f32 {
+        let t = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        lo + (hi - lo) * t
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[(self.next_u64() % items.len() as u64) as usize]
+    }
+}
+
+/// A seed from the clock, for explorations that need not be reproducible.
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// Sample `count` candidates around `base` within `bounds`, generate and
+/// score them in parallel, and return them best first.
+///
+/// Hues are jittered around those `base` generates with. The same seed
+/// gives the same candidates.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::explore::{ExploreConfig, explore};
+/// use themalingadingdong::generate::GenerateConfig;
+/// use themalingadingdong::validation::ValidationConfig;
+///
+/// let candidates = explore(
+///     &GenerateConfig::default(),
+///     &ValidationConfig::default(),
+///     &ExploreConfig::default(),
+///     4,
+///     7,
+/// );
+/// assert_eq!(candidates.len(), 4);
+/// assert!(candidates[0].score.score >= candidates[3].score.score);
+/// ```
+pub fn explore(
+    base: &GenerateConfig,
+    validation: &ValidationConfig,
+    bounds: &ExploreConfig,
+    count: usize,
+    seed: u64,
+) -> Vec<Candidate> {
+    let base_hues: [f32; 8] = {
+        let results = generate(base).base_accent_results;
+        std::array::from_fn(|i| {
+            results.get(i).map_or(
+                base.hue_overrides[i].unwrap_or(DEFAULT_BASE16_HUES[i]),
+                |r| r.hue,
+            )
+        })
+    };
+
+    let mut rng = Rng(seed);
+    let curves = if bounds.curve_types.is_empty() {
+        &[CurveType::Linear][..]
+    } else {
+        &bounds.curve_types
+    };
+    let params: Vec<ExploreParams> = (0..count)
+        .map(|_| ExploreParams {
+            hues: base_hues.map(|hue| {
+                (hue + rng.range(-bounds.hue_jitter, bounds.hue_jitter)).rem_euclid(360.0)
+            }),
+            target_m: rng.range(bounds.min_target_m, bounds.max_target_m),
+            lightness_curve: rng.pick(curves),
+            chroma_curve: rng.pick(curves),
+        })
+        .collect();
+
+    let weights = ScoreWeights::default();
+    let mut candidates: Vec<Candidate> = map_jobs(params, |_, params| {
+        let mut config = base.clone();
+        params.apply(&mut config);
+        let scheme = generate(&config).scheme;
+        let score = score_scheme_with(&scheme, validation, &weights);
+        (params, scheme, score)
+    })
+    .into_iter()
+    .map(|finished| {
+        let (params, scheme, score) = finished.output;
+        Candidate {
+            params,
+            scheme,
+            score,
+            elapsed: finished.elapsed,
+        }
+    })
+    .collect();
+    candidates.sort_by(|a, b| b.score.score.total_cmp(&a.score.score));
+    candidates
+}
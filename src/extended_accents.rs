@@ -0,0 +1,230 @@
+//! Derivation strategies for the extended accents (base10-base17).
+//!
+//! By default each extended accent is solved on its own with the
+//! `[extended_optimization]` settings, which only loosely ties base12 to
+//! base0A. Base24 templates treat base10-base17 as variants of base08-base0F
+//! (the "bright" ANSI colors), so they can instead be derived from the solved
+//! base accents: the same hue, darkened or desaturated.
+//!
+//! The `paired` mode goes further and solves both groups together: each
+//! extended accent sits a fixed J' offset from its base accent, toward the
+//! background, and the base accents' shared lightness target is raised
+//! until every pair clears its contrast floor. base08/base10 through
+//! base0F/base17 then read as normal/dim pairs of one palette rather than
+//! two unrelated sets.
+
+use palette::Srgb;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+use crate::contrast::ContrastModel;
+use crate::gamut_map::{Gamut, gamut_map, gamut_map_to};
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::{AccentResult, srgb_to_u8};
+
+/// How base10-base17 are produced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExtendedAccentMode {
+    /// Optimize each extended accent independently
+    #[default]
+    Independent,
+    /// The matching base accent with J' lowered by `amount`
+    Darker,
+    /// The matching base accent with `amount` percent less colorfulness
+    Desaturated,
+    /// The matching base accent moved `amount` J' toward the background,
+    /// solved jointly with the base accents
+    Paired,
+}
+
+impl ExtendedAccentMode {
+    /// Every mode, in cycling order.
+    pub const ALL: [Self; 4] = [
+        Self::Independent,
+        Self::Darker,
+        Self::Desaturated,
+        Self::Paired,
+    ];
+
+    /// Amount used when none is configured.
+    pub fn default_amount(self) -> f32 {
+        match self {
+            Self::Independent => 0.0,
+            Self::Darker => 10.0,
+            Self::Desaturated => 40.0,
+            Self::Paired => 10.0,
+        }
+    }
+
+    /// Largest allowed amount.
+    pub fn max_amount(self) -> f32 {
+        match self {
+            Self::Independent => f32::INFINITY,
+            Self::Darker => 50.0,
+            Self::Desaturated => 100.0,
+            Self::Paired => 30.0,
+        }
+    }
+
+    /// Config name, e.g. `"darker"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Independent => "independent",
+            Self::Darker => "darker",
+            Self::Desaturated => "desaturated",
+            Self::Paired => "paired",
+        }
+    }
+
+    /// The next mode, wrapping around.
+    pub fn next(self) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|m| *m == self)
+            .unwrap_or_default();
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// The previous mode, wrapping around.
+    pub fn prev(self) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|m| *m == self)
+            .unwrap_or_default();
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Extended accent derivation settings (the `[extended_accents]` table).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtendedAccents {
+    /// "independent" (default), "darker", "desaturated", or "paired"
+    pub mode: ExtendedAccentMode,
+    /// J' offset, or percent colorfulness to remove; defaults per mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f32>,
+}
+
+impl ExtendedAccents {
+    /// Whether these are the default settings (independent optimization).
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The configured amount, or the mode's default.
+    pub fn amount(&self) -> f32 {
+        self.amount.unwrap_or(self.mode.default_amount())
+    }
+
+    /// Check that the amount is in range for the mode.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let amount = self.amount();
+        let max = self.mode.max_amount();
+        if !(0.0..=max).contains(&amount) {
+            return Err(ConfigError::InvalidValue(format!(
+                "extended_accents.amount must be between 0 and {max} for {} (got {amount})",
+                self.mode.name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Derive base10-base17 from the solved base accents, or `None` for
+/// independent optimization.
+///
+/// Each result keeps its base accent's hue; the shifted color is mapped
+/// into `gamut` and checked against `min_contrast` on `background`, measured
+/// with `model`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::contrast::Apca;
+/// use themalingadingdong::extended_accents::{
+///     ExtendedAccentMode, ExtendedAccents, derive_extended_accents,
+/// };
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let config = GenerateConfig::default();
+/// let base = generate(&config).base_accent_results;
+/// let darker = ExtendedAccents {
+///     mode: ExtendedAccentMode::Darker,
+///     amount: Some(8.0),
+/// };
+///
+/// let extended =
+///     derive_extended_accents(&base, &darker, 60.0, config.background, config.gamut, &Apca)
+///         .unwrap();
+/// assert!((extended[0].hue - base[0].jmh.hue).abs() < 1.0);
+/// assert!(extended[0].jmh.lightness < base[0].jmh.lightness);
+/// ```
+pub fn derive_extended_accents(
+    base: &[AccentResult],
+    settings: &ExtendedAccents,
+    min_contrast: f64,
+    background: Srgb<u8>,
+    gamut: Gamut,
+    model: &dyn ContrastModel,
+) -> Option<Vec<AccentResult>> {
+    let amount = settings.amount();
+    let background_j = HellwigJmh::from_srgb_u8(background).lightness;
+    let shift = |jmh: HellwigJmh| match settings.mode {
+        ExtendedAccentMode::Independent => None,
+        ExtendedAccentMode::Darker => Some(HellwigJmh::new(
+            (jmh.lightness - amount).clamp(0.0, 100.0),
+            jmh.colorfulness,
+            jmh.hue,
+        )),
+        ExtendedAccentMode::Desaturated => Some(HellwigJmh::new(
+            jmh.lightness,
+            jmh.colorfulness * (1.0 - amount / 100.0),
+            jmh.hue,
+        )),
+        ExtendedAccentMode::Paired => {
+            let offset = if jmh.lightness > background_j {
+                -amount
+            } else {
+                amount
+            };
+            Some(HellwigJmh::new(
+                (jmh.lightness + offset).clamp(0.0, 100.0),
+                jmh.colorfulness,
+                jmh.hue,
+            ))
+        }
+    };
+
+    base.iter()
+        .enumerate()
+        .map(|(i, accent)| {
+            let target = shift(accent.jmh)?;
+            let jmh = gamut_map_to(target, gamut);
+            let color = gamut_map(jmh).into_srgb();
+            let contrast = model.contrast(srgb_to_u8(color), background).abs();
+            let met_minimum = contrast >= min_contrast;
+            Some(AccentResult {
+                color,
+                jmh,
+                hue: jmh.hue,
+                lightness: target.lightness,
+                post_clamp_lightness: jmh.lightness,
+                j_deviation: 0.0,
+                achieved_contrast: contrast,
+                met_minimum,
+                was_gamut_mapped: target.colorfulness > jmh.colorfulness + 0.8,
+                m_in_bounds: true,
+                warning: (!met_minimum).then(|| {
+                    format!(
+                        "base1{i:X} ({} base0{:X}): {} {contrast:.1} < {min_contrast:.1}",
+                        settings.mode.name(),
+                        8 + i,
+                        model.label()
+                    )
+                }),
+            })
+        })
+        .collect()
+}
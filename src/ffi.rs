@@ -0,0 +1,361 @@
+//! C ABI for embedding the generator (`ffi` feature).
+//!
+//! GUI wrappers and editor plugins written in other languages can link the
+//! library instead of running the CLI. Every function reports failure with a
+//! `TmdStatus`; `tmd_last_error` then describes the problem. Panics are
+//! caught at the boundary and reported as `TMD_STATUS_PANIC`.
+//!
+//! Build the shared library with
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib`; the
+//! matching header is `include/themalingadingdong.h` (regenerate it with
+//! `cbindgen --config cbindgen.toml --output include/themalingadingdong.h`).
+//!
+//! # Example
+//!
+//! ```
+//! use std::ffi::{CStr, CString};
+//! use themalingadingdong::ffi::{
+//!     TmdPalette, TmdStatus, TmdValidation, tmd_generate, tmd_validate, tmd_validation_free,
+//! };
+//!
+//! let bg = CString::new("#1a1a2e").unwrap();
+//! let fg = CString::new("#eaeaea").unwrap();
+//! let mut palette = TmdPalette::default();
+//! let status = unsafe { tmd_generate(bg.as_ptr(), fg.as_ptr(), &mut palette) };
+//! assert_eq!(status, TmdStatus::Ok);
+//! let base00 = unsafe { CStr::from_ptr(palette.colors[0].as_ptr()) };
+//! assert_eq!(base00.to_str().unwrap(), "1a1a2e");
+//!
+//! let mut validation = TmdValidation::default();
+//! assert_eq!(unsafe { tmd_validate(&palette, &mut validation) }, TmdStatus::Ok);
+//! assert!(validation.required_passed <= validation.required);
+//! unsafe { tmd_validation_free(&mut validation) };
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use tinted_builder::{Base16Scheme, Color, SchemeSystem, SchemeVariant};
+
+use crate::builder::{GeneratedTheme, ThemeBuilder};
+use crate::config::ThemeConfig;
+use crate::contrast_matrix::slot_name;
+use crate::validation::{ValidationResult, validate};
+
+/// Number of palette slots (base00-base17).
+pub const TMD_SLOTS: usize = 24;
+
+/// Outcome of an FFI call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TmdStatus {
+    Ok = 0,
+    /// A required pointer argument was null
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// Colors or configuration could not be parsed or were out of range
+    InvalidConfig = 3,
+    /// The generator panicked; the library is still usable
+    Panic = 4,
+}
+
+/// A generated palette.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TmdPalette {
+    /// base00-base17 as NUL-terminated lowercase hex, without `#`
+    pub colors: [[c_char; 7]; TMD_SLOTS],
+    /// Whether the scheme is a light variant
+    pub is_light: bool,
+    /// Number of generation warnings (unmet contrast targets and the like)
+    pub warning_count: u32,
+}
+
+impl Default for TmdPalette {
+    fn default() -> Self {
+        Self {
+            colors: [[0; 7]; TMD_SLOTS],
+            is_light: false,
+            warning_count: 0,
+        }
+    }
+}
+
+/// One contrast check of a foreground slot on a background slot.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TmdCheck {
+    /// Foreground slot index (0-23, base00-base17)
+    pub foreground: u8,
+    /// Background slot index (0-23)
+    pub background: u8,
+    /// APCA contrast (Lc, signed)
+    pub contrast: f64,
+    /// Minimum |Lc| the check requires
+    pub min_contrast: f64,
+    /// WCAG 2.x contrast ratio (1-21)
+    pub wcag_ratio: f64,
+    pub passes: bool,
+    /// Whether the check must pass for the scheme to be valid (the others
+    /// are informational)
+    pub required: bool,
+}
+
+/// Validation results; free with `tmd_validation_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct TmdValidation {
+    /// Required checks first, then informational ones
+    pub checks: *mut TmdCheck,
+    pub len: usize,
+    /// Number of required checks
+    pub required: usize,
+    /// Number of required checks that pass
+    pub required_passed: usize,
+}
+
+impl Default for TmdValidation {
+    fn default() -> Self {
+        Self {
+            checks: ptr::null_mut(),
+            len: 0,
+            required: 0,
+            required_passed: 0,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(message: impl Into<String>) {
+    let message = message.into().replace('\0', " ");
+    LAST_ERROR.with(|e| *e.borrow_mut() = CString::new(message).ok());
+}
+
+/// Run `f`, turning errors and panics into a status and `LAST_ERROR`.
+fn guard(f: impl FnOnce() -> Result<(), (TmdStatus, String)>) -> TmdStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => TmdStatus::Ok,
+        Ok(Err((status, message))) => {
+            set_error(message);
+            status
+        }
+        Err(_) => {
+            set_error("generator panicked");
+            TmdStatus::Panic
+        }
+    }
+}
+
+fn slot_index(name: &str) -> u8 {
+    u8::from_str_radix(name.trim_start_matches("base"), 16).unwrap_or(u8::MAX)
+}
+
+/// Read a required string argument.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string.
+unsafe fn arg<'a>(s: *const c_char, name: &str) -> Result<&'a str, (TmdStatus, String)> {
+    if s.is_null() {
+        return Err((TmdStatus::NullArgument, format!("{name} is null")));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|_| (TmdStatus::InvalidUtf8, format!("{name} is not UTF-8")))
+}
+
+fn write_palette(theme: &GeneratedTheme, out: &mut TmdPalette) {
+    for (index, color) in out.colors.iter_mut().enumerate() {
+        *color = [0; 7];
+        let hex = theme.hex(slot_name(index)).unwrap_or_default();
+        for (dst, src) in color.iter_mut().zip(hex.bytes().take(6)) {
+            *dst = src as c_char;
+        }
+    }
+    out.is_light = !theme.is_dark();
+    out.warning_count = u32::try_from(theme.warnings().len()).unwrap_or(u32::MAX);
+}
+
+fn build(builder: ThemeBuilder, out: &mut TmdPalette) -> Result<(), (TmdStatus, String)> {
+    let theme = builder
+        .build()
+        .map_err(|e| (TmdStatus::InvalidConfig, e.to_string()))?;
+    write_palette(&theme, out);
+    Ok(())
+}
+
+/// Generate a palette from background and foreground colors (any CSS
+/// format) with the default settings.
+///
+/// # Safety
+///
+/// `background` and `foreground` must be NUL-terminated strings and `out`
+/// must point to a writable `TmdPalette`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tmd_generate(
+    background: *const c_char,
+    foreground: *const c_char,
+    out: *mut TmdPalette,
+) -> TmdStatus {
+    guard(|| {
+        // SAFETY: per the function's contract
+        let (bg, fg) = unsafe {
+            (
+                arg(background, "background")?,
+                arg(foreground, "foreground")?,
+            )
+        };
+        // SAFETY: null or a writable TmdPalette per the function's contract
+        let out = unsafe { out.as_mut() }
+            .ok_or_else(|| (TmdStatus::NullArgument, "out is null".to_string()))?;
+        build(ThemeBuilder::new().background(bg).foreground(fg), out)
+    })
+}
+
+/// Generate a palette from a configuration in the config file's TOML format.
+///
+/// # Safety
+///
+/// `config_toml` must be a NUL-terminated string and `out` must point to a
+/// writable `TmdPalette`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tmd_generate_config(
+    config_toml: *const c_char,
+    out: *mut TmdPalette,
+) -> TmdStatus {
+    guard(|| {
+        // SAFETY: per the function's contract
+        let source = unsafe { arg(config_toml, "config_toml")? };
+        // SAFETY: null or a writable TmdPalette per the function's contract
+        let out = unsafe { out.as_mut() }
+            .ok_or_else(|| (TmdStatus::NullArgument, "out is null".to_string()))?;
+        let invalid = |e: String| (TmdStatus::InvalidConfig, e);
+        let config: ThemeConfig = toml::from_str(source).map_err(|e| invalid(e.to_string()))?;
+        crate::config::validate_config(&config).map_err(|e| invalid(e.to_string()))?;
+        let generate = config
+            .to_generate_config()
+            .map_err(|e| invalid(e.to_string()))?;
+        let mut builder =
+            ThemeBuilder::from_config(generate).validation_thresholds(config.validation);
+        match config.theme.variant.as_deref() {
+            Some("light") => builder = builder.variant(SchemeVariant::Light),
+            Some("dark") => builder = builder.variant(SchemeVariant::Dark),
+            _ => {}
+        }
+        build(builder, out)
+    })
+}
+
+fn check(result: &ValidationResult, required: bool) -> TmdCheck {
+    TmdCheck {
+        foreground: slot_index(result.pair.foreground),
+        background: slot_index(result.pair.background),
+        contrast: result.contrast,
+        min_contrast: result.pair.threshold.min_lc,
+        wcag_ratio: result.wcag_ratio,
+        passes: result.passes,
+        required,
+    }
+}
+
+/// Check a palette's contrast with the default thresholds (APCA).
+///
+/// On success `out` owns an array that must be released with
+/// `tmd_validation_free`.
+///
+/// # Safety
+///
+/// `palette` must point to a `TmdPalette` whose colors are NUL-terminated,
+/// and `out` to a writable `TmdValidation`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tmd_validate(
+    palette: *const TmdPalette,
+    out: *mut TmdValidation,
+) -> TmdStatus {
+    guard(|| {
+        // SAFETY: null or valid pointers per the function's contract
+        let (Some(palette), Some(out)) = (unsafe { palette.as_ref() }, unsafe { out.as_mut() })
+        else {
+            return Err((
+                TmdStatus::NullArgument,
+                "palette or out is null".to_string(),
+            ));
+        };
+
+        let mut colors = HashMap::with_capacity(TMD_SLOTS);
+        for (index, hex) in palette.colors.iter().enumerate() {
+            let hex: Vec<u8> = hex
+                .iter()
+                .take_while(|&&b| b != 0)
+                .map(|&b| b as u8)
+                .collect();
+            let hex = String::from_utf8_lossy(&hex);
+            let slot = slot_name(index);
+            let color = Color::new(hex.to_string())
+                .map_err(|e| (TmdStatus::InvalidConfig, format!("{slot}: {e}")))?;
+            colors.insert(slot.to_string(), color);
+        }
+        let scheme = Base16Scheme {
+            system: SchemeSystem::Base24,
+            name: String::new(),
+            slug: String::new(),
+            author: String::new(),
+            description: None,
+            variant: if palette.is_light {
+                SchemeVariant::Light
+            } else {
+                SchemeVariant::Dark
+            },
+            palette: colors,
+        };
+
+        let results = validate(&scheme);
+        let checks: Box<[TmdCheck]> = results
+            .required
+            .iter()
+            .map(|r| check(r, true))
+            .chain(results.reference.iter().map(|r| check(r, false)))
+            .collect();
+        out.len = checks.len();
+        out.required = results.required.len();
+        out.required_passed = results.required.iter().filter(|r| r.passes).count();
+        out.checks = Box::into_raw(checks).cast();
+        Ok(())
+    })
+}
+
+/// Release the checks of a `TmdValidation` filled by `tmd_validate`.
+/// Freeing an empty or already freed result does nothing.
+///
+/// # Safety
+///
+/// `validation` must be null or point to a `TmdValidation` filled by
+/// `tmd_validate` and not modified since.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tmd_validation_free(validation: *mut TmdValidation) {
+    // SAFETY: null or valid per the function's contract
+    let Some(validation) = (unsafe { validation.as_mut() }) else {
+        return;
+    };
+    if !validation.checks.is_null() {
+        let slice = ptr::slice_from_raw_parts_mut(validation.checks, validation.len);
+        // SAFETY: allocated by `tmd_validate` as a boxed slice of `len` checks
+        drop(unsafe { Box::from_raw(slice) });
+    }
+    *validation = TmdValidation::default();
+}
+
+/// Message for the last failed call on this thread, or null if none failed.
+/// Valid until the next failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn tmd_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
@@ -0,0 +1,331 @@
+//! Fit generator parameters to an existing scheme.
+//!
+//! Recovers the `GenerateConfig` whose output comes closest to an imported
+//! palette so an existing theme can be tweaked parametrically:
+//!
+//! - Background/foreground are taken from base00/base07 directly
+//! - Hue overrides come from the accent hues (base08-base0F)
+//! - Curve types/strengths are searched per channel against the UI ramp
+//! - Accent J'/M targets and contrast floors are seeded from the accent
+//!   statistics, then refined by pattern search against the solver output
+//!
+//! Residuals are reported per swatch as `HellwigJmh::delta_e`.
+
+use color_eyre::eyre::{Result, eyre};
+use palette::Srgb;
+use tinted_builder::Base16Scheme;
+use tracing::{debug, info};
+
+use crate::apca::apca_contrast;
+use crate::config::AccentOptSettings;
+use crate::contrast_matrix::slot_name;
+use crate::curves::{CurveConfig, CurveType, InterpolationConfig};
+use crate::generate::{GenerateConfig, generate};
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::{
+    build_hues_with_overrides, generate_accents_uniform, interpolate_with_curves, srgb_to_f32,
+    srgb_to_u8,
+};
+
+/// Sigmoid strengths tried during the curve search.
+const SIGMOID_STRENGTHS: [f32; 5] = [2.0, 4.0, 6.0, 8.0, 10.0];
+
+/// Residual error for one palette slot.
+#[derive(Debug, Clone)]
+pub struct SwatchResidual {
+    /// Palette slot (e.g. "base08")
+    pub slot: String,
+    /// Color in the imported scheme
+    pub target: Srgb<u8>,
+    /// Color produced by the fitted configuration
+    pub fitted: Srgb<u8>,
+    /// Color difference between the two
+    pub delta_e: f32,
+}
+
+/// Result of fitting a configuration to a scheme.
+#[derive(Debug)]
+pub struct FitResult {
+    /// Recovered generator configuration
+    pub config: GenerateConfig,
+    /// Scheme generated from `config`
+    pub scheme: Base16Scheme,
+    /// Per-swatch residuals, in slot order
+    pub residuals: Vec<SwatchResidual>,
+}
+
+impl FitResult {
+    /// Mean residual across all swatches.
+    pub fn mean_error(&self) -> f32 {
+        if self.residuals.is_empty() {
+            return 0.0;
+        }
+        self.residuals.iter().map(|r| r.delta_e).sum::<f32>() / self.residuals.len() as f32
+    }
+
+    /// Largest residual across all swatches.
+    pub fn max_error(&self) -> f32 {
+        self.residuals.iter().map(|r| r.delta_e).fold(0.0, f32::max)
+    }
+}
+
+/// Fit a `GenerateConfig` that reproduces `target` as closely as possible.
+///
+/// Base24 schemes also fit the extended accents (base10-base17); Base16
+/// schemes keep the default extended settings.
+pub fn fit_scheme(target: &Base16Scheme) -> Result<FitResult> {
+    let slot = |name: &str| slot_color(target, name);
+
+    let background = slot("base00").ok_or_else(|| eyre!("Scheme is missing base00"))?;
+    let foreground = slot("base07").ok_or_else(|| eyre!("Scheme is missing base07"))?;
+
+    let accents: Vec<Srgb<u8>> = (8..16)
+        .map(|i| slot(slot_name(i)).ok_or_else(|| eyre!("Scheme is missing {}", slot_name(i))))
+        .collect::<Result<_>>()?;
+
+    let mut hue_overrides = [None; 8];
+    for (i, color) in accents.iter().enumerate() {
+        let hellwig = HellwigJmh::from_srgb_u8(*color);
+        if hellwig.colorfulness > 5.0 {
+            hue_overrides[i] = Some(hellwig.hue);
+        }
+    }
+    let hues = build_hues_with_overrides(&hue_overrides);
+
+    let ui: Vec<Srgb<u8>> = (0..8)
+        .map(|i| slot(slot_name(i)).unwrap_or(background))
+        .collect();
+    let interpolation = fit_curves(background, foreground, &ui);
+
+    let defaults = GenerateConfig::default();
+    let (accent_opt, min_contrast) =
+        fit_accents(&hues, &accents, background, defaults.accent_opt.clone());
+
+    let extended: Option<Vec<Srgb<u8>>> = (16..24).map(|i| slot(slot_name(i))).collect();
+    let (extended_accent_opt, extended_min_contrast) = match extended {
+        Some(ref colors) => fit_accents(
+            &hues,
+            colors,
+            background,
+            defaults.extended_accent_opt.clone(),
+        ),
+        None => (
+            defaults.extended_accent_opt.clone(),
+            defaults.extended_min_contrast,
+        ),
+    };
+
+    let config = GenerateConfig {
+        background,
+        foreground,
+        hue_overrides,
+        // Hues were measured from the scheme, not derived from the background
+        adaptive_hues: false,
+        min_contrast,
+        extended_min_contrast,
+        name: target.name.clone(),
+        author: (!target.author.is_empty()).then(|| target.author.clone()),
+        interpolation,
+        accent_opt,
+        extended_accent_opt,
+        ..defaults
+    };
+
+    let scheme = generate(&config).scheme;
+    let residuals = residuals(target, &scheme);
+    let result = FitResult {
+        config,
+        scheme,
+        residuals,
+    };
+
+    info!(
+        mean_error = result.mean_error(),
+        max_error = result.max_error(),
+        "fit complete"
+    );
+    Ok(result)
+}
+
+/// Look up a palette color, accepting upper- or lowercase hex digits in the slot name.
+fn slot_color(scheme: &Base16Scheme, name: &str) -> Option<Srgb<u8>> {
+    let color = scheme
+        .palette
+        .get(name)
+        .or_else(|| scheme.palette.get(&name.to_lowercase()))?;
+    let (r, g, b) = color.rgb;
+    Some(Srgb::new(r, g, b))
+}
+
+/// Per-slot residuals for every slot present in both schemes.
+fn residuals(target: &Base16Scheme, fitted: &Base16Scheme) -> Vec<SwatchResidual> {
+    (0..24)
+        .map(slot_name)
+        .filter_map(|name| {
+            let t = slot_color(target, name)?;
+            let f = slot_color(fitted, name)?;
+            Some(SwatchResidual {
+                delta_e: HellwigJmh::from_srgb_u8(t).delta_e(&HellwigJmh::from_srgb_u8(f)),
+                slot: name.to_string(),
+                target: t,
+                fitted: f,
+            })
+        })
+        .collect()
+}
+
+/// Candidate curves for the per-channel search.
+fn curve_candidates() -> Vec<CurveConfig> {
+    let simple = [
+        CurveType::Linear,
+        CurveType::Smoothstep,
+        CurveType::Smootherstep,
+        CurveType::SmoothStart,
+        CurveType::SmoothEnd,
+    ]
+    .into_iter()
+    .map(|curve_type| CurveConfig {
+        curve_type,
+        ..Default::default()
+    });
+    let sigmoid = SIGMOID_STRENGTHS.into_iter().map(|strength| CurveConfig {
+        curve_type: CurveType::Sigmoid,
+        strength,
+        control_points: None,
+        stops: None,
+    });
+    simple.chain(sigmoid).collect()
+}
+
+/// Search lightness, chroma, and hue curves (coordinate descent) against base01-base06.
+fn fit_curves(background: Srgb<u8>, foreground: Srgb<u8>, ui: &[Srgb<u8>]) -> InterpolationConfig {
+    let targets: Vec<HellwigJmh> = ui.iter().map(|c| HellwigJmh::from_srgb_u8(*c)).collect();
+    let cost = |config: &InterpolationConfig| -> f32 {
+        let ramp =
+            interpolate_with_curves(srgb_to_f32(background), srgb_to_f32(foreground), 8, config);
+        // Endpoints are fixed by background/foreground; score the interior
+        (1..7)
+            .map(|i| HellwigJmh::from_srgb_u8(srgb_to_u8(ramp[i])).delta_e(&targets[i]))
+            .sum()
+    };
+
+    let candidates = curve_candidates();
+    let mut best = InterpolationConfig::default();
+    let mut best_cost = cost(&best);
+
+    for _ in 0..2 {
+        for channel in 0..3 {
+            for candidate in &candidates {
+                let mut trial = best.clone();
+                match channel {
+                    0 => trial.lightness = candidate.clone(),
+                    1 => trial.chroma = candidate.clone(),
+                    _ => trial.hue = candidate.clone(),
+                }
+                let c = cost(&trial);
+                if c < best_cost {
+                    best = trial;
+                    best_cost = c;
+                }
+            }
+        }
+    }
+
+    debug!(cost = best_cost, "curve fit");
+    best
+}
+
+/// Fit accent J'/M targets, tolerances, and contrast floor to observed accents.
+///
+/// Returns the settings and the minimum contrast to use.
+fn fit_accents(
+    hues: &[f32; 8],
+    colors: &[Srgb<u8>],
+    background: Srgb<u8>,
+    mut settings: AccentOptSettings,
+) -> (AccentOptSettings, f64) {
+    let observed: Vec<HellwigJmh> = colors
+        .iter()
+        .map(|c| HellwigJmh::from_srgb_u8(*c))
+        .collect();
+    let n = observed.len() as f32;
+    let mean_j = observed.iter().map(|c| c.lightness).sum::<f32>() / n;
+    let mean_m = observed.iter().map(|c| c.colorfulness).sum::<f32>() / n;
+    let spread_j = observed
+        .iter()
+        .map(|c| (c.lightness - mean_j).abs())
+        .fold(0.0, f32::max);
+    let spread_m = observed
+        .iter()
+        .map(|c| (c.colorfulness - mean_m).abs())
+        .fold(0.0, f32::max);
+
+    // Floor just below the weakest observed accent so contrast never drags the fit
+    let min_contrast = colors
+        .iter()
+        .map(|c| apca_contrast(*c, background).abs())
+        .fold(f64::INFINITY, f64::min)
+        .floor()
+        - 1.0;
+    let min_contrast = min_contrast.clamp(15.0, 90.0);
+
+    settings.delta_j = (spread_j + 1.0).clamp(1.0, 30.0);
+    settings.delta_m = (spread_m + 1.0).clamp(1.0, 50.0);
+
+    let cost = |x: &[f32; 2]| -> f32 {
+        let trial = AccentOptSettings {
+            target_j: x[0],
+            target_m: x[1],
+            ..settings.clone()
+        };
+        generate_accents_uniform(hues, &trial, min_contrast, background)
+            .iter()
+            .zip(&observed)
+            .map(|(r, o)| HellwigJmh::from_srgb(r.color).delta_e(o))
+            .sum()
+    };
+
+    let [target_j, target_m] = pattern_search([mean_j, mean_m], 8.0, 0.5, cost);
+    settings.target_j = target_j.clamp(0.0, 100.0);
+    settings.target_m = target_m.max(0.0);
+
+    debug!(
+        target_j = settings.target_j,
+        target_m = settings.target_m,
+        delta_j = settings.delta_j,
+        delta_m = settings.delta_m,
+        min_contrast,
+        "accent fit"
+    );
+    (settings, min_contrast)
+}
+
+/// Compass pattern search: probe +/-step on each axis, halving the step
+/// when no probe improves, until it drops below `min_step`.
+fn pattern_search<const N: usize>(
+    mut x: [f32; N],
+    mut step: f32,
+    min_step: f32,
+    cost: impl Fn(&[f32; N]) -> f32,
+) -> [f32; N] {
+    let mut best = cost(&x);
+    while step >= min_step {
+        let mut improved = false;
+        for axis in 0..N {
+            for dir in [1.0, -1.0] {
+                let mut trial = x;
+                trial[axis] += dir * step;
+                let c = cost(&trial);
+                if c < best {
+                    best = c;
+                    x = trial;
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            step /= 2.0;
+        }
+    }
+    x
+}
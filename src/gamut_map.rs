@@ -2,11 +2,19 @@
 //!
 //! Provides perceptually-accurate gamut mapping that preserves hue
 //! when projecting out-of-gamut colors toward the achromatic axis.
+//! sRGB is the default target; Display-P3 and Rec.2020 are supported for
+//! wide-gamut output.
 
-use std::cell::RefCell;
+use std::fmt;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+use palette::{LinSrgb, Srgb};
+use serde::{Deserialize, Serialize};
 
 use crate::generated::CUSP_LUT;
-use crate::hellwig::HellwigJmh;
+use crate::hellwig::{HellwigJmh, batch_into_linear_srgb, srgb_in_gamut};
+use crate::viewing::{ViewingModel, models, read_active_model};
 
 /// Maximum Newton-Raphson iterations before fallback to triangle estimate.
 const MAX_NEWTON_ITERS: usize = 5;
@@ -26,25 +34,230 @@ const J_RESOLUTION: f32 = 0.1;
 /// Number of J' buckets in the cache.
 const J_BUCKETS: usize = 1000;
 
+/// Upper bound on M searched for wide-gamut boundaries.
+const MAX_SEARCH_M: f32 = 200.0;
+
+/// Tolerance for wide-gamut containment checks (linear RGB units).
+const WIDE_GAMUT_TOLERANCE: f32 = 1e-5;
+
+/// Linear sRGB to linear Display-P3 (both D65).
+const SRGB_TO_P3: [[f32; 3]; 3] = [
+    [0.822_462_1, 0.177_538, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_7, 0.072_397_4, 0.910_519_9],
+];
+
+/// Linear sRGB to linear Rec.2020 (both D65).
+const SRGB_TO_REC2020: [[f32; 3]; 3] = [
+    [0.627_404, 0.329_282, 0.043_313_6],
+    [0.069_097, 0.919_540, 0.011_361_2],
+    [0.016_391_6, 0.088_013_2, 0.895_595],
+];
+
+/// Linear Display-P3 to linear sRGB (inverse of `SRGB_TO_P3`).
+const P3_TO_SRGB: [[f32; 3]; 3] = [
+    [1.224_940_1, -0.224_940_4, 0.0],
+    [-0.042_056_9, 1.042_057_1, 0.0],
+    [-0.019_637_6, -0.078_636_1, 1.098_273_5],
+];
+
+/// Linear Rec.2020 to linear sRGB (inverse of `SRGB_TO_REC2020`).
+const REC2020_TO_SRGB: [[f32; 3]; 3] = [
+    [1.660_491, -0.587_641_1, -0.072_849_9],
+    [-0.124_550_5, 1.132_899_9, -0.008_349_4],
+    [-0.018_150_8, -0.100_578_9, 1.118_729_7],
+];
+
+/// Rec.2020 transfer function constants (BT.2020 OETF, 12-bit precision).
+const REC2020_ALPHA: f32 = 1.099_296_8;
+const REC2020_BETA: f32 = 0.018_053_97;
+
+/// RGB color space that colors are mapped into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Gamut {
+    /// sRGB (default; what hex colors represent)
+    #[default]
+    Srgb,
+    /// Display-P3, used by most recent laptop and phone displays
+    #[serde(alias = "p3")]
+    DisplayP3,
+    /// Rec.2020 (ITU-R BT.2020)
+    Rec2020,
+}
+
+impl Gamut {
+    /// Whether this is the default sRGB gamut.
+    pub fn is_srgb(&self) -> bool {
+        *self == Gamut::Srgb
+    }
+
+    /// Predefined color space name used in CSS `color()`.
+    pub fn css_name(self) -> &'static str {
+        match self {
+            Gamut::Srgb => "srgb",
+            Gamut::DisplayP3 => "display-p3",
+            Gamut::Rec2020 => "rec2020",
+        }
+    }
+
+    /// Linear RGB in this gamut's primaries, unclamped.
+    pub fn linear_rgb(self, linear: LinSrgb<f32>) -> [f32; 3] {
+        let rgb = [linear.red, linear.green, linear.blue];
+        let matrix = match self {
+            Gamut::Srgb => return rgb,
+            Gamut::DisplayP3 => &SRGB_TO_P3,
+            Gamut::Rec2020 => &SRGB_TO_REC2020,
+        };
+        matrix.map(|row| row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2])
+    }
+
+    /// Encoded (non-linear) RGB channels of a color in this gamut, 0.0-1.0
+    /// when the color is in gamut.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::gamut_map::Gamut;
+    /// use themalingadingdong::hellwig::HellwigJmh;
+    ///
+    /// // sRGB red sits inside Display-P3, away from its red primary
+    /// let red = HellwigJmh::from_srgb_u8(palette::Srgb::new(255u8, 0, 0));
+    /// let [r, g, b] = Gamut::DisplayP3.encode(red);
+    /// assert!(r < 0.95 && g > 0.1 && b > 0.1);
+    /// ```
+    pub fn encode(self, color: HellwigJmh) -> [f32; 3] {
+        let linear = self.linear_rgb(color.into_linear_srgb_unclamped());
+        linear.map(|c| {
+            let sign = c.signum();
+            let c = c.abs();
+            let encoded = match self {
+                Gamut::Srgb | Gamut::DisplayP3 => {
+                    if c <= 0.003_130_8 {
+                        12.92 * c
+                    } else {
+                        1.055 * c.powf(1.0 / 2.4) - 0.055
+                    }
+                }
+                Gamut::Rec2020 => {
+                    if c < REC2020_BETA {
+                        4.5 * c
+                    } else {
+                        REC2020_ALPHA * c.powf(0.45) - (REC2020_ALPHA - 1.0)
+                    }
+                }
+            };
+            sign * encoded
+        })
+    }
+
+    /// Linear sRGB (unclamped) of encoded channels in this gamut; the inverse
+    /// of `encode`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::gamut_map::Gamut;
+    ///
+    /// // Display-P3 red lies outside sRGB
+    /// let red = Gamut::DisplayP3.decode([1.0, 0.0, 0.0]);
+    /// assert!(red.red > 1.0 && red.green < 0.0);
+    /// ```
+    pub fn decode(self, rgb: [f32; 3]) -> LinSrgb<f32> {
+        let linear = rgb.map(|e| {
+            let sign = e.signum();
+            let e = e.abs();
+            let c = match self {
+                Gamut::Srgb | Gamut::DisplayP3 => {
+                    if e <= 0.040_45 {
+                        e / 12.92
+                    } else {
+                        ((e + 0.055) / 1.055).powf(2.4)
+                    }
+                }
+                Gamut::Rec2020 => {
+                    if e < 4.5 * REC2020_BETA {
+                        e / 4.5
+                    } else {
+                        ((e + (REC2020_ALPHA - 1.0)) / REC2020_ALPHA).powf(1.0 / 0.45)
+                    }
+                }
+            };
+            sign * c
+        });
+        let matrix = match self {
+            Gamut::Srgb => return LinSrgb::new(linear[0], linear[1], linear[2]),
+            Gamut::DisplayP3 => &P3_TO_SRGB,
+            Gamut::Rec2020 => &REC2020_TO_SRGB,
+        };
+        let [r, g, b] =
+            matrix.map(|row| row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]);
+        LinSrgb::new(r, g, b)
+    }
+
+    /// Whether a color is representable in this gamut.
+    pub fn contains(self, color: HellwigJmh) -> bool {
+        self.contains_linear(color.into_linear_srgb_unclamped())
+    }
+
+    /// Whether a color, given as unclamped linear sRGB, is representable in
+    /// this gamut.
+    fn contains_linear(self, linear: LinSrgb<f32>) -> bool {
+        if self.is_srgb() {
+            return srgb_in_gamut(Srgb::from_linear(linear));
+        }
+        self.linear_rgb(linear)
+            .iter()
+            .all(|&c| (-WIDE_GAMUT_TOLERANCE..=1.0 + WIDE_GAMUT_TOLERANCE).contains(&c))
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Gamut::Srgb => 0,
+            Gamut::DisplayP3 => 1,
+            Gamut::Rec2020 => 2,
+        }
+    }
+}
+
+impl fmt::Display for Gamut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Gamut::Srgb => "sRGB",
+            Gamut::DisplayP3 => "Display-P3",
+            Gamut::Rec2020 => "Rec.2020",
+        })
+    }
+}
+
 /// Hue resolution for cache buckets (0.1 = 3600 buckets for 0-360 range).
 const HUE_RESOLUTION: f32 = 0.1;
 
 /// Number of hue buckets in the cache (0.1 degree precision).
 const HUE_BUCKETS: usize = 3600;
 
-/// Thread-local cache for gamut boundary M values.
+/// Bit pattern marking an empty cache slot (a NaN no boundary search returns).
+const EMPTY_SLOT: u32 = u32::MAX;
+
+/// Shared cache for gamut boundary M values.
 ///
-/// Stores the maximum in-gamut colorfulness M for each (J', hue) pair.
-/// Gamut boundaries are mathematically constant, so no invalidation is needed.
-/// Uses Vec<Vec<...>> to avoid stack overflow during initialization.
-struct GamutCache {
-    data: Vec<Vec<Option<f32>>>,
+/// Stores the maximum in-gamut colorfulness M for each (J', hue) bucket as
+/// `f32` bits in atomics, so every thread (including rayon workers) reads and
+/// fills the same table without locking. Gamut boundaries are mathematically
+/// constant and computed at bucket centers, so racing writers store the same
+/// value and no invalidation is needed.
+///
+/// Rows of hue buckets are allocated the first time a J' bucket is used, so
+/// memory grows with the lightness range actually queried, up to
+/// `J_BUCKETS * HUE_BUCKETS * 4` bytes per gamut.
+pub(crate) struct GamutCache {
+    rows: Box<[OnceLock<Box<[AtomicU32]>>]>,
 }
 
 impl GamutCache {
     fn new() -> Self {
         Self {
-            data: vec![vec![None; HUE_BUCKETS]; J_BUCKETS],
+            rows: (0..J_BUCKETS).map(|_| OnceLock::new()).collect(),
         }
     }
 
@@ -57,35 +270,136 @@ impl GamutCache {
     }
 
     fn get(&self, j: f32, hue: f32) -> Option<f32> {
-        let j_idx = Self::j_to_bucket(j);
-        let h_idx = Self::hue_to_bucket(hue);
-        self.data[j_idx][h_idx]
+        let row = self.rows[Self::j_to_bucket(j)].get()?;
+        let bits = row[Self::hue_to_bucket(hue)].load(Ordering::Relaxed);
+        (bits != EMPTY_SLOT).then(|| f32::from_bits(bits))
+    }
+
+    fn put(&self, j: f32, hue: f32, m_max: f32) {
+        let row = self.rows[Self::j_to_bucket(j)].get_or_init(|| {
+            (0..HUE_BUCKETS)
+                .map(|_| AtomicU32::new(EMPTY_SLOT))
+                .collect()
+        });
+        row[Self::hue_to_bucket(hue)].store(m_max.to_bits(), Ordering::Relaxed);
     }
 
-    fn put(&mut self, j: f32, hue: f32, m_max: f32) {
-        let j_idx = Self::j_to_bucket(j);
-        let h_idx = Self::hue_to_bucket(hue);
-        self.data[j_idx][h_idx] = Some(m_max);
+    fn allocated_rows(&self) -> usize {
+        self.rows.iter().filter(|row| row.get().is_some()).count()
     }
 
-    fn clear(&mut self) {
-        for j_slice in self.data.iter_mut() {
-            for entry in j_slice.iter_mut() {
-                *entry = None;
+    fn clear(&self) {
+        for row in self.rows.iter().filter_map(OnceLock::get) {
+            for slot in row.iter() {
+                slot.store(EMPTY_SLOT, Ordering::Relaxed);
             }
         }
     }
 }
 
-thread_local! {
-    static GAMUT_CACHE: RefCell<GamutCache> = RefCell::new(GamutCache::new());
+/// One cache per `Gamut` for the default viewing conditions, allocated on
+/// first use (other conditions keep theirs in their `ViewingModel`).
+static GAMUT_CACHES: [OnceLock<GamutCache>; 3] = [const { OnceLock::new() }; 3];
+
+/// Call `f` with the caches for the viewing conditions in effect on this
+/// thread.
+fn with_active_caches<R>(f: impl FnOnce(&[OnceLock<GamutCache>; 3]) -> R) -> R {
+    read_active_model(|model| f(model.map_or(&GAMUT_CACHES, |model| &model.gamut_caches)))
+}
+
+/// Call `f` with every allocated cache, for the default conditions and
+/// each kept viewing model.
+fn for_all_caches(mut f: impl FnMut(&GamutCache)) {
+    let models = models();
+    std::iter::once(&GAMUT_CACHES)
+        .chain(models.iter().map(|model| &model.gamut_caches))
+        .flatten()
+        .filter_map(OnceLock::get)
+        .for_each(&mut f);
+}
+
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Gamut boundary cache counters, shared by all threads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GamutCacheStats {
+    /// Boundary lookups answered from the cache
+    pub hits: u64,
+    /// Boundary lookups that had to be computed
+    pub misses: u64,
+    /// Memory held by allocated cache rows, across all gamuts and viewing
+    /// conditions
+    pub bytes: usize,
+}
+
+impl GamutCacheStats {
+    /// Fraction of lookups answered from the cache (0 when there were none).
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Current gamut cache hit/miss counts and memory use.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::gamut_map::{gamut_cache_stats, max_colorfulness_at};
+///
+/// let before = gamut_cache_stats();
+/// max_colorfulness_at(60.0, 25.0);
+/// max_colorfulness_at(60.0, 25.0);
+/// let after = gamut_cache_stats();
+/// assert!(after.hits > before.hits);
+/// assert!(after.bytes > 0);
+/// ```
+pub fn gamut_cache_stats() -> GamutCacheStats {
+    let mut rows = 0;
+    for_all_caches(|cache| rows += cache.allocated_rows());
+    GamutCacheStats {
+        hits: CACHE_HITS.load(Ordering::Relaxed),
+        misses: CACHE_MISSES.load(Ordering::Relaxed),
+        bytes: rows * HUE_BUCKETS * std::mem::size_of::<AtomicU32>(),
+    }
 }
 
-/// Clear the thread-local gamut cache.
+/// Clear the shared gamut caches and reset their counters.
 ///
-/// Useful for testing or when you need deterministic behavior.
+/// Allocated rows are kept (and reused), only their entries are emptied.
+/// Useful for testing or benchmarking cold-cache behavior.
 pub fn clear_gamut_cache() {
-    GAMUT_CACHE.with(|c| c.borrow_mut().clear());
+    for_all_caches(GamutCache::clear);
+    CACHE_HITS.store(0, Ordering::Relaxed);
+    CACHE_MISSES.store(0, Ordering::Relaxed);
+}
+
+fn cached_boundary(gamut: Gamut, j: f32, hue: f32) -> Option<f32> {
+    let cached = with_active_caches(|caches| {
+        caches[gamut.index()]
+            .get()
+            .and_then(|cache| cache.get(j, hue))
+    });
+    let counter = if cached.is_some() {
+        &CACHE_HITS
+    } else {
+        &CACHE_MISSES
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+    cached
+}
+
+fn cache_boundary(gamut: Gamut, j: f32, hue: f32, m_max: f32) {
+    with_active_caches(|caches| {
+        caches[gamut.index()]
+            .get_or_init(GamutCache::new)
+            .put(j, hue, m_max)
+    });
 }
 
 /// Get the center J' value for the bucket containing the given J'.
@@ -109,15 +423,18 @@ pub struct Cusp {
     pub m: f32,
 }
 
-/// Look up cusp for a given hue using linear interpolation.
+/// Look up cusp for a given hue using linear interpolation, under the
+/// viewing conditions in effect.
 #[inline]
 pub fn cusp_at_hue(hue_deg: f32) -> Cusp {
     let hue = hue_deg.rem_euclid(360.0);
     let idx = (hue as usize) % 360;
     let frac = hue - idx as f32;
 
-    let c0 = CUSP_LUT[idx];
-    let c1 = CUSP_LUT[(idx + 1) % 360];
+    let (c0, c1) = read_active_model(|model| {
+        let lut = model.map_or(&CUSP_LUT[..], ViewingModel::cusps);
+        (lut[idx], lut[(idx + 1) % 360])
+    });
 
     Cusp {
         j: c0.0 + (c1.0 - c0.0) * frac,
@@ -247,8 +564,15 @@ fn newton_refine(j: f32, h: f32, m_initial: f32) -> f32 {
 /// 4. Verify result is in gamut, reduce M if needed (bucket-center approximation)
 /// 5. Return color with M clamped to boundary
 pub fn gamut_map(color: HellwigJmh) -> HellwigJmh {
+    gamut_map_to(color, Gamut::Srgb)
+}
+
+/// Map an out-of-gamut color to the boundary of `gamut`.
+///
+/// Same algorithm as `gamut_map`, against the given target gamut.
+pub fn gamut_map_to(color: HellwigJmh, gamut: Gamut) -> HellwigJmh {
     // Fast path: check if already in gamut
-    if color.is_in_gamut() {
+    if gamut.contains(color) {
         return color;
     }
 
@@ -259,18 +583,18 @@ pub fn gamut_map(color: HellwigJmh) -> HellwigJmh {
     }
 
     // Use cached boundary lookup (computed at bucket center)
-    let mut m_boundary = max_colorfulness_at(color.lightness, color.hue);
+    let mut m_boundary = max_colorfulness_in(color.lightness, color.hue, gamut);
     m_boundary = m_boundary.min(color.colorfulness);
 
     // Verify result is in gamut (bucket-center value may be slightly off for edge queries)
     let mut result = HellwigJmh::new(color.lightness, m_boundary, color.hue);
-    if !result.is_in_gamut() {
+    if !gamut.contains(result) {
         // Binary search to find safe M for actual coordinates
         let mut lo = 0.0;
         let mut hi = m_boundary;
         while hi - lo > 0.01 {
             let mid = (lo + hi) / 2.0;
-            if HellwigJmh::new(color.lightness, mid, color.hue).is_in_gamut() {
+            if gamut.contains(HellwigJmh::new(color.lightness, mid, color.hue)) {
                 lo = mid;
             } else {
                 hi = mid;
@@ -286,18 +610,26 @@ pub fn gamut_map(color: HellwigJmh) -> HellwigJmh {
 ///
 /// Useful for optimization and constraint checking.
 /// Uses ULP-aware gamut checking from `is_in_gamut()` for accurate boundary detection.
-/// Results are cached in a thread-local 2D cache for performance.
+/// Results are cached in a shared 2D cache for performance.
 ///
 /// Computes boundaries at bucket centers to ensure deterministic results
 /// regardless of query order within the same bucket.
 pub fn max_colorfulness_at(j: f32, hue: f32) -> f32 {
+    max_colorfulness_in(j, hue, Gamut::Srgb)
+}
+
+/// Find the maximum M for a given J' and hue inside `gamut`.
+///
+/// sRGB boundaries are refined from the cusp LUT; wide gamuts have no LUT
+/// and are found by bisection. Both are cached per gamut.
+pub fn max_colorfulness_in(j: f32, hue: f32, gamut: Gamut) -> f32 {
     // Edge cases
     if !(MIN_SAFE_J..=MAX_SAFE_J).contains(&j) {
         return 0.0;
     }
 
     // Check cache first
-    if let Some(m_max) = GAMUT_CACHE.with(|c| c.borrow().get(j, hue)) {
+    if let Some(m_max) = cached_boundary(gamut, j, hue) {
         return m_max;
     }
 
@@ -305,36 +637,162 @@ pub fn max_colorfulness_at(j: f32, hue: f32) -> f32 {
     let j_center = bucket_center_j(j);
     let hue_center = bucket_center_hue(hue);
 
-    // Compute the boundary at bucket center
+    let m_center = if gamut.is_srgb() {
+        srgb_boundary(j_center, hue_center)
+    } else {
+        bisect_boundary(j_center, hue_center, 0.0, MAX_SEARCH_M, gamut)
+    };
+
+    // Note: m_center is computed at bucket center, so it may be slightly out of gamut
+    // for edge values in the bucket. This is acceptable (error < 0.05 in J' and hue).
+    let m_max = m_center;
+
+    // Store in cache
+    cache_boundary(gamut, j, hue, m_max);
+    m_max
+}
+
+/// Boundary of the constant-hue slice of `gamut`: the maximum M at each of
+/// `steps` J' values spread evenly over 0-100, ends included.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::gamut_map::{Gamut, gamut_slice};
+///
+/// let slice = gamut_slice(25.0, Gamut::Srgb, 11);
+/// assert_eq!(slice.len(), 11);
+/// assert_eq!(slice[0], (0.0, 0.0));
+/// // Red is most colorful somewhere between black and white
+/// let (cusp_j, _) = slice.iter().copied().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+/// assert!(cusp_j > 20.0 && cusp_j < 90.0);
+/// ```
+pub fn gamut_slice(hue: f32, gamut: Gamut, steps: usize) -> Vec<(f32, f32)> {
+    let points: Vec<(f32, f32)> = (0..steps)
+        .map(|i| {
+            (
+                100.0 * i as f32 / steps.saturating_sub(1).max(1) as f32,
+                hue,
+            )
+        })
+        .collect();
+    let mut boundary = vec![0.0; steps];
+    max_colorfulness_many(&points, gamut, &mut boundary);
+    points
+        .iter()
+        .zip(boundary)
+        .map(|(&(j, _), m)| (j, m))
+        .collect()
+}
+
+/// `max_colorfulness_in` for many `(J', hue)` points, writing each
+/// boundary to `out`.
+///
+/// Wide-gamut boundaries missing from the cache are bisected together in
+/// batches (see `hellwig::batch_into_linear_srgb`), which pays off with the
+/// `simd` feature; results match `max_colorfulness_in` to within its 0.01
+/// search tolerance. sRGB boundaries are refined one at a time.
+///
+/// # Panics
+///
+/// If `points` and `out` differ in length.
+pub fn max_colorfulness_many(points: &[(f32, f32)], gamut: Gamut, out: &mut [f32]) {
+    assert_eq!(points.len(), out.len(), "boundary output length mismatch");
+    if gamut.is_srgb() {
+        for (m, &(j, hue)) in out.iter_mut().zip(points) {
+            *m = max_colorfulness_in(j, hue, gamut);
+        }
+        return;
+    }
+
+    let mut misses = Vec::new();
+    for (i, (m, &(j, hue))) in out.iter_mut().zip(points).enumerate() {
+        if !(MIN_SAFE_J..=MAX_SAFE_J).contains(&j) {
+            *m = 0.0;
+        } else if let Some(m_max) = cached_boundary(gamut, j, hue) {
+            *m = m_max;
+        } else {
+            misses.push(i);
+        }
+    }
+    if misses.is_empty() {
+        return;
+    }
+
+    let centers: Vec<(f32, f32)> = misses
+        .iter()
+        .map(|&i| (bucket_center_j(points[i].0), bucket_center_hue(points[i].1)))
+        .collect();
+    let mut found = vec![0.0; centers.len()];
+    bisect_boundaries(&centers, gamut, &mut found);
+
+    for (&i, m_max) in misses.iter().zip(found) {
+        let (j, hue) = points[i];
+        cache_boundary(gamut, j, hue, m_max);
+        out[i] = m_max;
+    }
+}
+
+/// sRGB boundary M at a bucket center, starting from the cusp triangle.
+fn srgb_boundary(j_center: f32, hue_center: f32) -> f32 {
     let cusp = cusp_at_hue(hue_center);
     let estimate = triangle_estimate(j_center, cusp);
 
     // Check if estimate is in gamut
     let test = HellwigJmh::new(j_center, estimate, hue_center);
-    let m_center = if test.is_in_gamut() {
+    if test.is_in_gamut() {
         // Estimate is conservative - we might be able to go higher
         // Use binary search to find exact boundary
-        let mut lo = estimate;
-        let mut hi = estimate * 1.5;
+        bisect_boundary(j_center, hue_center, estimate, estimate * 1.5, Gamut::Srgb)
+    } else {
+        newton_refine(j_center, hue_center, estimate)
+    }
+}
 
-        while hi - lo > 0.01 {
-            let mid = (lo + hi) / 2.0;
-            if HellwigJmh::new(j_center, mid, hue_center).is_in_gamut() {
-                lo = mid;
+/// Largest M in `[lo, hi]` (to 0.01) that stays inside `gamut`, assuming
+/// `lo` is in gamut.
+fn bisect_boundary(j: f32, hue: f32, mut lo: f32, mut hi: f32, gamut: Gamut) -> f32 {
+    while hi - lo > 0.01 {
+        let mid = (lo + hi) / 2.0;
+        if gamut.contains(HellwigJmh::new(j, mid, hue)) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// `bisect_boundary` over `[0, MAX_SEARCH_M]` for several `(J', hue)`
+/// points, stepping all searches together so each step converts its
+/// midpoints in one batch.
+fn bisect_boundaries(points: &[(f32, f32)], gamut: Gamut, out: &mut [f32]) {
+    let mut bounds = vec![(0.0, MAX_SEARCH_M); points.len()];
+    let mut colors: Vec<HellwigJmh> = points
+        .iter()
+        .map(|&(j, hue)| HellwigJmh::new(j, 0.0, hue))
+        .collect();
+    let mut linear = vec![LinSrgb::new(0.0, 0.0, 0.0); points.len()];
+
+    while bounds.iter().any(|(lo, hi)| hi - lo > 0.01) {
+        for (color, (lo, hi)) in colors.iter_mut().zip(&bounds) {
+            color.colorfulness = (lo + hi) / 2.0;
+        }
+        batch_into_linear_srgb(&colors, &mut linear);
+
+        for ((color, rgb), (lo, hi)) in colors.iter().zip(&linear).zip(&mut bounds) {
+            if *hi - *lo <= 0.01 {
+                continue;
+            }
+            if gamut.contains_linear(*rgb) {
+                *lo = color.colorfulness;
             } else {
-                hi = mid;
+                *hi = color.colorfulness;
             }
         }
-        lo
-    } else {
-        newton_refine(j_center, hue_center, estimate)
-    };
-
-    // Note: m_center is computed at bucket center, so it may be slightly out of gamut
-    // for edge values in the bucket. This is acceptable (error < 0.05 in J' and hue).
-    let m_max = m_center;
+    }
 
-    // Store in cache
-    GAMUT_CACHE.with(|c| c.borrow_mut().put(j, hue, m_max));
-    m_max
+    for (m, (lo, _)) in out.iter_mut().zip(bounds) {
+        *m = lo;
+    }
 }
@@ -1,22 +1,34 @@
 //! Palette generation logic.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
 
-use csscolorparser::Color as CssColor;
 use palette::Srgb;
 use tinted_builder::{Base16Scheme, Color, SchemeSystem, SchemeVariant};
-use tracing::info;
+use tracing::{debug, info};
+use web_time::Instant;
 
 #[cfg(debug_assertions)]
 use tracing::instrument;
 
-use crate::config::AccentOptSettings;
+use crate::ansi::{AnsiConfig, semantic_warnings};
+use crate::config::{AccentAnchor, AccentOptSettings, CommentContrast};
+use crate::contrast::{ContrastModel, default_model};
+use crate::css_color::parse_srgb;
 use crate::curves::InterpolationConfig;
-use crate::hellwig::hellwig_lightness;
+use crate::extended_accents::{ExtendedAccentMode, ExtendedAccents, derive_extended_accents};
+use crate::gamut_map::{Gamut, gamut_map};
+use crate::hellwig::{HellwigJmh, hellwig_lightness};
+use crate::hue_spacing::{HueSpacing, optimize_hues_fixed};
 use crate::interpolation::{
-    AccentResult, build_hues_with_overrides, generate_accents_uniform, interpolate_with_curves,
-    srgb_to_f32, srgb_to_hex, srgb_to_u8,
+    AccentResult, avoid_background_hue, build_hues_with_overrides, generate_accents_in,
+    interpolate_with_curves, srgb_to_f32, srgb_to_hex, srgb_to_u8, tune_comment_contrast,
 };
+use crate::metadata::{describe_scheme, slugify};
+use crate::ramp::{LightnessRamp, solve_ramp};
+use crate::viewing::{ViewingConditions, with_viewing_conditions};
+use crate::wide_gamut::WidePalette;
 
 /// Result of palette generation including any warnings.
 #[derive(Debug)]
@@ -29,6 +41,43 @@ pub struct GenerationResult {
     pub base_accent_results: Vec<AccentResult>,
     /// Accent results for base10-base17
     pub extended_accent_results: Vec<AccentResult>,
+    /// Every slot in the target gamut (the hex palette is its sRGB fallback)
+    pub wide_palette: WidePalette,
+    /// Time spent in each generation stage
+    pub timings: Timings,
+}
+
+/// Wall-clock time spent in each stage of generation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timings {
+    /// UI ramp base00-base07: interpolation, ramp targets, comment contrast
+    pub ui_ramp: Duration,
+    /// Accent hue selection and spacing
+    pub hues: Duration,
+    /// Base accent optimization (base08-base0F)
+    pub accents: Duration,
+    /// Extended accents (base10-base17)
+    pub extended_accents: Duration,
+    /// Palette assembly, gamut encoding, and semantic checks
+    pub assembly: Duration,
+}
+
+impl Timings {
+    /// Stage names and durations, in pipeline order.
+    pub fn stages(&self) -> [(&'static str, Duration); 5] {
+        [
+            ("ui ramp", self.ui_ramp),
+            ("hues", self.hues),
+            ("accents", self.accents),
+            ("extended accents", self.extended_accents),
+            ("assembly", self.assembly),
+        ]
+    }
+
+    /// Time across all stages.
+    pub fn total(&self) -> Duration {
+        self.stages().iter().map(|(_, d)| *d).sum()
+    }
 }
 
 /// Configuration for palette generation.
@@ -38,27 +87,65 @@ pub struct GenerateConfig {
     pub background: Srgb<u8>,
     /// Foreground color (base07)
     pub foreground: Srgb<u8>,
+    /// Extend the UI ramp this many J' past the foreground, so base06/base07
+    /// end up brighter (or darker, for light variants) than `foreground`.
+    /// 0 keeps the foreground as base07.
+    pub foreground_headroom: f32,
     /// Hue overrides for accent colors (base08-base0F).
     /// `None` values use defaults from `DEFAULT_BASE16_HUES`.
     pub hue_overrides: [Option<f32>; 8],
+    /// Move default accent hues out of a tinted background's hue (and a
+    /// vivid background's complement). Ignored when any hue is overridden.
+    pub adaptive_hues: bool,
+    /// Nudge the accent hues apart for even perceptual spacing.
+    /// `None` uses the hues as given.
+    pub hue_spacing: Option<HueSpacing>,
     /// Minimum APCA contrast for accent colors (Lc value, 30-90 typical).
     /// Colors achieve at least this contrast while maintaining uniform lightness.
     pub min_contrast: f64,
     /// Minimum APCA contrast for extended accent colors base10-base17 (Lc value)
     pub extended_min_contrast: f64,
+    /// How accent contrast is measured; `min_contrast` and
+    /// `extended_min_contrast` are in its units. The UI ramp and comment
+    /// targets are always APCA Lc.
+    pub contrast_model: Arc<dyn ContrastModel>,
     /// Maximum per-hue lightness adjustment allowed (0-10 J' units, default 2.0).
     /// Small adjustments help difficult hues reach minimum contrast.
     pub max_lightness_adjustment: f32,
+    /// Target contrast range for base03 (comments) against base00.
+    /// `None` leaves base03 on the interpolation curve.
+    pub comment_contrast: Option<CommentContrast>,
+    /// Target contrast of base01-base06 against base00; untargeted slots
+    /// stay on the interpolation curve.
+    pub ramp: LightnessRamp,
     /// Scheme name
     pub name: String,
     /// Author name (optional)
     pub author: Option<String>,
+    /// Scheme description; `None` summarizes the generation parameters
+    pub description: Option<String>,
     /// Interpolation curve configuration for L/C/H
     pub interpolation: InterpolationConfig,
     /// Accent optimization settings for main accents (base08-0F)
     pub accent_opt: AccentOptSettings,
     /// Accent optimization settings for extended accents (base10-17)
     pub extended_accent_opt: AccentOptSettings,
+    /// How extended accents are derived; `extended_accent_opt` only applies
+    /// to independent optimization. In `paired` mode the base accents'
+    /// `target_j` may be moved away from the background so every pair meets
+    /// `extended_min_contrast`.
+    pub extended_accents: ExtendedAccents,
+    /// Gamut accents are optimized for. Wider gamuts allow more colorful
+    /// accents; the scheme's hex values are then their sRGB fallbacks.
+    pub gamut: Gamut,
+    /// Slots (e.g. `base0D`) held at exactly this color. A pinned accent
+    /// also fixes its hue, so hue spacing and the matching extended accent
+    /// are built around it; pinned base00/base07 become the ramp endpoints.
+    pub pins: BTreeMap<String, Srgb<u8>>,
+    /// Viewing conditions J'/M/h (targets, curves, gamut bounds) refer to
+    pub viewing: ViewingConditions,
+    /// Hue bands the accents must stay within to keep their ANSI meaning
+    pub ansi: AnsiConfig,
 }
 
 impl Default for GenerateConfig {
@@ -66,12 +153,19 @@ impl Default for GenerateConfig {
         Self {
             background: Srgb::new(26u8, 26, 46),    // #1a1a2e
             foreground: Srgb::new(234u8, 234, 234), // #eaeaea
-            hue_overrides: [None; 8],               // Use DEFAULT_BASE16_HUES
+            foreground_headroom: 0.0,
+            hue_overrides: [None; 8], // Use DEFAULT_BASE16_HUES
+            adaptive_hues: true,
+            hue_spacing: None,
             min_contrast: 75.0,
             extended_min_contrast: 60.0,
+            contrast_model: default_model(),
             max_lightness_adjustment: 2.0,
+            comment_contrast: None,
+            ramp: LightnessRamp::default(),
             name: "Generated Scheme".to_string(),
             author: None,
+            description: None,
             interpolation: InterpolationConfig::default(),
             accent_opt: AccentOptSettings::default(),
             extended_accent_opt: AccentOptSettings {
@@ -79,6 +173,11 @@ impl Default for GenerateConfig {
                 target_m: 35.0,
                 ..AccentOptSettings::default()
             },
+            extended_accents: ExtendedAccents::default(),
+            gamut: Gamut::Srgb,
+            pins: BTreeMap::new(),
+            viewing: ViewingConditions::default(),
+            ansi: AnsiConfig::default(),
         }
     }
 }
@@ -96,11 +195,353 @@ pub fn generate(config: &GenerateConfig) -> GenerationResult {
 ///
 /// If `forced_variant` is Some, swaps bg/fg colors for light variant.
 /// If None, auto-detects based on background luminance.
+///
+/// Runs under `config.viewing` (see `crate::viewing`).
 #[cfg_attr(debug_assertions, instrument(skip(config), fields(name = %config.name, min_contrast = config.min_contrast)))]
 pub fn generate_for_variant(
     config: &GenerateConfig,
     forced_variant: Option<SchemeVariant>,
 ) -> GenerationResult {
+    with_viewing_conditions(&config.viewing, || {
+        generate_in_conditions(config, forced_variant)
+    })
+}
+
+fn generate_in_conditions(
+    config: &GenerateConfig,
+    forced_variant: Option<SchemeVariant>,
+) -> GenerationResult {
+    GenerationCache::new().run(config, forced_variant)
+}
+
+/// Generation with its expensive stages cached between runs.
+///
+/// The pipeline runs in four cached stages: the UI ramp (surfaces), accent
+/// hue selection, base accents (base08-base0F), and extended accents
+/// (base10-base17). Each stage keeps the inputs it last ran with and is
+/// only recomputed when those inputs, or the upstream results it depends
+/// on, change; palette assembly always reruns. Editing an extended accent
+/// setting therefore re-solves only base10-base17.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, GenerationCache};
+///
+/// let mut cache = GenerationCache::new();
+/// let mut config = GenerateConfig::default();
+/// cache.generate(&config);
+///
+/// config.extended_min_contrast = 50.0;
+/// cache.generate(&config);
+/// assert_eq!(cache.recomputed(), ["extended accents"]);
+/// ```
+#[derive(Debug, Default)]
+pub struct GenerationCache {
+    surfaces: CachedStage<SurfacesKey, Surfaces>,
+    hues: CachedStage<HuesKey, [f32; 8]>,
+    accents: CachedStage<AccentsKey, Accents>,
+    extended_accents: CachedStage<ExtendedAccentsKey, Accents>,
+    /// Stages recomputed by the last run, in pipeline order
+    recomputed: Vec<&'static str>,
+}
+
+impl GenerationCache {
+    /// An empty cache; the first run computes every stage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached equivalent of [`generate`].
+    pub fn generate(&mut self, config: &GenerateConfig) -> GenerationResult {
+        self.generate_for_variant(config, None)
+    }
+
+    /// Cached equivalent of [`generate_for_variant`].
+    pub fn generate_for_variant(
+        &mut self,
+        config: &GenerateConfig,
+        forced_variant: Option<SchemeVariant>,
+    ) -> GenerationResult {
+        with_viewing_conditions(&config.viewing, || self.run(config, forced_variant))
+    }
+
+    /// Names of the stages the last run recomputed (as in
+    /// [`Timings::stages`]); the others were reused.
+    pub fn recomputed(&self) -> &[&'static str] {
+        &self.recomputed
+    }
+
+    fn run(
+        &mut self,
+        config: &GenerateConfig,
+        forced_variant: Option<SchemeVariant>,
+    ) -> GenerationResult {
+        let mut timings = Timings::default();
+        let mut stage = Instant::now();
+        let mut lap = |slot: &mut Duration| {
+            *slot = stage.elapsed();
+            stage = Instant::now();
+        };
+        self.recomputed.clear();
+
+        let key = SurfacesKey {
+            background: config.background,
+            foreground: config.foreground,
+            forced_dark: forced_variant
+                .as_ref()
+                .map(|v| matches!(v, SchemeVariant::Dark)),
+            foreground_headroom: config.foreground_headroom,
+            interpolation: config.interpolation.clone(),
+            ramp: config.ramp,
+            comment_contrast: config.comment_contrast,
+            pins: config.pins.clone(),
+            viewing: config.viewing,
+        };
+        let (surfaces, _) = self.surfaces.get_or_run(key, &mut self.recomputed, || {
+            solve_surfaces(config, forced_variant)
+        });
+        let background = surfaces.background;
+        lap(&mut timings.ui_ramp);
+
+        let key = HuesKey {
+            hue_overrides: config.hue_overrides,
+            adaptive_hues: config.adaptive_hues,
+            hue_spacing: config.hue_spacing,
+            target_j: config.accent_opt.target_j,
+            target_m: config.accent_opt.target_m,
+            anchors: config.accent_opt.anchors.clone(),
+            gamut: config.gamut,
+            pins: config.pins.clone(),
+            background: config.background,
+            viewing: config.viewing,
+        };
+        let (accent_hues, _) = self
+            .hues
+            .get_or_run(key, &mut self.recomputed, || select_hues(config));
+        lap(&mut timings.hues);
+
+        let key = AccentsKey {
+            accent_hues: *accent_hues,
+            model: ModelKey(Arc::clone(&config.contrast_model)),
+            settings: config.accent_opt.clone(),
+            min_contrast: config.min_contrast,
+            gamut: config.gamut,
+            pins: config.pins.clone(),
+            background,
+            viewing: config.viewing,
+            paired: (config.extended_accents.mode == ExtendedAccentMode::Paired).then(|| {
+                (
+                    config.extended_accents.amount(),
+                    config.extended_min_contrast,
+                )
+            }),
+        };
+        let (accents, accents_revision) =
+            self.accents.get_or_run(key, &mut self.recomputed, || {
+                solve_base_accents(config, accent_hues, background)
+            });
+        lap(&mut timings.accents);
+
+        let derived = config.extended_accents.mode != ExtendedAccentMode::Independent;
+        let key = ExtendedAccentsKey {
+            accents_revision: derived.then_some(accents_revision),
+            accent_hues: *accent_hues,
+            extended_accents: config.extended_accents,
+            model: ModelKey(Arc::clone(&config.contrast_model)),
+            settings: config.extended_accent_opt.clone(),
+            min_contrast: config.extended_min_contrast,
+            gamut: config.gamut,
+            pins: config.pins.clone(),
+            background,
+            viewing: config.viewing,
+        };
+        let (extended, _) = self
+            .extended_accents
+            .get_or_run(key, &mut self.recomputed, || {
+                solve_extended_accents(config, accent_hues, &accents.results, background)
+            });
+        lap(&mut timings.extended_accents);
+
+        let mut warnings = surfaces.warnings.clone();
+        warnings.extend(accents.warnings.iter().cloned());
+        warnings.extend(extended.warnings.iter().cloned());
+        let (scheme, wide_palette) =
+            assemble(config, surfaces, &accents.results, &extended.results);
+        warnings.extend(semantic_warnings(&scheme, &config.ansi));
+        lap(&mut timings.assembly);
+
+        if !warnings.is_empty() {
+            info!(
+                warning_count = warnings.len(),
+                "scheme generated with warnings"
+            );
+        }
+
+        GenerationResult {
+            scheme,
+            warnings,
+            base_accent_results: accents.results.clone(),
+            extended_accent_results: extended.results.clone(),
+            wide_palette,
+            timings,
+        }
+    }
+}
+
+/// A pipeline stage's last inputs and output.
+#[derive(Debug)]
+struct CachedStage<K, V> {
+    entry: Option<(K, V)>,
+    /// Bumped on every recompute, so downstream stages can key on it
+    revision: u64,
+}
+
+impl<K, V> Default for CachedStage<K, V> {
+    fn default() -> Self {
+        Self {
+            entry: None,
+            revision: 0,
+        }
+    }
+}
+
+impl<K: PartialEq, V> CachedStage<K, V> {
+    /// The output for `key`, running `compute` unless `key` matches the
+    /// last run. Also returns the stage's revision.
+    fn get_or_run(
+        &mut self,
+        key: K,
+        recomputed: &mut Vec<&'static str>,
+        compute: impl FnOnce() -> V,
+    ) -> (&V, u64)
+    where
+        Self: StageName,
+    {
+        if self.entry.as_ref().is_none_or(|(last, _)| *last != key) {
+            self.entry = Some((key, compute()));
+            self.revision += 1;
+            recomputed.push(Self::NAME);
+        }
+        let (_, value) = self.entry.as_ref().expect("stage just ran");
+        (value, self.revision)
+    }
+}
+
+/// Name of a cached stage, matching [`Timings::stages`].
+trait StageName {
+    const NAME: &'static str;
+}
+
+impl StageName for CachedStage<SurfacesKey, Surfaces> {
+    const NAME: &'static str = "ui ramp";
+}
+
+impl StageName for CachedStage<HuesKey, [f32; 8]> {
+    const NAME: &'static str = "hues";
+}
+
+impl StageName for CachedStage<AccentsKey, Accents> {
+    const NAME: &'static str = "accents";
+}
+
+impl StageName for CachedStage<ExtendedAccentsKey, Accents> {
+    const NAME: &'static str = "extended accents";
+}
+
+/// Inputs of the UI ramp stage.
+#[derive(Debug, PartialEq)]
+struct SurfacesKey {
+    background: Srgb<u8>,
+    foreground: Srgb<u8>,
+    /// Forced variant (`SchemeVariant` isn't comparable)
+    forced_dark: Option<bool>,
+    foreground_headroom: f32,
+    interpolation: InterpolationConfig,
+    ramp: LightnessRamp,
+    comment_contrast: Option<CommentContrast>,
+    pins: BTreeMap<String, Srgb<u8>>,
+    viewing: ViewingConditions,
+}
+
+/// The solved UI ramp.
+#[derive(Debug)]
+struct Surfaces {
+    /// base00 after variant selection and pins
+    background: Srgb<u8>,
+    variant: SchemeVariant,
+    /// base00-base07
+    ui_colors: Vec<Srgb<f32>>,
+    warnings: Vec<String>,
+}
+
+/// Inputs of the accent hue stage.
+#[derive(Debug, PartialEq)]
+struct HuesKey {
+    hue_overrides: [Option<f32>; 8],
+    adaptive_hues: bool,
+    hue_spacing: Option<HueSpacing>,
+    target_j: f32,
+    target_m: f32,
+    anchors: BTreeMap<String, AccentAnchor>,
+    gamut: Gamut,
+    pins: BTreeMap<String, Srgb<u8>>,
+    background: Srgb<u8>,
+    viewing: ViewingConditions,
+}
+
+/// A contrast model, compared by identity (share one `Arc` across runs to
+/// reuse cached accents).
+#[derive(Debug)]
+struct ModelKey(Arc<dyn ContrastModel>);
+
+impl PartialEq for ModelKey {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Inputs of the base accent stage.
+#[derive(Debug, PartialEq)]
+struct AccentsKey {
+    accent_hues: [f32; 8],
+    model: ModelKey,
+    settings: AccentOptSettings,
+    min_contrast: f64,
+    gamut: Gamut,
+    pins: BTreeMap<String, Srgb<u8>>,
+    background: Srgb<u8>,
+    viewing: ViewingConditions,
+    /// Pair offset and extended floor, when solved jointly with the
+    /// extended accents
+    paired: Option<(f32, f64)>,
+}
+
+/// Inputs of the extended accent stage.
+#[derive(Debug, PartialEq)]
+struct ExtendedAccentsKey {
+    /// Revision of the base accents, when derived from them
+    accents_revision: Option<u64>,
+    accent_hues: [f32; 8],
+    extended_accents: ExtendedAccents,
+    model: ModelKey,
+    settings: AccentOptSettings,
+    min_contrast: f64,
+    gamut: Gamut,
+    pins: BTreeMap<String, Srgb<u8>>,
+    background: Srgb<u8>,
+    viewing: ViewingConditions,
+}
+
+/// Solved accents and the warnings raised solving them.
+#[derive(Debug)]
+struct Accents {
+    results: Vec<AccentResult>,
+    warnings: Vec<String>,
+}
+
+/// Pick the variant and solve the UI ramp base00-base07.
+fn solve_surfaces(config: &GenerateConfig, forced_variant: Option<SchemeVariant>) -> Surfaces {
     let bg_l = hellwig_lightness(config.background);
     let fg_l = hellwig_lightness(config.foreground);
 
@@ -125,107 +566,364 @@ pub fn generate_for_variant(
         }
     };
 
+    let pinned = |slot: &str| config.pins.get(slot).copied();
+    let background = pinned("base00").unwrap_or(background);
+    let mut warnings = Vec::new();
+
     let bg_f32 = srgb_to_f32(background);
-    let fg_f32 = srgb_to_f32(foreground);
-    let ui_colors = interpolate_with_curves(bg_f32, fg_f32, 8, &config.interpolation);
+    let fg_f32 = if let Some(pin) = pinned("base07") {
+        srgb_to_f32(pin)
+    } else if config.foreground_headroom > 0.0 {
+        let (endpoint, achieved) =
+            extend_foreground(foreground, background, config.foreground_headroom);
+        if achieved + 0.5 < config.foreground_headroom {
+            warnings.push(format!(
+                "foreground headroom limited to {:.1} J' (requested {:.1})",
+                achieved, config.foreground_headroom
+            ));
+        }
+        endpoint
+    } else {
+        srgb_to_f32(foreground)
+    };
+    let mut ui_colors = interpolate_with_curves(bg_f32, fg_f32, 8, &config.interpolation);
+    warnings.extend(solve_ramp(&mut ui_colors, &config.ramp));
 
-    let mut warnings = Vec::new();
-    let accent_hues = build_hues_with_overrides(&config.hue_overrides);
+    // Solve base03 (comments) into its own contrast range instead of the curve
+    // position, staying between base02 and base04
+    if let Some(ref range) = config.comment_contrast {
+        let (comment, achieved) =
+            tune_comment_contrast(ui_colors[3], bg_f32, ui_colors[2], ui_colors[4], range);
+        ui_colors[3] = comment;
+        if achieved < range.min - 0.5 || achieved > range.max + 0.5 {
+            warnings.push(format!(
+                "base03: Lc {:.1} outside comment range {:.0}-{:.0} (kept between base02 and base04)",
+                achieved, range.min, range.max
+            ));
+        }
+    }
+    for (i, color) in ui_colors.iter_mut().enumerate() {
+        if let Some(pin) = pinned(&format!("base0{i:X}")) {
+            *color = srgb_to_f32(pin);
+        }
+    }
 
-    // Generate base accents (base08-base0F) with COBYLA optimization
-    // User's J/M bounds are HARD constraints; contrast is SOFT
-    let base_accent_results = generate_accents_uniform(
-        &accent_hues,
-        &config.accent_opt,
-        config.min_contrast,
+    Surfaces {
         background,
-    );
+        variant,
+        ui_colors,
+        warnings,
+    }
+}
 
-    warnings.extend(base_accent_results.iter().filter_map(|r| r.warning.clone()));
+/// Choose the accent hues: overrides, pins and anchors, background
+/// avoidance, then perceptual spacing.
+///
+/// Hues avoid the configured background rather than the variant's, so the
+/// dark and light variants of one config share their hues.
+fn select_hues(config: &GenerateConfig) -> [f32; 8] {
+    // Pinned and anchored accents fix their hue so everything else is built
+    // around them
+    let mut hue_overrides = config.hue_overrides;
+    let mut fixed_hues = [false; 8];
+    for (i, (hue, fixed)) in hue_overrides.iter_mut().zip(&mut fixed_hues).enumerate() {
+        let brand = config
+            .pins
+            .get(&format!("base0{:X}", 8 + i))
+            .map(|pin| HellwigJmh::from_srgb_u8(*pin))
+            .or_else(|| config.accent_opt.anchor(i).and_then(AccentAnchor::jmh));
+        if let Some(brand) = brand {
+            *hue = Some(brand.hue);
+            *fixed = true;
+        }
+    }
+    let mut accent_hues = build_hues_with_overrides(&hue_overrides);
+    if config.adaptive_hues && hue_overrides.iter().all(Option::is_none) {
+        accent_hues =
+            avoid_background_hue(&accent_hues, &HellwigJmh::from_srgb_u8(config.background));
+    }
+    if let Some(ref spacing) = config.hue_spacing {
+        accent_hues = optimize_hues_fixed(
+            &accent_hues,
+            &fixed_hues,
+            config.accent_opt.target_j,
+            config.accent_opt.target_m,
+            config.gamut,
+            spacing,
+        );
+    }
+    accent_hues
+}
 
-    // Generate extended accents (base10-base17) with COBYLA optimization
+/// Solve base08-base0F, then apply pins.
+fn solve_base_accents(
+    config: &GenerateConfig,
+    accent_hues: &[f32],
+    background: Srgb<u8>,
+) -> Accents {
+    // Generate base accents (base08-base0F) with COBYLA optimization
     // User's J/M bounds are HARD constraints; contrast is SOFT
-    let extended_accent_results = generate_accents_uniform(
-        &accent_hues,
-        &config.extended_accent_opt,
+    let solve = |settings: &AccentOptSettings| {
+        generate_accents_in(
+            accent_hues,
+            settings,
+            config.min_contrast,
+            background,
+            config.gamut,
+            &*config.contrast_model,
+        )
+    };
+    let mut results = if config.extended_accents.mode == ExtendedAccentMode::Paired {
+        solve_paired(config, background, solve)
+    } else {
+        solve(&config.accent_opt)
+    };
+    for (i, result) in results.iter_mut().enumerate() {
+        let slot = format!("base0{:X}", 8 + i);
+        if let Some(pin) = config.pins.get(&slot) {
+            pin_accent(
+                result,
+                &slot,
+                *pin,
+                background,
+                config.min_contrast,
+                &*config.contrast_model,
+            );
+        }
+    }
+    let warnings = results.iter().filter_map(|r| r.warning.clone()).collect();
+    Accents { results, warnings }
+}
+
+/// Largest move of the shared target J' when solving paired accents.
+const MAX_PAIRED_SHIFT: f32 = 20.0;
+
+/// J' the shared target moves per attempt when solving paired accents.
+const PAIRED_STEP: f32 = 2.5;
+
+/// Solve base08-base0F for `paired` extended accents.
+///
+/// The pairs share one lightness target: while a derived base10-base17
+/// misses `extended_min_contrast`, the base accents' `target_j` moves away
+/// from the background and everything is solved again, up to
+/// [`MAX_PAIRED_SHIFT`].
+fn solve_paired(
+    config: &GenerateConfig,
+    background: Srgb<u8>,
+    solve: impl Fn(&AccentOptSettings) -> Vec<AccentResult>,
+) -> Vec<AccentResult> {
+    let direction = if config.accent_opt.target_j >= hellwig_lightness(background) {
+        1.0
+    } else {
+        -1.0
+    };
+    let mut settings = config.accent_opt.clone();
+    loop {
+        let results = solve(&settings);
+        let pairs_met = derive_extended_accents(
+            &results,
+            &config.extended_accents,
+            config.extended_min_contrast,
+            background,
+            config.gamut,
+            &*config.contrast_model,
+        )
+        .is_none_or(|pairs| pairs.iter().all(|pair| pair.met_minimum));
+        let shift = (settings.target_j - config.accent_opt.target_j).abs();
+        let next = (settings.target_j + direction * PAIRED_STEP).clamp(0.0, 100.0);
+        if pairs_met || shift + PAIRED_STEP > MAX_PAIRED_SHIFT || next == settings.target_j {
+            if shift > 0.0 {
+                debug!(
+                    target_j = settings.target_j,
+                    "moved shared accent lightness to fit paired accents"
+                );
+            }
+            return results;
+        }
+        settings.target_j = next;
+    }
+}
+
+/// Derive or solve base10-base17, then apply pins.
+fn solve_extended_accents(
+    config: &GenerateConfig,
+    accent_hues: &[f32],
+    base_accent_results: &[AccentResult],
+    background: Srgb<u8>,
+) -> Accents {
+    let mut warnings = Vec::new();
+
+    // Generate extended accents (base10-base17) from the base accents, or
+    // independently with COBYLA optimization
+    let derived = derive_extended_accents(
+        base_accent_results,
+        &config.extended_accents,
         config.extended_min_contrast,
         background,
+        config.gamut,
+        &*config.contrast_model,
     );
+    if derived.is_some() && !config.extended_accent_opt.anchors.is_empty() {
+        warnings.push(format!(
+            "extended anchors ignored: base10-base17 are derived ({})",
+            config.extended_accents.mode.name()
+        ));
+    }
+    let mut results = derived.unwrap_or_else(|| {
+        generate_accents_in(
+            accent_hues,
+            &config.extended_accent_opt,
+            config.extended_min_contrast,
+            background,
+            config.gamut,
+            &*config.contrast_model,
+        )
+    });
+    for (i, result) in results.iter_mut().enumerate() {
+        let slot = format!("base1{i:X}");
+        if let Some(pin) = config.pins.get(&slot) {
+            pin_accent(
+                result,
+                &slot,
+                *pin,
+                background,
+                config.extended_min_contrast,
+                &*config.contrast_model,
+            );
+        }
+    }
 
-    warnings.extend(
-        extended_accent_results
-            .iter()
-            .filter_map(|r| r.warning.clone()),
-    );
+    warnings.extend(results.iter().filter_map(|r| r.warning.clone()));
+    Accents { results, warnings }
+}
 
-    // Build the palette HashMap
+/// Build the scheme and its wide-gamut palette from the solved stages.
+fn assemble(
+    config: &GenerateConfig,
+    surfaces: &Surfaces,
+    base_accent_results: &[AccentResult],
+    extended_accent_results: &[AccentResult],
+) -> (Base16Scheme, WidePalette) {
+    // Build the palette HashMap, and the same slots in the target gamut
     let mut palette = HashMap::new();
+    let mut wide_colors = BTreeMap::new();
 
-    for (i, color) in ui_colors.iter().enumerate() {
+    for (i, color) in surfaces.ui_colors.iter().enumerate() {
         let name = format!("base0{:X}", i);
-        let hex = srgb_to_hex(srgb_to_u8(*color));
-        palette.insert(name, Color::new(hex).expect("valid hex"));
+        let srgb = srgb_to_u8(*color);
+        wide_colors.insert(
+            name.clone(),
+            config.gamut.encode(HellwigJmh::from_srgb_u8(srgb)),
+        );
+        palette.insert(name, Color::new(srgb_to_hex(srgb)).expect("valid hex"));
     }
 
     for (i, result) in base_accent_results.iter().enumerate() {
         let name = format!("base0{:X}", 8 + i);
         let hex = srgb_to_hex(srgb_to_u8(result.color));
+        wide_colors.insert(name.clone(), config.gamut.encode(result.jmh));
         palette.insert(name, Color::new(hex).expect("valid hex"));
     }
 
     for (i, result) in extended_accent_results.iter().enumerate() {
         let name = format!("base1{:X}", i);
         let hex = srgb_to_hex(srgb_to_u8(result.color));
+        wide_colors.insert(name.clone(), config.gamut.encode(result.jmh));
         palette.insert(name, Color::new(hex).expect("valid hex"));
     }
 
     // Create slug from name with variant suffix
-    let variant_suffix = match variant {
+    let variant_suffix = match surfaces.variant {
         SchemeVariant::Dark => "-dark",
         SchemeVariant::Light => "-light",
-        v => unreachable!("unsupported variant: {v:?}"),
+        ref v => unreachable!("unsupported variant: {v:?}"),
     };
-    let base_slug: String = config
-        .name
-        .to_lowercase()
-        .replace(' ', "-")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect();
-    let slug = format!("{}{}", base_slug, variant_suffix);
-
-    let scheme = Base16Scheme {
+    let slug = format!("{}{}", slugify(&config.name), variant_suffix);
+
+    let mut scheme = Base16Scheme {
         system: SchemeSystem::Base24,
         name: config.name.clone(),
         slug,
         author: config.author.clone().unwrap_or_default(),
         description: None,
-        variant,
+        variant: surfaces.variant.clone(),
         palette,
     };
+    scheme.description = Some(
+        config
+            .description
+            .clone()
+            .unwrap_or_else(|| describe_scheme(&scheme, config)),
+    );
+    let wide_palette = WidePalette {
+        gamut: config.gamut,
+        colors: wide_colors,
+    };
+    (scheme, wide_palette)
+}
 
-    if !warnings.is_empty() {
-        info!(
-            warning_count = warnings.len(),
-            "scheme generated with warnings"
-        );
-    }
+/// Replace a solved accent with its pinned color, re-checking its contrast
+/// against `background` under `model`.
+fn pin_accent(
+    result: &mut AccentResult,
+    slot: &str,
+    pin: Srgb<u8>,
+    background: Srgb<u8>,
+    min_contrast: f64,
+    model: &dyn ContrastModel,
+) {
+    let jmh = HellwigJmh::from_srgb_u8(pin);
+    let contrast = model.contrast(pin, background).abs();
+    let met_minimum = contrast >= min_contrast;
+    *result = AccentResult {
+        color: srgb_to_f32(pin),
+        jmh,
+        hue: jmh.hue,
+        lightness: jmh.lightness,
+        post_clamp_lightness: jmh.lightness,
+        j_deviation: 0.0,
+        achieved_contrast: contrast,
+        met_minimum,
+        was_gamut_mapped: false,
+        m_in_bounds: true,
+        warning: (!met_minimum).then(|| {
+            format!(
+                "{slot} (pinned): {} {contrast:.1} < {min_contrast:.1}",
+                model.label()
+            )
+        }),
+    };
+}
 
-    GenerationResult {
-        scheme,
-        warnings,
-        base_accent_results,
-        extended_accent_results,
-    }
+/// Move the foreground `headroom` J' further from the background, keeping
+/// its hue and as much colorfulness as the sRGB gamut allows there.
+///
+/// Returns the new ramp endpoint and the J' distance actually gained, which
+/// falls short of `headroom` when the foreground is already near white (or
+/// black, for light variants).
+fn extend_foreground(
+    foreground: Srgb<u8>,
+    background: Srgb<u8>,
+    headroom: f32,
+) -> (Srgb<f32>, f32) {
+    let fg = HellwigJmh::from_srgb_u8(foreground);
+    let direction = if fg.lightness >= hellwig_lightness(background) {
+        1.0
+    } else {
+        -1.0
+    };
+    let lightness = (fg.lightness + direction * headroom).clamp(0.0, 100.0);
+    let extended = gamut_map(HellwigJmh::new(lightness, fg.colorfulness, fg.hue));
+    (
+        extended.into_srgb(),
+        (extended.lightness - fg.lightness).abs(),
+    )
 }
 
 /// Parse any CSS color string into `Srgb<u8>`.
 ///
-/// Supports: hex (#RRGGBB), rgb(), oklch(), named colors, etc.
+/// Supports hex (#RRGGBB), named colors, rgb(), hsl(), oklch(), color(), etc.
+/// Colors outside sRGB are gamut mapped; see `css_color`.
 pub fn parse_color(input: &str) -> Result<Srgb<u8>, String> {
-    let css_color: CssColor = input
-        .parse()
-        .map_err(|e| format!("Invalid color '{}': {}", input, e))?;
-    let [r, g, b, _a] = css_color.to_rgba8();
-    Ok(Srgb::new(r, g, b))
+    parse_srgb(input)
 }
@@ -0,0 +1,162 @@
+//! Accent hues from color-harmony rules (`--harmony`).
+//!
+//! The default accent hues follow ANSI convention. A harmony instead places
+//! all 8 around one seed hue by a classic rule: analogous hues fan out from
+//! the seed, complementary ones cluster at the seed and its opposite, and
+//! split-complementary, triadic, and tetradic ones cluster at three or four
+//! points around the wheel. Each cluster spreads its hues a fixed step apart
+//! so the accents stay distinguishable.
+//!
+//! The hues are handed out in wheel order, rotated to sit as close to the
+//! default hues as the rule allows, so base08 stays the reddest accent. Harmony hues replace the defaults; explicit hue overrides and
+//! links still apply on top of them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+use crate::interpolation::DEFAULT_BASE16_HUES;
+
+/// A color-harmony rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Harmony {
+    /// Neighbors of the seed
+    Analogous,
+    /// The seed and its opposite
+    Complementary,
+    /// The seed and the two hues beside its opposite
+    SplitComplementary,
+    /// Three hues 120° apart
+    Triadic,
+    /// Four hues 90° apart
+    Tetradic,
+}
+
+impl Harmony {
+    /// All rules, in TUI cycling order.
+    pub const ALL: [Harmony; 5] = [
+        Harmony::Analogous,
+        Harmony::Complementary,
+        Harmony::SplitComplementary,
+        Harmony::Triadic,
+        Harmony::Tetradic,
+    ];
+
+    /// Name as accepted by `--harmony`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Harmony::Analogous => "analogous",
+            Harmony::Complementary => "complementary",
+            Harmony::SplitComplementary => "split-complementary",
+            Harmony::Triadic => "triadic",
+            Harmony::Tetradic => "tetradic",
+        }
+    }
+
+    /// The rule after `current` when cycling (None = default hues).
+    pub fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::ALL[0]),
+            Some(rule) => {
+                let index = Self::ALL.iter().position(|r| *r == rule).unwrap_or(0);
+                Self::ALL.get(index + 1).copied()
+            }
+        }
+    }
+
+    /// Cluster centers relative to the seed, and the step between hues in
+    /// a cluster (degrees).
+    fn clusters(self) -> (&'static [f32], f32) {
+        match self {
+            Harmony::Analogous => (&[0.0], 15.0),
+            Harmony::Complementary => (&[0.0, 180.0], 20.0),
+            Harmony::SplitComplementary => (&[0.0, 150.0, 210.0], 20.0),
+            Harmony::Triadic => (&[0.0, 120.0, 240.0], 20.0),
+            Harmony::Tetradic => (&[0.0, 90.0, 180.0, 270.0], 25.0),
+        }
+    }
+
+    /// The 8 accent hues (base08 first) this rule places around `seed`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::harmony::Harmony;
+    ///
+    /// let hues = Harmony::Triadic.hues(220.0);
+    /// // Every hue sits within 20° of 220, 340, or 100
+    /// for hue in hues {
+    ///     let near = [220.0, 340.0, 100.0].iter().any(|c: &f32| {
+    ///         let d = (hue - c).rem_euclid(360.0);
+    ///         d.min(360.0 - d) <= 20.0
+    ///     });
+    ///     assert!(near, "{hue}");
+    /// }
+    /// ```
+    pub fn hues(self, seed: f32) -> [f32; 8] {
+        let (centers, step) = self.clusters();
+        let mut hues = Vec::with_capacity(8);
+        for (i, center) in centers.iter().enumerate() {
+            // Earlier clusters take the remainder, so the seed's is largest
+            let count = 8 / centers.len() + usize::from(i < 8 % centers.len());
+            let first = center - step * (count - 1) as f32 / 2.0;
+            hues.extend((0..count).map(|k| (seed + first + step * k as f32).rem_euclid(360.0)));
+        }
+        hues.sort_by(f32::total_cmp);
+
+        // Hand the hues out in wheel order, rotated to sit closest to the
+        // defaults
+        let distance = |a: f32, b: f32| {
+            let d = (a - b).rem_euclid(360.0);
+            d.min(360.0 - d)
+        };
+        let cost = |rotation: usize| -> f32 {
+            (0..8)
+                .map(|i| distance(hues[(i + rotation) % 8], DEFAULT_BASE16_HUES[i]))
+                .sum()
+        };
+        let rotation = (0..8)
+            .min_by(|a, b| cost(*a).total_cmp(&cost(*b)))
+            .unwrap_or(0);
+        std::array::from_fn(|i| hues[(i + rotation) % 8])
+    }
+}
+
+impl std::fmt::Display for Harmony {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Settings for harmony-derived accent hues.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HarmonyConfig {
+    pub rule: Harmony,
+    /// Hue the rule is anchored on (degrees; default base08's default hue)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed_hue: Option<f32>,
+}
+
+impl HarmonyConfig {
+    /// Check that the seed is a hue angle.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(seed) = self.seed_hue
+            && !(0.0..360.0).contains(&seed)
+        {
+            return Err(ConfigError::InvalidValue(format!(
+                "colors.harmony.seed_hue must be between 0 and 360 degrees (got {seed})"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The hue the rule is anchored on.
+    pub fn seed(&self) -> f32 {
+        self.seed_hue.unwrap_or(DEFAULT_BASE16_HUES[0])
+    }
+
+    /// The 8 accent hues, base08 first.
+    pub fn hues(&self) -> [f32; 8] {
+        self.rule.hues(self.seed())
+    }
+}
@@ -11,14 +11,17 @@ use std::sync::LazyLock;
 
 use palette::cam16::{BakedParameters, Cam16Jmh, Parameters, StaticWp};
 use palette::convert::IntoColorUnclamped;
-use palette::white_point::D65;
-use palette::{IntoColor, Srgb, Xyz};
+use palette::white_point::{Any, D65};
+use palette::{LinSrgb, Srgb, Xyz};
 
 use crate::gamut_map::gamut_map;
 use crate::generated::{ECCENTRICITY_CAM16_LUT, ECCENTRICITY_LUT, HK_HUE_LUT};
+use crate::viewing::{ViewingModel, read_active_model};
 
 /// Default viewing conditions for sRGB D65 display viewing.
 ///
+/// Used unless other conditions are in effect (see `crate::viewing`).
+///
 /// - adapting_luminance: 64/π × 0.2 cd/m² (gray world assumption)
 /// - background_luminance: 20% of Yw=100
 /// - surround: average
@@ -116,8 +119,18 @@ impl HellwigJmh {
     ///
     /// Applies eccentricity correction and HK effect.
     pub fn from_srgb(srgb: Srgb<f32>) -> Self {
-        let xyz: Xyz<D65, f32> = srgb.into_linear().into_color();
-        let cam16 = Cam16Jmh::from_xyz(xyz, *DEFAULT_PARAMS);
+        Self::from_linear_srgb(srgb.into_linear())
+    }
+
+    /// Convert from linear sRGB to Hellwig-Fairchild JMh.
+    ///
+    /// Channels may lie outside [0, 1], so wide-gamut colors convert too.
+    pub fn from_linear_srgb(linear: LinSrgb<f32>) -> Self {
+        let xyz: Xyz<D65, f32> = linear.into_color_unclamped();
+        let cam16 = read_active_model(|model| match model {
+            None => Cam16Jmh::from_xyz(xyz, *DEFAULT_PARAMS),
+            Some(model) => Cam16Jmh::from_xyz(xyz.with_white_point::<Any>(), model.params),
+        });
 
         let hue_rad = cam16.hue.into_radians();
 
@@ -155,6 +168,19 @@ impl HellwigJmh {
     /// Out-of-gamut colors may have values outside [0, 1].
     /// Use `is_in_gamut()` to check before using these values directly.
     pub fn into_srgb_unclamped(self) -> Srgb<f32> {
+        Srgb::from_linear(self.into_linear_srgb_unclamped())
+    }
+
+    /// Convert from Hellwig-Fairchild JMh to linear sRGB without clamping.
+    ///
+    /// Wide-gamut colors are representable as out-of-range linear sRGB, so
+    /// this is the starting point for conversions into other RGB spaces.
+    pub fn into_linear_srgb_unclamped(self) -> LinSrgb<f32> {
+        read_active_model(|model| self.into_linear_srgb_in(model))
+    }
+
+    /// `into_linear_srgb_unclamped` under `model` (`None` for the defaults).
+    pub(crate) fn into_linear_srgb_in(self, model: Option<&ViewingModel>) -> LinSrgb<f32> {
         let hue_rad = self.hue.to_radians();
 
         // Reverse HK effect
@@ -166,8 +192,11 @@ impl HellwigJmh {
         let colorfulness = self.colorfulness * e_ratio;
 
         let cam16 = Cam16Jmh::new(lightness_base, colorfulness, self.hue);
-        let xyz = cam16.into_xyz(*DEFAULT_PARAMS);
-        Srgb::from_linear(xyz.into_color_unclamped())
+        let xyz: Xyz<D65, f32> = match model {
+            None => cam16.into_xyz(*DEFAULT_PARAMS),
+            Some(model) => cam16.into_xyz(model.params).with_white_point(),
+        };
+        xyz.into_color_unclamped()
     }
 
     /// Convert from sRGB u8 to Hellwig-Fairchild JMh.
@@ -195,20 +224,7 @@ impl HellwigJmh {
     /// Returns true if the color can be represented in sRGB without clipping.
     /// Uses ULP-based comparison for boundary precision.
     pub fn is_in_gamut(&self) -> bool {
-        use float_cmp::approx_eq;
-
-        /// Check if a channel value is within valid sRGB bounds [0, 1].
-        /// Uses ULP comparison to handle floating-point precision at boundaries.
-        #[inline]
-        fn is_channel_in_bounds(c: f32) -> bool {
-            (c > 0.0 || approx_eq!(f32, c, 0.0, ulps = 2))
-                && (c < 1.0 || approx_eq!(f32, c, 1.0, ulps = 2))
-        }
-
-        let srgb = self.into_srgb_unclamped();
-        is_channel_in_bounds(srgb.red)
-            && is_channel_in_bounds(srgb.green)
-            && is_channel_in_bounds(srgb.blue)
+        srgb_in_gamut(self.into_srgb_unclamped())
     }
 
     /// Convert to sRGB with perceptual gamut mapping.
@@ -231,6 +247,201 @@ impl HellwigJmh {
             (srgb.blue.clamp(0.0, 1.0) * 255.0).round() as u8,
         )
     }
+
+    /// Color difference: Euclidean distance in (J', a, b), where a/b are M
+    /// projected onto the hue angle.
+    pub fn delta_e(&self, other: &Self) -> f32 {
+        let (h1, h2) = (self.hue.to_radians(), other.hue.to_radians());
+        let da = self.colorfulness * h1.cos() - other.colorfulness * h2.cos();
+        let db = self.colorfulness * h1.sin() - other.colorfulness * h2.sin();
+        let dj = self.lightness - other.lightness;
+        (dj * dj + da * da + db * db).sqrt()
+    }
+}
+
+/// Whether unclamped sRGB channels lie in [0, 1], to within 2 ULPs at the
+/// bounds.
+pub(crate) fn srgb_in_gamut(srgb: Srgb<f32>) -> bool {
+    use float_cmp::approx_eq;
+
+    /// Check if a channel value is within valid sRGB bounds [0, 1].
+    /// Uses ULP comparison to handle floating-point precision at boundaries.
+    #[inline]
+    fn is_channel_in_bounds(c: f32) -> bool {
+        (c > 0.0 || approx_eq!(f32, c, 0.0, ulps = 2))
+            && (c < 1.0 || approx_eq!(f32, c, 1.0, ulps = 2))
+    }
+
+    is_channel_in_bounds(srgb.red)
+        && is_channel_in_bounds(srgb.green)
+        && is_channel_in_bounds(srgb.blue)
+}
+
+/// Colors converted together by the batch functions: eight `f32x8` lanes
+/// with the `simd` feature, one at a time without it.
+pub const BATCH_LANES: usize = if cfg!(feature = "simd") { 8 } else { 1 };
+
+/// Convert a batch of sRGB colors to Hellwig JMh, `out[i]` from `colors[i]`.
+///
+/// Equivalent to `HellwigJmh::from_srgb` per color. With the `simd` feature
+/// the CAM16 step runs on eight colors at a time under the default viewing
+/// conditions (other conditions convert one at a time); results then differ
+/// from the scalar path in the last few ULPs.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::hellwig::{HellwigJmh, batch_from_srgb};
+///
+/// let colors = [Srgb::new(0.8f32, 0.2, 0.2), Srgb::new(0.2, 0.4, 0.9)];
+/// let mut jmh = [HellwigJmh::new(0.0, 0.0, 0.0); 2];
+/// batch_from_srgb(&colors, &mut jmh);
+/// assert!((jmh[1].hue - HellwigJmh::from_srgb(colors[1]).hue).abs() < 0.01);
+/// ```
+pub fn batch_from_srgb(colors: &[Srgb<f32>], out: &mut [HellwigJmh]) {
+    assert_eq!(colors.len(), out.len(), "batch input and output lengths");
+
+    #[cfg(feature = "simd")]
+    if read_active_model(|model| model.is_none()) {
+        for (colors, out) in colors.chunks(BATCH_LANES).zip(out.chunks_mut(BATCH_LANES)) {
+            let linear = simd::lanes(colors, |c| c.into_linear());
+            out.copy_from_slice(&simd::from_linear_srgb(&linear)[..out.len()]);
+        }
+        return;
+    }
+
+    for (color, out) in colors.iter().zip(out) {
+        *out = HellwigJmh::from_srgb(*color);
+    }
+}
+
+/// Convert a batch of Hellwig JMh colors to unclamped linear sRGB, `out[i]`
+/// from `colors[i]`.
+///
+/// Equivalent to `HellwigJmh::into_linear_srgb_unclamped` per color; see
+/// `batch_from_srgb` for the `simd` feature.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+pub fn batch_into_linear_srgb(colors: &[HellwigJmh], out: &mut [LinSrgb<f32>]) {
+    assert_eq!(colors.len(), out.len(), "batch input and output lengths");
+    read_active_model(|model| {
+        #[cfg(feature = "simd")]
+        if model.is_none() {
+            for (colors, out) in colors.chunks(BATCH_LANES).zip(out.chunks_mut(BATCH_LANES)) {
+                let colors = simd::lanes(colors, |c| *c);
+                out.copy_from_slice(&simd::into_linear_srgb(&colors)[..out.len()]);
+            }
+            return;
+        }
+
+        for (color, out) in colors.iter().zip(out) {
+            *out = color.into_linear_srgb_in(model);
+        }
+    })
+}
+
+/// Convert a batch of Hellwig JMh colors to sRGB, clamping out-of-gamut
+/// channels like `HellwigJmh::into_srgb`.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+pub fn batch_into_srgb(colors: &[HellwigJmh], out: &mut [Srgb<f32>]) {
+    let mut linear = vec![LinSrgb::new(0.0, 0.0, 0.0); colors.len()];
+    batch_into_linear_srgb(colors, &mut linear);
+    for (linear, out) in linear.into_iter().zip(out) {
+        let srgb: Srgb<f32> = Srgb::from_linear(linear);
+        *out = Srgb::new(
+            srgb.red.clamp(0.0, 1.0),
+            srgb.green.clamp(0.0, 1.0),
+            srgb.blue.clamp(0.0, 1.0),
+        );
+    }
+}
+
+/// CAM16 on `f32x8` lanes for the batch functions, under the default viewing
+/// conditions. The eccentricity and HK corrections use hue LUTs, so they
+/// stay scalar per lane.
+#[cfg(feature = "simd")]
+mod simd {
+    use palette::cam16::Cam16Jmh;
+    use palette::convert::IntoColorUnclamped;
+    use palette::white_point::D65;
+    use palette::{LinSrgb, Xyz};
+    use wide::f32x8;
+
+    use super::{
+        BATCH_LANES, DEFAULT_PARAMS, HellwigJmh, eccentricity, eccentricity_cam16,
+        hue_angle_dependency,
+    };
+
+    /// Up to `BATCH_LANES` items mapped into a full set of lanes, padding
+    /// with the first item.
+    pub(super) fn lanes<T, U: Copy>(items: &[T], f: impl Fn(&T) -> U) -> [U; BATCH_LANES] {
+        let first = f(&items[0]);
+        std::array::from_fn(|i| items.get(i).map_or(first, &f))
+    }
+
+    pub(super) fn from_linear_srgb(
+        colors: &[LinSrgb<f32>; BATCH_LANES],
+    ) -> [HellwigJmh; BATCH_LANES] {
+        let linear = LinSrgb::new(
+            f32x8::from(colors.map(|c| c.red)),
+            f32x8::from(colors.map(|c| c.green)),
+            f32x8::from(colors.map(|c| c.blue)),
+        );
+        let xyz: Xyz<D65, f32x8> = linear.into_color_unclamped();
+        let cam16 = Cam16Jmh::from_xyz(xyz, *DEFAULT_PARAMS);
+        let lightness = cam16.lightness.to_array();
+        let colorfulness = cam16.colorfulness.to_array();
+        let hue = cam16.hue.into_positive_degrees().to_array();
+
+        std::array::from_fn(|i| {
+            let hue_rad = hue[i].to_radians();
+            let e_ratio = eccentricity(hue_rad) / eccentricity_cam16(hue_rad);
+            let colorfulness = colorfulness[i] * e_ratio;
+            let chroma = colorfulness * 35.0 / 100.0;
+            HellwigJmh {
+                lightness: lightness[i] + hue_angle_dependency(hue_rad) * chroma.powf(0.587),
+                colorfulness,
+                hue: hue[i],
+            }
+        })
+    }
+
+    pub(super) fn into_linear_srgb(
+        colors: &[HellwigJmh; BATCH_LANES],
+    ) -> [LinSrgb<f32>; BATCH_LANES] {
+        // Reverse HK and eccentricity per lane, then CAM16 on all lanes
+        let corrected = colors.map(|c| {
+            let hue_rad = c.hue.to_radians();
+            let chroma = c.colorfulness * 35.0 / 100.0;
+            (
+                c.lightness - hue_angle_dependency(hue_rad) * chroma.powf(0.587),
+                c.colorfulness * (eccentricity_cam16(hue_rad) / eccentricity(hue_rad)),
+            )
+        });
+        let cam16 = Cam16Jmh::new(
+            f32x8::from(corrected.map(|(j, _)| j)),
+            f32x8::from(corrected.map(|(_, m)| m)),
+            f32x8::from(colors.map(|c| c.hue)),
+        );
+        let xyz: Xyz<D65, f32x8> = cam16.into_xyz(*DEFAULT_PARAMS);
+        let linear: LinSrgb<f32x8> = xyz.into_color_unclamped();
+        let (red, green, blue) = (
+            linear.red.to_array(),
+            linear.green.to_array(),
+            linear.blue.to_array(),
+        );
+        std::array::from_fn(|i| LinSrgb::new(red[i], green[i], blue[i]))
+    }
 }
 
 /// Get HellwigJmh lightness for an sRGB color.
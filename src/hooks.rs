@@ -0,0 +1,133 @@
+//! External commands run on generation events.
+//!
+//! Hooks are configured in the `[hooks]` table and run through the shell
+//! after each scheme file is written, including every rebuild under
+//! `--watch` and `--daemon`:
+//!
+//! ```toml
+//! [hooks]
+//! post_generate = "tinty apply base16-$(basename \"$THEMALINGADINGDONG_OUTPUT\" .yaml)"
+//! ```
+//!
+//! The command gets the written path in `THEMALINGADINGDONG_OUTPUT`, the
+//! event name in `THEMALINGADINGDONG_EVENT`, and a JSON [`HookSummary`] both
+//! in `THEMALINGADINGDONG_SUMMARY` and on stdin.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use color_eyre::eyre::{Result, WrapErr, bail};
+use serde::{Deserialize, Serialize};
+use tinted_builder::Base16Scheme;
+
+use crate::config::ConfigError;
+
+/// Commands to run on generation events.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Shell command run after each scheme file is written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_generate: Option<String>,
+}
+
+impl HooksConfig {
+    /// Whether no hook is set, for skipping the table when serializing.
+    pub fn is_empty(&self) -> bool {
+        self.post_generate.is_none()
+    }
+
+    /// Check that every hook is a non-empty command.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(ref command) = self.post_generate
+            && command.trim().is_empty()
+        {
+            return Err(ConfigError::InvalidValue(
+                "hooks.post_generate must be a command (remove the key to disable it)".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What a hook is told about the event, as JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookSummary {
+    /// Event that triggered the hook (`post_generate`)
+    pub event: &'static str,
+    /// File that was written
+    pub output: PathBuf,
+    pub name: String,
+    pub slug: String,
+    /// `dark` or `light`
+    pub variant: String,
+    /// `base16` or `base24`
+    pub system: String,
+    /// Whether every required validation check passes
+    pub passed: bool,
+    /// Generation and validation warnings
+    pub warnings: Vec<String>,
+}
+
+impl HookSummary {
+    /// Summary of `scheme` written to `output` after generation.
+    pub fn post_generate(
+        output: &Path,
+        scheme: &Base16Scheme,
+        passed: bool,
+        warnings: Vec<String>,
+    ) -> Self {
+        Self {
+            event: "post_generate",
+            output: output.to_path_buf(),
+            name: scheme.name.clone(),
+            slug: scheme.slug.clone(),
+            variant: scheme.variant.to_string(),
+            system: scheme.system.to_string(),
+            passed,
+            warnings,
+        }
+    }
+}
+
+/// Run `command` through the shell for the event `summary` describes, and
+/// wait for it.
+///
+/// The command inherits stdout and stderr. A non-zero exit status is an
+/// error.
+pub fn run_hook(command: &str, summary: &HookSummary) -> Result<()> {
+    let json = serde_json::to_string(summary).wrap_err("Failed to serialize hook summary")?;
+    let mut child = shell(command)
+        .env("THEMALINGADINGDONG_EVENT", summary.event)
+        .env("THEMALINGADINGDONG_OUTPUT", &summary.output)
+        .env("THEMALINGADINGDONG_SUMMARY", &json)
+        .stdin(Stdio::piped())
+        .spawn()
+        .wrap_err_with(|| format!("Failed to run {} hook `{command}`", summary.event))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores its stdin may exit before reading it
+        let _ = stdin.write_all(json.as_bytes());
+    }
+    let status = child
+        .wait()
+        .wrap_err_with(|| format!("Failed to wait for {} hook", summary.event))?;
+    if !status.success() {
+        bail!("{} hook `{command}` failed ({status})", summary.event);
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
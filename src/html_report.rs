@@ -0,0 +1,269 @@
+//! Standalone HTML accessibility report (`--report html`).
+//!
+//! One self-contained file with no external assets, for attaching to a
+//! design review: palette swatches, the validation checks with their APCA
+//! and WCAG 2.x results, the full contrast matrix, the palette under each
+//! simulated color vision deficiency, and the provenance of the parameters
+//! that produced the scheme.
+
+use std::fmt::Write;
+
+use color_eyre::eyre::{Result, WrapErr};
+use tinted_builder::Base16Scheme;
+
+use crate::contrast_matrix::{ContrastMatrix, SLOTS};
+use crate::cvd::{CvdKind, check_cvd, simulate_scheme};
+use crate::provenance::Provenance;
+use crate::semantic_pairs::SemanticPairResult;
+use crate::validation::{ValidationResult, ValidationResults};
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; background: #fafafa; }
+h1 { margin-bottom: 0.2rem; }
+h2 { margin-top: 2.5rem; border-bottom: 1px solid #ccc; }
+.meta { color: #666; }
+.swatches { display: flex; flex-wrap: wrap; gap: 0.5rem; }
+.swatch { width: 6.5rem; border: 1px solid #ccc; border-radius: 4px; overflow: hidden; background: #fff; }
+.swatch .chip { height: 3.5rem; }
+.swatch .label { font: 0.75rem monospace; padding: 0.25rem; }
+table { border-collapse: collapse; font-size: 0.85rem; }
+th, td { border: 1px solid #ddd; padding: 0.2rem 0.45rem; text-align: right; }
+th { background: #eee; }
+td.slot, th.slot { text-align: left; font-family: monospace; }
+td.sample { text-align: left; }
+.pass { color: #11692d; }
+.fail { color: #b00020; font-weight: bold; }
+td.below { background: #fbd5da; font-weight: bold; }
+pre { background: #fff; border: 1px solid #ddd; padding: 0.75rem; overflow-x: auto; }
+";
+
+/// Render the HTML report for `scheme`.
+///
+/// `validation` is shown as is, CVD conflicts are flagged below
+/// `cvd_threshold` ΔE, and `provenance`, when given, is embedded as JSON.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::cvd::DEFAULT_CVD_THRESHOLD;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::html_report::render_html;
+/// use themalingadingdong::validation::validate;
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let html = render_html(&scheme, &validate(&scheme), DEFAULT_CVD_THRESHOLD, None).unwrap();
+///
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// assert!(html.contains("Contrast matrix"));
+/// assert!(html.contains("deuteranopia"));
+/// ```
+pub fn render_html(
+    scheme: &Base16Scheme,
+    validation: &ValidationResults,
+    cvd_threshold: f32,
+    provenance: Option<&Provenance>,
+) -> Result<String> {
+    let mut out = String::new();
+    let title = format!("{} accessibility report", escape(&scheme.name));
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    let _ = write!(
+        out,
+        "<p class=\"meta\">{} &middot; {}",
+        scheme.system, scheme.variant
+    );
+    if !scheme.author.is_empty() {
+        let _ = write!(out, " &middot; by {}", escape(&scheme.author));
+    }
+    let _ = writeln!(
+        out,
+        " &middot; themalingadingdong {}</p>",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    out.push_str("<h2>Palette</h2>\n");
+    out.push_str(&swatches(scheme));
+
+    let _ = writeln!(
+        out,
+        "<h2>Required checks</h2>\n<p>{}/{} pass under the {} standard.</p>",
+        validation.required_passed(),
+        validation.required_count(),
+        validation.standard
+    );
+    out.push_str(&checks_table(scheme, &validation.required));
+    if !validation.semantic.is_empty() {
+        out.push_str(&semantic_table(&validation.semantic));
+    }
+    out.push_str("<h2>Reference checks</h2>\n<p>Informational; never fail the scheme.</p>\n");
+    out.push_str(&checks_table(scheme, &validation.reference));
+
+    out.push_str(&matrix_table(scheme));
+    out.push_str(&cvd_section(scheme, cvd_threshold));
+
+    if let Some(provenance) = provenance {
+        let json = serde_json::to_string_pretty(provenance)
+            .wrap_err("Failed to serialize provenance to JSON")?;
+        let _ = writeln!(
+            out,
+            "<h2>Provenance</h2>\n<p>Parameters and lookup tables that produced \
+             this scheme.</p>\n<pre id=\"provenance\">{}</pre>",
+            escape(&json)
+        );
+    }
+
+    out.push_str("</body>\n</html>\n");
+    Ok(out)
+}
+
+/// Escape text for HTML element content and attribute values.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `#rrggbb` of `slot` in `scheme`, if present.
+fn hex(scheme: &Base16Scheme, slot: &str) -> Option<String> {
+    let (r, g, b) = scheme.palette.get(slot)?.rgb;
+    Some(format!("#{r:02x}{g:02x}{b:02x}"))
+}
+
+fn swatches(scheme: &Base16Scheme) -> String {
+    let mut out = String::from("<div class=\"swatches\">\n");
+    for slot in SLOTS {
+        let Some(hex) = hex(scheme, slot) else {
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "<div class=\"swatch\"><div class=\"chip\" style=\"background: {hex}\"></div>\
+             <div class=\"label\">{slot}<br>{hex}</div></div>"
+        );
+    }
+    out.push_str("</div>\n");
+    out
+}
+
+fn checks_table(scheme: &Base16Scheme, results: &[ValidationResult]) -> String {
+    let mut out = String::from(
+        "<table>\n<tr><th class=\"slot\">Foreground</th><th class=\"slot\">Background</th>\
+         <th>Sample</th><th>APCA Lc</th><th>Min Lc</th><th>WCAG</th><th>Level</th>\
+         <th class=\"slot\">Usage</th><th>Result</th></tr>\n",
+    );
+    for r in results {
+        let fg = hex(scheme, r.pair.foreground).unwrap_or_default();
+        let bg = hex(scheme, r.pair.background).unwrap_or_default();
+        let (class, verdict) = if r.passes {
+            ("pass", "pass")
+        } else {
+            ("fail", "FAIL")
+        };
+        let level = r
+            .wcag_level
+            .map_or_else(|| "-".to_string(), |l| l.to_string());
+        let _ = writeln!(
+            out,
+            "<tr><td class=\"slot\">{}</td><td class=\"slot\">{}</td>\
+             <td class=\"sample\" style=\"color: {fg}; background: {bg}\">Sample text</td>\
+             <td>{:.1}</td><td>{:.0}</td><td>{:.2}:1</td><td>{level}</td>\
+             <td class=\"slot\">{}</td><td class=\"{class}\">{verdict}</td></tr>",
+            r.pair.foreground,
+            r.pair.background,
+            r.contrast.abs(),
+            r.pair.threshold.min_lc,
+            r.wcag_ratio,
+            r.usage
+        );
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+fn semantic_table(results: &[SemanticPairResult]) -> String {
+    let mut out = String::from(
+        "<h3>Semantic pairs</h3>\n<p>Each pair's worst case under normal vision and \
+         simulated color vision deficiency.</p>\n<table>\n<tr><th class=\"slot\">Pair</th>\
+         <th class=\"slot\">Vision</th><th>Hue Δ</th><th>Lightness Δ</th><th>Required</th>\
+         <th>Result</th></tr>\n",
+    );
+    for r in results {
+        let (class, verdict) = if r.passes {
+            ("pass", "pass")
+        } else {
+            ("fail", "FAIL")
+        };
+        let vision = r.vision.map_or("normal", |kind| kind.name());
+        let hue = r
+            .separation
+            .hue
+            .map_or_else(|| "-".to_string(), |h| format!("{h:.0}°"));
+        let _ = writeln!(
+            out,
+            "<tr><td class=\"slot\">{} / {}</td><td class=\"slot\">{vision}</td>\
+             <td>{hue}</td><td>{:.1}</td><td>{:.0}° or {:.1}</td>\
+             <td class=\"{class}\">{verdict}</td></tr>",
+            r.first, r.second, r.separation.lightness, r.min_hue, r.min_lightness
+        );
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+fn matrix_table(scheme: &Base16Scheme) -> String {
+    let matrix = ContrastMatrix::new(scheme);
+    let mut out = String::from(
+        "<h2>Contrast matrix</h2>\n<p>APCA |Lc| of each foreground (row) on each \
+         background (column). Highlighted cells are validated pairs below their \
+         threshold.</p>\n<table>\n<tr><th class=\"slot\">fg \\ bg</th>",
+    );
+    for bg in &matrix.slots {
+        let _ = write!(out, "<th>{}</th>", &bg[4..]);
+    }
+    out.push_str("</tr>\n");
+    for &fg in &matrix.slots {
+        let _ = write!(out, "<tr><th class=\"slot\">{fg}</th>");
+        for &bg in &matrix.slots {
+            let lc = matrix.get(fg, bg).unwrap_or_default().abs();
+            if matrix.is_below_threshold(fg, bg) {
+                let _ = write!(out, "<td class=\"below\">{lc:.0}</td>");
+            } else {
+                let _ = write!(out, "<td>{lc:.0}</td>");
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+fn cvd_section(scheme: &Base16Scheme, threshold: f32) -> String {
+    let mut out = String::from("<h2>Color vision deficiency simulation</h2>\n");
+    let conflicts = check_cvd(scheme, threshold);
+    for kind in CvdKind::ALL {
+        let _ = writeln!(out, "<h3>{kind}</h3>");
+        out.push_str(&swatches(&simulate_scheme(scheme, kind)));
+        let collapsed: Vec<_> = conflicts.iter().filter(|c| c.kind == kind).collect();
+        if collapsed.is_empty() {
+            let _ = writeln!(
+                out,
+                "<p class=\"pass\">No accent pairs closer than ΔE {threshold:.1}.</p>"
+            );
+        } else {
+            out.push_str("<ul>\n");
+            for c in collapsed {
+                let _ = writeln!(
+                    out,
+                    "<li class=\"fail\">{} vs {}: ΔE {:.1} (normal {:.1})</li>",
+                    c.first, c.second, c.delta_e, c.normal_delta_e
+                );
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+    out
+}
@@ -0,0 +1,181 @@
+//! Linked accent hues (`[colors.hue_links]`).
+//!
+//! A link holds one accent's hue at a fixed offset from another's:
+//! `base0F = "base08 + 30"` keeps magenta 30° past red, and
+//! `base0C = "base08 + 180"` makes cyan red's complement. Links may chain
+//! (base0E following base0F, which follows base08) but not loop. A linked
+//! hue replaces the slot's own override; a source without one starts from
+//! its default hue.
+//!
+//! The TUI keeps links while hues are edited: moving a linked hue moves its
+//! source by the same amount, and every hue linked to that source follows.
+
+use std::collections::BTreeMap;
+
+use crate::config::ConfigError;
+use crate::contrast_matrix::slot_name;
+use crate::interpolation::DEFAULT_BASE16_HUES;
+
+/// One accent's hue held relative to another's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HueLink {
+    /// Accent the hue follows (0 = base08)
+    pub source: usize,
+    /// Degrees added to the source's hue
+    pub offset: f32,
+}
+
+/// Links for base08-base0F, indexed like the hue overrides.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HueLinks {
+    pub links: [Option<HueLink>; 8],
+}
+
+/// Accent index of a base08-base0F slot name, in either case.
+pub fn accent_index(slot: &str) -> Option<usize> {
+    let digit = slot.trim().to_ascii_lowercase();
+    let digit = digit.strip_prefix("base0")?;
+    let index = usize::from_str_radix(digit, 16).ok()?;
+    (digit.len() == 1 && index >= 8).then(|| index - 8)
+}
+
+/// Parse a link: `"base08 + 30"`, `"base08 - 45"`, or a bare `"base08"`.
+pub fn parse_link(expr: &str) -> Result<HueLink, String> {
+    let invalid = |reason: &str| format!("{reason} (got '{expr}', expected e.g. \"base08 + 30\")");
+    let (slot, offset) = match expr.find(['+', '-']) {
+        Some(at) => {
+            let magnitude: f32 = expr[at + 1..]
+                .trim()
+                .trim_end_matches('°')
+                .trim_end()
+                .parse()
+                .ok()
+                .filter(|m: &f32| m.is_finite())
+                .ok_or_else(|| invalid("the offset is not a number of degrees"))?;
+            let sign = if expr[at..].starts_with('-') {
+                -1.0
+            } else {
+                1.0
+            };
+            (&expr[..at], sign * magnitude)
+        }
+        None => (expr, 0.0),
+    };
+    let source = accent_index(slot).ok_or_else(|| invalid("the source is not base08-base0F"))?;
+    Ok(HueLink { source, offset })
+}
+
+impl HueLinks {
+    /// Parse `[colors.hue_links]`, rejecting unknown slots, links to
+    /// themselves, and loops.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use themalingadingdong::hue_links::HueLinks;
+    ///
+    /// let map = BTreeMap::from([("base0F".to_string(), "base08 + 30".to_string())]);
+    /// let links = HueLinks::parse(&map).unwrap();
+    /// let hues = links.resolve([Some(20.0), None, None, None, None, None, None, None]);
+    /// assert_eq!(hues[7], Some(50.0));
+    ///
+    /// let looped = BTreeMap::from([
+    ///     ("base08".to_string(), "base0F - 30".to_string()),
+    ///     ("base0F".to_string(), "base08 + 30".to_string()),
+    /// ]);
+    /// assert!(HueLinks::parse(&looped).is_err());
+    /// ```
+    pub fn parse(map: &BTreeMap<String, String>) -> Result<Self, ConfigError> {
+        let mut links = [None; 8];
+        for (target, expr) in map {
+            let index = accent_index(target).ok_or_else(|| {
+                ConfigError::InvalidValue(format!(
+                    "colors.hue_links: '{target}' is not an accent (expected base08-base0F)"
+                ))
+            })?;
+            let link = parse_link(expr).map_err(|e| {
+                ConfigError::InvalidValue(format!("colors.hue_links.{target}: {e}"))
+            })?;
+            if link.source == index {
+                return Err(ConfigError::InvalidValue(format!(
+                    "colors.hue_links.{target} links to itself"
+                )));
+            }
+            links[index] = Some(link);
+        }
+        let links = Self { links };
+        for start in 0..8 {
+            let mut index = start;
+            for _ in 0..8 {
+                match links.links[index] {
+                    Some(link) if link.source == start => {
+                        return Err(ConfigError::InvalidValue(format!(
+                            "colors.hue_links: {} is linked back to itself through {}",
+                            slot_name(8 + start),
+                            slot_name(8 + index)
+                        )));
+                    }
+                    Some(link) => index = link.source,
+                    None => break,
+                }
+            }
+        }
+        Ok(links)
+    }
+
+    /// The links as `[colors.hue_links]` entries.
+    pub fn to_map(&self) -> BTreeMap<String, String> {
+        self.links
+            .iter()
+            .enumerate()
+            .filter_map(|(index, link)| {
+                let link = link.as_ref()?;
+                let sign = if link.offset < 0.0 { '-' } else { '+' };
+                Some((
+                    slot_name(8 + index).to_string(),
+                    format!(
+                        "{} {sign} {}",
+                        slot_name(8 + link.source),
+                        link.offset.abs()
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.iter().all(Option::is_none)
+    }
+
+    /// Hue of accent `index`: its source's hue plus the offset, or its own
+    /// override or default hue when unlinked.
+    fn hue(&self, overrides: &[Option<f32>; 8], index: usize) -> f32 {
+        match self.links[index] {
+            Some(link) => self.hue(overrides, link.source) + link.offset,
+            None => overrides[index].unwrap_or(DEFAULT_BASE16_HUES[index]),
+        }
+    }
+
+    /// Hue overrides with every linked hue set from its source.
+    pub fn resolve(&self, overrides: [Option<f32>; 8]) -> [Option<f32>; 8] {
+        std::array::from_fn(|index| match self.links[index] {
+            Some(_) => Some(self.hue(&overrides, index).rem_euclid(360.0)),
+            None => overrides[index],
+        })
+    }
+
+    /// Resolve `overrides` after accent `edited` changed, moving the root
+    /// of its links so the edited hue keeps its new value.
+    pub fn propagate(&self, overrides: &mut [Option<f32>; 8], edited: usize) {
+        if let Some(hue) = overrides[edited] {
+            let (mut root, mut root_hue) = (edited, hue);
+            while let Some(link) = self.links[root] {
+                root_hue -= link.offset;
+                root = link.source;
+            }
+            overrides[root] = Some(root_hue.rem_euclid(360.0));
+        }
+        *overrides = self.resolve(*overrides);
+    }
+}
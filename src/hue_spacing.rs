@@ -0,0 +1,163 @@
+//! Perceptual hue spacing for the accent colors.
+//!
+//! The default accent hues are spaced by ANSI convention, not by how far
+//! apart the resulting colors look: equal steps in degrees give unequal
+//! color differences around the wheel. This module nudges each hue within a
+//! bounded distance of its anchor (the default or overridden hue) to
+//! maximize the smallest pairwise HellwigJmh ΔE, so red stays red but no two
+//! accents crowd each other.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+use crate::gamut_map::{Gamut, gamut_map_to};
+use crate::hellwig::HellwigJmh;
+
+/// Step between candidate hues tried for each accent (degrees).
+const STEP: f32 = 1.0;
+
+/// Coordinate-descent passes before giving up on convergence.
+const MAX_PASSES: usize = 20;
+
+/// Settings for perceptual hue spacing.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HueSpacing {
+    /// Largest distance (degrees) a hue may move from its anchor
+    pub max_shift: f32,
+}
+
+impl Default for HueSpacing {
+    fn default() -> Self {
+        Self { max_shift: 15.0 }
+    }
+}
+
+impl HueSpacing {
+    /// Check that the shift keeps hues near their ANSI meaning.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(0.0..=45.0).contains(&self.max_shift) {
+            return Err(ConfigError::InvalidValue(format!(
+                "colors.hue_spacing.max_shift must be between 0 and 45 degrees (got {})",
+                self.max_shift
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Accent color at hue `h` for the given target J'/M, mapped into `gamut`.
+fn sample(hue: f32, j: f32, m: f32, gamut: Gamut) -> HellwigJmh {
+    gamut_map_to(HellwigJmh::new(j, m, hue.rem_euclid(360.0)), gamut)
+}
+
+/// All 28 pairwise ΔE values of `colors`, smallest first.
+fn sorted_delta_e(colors: &[HellwigJmh; 8]) -> Vec<f32> {
+    let mut distances = Vec::with_capacity(28);
+    for i in 0..8 {
+        for j in i + 1..8 {
+            distances.push(colors[i].delta_e(&colors[j]));
+        }
+    }
+    distances.sort_by(f32::total_cmp);
+    distances
+}
+
+/// Whether `a` spaces colors better than `b`: a larger smallest ΔE, then
+/// (when equal) a larger second smallest, and so on.
+fn spaced_better(a: &[f32], b: &[f32]) -> bool {
+    for (x, y) in a.iter().zip(b) {
+        if (x - y).abs() > 1e-3 {
+            return x > y;
+        }
+    }
+    false
+}
+
+/// Smallest pairwise ΔE of the accents at `hues`, sampled at J'/M in `gamut`.
+///
+/// Accents are later solved for contrast, so this approximates the final
+/// palette by sampling every hue at the optimizer's target J' and M.
+pub fn min_pairwise_delta_e(hues: &[f32; 8], j: f32, m: f32, gamut: Gamut) -> f32 {
+    sorted_delta_e(&hues.map(|h| sample(h, j, m, gamut)))[0]
+}
+
+/// Move each hue up to `spacing.max_shift` degrees from its anchor to
+/// maximize the smallest pairwise ΔE, sampled at J'/M in `gamut`.
+///
+/// Uses coordinate descent: each hue in turn takes the position (in 1°
+/// steps) that most improves the weakest pair, then the next weakest, until
+/// a pass changes nothing. Ties keep the hue closest to its anchor.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::gamut_map::Gamut;
+/// use themalingadingdong::hue_spacing::{HueSpacing, min_pairwise_delta_e, optimize_hues};
+/// use themalingadingdong::interpolation::DEFAULT_BASE16_HUES;
+///
+/// let spacing = HueSpacing { max_shift: 15.0 };
+/// let hues = optimize_hues(&DEFAULT_BASE16_HUES, 60.0, 30.0, Gamut::Srgb, &spacing);
+///
+/// for (hue, anchor) in hues.iter().zip(DEFAULT_BASE16_HUES) {
+///     assert!((hue - anchor).abs() <= 15.0);
+/// }
+/// assert!(
+///     min_pairwise_delta_e(&hues, 60.0, 30.0, Gamut::Srgb)
+///         >= min_pairwise_delta_e(&DEFAULT_BASE16_HUES, 60.0, 30.0, Gamut::Srgb)
+/// );
+/// ```
+pub fn optimize_hues(
+    anchors: &[f32; 8],
+    j: f32,
+    m: f32,
+    gamut: Gamut,
+    spacing: &HueSpacing,
+) -> [f32; 8] {
+    optimize_hues_fixed(anchors, &[false; 8], j, m, gamut, spacing)
+}
+
+/// `optimize_hues`, leaving the hues marked in `fixed` at their anchors
+/// (e.g. pinned accents) and spacing the others around them.
+pub fn optimize_hues_fixed(
+    anchors: &[f32; 8],
+    fixed: &[bool; 8],
+    j: f32,
+    m: f32,
+    gamut: Gamut,
+    spacing: &HueSpacing,
+) -> [f32; 8] {
+    let mut hues = *anchors;
+    let mut colors = hues.map(|h| sample(h, j, m, gamut));
+    let steps = (spacing.max_shift / STEP).floor() as i32;
+    if steps == 0 {
+        return hues;
+    }
+
+    for _ in 0..MAX_PASSES {
+        let mut changed = false;
+        for i in (0..8).filter(|&i| !fixed[i]) {
+            let mut best = (sorted_delta_e(&colors), hues[i], colors[i]);
+            // Try offsets nearest the anchor first so ties favor small moves
+            for k in (0..=steps).flat_map(|k| [k, -k]).skip(1) {
+                let hue = anchors[i] + k as f32 * STEP;
+                let mut trial = colors;
+                trial[i] = sample(hue, j, m, gamut);
+                let score = sorted_delta_e(&trial);
+                if spaced_better(&score, &best.0) {
+                    best = (score, hue, trial[i]);
+                }
+            }
+            if best.1 != hues[i] {
+                hues[i] = best.1;
+                colors[i] = best.2;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    hues.map(|h| h.rem_euclid(360.0))
+}
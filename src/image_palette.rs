@@ -0,0 +1,529 @@
+//! Theme colors picked from an image (`--from-image`).
+//!
+//! The image's pixels are clustered with k-means in Hellwig space (J' and
+//! the a/b plane of M and hue), and the clusters seed the generator:
+//!
+//! - the background is the darkest cluster covering at least
+//!   [`MIN_SHARE`] of the image (the lightest for a light theme), toned down
+//!   to a tinted surface;
+//! - the foreground is a near-neutral at the other end of the lightness
+//!   range, carrying the same tint;
+//! - each colorful cluster replaces the default accent hue nearest to it,
+//!   when within [`MAX_HUE_SHIFT`] degrees, so red stays red.
+//!
+//! The generator then solves the accents as usual, so the theme matches the
+//! wallpaper and still meets its contrast floors.
+//!
+//! PNG and binary PPM files are read directly; other formats are converted
+//! with ImageMagick when it is on `PATH`.
+
+use std::path::Path;
+use std::process::Command;
+
+use color_eyre::eyre::{Result, WrapErr, bail, eyre};
+use palette::Srgb;
+use tinted_builder::SchemeVariant;
+
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::DEFAULT_BASE16_HUES;
+
+/// Clusters the pixels are grouped into.
+pub const DEFAULT_CLUSTERS: usize = 8;
+
+/// Share of the image a cluster needs to become the background.
+pub const MIN_SHARE: f32 = 0.05;
+
+/// Furthest a default accent hue moves to a cluster's hue, in degrees.
+pub const MAX_HUE_SHIFT: f32 = 20.0;
+
+/// Colorfulness (M) a cluster needs to move an accent hue.
+const MIN_ACCENT_M: f32 = 12.0;
+
+/// Pixels sampled for clustering.
+const MAX_SAMPLES: usize = 8192;
+
+const KMEANS_ITERATIONS: usize = 24;
+
+/// Background lightness (J') caps for dark and light themes.
+const DARK_BACKGROUND_J: f32 = 18.0;
+const LIGHT_BACKGROUND_J: f32 = 94.0;
+
+/// Foreground lightness (J') for dark and light themes.
+const DARK_FOREGROUND_J: f32 = 90.0;
+const LIGHT_FOREGROUND_J: f32 = 20.0;
+
+/// Colorfulness caps of the tinted background and foreground.
+const BACKGROUND_M: f32 = 10.0;
+const FOREGROUND_M: f32 = 4.0;
+
+/// Opaque pixels of a decoded image.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major; fully or mostly transparent pixels are left out
+    pub pixels: Vec<Srgb<u8>>,
+}
+
+/// Read the image at `path`.
+pub fn load_image(path: &Path) -> Result<Image> {
+    let bytes =
+        std::fs::read(path).wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    let decoded = if bytes.starts_with(PNG_SIGNATURE) {
+        decode_png(&bytes)
+    } else if bytes.starts_with(b"P6") {
+        decode_ppm(&bytes)
+    } else {
+        Err(eyre!("not a PNG or PPM file"))
+    };
+    // Anything else (and PNG variants not decoded here) goes through ImageMagick
+    decoded.or_else(|_| convert_with_magick(path))
+}
+
+fn convert_with_magick(path: &Path) -> Result<Image> {
+    let output = Command::new("magick")
+        .arg(path)
+        .args(["-depth", "8", "ppm:-"])
+        .output()
+        .map_err(|_| {
+            eyre!(
+                "{} is not a PNG or PPM file; other formats need ImageMagick on PATH",
+                path.display()
+            )
+        })?;
+    if !output.status.success() {
+        bail!(
+            "magick failed to read {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    decode_ppm(&output.stdout)
+}
+
+/// Decode a binary PPM (P6) with 8-bit samples.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::image_palette::decode_ppm;
+///
+/// let mut ppm = b"P6\n# two pixels\n2 1\n255\n".to_vec();
+/// ppm.extend([255, 0, 0, 0, 0, 255]);
+/// let image = decode_ppm(&ppm).unwrap();
+/// assert_eq!((image.width, image.height), (2, 1));
+/// assert_eq!(image.pixels[1], palette::Srgb::new(0, 0, 255));
+/// ```
+pub fn decode_ppm(bytes: &[u8]) -> Result<Image> {
+    let mut pos = 0;
+    let mut fields = [0u32; 4];
+    for field in &mut fields {
+        // Whitespace and comments separate the header fields
+        loop {
+            match bytes.get(pos) {
+                Some(b'#') => {
+                    while bytes.get(pos).is_some_and(|&b| b != b'\n') {
+                        pos += 1;
+                    }
+                }
+                Some(b) if b.is_ascii_whitespace() => pos += 1,
+                _ => break,
+            }
+        }
+        let start = pos;
+        while bytes.get(pos).is_some_and(|b| !b.is_ascii_whitespace()) {
+            pos += 1;
+        }
+        let token = std::str::from_utf8(&bytes[start..pos]).unwrap_or("");
+        *field = if start == 0 {
+            (token == "P6")
+                .then_some(0)
+                .ok_or_else(|| eyre!("not a binary PPM"))?
+        } else {
+            token
+                .parse()
+                .map_err(|_| eyre!("invalid PPM header field '{token}'"))?
+        };
+    }
+    let [_, width, height, max] = fields;
+    if max != 255 {
+        bail!("only 8-bit PPM files are supported (maximum {max})");
+    }
+    // A single whitespace byte ends the header
+    let data = bytes.get(pos + 1..).unwrap_or_default();
+    let count = width as usize * height as usize;
+    if count.checked_mul(3).is_none_or(|len| data.len() < len) {
+        bail!("PPM data is truncated");
+    }
+    let pixels = data[..count * 3]
+        .chunks_exact(3)
+        .map(|p| Srgb::new(p[0], p[1], p[2]))
+        .collect();
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Decode a non-interlaced PNG of any color type and bit depth.
+///
+/// Pixels below half opacity are dropped; palette transparency is ignored.
+pub fn decode_png(bytes: &[u8]) -> Result<Image> {
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        bail!("not a PNG file");
+    }
+    let mut pos = PNG_SIGNATURE.len();
+    let mut header = None;
+    let mut palette = Vec::new();
+    let mut compressed = Vec::new();
+    while pos + 8 <= bytes.len() {
+        let len = u32::from_be_bytes(bytes[pos..pos + 4].try_into()?) as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        let data = pos
+            .checked_add(8 + len)
+            .and_then(|end| bytes.get(pos + 8..end))
+            .ok_or_else(|| eyre!("PNG chunk is truncated"))?;
+        match kind {
+            b"IHDR" if len >= 13 => header = Some(data),
+            b"PLTE" => {
+                palette = data
+                    .chunks_exact(3)
+                    .map(|c| Srgb::new(c[0], c[1], c[2]))
+                    .collect()
+            }
+            b"IDAT" => compressed.extend_from_slice(data),
+            b"IEND" => break,
+            _ => {}
+        }
+        // Length, type, data, and CRC
+        pos += 12 + len;
+    }
+    let header = header.ok_or_else(|| eyre!("PNG has no IHDR chunk"))?;
+    let width = u32::from_be_bytes(header[0..4].try_into()?);
+    let height = u32::from_be_bytes(header[4..8].try_into()?);
+    let (depth, color_type, interlace) = (header[8], header[9], header[12]);
+    if interlace != 0 {
+        bail!("interlaced PNGs are not supported");
+    }
+    let (channels, depths): (usize, &[u8]) = match color_type {
+        0 => (1, &[1, 2, 4, 8, 16]),
+        3 => (1, &[1, 2, 4, 8]),
+        2 => (3, &[8, 16]),
+        4 => (2, &[8, 16]),
+        6 => (4, &[8, 16]),
+        _ => bail!("unknown PNG color type {color_type}"),
+    };
+    if !depths.contains(&depth) {
+        bail!("invalid PNG bit depth {depth} for color type {color_type}");
+    }
+    if color_type == 3 && palette.is_empty() {
+        bail!("palette PNG has no PLTE chunk");
+    }
+
+    let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&compressed)
+        .map_err(|e| eyre!("PNG data is corrupt: {e:?}"))?;
+    let bits_per_pixel = channels * usize::from(depth);
+    let too_large = || eyre!("PNG dimensions {width}x{height} are too large");
+    let row_len = (width as usize)
+        .checked_mul(bits_per_pixel)
+        .ok_or_else(too_large)?
+        .div_ceil(8);
+    let stride = bits_per_pixel.div_ceil(8);
+    let data_len = (row_len + 1)
+        .checked_mul(height as usize)
+        .ok_or_else(too_large)?;
+    if raw.len() < data_len {
+        bail!("PNG data is truncated");
+    }
+
+    // Every pixel takes at least a bit of the data checked above
+    let mut pixels = Vec::with_capacity(width as usize * height as usize);
+    let mut previous = vec![0u8; row_len];
+    let mut row = vec![0u8; row_len];
+    for filtered in raw.chunks_exact(row_len + 1).take(height as usize) {
+        unfilter(filtered[0], &filtered[1..], &previous, stride, &mut row)?;
+        // Sample `i` of the row, scaled to 8 bits (palette indices as is)
+        let sample = |i: usize| -> u8 {
+            match depth {
+                16 => row[i * 2],
+                8 => row[i],
+                _ => {
+                    let bit = i * usize::from(depth);
+                    let shift = 8 - usize::from(depth) - bit % 8;
+                    let value = (row[bit / 8] >> shift) & ((1 << depth) - 1);
+                    if color_type == 3 {
+                        value
+                    } else {
+                        (u32::from(value) * 255 / ((1 << depth) - 1)) as u8
+                    }
+                }
+            }
+        };
+        for x in 0..width as usize {
+            let s = |c: usize| sample(x * channels + c);
+            let (color, alpha) = match color_type {
+                0 => (Srgb::new(s(0), s(0), s(0)), 255),
+                2 => (Srgb::new(s(0), s(1), s(2)), 255),
+                3 => (
+                    *palette
+                        .get(usize::from(s(0)))
+                        .ok_or_else(|| eyre!("PNG palette index out of range"))?,
+                    255,
+                ),
+                4 => (Srgb::new(s(0), s(0), s(0)), s(1)),
+                _ => (Srgb::new(s(0), s(1), s(2)), s(3)),
+            };
+            if alpha >= 128 {
+                pixels.push(color);
+            }
+        }
+        std::mem::swap(&mut previous, &mut row);
+    }
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Undo a PNG row filter into `out`.
+fn unfilter(filter: u8, line: &[u8], previous: &[u8], stride: usize, out: &mut [u8]) -> Result<()> {
+    for i in 0..line.len() {
+        let left = if i >= stride { out[i - stride] } else { 0 };
+        let up = previous[i];
+        let up_left = if i >= stride { previous[i - stride] } else { 0 };
+        let predicted = match filter {
+            0 => 0,
+            1 => left,
+            2 => up,
+            3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+            4 => paeth(left, up, up_left),
+            _ => bail!("unknown PNG filter {filter}"),
+        };
+        out[i] = line[i].wrapping_add(predicted);
+    }
+    Ok(())
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+    let (pa, pb, pc) = (
+        (p - i16::from(a)).abs(),
+        (p - i16::from(b)).abs(),
+        (p - i16::from(c)).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// A group of similar pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cluster {
+    /// Mean color of the group
+    pub jmh: HellwigJmh,
+    pub color: Srgb<u8>,
+    /// Fraction of the sampled pixels in the group
+    pub share: f32,
+}
+
+/// Cartesian Hellwig coordinates: J', then M along the hue's cosine and sine.
+fn to_point(jmh: HellwigJmh) -> [f32; 3] {
+    let h = jmh.hue.to_radians();
+    [
+        jmh.lightness,
+        jmh.colorfulness * h.cos(),
+        jmh.colorfulness * h.sin(),
+    ]
+}
+
+fn from_point(p: [f32; 3]) -> HellwigJmh {
+    HellwigJmh::new(
+        p[0],
+        p[1].hypot(p[2]),
+        p[2].atan2(p[1]).to_degrees().rem_euclid(360.0),
+    )
+}
+
+fn distance2(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+/// Group `pixels` into at most `k` clusters, largest first.
+///
+/// Deterministic: centers start from the lightness median and then the
+/// pixel farthest from every center so far.
+pub fn dominant_colors(pixels: &[Srgb<u8>], k: usize) -> Vec<Cluster> {
+    let step = pixels.len().div_ceil(MAX_SAMPLES).max(1);
+    let mut points: Vec<[f32; 3]> = pixels
+        .iter()
+        .step_by(step)
+        .map(|&p| to_point(HellwigJmh::from_srgb_u8(p)))
+        .collect();
+    if points.is_empty() || k == 0 {
+        return Vec::new();
+    }
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+
+    let mut centers = vec![points[points.len() / 2]];
+    while centers.len() < k {
+        let farthest = points
+            .iter()
+            .copied()
+            .max_by(|a, b| {
+                let near = |p: [f32; 3]| {
+                    centers
+                        .iter()
+                        .map(|&c| distance2(p, c))
+                        .fold(f32::INFINITY, f32::min)
+                };
+                near(*a).total_cmp(&near(*b))
+            })
+            .unwrap_or(points[0]);
+        if centers.contains(&farthest) {
+            break;
+        }
+        centers.push(farthest);
+    }
+
+    let mut assignment = vec![0usize; points.len()];
+    for _ in 0..KMEANS_ITERATIONS {
+        for (slot, &p) in assignment.iter_mut().zip(&points) {
+            *slot = (0..centers.len())
+                .min_by(|&a, &b| distance2(p, centers[a]).total_cmp(&distance2(p, centers[b])))
+                .unwrap_or(0);
+        }
+        let mut sums = vec![([0.0f32; 3], 0usize); centers.len()];
+        for (&slot, p) in assignment.iter().zip(&points) {
+            for (sum, value) in sums[slot].0.iter_mut().zip(p) {
+                *sum += value;
+            }
+            sums[slot].1 += 1;
+        }
+        let mut moved = false;
+        for (center, (sum, count)) in centers.iter_mut().zip(&sums) {
+            if *count > 0 {
+                let mean = sum.map(|s| s / *count as f32);
+                moved |= distance2(mean, *center) > 1e-4;
+                *center = mean;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    let mut counts = vec![0usize; centers.len()];
+    for &slot in &assignment {
+        counts[slot] += 1;
+    }
+    let mut clusters: Vec<Cluster> = centers
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(&center, count)| {
+            let jmh = from_point(center);
+            Cluster {
+                jmh,
+                color: jmh.into_srgb_u8_gamut_mapped(),
+                share: count as f32 / points.len() as f32,
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| b.share.total_cmp(&a.share));
+    clusters
+}
+
+/// Generator inputs picked from an image's clusters.
+#[derive(Debug, Clone)]
+pub struct ImageTheme {
+    pub variant: SchemeVariant,
+    pub background: Srgb<u8>,
+    pub foreground: Srgb<u8>,
+    /// Hues of base08-base0F moved to the image's colors (`None` keeps the
+    /// default)
+    pub hues: [Option<f32>; 8],
+}
+
+/// Pick a background, foreground, and accent hues from `clusters`, for
+/// `variant` (by the image's average lightness when `None`).
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::image_palette::{dominant_colors, image_theme};
+/// use tinted_builder::SchemeVariant;
+///
+/// // A dark blue wallpaper with an orange sunset
+/// let mut pixels = vec![Srgb::new(20u8, 30, 60); 800];
+/// pixels.extend(vec![Srgb::new(240u8, 130, 40); 200]);
+/// let theme = image_theme(&dominant_colors(&pixels, 4), None);
+/// assert!(matches!(theme.variant, SchemeVariant::Dark));
+/// assert!(theme.hues.iter().any(Option::is_some));
+/// ```
+pub fn image_theme(clusters: &[Cluster], variant: Option<SchemeVariant>) -> ImageTheme {
+    let mean_j: f32 = clusters.iter().map(|c| c.jmh.lightness * c.share).sum();
+    let variant = variant.unwrap_or(if mean_j < 50.0 {
+        SchemeVariant::Dark
+    } else {
+        SchemeVariant::Light
+    });
+    let dark = !matches!(variant, SchemeVariant::Light);
+
+    // Small clusters can't be the background unless nothing else is
+    let candidates: Vec<&Cluster> = match clusters.iter().filter(|c| c.share >= MIN_SHARE).count() {
+        0 => clusters.iter().collect(),
+        _ => clusters.iter().filter(|c| c.share >= MIN_SHARE).collect(),
+    };
+    let by_lightness = |a: &&Cluster, b: &&Cluster| a.jmh.lightness.total_cmp(&b.jmh.lightness);
+    let base = if dark {
+        candidates.into_iter().min_by(by_lightness)
+    } else {
+        candidates.into_iter().max_by(by_lightness)
+    };
+    let (tint_m, tint_hue) = base.map_or((0.0, 0.0), |c| (c.jmh.colorfulness, c.jmh.hue));
+    let base_j = base.map_or(if dark { 0.0 } else { 100.0 }, |c| c.jmh.lightness);
+
+    let background_j = if dark {
+        base_j.min(DARK_BACKGROUND_J)
+    } else {
+        base_j.max(LIGHT_BACKGROUND_J)
+    };
+    let background = HellwigJmh::new(background_j, tint_m.min(BACKGROUND_M), tint_hue)
+        .into_srgb_u8_gamut_mapped();
+    let foreground_j = if dark {
+        DARK_FOREGROUND_J
+    } else {
+        LIGHT_FOREGROUND_J
+    };
+    let foreground = HellwigJmh::new(foreground_j, tint_m.min(FOREGROUND_M), tint_hue)
+        .into_srgb_u8_gamut_mapped();
+
+    let hue_distance = |a: f32, b: f32| {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    };
+    let hues = DEFAULT_BASE16_HUES.map(|default| {
+        clusters
+            .iter()
+            .filter(|c| c.jmh.colorfulness >= MIN_ACCENT_M)
+            .map(|c| c.jmh.hue)
+            .filter(|&hue| hue_distance(hue, default) <= MAX_HUE_SHIFT)
+            .min_by(|&a, &b| hue_distance(a, default).total_cmp(&hue_distance(b, default)))
+    });
+
+    ImageTheme {
+        variant,
+        background,
+        foreground,
+        hues,
+    }
+}
@@ -1,20 +1,38 @@
 //! Import Base16/Base24 scheme files.
 //!
-//! Supports both the modern tinted-theming format and legacy Base16 format.
+//! Supports both the modern tinted-theming format and legacy Base16 format,
+//! plus terminal/editor themes (Alacritty, Kitty, Xresources, VS Code) which
+//! are mapped onto Base24 slots heuristically.
 
-use std::collections::HashMap;
-use std::path::Path;
+mod terminal;
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use color_eyre::eyre::{Result, WrapErr, bail};
+use figment::value::Dict;
 use palette::Srgb;
 use serde::Deserialize;
 use tinted_builder::{Base16Scheme, SchemeSystem, SchemeVariant};
 
 use crate::config::{
-    AccentOptSettings, ColorConfig, ContrastConfig, HueOverrides, ThemeConfig, ThemeMetadata,
+    AccentOptSettings, ColorConfig, ContrastConfig, HueOverrides, PreviewConfig, ThemeConfig,
+    ThemeMetadata,
 };
-use crate::curves::InterpolationConfig;
-use crate::hellwig::HellwigJmh;
+use crate::curves::{CurveConfig, CurveType, InterpolationConfig, invert};
+use crate::explore::ExploreConfig;
+use crate::extended_accents::ExtendedAccents;
+use crate::gamut_map::Gamut;
+use crate::hellwig::{HellwigJmh, hellwig_lightness};
+use crate::hooks::HooksConfig;
+use crate::keys::KeysConfig;
+use crate::metadata::slugify;
+use crate::migrate::CONFIG_VERSION;
+use crate::ramp::LightnessRamp;
+use crate::validation::ValidationConfig;
+use crate::viewing::ViewingConditions;
+
+pub use terminal::TerminalFormat;
 
 /// Legacy Base16 scheme format (colors at top level).
 #[derive(Debug, Deserialize)]
@@ -73,13 +91,7 @@ impl LegacyScheme {
         palette.insert("base0E".to_string(), Color::new(self.base0_e)?);
         palette.insert("base0F".to_string(), Color::new(self.base0_f)?);
 
-        let slug: String = self
-            .scheme
-            .to_lowercase()
-            .replace(' ', "-")
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == '-')
-            .collect();
+        let slug = slugify(&self.scheme);
 
         Ok(Base16Scheme {
             system: SchemeSystem::Base16,
@@ -99,29 +111,167 @@ pub struct ImportResult {
     pub config: ThemeConfig,
     /// Original parsed scheme for validation
     pub scheme: Base16Scheme,
+    /// Text of the file, for re-exporting it with its comments
+    /// (see [`patch_scheme_yaml`](crate::yaml_patch::patch_scheme_yaml))
+    pub source: String,
+    /// Colors a terminal theme was missing, and what replaced them
+    pub warnings: Vec<String>,
 }
 
 /// Import a scheme file and convert to ThemeConfig.
 ///
-/// Supports both modern tinted-theming format and legacy Base16 format.
-/// Extracts:
+/// Supports modern tinted-theming format, legacy Base16 format, and
+/// terminal/editor themes (see [`TerminalFormat`]). Extracts:
 /// - base00 as background
 /// - base07 as foreground
 /// - Hues from base08-base0F accent colors
 ///
 /// Returns both the ThemeConfig (for editing) and the original scheme (for validation).
 pub fn import_scheme(path: &Path) -> Result<ImportResult> {
+    let source = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    let (scheme, warnings) = parse_scheme(&source, path)?;
+    let config = scheme_to_config(&scheme)?;
+
+    Ok(ImportResult {
+        config,
+        scheme,
+        source,
+        warnings,
+    })
+}
+
+/// Read a scheme file in any supported format without recovering its
+/// generation parameters.
+pub fn read_scheme(path: &Path) -> Result<Base16Scheme> {
     let content = std::fs::read_to_string(path)
         .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    // Missing terminal colors are logged as they're filled in
+    Ok(parse_scheme(&content, path)?.0)
+}
 
-    let scheme = parse_scheme(&content, path)?;
-    let config = scheme_to_config(&scheme)?;
+/// Scheme files (`.yaml`, `.yml`, `.json`) under `dir`, sorted by path.
+///
+/// Subdirectories are searched too, so a tinted-theming `schemes` checkout
+/// (with its `base16/` and `base24/` folders) can be listed directly. Hidden
+/// entries such as `.git` are skipped.
+pub fn scheme_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| matches!(e.to_lowercase().as_str(), "yaml" | "yml" | "json"))
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Find the scheme an `extends` key names.
+///
+/// `extends` is a path, relative to `config_dir`, or a slug: the stem of a
+/// scheme file directly in `config_dir` or anywhere under `gallery_dir`.
+pub fn resolve_extends(
+    extends: &str,
+    config_dir: &Path,
+    gallery_dir: Option<&Path>,
+) -> Result<PathBuf> {
+    let config_dir = if config_dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        config_dir
+    };
+    let path = config_dir.join(extends);
+    if path.is_file() {
+        return Ok(path);
+    }
+    let is_path = extends.contains(['/', '\\']) || Path::new(extends).extension().is_some();
+    if is_path {
+        bail!("extends: {} not found", path.display());
+    }
+
+    let is_slug = |file: &PathBuf| file.file_stem().is_some_and(|stem| stem == extends);
+    let found = scheme_files_in(config_dir)
+        .into_iter()
+        .find(is_slug)
+        .or_else(|| gallery_dir.and_then(|dir| scheme_files(dir).into_iter().find(is_slug)));
+    match found {
+        Some(path) => Ok(path),
+        None => bail!(
+            "extends: no scheme named '{extends}' in {}{}",
+            config_dir.display(),
+            if gallery_dir.is_some() {
+                " or the gallery directory"
+            } else {
+                ""
+            }
+        ),
+    }
+}
+
+/// Config imported from the scheme an `extends` key names (see
+/// [`resolve_extends`]), as the base the rest of a config overrides.
+pub fn extends_config(
+    extends: &str,
+    config_dir: &Path,
+    gallery_dir: Option<&Path>,
+) -> Result<ThemeConfig> {
+    let path = resolve_extends(extends, config_dir, gallery_dir)?;
+    Ok(import_scheme(&path)
+        .wrap_err_with(|| format!("extends: failed to import {}", path.display()))?
+        .config)
+}
 
-    Ok(ImportResult { config, scheme })
+/// Scheme files directly in `dir`, without descending into subdirectories.
+fn scheme_files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| matches!(e.to_lowercase().as_str(), "yaml" | "yml" | "json"))
+        })
+        .collect();
+    files.sort();
+    files
 }
 
-/// Parse scheme content, trying modern format first, then legacy.
-fn parse_scheme(content: &str, path: &Path) -> Result<Base16Scheme> {
+/// Parse scheme content, trying terminal formats, then modern, then legacy.
+///
+/// Also returns the warnings from filling in a terminal theme's missing colors.
+fn parse_scheme(content: &str, path: &Path) -> Result<(Base16Scheme, Vec<String>)> {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if let Some(format) = terminal::detect_format(file_name, content) {
+        let colors = terminal::parse(format, content)
+            .wrap_err_with(|| format!("Failed to parse {:?} theme", format))?;
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Imported");
+        return Ok(terminal::to_scheme(stem, &colors));
+    }
+
     let is_json = path
         .extension()
         .and_then(|e| e.to_str())
@@ -135,7 +285,7 @@ fn parse_scheme(content: &str, path: &Path) -> Result<Base16Scheme> {
     };
 
     if let Ok(scheme) = modern_result {
-        return Ok(scheme);
+        return Ok((scheme, Vec::new()));
     }
 
     // Fall back to legacy Base16 format
@@ -146,9 +296,12 @@ fn parse_scheme(content: &str, path: &Path) -> Result<Base16Scheme> {
     };
 
     match legacy_result {
-        Ok(legacy) => legacy
-            .into_base16_scheme()
-            .wrap_err("Failed to convert legacy scheme"),
+        Ok(legacy) => Ok((
+            legacy
+                .into_base16_scheme()
+                .wrap_err("Failed to convert legacy scheme")?,
+            Vec::new(),
+        )),
         Err(e) => bail!(
             "Failed to parse scheme (tried modern and legacy formats): {}",
             e
@@ -180,6 +333,8 @@ fn scheme_to_config(scheme: &Base16Scheme) -> Result<ThemeConfig> {
     let variant_str = format!("{:?}", scheme.variant).to_lowercase();
 
     Ok(ThemeConfig {
+        config_version: CONFIG_VERSION,
+        extends: None,
         theme: ThemeMetadata {
             name: scheme.name.clone(),
             author: if scheme.author.is_empty() {
@@ -187,6 +342,7 @@ fn scheme_to_config(scheme: &Base16Scheme) -> Result<ThemeConfig> {
             } else {
                 Some(scheme.author.clone())
             },
+            description: None,
             variant: Some(variant_str),
         },
         colors: ColorConfig {
@@ -199,18 +355,93 @@ fn scheme_to_config(scheme: &Base16Scheme) -> Result<ThemeConfig> {
                 foreground.red, foreground.green, foreground.blue
             )),
             hue_overrides: Some(HueOverrides::from_array(hues)),
+            foreground_headroom: None,
+            hue_spacing: None,
+            gamut: Gamut::Srgb,
+            pins: None,
+            harmony: None,
+            hue_links: None,
+            adaptive_hues: None,
+        },
+        curves: InterpolationConfig {
+            lightness: lightness_curve_for(scheme),
+            ..InterpolationConfig::default()
         },
-        curves: InterpolationConfig::default(),
         contrast: ContrastConfig::default(),
+        validation: ValidationConfig::default(),
         optimization: AccentOptSettings::default(),
         extended_optimization: AccentOptSettings {
             target_j: 70.0,
             target_m: 35.0,
             ..AccentOptSettings::default()
         },
+        extended_accents: ExtendedAccents::default(),
+        ramp: LightnessRamp::default(),
+        preview: PreviewConfig::default(),
+        roles: None,
+        daemon: None,
+        viewing: ViewingConditions::default(),
+        ansi: None,
+        surfaces: None,
+        displays: BTreeMap::new(),
+        hooks: HooksConfig::default(),
+        keys: KeysConfig::default(),
+        explore: ExploreConfig::default(),
+        unexpanded: Dict::new(),
     })
 }
 
+/// Lightness curve under which the UI ramp (base00-base07) sits closest to
+/// evenly spaced steps.
+///
+/// Each step's normalized lightness is inverted through a candidate curve to
+/// recover the position it was sampled at; the curve whose recovered
+/// positions deviate least from `i / 7` wins. Falls back to the default
+/// curve when the ramp is incomplete or flat.
+fn lightness_curve_for(scheme: &Base16Scheme) -> CurveConfig {
+    let default = InterpolationConfig::default().lightness;
+
+    let Ok(ramp) = (0..8)
+        .map(|i| get_color(scheme, &format!("base0{i}")).map(hellwig_lightness))
+        .collect::<Result<Vec<f32>>>()
+    else {
+        return default;
+    };
+    let span = ramp[7] - ramp[0];
+    if span.abs() < 1.0 {
+        return default;
+    }
+
+    let cost = |curve: &CurveConfig| -> f32 {
+        ramp.iter()
+            .enumerate()
+            .map(|(i, j)| {
+                let t = invert(curve, (j - ramp[0]) / span);
+                (t - i as f32 / 7.0).powi(2)
+            })
+            .sum()
+    };
+
+    // Default first so it wins ties
+    let mut best = (cost(&default), default.clone());
+    for curve_type in [
+        CurveType::Linear,
+        CurveType::Smootherstep,
+        CurveType::SmoothStart,
+        CurveType::SmoothEnd,
+    ] {
+        let candidate = CurveConfig {
+            curve_type,
+            ..default.clone()
+        };
+        let c = cost(&candidate);
+        if c < best.0 {
+            best = (c, candidate);
+        }
+    }
+    best.1
+}
+
 /// Extract an sRGB color from the scheme palette.
 fn get_color(scheme: &Base16Scheme, name: &str) -> Result<Srgb<u8>> {
     // Try both uppercase and lowercase variants for base0A-base0F
@@ -0,0 +1,396 @@
+//! Terminal and editor color config import.
+//!
+//! Parses Alacritty TOML, Kitty conf, Xresources, and VS Code JSON themes into
+//! a common set of terminal colors, then maps them onto Base24 slots:
+//!
+//! - base00: background, base05: foreground
+//! - base01-base04, base06: interpolated from background toward bright white
+//! - base03: bright black (ANSI 8) when present
+//! - base07: bright white (ANSI 15), falling back to foreground
+//! - base08/0A/0B/0C/0D/0E: ANSI red/yellow/green/cyan/blue/magenta
+//! - base09: hue midway between red and yellow; base0F: darkened red
+//! - base10-base17: the same from the bright ANSI colors, in the base08-base0F
+//!   slot order the extended accents follow
+//!
+//! A missing color falls back to its normal or bright counterpart, then to
+//! the foreground, with a warning.
+
+use std::collections::HashMap;
+
+use color_eyre::eyre::{Result, bail, eyre};
+use palette::Srgb;
+use tinted_builder::{Base16Scheme, Color, SchemeSystem, SchemeVariant};
+use tracing::warn;
+
+use crate::contrast_matrix::SLOTS;
+use crate::curves::InterpolationConfig;
+use crate::generate::parse_color;
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::{interpolate_with_curves, srgb_to_f32, srgb_to_hex, srgb_to_u8};
+use crate::metadata::slugify;
+
+/// ANSI color names in index order (0-7), as used by Alacritty and VS Code.
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// UI ramp slot names (base00-base07).
+const UI_SLOTS: [&str; 8] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07",
+];
+
+/// Supported terminal/editor config formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalFormat {
+    Alacritty,
+    Kitty,
+    Xresources,
+    VsCode,
+}
+
+/// Colors extracted from a terminal or editor config.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalColors {
+    /// Theme name, when the format carries one (VS Code)
+    pub name: Option<String>,
+    pub background: Option<Srgb<u8>>,
+    pub foreground: Option<Srgb<u8>>,
+    /// ANSI colors 0-15 (normal 0-7, bright 8-15)
+    pub ansi: [Option<Srgb<u8>>; 16],
+}
+
+/// Guess the terminal format from file name and content.
+///
+/// Returns `None` for files that look like Base16/Base24 schemes.
+pub fn detect_format(file_name: &str, content: &str) -> Option<TerminalFormat> {
+    let lower = file_name.to_ascii_lowercase();
+    let ext = lower.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+
+    if matches!(ext, "yaml" | "yml") {
+        return None;
+    }
+    if ext == "toml" {
+        return Some(TerminalFormat::Alacritty);
+    }
+    if ext == "conf" || lower.starts_with("kitty") {
+        return Some(TerminalFormat::Kitty);
+    }
+    if matches!(ext, "xresources" | "xdefaults" | "ad") || lower.starts_with(".x") {
+        return Some(TerminalFormat::Xresources);
+    }
+    if ext == "json" {
+        let has_vscode_keys = content.contains("\"editor.background\"")
+            || content.contains("\"terminal.ansiBlack\"")
+            || content.contains("\"terminal.background\"");
+        return has_vscode_keys.then_some(TerminalFormat::VsCode);
+    }
+
+    // Unknown extension: sniff line syntax
+    let mut lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('!'));
+    lines.find_map(|line| {
+        let key = line.split([' ', '\t', ':']).next().unwrap_or("");
+        if line.contains(':') && (key.contains('*') || key.contains('.')) {
+            Some(TerminalFormat::Xresources)
+        } else if key == "background" || key == "foreground" || is_color_key(key) {
+            Some(TerminalFormat::Kitty)
+        } else {
+            None
+        }
+    })
+}
+
+/// Parse a terminal config in the given format.
+pub fn parse(format: TerminalFormat, content: &str) -> Result<TerminalColors> {
+    let colors = match format {
+        TerminalFormat::Alacritty => parse_alacritty(content)?,
+        TerminalFormat::Kitty => parse_kitty(content),
+        TerminalFormat::Xresources => parse_xresources(content),
+        TerminalFormat::VsCode => parse_vscode(content)?,
+    };
+
+    if colors.background.is_none() && colors.ansi[0].is_none() {
+        bail!("No background color found in {:?} config", format);
+    }
+    if colors.foreground.is_none() && colors.ansi[7].is_none() && colors.ansi[15].is_none() {
+        bail!("No foreground color found in {:?} config", format);
+    }
+
+    Ok(colors)
+}
+
+/// Parse a color value, accepting Alacritty's `0xRRGGBB` form.
+fn color_value(value: &str) -> Option<Srgb<u8>> {
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    let normalized = match value.strip_prefix("0x") {
+        Some(hex) => format!("#{hex}"),
+        None => value.to_string(),
+    };
+    parse_color(&normalized).ok()
+}
+
+/// Whether a key looks like `color0`..`color15`.
+fn is_color_key(key: &str) -> bool {
+    color_index(key).is_some()
+}
+
+/// Parse `colorN` into an ANSI index (0-15).
+fn color_index(key: &str) -> Option<usize> {
+    key.strip_prefix("color")
+        .and_then(|n| n.parse::<usize>().ok())
+        .filter(|&n| n < 16)
+}
+
+fn parse_alacritty(content: &str) -> Result<TerminalColors> {
+    let value: toml::Table =
+        toml::from_str(content).map_err(|e| eyre!("Invalid Alacritty TOML: {}", e))?;
+    let colors = value
+        .get("colors")
+        .and_then(|c| c.as_table())
+        .ok_or_else(|| eyre!("Alacritty config has no [colors] section"))?;
+
+    let lookup = |section: &str, key: &str| {
+        colors
+            .get(section)
+            .and_then(|s| s.get(key))
+            .and_then(|v| v.as_str())
+            .and_then(color_value)
+    };
+
+    let mut result = TerminalColors {
+        background: lookup("primary", "background"),
+        foreground: lookup("primary", "foreground"),
+        ..Default::default()
+    };
+    for (i, name) in ANSI_NAMES.iter().enumerate() {
+        result.ansi[i] = lookup("normal", name);
+        result.ansi[i + 8] = lookup("bright", name);
+    }
+    Ok(result)
+}
+
+fn parse_kitty(content: &str) -> TerminalColors {
+    let mut result = TerminalColors::default();
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        assign(&mut result, key, value);
+    }
+    result
+}
+
+fn parse_xresources(content: &str) -> TerminalColors {
+    let mut defines: HashMap<&str, &str> = HashMap::new();
+    let mut result = TerminalColors::default();
+
+    for line in content.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("#define") {
+            let mut parts = rest.split_whitespace();
+            if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+                defines.insert(name, value);
+            }
+            continue;
+        }
+        if line.starts_with('!') || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // `*.color0`, `URxvt*background`, `*foreground` -> last resource component
+        let key = key.trim().rsplit(['.', '*']).next().unwrap_or("");
+        let value = value.trim();
+        let value = defines.get(value).copied().unwrap_or(value);
+        assign(&mut result, key, value);
+    }
+    result
+}
+
+/// Assign a `background`/`foreground`/`colorN` key to the result.
+fn assign(result: &mut TerminalColors, key: &str, value: &str) {
+    let Some(color) = color_value(value) else {
+        return;
+    };
+    match key {
+        "background" => result.background = Some(color),
+        "foreground" => result.foreground = Some(color),
+        _ => {
+            if let Some(i) = color_index(key) {
+                result.ansi[i] = Some(color);
+            }
+        }
+    }
+}
+
+fn parse_vscode(content: &str) -> Result<TerminalColors> {
+    // VS Code themes are JSONC; drop whole-line comments before parsing
+    let stripped: String = content
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let value: serde_json::Value =
+        serde_json::from_str(&stripped).map_err(|e| eyre!("Invalid VS Code theme JSON: {}", e))?;
+    let colors = value
+        .get("colors")
+        .and_then(|c| c.as_object())
+        .ok_or_else(|| eyre!("VS Code theme has no \"colors\" object"))?;
+
+    let lookup = |key: &str| {
+        colors
+            .get(key)
+            .and_then(|v| v.as_str())
+            .and_then(color_value)
+    };
+
+    let mut result = TerminalColors {
+        name: value
+            .get("name")
+            .and_then(|n| n.as_str())
+            .map(str::to_string),
+        background: lookup("terminal.background").or_else(|| lookup("editor.background")),
+        foreground: lookup("terminal.foreground").or_else(|| lookup("editor.foreground")),
+        ..Default::default()
+    };
+    for (i, name) in ANSI_NAMES.iter().enumerate() {
+        let mut title = name.to_string();
+        title[..1].make_ascii_uppercase();
+        result.ansi[i] = lookup(&format!("terminal.ansi{title}"));
+        result.ansi[i + 8] = lookup(&format!("terminal.ansiBright{title}"));
+    }
+    Ok(result)
+}
+
+/// Map terminal colors onto a Base24 scheme.
+///
+/// `fallback_name` is used when the config does not carry its own name.
+/// Also returns a warning for each accent color missing from the config.
+pub fn to_scheme(fallback_name: &str, colors: &TerminalColors) -> (Base16Scheme, Vec<String>) {
+    let name = colors.name.as_deref().unwrap_or(fallback_name);
+    let background = colors
+        .background
+        .or(colors.ansi[0])
+        .expect("background checked in parse");
+    let foreground = colors
+        .foreground
+        .or(colors.ansi[7])
+        .or(colors.ansi[15])
+        .expect("foreground checked in parse");
+    let bright_white = colors.ansi[15].unwrap_or(foreground);
+
+    // Normal and bright variants fall back to each other, then to the foreground
+    let mut warnings = Vec::new();
+    let mut ansi = [foreground; 16];
+    for i in (1..7).chain(9..15) {
+        let counterpart = (i + 8) % 16;
+        ansi[i] = match (colors.ansi[i], colors.ansi[counterpart]) {
+            (Some(color), _) => color,
+            (None, Some(color)) => {
+                warnings.push(format!(
+                    "{} is missing; using {}",
+                    ansi_name(i),
+                    ansi_name(counterpart)
+                ));
+                color
+            }
+            (None, None) => {
+                warnings.push(format!("{} is missing; using the foreground", ansi_name(i)));
+                foreground
+            }
+        };
+    }
+    for warning in &warnings {
+        warn!(theme = name, "{warning}");
+    }
+
+    let ramp = interpolate_with_curves(
+        srgb_to_f32(background),
+        srgb_to_f32(bright_white),
+        8,
+        &InterpolationConfig::default(),
+    );
+
+    let mut slots: Vec<(&str, Srgb<u8>)> =
+        (0..8).map(|i| (UI_SLOTS[i], srgb_to_u8(ramp[i]))).collect();
+    slots[0].1 = background;
+    slots[5].1 = foreground;
+    slots[7].1 = bright_white;
+    if let Some(comment) = colors.ansi[8] {
+        slots[3].1 = comment;
+    }
+
+    // base08-base0F from the normal colors, base10-base17 from the bright
+    // ones
+    for offset in [0, 8] {
+        let red = HellwigJmh::from_srgb_u8(ansi[offset + 1]);
+        let yellow = HellwigJmh::from_srgb_u8(ansi[offset + 3]);
+        let accents = [
+            ansi[offset + 1],
+            blend(red, yellow),
+            ansi[offset + 3],
+            ansi[offset + 2],
+            ansi[offset + 6],
+            ansi[offset + 4],
+            ansi[offset + 5],
+            HellwigJmh::new(red.lightness * 0.7, red.colorfulness * 0.7, red.hue)
+                .into_srgb_u8_gamut_mapped(),
+        ];
+        slots.extend(SLOTS[8 + offset..].iter().copied().zip(accents));
+    }
+
+    let palette = slots
+        .into_iter()
+        .map(|(slot, color)| {
+            let hex = srgb_to_hex(color);
+            (slot.to_string(), Color::new(hex).expect("valid hex"))
+        })
+        .collect();
+
+    let variant = if HellwigJmh::from_srgb_u8(background).lightness
+        < HellwigJmh::from_srgb_u8(foreground).lightness
+    {
+        SchemeVariant::Dark
+    } else {
+        SchemeVariant::Light
+    };
+
+    let scheme = Base16Scheme {
+        system: SchemeSystem::Base24,
+        name: name.to_string(),
+        slug: slugify(name),
+        author: String::new(),
+        description: None,
+        variant,
+        palette,
+    };
+    (scheme, warnings)
+}
+
+/// Name of ANSI color `index` (0-15) for messages, e.g. "color 9 (bright red)".
+fn ansi_name(index: usize) -> String {
+    let bright = if index >= 8 { "bright " } else { "" };
+    format!("color {index} ({bright}{})", ANSI_NAMES[index % 8])
+}
+
+/// Blend two colors halfway in HellwigJmh, taking the shorter hue path.
+fn blend(a: HellwigJmh, b: HellwigJmh) -> Srgb<u8> {
+    let mut diff = b.hue - a.hue;
+    if diff > 180.0 {
+        diff -= 360.0;
+    } else if diff < -180.0 {
+        diff += 360.0;
+    }
+    HellwigJmh::new(
+        (a.lightness + b.lightness) / 2.0,
+        (a.colorfulness + b.colorfulness) / 2.0,
+        (a.hue + diff / 2.0).rem_euclid(360.0),
+    )
+    .into_srgb_u8_gamut_mapped()
+}
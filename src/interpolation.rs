@@ -5,10 +5,13 @@ use palette::Srgb;
 #[cfg(debug_assertions)]
 use tracing::instrument;
 
-use crate::accent_solver::optimize_accents;
-use crate::config::AccentOptSettings;
+use crate::accent_solver::optimize_accents_with;
+use crate::apca::apca_contrast;
+use crate::config::{AccentOptSettings, CommentContrast};
+use crate::contrast::{Apca, ContrastModel};
 use crate::curves::{InterpolationConfig, evaluate_curve};
-use crate::hellwig::HellwigJmh;
+use crate::gamut_map::Gamut;
+use crate::hellwig::{HellwigJmh, batch_into_srgb};
 
 /// Default hues for base16 accent colors (base08-base0F).
 ///
@@ -61,11 +64,94 @@ pub fn build_hues_with_overrides(overrides: &[Option<f32>; 8]) -> [f32; 8] {
     hues
 }
 
+/// Background colorfulness (M) below which its hue is too faint to matter.
+const NEUTRAL_BACKGROUND_M: f32 = 5.0;
+
+/// Colorfulness (M) over which an avoidance window grows to full width.
+const WINDOW_RAMP_M: f32 = 10.0;
+
+/// Background colorfulness (M) above which its complement is also avoided.
+const VIVID_BACKGROUND_M: f32 = 20.0;
+
+/// Degrees kept clear around the background hue.
+const BACKGROUND_HUE_WINDOW: f32 = 20.0;
+
+/// Degrees kept clear around the background's complement.
+const COMPLEMENT_HUE_WINDOW: f32 = 15.0;
+
+/// Push accent hues out of the background's hue, and its complement when
+/// the background is vivid.
+///
+/// An accent sharing a tinted background's hue blends into it, and one
+/// opposite a vivid background vibrates against it. Hues inside the window
+/// around either are moved to the edge with more room to the neighbouring
+/// accents, so they don't crowd another accent; the window widens with
+/// background colorfulness, so near-neutral backgrounds leave the hues
+/// untouched.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::hellwig::HellwigJmh;
+/// use themalingadingdong::interpolation::{DEFAULT_BASE16_HUES, avoid_background_hue};
+///
+/// // A purple-tinted background pushes purple (285°) away from blue (250°)
+/// let background = HellwigJmh::new(11.0, 16.0, 286.0);
+/// let hues = avoid_background_hue(&DEFAULT_BASE16_HUES, &background);
+/// assert!((hues[6] - 306.0).abs() < 0.01);
+/// assert_eq!(hues[0], DEFAULT_BASE16_HUES[0]);
+///
+/// // A neutral one changes nothing
+/// let gray = HellwigJmh::new(11.0, 2.0, 286.0);
+/// assert_eq!(avoid_background_hue(&DEFAULT_BASE16_HUES, &gray), DEFAULT_BASE16_HUES);
+/// ```
+pub fn avoid_background_hue(hues: &[f32; 8], background: &HellwigJmh) -> [f32; 8] {
+    let ramp = |from: f32| ((background.colorfulness - from) / WINDOW_RAMP_M).clamp(0.0, 1.0);
+    let windows = [
+        (
+            background.hue,
+            BACKGROUND_HUE_WINDOW * ramp(NEUTRAL_BACKGROUND_M),
+        ),
+        (
+            background.hue + 180.0,
+            COMPLEMENT_HUE_WINDOW * ramp(VIVID_BACKGROUND_M),
+        ),
+    ];
+    // Signed distance from `b` to `a`, in [-180, 180)
+    let offset = |a: f32, b: f32| (a - b + 180.0).rem_euclid(360.0) - 180.0;
+
+    let mut shifted = *hues;
+    for i in 0..shifted.len() {
+        for &(center, window) in &windows {
+            let from_center = offset(shifted[i], center);
+            if window <= 0.0 || from_center.abs() >= window {
+                continue;
+            }
+            // Distance to the closest other accent
+            let room = |hue: f32| {
+                shifted
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &other)| offset(hue, other).abs())
+                    .fold(f32::INFINITY, f32::min)
+            };
+            let near = center + window.copysign(from_center);
+            let far = center - window.copysign(from_center);
+            let edge = if room(far) > room(near) { far } else { near };
+            shifted[i] = edge.rem_euclid(360.0);
+        }
+    }
+    shifted
+}
+
 /// Result of generating an accent color with contrast optimization.
 #[derive(Debug, Clone)]
 pub struct AccentResult {
     /// The generated sRGB color
     pub color: Srgb<f32>,
+    /// The generated color in the target gamut (equals `color` for sRGB)
+    pub jmh: HellwigJmh,
     /// The hue used (degrees, 0-360)
     pub hue: f32,
     /// The input lightness for this hue (HellwigJmh J', 0-100)
@@ -131,7 +217,7 @@ pub fn interpolate_with_curves(
     let start_hellwig = HellwigJmh::from_srgb_u8(srgb_to_u8(start));
     let end_hellwig = HellwigJmh::from_srgb_u8(srgb_to_u8(end));
 
-    (0..steps)
+    let jmh: Vec<HellwigJmh> = (0..steps)
         .map(|i| {
             let linear_t = i as f32 / (steps - 1) as f32;
 
@@ -143,18 +229,22 @@ pub fn interpolate_with_curves(
             let m = lerp(start_hellwig.colorfulness, end_hellwig.colorfulness, t_m);
             let h = lerp_hue(start_hellwig.hue, end_hellwig.hue, t_h);
 
-            HellwigJmh::new(j, m, h).into_srgb()
+            HellwigJmh::new(j, m, h)
         })
-        .collect()
+        .collect();
+
+    let mut colors = vec![Srgb::new(0.0, 0.0, 0.0); steps];
+    batch_into_srgb(&jmh, &mut colors);
+    colors
 }
 
 /// Linear interpolation helper.
-fn lerp(a: f32, b: f32, t: f32) -> f32 {
+pub(crate) fn lerp(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t
 }
 
 /// Hue interpolation with shortest-path handling.
-fn lerp_hue(a: f32, b: f32, t: f32) -> f32 {
+pub(crate) fn lerp_hue(a: f32, b: f32, t: f32) -> f32 {
     let diff = b - a;
     let adjusted_diff = if diff > 180.0 {
         diff - 360.0
@@ -202,7 +292,23 @@ pub fn generate_accents_uniform(
     min_contrast: f64,
     background: Srgb<u8>,
 ) -> Vec<AccentResult> {
-    let result = optimize_accents(background, hues, settings, min_contrast);
+    generate_accents_in(hues, settings, min_contrast, background, Gamut::Srgb, &Apca)
+}
+
+/// Generate accent colors with COBYLA optimization against a target gamut.
+///
+/// Like `generate_accents_uniform`, but colorfulness may use all of `gamut`
+/// and contrast is measured with `model`. `AccentResult::color` holds the
+/// sRGB fallback.
+pub fn generate_accents_in(
+    hues: &[f32],
+    settings: &AccentOptSettings,
+    min_contrast: f64,
+    background: Srgb<u8>,
+    gamut: Gamut,
+    model: &dyn ContrastModel,
+) -> Vec<AccentResult> {
+    let result = optimize_accents_with(background, hues, settings, min_contrast, gamut, model);
 
     result
         .hue_results
@@ -210,6 +316,7 @@ pub fn generate_accents_uniform(
         .map(|hr| {
             AccentResult {
                 color: hr.color,
+                jmh: hr.jmh,
                 hue: hr.hue,
                 lightness: hr.j,
                 post_clamp_lightness: hr.post_clamp_j,
@@ -224,6 +331,118 @@ pub fn generate_accents_uniform(
         .collect()
 }
 
+/// Solve a comment color (base03) into a target APCA contrast range.
+///
+/// Keeps the colorfulness and hue of `color` and searches lightness between
+/// its ramp neighbors `below` and `above` (base02 and base04) for a contrast
+/// inside `range`, so the surfaces stay in order. Colors that already fall
+/// inside the range are returned unchanged; otherwise the nearest range
+/// bound is targeted, stopping at the neighbors when it lies beyond them.
+///
+/// Returns the tuned color and its achieved contrast (absolute Lc).
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::config::CommentContrast;
+/// use themalingadingdong::interpolation::tune_comment_contrast;
+///
+/// let bg = Srgb::new(0.1f32, 0.1, 0.12);
+/// let fg = Srgb::new(0.9f32, 0.9, 0.88);
+/// let range = CommentContrast { min: 40.0, max: 55.0 };
+/// let (_, lc) = tune_comment_contrast(Srgb::new(0.2, 0.2, 0.22), bg, bg, fg, &range);
+/// assert!(lc >= 39.5 && lc <= 55.5);
+///
+/// // A range past the neighbors stops at them
+/// let above = Srgb::new(0.5f32, 0.5, 0.5);
+/// let (_, capped) = tune_comment_contrast(Srgb::new(0.2, 0.2, 0.22), bg, bg, above, &range);
+/// assert!(capped < 40.0);
+/// ```
+pub fn tune_comment_contrast(
+    color: Srgb<f32>,
+    background: Srgb<f32>,
+    below: Srgb<f32>,
+    above: Srgb<f32>,
+    range: &CommentContrast,
+) -> (Srgb<f32>, f64) {
+    let bg_u8 = srgb_to_u8(background);
+    let contrast_of = |c: Srgb<f32>| apca_contrast(srgb_to_u8(c), bg_u8).abs();
+
+    let current = contrast_of(color);
+    if current >= range.min && current <= range.max {
+        return (color, current);
+    }
+
+    let target = if current < range.min {
+        range.min
+    } else {
+        range.max
+    };
+    let lightness = |c: Srgb<f32>| HellwigJmh::from_srgb_u8(srgb_to_u8(c)).lightness;
+    tune_contrast_within(
+        color,
+        background,
+        (lightness(below), lightness(above)),
+        target,
+    )
+}
+
+/// Move `color` along J' (keeping colorfulness and hue) to `target` contrast
+/// against `background`.
+///
+/// Searches lightness between the background and foreground, so targets
+/// beyond the foreground's contrast stop at it. Returns the tuned color and
+/// its achieved contrast (absolute Lc).
+pub fn tune_contrast(
+    color: Srgb<f32>,
+    background: Srgb<f32>,
+    foreground: Srgb<f32>,
+    target: f64,
+) -> (Srgb<f32>, f64) {
+    let lightness = |c: Srgb<f32>| HellwigJmh::from_srgb_u8(srgb_to_u8(c)).lightness;
+    tune_contrast_within(
+        color,
+        background,
+        (lightness(background), lightness(foreground)),
+        target,
+    )
+}
+
+/// [`tune_contrast`] searching J' from `near` (closest to the background)
+/// to `far`.
+fn tune_contrast_within(
+    color: Srgb<f32>,
+    background: Srgb<f32>,
+    (mut near, mut far): (f32, f32),
+    target: f64,
+) -> (Srgb<f32>, f64) {
+    let bg_u8 = srgb_to_u8(background);
+    let contrast_of = |c: Srgb<f32>| apca_contrast(srgb_to_u8(c), bg_u8).abs();
+
+    let jmh = HellwigJmh::from_srgb_u8(srgb_to_u8(color));
+    let at = |j: f32| HellwigJmh::new(j, jmh.colorfulness, jmh.hue).into_srgb_gamut_mapped();
+
+    // Contrast grows monotonically as J' moves from the background toward the foreground
+    for _ in 0..32 {
+        let mid = (near + far) / 2.0;
+        if contrast_of(at(mid)) < target {
+            near = mid;
+        } else {
+            far = mid;
+        }
+    }
+
+    // Pick whichever bracket endpoint rounds closest to the target after u8 quantization
+    let (near_color, far_color) = (at(near), at(far));
+    let (near_lc, far_lc) = (contrast_of(near_color), contrast_of(far_color));
+    if (near_lc - target).abs() <= (far_lc - target).abs() {
+        (near_color, near_lc)
+    } else {
+        (far_color, far_lc)
+    }
+}
+
 /// Clamp an sRGB color to valid range [0, 1] for each channel.
 ///
 /// HellwigJmh colors can produce out-of-gamut sRGB values, so clamping is necessary.
@@ -0,0 +1,121 @@
+//! A small job system for multi-scheme workloads: batch manifests,
+//! `--variant both`, and `--explore` candidates.
+//!
+//! Jobs run on the rayon pool inside a scope and send what they produce
+//! back over a bounded channel to the calling thread, which consumes each
+//! result as it finishes. Once [`queue_bound`] results are waiting, workers
+//! hold off until the consumer catches up, so slow consumers (writing
+//! files, say) cap how much finished work sits in memory. Every result
+//! carries how long its job took.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// A job's output, with its input position and how long it ran.
+#[derive(Debug, Clone)]
+pub struct Finished<T> {
+    /// Position of the job's input
+    pub index: usize,
+    pub output: T,
+    /// Time spent in the job itself, not waiting to be consumed
+    pub elapsed: Duration,
+}
+
+/// Results allowed to wait for the consumer: one per worker thread.
+pub fn queue_bound() -> usize {
+    rayon::current_num_threads().max(1)
+}
+
+/// Run `job` on every input in parallel, handing each result to `consume`
+/// on the calling thread in the order they finish.
+///
+/// An error from `consume` stops jobs that haven't started yet and is
+/// returned once the running ones are done.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::jobs::run_jobs;
+///
+/// let mut total = 0;
+/// run_jobs(vec![1, 2, 3], |_, n| n * n, |finished| {
+///     total += finished.output;
+///     Ok::<_, String>(())
+/// })
+/// .unwrap();
+/// assert_eq!(total, 14);
+///
+/// let stopped = run_jobs(vec![1, 2, 3], |_, n| n, |_| Err("disk full"));
+/// assert_eq!(stopped, Err("disk full"));
+/// ```
+pub fn run_jobs<I, T, E>(
+    inputs: Vec<I>,
+    job: impl Fn(usize, I) -> T + Sync,
+    mut consume: impl FnMut(Finished<T>) -> Result<(), E>,
+) -> Result<(), E>
+where
+    I: Send,
+    T: Send,
+{
+    let cancelled = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::sync_channel(queue_bound());
+
+    std::thread::scope(|threads| {
+        let (job, cancelled) = (&job, &cancelled);
+        // The consumer stays off the pool, so even a single worker thread
+        // can't end up waiting on itself
+        threads.spawn(move || {
+            rayon::scope(|scope| {
+                for (index, input) in inputs.into_iter().enumerate() {
+                    let sender = sender.clone();
+                    scope.spawn(move |_| {
+                        if cancelled.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        let started = Instant::now();
+                        let output = job(index, input);
+                        // Only fails once the consumer is gone
+                        let _ = sender.send(Finished {
+                            index,
+                            output,
+                            elapsed: started.elapsed(),
+                        });
+                    });
+                }
+            });
+        });
+
+        let mut outcome = Ok(());
+        for finished in receiver {
+            if outcome.is_ok()
+                && let Err(e) = consume(finished)
+            {
+                cancelled.store(true, Ordering::Relaxed);
+                outcome = Err(e);
+            }
+        }
+        outcome
+    })
+}
+
+/// Run `job` on every input in parallel and return the results in input
+/// order.
+pub fn map_jobs<I, T>(inputs: Vec<I>, job: impl Fn(usize, I) -> T + Sync) -> Vec<Finished<T>>
+where
+    I: Send,
+    T: Send,
+{
+    let mut results = Vec::with_capacity(inputs.len());
+    let Ok(()) = run_jobs(inputs, job, |finished| {
+        results.push(finished);
+        Ok::<_, std::convert::Infallible>(())
+    });
+    results.sort_by_key(|finished| finished.index);
+    results
+}
+
+/// Milliseconds in `duration`, for summaries.
+pub fn millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
@@ -0,0 +1,295 @@
+//! Key bindings for the interactive editor (`[keys]`).
+//!
+//! Each entry rebinds one action to a key or a list of keys, replacing its
+//! default keys; an empty list unbinds it. A key bound here is taken away
+//! from whichever action had it by default:
+//!
+//! ```toml
+//! [keys]
+//! value_decrement = "-"
+//! value_increment = "="
+//! value_decrement_large = "_"
+//! value_increment_large = "+"
+//! export = ["e", "ctrl+s"]
+//! ```
+//!
+//! Keys are a character (`a`, `[`, `+`) or a name (`enter`, `esc`, `tab`,
+//! `backtab`, `backspace`, `delete`, `space`, `left`, `right`, `up`,
+//! `down`, `home`, `end`, `pageup`, `pagedown`, `f1`-`f12`), optionally
+//! prefixed by `ctrl+`, `alt+`, and `shift+`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+
+/// Actions that can be rebound, by config name.
+pub const ACTIONS: &[&str] = &[
+    // Editor actions
+    "code_preview",
+    "gallery",
+    "export",
+    "screenshot",
+    "value_decrement",
+    "value_increment",
+    "value_decrement_large",
+    "value_increment_large",
+    "toggle_dark_light",
+    "cycle_cvd",
+    "cycle_display_simulation",
+    "toggle_contrast_matrix",
+    "toggle_split_view",
+    "toggle_log",
+    "toggle_perceptual_hues",
+    "cycle_harmony",
+    "maximize_contrast",
+    "shuffle",
+    "toggle_gamut_slice",
+    "toggle_curve_plot",
+    "recall_color",
+    "toggle_favorite_color",
+    "toggle_pin",
+    "edit_anchor",
+    "replay_step",
+    "shrink_left_column",
+    "grow_left_column",
+    "shrink_preview",
+    "grow_preview",
+    "next_preview_tab",
+    // Navigation
+    "left",
+    "right",
+    "up",
+    "down",
+    "home",
+    "end",
+    "page_up",
+    "page_down",
+    "back",
+    "forward",
+    // Text input
+    "confirm",
+    "cancel",
+    "delete",
+    "backspace",
+    "clear",
+    "word_left",
+    "word_right",
+    "delete_word",
+    "delete_word_back",
+    "kill_line",
+    // Lists
+    "next",
+    "prev",
+    "toggle",
+    "select_all",
+    "first",
+    "last",
+    // Application
+    "quit",
+    "help",
+    "refresh",
+    "search",
+    "filter",
+];
+
+/// Named keys, as written in `[keys]`.
+const NAMED_KEYS: &[(&str, KeyName)] = &[
+    ("enter", KeyName::Enter),
+    ("esc", KeyName::Esc),
+    ("tab", KeyName::Tab),
+    ("backtab", KeyName::BackTab),
+    ("backspace", KeyName::Backspace),
+    ("delete", KeyName::Delete),
+    ("space", KeyName::Char(' ')),
+    ("left", KeyName::Left),
+    ("right", KeyName::Right),
+    ("up", KeyName::Up),
+    ("down", KeyName::Down),
+    ("home", KeyName::Home),
+    ("end", KeyName::End),
+    ("pageup", KeyName::PageUp),
+    ("pagedown", KeyName::PageDown),
+];
+
+/// A key without modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyName {
+    Char(char),
+    /// Function key F1-F12
+    F(u8),
+    Enter,
+    Esc,
+    Tab,
+    BackTab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+}
+
+/// A key with its modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: KeyName,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    /// `key` with no modifiers.
+    pub fn new(key: KeyName) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    /// In the `[keys]` syntax, so printed bindings can be pasted back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (held, name) in [
+            (self.ctrl, "ctrl+"),
+            (self.alt, "alt+"),
+            (self.shift, "shift+"),
+        ] {
+            if held {
+                f.write_str(name)?;
+            }
+        }
+        match self.key {
+            KeyName::Char(' ') => f.write_str("space"),
+            KeyName::Char(c) => write!(f, "{c}"),
+            KeyName::F(n) => write!(f, "f{n}"),
+            key => {
+                let (name, _) = NAMED_KEYS
+                    .iter()
+                    .find(|(_, k)| *k == key)
+                    .expect("every named key has a name");
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+/// Parse a key as written in `[keys]`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::keys::{KeyChord, KeyName, parse_key};
+///
+/// assert_eq!(parse_key("[").unwrap(), KeyChord::new(KeyName::Char('[')));
+/// let save = parse_key("Ctrl+s").unwrap();
+/// assert!(save.ctrl && save.key == KeyName::Char('s'));
+/// assert_eq!(parse_key("ctrl++").unwrap().key, KeyName::Char('+'));
+/// assert_eq!(parse_key("f5").unwrap().to_string(), "f5");
+/// assert!(parse_key("hyper+x").is_err());
+/// assert!(parse_key("f13").is_err());
+/// ```
+pub fn parse_key(text: &str) -> Result<KeyChord, String> {
+    let mut rest = text.trim();
+    let mut chord = KeyChord::new(KeyName::Char(' '));
+    // A trailing "+" is the key itself, as in "ctrl++"
+    while let Some((modifier, tail)) = rest.split_once('+')
+        && !tail.is_empty()
+    {
+        match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => chord.ctrl = true,
+            "alt" | "meta" => chord.alt = true,
+            "shift" => chord.shift = true,
+            other => return Err(format!("'{other}' is not a modifier in key '{text}'")),
+        }
+        rest = tail;
+    }
+    let mut chars = rest.chars();
+    chord.key = match (chars.next(), chars.next()) {
+        (None, _) => return Err(format!("key '{text}' is empty")),
+        (Some(c), None) => KeyName::Char(c),
+        _ => {
+            let name = rest.to_ascii_lowercase();
+            if let Some((_, key)) = NAMED_KEYS.iter().find(|(n, _)| *n == name) {
+                *key
+            } else if let Some(n) = name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok())
+                && (1..=12).contains(&n)
+            {
+                KeyName::F(n)
+            } else {
+                return Err(format!("'{rest}' is not a key in '{text}'"));
+            }
+        }
+    };
+    Ok(chord)
+}
+
+/// One key or several, as an entry of `[keys]` may be written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    fn keys(&self) -> &[String] {
+        match self {
+            KeyList::One(key) => std::slice::from_ref(key),
+            KeyList::Many(keys) => keys,
+        }
+    }
+}
+
+/// Rebound actions, by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KeysConfig(pub BTreeMap<String, KeyList>);
+
+impl KeysConfig {
+    /// Whether no action is rebound.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Check that every action exists, every key parses, and no key is
+    /// bound to two actions.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.bindings()
+            .map(|_| ())
+            .map_err(ConfigError::InvalidValue)
+    }
+
+    /// The parsed keys of each rebound action.
+    pub fn bindings(&self) -> Result<Vec<(&str, Vec<KeyChord>)>, String> {
+        let mut taken: HashMap<KeyChord, &str> = HashMap::new();
+        let mut bindings = Vec::with_capacity(self.0.len());
+        for (action, keys) in &self.0 {
+            if !ACTIONS.contains(&action.as_str()) {
+                return Err(format!("keys.{action}: unknown action"));
+            }
+            let mut chords = Vec::new();
+            for key in keys.keys() {
+                let chord = parse_key(key).map_err(|e| format!("keys.{action}: {e}"))?;
+                if let Some(other) = taken.insert(chord, action) {
+                    return Err(format!(
+                        "keys.{action}: '{chord}' is already bound to {other}"
+                    ));
+                }
+                chords.push(chord);
+            }
+            bindings.push((action.as_str(), chords));
+        }
+        Ok(bindings)
+    }
+}
@@ -1,15 +1,73 @@
+pub mod accent_order;
 pub mod accent_solver;
+pub mod animate;
+pub mod annotate;
+pub mod ansi;
 pub mod apca;
+pub mod autotune;
+pub mod batch;
+pub mod builder;
 pub mod cli;
 pub mod cli_args;
 pub mod config;
+pub mod config_schema;
+pub mod contrast;
+pub mod contrast_matrix;
+pub mod convert;
+pub mod css_color;
 pub mod curves;
+pub mod cvd;
+pub mod daemon;
+pub mod display_profile;
+pub mod display_sim;
+pub mod env_expand;
+pub mod explore;
+pub mod extended_accents;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fit;
 pub mod gamut_map;
 pub mod generate;
 pub mod generated;
+pub mod harmony;
 pub mod hellwig;
+pub mod hooks;
+pub mod html_report;
+pub mod hue_links;
+pub mod hue_spacing;
+pub mod image_palette;
 pub mod import;
 pub mod interpolation;
+pub mod jobs;
+pub mod keys;
+pub mod linked;
+pub mod lint;
 pub mod logging;
+pub mod merge;
+pub mod metadata;
+pub mod migrate;
+pub mod preview;
+pub mod provenance;
+pub mod ramp;
+pub mod render;
+pub mod report;
+pub mod roles;
+pub mod schedule;
+pub mod score;
+pub mod semantic_pairs;
+pub mod surfaces;
+pub mod term_color;
+#[cfg(feature = "cli")]
 pub mod tui;
 pub mod validation;
+pub mod variant_match;
+pub mod viewing;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "cli")]
+pub mod watch;
+pub mod wcag;
+pub mod web_vars;
+pub mod wide_gamut;
+pub mod xterm256;
+pub mod yaml_patch;
@@ -0,0 +1,114 @@
+//! Dark and light variants designed together.
+//!
+//! `--variant both` generates each variant on its own from the same
+//! parameters, so the accents are optimized for whichever background the
+//! targets were written for and the other variant's hues drift with its
+//! background. Linked generation builds the variant the configuration
+//! describes first, then derives its counterpart from it: every accent keeps
+//! the hue it was given (after adaptive hues, spacing, and pins), and the
+//! accent lightness targets are mirrored (J' → 100 − J', as the TUI's
+//! dark/light toggle does) so the counterpart's accents sit as far from
+//! their background as the original's do.
+
+use tinted_builder::SchemeVariant;
+
+use crate::generate::{GenerateConfig, GenerationResult, generate_for_variant};
+
+/// A dark and a light variant generated as a pair.
+#[derive(Debug)]
+pub struct LinkedVariants {
+    pub dark: GenerationResult,
+    pub light: GenerationResult,
+    /// Configuration `dark` was generated from (with a forced dark variant)
+    pub dark_config: GenerateConfig,
+    /// Configuration `light` was generated from (with a forced light variant)
+    pub light_config: GenerateConfig,
+}
+
+impl LinkedVariants {
+    /// The variants in output order: dark, then light.
+    pub fn iter(&self) -> impl Iterator<Item = (&GenerateConfig, &GenerationResult)> {
+        [
+            (&self.dark_config, &self.dark),
+            (&self.light_config, &self.light),
+        ]
+        .into_iter()
+    }
+}
+
+/// Generate both variants with shared hues and mirrored accent lightness.
+///
+/// The variant matching `config`'s background (as auto-detection picks it)
+/// is generated from `config` unchanged; the other one from
+/// `counterpart_config`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::GenerateConfig;
+/// use themalingadingdong::linked::generate_linked;
+///
+/// let linked = generate_linked(&GenerateConfig::default());
+/// let dark = &linked.dark.base_accent_results;
+/// let light = &linked.light.base_accent_results;
+///
+/// // Same hue per slot, lightness on opposite sides of the middle
+/// for (d, l) in dark.iter().zip(light) {
+///     assert!((d.hue - l.hue).abs() < 0.5);
+///     assert!(d.lightness > 50.0 && l.lightness < 50.0);
+/// }
+/// ```
+pub fn generate_linked(config: &GenerateConfig) -> LinkedVariants {
+    let primary = generate_for_variant(config, None);
+    let (other_config, other) = generate_counterpart(config, &primary);
+
+    if matches!(primary.scheme.variant, SchemeVariant::Light) {
+        LinkedVariants {
+            dark: other,
+            light: primary,
+            dark_config: other_config,
+            light_config: config.clone(),
+        }
+    } else {
+        LinkedVariants {
+            dark: primary,
+            light: other,
+            dark_config: config.clone(),
+            light_config: other_config,
+        }
+    }
+}
+
+/// Generate the opposite variant of `primary` (generated from `config`),
+/// returning it with the configuration it was generated from.
+pub fn generate_counterpart(
+    config: &GenerateConfig,
+    primary: &GenerationResult,
+) -> (GenerateConfig, GenerationResult) {
+    let variant = match primary.scheme.variant {
+        SchemeVariant::Light => SchemeVariant::Dark,
+        _ => SchemeVariant::Light,
+    };
+    let counterpart = counterpart_config(config, primary);
+    let result = generate_for_variant(&counterpart, Some(variant));
+    (counterpart, result)
+}
+
+/// Configuration for the opposite variant of `primary`, generated from
+/// `config`: its accent hues fixed to `primary`'s and its accent lightness
+/// targets mirrored.
+pub fn counterpart_config(config: &GenerateConfig, primary: &GenerationResult) -> GenerateConfig {
+    let mut hue_overrides = [None; 8];
+    for (hue, result) in hue_overrides.iter_mut().zip(&primary.base_accent_results) {
+        *hue = Some(result.hue);
+    }
+
+    let mut counterpart = config.clone();
+    counterpart.hue_overrides = hue_overrides;
+    // The hues are final; don't move them again for the other background
+    counterpart.adaptive_hues = false;
+    counterpart.hue_spacing = None;
+    counterpart.accent_opt.target_j = 100.0 - config.accent_opt.target_j;
+    counterpart.extended_accent_opt.target_j = 100.0 - config.extended_accent_opt.target_j;
+    counterpart
+}
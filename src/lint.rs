@@ -0,0 +1,341 @@
+//! Lint checks for existing scheme files (`lint`).
+//!
+//! Hand-made and imported schemes often carry small defects that the
+//! generator avoids by construction. Each finding has a stable,
+//! machine-readable code:
+//!
+//! - `duplicate-color`: two slots share one color
+//! - `flat-surfaces`: adjacent surfaces (base00-base07) are nearly identical
+//! - `surface-order`: surface lightness turns back on itself
+//! - `ansi-hue`: an accent's hue has left its ANSI color's band
+//!   (see [`crate::ansi`])
+//! - `low-contrast`: a required validation pair misses its threshold
+//!
+//! Most findings carry a fix: the smallest change in HellwigJmh that
+//! resolves them, moving only J' for lightness and contrast problems and
+//! only hue for ANSI hues. [`fix_scheme`] applies fixes and re-lints until
+//! nothing more can be fixed, since one fix can expose another.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use palette::Srgb;
+use serde::Serialize;
+use tinted_builder::{Base16Scheme, Color};
+
+use crate::ansi::{AnsiConfig, out_of_band};
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::{srgb_to_f32, srgb_to_hex, srgb_to_u8, tune_contrast};
+use crate::validation::{ContrastStandard, ValidationConfig, validate_with_config};
+
+/// Surface slots, darkest to lightest in a dark scheme.
+const SURFACES: [&str; 8] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07",
+];
+
+/// Smallest ΔE between adjacent surfaces, and the J' step a fix restores.
+const MIN_SURFACE_STEP: f32 = 2.0;
+
+/// J' a duplicate is moved by per attempt until its hex changes.
+const DUPLICATE_NUDGE: f32 = 1.0;
+
+/// Degrees inside its band an out-of-band hue is moved to.
+const HUE_INSET: f32 = 2.0;
+
+/// Lc above the threshold a contrast fix aims for, so u8 rounding keeps it
+/// passing.
+const CONTRAST_MARGIN: f64 = 1.0;
+
+/// Lint and fix rounds [`fix_scheme`] runs at most.
+const FIX_PASSES: usize = 4;
+
+/// What a finding is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintCode {
+    DuplicateColor,
+    FlatSurfaces,
+    SurfaceOrder,
+    AnsiHue,
+    LowContrast,
+}
+
+impl LintCode {
+    /// The code as printed and serialized.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DuplicateColor => "duplicate-color",
+            Self::FlatSurfaces => "flat-surfaces",
+            Self::SurfaceOrder => "surface-order",
+            Self::AnsiHue => "ansi-hue",
+            Self::LowContrast => "low-contrast",
+        }
+    }
+}
+
+impl fmt::Display for LintCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A problem found in a scheme.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintFinding {
+    pub code: LintCode,
+    /// Slot the finding is about, and the one its fix changes
+    pub slot: String,
+    pub message: String,
+    /// Hex color (without `#`) the fix gives `slot`, if it can be fixed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}] {}", self.slot, self.code, self.message)?;
+        if let Some(ref hex) = self.fix {
+            write!(f, " (fix: #{hex})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lint a scheme with the default validation thresholds and ANSI hue bands.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::lint::{LintCode, lint_scheme};
+///
+/// let mut scheme = generate(&GenerateConfig::default()).scheme;
+/// let red = scheme.palette["base08"].clone();
+/// scheme.palette.insert("base09".to_string(), red);
+///
+/// let findings = lint_scheme(&scheme);
+/// assert_eq!(findings[0].code, LintCode::DuplicateColor);
+/// assert_eq!(findings[0].slot, "base09");
+/// assert!(findings[0].fix.is_some());
+/// ```
+pub fn lint_scheme(scheme: &Base16Scheme) -> Vec<LintFinding> {
+    lint_scheme_with(scheme, &ValidationConfig::default(), &AnsiConfig::default())
+}
+
+/// Lint a scheme against `validation`'s thresholds and `ansi`'s hue bands,
+/// ordered by code, then slot.
+pub fn lint_scheme_with(
+    scheme: &Base16Scheme,
+    validation: &ValidationConfig,
+    ansi: &AnsiConfig,
+) -> Vec<LintFinding> {
+    let mut findings = duplicates(scheme);
+    findings.extend(surfaces(scheme));
+    findings.extend(ansi_hues(scheme, ansi));
+    findings.extend(contrast(scheme, validation));
+    findings.sort_by(|a, b| (a.code, &a.slot).cmp(&(b.code, &b.slot)));
+    findings
+}
+
+/// Apply fixes until the scheme lints clean or nothing more can be fixed,
+/// returning the findings that were fixed. Lint again for what remains.
+///
+/// Each round changes a slot at most once, with its first finding's fix.
+pub fn fix_scheme(
+    scheme: &mut Base16Scheme,
+    validation: &ValidationConfig,
+    ansi: &AnsiConfig,
+) -> Vec<LintFinding> {
+    let mut fixed = Vec::new();
+    for _ in 0..FIX_PASSES {
+        let mut changed = BTreeSet::new();
+        for finding in lint_scheme_with(scheme, validation, ansi) {
+            let Some(ref hex) = finding.fix else {
+                continue;
+            };
+            if !changed.insert(finding.slot.clone()) {
+                continue;
+            }
+            scheme.palette.insert(
+                finding.slot.clone(),
+                Color::new(hex.clone()).expect("valid hex"),
+            );
+            fixed.push(finding);
+        }
+        if changed.is_empty() {
+            break;
+        }
+    }
+    fixed
+}
+
+fn jmh_of(scheme: &Base16Scheme, slot: &str) -> Option<HellwigJmh> {
+    let (r, g, b) = scheme.palette.get(slot)?.rgb;
+    Some(HellwigJmh::from_srgb_u8(Srgb::new(r, g, b)))
+}
+
+fn hex_of(jmh: HellwigJmh) -> String {
+    srgb_to_hex(jmh.into_srgb_u8_gamut_mapped())
+}
+
+/// +1 when surfaces get lighter from base00 to base07 (a dark scheme), -1
+/// otherwise.
+fn surface_direction(scheme: &Base16Scheme) -> f32 {
+    match (jmh_of(scheme, "base00"), jmh_of(scheme, "base07")) {
+        (Some(bg), Some(fg)) if fg.lightness < bg.lightness => -1.0,
+        _ => 1.0,
+    }
+}
+
+/// Later slots sharing an earlier slot's color. Surfaces are left to the
+/// surface checks.
+fn duplicates(scheme: &Base16Scheme) -> Vec<LintFinding> {
+    let direction = surface_direction(scheme);
+    let mut slots: Vec<&String> = scheme.palette.keys().collect();
+    slots.sort();
+
+    let mut findings = Vec::new();
+    for (i, slot) in slots.iter().enumerate() {
+        let rgb = scheme.palette[*slot].rgb;
+        let Some(first) = slots[..i].iter().find(|other| {
+            scheme.palette[**other].rgb == rgb
+                && !(SURFACES.contains(&other.as_str()) && SURFACES.contains(&slot.as_str()))
+        }) else {
+            continue;
+        };
+
+        // Step J' away from the background until the color rounds differently
+        let jmh = jmh_of(scheme, slot).expect("slot exists");
+        let original = srgb_to_hex(Srgb::new(rgb.0, rgb.1, rgb.2));
+        let fix = (1..=5)
+            .map(|step| {
+                let j = jmh.lightness + direction * DUPLICATE_NUDGE * step as f32;
+                hex_of(HellwigJmh::new(j, jmh.colorfulness, jmh.hue))
+            })
+            .find(|hex| *hex != original);
+        findings.push(LintFinding {
+            code: LintCode::DuplicateColor,
+            slot: slot.to_string(),
+            message: format!("same color as {first}"),
+            fix,
+        });
+    }
+    findings
+}
+
+/// Surfaces that step back against the ramp's direction, or barely move.
+/// The fix sets a surface one step past the one before it.
+fn surfaces(scheme: &Base16Scheme) -> Vec<LintFinding> {
+    let direction = surface_direction(scheme);
+    let mut findings = Vec::new();
+    for pair in SURFACES.windows(2) {
+        let (Some(prev), Some(jmh)) = (jmh_of(scheme, pair[0]), jmh_of(scheme, pair[1])) else {
+            continue;
+        };
+        let step = direction * (jmh.lightness - prev.lightness);
+        let (code, message) = if step < 0.0 {
+            (
+                LintCode::SurfaceOrder,
+                format!(
+                    "J' {:.1} is {} than {} ({:.1})",
+                    jmh.lightness,
+                    if direction > 0.0 { "darker" } else { "lighter" },
+                    pair[0],
+                    prev.lightness
+                ),
+            )
+        } else if jmh.delta_e(&prev) < MIN_SURFACE_STEP {
+            (
+                LintCode::FlatSurfaces,
+                format!(
+                    "only ΔE {:.1} from {} (needs {MIN_SURFACE_STEP})",
+                    jmh.delta_e(&prev),
+                    pair[0]
+                ),
+            )
+        } else {
+            continue;
+        };
+        let j = prev.lightness + direction * MIN_SURFACE_STEP;
+        findings.push(LintFinding {
+            code,
+            slot: pair[1].to_string(),
+            message,
+            fix: Some(hex_of(HellwigJmh::new(j, jmh.colorfulness, jmh.hue))),
+        });
+    }
+    findings
+}
+
+/// Accents outside their ANSI hue band, fixed by turning the hue just
+/// inside the nearer edge.
+fn ansi_hues(scheme: &Base16Scheme, config: &AnsiConfig) -> Vec<LintFinding> {
+    out_of_band(scheme, config)
+        .into_iter()
+        .map(|(accent, band, jmh)| {
+            let gap = |edge: f32| {
+                let d = (jmh.hue - edge).rem_euclid(360.0);
+                d.min(360.0 - d)
+            };
+            let width = (band.to - band.from).rem_euclid(360.0);
+            let inset = HUE_INSET.min(width / 2.0);
+            let hue = if gap(band.from) <= gap(band.to) {
+                band.from + inset
+            } else {
+                band.to - inset
+            };
+            LintFinding {
+                code: LintCode::AnsiHue,
+                slot: accent.slot.to_string(),
+                message: format!(
+                    "hue {:.0}° is outside the {} band {:.0}°-{:.0}°",
+                    jmh.hue, accent.name, band.from, band.to
+                ),
+                fix: Some(hex_of(HellwigJmh::new(
+                    jmh.lightness,
+                    jmh.colorfulness,
+                    hue.rem_euclid(360.0),
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Required validation pairs below their APCA threshold, fixed by moving
+/// the foreground's J' away from the background just far enough.
+fn contrast(scheme: &Base16Scheme, validation: &ValidationConfig) -> Vec<LintFinding> {
+    let results = validate_with_config(scheme, &[], &[], ContrastStandard::Apca, validation);
+    results
+        .required
+        .iter()
+        .filter(|result| !result.apca_passes)
+        .filter_map(|result| {
+            let pair = &result.pair;
+            let rgb = |slot: &str| {
+                let (r, g, b) = scheme.palette.get(slot)?.rgb;
+                Some(srgb_to_f32(Srgb::new(r, g, b)))
+            };
+            let (fg, bg) = (rgb(pair.foreground)?, rgb(pair.background)?);
+            let lighter = jmh_of(scheme, pair.foreground)?.lightness
+                >= jmh_of(scheme, pair.background)?.lightness;
+            let extreme = if lighter {
+                Srgb::new(1.0, 1.0, 1.0)
+            } else {
+                Srgb::new(0.0, 0.0, 0.0)
+            };
+            let target = pair.threshold.min_lc + CONTRAST_MARGIN;
+            let (tuned, lc) = tune_contrast(fg, bg, extreme, target);
+            Some(LintFinding {
+                code: LintCode::LowContrast,
+                slot: pair.foreground.to_string(),
+                message: format!(
+                    "Lc {:.1} on {}, needs {:.0}",
+                    result.contrast.abs(),
+                    pair.background,
+                    pair.threshold.min_lc
+                ),
+                fix: (lc >= pair.threshold.min_lc).then(|| srgb_to_hex(srgb_to_u8(tuned))),
+            })
+        })
+        .collect()
+}
@@ -1,11 +1,158 @@
 //! Logging configuration using tracing with file appender.
+//!
+//! Besides the log file, the most recent events are kept in memory
+//! ([`log_buffer`]) for the TUI's log panel, so solver warnings show up as
+//! they happen.
 
+use std::collections::VecDeque;
+use std::fmt::{self as std_fmt, Write};
 use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::layer::{Context, Layer};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{EnvFilter, fmt};
 
+/// Events kept by [`log_buffer`].
+pub const LOG_BUFFER_CAPACITY: usize = 500;
+
+/// One recorded log event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub level: Level,
+    /// Module that emitted the event, e.g. `themalingadingdong::accent_solver`
+    pub target: String,
+    /// The message followed by the other fields as `key=value`
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+struct LogRing {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    /// Warnings and errors recorded so far, including dropped ones
+    warnings: u64,
+}
+
+/// The most recent log events, oldest first; full buffers drop their
+/// oldest event. Clones share the same events.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    ring: Arc<Mutex<LogRing>>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ring: Arc::new(Mutex::new(LogRing {
+                capacity,
+                ..Default::default()
+            })),
+        }
+    }
+
+    pub fn push(&self, entry: LogEntry) {
+        let mut ring = self.ring.lock().unwrap_or_else(PoisonError::into_inner);
+        if entry.level <= Level::WARN {
+            ring.warnings += 1;
+        }
+        if ring.entries.len() == ring.capacity {
+            ring.entries.pop_front();
+        }
+        if ring.capacity > 0 {
+            ring.entries.push_back(entry);
+        }
+    }
+
+    /// The last `count` events, oldest first.
+    pub fn tail(&self, count: usize) -> Vec<LogEntry> {
+        let ring = self.ring.lock().unwrap_or_else(PoisonError::into_inner);
+        let skip = ring.entries.len().saturating_sub(count);
+        ring.entries.iter().skip(skip).cloned().collect()
+    }
+
+    /// Number of events held.
+    pub fn len(&self) -> usize {
+        self.ring
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entries
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Warnings and errors recorded since the buffer was created, including
+    /// any since dropped; compare two readings to tell whether new ones came in.
+    pub fn warning_count(&self) -> u64 {
+        self.ring
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .warnings
+    }
+}
+
+/// Buffer fed by the subscriber [`init_logging`] installs.
+pub fn log_buffer() -> &'static LogBuffer {
+    static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+    BUFFER.get_or_init(|| LogBuffer::new(LOG_BUFFER_CAPACITY))
+}
+
+/// Layer recording every event that passes the subscriber's filter into a
+/// [`LogBuffer`].
+pub struct LogBufferLayer {
+    buffer: LogBuffer,
+}
+
+impl LogBufferLayer {
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.buffer.push(LogEntry {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message + &visitor.fields,
+        });
+    }
+}
+
+/// Collects an event's message and its other fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std_fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
 /// Initialize tracing with file output.
 ///
 /// Returns a guard that must be held for the duration of the program to ensure
@@ -45,6 +192,7 @@ pub fn init_logging(log_path: Option<&Path>, level: Option<&str>) -> WorkerGuard
     tracing_subscriber::registry()
         .with(filter)
         .with(file_layer)
+        .with(LogBufferLayer::new(log_buffer().clone()))
         .init();
 
     guard
@@ -1,19 +1,66 @@
 //! CLI entry point for themalingadingdong.
 
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use clap::{CommandFactory, Parser};
 use color_eyre::eyre::{Result, WrapErr, bail, eyre};
-use tinted_builder::SchemeVariant;
+use serde::Serialize;
+use tinted_builder::{Base16Scheme, SchemeVariant};
 use tracing::{info, warn};
 
-use themalingadingdong::cli::{Cli, OutputFormat, VariantArg};
-use themalingadingdong::config::{load_config, validate_config};
-use themalingadingdong::generate::generate_for_variant;
-use themalingadingdong::import::import_scheme;
+use themalingadingdong::accent_order::match_accent_order;
+use themalingadingdong::animate::{css_keyframes, interpolate_schemes};
+use themalingadingdong::annotate::{annotate_yaml, slot_notes};
+use themalingadingdong::ansi::{AnsiConfig, AnsiMap, semantic_warnings};
+use themalingadingdong::autotune::{max_feasible_contrast, max_feasible_linked_contrast};
+use themalingadingdong::batch::{BatchEvent, BatchManifest, run_batch, run_batch_with_progress};
+use themalingadingdong::cli::{Cli, Command, OutputFormat, ReportKind, VariantArg, Xterm256Mode};
+use themalingadingdong::config::{
+    HueOverrides, STDIN_CONFIG, TerminalAttributes, ThemeConfig, load_config_with_profile,
+    validate_config,
+};
+use themalingadingdong::config_schema::json_schema;
+use themalingadingdong::contrast_matrix::{ContrastMatrix, render_table};
+use themalingadingdong::convert::convert_color;
+use themalingadingdong::cvd::check_cvd;
+use themalingadingdong::daemon::rebuild;
+use themalingadingdong::display_sim::DisplaySimulation;
+use themalingadingdong::explore::{EXPLORE_SHOWN, explore, random_seed};
+use themalingadingdong::fit::fit_scheme;
+use themalingadingdong::generate::{GenerateConfig, Timings, generate_for_variant};
+use themalingadingdong::hooks::{HookSummary, run_hook};
+use themalingadingdong::html_report::render_html;
+use themalingadingdong::image_palette::{
+    DEFAULT_CLUSTERS, dominant_colors, image_theme, load_image,
+};
+use themalingadingdong::import::{import_scheme, read_scheme};
+use themalingadingdong::interpolation::srgb_to_hex;
+use themalingadingdong::jobs::map_jobs;
+use themalingadingdong::linked::generate_linked;
+use themalingadingdong::lint::{LintFinding, fix_scheme, lint_scheme_with};
 use themalingadingdong::logging::init_logging;
+use themalingadingdong::merge::{MergePlan, merge_schemes, parse_pick};
+use themalingadingdong::metadata::{check_generated_metadata, check_publishable, fix_metadata};
+use themalingadingdong::migrate::{CONFIG_VERSION, migrate_config};
+use themalingadingdong::preview::render_preview_at;
+use themalingadingdong::provenance::{Provenance, lut_info, reproduce};
+use themalingadingdong::render::{render_template_dir, template_dirs};
+use themalingadingdong::report::{Report, SchemeReport, SolverReport, ValidationReport, Warnings};
+use themalingadingdong::roles::{RolesConfig, SemanticRoles, derive_roles};
+use themalingadingdong::schedule::{ScheduleManifest, generate_schedule};
+use themalingadingdong::score::{SchemeScore, ScoreWeights, score_scheme_with};
+use themalingadingdong::surfaces::SurfaceRamp;
 use themalingadingdong::tui;
-use themalingadingdong::validation::{validate, validate_with_warnings};
+use themalingadingdong::validation::{
+    ContrastStandard, ValidationResult, terminal_attribute_warnings, validate_with_config,
+    validate_with_standard, validation_warnings,
+};
+use themalingadingdong::variant_match::compare_variants;
+use themalingadingdong::watch::{DEFAULT_DEBOUNCE, watch_file};
+use themalingadingdong::xterm256::{approximation_table_with, comment_block};
 
 fn main() -> Result<()> {
     color_eyre::install()?;
@@ -31,22 +78,289 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if cli.print_lut_info {
+        print!("{}", lut_info());
+        return Ok(());
+    }
+
+    if cli.print_keys {
+        let config = load_config_with_profile(
+            cli.config.as_deref(),
+            cli.profile.as_deref(),
+            &cli.to_config_overrides(),
+        )
+        .map_err(|e| eyre!("Configuration error: {}", e))?;
+        print!("{}", tui::key_bindings_table(&config.keys)?);
+        return Ok(());
+    }
+
+    // Standalone subcommands skip logging and generation entirely
+    if let Some(Command::Convert { ref color }) = cli.command {
+        let report = convert_color(color).map_err(|e| eyre!(e))?;
+        print!("{report}");
+        return Ok(());
+    }
+
+    if let Some(Command::PublishCheck { ref files }) = cli.command {
+        return publish_check(files);
+    }
+
+    if let Some(Command::Animate {
+        ref from,
+        ref to,
+        steps,
+        ref output_dir,
+        keyframes,
+    }) = cli.command
+    {
+        return animate(&cli, from, to, steps.into(), output_dir, keyframes);
+    }
+
+    if let Some(Command::Merge {
+        ref a,
+        ref b,
+        surfaces,
+        accents,
+        extended_accents,
+        ref pick,
+    }) = cli.command
+    {
+        let mut plan = MergePlan {
+            surfaces: surfaces.into(),
+            accents: accents.into(),
+            extended_accents: extended_accents.unwrap_or(accents).into(),
+            picks: BTreeMap::new(),
+        };
+        for pick in pick {
+            let (slot, side) = parse_pick(pick)?;
+            plan.picks.insert(slot, side);
+        }
+        return merge(&cli, a, b, &plan);
+    }
+
+    if let Some(Command::ConfigSchema) = cli.command {
+        println!("{}", serde_json::to_string_pretty(&json_schema())?);
+        return Ok(());
+    }
+
+    if let Some(Command::MigrateConfig {
+        ref file,
+        ref output,
+        dry_run,
+    }) = cli.command
+    {
+        return migrate_config_file(
+            file,
+            output.as_deref().or(cli.output.as_deref()),
+            dry_run || cli.dry_run,
+        );
+    }
+
     let _log_guard = init_logging(cli.log_file.as_deref(), Some(&cli.log_level));
 
     info!(version = env!("CARGO_PKG_VERSION"), "started");
 
-    // Launch TUI only if --interactive is specified
-    if cli.interactive {
+    if let Some(Command::Preview) = cli.command {
+        let simulation = cli.simulate.map(DisplaySimulation::from);
+        let depth = cli.color_depth(std::io::stdout().is_terminal());
+        if let Some(warning) = depth.warning() {
+            eprintln!("Warning: {warning}");
+        }
+        for scheme in subcommand_schemes(&cli)? {
+            match simulation {
+                Some(simulation) => {
+                    println!("Simulating {simulation} display");
+                    print!(
+                        "{}",
+                        render_preview_at(&simulation.apply_scheme(&scheme), depth)
+                    );
+                }
+                None => print!("{}", render_preview_at(&scheme, depth)),
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Render {
+        ref templates,
+        ref template_glob,
+        ref output_dir,
+    }) = cli.command
+    {
+        return render_templates(&cli, templates, template_glob, output_dir.as_deref());
+    }
+
+    if let Some(Command::Reproduce { ref file }) = cli.command {
+        let content = std::fs::read_to_string(file)
+            .wrap_err_with(|| format!("Failed to read {}", file.display()))?;
+        let report = reproduce(&content)?;
+        print!("{report}");
+        if !report.is_identical() {
+            bail!("{} is not reproducible", file.display());
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Schedule {
+        steps,
+        ref times,
+        ref output_dir,
+    }) = cli.command
+    {
+        return write_schedule(&cli, steps.into(), times, output_dir);
+    }
+
+    if let Some(Command::Score { ref files }) = cli.command {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&rank_schemes(&cli, files)?)?
+        );
+        return Ok(());
+    }
+
+    if let Some(Command::Lint {
+        ref files,
+        fix,
+        json,
+    }) = cli.command
+    {
+        return lint_files(&cli, files, fix, json);
+    }
+
+    if let Some(Command::MatchVariants {
+        ref dark,
+        ref light,
+        json,
+    }) = cli.command
+    {
+        return match_variants(&cli, dark.as_deref(), light.as_deref(), json);
+    }
+
+    if let Some(ref path) = cli.screenshot {
+        tui::screenshot(&cli, path)?;
+        eprintln!("Saved screenshot to {}", path.display());
+        return Ok(());
+    }
+
+    // Launch TUI only if --interactive (or --replay/--resume) is specified
+    if cli.interactive || cli.replay.is_some() || cli.resume {
         return tui::run(&cli);
     }
 
+    // Handle --batch: generate every scheme in the manifest
+    if let Some(ref manifest_path) = cli.batch {
+        let manifest = BatchManifest::load(manifest_path)?;
+        let output_dir = cli
+            .output
+            .clone()
+            .or_else(|| manifest.output_dir.clone())
+            .unwrap_or_else(|| ".".into());
+
+        if cli.progress_json {
+            let emit = |event: &BatchEvent| {
+                if let Ok(line) = serde_json::to_string(event) {
+                    let _ = writeln!(std::io::stderr().lock(), "{line}");
+                }
+            };
+            run_batch_with_progress(
+                &manifest,
+                &output_dir,
+                cli.format,
+                &cli.var_prefix,
+                cli.fix_metadata,
+                &emit,
+            )?;
+            return Ok(());
+        }
+
+        let started = Instant::now();
+        let outcomes = run_batch(
+            &manifest,
+            &output_dir,
+            cli.format,
+            &cli.var_prefix,
+            cli.fix_metadata,
+        )?;
+        for outcome in &outcomes {
+            for warning in &outcome.warnings {
+                warn!(scheme = %outcome.scheme.slug, warning = %warning, "batch warning");
+                eprintln!("{}: {warning}", outcome.scheme.slug);
+            }
+            eprintln!(
+                "Wrote scheme to {} ({})",
+                outcome.path.display(),
+                format_ms(outcome.elapsed)
+            );
+        }
+        eprintln!(
+            "Generated {} schemes in {}",
+            outcomes.len(),
+            format_ms(started.elapsed())
+        );
+        return Ok(());
+    }
+
     // Handle --input without TUI: validate and output the imported scheme
     if let Some(ref input_path) = cli.input {
         let import_result = import_scheme(input_path)
             .wrap_err_with(|| format!("Failed to import {}", input_path.display()))?;
+        for warning in &import_result.warnings {
+            eprintln!("Warning: {warning}");
+        }
+
+        // Handle --fit: recover generator parameters instead of re-emitting the scheme
+        if cli.fit {
+            let fit = fit_scheme(&import_result.scheme)?;
+
+            eprintln!("Fit residuals (delta E):");
+            for r in &fit.residuals {
+                eprintln!(
+                    "  {}: #{} -> #{}  {:5.1}",
+                    r.slot,
+                    srgb_to_hex(r.target),
+                    srgb_to_hex(r.fitted),
+                    r.delta_e
+                );
+            }
+            eprintln!(
+                "Mean delta E {:.1}, max {:.1}",
+                fit.mean_error(),
+                fit.max_error()
+            );
 
-        let scheme = &import_result.scheme;
-        let results = validate(scheme);
+            let mut fitted = ThemeConfig::from_generate_config(&fit.config);
+            fitted.theme.variant = import_result.config.theme.variant.clone();
+            if let Some(ref save_path) = cli.save_config {
+                fitted
+                    .save(save_path)
+                    .map_err(|e| eyre!("Failed to save config: {}", e))?;
+                eprintln!("Saved configuration to {}", save_path.display());
+            } else {
+                print!("{}", toml::to_string_pretty(&fitted)?);
+            }
+            return Ok(());
+        }
+
+        let mut scheme = import_result.scheme;
+        let scheme = &mut scheme;
+        let standard: ContrastStandard = cli.contrast_standard.map(Into::into).unwrap_or_default();
+        // Thresholds and extra pairs come from --config's [validation], if given
+        let results = match cli.config {
+            Some(ref path) => {
+                let config = load_config_with_profile(
+                    Some(path),
+                    cli.profile.as_deref(),
+                    &cli.to_config_overrides(),
+                )
+                .map_err(|e| eyre!("Configuration error: {}", e))?;
+                config
+                    .validation
+                    .validate()
+                    .map_err(|e| eyre!("Configuration error: {}", e))?;
+                validate_with_config(scheme, &[], &[], standard, &config.validation)
+            }
+            None => validate_with_standard(scheme, standard),
+        };
 
         // Print validation results
         eprintln!("Imported: {} by {}", scheme.name, scheme.author);
@@ -54,48 +368,100 @@ fn main() -> Result<()> {
         eprintln!();
 
         // Print required validations
-        eprintln!("Required contrast checks:");
+        eprintln!("Required contrast checks ({standard}):");
         for result in &results.required {
             let status = if result.passes { "PASS" } else { "FAIL" };
             eprintln!(
-                "  {} on {}: Lc {:5.1} (min {:5.1}) [{}]",
+                "  {} on {}: Lc {:5.1} (min {:5.1}, {})  {} [{}] {}",
                 result.pair.foreground,
                 result.pair.background,
                 result.contrast.abs(),
                 result.pair.threshold.min_lc,
-                status
+                result.polarity,
+                format_wcag(result),
+                status,
+                result.usage
             );
         }
 
-        // Print reference validations (informational)
+        // Print every accent/background pair (informational)
         if !results.reference.is_empty() {
             eprintln!();
-            eprintln!("Reference contrast (informational):");
+            eprintln!("Accent contrast by background (informational):");
             for result in &results.reference {
                 let status = if result.passes { "pass" } else { "low" };
                 eprintln!(
-                    "  {} on {}: Lc {:5.1} ({})",
+                    "  {} on {}: Lc {:5.1} (min {:5.1})  {} ({}, {})",
                     result.pair.foreground,
                     result.pair.background,
                     result.contrast.abs(),
-                    status
+                    result.pair.threshold.min_lc,
+                    format_wcag(result),
+                    status,
+                    result.usage
                 );
             }
         }
 
-        let pass_count = results.required.iter().filter(|r| r.passes).count();
-        let total = results.required.len();
+        if !results.semantic.is_empty() {
+            eprintln!();
+            eprintln!("Semantic pairs:");
+            for result in &results.semantic {
+                let status = if result.passes { "PASS" } else { "FAIL" };
+                eprintln!("  {result} [{status}]");
+            }
+        }
+
+        eprintln!();
+        eprintln!(
+            "Summary: {}/{} required checks pass",
+            results.required_passed(),
+            results.required_count()
+        );
+
         eprintln!();
-        eprintln!("Summary: {}/{} required checks pass", pass_count, total);
+        report_metadata(scheme, cli.fix_metadata, true);
+
+        for warning in semantic_warnings(scheme, &AnsiConfig::default()) {
+            warn!(warning = %warning, "ansi warning");
+            eprintln!("Warning: {warning}");
+        }
+
+        if cli.check_cvd {
+            eprintln!();
+            report_cvd(scheme, cli.cvd_threshold);
+        }
+
+        if let Some(attrs) = cli.terminal_attributes() {
+            attrs.validate()?;
+            eprintln!();
+            report_terminal_attributes(scheme, &attrs);
+        }
 
         // Output the scheme in requested format (unless --dry-run)
         if !cli.dry_run {
-            let output_content = match cli.format {
-                OutputFormat::Yaml => {
-                    serde_yaml::to_string(scheme).wrap_err("Failed to serialize scheme to YAML")?
-                }
-                OutputFormat::Json => serde_json::to_string_pretty(scheme)
-                    .wrap_err("Failed to serialize scheme to JSON")?,
+            let roles = cli
+                .roles
+                .then(|| derive_roles(scheme, &RolesConfig::default()));
+            let ansi = cli.ansi_map.then(|| AnsiMap::new(scheme));
+            let surfaces = cli
+                .surface_steps
+                .map(|steps| SurfaceRamp::new(scheme, steps.into()));
+            // Plain YAML keeps the imported file's comments and layout
+            let plain = roles.is_none() && ansi.is_none() && surfaces.is_none();
+            let patched = plain
+                .then(|| cli.format.patch_source(scheme, &import_result.source))
+                .flatten();
+            let output_content = match patched {
+                Some(output) => output,
+                None => cli.format.serialize_prefixed(
+                    scheme,
+                    None,
+                    roles.as_ref(),
+                    ansi.as_ref(),
+                    surfaces.as_ref(),
+                    &cli.var_prefix,
+                )?,
             };
 
             if let Some(ref output_path) = cli.output {
@@ -110,12 +476,774 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Handle --watch: regenerate on every config change until interrupted
+    if cli.watch {
+        return watch_config(&cli);
+    }
+
+    // Handle --daemon: like --watch, but write every [daemon] output and
+    // recolor attached terminals
+    if cli.daemon {
+        return run_daemon(&cli);
+    }
+
+    generate_from_config(&cli)
+}
+
+/// Raise the contrast floors to the highest values every accent can meet
+/// (across all requested variants).
+fn maximize_contrast(
+    theme_config: &mut ThemeConfig,
+    variants: &[Option<SchemeVariant>],
+    linked: bool,
+) -> Result<()> {
+    let config = theme_config
+        .to_generate_config()
+        .map_err(|e| eyre!("Invalid configuration: {}", e))?;
+    let ceiling = if linked {
+        max_feasible_linked_contrast(&config)
+    } else {
+        max_feasible_contrast(&config, variants)
+    };
+    let Some(min_contrast) = ceiling.min_contrast else {
+        bail!("No contrast floor keeps every accent within its J'/M bounds; widen the bounds");
+    };
+    theme_config.contrast.minimum = min_contrast;
+    eprintln!("Max feasible min_contrast: Lc {min_contrast:.1}");
+    match ceiling.extended_min_contrast {
+        Some(extended) => {
+            theme_config.contrast.extended_minimum = extended;
+            eprintln!("Max feasible extended_min_contrast: Lc {extended:.1}");
+        }
+        None => eprintln!(
+            "No feasible extended_min_contrast; keeping Lc {:.1}",
+            theme_config.contrast.extended_minimum
+        ),
+    }
+    Ok(())
+}
+
+/// Rebuild the output now and again after each change to the `--config` file.
+///
+/// Errors during a rebuild (e.g. a half-edited TOML file) are reported and the
+/// watch continues; only watcher failures end it.
+fn watch_config(cli: &Cli) -> Result<()> {
+    let config_path = cli
+        .config
+        .as_deref()
+        .ok_or_else(|| eyre!("--watch requires --config"))?;
+    if config_path == Path::new(STDIN_CONFIG) {
+        bail!("--watch requires a config file, not standard input");
+    }
+
+    let rebuild = || {
+        if let Err(e) = generate_from_config(cli) {
+            warn!(error = %e, "rebuild failed");
+            eprintln!("Error: {e}");
+        }
+    };
+
+    rebuild();
+    eprintln!(
+        "Watching {} for changes (Ctrl+C to stop)",
+        config_path.display()
+    );
+    watch_file(config_path, DEFAULT_DEBOUNCE, || {
+        eprintln!();
+        eprintln!("{} changed, regenerating", config_path.display());
+        rebuild();
+    })
+}
+
+/// Rebuild all daemon outputs now and again after each change to the
+/// `--config` file.
+///
+/// As with `--watch`, a failed rebuild is reported and the daemon keeps
+/// running with the last good outputs in place.
+fn run_daemon(cli: &Cli) -> Result<()> {
+    let config_path = cli
+        .config
+        .as_deref()
+        .ok_or_else(|| eyre!("--daemon requires --config"))?;
+    if config_path == Path::new(STDIN_CONFIG) {
+        bail!("--daemon requires a config file, not standard input");
+    }
+
+    let rebuild = || {
+        if let Err(e) = daemon_rebuild(cli, config_path) {
+            warn!(error = %e, "daemon rebuild failed");
+            eprintln!("Error: {e}");
+        }
+    };
+
+    rebuild();
+    eprintln!(
+        "Daemon watching {} for changes (Ctrl+C to stop)",
+        config_path.display()
+    );
+    watch_file(config_path, DEFAULT_DEBOUNCE, || {
+        eprintln!();
+        eprintln!("{} changed, regenerating", config_path.display());
+        rebuild();
+    })
+}
+
+/// Load the layered configuration and run one daemon rebuild.
+fn daemon_rebuild(cli: &Cli, config_path: &Path) -> Result<()> {
+    let theme_config = load_config_with_profile(
+        Some(config_path),
+        cli.profile.as_deref(),
+        &cli.to_config_overrides(),
+    )
+    .map_err(|e| eyre!("Configuration error: {}", e))?;
+    validate_config(&theme_config).map_err(|e| eyre!("{}", e))?;
+
+    let config_dir = config_path.parent().unwrap_or(Path::new(""));
+    let daemon = cli.daemon_config(&theme_config, config_dir);
+    daemon.validate().map_err(|e| eyre!("{}", e))?;
+    if daemon.outputs.is_empty() && daemon.terminals.is_empty() {
+        bail!(
+            "--daemon has nothing to do: add [daemon] outputs or terminals, --output, or --osc-tty"
+        );
+    }
+
+    let outcome = rebuild(&theme_config, &daemon)?;
+    for warning in &outcome.warnings {
+        warn!(warning = %warning, "daemon warning");
+        eprintln!("Warning: {warning}");
+    }
+    for path in &outcome.written {
+        eprintln!("Wrote scheme to {}", path.display());
+    }
+    if !outcome.written.is_empty() {
+        let passed = validation_warnings(
+            &outcome.scheme,
+            theme_config.contrast.standard,
+            &theme_config.validation,
+        )
+        .is_empty();
+        for path in &outcome.written {
+            run_post_generate(
+                &theme_config,
+                path,
+                &outcome.scheme,
+                passed,
+                outcome.warnings.clone(),
+            )?;
+        }
+    }
+    for tty in &outcome.applied {
+        eprintln!("Applied colors to {}", tty.display());
+    }
+    Ok(())
+}
+
+/// Schemes for `preview` and `render`: the `--input` scheme, or every
+/// requested variant generated from the layered configuration.
+fn subcommand_schemes(cli: &Cli) -> Result<Vec<Base16Scheme>> {
+    if let Some(ref input_path) = cli.input {
+        let import_result = import_scheme(input_path)
+            .wrap_err_with(|| format!("Failed to import {}", input_path.display()))?;
+        for warning in &import_result.warnings {
+            eprintln!("Warning: {warning}");
+        }
+        return Ok(vec![import_result.scheme]);
+    }
+
+    let config = subcommand_config(cli)?;
+    let variants = match cli.variant {
+        VariantArg::Dark => vec![Some(SchemeVariant::Dark)],
+        VariantArg::Light => vec![Some(SchemeVariant::Light)],
+        VariantArg::Both => vec![Some(SchemeVariant::Dark), Some(SchemeVariant::Light)],
+        VariantArg::Auto => vec![None],
+        VariantArg::Linked => {
+            let linked = generate_linked(&config);
+            return Ok(vec![linked.dark.scheme, linked.light.scheme]);
+        }
+    };
+    Ok(variants
+        .into_iter()
+        .map(|variant| generate_for_variant(&config, variant).scheme)
+        .collect())
+}
+
+/// Generation settings for subcommands, from the layered configuration.
+fn subcommand_config(cli: &Cli) -> Result<GenerateConfig> {
+    let theme_config = load_config_with_profile(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        &cli.to_config_overrides(),
+    )
+    .map_err(|e| eyre!("Configuration error: {}", e))?;
+    validate_config(&theme_config).map_err(|e| eyre!("{}", e))?;
+    theme_config
+        .to_generate_config()
+        .map_err(|e| eyre!("Invalid configuration: {}", e))
+}
+
+/// Write a day/night schedule's palettes and its `schedule.toml` manifest.
+fn write_schedule(cli: &Cli, steps: usize, times: &[String], output_dir: &Path) -> Result<()> {
+    let steps = match times.len() {
+        0 => steps,
+        1 => bail!("--times needs at least 2 times, one each for the light and dark variants"),
+        n => n,
+    };
+    let schedule = generate_schedule(&subcommand_config(cli)?, steps);
+
+    std::fs::create_dir_all(output_dir)
+        .wrap_err_with(|| format!("Failed to create {}", output_dir.display()))?;
+    let extension = cli.format.extension();
+    for step in &schedule {
+        let scheme = &step.result.scheme;
+        for warning in &step.result.warnings {
+            warn!(scheme = %scheme.slug, warning = %warning, "schedule warning");
+            eprintln!("{}: {warning}", scheme.slug);
+        }
+        let path = output_dir.join(format!("{}.{extension}", scheme.slug));
+        let content = cli.format.serialize_prefixed(
+            scheme,
+            Some(&step.result.wide_palette),
+            None,
+            None,
+            None,
+            &cli.var_prefix,
+        )?;
+        write_output(cli, &path, &content, scheme)?;
+    }
+
+    let manifest = ScheduleManifest::new(&schedule, extension, times);
+    let path = output_dir.join("schedule.toml");
+    std::fs::write(&path, toml::to_string_pretty(&manifest)?)
+        .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+    eprintln!("Wrote schedule to {}", path.display());
+    Ok(())
+}
+
+/// A scored scheme in `score` output, best first.
+#[derive(Serialize)]
+struct RankedScheme {
+    rank: usize,
+    /// Scheme file, or "generated"
+    source: String,
+    #[serde(flatten)]
+    score: SchemeScore,
+}
+
+/// Score the given scheme files (or the generated scheme when there are
+/// none) against the configured thresholds, best first.
+fn rank_schemes(cli: &Cli, files: &[PathBuf]) -> Result<Vec<RankedScheme>> {
+    let schemes = if files.is_empty() {
+        subcommand_schemes(cli)?
+            .into_iter()
+            .map(|scheme| ("generated".to_string(), scheme))
+            .collect()
+    } else {
+        files
+            .iter()
+            .map(|path| Ok((path.display().to_string(), read_scheme(path)?)))
+            .collect::<Result<Vec<_>>>()?
+    };
+    let theme_config = load_config_with_profile(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        &cli.to_config_overrides(),
+    )
+    .map_err(|e| eyre!("Configuration error: {}", e))?;
+
+    let mut ranked: Vec<RankedScheme> = schemes
+        .into_iter()
+        .map(|(source, scheme)| RankedScheme {
+            rank: 0,
+            source,
+            score: score_scheme_with(&scheme, &theme_config.validation, &ScoreWeights::default()),
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.score.total_cmp(&a.score.score));
+    for (i, entry) in ranked.iter_mut().enumerate() {
+        entry.rank = i + 1;
+    }
+    Ok(ranked)
+}
+
+/// Lint results for one file in `lint --json` output.
+#[derive(Serialize)]
+struct LintedFile {
+    file: String,
+    /// Findings fixed by `--fix`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixed: Vec<LintFinding>,
+    /// Findings that remain
+    findings: Vec<LintFinding>,
+}
+
+/// Lint each file, applying fixes with `fix`; fails if any findings remain.
+fn lint_files(cli: &Cli, files: &[PathBuf], fix: bool, json: bool) -> Result<()> {
+    let theme_config = load_config_with_profile(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        &cli.to_config_overrides(),
+    )
+    .map_err(|e| eyre!("Configuration error: {}", e))?;
+    let validation = &theme_config.validation;
+    let ansi = &theme_config.ansi.clone().unwrap_or_default();
+
+    let mut linted = Vec::new();
+    for path in files {
+        let mut scheme = read_scheme(path)?;
+        let fixed = if fix {
+            fix_scheme(&mut scheme, validation, ansi)
+        } else {
+            Vec::new()
+        };
+        if !fixed.is_empty() {
+            write_fixed_scheme(cli, path, &scheme)?;
+        }
+        linted.push(LintedFile {
+            file: path.display().to_string(),
+            fixed,
+            findings: lint_scheme_with(&scheme, validation, ansi),
+        });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&linted)?);
+    } else {
+        for file in &linted {
+            if file.fixed.is_empty() && file.findings.is_empty() {
+                println!("{}: ok", file.file);
+                continue;
+            }
+            println!("{}:", file.file);
+            for finding in &file.fixed {
+                println!("  fixed {finding}");
+            }
+            for finding in &file.findings {
+                println!("  - {finding}");
+            }
+        }
+    }
+
+    let remaining: usize = linted.iter().map(|file| file.findings.len()).sum();
+    if remaining > 0 {
+        bail!("{remaining} lint findings in {} files", files.len());
+    }
+    Ok(())
+}
+
+/// Compare the accents of a dark and a light variant: the two scheme files,
+/// or the variants the generation options produce.
+fn match_variants(cli: &Cli, dark: Option<&Path>, light: Option<&Path>, json: bool) -> Result<()> {
+    let (dark, light) = match (dark, light) {
+        (Some(dark), Some(light)) => (read_scheme(dark)?, read_scheme(light)?),
+        _ => {
+            let config = subcommand_config(cli)?;
+            if cli.variant == VariantArg::Linked {
+                let linked = generate_linked(&config);
+                (linked.dark.scheme, linked.light.scheme)
+            } else {
+                (
+                    generate_for_variant(&config, Some(SchemeVariant::Dark)).scheme,
+                    generate_for_variant(&config, Some(SchemeVariant::Light)).scheme,
+                )
+            }
+        }
+    };
+    let theme_config = load_config_with_profile(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        &cli.to_config_overrides(),
+    )
+    .map_err(|e| eyre!("Configuration error: {}", e))?;
+    let slots = compare_variants(&dark, &light, &theme_config.validation.variants);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&slots)?);
+    } else {
+        println!("Dark '{}' -> light '{}':", dark.name, light.name);
+        for slot in &slots {
+            println!("  {slot}");
+        }
+    }
+
+    let diverging = slots.iter().filter(|slot| slot.diverges()).count();
+    if diverging > 0 {
+        bail!(
+            "{diverging} of {} accents diverge between the variants",
+            slots.len()
+        );
+    }
+    Ok(())
+}
+
+/// Rewrite a linted scheme file as YAML or JSON (by its extension), keeping
+/// the original as a `.bak` copy, or print it with `--dry-run`.
+fn write_fixed_scheme(cli: &Cli, path: &Path, scheme: &Base16Scheme) -> Result<()> {
+    let format = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => OutputFormat::Yaml,
+        Some("json") => OutputFormat::Json,
+        _ => bail!(
+            "--fix can only rewrite YAML or JSON schemes, not {}",
+            path.display()
+        ),
+    };
+    let content = format.serialize(scheme)?;
+    if cli.dry_run {
+        print!("{content}");
+        return Ok(());
+    }
+
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    std::fs::copy(path, &backup)
+        .wrap_err_with(|| format!("Failed to write {}", Path::new(&backup).display()))?;
+    std::fs::write(path, content)
+        .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+    eprintln!("Fixed {}", path.display());
+    Ok(())
+}
+
+/// Check each file against the schemes repository's CI rules, printing its
+/// issues; fails if any file would be rejected.
+fn publish_check(files: &[PathBuf]) -> Result<()> {
+    let mut rejected = 0;
+    for path in files {
+        let content = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        let stem = path.file_stem().and_then(|stem| stem.to_str());
+        let issues = check_publishable(&content, stem);
+        if issues.is_empty() {
+            println!("{}: ok", path.display());
+            continue;
+        }
+        rejected += 1;
+        println!("{}:", path.display());
+        for issue in issues {
+            println!("  - {issue}");
+        }
+    }
+    if rejected > 0 {
+        bail!("{rejected} of {} files would be rejected", files.len());
+    }
+    Ok(())
+}
+
+/// Upgrade a config file to the current config version: in place with a
+/// `.bak` copy, to `--output`, or to stdout with `--dry-run`.
+fn migrate_config_file(path: &Path, output: Option<&Path>, dry_run: bool) -> Result<()> {
+    let source = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    let migrated = migrate_config(&source).map_err(|e| eyre!("{}: {e}", path.display()))?;
+    if migrated.is_current() {
+        eprintln!(
+            "{} is already at config version {CONFIG_VERSION}",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    eprintln!(
+        "Upgrading {} from config version {} to {CONFIG_VERSION}",
+        path.display(),
+        migrated.from_version
+    );
+    for change in &migrated.changes {
+        eprintln!("  - {change}");
+    }
+    let content = migrated.to_toml();
+    if dry_run {
+        print!("{content}");
+        return Ok(());
+    }
+
+    let output = match output {
+        Some(output) => output.to_path_buf(),
+        None => {
+            let backup = path.with_extension("toml.bak");
+            std::fs::copy(path, &backup)
+                .wrap_err_with(|| format!("Failed to write {}", backup.display()))?;
+            eprintln!("Kept the original as {}", backup.display());
+            path.to_path_buf()
+        }
+    };
+    std::fs::write(&output, content)
+        .wrap_err_with(|| format!("Failed to write {}", output.display()))?;
+    eprintln!("Wrote {}", output.display());
+    Ok(())
+}
+
+/// Write the frames of a transition between two schemes, or print them as
+/// CSS keyframes.
+fn animate(
+    cli: &Cli,
+    from: &Path,
+    to: &Path,
+    steps: usize,
+    output_dir: &Path,
+    keyframes: bool,
+) -> Result<()> {
+    let (from, to) = (read_scheme(from)?, read_scheme(to)?);
+    let frames = interpolate_schemes(&from, &to, steps)?;
+
+    if keyframes {
+        let css = css_keyframes(&frames, &format!("{}-to-{}", from.slug, to.slug));
+        match &cli.output {
+            Some(path) => {
+                std::fs::write(path, css)
+                    .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+                eprintln!("Wrote keyframes to {}", path.display());
+            }
+            None => print!("{css}"),
+        }
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .wrap_err_with(|| format!("Failed to create {}", output_dir.display()))?;
+    for frame in &frames {
+        let path = output_dir.join(format!("{}.{}", frame.slug, cli.format.extension()));
+        let content =
+            cli.format
+                .serialize_prefixed(frame, None, None, None, None, &cli.var_prefix)?;
+        std::fs::write(&path, content)
+            .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+        eprintln!("Wrote frame to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Merge two scheme files, report what revalidation found, and print or
+/// write the result.
+fn merge(cli: &Cli, a: &Path, b: &Path, plan: &MergePlan) -> Result<()> {
+    let (a, b) = (read_scheme(a)?, read_scheme(b)?);
+    let standard: ContrastStandard = cli.contrast_standard.map(Into::into).unwrap_or_default();
+    let validation = match cli.config {
+        Some(ref path) => {
+            let config = load_config_with_profile(
+                Some(path),
+                cli.profile.as_deref(),
+                &cli.to_config_overrides(),
+            )
+            .map_err(|e| eyre!("Configuration error: {}", e))?;
+            config
+                .validation
+                .validate()
+                .map_err(|e| eyre!("Configuration error: {}", e))?;
+            config.validation
+        }
+        None => Default::default(),
+    };
+    let merged = merge_schemes(&a, &b, plan, cli.name.as_deref(), standard, &validation)?;
+
+    for breakage in &merged.breakages {
+        eprintln!("Warning: merge broke {breakage}");
+    }
+    eprintln!(
+        "Summary: {}/{} required checks pass for '{}'",
+        merged.validation.required_passed(),
+        merged.validation.required_count(),
+        merged.scheme.name
+    );
+
+    let content =
+        cli.format
+            .serialize_prefixed(&merged.scheme, None, None, None, None, &cli.var_prefix)?;
+    match cli.output {
+        Some(ref path) => write_output(cli, path, &content, &merged.scheme),
+        None => {
+            print!("{content}");
+            Ok(())
+        }
+    }
+}
+
+/// Render template repositories (given directly or matched by glob) with
+/// every requested scheme.
+fn render_templates(
+    cli: &Cli,
+    templates: &[PathBuf],
+    globs: &[String],
+    output_dir: Option<&Path>,
+) -> Result<()> {
+    let mut dirs = templates.to_vec();
+    for pattern in globs {
+        let matched = template_dirs(pattern);
+        if matched.is_empty() {
+            eprintln!("Warning: no template repositories match '{pattern}'");
+        }
+        dirs.extend(matched);
+    }
+    dirs.sort();
+    dirs.dedup();
+    if dirs.is_empty() {
+        bail!("render needs at least one template directory or --template-glob match");
+    }
+
+    let schemes = subcommand_schemes(cli)?;
+    for dir in &dirs {
+        let base = match output_dir {
+            Some(out) => out.join(dir.file_name().unwrap_or(dir.as_os_str())),
+            None => dir.clone(),
+        };
+        for scheme in &schemes {
+            let written = render_template_dir(dir, scheme, &base)
+                .wrap_err_with(|| format!("Failed to render {}", dir.display()))?;
+            for path in written {
+                eprintln!("Wrote {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sample `count` parameter sets around `theme_config`, list the best, and
+/// continue with the `pick`-th (1 is the best).
+fn explore_parameters(
+    theme_config: &mut ThemeConfig,
+    count: usize,
+    seed: Option<u64>,
+    pick: usize,
+) -> Result<()> {
+    let base = theme_config
+        .to_generate_config()
+        .map_err(|e| eyre!("Invalid configuration: {}", e))?;
+    let seed = seed.unwrap_or_else(random_seed);
+    let candidates = explore(
+        &base,
+        &theme_config.validation,
+        &theme_config.explore,
+        count,
+        seed,
+    );
+    eprintln!("Explored {count} candidates (seed {seed}):");
+    for (rank, candidate) in candidates.iter().take(EXPLORE_SHOWN.max(pick)).enumerate() {
+        let marker = if rank + 1 == pick { '>' } else { ' ' };
+        eprintln!(
+            "{marker} {:>2}. {:5.1}  {}  ({})",
+            rank + 1,
+            candidate.score.score,
+            candidate.params,
+            format_ms(candidate.elapsed)
+        );
+    }
+    let chosen = candidates.get(pick - 1).ok_or_else(|| {
+        eyre!(
+            "--explore-pick {pick} is past the {} candidates",
+            candidates.len()
+        )
+    })?;
+    chosen.params.apply_to_config(theme_config);
+    eprintln!("Rerun with --explore-seed {seed} --explore-pick <RANK> to generate another");
+    Ok(())
+}
+
+/// Seed `theme_config` with colors picked from the image at `path`.
+///
+/// Explicit `-b`/`-f` and per-slot hue overrides win over the image.
+fn apply_image_theme(cli: &Cli, theme_config: &mut ThemeConfig, path: &Path) -> Result<()> {
+    let image = load_image(path)?;
+    if image.pixels.is_empty() {
+        bail!("{} has no opaque pixels", path.display());
+    }
+    let clusters = dominant_colors(&image.pixels, DEFAULT_CLUSTERS);
+    let variant = match cli.variant {
+        VariantArg::Dark => Some(SchemeVariant::Dark),
+        VariantArg::Light => Some(SchemeVariant::Light),
+        _ => None,
+    };
+    let theme = image_theme(&clusters, variant);
+
+    eprintln!(
+        "Dominant colors of {} ({}x{}):",
+        path.display(),
+        image.width,
+        image.height
+    );
+    for cluster in &clusters {
+        eprintln!(
+            "  #{}  {:4.1}%  J' {:5.1}  M {:5.1}  h {:5.1}",
+            srgb_to_hex(cluster.color),
+            cluster.share * 100.0,
+            cluster.jmh.lightness,
+            cluster.jmh.colorfulness,
+            cluster.jmh.hue
+        );
+    }
+
+    let colors = &mut theme_config.colors;
+    if cli.background.is_none() {
+        colors.background = Some(format!("#{}", srgb_to_hex(theme.background)));
+    }
+    if cli.foreground.is_none() {
+        colors.foreground = Some(format!("#{}", srgb_to_hex(theme.foreground)));
+    }
+    let mut hues = colors
+        .hue_overrides
+        .as_ref()
+        .map(HueOverrides::to_array)
+        .unwrap_or_default();
+    for (hue, picked) in hues.iter_mut().zip(theme.hues) {
+        *hue = hue.or(picked);
+    }
+    if hues.iter().any(Option::is_some) {
+        colors.hue_overrides = Some(HueOverrides::from_array(hues));
+    }
+    Ok(())
+}
+
+/// Load the layered configuration and generate every requested variant.
+fn generate_from_config(cli: &Cli) -> Result<()> {
     // Load configuration with Figment layering: defaults < TOML file < CLI args
-    let theme_config = load_config(cli.config.as_deref(), &cli.to_config_overrides())
-        .map_err(|e| eyre!("Configuration error: {}", e))?;
+    let mut theme_config = load_config_with_profile(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        &cli.to_config_overrides(),
+    )
+    .map_err(|e| eyre!("Configuration error: {}", e))?;
+
+    if let Some(ref path) = cli.from_image {
+        apply_image_theme(cli, &mut theme_config, path)?;
+    }
 
     // Validate required fields
     validate_config(&theme_config).map_err(|e| eyre!("{}", e))?;
+    if cli.per_display && theme_config.displays.is_empty() {
+        bail!("--per-display needs at least one [displays.<name>] table in the config");
+    }
+
+    // Determine which variants to generate
+    let linked = cli.variant == VariantArg::Linked;
+    let variants_to_generate: Vec<Option<SchemeVariant>> = match cli.variant {
+        VariantArg::Dark => vec![Some(SchemeVariant::Dark)],
+        VariantArg::Light => vec![Some(SchemeVariant::Light)],
+        VariantArg::Both | VariantArg::Linked => {
+            if cli.output.is_none() && !cli.json_report {
+                let name = if linked { "linked" } else { "both" };
+                bail!("--variant {name} requires --output to specify base filename");
+            }
+            vec![Some(SchemeVariant::Dark), Some(SchemeVariant::Light)]
+        }
+        VariantArg::Auto => vec![None],
+    };
+
+    if let Some(count) = cli.explore {
+        explore_parameters(
+            &mut theme_config,
+            count.into(),
+            cli.explore_seed,
+            cli.explore_pick.into(),
+        )?;
+    }
+
+    if let Some(ref path) = cli.match_order {
+        let reference = read_scheme(path)?;
+        let order = match_accent_order(&mut theme_config, &reference, &variants_to_generate)
+            .map_err(|e| eyre!("--match-order: {}", e))?;
+        eprintln!("Accent order matched to '{}': {order}", reference.name);
+    }
+
+    if cli.max_contrast {
+        maximize_contrast(&mut theme_config, &variants_to_generate, linked)?;
+    }
 
     // Handle --save-config if specified
     if let Some(ref save_path) = cli.save_config {
@@ -129,23 +1257,66 @@ fn main() -> Result<()> {
     let config = theme_config
         .to_generate_config()
         .map_err(|e| eyre!("Invalid configuration: {}", e))?;
+    let roles_config = cli.roles_config(&theme_config);
+    let ansi_config = cli.ansi_config(&theme_config);
+    let surfaces_config = cli.surfaces_config(&theme_config);
 
-    // Determine which variants to generate
-    let variants_to_generate: Vec<Option<SchemeVariant>> = match cli.variant {
-        VariantArg::Dark => vec![Some(SchemeVariant::Dark)],
-        VariantArg::Light => vec![Some(SchemeVariant::Light)],
-        VariantArg::Both => {
-            if cli.output.is_none() {
-                bail!("--variant both requires --output to specify base filename");
-            }
-            vec![Some(SchemeVariant::Dark), Some(SchemeVariant::Light)]
+    match cli.xterm256 {
+        Some(Xterm256Mode::Comment) if cli.format != OutputFormat::Yaml => {
+            bail!("--xterm256 comment requires YAML output; use --xterm256 json instead")
         }
-        VariantArg::Auto => vec![None],
+        _ if cli.annotate && cli.format != OutputFormat::Yaml => {
+            bail!("--annotate requires YAML output")
+        }
+        Some(Xterm256Mode::Json) if cli.output.is_none() => {
+            bail!("--xterm256 json requires --output to place the table next to the scheme")
+        }
+        _ => {}
+    }
+
+    // Each variant with the configuration it was generated from
+    let generations = if linked {
+        let linked = generate_linked(&config);
+        vec![
+            (linked.dark_config, Some(SchemeVariant::Dark), linked.dark),
+            (
+                linked.light_config,
+                Some(SchemeVariant::Light),
+                linked.light,
+            ),
+        ]
+    } else {
+        // --variant both generates its variants as parallel jobs
+        let started = Instant::now();
+        let generated = map_jobs(variants_to_generate, |_, variant| {
+            let result = generate_for_variant(&config, variant.clone());
+            (variant, result)
+        });
+        if generated.len() > 1 {
+            let jobs: Vec<String> = generated
+                .iter()
+                .map(|job| format!("{} {}", job.output.1.scheme.variant, format_ms(job.elapsed)))
+                .collect();
+            eprintln!(
+                "Generated {} variants in {} ({})",
+                generated.len(),
+                format_ms(started.elapsed()),
+                jobs.join(", ")
+            );
+        }
+        generated
+            .into_iter()
+            .map(|job| {
+                let (variant, result) = job.output;
+                (config.clone(), variant, result)
+            })
+            .collect()
     };
 
-    for forced_variant in variants_to_generate {
-        let result = generate_for_variant(&config, forced_variant);
-        let scheme = result.scheme;
+    let mut reports = Vec::new();
+    let mut validation_failed = false;
+    for (config, forced_variant, result) in generations {
+        let mut scheme = result.scheme.clone();
 
         if !result.warnings.is_empty() {
             eprintln!("Generation warnings:");
@@ -155,7 +1326,11 @@ fn main() -> Result<()> {
             }
         }
 
-        let warnings = validate_with_warnings(&scheme);
+        let warnings = validation_warnings(
+            &scheme,
+            theme_config.contrast.standard,
+            &theme_config.validation,
+        );
         if !warnings.is_empty() {
             if cli.no_adjust {
                 eprintln!("Validation failed for the following color pairs:");
@@ -163,7 +1338,11 @@ fn main() -> Result<()> {
                     warn!(warning = %warning, "validation failure");
                     eprintln!("  {warning}");
                 }
-                bail!("Validation failed");
+                // The report still describes the failing scheme
+                if !cli.json_report {
+                    bail!("Validation failed");
+                }
+                validation_failed = true;
             }
             for warning in &warnings {
                 warn!(warning = %warning, "validation warning");
@@ -171,6 +1350,76 @@ fn main() -> Result<()> {
             }
         }
 
+        let metadata_issues = report_metadata(
+            &mut scheme,
+            cli.fix_metadata,
+            theme_config.theme.author.is_some(),
+        );
+
+        if cli.check_cvd {
+            report_cvd(&scheme, cli.cvd_threshold);
+        }
+
+        if cli.timings {
+            report_timings(&scheme, &result.timings);
+        }
+
+        if let Some(attrs) = theme_config.contrast.terminal {
+            report_terminal_attributes(&scheme, &attrs);
+        }
+
+        if cli.report == Some(ReportKind::ContrastMatrix) {
+            eprint!("{}", render_table(&ContrastMatrix::new(&scheme)));
+        }
+        if cli.report == Some(ReportKind::Html) {
+            let mut provenance = Provenance::new(&config, forced_variant.clone(), cli.format);
+            provenance.config.validation = theme_config.validation.clone();
+            let validation = validate_with_config(
+                &scheme,
+                &[],
+                &[],
+                theme_config.contrast.standard,
+                &theme_config.validation,
+            );
+            let html = render_html(&scheme, &validation, cli.cvd_threshold, Some(&provenance))?;
+            let report_path = match cli.output {
+                Some(ref base_path)
+                    if matches!(cli.variant, VariantArg::Both | VariantArg::Linked) =>
+                {
+                    variant_filename(base_path, &scheme.variant, cli.format)
+                }
+                Some(ref base_path) => base_path.clone(),
+                None => PathBuf::from(&scheme.slug),
+            }
+            .with_extension("report.html");
+            std::fs::write(&report_path, html)
+                .wrap_err_with(|| format!("Failed to write to {}", report_path.display()))?;
+            eprintln!("Wrote accessibility report to {}", report_path.display());
+        }
+
+        let roles = roles_config.as_ref().map(|c| derive_roles(&scheme, c));
+        let role_warnings: Vec<String> = roles.iter().flat_map(SemanticRoles::warnings).collect();
+        for warning in &role_warnings {
+            warn!(warning = %warning, "role warning");
+            eprintln!("Warning: {warning}");
+        }
+
+        if cli.watch {
+            let results = validate_with_config(
+                &scheme,
+                &[],
+                &[],
+                theme_config.contrast.standard,
+                &theme_config.validation,
+            );
+            eprintln!(
+                "Summary: {}/{} required checks pass for '{}'",
+                results.required_passed(),
+                results.required_count(),
+                scheme.name
+            );
+        }
+
         // Handle --dry-run: show validation results without generating output
         if cli.dry_run {
             let status = if warnings.is_empty() {
@@ -185,33 +1434,278 @@ fn main() -> Result<()> {
             continue;
         }
 
-        let output_content = match cli.format {
-            OutputFormat::Yaml => {
-                serde_yaml::to_string(&scheme).wrap_err("Failed to serialize scheme to YAML")?
+        let ansi = ansi_config.is_some().then(|| AnsiMap::new(&scheme));
+        let surfaces = surfaces_config
+            .as_ref()
+            .map(|c| SurfaceRamp::new(&scheme, c.steps));
+
+        if cli.json_report {
+            let json = OutputFormat::Json.serialize_with(
+                &scheme,
+                Some(&result.wide_palette),
+                roles.as_ref(),
+                ansi.as_ref(),
+                surfaces.as_ref(),
+            )?;
+            let validation = ValidationReport::new(&validate_with_config(
+                &scheme,
+                &[],
+                &[],
+                theme_config.contrast.standard,
+                &theme_config.validation,
+            ));
+            reports.push(SchemeReport {
+                scheme: serde_json::from_str(&json)?,
+                passed: validation.required_passed == validation.required,
+                validation,
+                warnings: Warnings {
+                    generation: result.warnings.clone(),
+                    validation: warnings,
+                    metadata: metadata_issues,
+                    roles: role_warnings,
+                },
+                solver: SolverReport::new(&result),
+            });
+            continue;
+        }
+
+        if cli.per_display {
+            // --per-display requires --output
+            let base_path = cli.output.as_deref().unwrap_or(Path::new(""));
+            let base_path = if matches!(cli.variant, VariantArg::Both | VariantArg::Linked) {
+                variant_filename(base_path, &scheme.variant, cli.format)
+            } else {
+                base_path.to_path_buf()
+            };
+            for (name, display) in &theme_config.displays {
+                let calibrated = display.calibrate_scheme(&scheme, name);
+                let roles = roles_config.as_ref().map(|c| derive_roles(&calibrated, c));
+                let ansi = ansi_config.is_some().then(|| AnsiMap::new(&calibrated));
+                let surfaces = surfaces_config
+                    .as_ref()
+                    .map(|c| SurfaceRamp::new(&calibrated, c.steps));
+                let mut output_content = cli.format.serialize_prefixed(
+                    &calibrated,
+                    None,
+                    roles.as_ref(),
+                    ansi.as_ref(),
+                    surfaces.as_ref(),
+                    &cli.var_prefix,
+                )?;
+                if cli.annotate {
+                    let notes = slot_notes(&calibrated, Some(&SolverReport::new(&result)));
+                    output_content = annotate_yaml(&output_content, &notes);
+                }
+                if cli.xterm256 == Some(Xterm256Mode::Comment) {
+                    output_content.push_str(&comment_block(&approximation_table_with(
+                        &calibrated,
+                        cli.xterm256_quantize.into(),
+                    )));
+                }
+                let output_path = display_filename(&base_path, name, cli.format);
+                write_output(cli, &output_path, &output_content, &calibrated)?;
+                let generation = result.warnings.iter().chain(&warnings).cloned().collect();
+                run_post_generate(
+                    &theme_config,
+                    &output_path,
+                    &calibrated,
+                    warnings.is_empty(),
+                    generation,
+                )?;
             }
-            OutputFormat::Json => serde_json::to_string_pretty(&scheme)
-                .wrap_err("Failed to serialize scheme to JSON")?,
-        };
+            continue;
+        }
+
+        let mut output_content = cli.format.serialize_prefixed(
+            &scheme,
+            Some(&result.wide_palette),
+            roles.as_ref(),
+            ansi.as_ref(),
+            surfaces.as_ref(),
+            &cli.var_prefix,
+        )?;
+        if cli.annotate {
+            let notes = slot_notes(&scheme, Some(&SolverReport::new(&result)));
+            output_content = annotate_yaml(&output_content, &notes);
+        }
+        if cli.emit_provenance {
+            let mut provenance = Provenance::new(&config, forced_variant, cli.format);
+            provenance.var_prefix = cli.var_prefix.clone();
+            provenance.config.roles = roles_config.clone();
+            provenance.config.ansi = ansi_config.clone();
+            provenance.config.surfaces = surfaces_config.clone();
+            provenance.fix_metadata = cli.fix_metadata;
+            provenance.annotate = cli.annotate;
+            provenance.xterm256_comment = cli.xterm256 == Some(Xterm256Mode::Comment);
+            provenance.xterm256_quantization = cli.xterm256_quantize.into();
+            output_content = provenance.embed(output_content)?;
+        }
+        if cli.xterm256 == Some(Xterm256Mode::Comment) {
+            output_content.push_str(&comment_block(&approximation_table_with(
+                &scheme,
+                cli.xterm256_quantize.into(),
+            )));
+        }
 
         if let Some(ref base_path) = cli.output {
-            let output_path = if matches!(cli.variant, VariantArg::Both) {
+            let output_path = if matches!(cli.variant, VariantArg::Both | VariantArg::Linked) {
                 variant_filename(base_path, &scheme.variant, cli.format)
             } else {
                 base_path.clone()
             };
 
-            info!(path = %output_path.display(), "wrote scheme");
-            std::fs::write(&output_path, &output_content)
-                .wrap_err_with(|| format!("Failed to write to {}", output_path.display()))?;
-            eprintln!("Wrote scheme to {}", output_path.display());
+            write_output(cli, &output_path, &output_content, &scheme)?;
+            let generation = result.warnings.iter().chain(&warnings).cloned().collect();
+            run_post_generate(
+                &theme_config,
+                &output_path,
+                &scheme,
+                warnings.is_empty(),
+                generation,
+            )?;
         } else {
             print!("{output_content}");
         }
     }
 
+    if cli.json_report {
+        println!("{}", serde_json::to_string_pretty(&Report::new(reports))?);
+    }
+    if validation_failed {
+        bail!("Validation failed");
+    }
+
+    Ok(())
+}
+
+/// Write a serialized scheme to `path`, with its xterm-256 table alongside
+/// under `--xterm256 json`.
+fn write_output(cli: &Cli, path: &Path, content: &str, scheme: &Base16Scheme) -> Result<()> {
+    info!(path = %path.display(), "wrote scheme");
+    std::fs::write(path, content)
+        .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+    eprintln!("Wrote scheme to {}", path.display());
+
+    if cli.xterm256 == Some(Xterm256Mode::Json) {
+        let table_path = path.with_extension("xterm256.json");
+        let table = serde_json::to_string_pretty(&approximation_table_with(
+            scheme,
+            cli.xterm256_quantize.into(),
+        ))?;
+        std::fs::write(&table_path, table + "\n")
+            .wrap_err_with(|| format!("Failed to write to {}", table_path.display()))?;
+        eprintln!("Wrote xterm-256 table to {}", table_path.display());
+    }
     Ok(())
 }
 
+/// Run the config's `post_generate` hook, if any, for a scheme written to
+/// `path`.
+fn run_post_generate(
+    theme_config: &ThemeConfig,
+    path: &Path,
+    scheme: &Base16Scheme,
+    passed: bool,
+    warnings: Vec<String>,
+) -> Result<()> {
+    let Some(ref command) = theme_config.hooks.post_generate else {
+        return Ok(());
+    };
+    info!(command = %command, path = %path.display(), "running post_generate hook");
+    run_hook(
+        command,
+        &HookSummary::post_generate(path, scheme, passed, warnings),
+    )
+}
+
+/// WCAG 2.x ratio and achieved level, e.g. "5.12:1 AA" or "2.40:1 -".
+fn format_wcag(result: &ValidationResult) -> String {
+    let level = result
+        .wcag_level
+        .map(|l| l.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    format!("{:5.2}:1 {:<3}", result.wcag_ratio, level)
+}
+
+/// Print gallery metadata issues, normalizing them first with `--fix-metadata`,
+/// and return the issues that remain. An empty author is only reported with
+/// `report_empty_author` or `--fix-metadata`.
+fn report_metadata(scheme: &mut Base16Scheme, fix: bool, report_empty_author: bool) -> Vec<String> {
+    if fix {
+        for issue in fix_metadata(scheme) {
+            info!(issue = %issue, "fixed metadata");
+            eprintln!("Fixed metadata: {issue}");
+        }
+    }
+    let issues: Vec<String> = check_generated_metadata(scheme, report_empty_author || fix)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    for issue in &issues {
+        warn!(issue = %issue, "metadata issue");
+        eprintln!("Metadata warning: {issue}");
+    }
+    issues
+}
+
+/// A duration as milliseconds, for summaries.
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1e3)
+}
+
+/// Print the time each generation stage took.
+fn report_timings(scheme: &Base16Scheme, timings: &Timings) {
+    eprintln!("Timings for '{}':", scheme.name);
+    for (stage, duration) in timings.stages() {
+        eprintln!("  {stage:<17} {:>9.2} ms", duration.as_secs_f64() * 1e3);
+    }
+    eprintln!(
+        "  {:<17} {:>9.2} ms",
+        "total",
+        timings.total().as_secs_f64() * 1e3
+    );
+}
+
+/// Print accent pairs that collapse under colorblindness simulation.
+fn report_cvd(scheme: &Base16Scheme, threshold: f32) {
+    let conflicts = check_cvd(scheme, threshold);
+    if conflicts.is_empty() {
+        eprintln!("CVD check: no accent pairs below delta E {threshold:.1}");
+        return;
+    }
+    eprintln!(
+        "CVD check: {} accent pairs below delta E {threshold:.1}:",
+        conflicts.len()
+    );
+    for conflict in &conflicts {
+        warn!(conflict = %conflict, "cvd conflict");
+        eprintln!("  {conflict}");
+    }
+}
+
+/// Print accents that drop below readable contrast as dim or bold text.
+fn report_terminal_attributes(scheme: &Base16Scheme, attrs: &TerminalAttributes) {
+    let warnings = terminal_attribute_warnings(scheme, attrs);
+    if warnings.is_empty() {
+        eprintln!(
+            "Attribute check: accents stay readable as dim (x{}) and bold (x{}) text",
+            attrs.dim, attrs.bold
+        );
+        return;
+    }
+    for warning in &warnings {
+        warn!(warning = %warning, "terminal attribute warning");
+        eprintln!("Warning: {warning}");
+    }
+}
+
+/// `<stem>-<display>.<ext>` next to `base_path`.
+fn display_filename(base_path: &Path, display: &str, format: OutputFormat) -> PathBuf {
+    let stem = base_path.file_stem().unwrap_or_default().to_string_lossy();
+    let parent = base_path.parent().unwrap_or(Path::new(""));
+    parent.join(format!("{stem}-{display}.{}", format.extension()))
+}
+
 /// Generate output filename with variant suffix and format extension.
 fn variant_filename(
     base_path: &Path,
@@ -224,10 +1718,7 @@ fn variant_filename(
         v => unreachable!("unsupported variant: {v:?}"),
     };
 
-    let ext = match format {
-        OutputFormat::Yaml => "yaml",
-        OutputFormat::Json => "json",
-    };
+    let ext = format.extension();
 
     let stem = base_path.file_stem().unwrap_or_default().to_string_lossy();
     let parent = base_path.parent().unwrap_or(Path::new(""));
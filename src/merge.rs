@@ -0,0 +1,286 @@
+//! Composing a scheme from two others (`merge`).
+//!
+//! The surfaces (base00-base07), the accents (base08-base0F), and the
+//! extended accents (base10-base17) are each taken from scheme A or B, and
+//! single slots can be cherry-picked on top. A slot missing from the chosen
+//! scheme (base10-base17 of a Base16 scheme) comes from the other one.
+//!
+//! Colors picked for one scheme's surfaces can lose their contrast on the
+//! other's, so the result is revalidated: a required pair failing in the
+//! merged scheme but passing in the scheme its foreground came from is
+//! reported as a breakage the merge introduced.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use color_eyre::eyre::{Result, bail};
+use tinted_builder::{Base16Scheme, SchemeSystem};
+
+use crate::metadata::{detect_variant, slugify};
+use crate::validation::{
+    ContrastStandard, ValidationConfig, ValidationResult, ValidationResults, validate_with_config,
+};
+
+/// Slot groups and their members, in palette order.
+const GROUPS: [(&str, [&str; 8]); 3] = [
+    (
+        "surfaces",
+        [
+            "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07",
+        ],
+    ),
+    (
+        "accents",
+        [
+            "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+        ],
+    ),
+    (
+        "extended accents",
+        [
+            "base10", "base11", "base12", "base13", "base14", "base15", "base16", "base17",
+        ],
+    ),
+];
+
+/// One of the two merged schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+impl Side {
+    fn other(self) -> Self {
+        match self {
+            Side::A => Side::B,
+            Side::B => Side::A,
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Side::A => "A",
+            Side::B => "B",
+        })
+    }
+}
+
+/// Which scheme each slot comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergePlan {
+    /// Source of base00-base07
+    pub surfaces: Side,
+    /// Source of base08-base0F
+    pub accents: Side,
+    /// Source of base10-base17
+    pub extended_accents: Side,
+    /// Single slots taken from a scheme regardless of their group
+    pub picks: BTreeMap<String, Side>,
+}
+
+impl Default for MergePlan {
+    /// Surfaces from A, every accent from B.
+    fn default() -> Self {
+        Self {
+            surfaces: Side::A,
+            accents: Side::B,
+            extended_accents: Side::B,
+            picks: BTreeMap::new(),
+        }
+    }
+}
+
+impl MergePlan {
+    /// Source of `slot` under this plan, before falling back for slots the
+    /// scheme lacks.
+    pub fn source(&self, slot: &str) -> Side {
+        if let Some(side) = self.picks.get(slot) {
+            return *side;
+        }
+        match GROUPS.iter().position(|(_, slots)| slots.contains(&slot)) {
+            Some(0) => self.surfaces,
+            Some(1) => self.accents,
+            _ => self.extended_accents,
+        }
+    }
+}
+
+/// Parse a cherry-pick: `"base0D=b"` takes base0D from scheme B.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::merge::{Side, parse_pick};
+///
+/// assert_eq!(parse_pick("base0d=B").unwrap(), ("base0D".to_string(), Side::B));
+/// assert!(parse_pick("base0D").is_err());
+/// assert!(parse_pick("base18=a").is_err());
+/// ```
+pub fn parse_pick(pick: &str) -> Result<(String, Side)> {
+    let Some((slot, side)) = pick.split_once('=') else {
+        bail!("--pick expects SLOT=a or SLOT=b (got '{pick}')");
+    };
+    let Some(slot) = GROUPS
+        .iter()
+        .flat_map(|(_, slots)| slots)
+        .find(|s| s.eq_ignore_ascii_case(slot.trim()))
+    else {
+        bail!(
+            "--pick: '{}' is not a slot (expected base00-base17)",
+            slot.trim()
+        );
+    };
+    let side = match side.trim().to_ascii_lowercase().as_str() {
+        "a" => Side::A,
+        "b" => Side::B,
+        other => bail!("--pick: '{other}' is not a scheme (expected a or b)"),
+    };
+    Ok((slot.to_string(), side))
+}
+
+/// A merged scheme and what revalidating it found.
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    pub scheme: Base16Scheme,
+    /// Scheme each slot of the result came from
+    pub sources: BTreeMap<String, Side>,
+    /// Validation of the merged scheme
+    pub validation: ValidationResults,
+    /// Required pairs the merge broke: failing here, passing in the scheme
+    /// the foreground came from
+    pub breakages: Vec<String>,
+}
+
+/// Merge `a` and `b` as `plan` says, naming the result `name` (or
+/// "A + B"), and revalidate it under `standard` and `config`.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::merge::{MergePlan, merge_schemes};
+/// use themalingadingdong::validation::{ContrastStandard, ValidationConfig};
+///
+/// let dark = generate(&GenerateConfig::default()).scheme;
+/// let other = generate(&GenerateConfig {
+///     background: Srgb::new(0, 43, 54),
+///     ..Default::default()
+/// })
+/// .scheme;
+///
+/// let merged = merge_schemes(
+///     &dark,
+///     &other,
+///     &MergePlan::default(),
+///     None,
+///     ContrastStandard::Apca,
+///     &ValidationConfig::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(merged.scheme.palette["base00"].rgb, dark.palette["base00"].rgb);
+/// assert_eq!(merged.scheme.palette["base0D"].rgb, other.palette["base0D"].rgb);
+/// ```
+pub fn merge_schemes(
+    a: &Base16Scheme,
+    b: &Base16Scheme,
+    plan: &MergePlan,
+    name: Option<&str>,
+    standard: ContrastStandard,
+    config: &ValidationConfig,
+) -> Result<MergeResult> {
+    let scheme_of = |side: Side| match side {
+        Side::A => a,
+        Side::B => b,
+    };
+
+    let mut palette = HashMap::new();
+    let mut sources = BTreeMap::new();
+    for slot in GROUPS.iter().flat_map(|(_, slots)| slots) {
+        let wanted = plan.source(slot);
+        let found = [wanted, wanted.other()]
+            .into_iter()
+            .find_map(|side| Some((side, scheme_of(side).palette.get(*slot)?)));
+        if let Some((side, color)) = found {
+            palette.insert(slot.to_string(), color.clone());
+            sources.insert(slot.to_string(), side);
+        }
+    }
+    if let Some((group, _)) = GROUPS[..2]
+        .iter()
+        .find(|(_, slots)| slots.iter().any(|slot| !palette.contains_key(*slot)))
+    {
+        bail!("neither {} nor {} has all the {group}", a.name, b.name);
+    }
+
+    let name = name.map_or_else(|| format!("{} + {}", a.name, b.name), str::to_string);
+    let mut scheme = Base16Scheme {
+        system: if palette.contains_key("base10") {
+            SchemeSystem::Base24
+        } else {
+            SchemeSystem::Base16
+        },
+        slug: slugify(&name),
+        description: Some(format!("Merged from {} and {}", a.name, b.name)),
+        name,
+        palette,
+        ..a.clone()
+    };
+    if let Some(variant) = detect_variant(&scheme) {
+        scheme.variant = variant;
+    }
+
+    let validation = validate_with_config(&scheme, &[], &[], standard, config);
+    let passing_in = |side: Side| {
+        let results = validate_with_config(scheme_of(side), &[], &[], standard, config);
+        results
+            .required
+            .into_iter()
+            .chain(results.reference)
+            .map(|r| ((r.pair.foreground, r.pair.background), r.passes))
+            .collect::<HashMap<_, _>>()
+    };
+    let passing = [passing_in(Side::A), passing_in(Side::B)];
+    let breakages = validation
+        .required
+        .iter()
+        .filter(|r| !r.passes)
+        .filter(|r| {
+            let side = sources[r.pair.foreground];
+            let passed = &passing[(side == Side::B) as usize];
+            passed
+                .get(&(r.pair.foreground, r.pair.background))
+                .copied()
+                .unwrap_or(false)
+        })
+        .map(|r| describe_breakage(r, sources[r.pair.foreground], &sources))
+        .collect();
+
+    Ok(MergeResult {
+        scheme,
+        sources,
+        validation,
+        breakages,
+    })
+}
+
+/// E.g. "base08 (from B) on base00 (from A): Lc 41.2 < 60".
+fn describe_breakage(
+    result: &ValidationResult,
+    fg_side: Side,
+    sources: &BTreeMap<String, Side>,
+) -> String {
+    let bg_side = sources
+        .get(result.pair.background)
+        .map_or_else(|| "-".to_string(), Side::to_string);
+    format!(
+        "{} (from {fg_side}) on {} (from {bg_side}): Lc {:.1} < {:.0}",
+        result.pair.foreground,
+        result.pair.background,
+        result.contrast.abs(),
+        result.pair.threshold.min_lc
+    )
+}
@@ -0,0 +1,496 @@
+//! Scheme metadata checks for tinted-theming gallery submissions.
+//!
+//! The gallery rejects schemes with empty names, slugs that are not safe
+//! filenames, malformed author fields, or a `variant` that disagrees with the
+//! palette. `check_metadata` reports these; `fix_metadata` normalizes what can
+//! be derived automatically. `check_publishable` applies the rest of the
+//! schemes repository's CI rules to a YAML file as written: required keys,
+//! palette slots, hex formats, and a file name matching the slug.
+
+use std::fmt;
+
+use palette::Srgb;
+use serde_yaml::{Mapping, Value};
+use tinted_builder::{Base16Scheme, SchemeVariant};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+use crate::generate::GenerateConfig;
+use crate::hellwig::hellwig_lightness;
+
+/// Top-level keys a scheme file must have.
+const REQUIRED_KEYS: [&str; 5] = ["system", "name", "author", "variant", "palette"];
+
+/// Top-level keys the scheme spec allows.
+const KNOWN_KEYS: [&str; 7] = [
+    "system",
+    "name",
+    "slug",
+    "author",
+    "description",
+    "variant",
+    "palette",
+];
+
+/// A metadata problem found in a scheme.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataIssue {
+    /// Scheme name is empty or whitespace
+    EmptyName,
+    /// Name has leading/trailing or repeated whitespace
+    UntidyName(String),
+    /// Slug is empty or contains characters unsafe in filenames
+    UnsafeSlug { slug: String, expected: String },
+    /// Author is empty
+    EmptyAuthor,
+    /// Author is present but malformed
+    AuthorFormat(String),
+    /// Declared variant disagrees with the palette lightness
+    VariantMismatch {
+        declared: &'static str,
+        detected: &'static str,
+    },
+}
+
+impl fmt::Display for MetadataIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "name is empty"),
+            Self::UntidyName(name) => write!(f, "name '{name}' has stray whitespace"),
+            Self::UnsafeSlug { slug, expected } => {
+                write!(
+                    f,
+                    "slug '{slug}' is not filename-safe (expected '{expected}')"
+                )
+            }
+            Self::EmptyAuthor => write!(f, "author is empty"),
+            Self::AuthorFormat(reason) => write!(f, "author {reason}"),
+            Self::VariantMismatch { declared, detected } => write!(
+                f,
+                "variant is '{declared}' but base00 is {} than base05 ({detected})",
+                if *detected == "dark" {
+                    "darker"
+                } else {
+                    "lighter"
+                }
+            ),
+        }
+    }
+}
+
+/// A reason the tinted-theming schemes repository would reject a file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublishIssue {
+    /// File is not a YAML mapping
+    Parse(String),
+    /// Required top-level key is missing
+    MissingKey(&'static str),
+    /// Top-level key the scheme spec does not define
+    UnknownKey(String),
+    /// `system` is neither base16 nor base24
+    UnknownSystem(String),
+    /// `variant` is neither dark nor light
+    UnknownVariant(String),
+    /// Palette lacks a slot the system requires
+    MissingSlot(String),
+    /// Palette has a slot the system does not define
+    ExtraSlot(String),
+    /// Palette value is not a 6-digit hex color
+    BadHex { slot: String, value: String },
+    /// File is not named after the slug
+    FileName { stem: String, expected: String },
+    /// Name, slug, author, or variant problem
+    Metadata(MetadataIssue),
+}
+
+impl fmt::Display for PublishIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(reason) => write!(f, "not a scheme: {reason}"),
+            Self::MissingKey(key) => write!(f, "missing required key '{key}'"),
+            Self::UnknownKey(key) => write!(f, "unknown key '{key}'"),
+            Self::UnknownSystem(system) => {
+                write!(f, "system '{system}' is not base16 or base24")
+            }
+            Self::UnknownVariant(variant) => {
+                write!(f, "variant '{variant}' is not dark or light")
+            }
+            Self::MissingSlot(slot) => write!(f, "palette is missing {slot}"),
+            Self::ExtraSlot(slot) => write!(f, "palette has unexpected slot {slot}"),
+            Self::BadHex { slot, value } => {
+                write!(f, "{slot} '{value}' is not a 6-digit hex color")
+            }
+            Self::FileName { stem, expected } => {
+                write!(f, "file is named '{stem}' but the slug is '{expected}'")
+            }
+            Self::Metadata(issue) => issue.fmt(f),
+        }
+    }
+}
+
+/// Derive a filename-safe slug: lowercase ASCII letters and digits separated
+/// by single hyphens. Accented letters lose their accents.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::metadata::slugify;
+///
+/// assert_eq!(slugify("Gruvbox Dark (Hard)"), "gruvbox-dark-hard");
+/// assert_eq!(slugify("  rosé_pine  "), "rose-pine");
+/// assert_eq!(slugify("Ünïcode Dark"), "unicode-dark");
+/// ```
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    // Decomposed, an accented letter is its base letter and combining marks
+    for c in name.nfd().filter(|c| !is_combining_mark(*c)) {
+        let base = match c {
+            'ß' => "ss",
+            'æ' | 'Æ' => "ae",
+            'œ' | 'Œ' => "oe",
+            'ø' | 'Ø' => "o",
+            'ł' | 'Ł' => "l",
+            'đ' | 'Đ' | 'ð' | 'Ð' => "d",
+            'þ' | 'Þ' => "th",
+            _ if c.is_ascii_alphanumeric() => {
+                slug.push(c.to_ascii_lowercase());
+                continue;
+            }
+            _ => {
+                if !slug.is_empty() && !slug.ends_with('-') {
+                    slug.push('-');
+                }
+                continue;
+            }
+        };
+        slug.push_str(base);
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+/// Variant implied by the palette: dark when base00 is darker than base05.
+pub fn detect_variant(scheme: &Base16Scheme) -> Option<SchemeVariant> {
+    let lightness = |slot: &str| {
+        let (r, g, b) = scheme.palette.get(slot)?.rgb;
+        Some(hellwig_lightness(Srgb::new(r, g, b)))
+    };
+    let (bg, fg) = (lightness("base00")?, lightness("base05")?);
+    Some(if bg < fg {
+        SchemeVariant::Dark
+    } else {
+        SchemeVariant::Light
+    })
+}
+
+/// Report metadata problems that would get a scheme rejected by the gallery.
+pub fn check_metadata(scheme: &Base16Scheme) -> Vec<MetadataIssue> {
+    let mut issues = Vec::new();
+
+    let name = scheme.name.trim();
+    if name.is_empty() {
+        issues.push(MetadataIssue::EmptyName);
+    } else if tidy(&scheme.name) != scheme.name {
+        issues.push(MetadataIssue::UntidyName(scheme.name.clone()));
+    }
+
+    let expected = slugify(&scheme.slug);
+    if scheme.slug.is_empty() || expected != scheme.slug {
+        let expected = if expected.is_empty() {
+            slugify(name)
+        } else {
+            expected
+        };
+        issues.push(MetadataIssue::UnsafeSlug {
+            slug: scheme.slug.clone(),
+            expected,
+        });
+    }
+
+    if scheme.author.trim().is_empty() {
+        issues.push(MetadataIssue::EmptyAuthor);
+    } else if let Some(reason) = author_problem(&scheme.author) {
+        issues.push(MetadataIssue::AuthorFormat(reason));
+    }
+
+    if let Some(detected) = detect_variant(scheme)
+        && variant_name(&detected) != variant_name(&scheme.variant)
+    {
+        issues.push(MetadataIssue::VariantMismatch {
+            declared: variant_name(&scheme.variant),
+            detected: variant_name(&detected),
+        });
+    }
+
+    issues
+}
+
+/// Check a scheme file against the tinted-theming schemes repository's CI
+/// rules, given its contents and file name without extension.
+///
+/// Extra top-level keys (e.g. `roles`, `ansi`, or `provenance` sections)
+/// are reported, since the repository only accepts the scheme spec's keys.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::metadata::{PublishIssue, check_publishable};
+///
+/// let yaml = "system: base16\nname: Tiny\nauthor: Me\nvariant: dark\npalette:\n  base00: '#000'\n";
+/// let issues = check_publishable(yaml, Some("tiny"));
+/// assert!(issues.contains(&PublishIssue::MissingSlot("base01".to_string())));
+/// assert!(issues.iter().any(|i| matches!(i, PublishIssue::BadHex { .. })));
+/// ```
+pub fn check_publishable(yaml: &str, file_stem: Option<&str>) -> Vec<PublishIssue> {
+    let document = match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Mapping(document)) => document,
+        Ok(_) => return vec![PublishIssue::Parse("expected a mapping".to_string())],
+        Err(e) => return vec![PublishIssue::Parse(e.to_string())],
+    };
+    let text = |key: &str| document.get(key).and_then(Value::as_str);
+    let mut issues = Vec::new();
+
+    for key in REQUIRED_KEYS {
+        if !document.contains_key(key) {
+            issues.push(PublishIssue::MissingKey(key));
+        }
+    }
+    for key in document.keys() {
+        let key = key
+            .as_str()
+            .map_or_else(|| format!("{key:?}"), str::to_string);
+        if !KNOWN_KEYS.contains(&key.as_str()) {
+            issues.push(PublishIssue::UnknownKey(key));
+        }
+    }
+
+    let slots = match text("system") {
+        Some("base16") => 16,
+        Some("base24") => 24,
+        Some(other) => {
+            issues.push(PublishIssue::UnknownSystem(other.to_string()));
+            0
+        }
+        None => 0,
+    };
+    if let Some(variant) = text("variant")
+        && !matches!(variant, "dark" | "light")
+    {
+        issues.push(PublishIssue::UnknownVariant(variant.to_string()));
+    }
+    if let Some(palette) = document.get("palette").and_then(Value::as_mapping) {
+        check_palette(palette, slots, &mut issues);
+    }
+
+    // Name, author, and variant checks need a well-formed scheme
+    if !issues.is_empty() {
+        return issues;
+    }
+    let mut scheme: Base16Scheme = match serde_yaml::from_value(Value::Mapping(document.clone())) {
+        Ok(scheme) => scheme,
+        Err(e) => return vec![PublishIssue::Parse(e.to_string())],
+    };
+    // Deserializing slugifies the slug; check the one actually written
+    scheme.slug = text("slug").map_or_else(|| slugify(&scheme.name), str::to_string);
+    issues.extend(
+        check_metadata(&scheme)
+            .into_iter()
+            .map(PublishIssue::Metadata),
+    );
+
+    if let Some(stem) = file_stem
+        && stem != scheme.slug
+    {
+        issues.push(PublishIssue::FileName {
+            stem: stem.to_string(),
+            expected: scheme.slug,
+        });
+    }
+    issues
+}
+
+/// Check palette slots and hex values; `slots` is 16 or 24 (0 when the
+/// system is unknown, which skips the slot checks).
+fn check_palette(palette: &Mapping, slots: usize, issues: &mut Vec<PublishIssue>) {
+    let expected: Vec<String> = (0..slots).map(|i| format!("base{i:02X}")).collect();
+    if slots > 0 {
+        for slot in &expected {
+            if !palette.contains_key(slot.as_str()) {
+                issues.push(PublishIssue::MissingSlot(slot.clone()));
+            }
+        }
+    }
+    for (slot, value) in palette {
+        let slot = slot
+            .as_str()
+            .map_or_else(|| format!("{slot:?}"), str::to_string);
+        if slots > 0 && !expected.contains(&slot) {
+            issues.push(PublishIssue::ExtraSlot(slot.clone()));
+        }
+        let hex = value.as_str().unwrap_or_default();
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            let value = value
+                .as_str()
+                .map_or_else(|| format!("{value:?}"), str::to_string);
+            issues.push(PublishIssue::BadHex { slot, value });
+        }
+    }
+}
+
+/// One-sentence summary of how a generated scheme was made, used as its
+/// description when none is configured.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// assert_eq!(
+///     scheme.description.as_deref(),
+///     Some("Dark Base24 scheme on #1a1a2e with #eaeaea text; accents at APCA Lc 75+, extended accents at Lc 60+")
+/// );
+/// ```
+pub fn describe_scheme(scheme: &Base16Scheme, config: &GenerateConfig) -> String {
+    let hex = |slot: &str| {
+        scheme
+            .palette
+            .get(slot)
+            .map(|color| format!("#{}", color.to_hex()))
+            .unwrap_or_default()
+    };
+    let model = &config.contrast_model;
+    let units = if model.label() == model.name() {
+        model.name().to_string()
+    } else {
+        format!("{} {}", model.name(), model.label())
+    };
+    let variant = match variant_name(&scheme.variant) {
+        "light" => "Light",
+        _ => "Dark",
+    };
+    format!(
+        "{variant} Base24 scheme on {} with {} text; accents at {units} {}+, extended accents at {} {}+",
+        hex("base00"),
+        hex("base07"),
+        config.min_contrast,
+        model.label(),
+        config.extended_min_contrast,
+    )
+}
+
+/// [`check_metadata`] for a generated scheme. Generated schemes rarely set
+/// an author, so an empty one is only reported with `report_empty_author`
+/// (when an author was given, or metadata is being fixed); `publish-check`
+/// still catches it before submission.
+pub fn check_generated_metadata(
+    scheme: &Base16Scheme,
+    report_empty_author: bool,
+) -> Vec<MetadataIssue> {
+    let mut issues = check_metadata(scheme);
+    if !report_empty_author {
+        issues.retain(|issue| *issue != MetadataIssue::EmptyAuthor);
+    }
+    issues
+}
+
+/// Normalize the metadata in place, returning the issues that were fixed.
+///
+/// Empty names are derived from the slug; slugs are re-derived from the
+/// name; whitespace in name and author is tidied; the variant is set from
+/// the palette. An empty author cannot be derived and is left as is.
+pub fn fix_metadata(scheme: &mut Base16Scheme) -> Vec<MetadataIssue> {
+    let mut fixed = Vec::new();
+    for issue in check_metadata(scheme) {
+        match issue {
+            MetadataIssue::EmptyName => {
+                scheme.name = name_from_slug(&scheme.slug);
+            }
+            MetadataIssue::UntidyName(_) => {
+                scheme.name = tidy(&scheme.name);
+            }
+            MetadataIssue::UnsafeSlug { ref expected, .. } => {
+                scheme.slug = if expected.is_empty() {
+                    slugify(&scheme.name)
+                } else {
+                    expected.clone()
+                };
+            }
+            MetadataIssue::AuthorFormat(_) => {
+                let author = tidy(&scheme.author);
+                if author_problem(&author).is_some() {
+                    continue;
+                }
+                scheme.author = author;
+            }
+            MetadataIssue::VariantMismatch { .. } => {
+                if let Some(detected) = detect_variant(scheme) {
+                    scheme.variant = detected;
+                }
+            }
+            MetadataIssue::EmptyAuthor => continue,
+        }
+        fixed.push(issue);
+    }
+    fixed
+}
+
+/// Trim and collapse internal whitespace runs to single spaces.
+fn tidy(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Title-cased name from a slug ("gruvbox-dark" -> "Gruvbox Dark").
+fn name_from_slug(slug: &str) -> String {
+    let words: Vec<String> = slug
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect();
+    if words.is_empty() {
+        "Untitled".to_string()
+    } else {
+        words.join(" ")
+    }
+}
+
+/// Describe what is wrong with a non-empty author field, if anything.
+///
+/// Accepted forms are a plain name, optionally followed by `<email>` or a
+/// `(url)`, e.g. "Jane Doe <jane@example.com>".
+fn author_problem(author: &str) -> Option<String> {
+    if author.chars().any(char::is_control) {
+        return Some("contains control characters".to_string());
+    }
+    if tidy(author) != author {
+        return Some("has stray whitespace".to_string());
+    }
+    for (open, close) in [('<', '>'), ('(', ')')] {
+        if author.matches(open).count() != author.matches(close).count() {
+            return Some(format!("has unbalanced '{open}{close}'"));
+        }
+    }
+    if let (Some(start), Some(end)) = (author.find('<'), author.find('>'))
+        && !author[start..end].contains('@')
+    {
+        return Some("has an email without '@'".to_string());
+    }
+    None
+}
+
+fn variant_name(variant: &SchemeVariant) -> &'static str {
+    match variant {
+        SchemeVariant::Dark => "dark",
+        SchemeVariant::Light => "light",
+        _ => "other",
+    }
+}
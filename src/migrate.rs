@@ -0,0 +1,219 @@
+//! Config file versions and migrations (`migrate-config`).
+//!
+//! Saved configs record the `config_version` they were written for; files
+//! without one are version 1. When keys are renamed or moved the version
+//! goes up and a migration lists the renames, so older files are upgraded
+//! as they load instead of failing validation. `migrate-config` writes the
+//! upgraded file and reports what changed.
+//!
+//! Migrations rename keys in place in the TOML document, so upgraded files
+//! keep their comments and layout.
+//!
+//! Version 2 settled the `[contrast]` key names: `target`, `extended`, and
+//! `contrast_standard` became `minimum`, `extended_minimum`, and `standard`.
+
+use toml_edit::{DocumentMut, Item, Key, TableLike, Value, value};
+
+use crate::config::ConfigError;
+
+/// Version of the config layout this build reads and writes.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Renames that upgrade a config to version `to`.
+struct Migration {
+    to: u32,
+    /// (old, new) dotted keys, applied to the root and to every profile
+    renames: &'static [(&'static str, &'static str)],
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 2,
+    renames: &[
+        ("contrast.target", "contrast.minimum"),
+        ("contrast.extended", "contrast.extended_minimum"),
+        ("contrast.contrast_standard", "contrast.standard"),
+    ],
+}];
+
+/// A config upgraded to [`CONFIG_VERSION`].
+#[derive(Debug, Clone)]
+pub struct MigratedConfig {
+    /// Version the source was written for
+    pub from_version: u32,
+    pub document: DocumentMut,
+    /// Keys that changed, e.g. `contrast.target -> contrast.minimum`;
+    /// empty when only `config_version` went up
+    pub changes: Vec<String>,
+}
+
+impl MigratedConfig {
+    /// Whether the source was already at [`CONFIG_VERSION`].
+    pub fn is_current(&self) -> bool {
+        self.from_version == CONFIG_VERSION
+    }
+
+    /// The upgraded config as TOML.
+    pub fn to_toml(&self) -> String {
+        self.document.to_string()
+    }
+}
+
+/// Upgrade a config's TOML source to [`CONFIG_VERSION`].
+///
+/// Fails on TOML syntax errors and on files from a newer version.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::migrate::{CONFIG_VERSION, migrate_config};
+///
+/// let migrated = migrate_config("[contrast]\ntarget = 80.0\n").unwrap();
+/// assert_eq!(migrated.from_version, 1);
+/// assert!(migrated.changes.contains(&"contrast.target -> contrast.minimum".to_string()));
+///
+/// let toml = migrated.to_toml();
+/// assert!(toml.contains(&format!("config_version = {CONFIG_VERSION}")));
+/// assert!(toml.contains("minimum = 80.0"));
+/// ```
+pub fn migrate_config(source: &str) -> Result<MigratedConfig, ConfigError> {
+    let mut document: DocumentMut = source
+        .parse()
+        .map_err(|e| ConfigError::InvalidValue(format!("invalid TOML: {e}")))?;
+    let from_version = match document.get("config_version").and_then(Item::as_value) {
+        None => 1,
+        Some(Value::Integer(v)) if (1..=i64::from(CONFIG_VERSION)).contains(v.value()) => {
+            *v.value() as u32
+        }
+        Some(Value::Integer(v)) if *v.value() > i64::from(CONFIG_VERSION) => {
+            return Err(ConfigError::InvalidValue(format!(
+                "config_version {} is newer than this build supports ({CONFIG_VERSION}); \
+                 upgrade themalingadingdong",
+                v.value()
+            )));
+        }
+        Some(other) => {
+            return Err(ConfigError::InvalidValue(format!(
+                "config_version must be a whole number from 1 to {CONFIG_VERSION} (got {})",
+                other.to_string().trim()
+            )));
+        }
+    };
+
+    let mut changes = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.to > from_version) {
+        for (old, new) in migration.renames {
+            rename(document.as_table_mut(), old, new, "", &mut changes);
+            if let Some(profiles) = document
+                .get_mut("profile")
+                .and_then(Item::as_table_like_mut)
+            {
+                for (name, profile) in profiles.iter_mut() {
+                    if let Some(profile) = profile.as_table_like_mut() {
+                        let prefix = format!("profile.{}.", name.get());
+                        rename(profile, old, new, &prefix, &mut changes);
+                    }
+                }
+            }
+        }
+    }
+    if from_version < CONFIG_VERSION {
+        document.insert("config_version", value(i64::from(CONFIG_VERSION)));
+    }
+
+    Ok(MigratedConfig {
+        from_version,
+        document,
+        changes,
+    })
+}
+
+/// Move the value at dotted key `old` to `new`, unless `new` is already set.
+///
+/// A key renamed within its table keeps its place and comments.
+fn rename(
+    table: &mut dyn TableLike,
+    old: &str,
+    new: &str,
+    prefix: &str,
+    changes: &mut Vec<String>,
+) {
+    let (old_parents, old_key) = split(old);
+    let (new_parents, new_key) = split(new);
+    let Some(source) = descend(table, &old_parents) else {
+        return;
+    };
+    if !source.contains_key(old_key) {
+        return;
+    }
+    if old_parents == new_parents {
+        if source.contains_key(new_key) {
+            source.remove(old_key);
+            changes.push(format!(
+                "{prefix}{old} dropped ({prefix}{new} is already set)"
+            ));
+        } else {
+            rename_in_place(source, old_key, new_key);
+            changes.push(format!("{prefix}{old} -> {prefix}{new}"));
+        }
+        return;
+    }
+
+    let Some(item) = source.remove(old_key) else {
+        return;
+    };
+    let mut target = table;
+    for parent in new_parents {
+        let entry = target.entry(parent).or_insert_with(toml_edit::table);
+        let Some(next) = entry.as_table_like_mut() else {
+            changes.push(format!(
+                "{prefix}{old} dropped ({prefix}{parent} is not a table)"
+            ));
+            return;
+        };
+        target = next;
+    }
+    if target.contains_key(new_key) {
+        changes.push(format!(
+            "{prefix}{old} dropped ({prefix}{new} is already set)"
+        ));
+        return;
+    }
+    target.insert(new_key, item);
+    changes.push(format!("{prefix}{old} -> {prefix}{new}"));
+}
+
+/// Rename `old` to `new` in `table`, keeping its position and decor.
+fn rename_in_place(table: &mut dyn TableLike, old: &str, new: &str) {
+    // Entries can't be renamed, so reinsert them all in order
+    let keys: Vec<String> = table.iter().map(|(key, _)| key.to_string()).collect();
+    let entries: Vec<(Key, Item)> = keys
+        .iter()
+        .filter_map(|key| Some((table.key(key)?.clone(), table.remove(key)?)))
+        .collect();
+    for (key, item) in entries {
+        let key = if key.get() == old {
+            Key::new(new)
+                .with_leaf_decor(key.leaf_decor().clone())
+                .with_dotted_decor(key.dotted_decor().clone())
+        } else {
+            key
+        };
+        table.entry_format(&key).or_insert(item);
+    }
+}
+
+/// The table at the dotted path `parents` below `table`.
+fn descend<'a>(table: &'a mut dyn TableLike, parents: &[&str]) -> Option<&'a mut dyn TableLike> {
+    let mut current = table;
+    for parent in parents {
+        current = current.get_mut(parent)?.as_table_like_mut()?;
+    }
+    Some(current)
+}
+
+/// Parent tables and final key of a dotted key.
+fn split(path: &str) -> (Vec<&str>, &str) {
+    let mut parts: Vec<&str> = path.split('.').collect();
+    let key = parts.pop().unwrap_or_default();
+    (parts, key)
+}
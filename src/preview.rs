@@ -0,0 +1,217 @@
+//! Terminal preview for the `preview` subcommand.
+//!
+//! Prints the palette with 24-bit escape sequences instead of entering the
+//! TUI, so a scheme can be checked over SSH or in CI logs: swatch rows for
+//! every slot, sample `ls`, `git status`, and diff output drawn on base00,
+//! and the slot behind each ANSI color 0-15. Terminals with fewer colors get
+//! the closest they can show (see `term_color`).
+
+use std::fmt::Write;
+
+use palette::Srgb;
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+use crate::ansi::ansi_slots;
+use crate::apca::apca_contrast;
+use crate::term_color::{ColorDepth, TermPalette};
+
+/// Reset all attributes.
+const RESET: &str = "\x1b[0m";
+
+/// Slot rows shown as swatches: UI ramp, accents, bright accents.
+const SWATCH_ROWS: [[&str; 8]; 3] = [
+    [
+        "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07",
+    ],
+    [
+        "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+    ],
+    [
+        "base10", "base11", "base12", "base13", "base14", "base15", "base16", "base17",
+    ],
+];
+
+/// Sample terminal output: each line is a list of (slot, text) spans.
+const SAMPLES: &[(&str, &[(&str, &str)])] = &[
+    (
+        "ls",
+        &[
+            ("base0D", "src/"),
+            ("base05", "  README.md  "),
+            ("base0B", "build.sh"),
+            ("base05", "  "),
+            ("base0C", "latest -> v1.0"),
+            ("base05", "  "),
+            ("base08", "dist.tar.gz"),
+        ],
+    ),
+    (
+        "git status",
+        &[("base05", "On branch "), ("base0E", "main")],
+    ),
+    ("", &[("base0B", "        new file:   src/preview.rs")]),
+    ("", &[("base08", "        modified:   src/main.rs")]),
+    ("", &[("base03", "        (use \"git add\" to update)")]),
+    ("git diff", &[("base0C", "@@ -12,3 +12,4 @@ fn main() {")]),
+    ("", &[("base05", "     let cli = Cli::parse();")]),
+    ("", &[("base08", "-    run(&cli)")]),
+    ("", &[("base0B", "+    let code = run(&cli);")]),
+    ("", &[("base0B", "+    std::process::exit(code)")]),
+    (
+        "",
+        &[
+            ("base0E", "     fn "),
+            ("base0D", "run"),
+            ("base05", "("),
+            ("base08", "cli"),
+            ("base05", ": &"),
+            ("base0A", "Cli"),
+            ("base05", ") "),
+            ("base03", "// entry point"),
+        ],
+    ),
+];
+
+fn color(scheme: &Base16Scheme, slot: &str) -> Option<Srgb<u8>> {
+    scheme
+        .palette
+        .get(slot)
+        .map(|c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+}
+
+/// Escape codes for colors, at the terminal's depth.
+struct Painter(TermPalette);
+
+impl Painter {
+    fn fg(&mut self, c: Srgb<u8>) -> String {
+        self.0.map(c).fg()
+    }
+
+    fn bg(&mut self, c: Srgb<u8>) -> String {
+        self.0.map(c).bg()
+    }
+}
+
+/// base00 or base07, whichever reads better on `swatch`.
+fn label_color(scheme: &Base16Scheme, swatch: Srgb<u8>) -> Srgb<u8> {
+    let dark = color(scheme, "base00").unwrap_or(Srgb::new(0, 0, 0));
+    let light = color(scheme, "base07").unwrap_or(Srgb::new(255, 255, 255));
+    if apca_contrast(dark, swatch).abs() >= apca_contrast(light, swatch).abs() {
+        dark
+    } else {
+        light
+    }
+}
+
+/// Render the full preview for `scheme` as 24-bit escape-coded text.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::preview::render_preview;
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let preview = render_preview(&scheme);
+/// assert!(preview.contains("\x1b[48;2;26;26;46m")); // base00 (#1a1a2e)
+/// assert!(preview.contains("ANSI"));
+/// ```
+pub fn render_preview(scheme: &Base16Scheme) -> String {
+    render_preview_at(scheme, ColorDepth::TrueColor)
+}
+
+/// Render the full preview for `scheme` with the colors a terminal of
+/// `depth` can show.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::preview::render_preview_at;
+/// use themalingadingdong::term_color::ColorDepth;
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let preview = render_preview_at(&scheme, ColorDepth::Ansi16);
+/// assert!(preview.contains("\x1b[40m")); // base00 as ANSI black
+/// assert!(!preview.contains("\x1b[48;2;"));
+/// ```
+pub fn render_preview_at(scheme: &Base16Scheme, depth: ColorDepth) -> String {
+    let mut paint = Painter(TermPalette::new(depth, Some(scheme)));
+    let mut out = String::new();
+    let variant = match scheme.variant {
+        SchemeVariant::Light => "light",
+        _ => "dark",
+    };
+    let _ = writeln!(out, "{} ({variant})", scheme.name);
+    out.push('\n');
+
+    // Swatch rows, two lines tall: slot name, then hex
+    for row in SWATCH_ROWS {
+        let swatches: Vec<(&str, Srgb<u8>)> = row
+            .iter()
+            .filter_map(|slot| Some((*slot, color(scheme, slot)?)))
+            .collect();
+        if swatches.is_empty() {
+            continue;
+        }
+        for line in 0..2 {
+            for (slot, c) in &swatches {
+                let text = if line == 0 {
+                    format!(" {:<7}", slot)
+                } else {
+                    format!(" {:02x}{:02x}{:02x} ", c.red, c.green, c.blue)
+                };
+                let label = label_color(scheme, *c);
+                let _ = write!(out, "{}{}{text}", paint.bg(*c), paint.fg(label));
+            }
+            let _ = writeln!(out, "{RESET}");
+        }
+    }
+    out.push('\n');
+
+    // Sample output on the scheme's own background
+    let (Some(base00), Some(base05)) = (color(scheme, "base00"), color(scheme, "base05")) else {
+        return out;
+    };
+    let width = 60;
+    for (command, spans) in SAMPLES {
+        if !command.is_empty() {
+            let prompt = format!("$ {command}");
+            let _ = writeln!(
+                out,
+                "{}{}{prompt:<width$}{RESET}",
+                paint.bg(base00),
+                paint.fg(base05)
+            );
+        }
+        let _ = write!(out, "{}", paint.bg(base00));
+        let mut len = 0;
+        for (slot, text) in *spans {
+            let c = color(scheme, slot).unwrap_or(base05);
+            len += text.chars().count();
+            let _ = write!(out, "{}{text}", paint.fg(c));
+        }
+        let _ = writeln!(out, "{}{RESET}", " ".repeat(width.saturating_sub(len)));
+    }
+    out.push('\n');
+
+    // ANSI 0-15 as a terminal using this scheme would show them
+    out.push_str("ANSI 0-15:\n");
+    for (row, slots) in ansi_slots(scheme).chunks(8).enumerate() {
+        for (i, slot) in slots.iter().enumerate() {
+            let index = row * 8 + i;
+            if let Some(c) = color(scheme, slot) {
+                let _ = write!(
+                    out,
+                    "{}{} {index:>2} {:<7}",
+                    paint.bg(c),
+                    paint.fg(label_color(scheme, c)),
+                    slot
+                );
+            }
+        }
+        let _ = writeln!(out, "{RESET}");
+    }
+
+    out
+}
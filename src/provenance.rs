@@ -0,0 +1,386 @@
+//! Provenance metadata for reproducible output (`--emit-provenance`).
+//!
+//! The effective generation settings, crate version, and checksums of the
+//! build-time lookup tables are embedded in the output, so the `reproduce`
+//! subcommand can regenerate the file and confirm it is byte-identical:
+//!
+//! - YAML and JSON get a top-level `provenance` key
+//! - CSS, SCSS, and Tailwind get a trailing
+//!   `/* themalingadingdong provenance ... */` comment
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write as _;
+
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+use tinted_builder::SchemeVariant;
+
+use crate::annotate::{annotate_yaml, slot_notes};
+use crate::ansi::AnsiMap;
+use crate::batch::forced_variant;
+use crate::cli::OutputFormat;
+use crate::config::ThemeConfig;
+use crate::generate::{GenerateConfig, generate_for_variant};
+use crate::generated::{
+    BAKED_CUSP_LUTS, CUSP_LUT, CUSP_LUT_HASH, ECCENTRICITY_CAM16_LUT, ECCENTRICITY_LUT, GAMMA_LUT,
+    GAMMA_LUT_F32, HK_HUE_LUT,
+};
+use crate::metadata::fix_metadata;
+use crate::report::SolverReport;
+use crate::roles::derive_roles;
+use crate::surfaces::SurfaceRamp;
+use crate::xterm256::{Quantization, approximation_table_with, comment_block};
+
+/// Opening of the CSS provenance comment.
+const CSS_MARKER: &str = "/* themalingadingdong provenance";
+
+/// Everything needed to regenerate an output file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Crate version that wrote the output
+    pub version: String,
+    /// Output format
+    pub format: OutputFormat,
+    /// Variable-name prefix of CSS, SCSS, and Tailwind output (`--var-prefix`)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub var_prefix: String,
+    /// Whether metadata was normalized (`--fix-metadata`)
+    #[serde(default)]
+    pub fix_metadata: bool,
+    /// Whether palette entries were commented with diagnostics (`--annotate`)
+    #[serde(default)]
+    pub annotate: bool,
+    /// Whether the xterm-256 comment block was appended (`--xterm256 comment`)
+    #[serde(default)]
+    pub xterm256_comment: bool,
+    /// How the comment block mapped slots to indices (`--xterm256-quantize`)
+    #[serde(default, skip_serializing_if = "Quantization::is_nearest")]
+    pub xterm256_quantization: Quantization,
+    /// Checksum of each build-time lookup table, by name
+    pub luts: BTreeMap<String, String>,
+    /// Effective settings, with every generation parameter spelled out
+    pub config: ThemeConfig,
+}
+
+/// 64-bit FNV-1a hash.
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Checksums of the lookup tables generated by build.rs.
+///
+/// A table built differently (e.g. by another `powf` implementation) shifts
+/// colors by a code value or two, so a mismatch explains a failed reproduction.
+/// Cusp tables baked for extra viewing conditions are listed as
+/// `CUSP_LUT[surround L_A white]`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generated::BAKED_CUSP_LUTS;
+/// use themalingadingdong::provenance::lut_checksums;
+///
+/// let luts = lut_checksums();
+/// assert_eq!(luts.len(), 6 + BAKED_CUSP_LUTS.len());
+/// assert_eq!(luts["GAMMA_LUT"].len(), 16);
+/// ```
+pub fn lut_checksums() -> BTreeMap<String, String> {
+    let f64s = |lut: &[f64]| fnv1a(lut.iter().flat_map(|v| v.to_le_bytes()));
+    let f32s = |lut: &[f32]| fnv1a(lut.iter().flat_map(|v| v.to_le_bytes()));
+    let cusps = |lut: &[(f32, f32)]| {
+        fnv1a(
+            lut.iter()
+                .flat_map(|(j, m)| j.to_le_bytes().into_iter().chain(m.to_le_bytes())),
+        )
+    };
+    let baked = BAKED_CUSP_LUTS.iter().map(|lut| {
+        (
+            format!(
+                "CUSP_LUT[{} {} {}]",
+                lut.surround, lut.adapting_luminance, lut.white_point
+            ),
+            cusps(&lut.cusps),
+        )
+    });
+    [
+        ("GAMMA_LUT", f64s(&GAMMA_LUT)),
+        ("GAMMA_LUT_F32", f64s(&GAMMA_LUT_F32)),
+        ("ECCENTRICITY_LUT", f32s(&ECCENTRICITY_LUT)),
+        ("ECCENTRICITY_CAM16_LUT", f32s(&ECCENTRICITY_CAM16_LUT)),
+        ("HK_HUE_LUT", f32s(&HK_HUE_LUT)),
+        ("CUSP_LUT", cusps(&CUSP_LUT)),
+    ]
+    .into_iter()
+    .map(|(name, sum)| (name.to_string(), sum))
+    .chain(baked)
+    .map(|(name, sum)| (name, format!("{sum:016x}")))
+    .collect()
+}
+
+/// Human-readable summary of the lookup tables this binary was built with,
+/// for `--print-lut-info`.
+///
+/// Lists each table's checksum and the viewing conditions cusp tables were
+/// baked for, with the parameter hash build.rs caches them under.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::provenance::lut_info;
+///
+/// let info = lut_info();
+/// assert!(info.contains("CUSP_LUT"));
+/// assert!(info.contains("average, 4.07 cd/m², d65"));
+/// ```
+pub fn lut_info() -> String {
+    let mut info = String::from("Lookup tables:\n");
+    for (name, sum) in lut_checksums() {
+        let _ = writeln!(info, "  {name:<32} {sum}");
+    }
+
+    let default = crate::viewing::ViewingConditions::default();
+    info.push_str("\nCusp tables (sRGB gamut):\n");
+    let _ = writeln!(
+        info,
+        "  {}, {:.2} cd/m², {} (default)  hash {CUSP_LUT_HASH}",
+        default.surround.name(),
+        default.adapting_luminance,
+        default.white_point.name()
+    );
+    for lut in BAKED_CUSP_LUTS {
+        let _ = writeln!(
+            info,
+            "  {}, {:.2} cd/m², {}  hash {}",
+            lut.surround, lut.adapting_luminance, lut.white_point, lut.hash
+        );
+    }
+    info.push_str(
+        "\nOther viewing conditions compute their cusps on first use; gamuts other \
+         than sRGB are searched at runtime.\n\
+         Bake more conditions with THEMALINGADINGDONG_LUT_CONDITIONS at build time.\n",
+    );
+    info
+}
+
+/// Wrapper giving the provenance its top-level key in YAML.
+#[derive(Serialize, Deserialize)]
+struct Document<T> {
+    provenance: T,
+}
+
+impl Provenance {
+    /// Provenance for `config` generated as `variant` (`None` for auto) in
+    /// `format`, stamped with this build's version and lookup tables.
+    ///
+    /// Output options that change the file (roles, the variable prefix,
+    /// metadata fixes, the xterm-256 comment) are set on the returned value.
+    pub fn new(
+        config: &GenerateConfig,
+        variant: Option<SchemeVariant>,
+        format: OutputFormat,
+    ) -> Self {
+        let mut config = ThemeConfig::from_generate_config(config);
+        config.theme.variant = match variant {
+            Some(SchemeVariant::Light) => Some("light".to_string()),
+            Some(_) => Some("dark".to_string()),
+            None => None,
+        };
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            format,
+            var_prefix: String::new(),
+            fix_metadata: false,
+            annotate: false,
+            xterm256_comment: false,
+            xterm256_quantization: Quantization::Nearest,
+            luts: lut_checksums(),
+            config,
+        }
+    }
+
+    /// Add this provenance to `output`, a scheme serialized in `self.format`.
+    ///
+    /// Call after annotating and before appending the xterm-256 comment block.
+    pub fn embed(&self, mut output: String) -> Result<String> {
+        match self.format {
+            OutputFormat::Yaml => {
+                let section = serde_yaml::to_string(&Document { provenance: self })
+                    .wrap_err("Failed to serialize provenance to YAML")?;
+                output.push_str(&section);
+            }
+            OutputFormat::Json => {
+                // Splice in as the last key, keeping the scheme's key order
+                let body = output
+                    .trim_end()
+                    .strip_suffix('}')
+                    .ok_or_else(|| eyre!("JSON output is not an object"))?
+                    .trim_end()
+                    .to_string();
+                let section = serde_json::to_string_pretty(self)
+                    .wrap_err("Failed to serialize provenance to JSON")?;
+                output = format!(
+                    "{body},\n  \"provenance\": {}\n}}",
+                    section.replace('\n', "\n  ")
+                );
+            }
+            OutputFormat::Css | OutputFormat::Scss | OutputFormat::Tailwind => {
+                let section = serde_json::to_string_pretty(self)
+                    .wrap_err("Failed to serialize provenance to JSON")?;
+                output.push_str(&format!("\n{CSS_MARKER}\n{section}\n*/\n"));
+            }
+        }
+        Ok(output)
+    }
+
+    /// Read the provenance embedded in an output file's contents.
+    pub fn extract(content: &str) -> Result<Self> {
+        if let Some(start) = content.find(CSS_MARKER) {
+            let body = &content[start + CSS_MARKER.len()..];
+            let end = body
+                .find("*/")
+                .ok_or_else(|| eyre!("unterminated provenance comment"))?;
+            return serde_json::from_str(&body[..end]).wrap_err("Failed to parse provenance");
+        }
+        // JSON is also YAML
+        let document: Document<Option<Self>> =
+            serde_yaml::from_str(content).wrap_err("Failed to parse provenance")?;
+        document
+            .provenance
+            .ok_or_else(|| eyre!("no provenance found (generate with --emit-provenance)"))
+    }
+
+    /// Regenerate the output file this provenance describes, provenance included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::cli::OutputFormat;
+    /// use themalingadingdong::generate::GenerateConfig;
+    /// use themalingadingdong::provenance::Provenance;
+    ///
+    /// let provenance = Provenance::new(&GenerateConfig::default(), None, OutputFormat::Yaml);
+    /// let output = provenance.regenerate().unwrap();
+    ///
+    /// assert!(output.contains("provenance:"));
+    /// assert_eq!(Provenance::extract(&output).unwrap().regenerate().unwrap(), output);
+    /// ```
+    pub fn regenerate(&self) -> Result<String> {
+        let config = self
+            .config
+            .to_generate_config()
+            .map_err(|e| eyre!("Invalid configuration: {}", e))?;
+        let variant = forced_variant(self.config.theme.variant.as_deref())?;
+
+        let result = generate_for_variant(&config, variant);
+        let solver = SolverReport::new(&result);
+        let mut scheme = result.scheme;
+        if self.fix_metadata {
+            fix_metadata(&mut scheme);
+        }
+        let roles = self.config.roles.as_ref().map(|c| derive_roles(&scheme, c));
+        let ansi = self.config.ansi.is_some().then(|| AnsiMap::new(&scheme));
+        let surfaces = self
+            .config
+            .surfaces
+            .as_ref()
+            .map(|c| SurfaceRamp::new(&scheme, c.steps));
+
+        let mut output = self.format.serialize_prefixed(
+            &scheme,
+            Some(&result.wide_palette),
+            roles.as_ref(),
+            ansi.as_ref(),
+            surfaces.as_ref(),
+            &self.var_prefix,
+        )?;
+        if self.annotate {
+            output = annotate_yaml(&output, &slot_notes(&scheme, Some(&solver)));
+        }
+        let mut output = self.embed(output)?;
+        if self.xterm256_comment {
+            output.push_str(&comment_block(&approximation_table_with(
+                &scheme,
+                self.xterm256_quantization,
+            )));
+        }
+        Ok(output)
+    }
+}
+
+/// Outcome of regenerating an output file from its provenance.
+#[derive(Debug, Clone)]
+pub struct Reproduction {
+    /// Version recorded in the file
+    pub recorded_version: String,
+    /// Lookup tables whose checksum differs from this build's
+    pub lut_mismatches: Vec<String>,
+    /// First line (1-based) where the regenerated output differs, if any
+    pub first_difference: Option<usize>,
+}
+
+impl Reproduction {
+    /// Whether the regenerated output matched byte for byte.
+    pub fn is_identical(&self) -> bool {
+        self.first_difference.is_none()
+    }
+}
+
+impl fmt::Display for Reproduction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Recorded with themalingadingdong {} (this build: {})",
+            self.recorded_version,
+            env!("CARGO_PKG_VERSION")
+        )?;
+        if self.lut_mismatches.is_empty() {
+            writeln!(f, "Lookup tables: match")?;
+        } else {
+            writeln!(
+                f,
+                "Lookup tables: differ ({})",
+                self.lut_mismatches.join(", ")
+            )?;
+        }
+        match self.first_difference {
+            None => writeln!(f, "Output: byte-identical"),
+            Some(line) => writeln!(f, "Output: differs from line {line}"),
+        }
+    }
+}
+
+/// Regenerate `content` (an output file written with `--emit-provenance`)
+/// and compare the result byte for byte.
+pub fn reproduce(content: &str) -> Result<Reproduction> {
+    let provenance = Provenance::extract(content)?;
+    let regenerated = provenance.regenerate()?;
+
+    let current = lut_checksums();
+    let lut_mismatches = provenance
+        .luts
+        .iter()
+        .filter(|(name, sum)| current.get(*name) != Some(*sum))
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    let first_difference = (regenerated != content).then(|| {
+        let mut expected = regenerated.lines();
+        let mut actual = content.lines();
+        let mut line = 1;
+        loop {
+            match (expected.next(), actual.next()) {
+                (Some(e), Some(a)) if e == a => line += 1,
+                _ => break line,
+            }
+        }
+    });
+
+    Ok(Reproduction {
+        recorded_version: provenance.version,
+        lut_mismatches,
+        first_difference,
+    })
+}
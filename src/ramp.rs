@@ -0,0 +1,141 @@
+//! Lightness ramp designer for the UI slots (base01-base06).
+//!
+//! By default the surfaces between base00 and base07 sit wherever the
+//! lightness curve puts them. A ramp instead gives some (or all) of them a
+//! target APCA contrast against base00, e.g. base01 at Lc 10 for a barely
+//! raised surface and base02 at Lc 25 for selections. Each targeted slot
+//! keeps the curve's colorfulness and hue and has its J' solved for the
+//! target; untargeted slots stay on the curve.
+
+use palette::Srgb;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+use crate::interpolation::tune_contrast;
+
+/// Slots a ramp can target, in order.
+pub const RAMP_SLOTS: [&str; 6] = ["base01", "base02", "base03", "base04", "base05", "base06"];
+
+/// Largest usable target (APCA tops out around Lc 106).
+pub const MAX_RAMP_LC: f64 = 106.0;
+
+/// Allowed miss before a slot is reported as off target (Lc).
+const RAMP_TOLERANCE: f64 = 0.5;
+
+/// Target contrast of base01-base06 against base00 (absolute Lc).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LightnessRamp {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base01: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base02: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base03: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base04: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base05: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base06: Option<f64>,
+}
+
+impl LightnessRamp {
+    /// Build a ramp from per-slot targets (base01 first).
+    pub fn from_targets(targets: [Option<f64>; 6]) -> Self {
+        let [base01, base02, base03, base04, base05, base06] = targets;
+        Self {
+            base01,
+            base02,
+            base03,
+            base04,
+            base05,
+            base06,
+        }
+    }
+
+    /// Per-slot targets, base01 first.
+    pub fn targets(&self) -> [Option<f64>; 6] {
+        [
+            self.base01,
+            self.base02,
+            self.base03,
+            self.base04,
+            self.base05,
+            self.base06,
+        ]
+    }
+
+    /// Whether no slot has a target (every surface follows the curve).
+    pub fn is_empty(&self) -> bool {
+        self.targets().iter().all(Option::is_none)
+    }
+
+    /// Check that targets are usable and increase from base01 to base06.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut previous: Option<(&str, f64)> = None;
+        for (slot, target) in RAMP_SLOTS.iter().zip(self.targets()) {
+            let Some(target) = target else { continue };
+            if !(target > 0.0 && target <= MAX_RAMP_LC) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "ramp.{slot}: Lc {target} must be in (0, {MAX_RAMP_LC:.0}]"
+                )));
+            }
+            if let Some((prev_slot, prev)) = previous
+                && target <= prev
+            {
+                return Err(ConfigError::InvalidValue(format!(
+                    "ramp.{slot}: Lc {target} must exceed ramp.{prev_slot} (Lc {prev})"
+                )));
+            }
+            previous = Some((slot, target));
+        }
+        Ok(())
+    }
+}
+
+/// Move each targeted slot of `ui_colors` (base00-base07) to its ramp
+/// contrast against `ui_colors[0]`.
+///
+/// J' is searched between base00 and base07, so targets beyond base07's
+/// own contrast stop there. Returns a warning for each slot that misses its
+/// target.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::apca::apca_contrast;
+/// use themalingadingdong::curves::InterpolationConfig;
+/// use themalingadingdong::interpolation::{interpolate_with_curves, srgb_to_u8};
+/// use themalingadingdong::ramp::{LightnessRamp, solve_ramp};
+///
+/// let bg = Srgb::new(0.1f32, 0.1, 0.12);
+/// let fg = Srgb::new(0.9f32, 0.9, 0.88);
+/// let mut ui = interpolate_with_curves(bg, fg, 8, &InterpolationConfig::default());
+///
+/// let ramp = LightnessRamp {
+///     base01: Some(10.0),
+///     base02: Some(25.0),
+///     ..LightnessRamp::default()
+/// };
+/// assert!(solve_ramp(&mut ui, &ramp).is_empty());
+///
+/// let lc = apca_contrast(srgb_to_u8(ui[2]), srgb_to_u8(ui[0])).abs();
+/// assert!((lc - 25.0).abs() <= 0.5);
+/// ```
+pub fn solve_ramp(ui_colors: &mut [Srgb<f32>], ramp: &LightnessRamp) -> Vec<String> {
+    let (background, foreground) = (ui_colors[0], ui_colors[ui_colors.len() - 1]);
+    let mut warnings = Vec::new();
+    for (i, (slot, target)) in RAMP_SLOTS.iter().zip(ramp.targets()).enumerate() {
+        let Some(target) = target else { continue };
+        let (color, achieved) = tune_contrast(ui_colors[i + 1], background, foreground, target);
+        ui_colors[i + 1] = color;
+        if (achieved - target).abs() > RAMP_TOLERANCE {
+            warnings.push(format!(
+                "{slot}: Lc {achieved:.1} misses ramp target {target:.0}"
+            ));
+        }
+    }
+    warnings
+}
@@ -0,0 +1,215 @@
+//! Render tinted-theming template repositories for the `render` subcommand.
+//!
+//! A template repository has a `templates/config.yaml` listing its
+//! templates, each rendered from `templates/<name>.mustache`:
+//!
+//! ```yaml
+//! default:
+//!   filename: "colors/{{ scheme-system }}-{{ scheme-slug }}.conf"
+//!   supported-systems: [base16, base24]
+//! ```
+//!
+//! Older repositories give `extension` and `output` instead of `filename`;
+//! those outputs are named `<output>/<system>-<slug><extension>`.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use color_eyre::eyre::{Result, WrapErr, bail, eyre};
+use serde::Deserialize;
+use tinted_builder::{Base16Scheme, Scheme, SchemeSystem, Template};
+use tracing::info;
+
+/// Palette slots a Base16 template can use.
+const BASE16_SLOTS: [&str; 16] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07", "base08",
+    "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+];
+
+/// One entry of a template repository's `templates/config.yaml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TemplateEntry {
+    filename: Option<String>,
+    extension: Option<String>,
+    output: Option<String>,
+    supported_systems: Option<Vec<SchemeSystem>>,
+}
+
+/// The templates listed in `dir/templates/config.yaml`, by name.
+fn template_entries(dir: &Path) -> Result<BTreeMap<String, TemplateEntry>> {
+    let path = dir.join("templates").join("config.yaml");
+    let content = std::fs::read_to_string(&path)
+        .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    serde_yaml::from_str(&content).wrap_err_with(|| format!("Failed to parse {}", path.display()))
+}
+
+/// `scheme` as seen by a template supporting `systems`, or `None` if it
+/// supports neither Base24 nor Base16.
+///
+/// Generated schemes are Base24; Base16-only templates get the first 16
+/// slots with the system reported as `base16`.
+fn scheme_for(scheme: &Base16Scheme, systems: &[SchemeSystem]) -> Option<Scheme> {
+    if systems.contains(&SchemeSystem::Base24) {
+        return Some(Scheme::Base24(scheme.clone()));
+    }
+    if !systems.contains(&SchemeSystem::Base16) {
+        return None;
+    }
+    let mut base16 = scheme.clone();
+    base16.system = SchemeSystem::Base16;
+    base16
+        .palette
+        .retain(|slot, _| BASE16_SLOTS.contains(&slot.as_str()));
+    Some(Scheme::Base16(base16))
+}
+
+/// Render every template in the repository at `dir` with `scheme`, writing
+/// the outputs under `output_base`. Returns the files written.
+///
+/// Templates that support neither Base16 nor Base24 are skipped. Rendered
+/// filenames must stay inside `output_base`.
+pub fn render_template_dir(
+    dir: &Path,
+    scheme: &Base16Scheme,
+    output_base: &Path,
+) -> Result<Vec<PathBuf>> {
+    if scheme.slug.is_empty() {
+        bail!("rendering templates requires a scheme name (--name or [theme] name)");
+    }
+
+    let mut written = Vec::new();
+    for (name, entry) in template_entries(dir)? {
+        let systems = entry
+            .supported_systems
+            .clone()
+            .unwrap_or_else(|| vec![SchemeSystem::Base16]);
+        let Some(view) = scheme_for(scheme, &systems) else {
+            info!(template = %name, "skipping template without base16/base24 support");
+            continue;
+        };
+
+        let template_path = dir.join("templates").join(format!("{name}.mustache"));
+        let content = std::fs::read_to_string(&template_path)
+            .wrap_err_with(|| format!("Failed to read {}", template_path.display()))?;
+        let rendered = Template::new(content, view.clone())
+            .render()
+            .map_err(|e| eyre!("{}: {}", template_path.display(), e))?;
+
+        let relative = match entry.filename {
+            Some(ref filename) => PathBuf::from(
+                Template::new(filename.clone(), view.clone())
+                    .render()
+                    .map_err(|e| eyre!("{name}: filename: {e}"))?,
+            ),
+            None => {
+                let system = view.get_scheme_system();
+                Path::new(entry.output.as_deref().unwrap_or(".")).join(format!(
+                    "{system}-{}{}",
+                    scheme.slug,
+                    entry.extension.as_deref().unwrap_or_default()
+                ))
+            }
+        };
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            bail!(
+                "{name}: output '{}' escapes {}",
+                relative.display(),
+                output_base.display()
+            );
+        }
+
+        let path = output_base.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, rendered)
+            .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+        info!(template = %name, path = %path.display(), "rendered template");
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters
+/// and `?` any single character.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::render::glob_match;
+///
+/// assert!(glob_match("base16-*", "base16-vim"));
+/// assert!(glob_match("tinted-?erm", "tinted-term"));
+/// assert!(!glob_match("base16-*", "base24-vim"));
+/// ```
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Classic wildcard matching, backtracking to the last `*`
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((sp, sn)) = star {
+            p = sp + 1;
+            n = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Template repositories matching `pattern`, sorted.
+///
+/// Wildcards may appear in any path component; only directories that
+/// contain `templates/config.yaml` are returned.
+pub fn template_dirs(pattern: &str) -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        let part = component.as_os_str().to_string_lossy();
+        if !part.contains(['*', '?']) {
+            for candidate in &mut candidates {
+                candidate.push(component);
+            }
+            continue;
+        }
+        let mut next = Vec::new();
+        for candidate in &candidates {
+            let dir = if candidate.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                candidate.as_path()
+            };
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if glob_match(&part, &name) && entry.path().is_dir() {
+                    next.push(candidate.join(name));
+                }
+            }
+        }
+        candidates = next;
+    }
+
+    let mut dirs: Vec<PathBuf> = candidates
+        .into_iter()
+        .filter(|dir| dir.join("templates").join("config.yaml").is_file())
+        .collect();
+    dirs.sort();
+    dirs
+}
@@ -0,0 +1,238 @@
+//! Machine-readable generation reports (`--json-report`).
+//!
+//! A report is one JSON document holding every generated scheme with its
+//! full validation results, warnings, and accent solver diagnostics, for
+//! build pipelines that post-process the output. `schema_version` is bumped
+//! whenever a field is renamed or removed; new fields may appear without a
+//! bump.
+
+use serde::Serialize;
+
+use crate::generate::GenerationResult;
+use crate::interpolation::AccentResult;
+use crate::semantic_pairs::SemanticPairResult;
+use crate::validation::{ContrastStandard, TextUsage, ValidationResult, ValidationResults};
+
+/// Version of the report layout.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Top-level `--json-report` document.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub schema_version: u32,
+    /// Crate version that wrote the report
+    pub version: &'static str,
+    /// One entry per generated variant
+    pub schemes: Vec<SchemeReport>,
+}
+
+impl Report {
+    /// A report for `schemes` at the current schema version.
+    pub fn new(schemes: Vec<SchemeReport>) -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            version: env!("CARGO_PKG_VERSION"),
+            schemes,
+        }
+    }
+}
+
+/// One generated scheme and everything known about it.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemeReport {
+    /// The scheme as `--format json` writes it
+    pub scheme: serde_json::Value,
+    /// Whether every required check passes
+    pub passed: bool,
+    pub validation: ValidationReport,
+    pub warnings: Warnings,
+    pub solver: SolverReport,
+}
+
+/// Warnings raised while generating and checking a scheme.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Warnings {
+    /// Accents that missed their contrast target, and semantic conflicts
+    pub generation: Vec<String>,
+    /// Required checks that failed
+    pub validation: Vec<String>,
+    /// Gallery metadata issues
+    pub metadata: Vec<String>,
+    /// Semantic role conflicts (with `--roles`)
+    pub roles: Vec<String>,
+}
+
+/// Contrast results for a scheme, required checks first.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub standard: ContrastStandard,
+    /// Number of required checks, contrast and semantic
+    pub required: usize,
+    /// Number of required checks that pass
+    pub required_passed: usize,
+    pub checks: Vec<Check>,
+    /// Semantic pairs from `[validation.semantic]` (all required)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub semantic: Vec<SemanticCheck>,
+}
+
+impl ValidationReport {
+    pub fn new(results: &ValidationResults) -> Self {
+        let checks = results
+            .required
+            .iter()
+            .map(|r| Check::new(r, true))
+            .chain(results.reference.iter().map(|r| Check::new(r, false)))
+            .collect();
+        Self {
+            standard: results.standard,
+            required: results.required_count(),
+            required_passed: results.required_passed(),
+            checks,
+            semantic: results.semantic.iter().map(SemanticCheck::new).collect(),
+        }
+    }
+}
+
+/// The worst case of a semantic pair that must stay distinguishable.
+#[derive(Debug, Clone, Serialize)]
+pub struct SemanticCheck {
+    pub first: &'static str,
+    pub second: &'static str,
+    /// Simulated deficiency of the worst case, or `normal`
+    pub vision: &'static str,
+    /// Hue difference (degrees), absent when either color is near-gray
+    pub hue_difference: Option<f32>,
+    /// J' difference
+    pub lightness_difference: f32,
+    pub passes: bool,
+}
+
+impl SemanticCheck {
+    pub fn new(result: &SemanticPairResult) -> Self {
+        Self {
+            first: result.first,
+            second: result.second,
+            vision: result.vision.map_or("normal", |kind| kind.name()),
+            hue_difference: result.separation.hue,
+            lightness_difference: result.separation.lightness,
+            passes: result.passes,
+        }
+    }
+}
+
+/// One contrast check of a foreground slot on a background slot.
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub foreground: &'static str,
+    pub background: &'static str,
+    /// APCA contrast (Lc, signed)
+    pub contrast: f64,
+    /// Minimum |Lc| the check requires
+    pub min_contrast: f64,
+    pub wcag_ratio: f64,
+    /// Smallest text the APCA Lc is fit for
+    pub usage: TextUsage,
+    pub passes: bool,
+    pub required: bool,
+}
+
+impl Check {
+    pub fn new(result: &ValidationResult, required: bool) -> Self {
+        Self {
+            foreground: result.pair.foreground,
+            background: result.pair.background,
+            contrast: result.contrast,
+            min_contrast: result.pair.threshold.min_lc,
+            wcag_ratio: result.wcag_ratio,
+            usage: result.usage,
+            passes: result.passes,
+            required,
+        }
+    }
+}
+
+/// What the accent solver produced for each accent, and how long each
+/// generation stage took.
+#[derive(Debug, Clone, Serialize)]
+pub struct SolverReport {
+    /// base08-base17, in slot order
+    pub accents: Vec<AccentDiagnostics>,
+    /// Generation stages in pipeline order
+    pub timings: Vec<StageTiming>,
+}
+
+impl SolverReport {
+    pub fn new(result: &GenerationResult) -> Self {
+        let base = result
+            .base_accent_results
+            .iter()
+            .enumerate()
+            .map(|(i, r)| AccentDiagnostics::new(format!("base0{:X}", 8 + i), r));
+        let extended = result
+            .extended_accent_results
+            .iter()
+            .enumerate()
+            .map(|(i, r)| AccentDiagnostics::new(format!("base1{i:X}"), r));
+        Self {
+            accents: base.chain(extended).collect(),
+            timings: result
+                .timings
+                .stages()
+                .into_iter()
+                .map(|(stage, duration)| StageTiming {
+                    stage,
+                    ms: duration.as_secs_f64() * 1e3,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Time spent in one generation stage.
+#[derive(Debug, Clone, Serialize)]
+pub struct StageTiming {
+    pub stage: &'static str,
+    pub ms: f64,
+}
+
+/// Solver outcome for one accent slot.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccentDiagnostics {
+    pub slot: String,
+    /// Hue (degrees)
+    pub hue: f32,
+    /// Lightness the solver chose (J')
+    pub lightness: f32,
+    /// Lightness after gamut mapping (J')
+    pub post_clamp_lightness: f32,
+    /// Colorfulness after gamut mapping (M)
+    pub colorfulness: f32,
+    /// Distance of `post_clamp_lightness` from the accents' target J'
+    pub j_deviation: f32,
+    /// APCA contrast on base00 (Lc)
+    pub contrast: f64,
+    pub met_minimum: bool,
+    pub gamut_mapped: bool,
+    /// Whether M stayed within its bounds after gamut mapping
+    pub m_in_bounds: bool,
+    pub warning: Option<String>,
+}
+
+impl AccentDiagnostics {
+    fn new(slot: String, result: &AccentResult) -> Self {
+        Self {
+            slot,
+            hue: result.hue,
+            lightness: result.lightness,
+            post_clamp_lightness: result.post_clamp_lightness,
+            colorfulness: result.jmh.colorfulness,
+            j_deviation: result.j_deviation,
+            contrast: result.achieved_contrast,
+            met_minimum: result.met_minimum,
+            gamut_mapped: result.was_gamut_mapped,
+            m_in_bounds: result.m_in_bounds,
+            warning: result.warning.clone(),
+        }
+    }
+}
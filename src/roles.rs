@@ -0,0 +1,258 @@
+//! Semantic color roles derived from the generated palette.
+//!
+//! Templates often need colors by meaning (error, selection background,
+//! cursor) rather than by slot. Each role lists candidate slots in order of
+//! preference; the first one that meets the role's contrast requirement is
+//! used, so roles stay readable even when a preferred accent is weak.
+
+use std::fmt::Write;
+
+use palette::Srgb;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use tinted_builder::Base16Scheme;
+
+use crate::apca::apca_contrast;
+use crate::config::ConfigError;
+
+/// Candidate slots for each semantic role, plus the contrast they must meet.
+///
+/// Text roles (error through diff_removed) are measured as foreground on
+/// base00, `selection_bg` as the background behind base05 text, and `cursor`
+/// against base00.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RolesConfig {
+    pub error: Vec<String>,
+    pub warning: Vec<String>,
+    pub success: Vec<String>,
+    pub info: Vec<String>,
+    pub diff_added: Vec<String>,
+    pub diff_removed: Vec<String>,
+    pub selection_bg: Vec<String>,
+    pub cursor: Vec<String>,
+    /// Minimum APCA contrast (Lc) for text roles on base00
+    pub text_min_contrast: f64,
+    /// Minimum APCA contrast (Lc) for base05 text on the selection background
+    pub selection_min_contrast: f64,
+    /// Minimum APCA contrast (Lc) for the cursor against base00
+    pub cursor_min_contrast: f64,
+}
+
+fn slots(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+impl Default for RolesConfig {
+    fn default() -> Self {
+        Self {
+            error: slots(&["base08", "base12"]),
+            warning: slots(&["base0A", "base09", "base13"]),
+            success: slots(&["base0B", "base14"]),
+            info: slots(&["base0D", "base0C", "base16"]),
+            diff_added: slots(&["base0B", "base14"]),
+            diff_removed: slots(&["base08", "base12"]),
+            selection_bg: slots(&["base02", "base01"]),
+            cursor: slots(&["base05", "base06", "base07"]),
+            text_min_contrast: 60.0,
+            selection_min_contrast: 45.0,
+            cursor_min_contrast: 45.0,
+        }
+    }
+}
+
+impl RolesConfig {
+    /// Role names paired with their candidate slots, in output order.
+    pub fn candidates(&self) -> [(&'static str, &[String]); 8] {
+        [
+            ("error", &self.error),
+            ("warning", &self.warning),
+            ("success", &self.success),
+            ("info", &self.info),
+            ("diff_added", &self.diff_added),
+            ("diff_removed", &self.diff_removed),
+            ("selection_bg", &self.selection_bg),
+            ("cursor", &self.cursor),
+        ]
+    }
+
+    /// Check that every role has candidates naming real Base24 slots.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (role, candidates) in self.candidates() {
+            if candidates.is_empty() {
+                return Err(ConfigError::InvalidValue(format!(
+                    "roles.{role} must list at least one slot"
+                )));
+            }
+            if let Some(bad) = candidates.iter().find(|s| !is_slot(s)) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "roles.{role}: '{bad}' is not a slot (expected base00-base17)"
+                )));
+            }
+        }
+        for (key, lc) in [
+            ("text_min_contrast", self.text_min_contrast),
+            ("selection_min_contrast", self.selection_min_contrast),
+            ("cursor_min_contrast", self.cursor_min_contrast),
+        ] {
+            if !(0.0..=106.0).contains(&lc) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "roles.{key} must be between 0 and 106 Lc (got {lc})"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` is a Base24 slot (base00-base0F or base10-base17).
+pub(crate) fn is_slot(name: &str) -> bool {
+    let Some(digits) = name.strip_prefix("base") else {
+        return false;
+    };
+    // Palette keys use uppercase hex digits (base0A, not base0a)
+    digits.len() == 2
+        && !digits.chars().any(|c| c.is_ascii_lowercase())
+        && u8::from_str_radix(digits, 16).is_ok_and(|n| n <= 0x17)
+}
+
+/// A semantic role resolved to a palette slot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Role {
+    pub name: &'static str,
+    /// Slot the role was resolved to
+    pub slot: String,
+    /// Hex color without the leading `#`, matching the palette section
+    pub hex: String,
+    /// APCA contrast (Lc, absolute) the role was measured at
+    pub contrast: f64,
+    /// Whether the contrast requirement was met
+    pub passes: bool,
+}
+
+/// All resolved roles, serialized as a `name: hex` map.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SemanticRoles {
+    pub roles: Vec<Role>,
+}
+
+impl SemanticRoles {
+    /// Look up a role by name.
+    pub fn get(&self, name: &str) -> Option<&Role> {
+        self.roles.iter().find(|r| r.name == name)
+    }
+
+    /// Warnings for roles where no candidate met the contrast requirement.
+    pub fn warnings(&self) -> Vec<String> {
+        self.roles
+            .iter()
+            .filter(|r| !r.passes)
+            .map(|r| {
+                format!(
+                    "role {}: no candidate meets the contrast requirement, using {} (Lc={:.1})",
+                    r.name, r.slot, r.contrast
+                )
+            })
+            .collect()
+    }
+}
+
+impl Serialize for SemanticRoles {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.roles.len()))?;
+        for role in &self.roles {
+            map.serialize_entry(role.name, &role.hex)?;
+        }
+        map.end()
+    }
+}
+
+/// Resolve every role against a generated scheme.
+///
+/// Each role takes its first candidate that meets the contrast requirement.
+/// When none do, the highest-contrast candidate is used and the role is
+/// marked as failing. Candidates missing from the palette are ignored.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::roles::{RolesConfig, derive_roles};
+///
+/// let scheme = generate(&GenerateConfig {
+///     background: Srgb::new(0x1d, 0x20, 0x21),
+///     foreground: Srgb::new(0xeb, 0xdb, 0xb2),
+///     ..Default::default()
+/// })
+/// .scheme;
+///
+/// let roles = derive_roles(&scheme, &RolesConfig::default());
+/// assert_eq!(roles.get("error").unwrap().slot, "base08");
+/// assert_eq!(roles.get("selection_bg").unwrap().slot, "base02");
+/// ```
+pub fn derive_roles(scheme: &Base16Scheme, config: &RolesConfig) -> SemanticRoles {
+    let color = |slot: &str| {
+        scheme
+            .palette
+            .get(slot)
+            .map(|c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+    };
+    let (Some(base00), Some(base05)) = (color("base00"), color("base05")) else {
+        return SemanticRoles::default();
+    };
+
+    let roles = config
+        .candidates()
+        .into_iter()
+        .filter_map(|(name, candidates)| {
+            let min_lc = match name {
+                "selection_bg" => config.selection_min_contrast,
+                "cursor" => config.cursor_min_contrast,
+                _ => config.text_min_contrast,
+            };
+            let measured: Vec<(&String, Srgb<u8>, f64)> = candidates
+                .iter()
+                .filter_map(|slot| {
+                    let c = color(slot)?;
+                    let lc = if name == "selection_bg" {
+                        apca_contrast(base05, c)
+                    } else {
+                        apca_contrast(c, base00)
+                    };
+                    Some((slot, c, lc.abs()))
+                })
+                .collect();
+
+            let (slot, c, contrast) = measured
+                .iter()
+                .find(|(_, _, lc)| *lc >= min_lc)
+                .or_else(|| measured.iter().max_by(|a, b| a.2.total_cmp(&b.2)))?;
+            Some(Role {
+                name,
+                slot: (*slot).clone(),
+                hex: format!("{:02x}{:02x}{:02x}", c.red, c.green, c.blue),
+                contrast: *contrast,
+                passes: *contrast >= min_lc,
+            })
+        })
+        .collect();
+
+    SemanticRoles { roles }
+}
+
+/// CSS custom properties mapping each role to its slot variable, both named
+/// with `prefix`.
+pub fn roles_css(roles: &SemanticRoles, prefix: &str) -> String {
+    let mut css = String::from("\n:root {\n");
+    for role in &roles.roles {
+        let _ = writeln!(
+            css,
+            "  --{prefix}{}: var(--{prefix}{});",
+            role.name.replace('_', "-"),
+            role.slot.to_lowercase()
+        );
+    }
+    css.push_str("}\n");
+    css
+}
@@ -0,0 +1,168 @@
+//! Day/night schedules: palettes stepping from the light variant to the
+//! dark one (`schedule`).
+//!
+//! The ends are the linked variant pair (see [`crate::linked`]). Each step
+//! between them is generated in full rather than blended: its background is
+//! mixed from the two variants' backgrounds in HellwigJmh, so background
+//! lightness falls monotonically from the first step to the last, and its
+//! accents are solved against that background. Every step keeps the
+//! linked pair's accent hues. Steps on the light half take the light
+//! variant's text and accent targets, the rest the dark variant's.
+//!
+//! A manifest (`schedule.toml`) lists the steps in order with the time of
+//! day each one starts, for theme switchers such as tinty to step through.
+
+use palette::Srgb;
+use serde::Serialize;
+use tinted_builder::SchemeVariant;
+
+use crate::animate::mix_srgb;
+use crate::generate::{GenerateConfig, GenerationResult, generate_for_variant};
+use crate::hellwig::hellwig_lightness;
+use crate::interpolation::srgb_to_hex;
+use crate::linked::generate_linked;
+use crate::metadata::slugify;
+
+/// One palette in a schedule.
+#[derive(Debug)]
+pub struct ScheduleStep {
+    pub result: GenerationResult,
+    /// Configuration the step was generated from
+    pub config: GenerateConfig,
+}
+
+/// Generate `steps` palettes from the light variant of `config` to its dark
+/// variant, both included.
+///
+/// Steps are named `<name> (k of n)` with slug `<name>-<k>`. A `base00` pin
+/// holds only at the ends; the steps between need their own backgrounds.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::generate::GenerateConfig;
+/// use themalingadingdong::hellwig::hellwig_lightness;
+/// use themalingadingdong::schedule::generate_schedule;
+///
+/// let steps = generate_schedule(&GenerateConfig::default(), 5);
+/// assert_eq!(steps.len(), 5);
+///
+/// // Backgrounds darken step by step
+/// let backgrounds: Vec<f32> = steps
+///     .iter()
+///     .map(|step| {
+///         let (r, g, b) = step.result.scheme.palette["base00"].rgb;
+///         hellwig_lightness(Srgb::new(r, g, b))
+///     })
+///     .collect();
+/// assert!(backgrounds.windows(2).all(|pair| pair[0] > pair[1]));
+/// ```
+pub fn generate_schedule(config: &GenerateConfig, steps: usize) -> Vec<ScheduleStep> {
+    let steps = steps.max(2);
+    let last = steps - 1;
+    let linked = generate_linked(config);
+
+    // Every step keeps the pair's hues
+    let mut hue_overrides = [None; 8];
+    for (hue, result) in hue_overrides
+        .iter_mut()
+        .zip(&linked.dark.base_accent_results)
+    {
+        *hue = Some(result.hue);
+    }
+    let background = |result: &GenerationResult| {
+        let (r, g, b) = result.scheme.palette["base00"].rgb;
+        Srgb::new(r, g, b)
+    };
+    let (light_bg, dark_bg) = (background(&linked.light), background(&linked.dark));
+    // Text for the light half, and for the dark half
+    let (dark_text, light_text) =
+        if hellwig_lightness(config.background) < hellwig_lightness(config.foreground) {
+            (config.background, config.foreground)
+        } else {
+            (config.foreground, config.background)
+        };
+
+    let mut schedule = Vec::with_capacity(steps);
+    schedule.push(ScheduleStep {
+        result: linked.light,
+        config: linked.light_config.clone(),
+    });
+    for i in 1..last {
+        let light_half = 2 * i < last;
+        let (mut step, variant, text) = if light_half {
+            (linked.light_config.clone(), SchemeVariant::Light, dark_text)
+        } else {
+            (linked.dark_config.clone(), SchemeVariant::Dark, light_text)
+        };
+        step.hue_overrides = hue_overrides;
+        step.adaptive_hues = false;
+        step.hue_spacing = None;
+        step.pins.remove("base00");
+        step.background = mix_srgb(light_bg, dark_bg, i as f32 / last as f32);
+        step.foreground = text;
+        let result = generate_for_variant(&step, Some(variant));
+        schedule.push(ScheduleStep {
+            result,
+            config: step,
+        });
+    }
+    schedule.push(ScheduleStep {
+        result: linked.dark,
+        config: linked.dark_config,
+    });
+
+    let width = steps.to_string().len();
+    let slug = slugify(&config.name);
+    for (i, step) in schedule.iter_mut().enumerate() {
+        let scheme = &mut step.result.scheme;
+        scheme.name = format!("{} ({} of {steps})", config.name, i + 1);
+        scheme.slug = format!("{slug}-{:0width$}", i + 1);
+    }
+    schedule
+}
+
+/// `schedule.toml`: the steps in order, for a theme switcher.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleManifest {
+    pub steps: Vec<ManifestStep>,
+}
+
+/// One step in a [`ScheduleManifest`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestStep {
+    /// Time of day (`HH:MM`) the step starts, when times were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    /// Scheme name as tinty knows it, e.g. `base24-my-theme-2`
+    pub scheme: String,
+    /// File the step was written to, relative to the manifest
+    pub file: String,
+    pub variant: String,
+    /// base00, to show where the step sits between day and night
+    pub background: String,
+}
+
+impl ScheduleManifest {
+    /// A manifest for `schedule` written as `<slug>.<extension>` files,
+    /// starting at `times` (one per step, or none).
+    pub fn new(schedule: &[ScheduleStep], extension: &str, times: &[String]) -> Self {
+        let steps = schedule
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let scheme = &step.result.scheme;
+                let (r, g, b) = scheme.palette["base00"].rgb;
+                ManifestStep {
+                    time: times.get(i).cloned(),
+                    scheme: format!("{}-{}", scheme.system, scheme.slug),
+                    file: format!("{}.{extension}", scheme.slug),
+                    variant: scheme.variant.to_string(),
+                    background: format!("#{}", srgb_to_hex(Srgb::new(r, g, b))),
+                }
+            })
+            .collect();
+        Self { steps }
+    }
+}
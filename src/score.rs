@@ -0,0 +1,251 @@
+//! Aggregate quality metrics for comparing schemes.
+//!
+//! Each metric is reduced to a 0-1 score (1 is best) and the scores are
+//! combined into a weighted 0-100 total, so candidate palettes can be ranked
+//! objectively. The raw values are kept alongside for the breakdown:
+//!
+//! - contrast margin: APCA |Lc| above each required check's threshold, as
+//!   the mean and the worst case
+//! - lightness uniformity: spread (standard deviation) of J' across base08-base0F
+//! - chroma variance: variance of M across base08-base0F
+//! - hue spacing: smallest gap between accent hues relative to an even split
+//! - CVD separability: share of accent pairs that stay apart under simulated
+//!   colorblindness (see [`crate::cvd::cvd_separations`])
+
+use palette::Srgb;
+use serde::Serialize;
+use tinted_builder::Base16Scheme;
+
+use crate::cvd::{DEFAULT_CVD_THRESHOLD, cvd_separations};
+use crate::hellwig::HellwigJmh;
+use crate::validation::{ContrastStandard, ValidationConfig, validate_with_config};
+
+/// Base accent slots the uniformity metrics are measured over.
+const BASE_ACCENTS: [&str; 8] = [
+    "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+];
+
+/// Mean contrast margin (Lc) that scores 1.
+const FULL_MEAN_MARGIN: f64 = 15.0;
+
+/// Worst-case margin (Lc) that scores 0; a margin of 0 scores 0.5.
+const ZERO_MIN_MARGIN: f64 = -10.0;
+
+/// J' standard deviation across accents that scores 0.
+const ZERO_LIGHTNESS_SPREAD: f64 = 10.0;
+
+/// M standard deviation across accents that scores 0.
+const ZERO_CHROMA_SPREAD: f64 = 15.0;
+
+/// Relative weight of each metric in the total.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreWeights {
+    pub contrast_margin_mean: f64,
+    pub contrast_margin_min: f64,
+    pub lightness_uniformity: f64,
+    pub chroma_variance: f64,
+    pub hue_spacing: f64,
+    pub cvd_separability: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            contrast_margin_mean: 1.0,
+            contrast_margin_min: 2.0,
+            lightness_uniformity: 1.5,
+            chroma_variance: 1.0,
+            hue_spacing: 1.0,
+            cvd_separability: 1.5,
+        }
+    }
+}
+
+/// One metric: its raw value, 0-1 score, and weight in the total.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Metric {
+    pub value: f64,
+    pub score: f64,
+    pub weight: f64,
+}
+
+impl Metric {
+    fn new(value: f64, score: f64, weight: f64) -> Self {
+        Self {
+            value,
+            score: score.clamp(0.0, 1.0),
+            weight,
+        }
+    }
+}
+
+/// Per-metric breakdown of a scheme's score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Metrics {
+    /// Mean APCA margin over the required checks (Lc)
+    pub contrast_margin_mean: Metric,
+    /// Smallest APCA margin over the required checks (Lc, negative fails)
+    pub contrast_margin_min: Metric,
+    /// Standard deviation of accent J'
+    pub lightness_uniformity: Metric,
+    /// Variance of accent M
+    pub chroma_variance: Metric,
+    /// Smallest accent hue gap divided by 360° / accent count (1 = even)
+    pub hue_spacing: Metric,
+    /// Smallest simulated ΔE between accents under any deficiency; scored
+    /// by the share of pairs and deficiencies at or above the `--check-cvd`
+    /// threshold
+    pub cvd_separability: Metric,
+}
+
+impl Metrics {
+    fn all(&self) -> [Metric; 6] {
+        [
+            self.contrast_margin_mean,
+            self.contrast_margin_min,
+            self.lightness_uniformity,
+            self.chroma_variance,
+            self.hue_spacing,
+            self.cvd_separability,
+        ]
+    }
+}
+
+/// A scheme's weighted score and its breakdown.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemeScore {
+    pub name: String,
+    /// Weighted score, 0-100
+    pub score: f64,
+    pub metrics: Metrics,
+}
+
+/// Score a scheme with the default validation thresholds and weights.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::score::score_scheme;
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let score = score_scheme(&scheme);
+///
+/// assert!(score.score > 50.0 && score.score <= 100.0);
+/// assert!(score.metrics.hue_spacing.value > 0.0);
+/// ```
+pub fn score_scheme(scheme: &Base16Scheme) -> SchemeScore {
+    score_scheme_with(
+        scheme,
+        &ValidationConfig::default(),
+        &ScoreWeights::default(),
+    )
+}
+
+/// Score a scheme against `validation`'s thresholds, weighting metrics by
+/// `weights`.
+pub fn score_scheme_with(
+    scheme: &Base16Scheme,
+    validation: &ValidationConfig,
+    weights: &ScoreWeights,
+) -> SchemeScore {
+    let results = validate_with_config(scheme, &[], &[], ContrastStandard::Apca, validation);
+    let margins: Vec<f64> = results.required.iter().map(|r| r.margin()).collect();
+    let mean_margin = mean(&margins);
+    let min_margin = margins.iter().copied().fold(f64::INFINITY, f64::min);
+    let min_margin = if min_margin.is_finite() {
+        min_margin
+    } else {
+        0.0
+    };
+
+    let accents: Vec<HellwigJmh> = BASE_ACCENTS
+        .iter()
+        .filter_map(|name| scheme.palette.get(*name))
+        .map(|c| {
+            let (r, g, b) = c.rgb;
+            HellwigJmh::from_srgb_u8(Srgb::new(r, g, b))
+        })
+        .collect();
+    let lightness: Vec<f64> = accents.iter().map(|c| c.lightness as f64).collect();
+    let colorfulness: Vec<f64> = accents.iter().map(|c| c.colorfulness as f64).collect();
+    let lightness_spread = variance(&lightness).sqrt();
+    let chroma_variance = variance(&colorfulness);
+    let spacing = hue_spacing(&accents.iter().map(|c| c.hue).collect::<Vec<_>>());
+    let separations = cvd_separations(scheme);
+    let cvd_min = separations.iter().copied().fold(f32::INFINITY, f32::min);
+    let cvd_min = if cvd_min.is_finite() {
+        cvd_min as f64
+    } else {
+        0.0
+    };
+    let separated = separations
+        .iter()
+        .filter(|&&d| d >= DEFAULT_CVD_THRESHOLD)
+        .count() as f64
+        / separations.len().max(1) as f64;
+
+    let metrics = Metrics {
+        contrast_margin_mean: Metric::new(
+            mean_margin,
+            mean_margin / FULL_MEAN_MARGIN,
+            weights.contrast_margin_mean,
+        ),
+        contrast_margin_min: Metric::new(
+            min_margin,
+            (min_margin - ZERO_MIN_MARGIN) / (-2.0 * ZERO_MIN_MARGIN),
+            weights.contrast_margin_min,
+        ),
+        lightness_uniformity: Metric::new(
+            lightness_spread,
+            1.0 - lightness_spread / ZERO_LIGHTNESS_SPREAD,
+            weights.lightness_uniformity,
+        ),
+        chroma_variance: Metric::new(
+            chroma_variance,
+            1.0 - chroma_variance.sqrt() / ZERO_CHROMA_SPREAD,
+            weights.chroma_variance,
+        ),
+        hue_spacing: Metric::new(spacing, spacing, weights.hue_spacing),
+        cvd_separability: Metric::new(cvd_min, separated, weights.cvd_separability),
+    };
+
+    let total_weight: f64 = metrics.all().iter().map(|m| m.weight).sum();
+    let weighted: f64 = metrics.all().iter().map(|m| m.score * m.weight).sum();
+    let score = if total_weight > 0.0 {
+        100.0 * weighted / total_weight
+    } else {
+        0.0
+    };
+
+    SchemeScore {
+        name: scheme.name.clone(),
+        score,
+        metrics,
+    }
+}
+
+/// Smallest circular gap between hues relative to an even split (0-1).
+fn hue_spacing(hues: &[f32]) -> f64 {
+    if hues.len() < 2 {
+        return 0.0;
+    }
+    let mut sorted: Vec<f64> = hues.iter().map(|&h| h.rem_euclid(360.0) as f64).collect();
+    sorted.sort_by(f64::total_cmp);
+    let wrap = sorted[0] + 360.0 - sorted[sorted.len() - 1];
+    let min_gap = sorted.windows(2).map(|w| w[1] - w[0]).fold(wrap, f64::min);
+    min_gap / (360.0 / hues.len() as f64)
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn variance(values: &[f64]) -> f64 {
+    let mean = mean(values);
+    let squares: Vec<f64> = values.iter().map(|v| (v - mean).powi(2)).collect();
+    self::mean(&squares)
+}
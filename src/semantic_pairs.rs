@@ -0,0 +1,286 @@
+//! Accent pairs that must stay distinguishable (`[validation.semantic]`).
+//!
+//! Some accents only carry meaning next to each other: a diff's additions
+//! (base0B) against its removals (base08), a warning (base0A) against an
+//! error (base08). Each declared pair must differ by at least `min_hue`
+//! degrees of hue or `min_lightness` J' under normal vision and under every
+//! simulated color vision deficiency:
+//!
+//! ```toml
+//! [validation.semantic]
+//! pairs = ["base0B/base08", "base0A/base08"]
+//! min_hue = 30
+//! min_lightness = 8
+//! ```
+//!
+//! Validation reports each pair's worst case as a required check, and the
+//! accent solver pushes pairs within an accent group (base08-base0F or
+//! base10-base17) apart, widening their J' box enough to split them in
+//! lightness where hue alone cannot tell them apart.
+
+use std::fmt;
+
+use palette::Srgb;
+use serde::{Deserialize, Serialize};
+use tinted_builder::Base16Scheme;
+
+use crate::config::{AccentOptSettings, ConfigError};
+use crate::cvd::{CvdKind, simulate};
+use crate::hellwig::HellwigJmh;
+
+/// Default hue difference (degrees) that tells a pair apart.
+pub const DEFAULT_MIN_HUE: f32 = 30.0;
+
+/// Default J' difference that tells a pair apart.
+pub const DEFAULT_MIN_LIGHTNESS: f32 = 8.0;
+
+/// Colorfulness (M) below which a color's hue is too faint to count.
+pub const ACHROMATIC_M: f32 = 5.0;
+
+/// Accent groups; the solver only separates pairs within one.
+const ACCENT_GROUPS: [[&str; 8]; 2] = [
+    [
+        "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+    ],
+    [
+        "base10", "base11", "base12", "base13", "base14", "base15", "base16", "base17",
+    ],
+];
+
+/// Declared semantic pairs and the separation they need.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticPairs {
+    /// Pairs as `"base0B/base08"`
+    pub pairs: Vec<String>,
+    /// Hue difference (degrees) that tells a pair apart
+    pub min_hue: f32,
+    /// J' difference that tells a pair apart
+    pub min_lightness: f32,
+}
+
+impl Default for SemanticPairs {
+    fn default() -> Self {
+        Self {
+            pairs: Vec::new(),
+            min_hue: DEFAULT_MIN_HUE,
+            min_lightness: DEFAULT_MIN_LIGHTNESS,
+        }
+    }
+}
+
+impl SemanticPairs {
+    /// Whether these are the defaults (no pairs declared).
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that every pair parses and the separations are in range.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.min_hue > 0.0 && self.min_hue <= 180.0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "validation.semantic.min_hue must be in (0, 180] (got {})",
+                self.min_hue
+            )));
+        }
+        if !(self.min_lightness > 0.0 && self.min_lightness <= 50.0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "validation.semantic.min_lightness must be in (0, 50] (got {})",
+                self.min_lightness
+            )));
+        }
+        for pair in &self.pairs {
+            parse_semantic_pair(pair).map_err(|e| {
+                ConfigError::InvalidValue(format!("validation.semantic.pairs: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// The declared pairs, skipping any that don't parse
+    /// (`SemanticPairs::validate` reports those).
+    pub fn slots(&self) -> Vec<(&'static str, &'static str)> {
+        self.pairs
+            .iter()
+            .filter_map(|pair| parse_semantic_pair(pair).ok())
+            .collect()
+    }
+
+    /// `settings` for solving the `group`-th accent group (0 for
+    /// base08-base0F, 1 for base10-base17), with the pairs inside it.
+    pub fn apply(&self, settings: &AccentOptSettings, group: usize) -> AccentOptSettings {
+        let index = |slot: &str| ACCENT_GROUPS[group].iter().position(|s| *s == slot);
+        let pairs: Vec<(usize, usize)> = self
+            .slots()
+            .into_iter()
+            .filter_map(|(first, second)| Some((index(first)?, index(second)?)))
+            .collect();
+        AccentOptSettings {
+            semantic: (!pairs.is_empty()).then_some(SemanticSeparation {
+                pairs,
+                min_hue: self.min_hue,
+                min_lightness: self.min_lightness,
+            }),
+            ..settings.clone()
+        }
+    }
+}
+
+/// Parse a pair as written in `pairs`: two accent slots separated by `/`,
+/// in either case.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::semantic_pairs::parse_semantic_pair;
+///
+/// assert_eq!(parse_semantic_pair("base0b / base08").unwrap(), ("base0B", "base08"));
+/// assert!(parse_semantic_pair("base0B/base0B").is_err());
+/// assert!(parse_semantic_pair("base05/base08").is_err());
+/// ```
+pub fn parse_semantic_pair(text: &str) -> Result<(&'static str, &'static str), String> {
+    let invalid =
+        |reason: &str| format!("{reason} (got '{text}', expected e.g. \"base0B/base08\")");
+    let slot = |name: &str| {
+        ACCENT_GROUPS
+            .iter()
+            .flatten()
+            .copied()
+            .find(|slot| slot.eq_ignore_ascii_case(name.trim()))
+    };
+    let (first, second) = text
+        .split_once('/')
+        .ok_or_else(|| invalid("missing '/' between the slots"))?;
+    let first = slot(first).ok_or_else(|| invalid("the first slot is not an accent"))?;
+    let second = slot(second).ok_or_else(|| invalid("the second slot is not an accent"))?;
+    if first == second {
+        return Err(invalid("a slot cannot be paired with itself"));
+    }
+    Ok((first, second))
+}
+
+/// Semantic pairs within one accent group, by index (0 for base08 or
+/// base10), for the accent solver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticSeparation {
+    pub pairs: Vec<(usize, usize)>,
+    pub min_hue: f32,
+    pub min_lightness: f32,
+}
+
+/// How far apart two colors are in hue and lightness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Separation {
+    /// Hue difference (degrees), unless either color is near-achromatic
+    pub hue: Option<f32>,
+    /// Absolute J' difference
+    pub lightness: f32,
+}
+
+impl Separation {
+    /// Separation of `a` and `b` as seen with `vision` (normal when `None`).
+    pub fn between(a: Srgb<u8>, b: Srgb<u8>, vision: Option<CvdKind>) -> Self {
+        let seen =
+            |color| HellwigJmh::from_srgb_u8(vision.map_or(color, |kind| simulate(color, kind)));
+        let (a, b) = (seen(a), seen(b));
+        let hue = (a.colorfulness >= ACHROMATIC_M && b.colorfulness >= ACHROMATIC_M).then(|| {
+            let d = (a.hue - b.hue).rem_euclid(360.0);
+            d.min(360.0 - d)
+        });
+        Self {
+            hue,
+            lightness: (a.lightness - b.lightness).abs(),
+        }
+    }
+
+    /// The larger of the hue and lightness differences, as a fraction of
+    /// what tells a pair apart; 1 or more passes.
+    pub fn score(&self, min_hue: f32, min_lightness: f32) -> f32 {
+        (self.hue.unwrap_or(0.0) / min_hue).max(self.lightness / min_lightness)
+    }
+}
+
+/// The worst case of one semantic pair.
+#[derive(Debug, Clone)]
+pub struct SemanticPairResult {
+    pub first: &'static str,
+    pub second: &'static str,
+    /// Deficiency of the worst case (normal vision when `None`)
+    pub vision: Option<CvdKind>,
+    pub separation: Separation,
+    pub min_hue: f32,
+    pub min_lightness: f32,
+    /// Whether the pair is told apart under every vision
+    pub passes: bool,
+}
+
+impl fmt::Display for SemanticPairResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} vs {} (", self.first, self.second)?;
+        match self.vision {
+            Some(kind) => write!(f, "{kind}")?,
+            None => f.write_str("normal vision")?,
+        }
+        f.write_str("): hue ")?;
+        match self.separation.hue {
+            Some(hue) => write!(f, "{hue:.0}°")?,
+            None => f.write_str("n/a")?,
+        }
+        write!(
+            f,
+            ", lightness {:.1} (required: hue {:.0}° or lightness {:.1})",
+            self.separation.lightness, self.min_hue, self.min_lightness
+        )
+    }
+}
+
+/// Check each semantic pair in `scheme` under normal vision and every
+/// deficiency, keeping the worst case of each.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::semantic_pairs::{SemanticPairs, check_semantic_pairs};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let config = SemanticPairs {
+///     pairs: vec!["base0B/base08".to_string()],
+///     ..Default::default()
+/// };
+/// let results = check_semantic_pairs(&scheme, &config);
+/// assert_eq!(results.len(), 1);
+/// assert_eq!((results[0].first, results[0].second), ("base0B", "base08"));
+/// ```
+pub fn check_semantic_pairs(
+    scheme: &Base16Scheme,
+    config: &SemanticPairs,
+) -> Vec<SemanticPairResult> {
+    let color = |slot: &str| {
+        scheme.palette.get(slot).map(|c| {
+            let (r, g, b) = c.rgb;
+            Srgb::new(r, g, b)
+        })
+    };
+    let score = |s: &Separation| s.score(config.min_hue, config.min_lightness);
+    config
+        .slots()
+        .into_iter()
+        .filter_map(|(first, second)| {
+            let (a, b) = (color(first)?, color(second)?);
+            let (vision, separation) = std::iter::once(None)
+                .chain(CvdKind::ALL.map(Some))
+                .map(|vision| (vision, Separation::between(a, b, vision)))
+                .min_by(|(_, x), (_, y)| score(x).total_cmp(&score(y)))?;
+            Some(SemanticPairResult {
+                first,
+                second,
+                vision,
+                separation,
+                min_hue: config.min_hue,
+                min_lightness: config.min_lightness,
+                passes: score(&separation) >= 1.0,
+            })
+        })
+        .collect()
+}
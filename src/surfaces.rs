@@ -0,0 +1,142 @@
+//! Extended surface ramp between the Base16 surfaces.
+//!
+//! Base16 has eight surfaces (base00-base07), which leaves no room for the
+//! hover, active, and pressed shades UI frameworks place half a step away
+//! from a surface. The extended ramp walks base00 to base07 in Hellwig JMh
+//! and samples it at `steps` evenly spaced points. With 15 steps every other
+//! shade is a Base16 surface and the ones between are the half steps
+//! (base00.5, base01.5, ...); other counts space the shades evenly without
+//! landing on the surfaces.
+
+use std::fmt::Write;
+
+use palette::Srgb;
+use serde::{Deserialize, Serialize};
+use tinted_builder::Base16Scheme;
+
+use crate::config::ConfigError;
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::{lerp, lerp_hue};
+
+/// The surfaces the ramp walks, darkest-first in a dark scheme.
+const SURFACES: [&str; 8] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07",
+];
+
+/// Largest number of shades a ramp may have.
+pub const MAX_STEPS: usize = 64;
+
+/// How many shades the extended surface ramp has.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SurfacesConfig {
+    /// Number of shades from base00 to base07, both included
+    pub steps: usize,
+}
+
+impl Default for SurfacesConfig {
+    fn default() -> Self {
+        Self { steps: 16 }
+    }
+}
+
+impl SurfacesConfig {
+    /// Check that the ramp has between 2 and `MAX_STEPS` shades.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(2..=MAX_STEPS).contains(&self.steps) {
+            return Err(ConfigError::InvalidValue(format!(
+                "surfaces.steps must be between 2 and {MAX_STEPS} (got {})",
+                self.steps
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Surface shades from base00 to base07, serialized as an array of hex
+/// values.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct SurfaceRamp {
+    /// Lowercase hex values without `#`, base00 first
+    pub shades: Vec<String>,
+}
+
+impl SurfaceRamp {
+    /// Sample `steps` shades from a scheme's surfaces.
+    ///
+    /// The first and last shades are base00 and base07, and shades that fall
+    /// on a surface keep its exact value. Surfaces missing from the palette
+    /// are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::generate::{GenerateConfig, generate};
+    /// use themalingadingdong::surfaces::SurfaceRamp;
+    ///
+    /// let scheme = generate(&GenerateConfig::default()).scheme;
+    /// let ramp = SurfaceRamp::new(&scheme, 15);
+    ///
+    /// let (r, g, b) = scheme.palette["base01"].rgb;
+    /// assert_eq!(ramp.shades.len(), 15);
+    /// assert_eq!(ramp.shades[2], format!("{r:02x}{g:02x}{b:02x}"));
+    /// ```
+    pub fn new(scheme: &Base16Scheme, steps: usize) -> Self {
+        let surfaces: Vec<(Srgb<u8>, HellwigJmh)> = SURFACES
+            .iter()
+            .filter_map(|slot| scheme.palette.get(*slot))
+            .map(|color| {
+                let (r, g, b) = color.rgb;
+                let srgb = Srgb::new(r, g, b);
+                (srgb, HellwigJmh::from_srgb_u8(srgb))
+            })
+            .collect();
+        if surfaces.len() < 2 || steps < 2 {
+            return Self { shades: Vec::new() };
+        }
+
+        let segments = (surfaces.len() - 1) as f32;
+        let shades = (0..steps)
+            .map(|i| {
+                let position = i as f32 * segments / (steps - 1) as f32;
+                let index = (position.floor() as usize).min(surfaces.len() - 2);
+                let t = position - index as f32;
+                let (from, to) = (&surfaces[index], &surfaces[index + 1]);
+                let color = if t < 1e-4 {
+                    from.0
+                } else if t > 1.0 - 1e-4 {
+                    to.0
+                } else {
+                    HellwigJmh::new(
+                        lerp(from.1.lightness, to.1.lightness, t),
+                        lerp(from.1.colorfulness, to.1.colorfulness, t),
+                        lerp_hue(from.1.hue, to.1.hue, t),
+                    )
+                    .into_srgb_u8_gamut_mapped()
+                };
+                format!("{:02x}{:02x}{:02x}", color.red, color.green, color.blue)
+            })
+            .collect();
+        Self { shades }
+    }
+
+    /// Each shade as `(surface-<index>, #hex)`, named with `prefix`.
+    pub fn named(&self, prefix: &str) -> impl Iterator<Item = (String, String)> + '_ {
+        let prefix = prefix.to_string();
+        self.shades
+            .iter()
+            .enumerate()
+            .map(move |(index, hex)| (format!("{prefix}surface-{index}"), format!("#{hex}")))
+    }
+
+    /// CSS custom properties for every shade, named with `prefix`.
+    pub fn css(&self, prefix: &str) -> String {
+        let mut css = String::from("\n:root {\n");
+        for (name, hex) in self.named(prefix) {
+            let _ = writeln!(css, "  --{name}: {hex};");
+        }
+        css.push_str("}\n");
+        css
+    }
+}
@@ -0,0 +1,202 @@
+//! Terminal color depth detection and downsampling for previews.
+//!
+//! The `preview` subcommand and the TUI draw with 24-bit colors, which
+//! terminals without truecolor support round unpredictably or drop. The
+//! depth is read from `COLORTERM` and `TERM` (terminfo names ending in
+//! `-direct` are truecolor, `*-256color` have 256 colors), or set with
+//! `--color-depth`. Below truecolor, colors are replaced with:
+//!
+//! - 256 colors: the nearest xterm index, 16-255 (see `xterm256`).
+//! - 16 colors: the ANSI color the scheme assigns a slot to (see `ansi`),
+//!   so a terminal themed with the scheme shows it exactly; other colors
+//!   fall back to the nearest of xterm's default ANSI colors.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use palette::Srgb;
+use tinted_builder::Base16Scheme;
+
+use crate::ansi::ansi_slots;
+use crate::xterm256::{nearest_ansi16, nearest_xterm};
+
+/// How many colors a terminal can show.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// The 16 ANSI colors, as the terminal's palette defines them
+    Ansi16,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// 24-bit color
+    #[default]
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detect the depth of the terminal this process runs in.
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// Depth implied by `COLORTERM` and `TERM` values.
+    ///
+    /// Without either variable there is nothing to go on, and truecolor is
+    /// assumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::term_color::ColorDepth;
+    ///
+    /// assert_eq!(ColorDepth::from_env(Some("truecolor"), Some("xterm")), ColorDepth::TrueColor);
+    /// assert_eq!(ColorDepth::from_env(None, Some("xterm-256color")), ColorDepth::Ansi256);
+    /// assert_eq!(ColorDepth::from_env(None, Some("linux")), ColorDepth::Ansi16);
+    /// assert_eq!(ColorDepth::from_env(None, None), ColorDepth::TrueColor);
+    /// ```
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        let colorterm = colorterm.unwrap_or_default().to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+        let term = term.unwrap_or_default().to_ascii_lowercase();
+        let unknown = term.is_empty() && colorterm.is_empty();
+        if unknown || term.ends_with("-direct") || term.contains("truecolor") {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Short name, as accepted by `--color-depth`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorDepth::Ansi16 => "16",
+            ColorDepth::Ansi256 => "256",
+            ColorDepth::TrueColor => "truecolor",
+        }
+    }
+
+    /// Why a preview at this depth can't show the scheme faithfully, or
+    /// `None` for truecolor.
+    pub fn warning(self) -> Option<&'static str> {
+        match self {
+            ColorDepth::TrueColor => None,
+            ColorDepth::Ansi256 => Some(
+                "terminal shows 256 colors; the preview uses the nearest xterm colors \
+                 (set COLORTERM=truecolor or --color-depth truecolor if it supports more)",
+            ),
+            ColorDepth::Ansi16 => Some(
+                "terminal shows 16 colors; the preview uses the terminal's ANSI colors, \
+                 which only match the scheme when the terminal is themed with it",
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ColorDepth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A color a terminal can show at some depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermColor {
+    Rgb(Srgb<u8>),
+    /// xterm palette index; 0-15 are the terminal's ANSI colors
+    Indexed(u8),
+}
+
+impl TermColor {
+    /// SGR escape setting this as the foreground color.
+    pub fn fg(self) -> String {
+        self.sgr(38, 30)
+    }
+
+    /// SGR escape setting this as the background color.
+    pub fn bg(self) -> String {
+        self.sgr(48, 40)
+    }
+
+    /// `extended` introduces 256-color and 24-bit values; ANSI colors use
+    /// the basic (`base`) and bright (`base + 60`) codes, which 16-color
+    /// terminals understand.
+    fn sgr(self, extended: u8, base: u8) -> String {
+        match self {
+            TermColor::Rgb(c) => format!("\x1b[{extended};2;{};{};{}m", c.red, c.green, c.blue),
+            TermColor::Indexed(i @ 0..8) => format!("\x1b[{}m", base + i),
+            TermColor::Indexed(i @ 8..16) => format!("\x1b[{}m", base + 60 + i - 8),
+            TermColor::Indexed(i) => format!("\x1b[{extended};5;{i}m"),
+        }
+    }
+}
+
+/// Maps colors to what a terminal of some depth can show, remembering each
+/// color's match.
+#[derive(Debug, Clone)]
+pub struct TermPalette {
+    depth: ColorDepth,
+    /// Colors of the slots behind ANSI 0-15, for 16-color terminals
+    ansi: Vec<(Srgb<u8>, u8)>,
+    cache: HashMap<(u8, u8, u8), TermColor>,
+}
+
+impl TermPalette {
+    /// A palette for `depth`. At 16 colors, `scheme`'s slots map to the
+    /// ANSI colors they back.
+    pub fn new(depth: ColorDepth, scheme: Option<&Base16Scheme>) -> Self {
+        let ansi = scheme
+            .filter(|_| depth == ColorDepth::Ansi16)
+            .map(|scheme| {
+                ansi_slots(scheme)
+                    .iter()
+                    .zip(0u8..)
+                    .filter_map(|(slot, index)| {
+                        let (r, g, b) = scheme.palette.get(*slot)?.rgb;
+                        Some((Srgb::new(r, g, b), index))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            depth,
+            ansi,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// The closest color the terminal can show to `color`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use palette::Srgb;
+    /// use themalingadingdong::term_color::{ColorDepth, TermColor, TermPalette};
+    ///
+    /// let mut palette = TermPalette::new(ColorDepth::Ansi256, None);
+    /// assert_eq!(palette.map(Srgb::new(255, 0, 0)), TermColor::Indexed(196));
+    /// ```
+    pub fn map(&mut self, color: Srgb<u8>) -> TermColor {
+        if self.depth == ColorDepth::TrueColor {
+            return TermColor::Rgb(color);
+        }
+        let key = (color.red, color.green, color.blue);
+        if let Some(mapped) = self.cache.get(&key) {
+            return *mapped;
+        }
+        let mapped = match self.depth {
+            ColorDepth::Ansi256 => TermColor::Indexed(nearest_xterm(color).0),
+            _ => match self.ansi.iter().find(|(c, _)| *c == color) {
+                Some((_, index)) => TermColor::Indexed(*index),
+                None => TermColor::Indexed(nearest_ansi16(color).0),
+            },
+        };
+        self.cache.insert(key, mapped);
+        mapped
+    }
+}
@@ -23,8 +23,8 @@ use tuirealm::{
 use crate::tui::activity::{Activity, Context, ExitReason};
 use crate::tui::components::{CODE_PREVIEW_FOOTER_ACTIONS, format_footer};
 use crate::tui::highlighting::Highlighter;
-use crate::tui::snippets::SNIPPETS;
-use crate::tui::{AppAction, dispatcher, handle_global_app_events};
+use crate::tui::snippets::{CodeSnippet, load_snippets};
+use crate::tui::{AppAction, dispatcher, downsample_buffer, handle_global_app_events};
 
 // ============================================================================
 // Component identifiers (scoped to CodePreviewActivity)
@@ -45,6 +45,7 @@ pub enum Msg {
     Back,
     NextLanguage,
     PrevLanguage,
+    ReloadSnippets,
     ScrollUp,
     ScrollDown,
 }
@@ -191,6 +192,7 @@ impl Component<Msg, UserEvent> for CodeView {
             return match msg {
                 crate::tui::activities::Msg::Quit => Some(Msg::Quit),
                 crate::tui::activities::Msg::SwitchToCodePreview => Some(Msg::Back), // Toggle back
+                crate::tui::activities::Msg::Regenerate => Some(Msg::ReloadSnippets),
                 _ => None,
             };
         }
@@ -225,6 +227,7 @@ pub struct CodePreviewActivity {
     context: Option<Context>,
     exit_reason: Option<ExitReason>,
     highlighter: Option<Highlighter>,
+    snippets: Vec<CodeSnippet>,
     current_language: usize,
     needs_clear: bool,
 }
@@ -264,26 +267,45 @@ impl CodePreviewActivity {
     }
 
     fn highlight_current_snippet(&self) -> Vec<Line<'static>> {
-        if let Some(ref highlighter) = self.highlighter {
-            let snippet = &SNIPPETS[self.current_language];
-            highlighter.highlight(snippet.code, snippet.extension)
+        if let Some(ref highlighter) = self.highlighter
+            && let Some(snippet) = self.snippets.get(self.current_language)
+        {
+            highlighter.highlight(&snippet.code, &snippet.extension)
         } else {
             Vec::new()
         }
     }
 
     fn next_language(&mut self) {
-        self.current_language = (self.current_language + 1) % SNIPPETS.len();
+        self.current_language = (self.current_language + 1) % self.snippets.len();
         self.needs_clear = true;
         self.update_code_view();
     }
 
     fn prev_language(&mut self) {
-        self.current_language = (self.current_language + SNIPPETS.len() - 1) % SNIPPETS.len();
+        self.current_language =
+            (self.current_language + self.snippets.len() - 1) % self.snippets.len();
         self.needs_clear = true;
         self.update_code_view();
     }
 
+    /// Re-read the user snippets directory, keeping the current snippet
+    /// selected when it still exists.
+    fn reload_snippets(&mut self) {
+        let current = self
+            .snippets
+            .get(self.current_language)
+            .map(|s| s.display_name.clone());
+        let dir = self
+            .context
+            .as_ref()
+            .and_then(|ctx| ctx.model.snippets_dir.clone());
+        self.snippets = load_snippets(dir.as_deref());
+        self.current_language = current
+            .and_then(|name| self.snippets.iter().position(|s| s.display_name == name))
+            .unwrap_or(0);
+    }
+
     fn update_code_view(&mut self) {
         let lines = self.highlight_current_snippet();
         let colors = self.code_view_colors();
@@ -298,12 +320,16 @@ impl CodePreviewActivity {
 }
 
 impl Activity for CodePreviewActivity {
-    fn on_create(&mut self, context: Context) {
+    fn on_create(&mut self, mut context: Context) {
+        // Highlight the palette for the latest edits, not one still regenerating
+        context.model.finish_regeneration();
+
         // Create highlighter from current scheme
         if let Some(ref scheme) = context.model.current_scheme {
             self.highlighter = Some(Highlighter::new(scheme));
         }
         self.context = Some(context);
+        self.reload_snippets();
 
         // Create application and mount component
         let mut app = Self::create_application();
@@ -323,7 +349,12 @@ impl Activity for CodePreviewActivity {
         }
 
         let app = self.app.as_mut().expect("app should be initialized");
-        let snippet = &SNIPPETS[self.current_language];
+        let model = &self.context.as_ref().expect("context should be set").model;
+        let snippet_name = self
+            .snippets
+            .get(self.current_language)
+            .map(|s| s.display_name.as_ref())
+            .unwrap_or_default();
 
         // Draw UI
         terminal.draw(|frame| {
@@ -339,7 +370,7 @@ impl Activity for CodePreviewActivity {
                 .split(area);
 
             // Title bar with language tabs
-            let title = format!(" Code Preview - [{}]", snippet.display_name);
+            let title = format!(" Code Preview - [{snippet_name}]");
             let title_widget =
                 Paragraph::new(title).style(Style::default().add_modifier(Modifier::BOLD));
             frame.render_widget(title_widget, rows[0]);
@@ -352,6 +383,12 @@ impl Activity for CodePreviewActivity {
             let status_widget =
                 Paragraph::new(status).style(Style::default().add_modifier(Modifier::DIM));
             frame.render_widget(status_widget, rows[2]);
+
+            downsample_buffer(
+                frame.buffer_mut(),
+                model.color_depth,
+                model.current_scheme.as_ref(),
+            );
         })?;
 
         // Process events through tui-realm
@@ -369,6 +406,11 @@ impl Activity for CodePreviewActivity {
                         }
                         Msg::NextLanguage => self.next_language(),
                         Msg::PrevLanguage => self.prev_language(),
+                        Msg::ReloadSnippets => {
+                            self.reload_snippets();
+                            self.needs_clear = true;
+                            self.update_code_view();
+                        }
                         Msg::ScrollUp | Msg::ScrollDown => {
                             // Already handled in component
                         }
@@ -0,0 +1,466 @@
+//! Scheme gallery activity - browse scheme files and load one for editing.
+
+use std::io::Stdout;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use color_eyre::eyre::Result;
+use crossterm_actions::{NavigationEvent, TuiEvent};
+use palette::Srgb;
+use ratatui::{
+    Terminal,
+    layout::{Constraint, Direction, Layout, Rect},
+    prelude::CrosstermBackend,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+use tinted_builder::Base16Scheme;
+use tuirealm::{
+    Application, Component, Event, EventListenerCfg, MockComponent, PollStrategy, State,
+    StateValue, Update,
+    command::{Cmd, CmdResult, Direction as CmdDirection, Position},
+    props::{AttrValue, Attribute, Props},
+};
+
+use crate::curves::CurveConfig;
+use crate::import::{read_scheme, scheme_files};
+use crate::tui::activity::{Activity, Context, ExitReason};
+use crate::tui::components::{GALLERY_FOOTER_ACTIONS, Palette, Preview, format_footer};
+use crate::tui::{AppAction, dispatcher, downsample_buffer, handle_global_app_events};
+
+// ============================================================================
+// Component identifiers (scoped to GalleryActivity)
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Id {
+    SchemeList,
+}
+
+// ============================================================================
+// Messages (scoped to GalleryActivity)
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Msg {
+    Quit,
+    Back,
+    Select(usize),
+    Load,
+    Rescan,
+}
+
+// ============================================================================
+// User events (required by tui-realm)
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserEvent {}
+
+// ============================================================================
+// SchemeList Component
+// ============================================================================
+
+/// Scrollable list of scheme file names.
+pub struct SchemeList {
+    props: Props,
+    names: Vec<String>,
+    selected: usize,
+    scroll: usize,
+    visible_height: usize,
+    /// Shown instead of the list when it is empty
+    placeholder: String,
+}
+
+impl SchemeList {
+    pub fn new(names: Vec<String>, selected: usize, placeholder: String) -> Self {
+        Self {
+            props: Props::default(),
+            selected: selected.min(names.len().saturating_sub(1)),
+            names,
+            scroll: 0,
+            visible_height: 20,
+            placeholder,
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.names.len().saturating_sub(1));
+    }
+
+    /// Keep the selection within the visible rows.
+    fn scroll_to_selection(&mut self) {
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + self.visible_height {
+            self.scroll = self.selected + 1 - self.visible_height;
+        }
+    }
+}
+
+impl MockComponent for SchemeList {
+    fn view(&mut self, frame: &mut ratatui::Frame, area: Rect) {
+        let title = format!(" Schemes ({}) ", self.names.len());
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.names.is_empty() {
+            let placeholder = Paragraph::new(self.placeholder.as_str())
+                .style(Style::default().add_modifier(Modifier::DIM));
+            frame.render_widget(placeholder, inner);
+            return;
+        }
+
+        self.visible_height = (inner.height as usize).max(1);
+        self.scroll_to_selection();
+
+        let lines: Vec<Line> = self
+            .names
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.visible_height)
+            .map(|(i, name)| {
+                if i == self.selected {
+                    Line::styled(
+                        format!("> {name}"),
+                        Style::default().add_modifier(Modifier::REVERSED),
+                    )
+                } else {
+                    Line::raw(format!("  {name}"))
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+
+        if self.names.len() > self.visible_height {
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
+            let mut scrollbar_state = ScrollbarState::new(self.names.len()).position(self.selected);
+            frame.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        State::One(StateValue::Usize(self.selected))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        let page = self.visible_height.max(1);
+        let before = self.selected;
+        match cmd {
+            Cmd::Move(CmdDirection::Up) => self.select(self.selected.saturating_sub(1)),
+            Cmd::Move(CmdDirection::Down) => self.select(self.selected + 1),
+            Cmd::Scroll(CmdDirection::Up) => self.select(self.selected.saturating_sub(page)),
+            Cmd::Scroll(CmdDirection::Down) => self.select(self.selected + page),
+            Cmd::GoTo(Position::Begin) => self.select(0),
+            Cmd::GoTo(Position::End) => self.select(self.names.len()),
+            _ => return CmdResult::None,
+        }
+        if self.selected == before {
+            CmdResult::None
+        } else {
+            CmdResult::Changed(self.state())
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for SchemeList {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let Event::Keyboard(key_event) = ev else {
+            return None;
+        };
+
+        // Esc goes back and Enter loads (not mapped in dispatcher)
+        match key_event.code {
+            tuirealm::event::Key::Esc => return Some(Msg::Back),
+            tuirealm::event::Key::Enter if !self.names.is_empty() => return Some(Msg::Load),
+            _ => {}
+        }
+
+        let action = dispatcher().dispatch(&key_event)?;
+
+        if let Some(msg) = handle_global_app_events(&action) {
+            // Convert global Msg to our local Msg
+            return match msg {
+                crate::tui::activities::Msg::Quit => Some(Msg::Quit),
+                crate::tui::activities::Msg::SwitchToGallery => Some(Msg::Back), // Toggle back
+                crate::tui::activities::Msg::Regenerate => Some(Msg::Rescan),
+                _ => None,
+            };
+        }
+
+        let cmd = match action {
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Up)) => {
+                Cmd::Move(CmdDirection::Up)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Down)) => {
+                Cmd::Move(CmdDirection::Down)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::PageUp)) => {
+                Cmd::Scroll(CmdDirection::Up)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::PageDown)) => {
+                Cmd::Scroll(CmdDirection::Down)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Home)) => {
+                Cmd::GoTo(Position::Begin)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::End)) => Cmd::GoTo(Position::End),
+            _ => return None,
+        };
+        match self.perform(cmd) {
+            CmdResult::Changed(_) => Some(Msg::Select(self.selected)),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// GalleryActivity
+// ============================================================================
+
+#[derive(Default)]
+pub struct GalleryActivity {
+    app: Option<Application<Id, Msg, UserEvent>>,
+    context: Option<Context>,
+    exit_reason: Option<ExitReason>,
+    dir: Option<PathBuf>,
+    files: Vec<PathBuf>,
+    selected: usize,
+    palette: Palette,
+    preview: Preview,
+    /// Why the selected file couldn't be previewed
+    error: Option<String>,
+    needs_clear: bool,
+}
+
+impl GalleryActivity {
+    fn create_application() -> Application<Id, Msg, UserEvent> {
+        Application::init(
+            EventListenerCfg::default()
+                .crossterm_input_listener(Duration::from_millis(20), 10)
+                .poll_timeout(Duration::from_millis(50)),
+        )
+    }
+
+    /// Re-list the gallery directory, keeping the current file selected when
+    /// it still exists.
+    fn rescan(&mut self) {
+        let current = self.files.get(self.selected).cloned();
+        self.files = self.dir.as_deref().map(scheme_files).unwrap_or_default();
+        self.selected = current
+            .and_then(|path| self.files.iter().position(|f| *f == path))
+            .unwrap_or(0);
+    }
+
+    /// Name shown in the list: the path relative to the gallery directory.
+    fn display_name(&self, path: &Path) -> String {
+        self.dir
+            .as_deref()
+            .and_then(|dir| path.strip_prefix(dir).ok())
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    fn placeholder(&self) -> String {
+        match self.dir {
+            Some(ref dir) => format!("No scheme files in {}", dir.display()),
+            None => "Set [preview] gallery_dir to a directory of schemes".to_string(),
+        }
+    }
+
+    fn mount_list(&mut self) {
+        let names = self.files.iter().map(|f| self.display_name(f)).collect();
+        let list = SchemeList::new(names, self.selected, self.placeholder());
+        if let Some(ref mut app) = self.app {
+            let _ = app.umount(&Id::SchemeList);
+            let _ = app.mount(Id::SchemeList, Box::new(list), vec![]);
+            let _ = app.active(&Id::SchemeList);
+        }
+    }
+
+    /// Read the selected file into the palette and preview.
+    fn preview_selected(&mut self) {
+        let scheme = self.files.get(self.selected).map(|path| read_scheme(path));
+        let scheme = match scheme {
+            Some(Ok(scheme)) => {
+                self.error = None;
+                Some(scheme)
+            }
+            Some(Err(e)) => {
+                self.error = Some(format!("{e:#}"));
+                None
+            }
+            None => {
+                self.error = None;
+                None
+            }
+        };
+
+        self.palette = Palette::new();
+        if let Some(ref scheme) = scheme {
+            self.palette.set_colors(
+                slot_color(scheme, "base00"),
+                slot_color(scheme, "base07"),
+                CurveConfig::default(),
+            );
+        }
+        self.palette.set_scheme(scheme.clone());
+        self.preview.set_scheme(scheme);
+        self.needs_clear = true;
+    }
+}
+
+/// sRGB value of `slot`, black if missing.
+fn slot_color(scheme: &Base16Scheme, slot: &str) -> Srgb<u8> {
+    scheme
+        .palette
+        .get(slot)
+        .map(|c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+        .unwrap_or(Srgb::new(0, 0, 0))
+}
+
+impl Activity for GalleryActivity {
+    fn on_create(&mut self, context: Context) {
+        self.dir = context.model.gallery_dir.clone();
+        self.context = Some(context);
+        self.rescan();
+
+        self.app = Some(Self::create_application());
+        self.mount_list();
+        self.preview_selected();
+    }
+
+    fn on_draw(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+        // Force full redraw when switching schemes to prevent ghost text
+        if self.needs_clear {
+            terminal.clear()?;
+            self.needs_clear = false;
+        }
+
+        let app = self.app.as_mut().expect("app should be initialized");
+        let title = match self.dir {
+            Some(ref dir) => format!(" Scheme Gallery - {}", dir.display()),
+            None => " Scheme Gallery".to_string(),
+        };
+        let (palette, preview, error) = (&mut self.palette, &mut self.preview, &self.error);
+        let depth = self
+            .context
+            .as_ref()
+            .map_or_else(Default::default, |c| c.model.color_depth);
+
+        // Draw UI
+        terminal.draw(|frame| {
+            let area = frame.area();
+
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1), // Title
+                    Constraint::Min(10),   // Content
+                    Constraint::Length(1), // Status
+                ])
+                .split(area);
+
+            let title_widget =
+                Paragraph::new(title).style(Style::default().add_modifier(Modifier::BOLD));
+            frame.render_widget(title_widget, rows[0]);
+
+            // Content: scheme list | palette + preview of the selection
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .split(rows[1]);
+            app.view(&Id::SchemeList, frame, cols[0]);
+
+            match error {
+                Some(error) => {
+                    let block = Block::default().title(" Preview ").borders(Borders::ALL);
+                    let message = Paragraph::new(error.as_str()).block(block);
+                    frame.render_widget(message, cols[1]);
+                }
+                None => {
+                    let right_rows = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Min(10), Constraint::Percentage(50)])
+                        .split(cols[1]);
+                    palette.view(frame, right_rows[0]);
+                    preview.view(frame, right_rows[1]);
+                }
+            }
+
+            // Status bar
+            let status = format_footer(
+                GALLERY_FOOTER_ACTIONS,
+                &[("load", "Enter"), ("back", "Esc")],
+            );
+            let status_widget =
+                Paragraph::new(status).style(Style::default().add_modifier(Modifier::DIM));
+            frame.render_widget(status_widget, rows[2]);
+
+            downsample_buffer(frame.buffer_mut(), depth, palette.scheme());
+        })?;
+
+        // Process events through tui-realm
+        match app.tick(PollStrategy::Once) {
+            Ok(messages) => {
+                for msg in messages {
+                    match msg {
+                        Msg::Quit => {
+                            self.exit_reason = Some(ExitReason::Quit);
+                            return Ok(());
+                        }
+                        Msg::Back => {
+                            self.exit_reason = Some(ExitReason::SwitchToMain);
+                            return Ok(());
+                        }
+                        Msg::Select(index) => {
+                            self.selected = index;
+                            self.preview_selected();
+                        }
+                        Msg::Load => {
+                            if let Some(path) = self.files.get(self.selected).cloned()
+                                && let Some(ref mut context) = self.context
+                            {
+                                let load = crate::tui::activities::Msg::LoadScheme(path);
+                                context.model.update(Some(load));
+                            }
+                            self.exit_reason = Some(ExitReason::SwitchToMain);
+                            return Ok(());
+                        }
+                        Msg::Rescan => {
+                            self.rescan();
+                            self.mount_list();
+                            self.preview_selected();
+                        }
+                    }
+                }
+            }
+            Err(_) => {
+                // Timeout, continue
+            }
+        }
+
+        Ok(())
+    }
+
+    fn will_umount(&self) -> Option<&ExitReason> {
+        self.exit_reason.as_ref()
+    }
+
+    fn on_destroy(&mut self) -> Option<Context> {
+        self.app = None;
+        self.context.take()
+    }
+}
@@ -1,9 +1,11 @@
 //! Main activity - the primary palette editing screen.
 
 use std::io::Stdout;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use color_eyre::eyre::Result;
+use palette::Srgb;
 use ratatui::{
     Terminal,
     crossterm::event::{self, Event, KeyCode},
@@ -12,19 +14,30 @@ use ratatui::{
     style::{Modifier, Style},
     widgets::{Block, Borders, Paragraph},
 };
+use serde::{Deserialize, Serialize};
+use tuirealm::props::{AttrValue, PropPayload, PropValue};
 use tuirealm::{Application, EventListenerCfg, PollStrategy, Update};
 
+use crate::apca::apca_contrast;
 use crate::cli::VariantArg;
-use crate::curves::CurveType;
-use crate::tui::Model;
+use crate::contrast_matrix::ContrastMatrix;
+use crate::curves::{CurveType, MANUAL_STOPS};
+use crate::explore::ExploreParams;
+use crate::extended_accents::ExtendedAccents;
+use crate::logging::log_buffer;
+use crate::ramp::RAMP_SLOTS;
 use crate::tui::activity::{Activity, Context, ExitReason};
 use crate::tui::components::params::{
-    AccentControls, AccentControlsType, AccentValues, CurveControls, CurveValues, HellwigPicker,
-    HellwigPickerType, HellwigValues, HueGrid, WeightControls, WeightValues,
+    ACHIEVED_ATTR, AccentControls, AccentControlsType, AccentValues, CurveControls, CurveValues,
+    HUES_ATTR, HellwigPicker, HellwigPickerType, HellwigValues, HueGrid, HueWheel, RampControls,
+    WeightControls, WeightValues, WheelSpacing, hues_attr,
 };
 use crate::tui::components::{
-    MAIN_FOOTER_ACTIONS, Palette, Preview, Validation, format_footer, render_help,
+    LOG_PANEL_HEIGHT, MAIN_FOOTER_ACTIONS, Palette, Preview, Validation, format_footer,
+    render_curve_plot, render_explore, render_gamut_slice, render_help, render_log,
 };
+use crate::tui::session::ReplayMode;
+use crate::tui::{Model, downsample_buffer};
 
 // ============================================================================
 // Component identifiers (scoped to MainActivity)
@@ -33,9 +46,12 @@ use crate::tui::components::{
 /// Unique identifiers for all components in MainActivity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Id {
-    // Display panels (read-only)
+    // Display panels (the palette selects swatches to inspect)
     Palette,
     Preview,
+    // Opposite variant in the split view
+    CounterpartPalette,
+    CounterpartValidation,
 
     // Parameter groups (editable)
     BackgroundPicker,
@@ -45,6 +61,8 @@ pub enum Id {
     AccentControls,
     ExtendedAccentControls,
     HueOverrides,
+    RampControls,
+    HueWheel,
 
     // Scrollable panel
     Validation,
@@ -55,7 +73,9 @@ pub enum Id {
 // ============================================================================
 
 /// All possible messages that can be sent in MainActivity.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Serializable so parameter changes can be written to a session log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Msg {
     // Application control
     Quit,
@@ -76,6 +96,10 @@ pub enum Msg {
     ForegroundMChanged(f32),
     ForegroundHChanged(f32),
 
+    // Recall a remembered color (hex) into a picker
+    BackgroundRecalled(String),
+    ForegroundRecalled(String),
+
     // Numeric parameter changes
     MinContrastChanged(f64),
     ExtendedMinContrastChanged(f64),
@@ -88,9 +112,37 @@ pub enum Msg {
     ChromaCurveStrengthChanged(f32),
     HueCurveTypeChanged(CurveType),
     HueCurveStrengthChanged(f32),
+    // Bézier handles (x1, y1), (x2, y2) of a cubic_bezier curve
+    LightnessCurvePointsChanged([(f32, f32); 2]),
+    ChromaCurvePointsChanged([(f32, f32); 2]),
+    HueCurvePointsChanged([(f32, f32); 2]),
+    // Stops (base00-base07) of a manual curve
+    LightnessCurveStopsChanged([f32; MANUAL_STOPS]),
+    ChromaCurveStopsChanged([f32; MANUAL_STOPS]),
+    HueCurveStopsChanged([f32; MANUAL_STOPS]),
+    // Curve shown in the curve plot (0 lightness, 1 colorfulness, 2 hue)
+    CurveFocused(u8),
 
     // Hue override changes (index 0-7)
     HueOverrideChanged(u8, Option<f32>),
+    // Rotate all 8 hues together (degrees)
+    HueOverridesRotated(f32),
+    TogglePerceptualHues,
+    // Place the hues by the next color-harmony rule
+    CycleHarmony,
+    // Raise min/extended contrast to the highest feasible values
+    MaximizeContrast,
+    // Sample random parameter sets for the shuffle picker
+    Shuffle,
+    // Start from a candidate picked in the shuffle picker
+    ExplorePicked(ExploreParams),
+    // Pin or unpin a slot at its current color
+    TogglePin(String),
+    // Anchor a slot near a hex color, or clear its anchor
+    AnchorChanged(String, Option<String>),
+
+    // Lightness ramp target changes (index 0-5 = base01-base06)
+    RampTargetChanged(u8, Option<f64>),
 
     // Accent optimization changes
     AccentTargetJChanged(f32),
@@ -101,21 +153,29 @@ pub enum Msg {
     ExtendedDeltaJChanged(f32),
     ExtendedTargetMChanged(f32),
     ExtendedDeltaMChanged(f32),
+    ExtendedAccentsChanged(ExtendedAccents),
 
     // Optimization weight changes
     ContrastWeightChanged(f32),
     JWeightChanged(f32),
+    /// Preferred accent contrast (0 = off)
+    PreferredContrastChanged(f32),
 
     // Metadata changes
     NameChanged(String),
     AuthorChanged(String),
 
+    // Replace all parameters with those recovered from a scheme file
+    LoadScheme(PathBuf),
+
     // Palette regeneration (chained after parameter changes)
     Regenerate,
 
     // Export flow
     ExportPathChanged(String),
     DoExport,
+    // Save an image of the palette and preview next to the export path
+    Screenshot,
     ExportSuccess(String),
     ExportError(String),
 
@@ -123,11 +183,90 @@ pub enum Msg {
     ValidationScrollUp,
     ValidationScrollDown,
 
-    // Activity transition
+    // Activity transitions
     SwitchToCodePreview,
+    SwitchToGallery,
 
     // Toggle dark/light variant
     ToggleDarkLight,
+
+    // Apply the next message of a stepwise session replay
+    ReplayStep,
+
+    // Cycle the palette colorblindness simulation overlay
+    CycleCvdSimulation,
+
+    // Cycle the simulated display (dim, bright, gamma 2.4) for palette and preview
+    CycleDisplaySimulation,
+
+    // Show the full contrast matrix heatmap instead of the validation list
+    ToggleContrastMatrix,
+
+    // Show dark and light variants side by side, generated as a linked pair
+    ToggleSplitView,
+
+    // Show or hide the log panel under the editor
+    ToggleLog,
+
+    // Show the selected swatch's gamut slice in place of the preview
+    ToggleGamutSlice,
+
+    // Show the focused interpolation curve in place of the preview
+    ToggleCurvePlot,
+
+    // Star or unstar a color (hex) in the remembered colors
+    ToggleFavoriteColor(String),
+
+    // Pane resizing (delta in percent)
+    ResizeColumns(i16),
+    ResizePreview(i16),
+
+    // Show the next preview mockup
+    NextPreviewTab,
+
+    // Select a palette swatch (index in Base24 order) and show its inspector
+    SelectSwatch(usize),
+    ToggleSwatchInspector,
+}
+
+impl Msg {
+    /// Whether this message changes a palette parameter (recorded in session logs).
+    pub fn is_parameter_change(&self) -> bool {
+        !matches!(
+            self,
+            Msg::Quit
+                | Msg::ShowHelp
+                | Msg::HideHelp
+                | Msg::FocusNext
+                | Msg::FocusPrev
+                | Msg::Regenerate
+                | Msg::ExportPathChanged(_)
+                | Msg::DoExport
+                | Msg::Screenshot
+                | Msg::ExportSuccess(_)
+                | Msg::ExportError(_)
+                | Msg::ValidationScrollUp
+                | Msg::ValidationScrollDown
+                | Msg::SwitchToCodePreview
+                | Msg::SwitchToGallery
+                | Msg::ReplayStep
+                | Msg::CycleCvdSimulation
+                | Msg::CycleDisplaySimulation
+                | Msg::ToggleContrastMatrix
+                | Msg::ToggleSplitView
+                | Msg::ToggleLog
+                | Msg::ToggleGamutSlice
+                | Msg::ToggleCurvePlot
+                | Msg::CurveFocused(_)
+                | Msg::Shuffle
+                | Msg::ToggleFavoriteColor(_)
+                | Msg::ResizeColumns(_)
+                | Msg::ResizePreview(_)
+                | Msg::NextPreviewTab
+                | Msg::SelectSwatch(_)
+                | Msg::ToggleSwatchInspector
+        )
+    }
 }
 
 // ============================================================================
@@ -151,7 +290,10 @@ const ALL_FOCUS_IDS: &[Id] = &[
     Id::AccentControls,
     Id::ExtendedAccentControls,
     Id::HueOverrides,
+    Id::RampControls,
+    Id::HueWheel,
     Id::Validation,
+    Id::Palette,
 ];
 
 /// Manages focus state for Tab navigation in MainActivity.
@@ -217,17 +359,12 @@ impl MainActivity {
     /// Mount all initial components.
     fn mount_components(app: &mut Application<Id, Msg, UserEvent>, model: &Model) -> Result<()> {
         // Display components (read-only)
-        let mut palette = Palette::new();
-        palette.set_scheme(model.current_scheme.clone());
-        palette.set_colors(
-            model.background,
-            model.foreground,
-            model.interpolation.lightness.clone(),
-        );
-        app.mount(Id::Palette, Box::new(palette), vec![])?;
+        app.mount(Id::Palette, Box::new(Self::palette(model)), vec![])?;
 
         let mut preview = Preview::new();
         preview.set_scheme(model.current_scheme.clone());
+        preview.set_tab(model.preview_tab);
+        preview.set_simulation(model.display_simulation);
         app.mount(Id::Preview, Box::new(preview), vec![])?;
 
         // Parameter components
@@ -240,7 +377,8 @@ impl MainActivity {
                 out_of_gamut: model.background_hellwig.out_of_gamut,
             },
             model.background,
-        );
+        )
+        .with_memory(&model.color_memory);
         app.mount(Id::BackgroundPicker, Box::new(bg_picker), vec![])?;
 
         let fg_picker = HellwigPicker::new(
@@ -252,12 +390,24 @@ impl MainActivity {
                 out_of_gamut: model.foreground_hellwig.out_of_gamut,
             },
             model.foreground,
-        );
+        )
+        .with_memory(&model.color_memory);
         app.mount(Id::ForegroundPicker, Box::new(fg_picker), vec![])?;
 
-        let hue_grid = HueGrid::new(model.hue_overrides);
+        let hue_grid = HueGrid::new(model.hue_overrides)
+            .with_pins(model.pinned_hues())
+            .with_anchors(model.accent_anchors())
+            .with_links(model.linked_hues())
+            .with_harmony(model.harmony);
         app.mount(Id::HueOverrides, Box::new(hue_grid), vec![])?;
 
+        let hue_wheel = Self::hue_wheel(model);
+        app.mount(Id::HueWheel, Box::new(hue_wheel), vec![])?;
+
+        let ramp_controls = RampControls::new(model.ramp.targets());
+        app.mount(Id::RampControls, Box::new(ramp_controls), vec![])?;
+        Self::push_ramp_contrast(app, model);
+
         // Grouped curve controls (J/M/h interpolation)
         let curve_controls = CurveControls::new(CurveValues {
             j_type: model.interpolation.lightness.curve_type,
@@ -266,6 +416,12 @@ impl MainActivity {
             m_strength: model.interpolation.chroma.strength,
             h_type: model.interpolation.hue.curve_type,
             h_strength: model.interpolation.hue.strength,
+            j_points: model.interpolation.lightness.bezier_points(),
+            m_points: model.interpolation.chroma.bezier_points(),
+            h_points: model.interpolation.hue.bezier_points(),
+            j_stops: model.interpolation.lightness.manual_stops(),
+            m_stops: model.interpolation.chroma.manual_stops(),
+            h_stops: model.interpolation.hue.manual_stops(),
         });
         app.mount(Id::CurveControls, Box::new(curve_controls), vec![])?;
 
@@ -273,6 +429,7 @@ impl MainActivity {
         let weight_controls = WeightControls::new(WeightValues {
             contrast_weight: model.accent_opt.contrast_weight,
             j_weight: model.accent_opt.j_weight,
+            preferred_contrast: model.accent_opt.preferred_contrast.unwrap_or(0.0) as f32,
         });
         app.mount(Id::WeightControls, Box::new(weight_controls), vec![])?;
 
@@ -285,6 +442,7 @@ impl MainActivity {
                 delta_j: model.accent_opt.delta_j,
                 target_m: model.accent_opt.target_m,
                 delta_m: model.accent_opt.delta_m,
+                derivation: None,
             },
         );
         app.mount(Id::AccentControls, Box::new(accent_controls), vec![])?;
@@ -298,6 +456,7 @@ impl MainActivity {
                 delta_j: model.extended_accent_opt.delta_j,
                 target_m: model.extended_accent_opt.target_m,
                 delta_m: model.extended_accent_opt.delta_m,
+                derivation: Some(model.extended_accents),
             },
         );
         app.mount(
@@ -307,13 +466,8 @@ impl MainActivity {
         )?;
 
         // Validation panel
-        let mut validation = Validation::new();
-        validation.set_data(
-            model.validation_results.clone(),
-            model.generation_warnings.clone(),
-            model.current_scheme.is_some(),
-        );
-        app.mount(Id::Validation, Box::new(validation), vec![])?;
+        app.mount(Id::Validation, Box::new(Self::validation(model)), vec![])?;
+        Self::mount_counterpart(app, model);
 
         // Set initial focus
         app.active(&Id::BackgroundPicker)?;
@@ -325,33 +479,183 @@ impl MainActivity {
     fn sync_display_components(app: &mut Application<Id, Msg, UserEvent>, model: &Model) {
         // Remount Palette with updated scheme and colors
         let _ = app.umount(&Id::Palette);
-        let mut palette = Palette::new();
-        palette.set_scheme(model.current_scheme.clone());
-        palette.set_colors(
-            model.background,
-            model.foreground,
-            model.interpolation.lightness.clone(),
-        );
-        let _ = app.mount(Id::Palette, Box::new(palette), vec![]);
+        let _ = app.mount(Id::Palette, Box::new(Self::palette(model)), vec![]);
 
         // Remount Preview with updated scheme
         let _ = app.umount(&Id::Preview);
         let mut preview = Preview::new();
         preview.set_scheme(model.current_scheme.clone());
+        preview.set_tab(model.preview_tab);
+        preview.set_simulation(model.display_simulation);
         let _ = app.mount(Id::Preview, Box::new(preview), vec![]);
 
         // Remount Validation with updated results
         let _ = app.umount(&Id::Validation);
+        let _ = app.mount(Id::Validation, Box::new(Self::validation(model)), vec![]);
+
+        let _ = app.umount(&Id::CounterpartPalette);
+        let _ = app.umount(&Id::CounterpartValidation);
+        Self::mount_counterpart(app, model);
+
+        Self::push_ramp_contrast(app, model);
+    }
+
+    /// Palette panel for the edited variant.
+    pub(crate) fn palette(model: &Model) -> Palette {
+        let mut palette = Palette::new();
+        palette.set_scheme(model.current_scheme.clone());
+        palette.set_colors(
+            model.background,
+            model.foreground,
+            model.interpolation.lightness.clone(),
+        );
+        palette.set_cvd(model.cvd_overlay, model.cvd_threshold);
+        palette.set_simulation(model.display_simulation);
+        let slot = Palette::slot_name(model.selected_swatch);
+        palette.set_selection(
+            model.selected_swatch,
+            model.inspect_swatch.then(|| model.slot_stage(slot)),
+        );
+        if model.split_view
+            && let Some(ref scheme) = model.current_scheme
+        {
+            palette.set_label(scheme.variant.to_string());
+        }
+        palette
+    }
+
+    /// Validation panel for the edited variant.
+    fn validation(model: &Model) -> Validation {
         let mut validation = Validation::new();
         validation.set_data(
             model.validation_results.clone(),
             model.generation_warnings.clone(),
             model.current_scheme.is_some(),
         );
-        let _ = app.mount(Id::Validation, Box::new(validation), vec![]);
+        validation.set_matrix(Self::contrast_matrix(model));
+        if model.split_view
+            && let Some(ref scheme) = model.current_scheme
+        {
+            validation.set_label(scheme.variant.to_string());
+        }
+        validation
+    }
+
+    /// Mount the opposite variant's palette and validation panels, once the
+    /// split view has one to show.
+    fn mount_counterpart(app: &mut Application<Id, Msg, UserEvent>, model: &Model) {
+        let Some(ref counterpart) = model.counterpart else {
+            return;
+        };
+        let scheme = &counterpart.scheme;
+        let label = format!("{}, linked", scheme.variant);
+        let rgb = |slot: &str| {
+            scheme
+                .palette
+                .get(slot)
+                .map_or(Srgb::new(0, 0, 0), |c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+        };
+
+        let mut palette = Palette::new();
+        palette.set_scheme(Some(scheme.clone()));
+        palette.set_colors(
+            rgb("base00"),
+            rgb("base07"),
+            model.interpolation.lightness.clone(),
+        );
+        palette.set_cvd(model.cvd_overlay, model.cvd_threshold);
+        palette.set_simulation(model.display_simulation);
+        palette.set_label(label.clone());
+        let _ = app.mount(Id::CounterpartPalette, Box::new(palette), vec![]);
+
+        let mut validation = Validation::new();
+        validation.set_data(
+            Some(counterpart.validation.clone()),
+            counterpart.warnings.clone(),
+            true,
+        );
+        validation.set_matrix(
+            model
+                .show_contrast_matrix
+                .then(|| ContrastMatrix::new(scheme)),
+        );
+        validation.set_label(label);
+        let _ = app.mount(Id::CounterpartValidation, Box::new(validation), vec![]);
+    }
+
+    /// Hand the ramp editor the contrast base01-base06 reach, without
+    /// remounting it (which would drop its selection).
+    fn push_ramp_contrast(app: &mut Application<Id, Msg, UserEvent>, model: &Model) {
+        let Some(ref scheme) = model.current_scheme else {
+            return;
+        };
+        let rgb = |slot: &str| {
+            scheme
+                .palette
+                .get(slot)
+                .map(|c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+        };
+        let Some(base00) = rgb("base00") else {
+            return;
+        };
+        let achieved = RAMP_SLOTS
+            .iter()
+            .map(|slot| {
+                let lc = rgb(slot).map_or(0.0, |c| apca_contrast(c, base00).abs());
+                PropValue::F64(lc)
+            })
+            .collect();
+        let _ = app.attr(
+            &Id::RampControls,
+            ACHIEVED_ATTR,
+            AttrValue::Payload(PropPayload::Vec(achieved)),
+        );
+    }
+
+    /// Contrast matrix for the validation pane, when the heatmap is shown.
+    fn contrast_matrix(model: &Model) -> Option<ContrastMatrix> {
+        if !model.show_contrast_matrix {
+            return None;
+        }
+        model.current_scheme.as_ref().map(ContrastMatrix::new)
+    }
+
+    /// Hue wheel for the model's hues, marking perceptual spacing when on.
+    fn hue_wheel(model: &Model) -> HueWheel {
+        let spacing = model.perceptual_hues.then_some(WheelSpacing {
+            spacing: model.hue_spacing,
+            target_j: model.accent_opt.target_j,
+            target_m: model.accent_opt.target_m,
+            gamut: model.gamut,
+        });
+        HueWheel::new(model.hue_overrides, spacing)
+    }
+
+    /// Remount whichever hue editor (grid or wheel) is not focused, so both
+    /// show the same hues without resetting the focused one's selection.
+    /// Linked hues can move with an edit, so the focused editor is sent the
+    /// model's hues too.
+    fn sync_hue_editors(app: &mut Application<Id, Msg, UserEvent>, model: &Model, focused: Id) {
+        if focused != Id::HueOverrides {
+            let _ = app.umount(&Id::HueOverrides);
+            let hue_grid = HueGrid::new(model.hue_overrides)
+                .with_pins(model.pinned_hues())
+                .with_anchors(model.accent_anchors())
+                .with_links(model.linked_hues())
+                .with_harmony(model.harmony);
+            let _ = app.mount(Id::HueOverrides, Box::new(hue_grid), vec![]);
+        }
+        if focused != Id::HueWheel {
+            let _ = app.umount(&Id::HueWheel);
+            let hue_wheel = Self::hue_wheel(model);
+            let _ = app.mount(Id::HueWheel, Box::new(hue_wheel), vec![]);
+        }
+        if matches!(focused, Id::HueOverrides | Id::HueWheel) && !model.hue_links.is_empty() {
+            let _ = app.attr(&focused, HUES_ATTR, hues_attr(&model.hue_overrides));
+        }
     }
 
-    /// Sync all components including parameter editors (used after dark/light toggle).
+    /// Sync all components including parameter editors (used after dark/light toggle and replay).
     fn sync_all_components(app: &mut Application<Id, Msg, UserEvent>, model: &Model) {
         // Remount background picker
         let _ = app.umount(&Id::BackgroundPicker);
@@ -364,7 +668,8 @@ impl MainActivity {
                 out_of_gamut: model.background_hellwig.out_of_gamut,
             },
             model.background,
-        );
+        )
+        .with_memory(&model.color_memory);
         let _ = app.mount(Id::BackgroundPicker, Box::new(bg_picker), vec![]);
 
         // Remount foreground picker
@@ -378,7 +683,8 @@ impl MainActivity {
                 out_of_gamut: model.foreground_hellwig.out_of_gamut,
             },
             model.foreground,
-        );
+        )
+        .with_memory(&model.color_memory);
         let _ = app.mount(Id::ForegroundPicker, Box::new(fg_picker), vec![]);
 
         // Remount accent controls (target_j changed)
@@ -391,6 +697,7 @@ impl MainActivity {
                 delta_j: model.accent_opt.delta_j,
                 target_m: model.accent_opt.target_m,
                 delta_m: model.accent_opt.delta_m,
+                derivation: None,
             },
         );
         let _ = app.mount(Id::AccentControls, Box::new(accent_controls), vec![]);
@@ -405,6 +712,7 @@ impl MainActivity {
                 delta_j: model.extended_accent_opt.delta_j,
                 target_m: model.extended_accent_opt.target_m,
                 delta_m: model.extended_accent_opt.delta_m,
+                derivation: Some(model.extended_accents),
             },
         );
         let _ = app.mount(
@@ -413,6 +721,48 @@ impl MainActivity {
             vec![],
         );
 
+        // Remount curve, weight, and hue override editors
+        let _ = app.umount(&Id::CurveControls);
+        let curve_controls = CurveControls::new(CurveValues {
+            j_type: model.interpolation.lightness.curve_type,
+            j_strength: model.interpolation.lightness.strength,
+            m_type: model.interpolation.chroma.curve_type,
+            m_strength: model.interpolation.chroma.strength,
+            h_type: model.interpolation.hue.curve_type,
+            h_strength: model.interpolation.hue.strength,
+            j_points: model.interpolation.lightness.bezier_points(),
+            m_points: model.interpolation.chroma.bezier_points(),
+            h_points: model.interpolation.hue.bezier_points(),
+            j_stops: model.interpolation.lightness.manual_stops(),
+            m_stops: model.interpolation.chroma.manual_stops(),
+            h_stops: model.interpolation.hue.manual_stops(),
+        });
+        let _ = app.mount(Id::CurveControls, Box::new(curve_controls), vec![]);
+
+        let _ = app.umount(&Id::WeightControls);
+        let weight_controls = WeightControls::new(WeightValues {
+            contrast_weight: model.accent_opt.contrast_weight,
+            j_weight: model.accent_opt.j_weight,
+            preferred_contrast: model.accent_opt.preferred_contrast.unwrap_or(0.0) as f32,
+        });
+        let _ = app.mount(Id::WeightControls, Box::new(weight_controls), vec![]);
+
+        let _ = app.umount(&Id::HueOverrides);
+        let hue_grid = HueGrid::new(model.hue_overrides)
+            .with_pins(model.pinned_hues())
+            .with_anchors(model.accent_anchors())
+            .with_links(model.linked_hues())
+            .with_harmony(model.harmony);
+        let _ = app.mount(Id::HueOverrides, Box::new(hue_grid), vec![]);
+
+        let _ = app.umount(&Id::HueWheel);
+        let hue_wheel = Self::hue_wheel(model);
+        let _ = app.mount(Id::HueWheel, Box::new(hue_wheel), vec![]);
+
+        let _ = app.umount(&Id::RampControls);
+        let ramp_controls = RampControls::new(model.ramp.targets());
+        let _ = app.mount(Id::RampControls, Box::new(ramp_controls), vec![]);
+
         // Also sync display components
         Self::sync_display_components(app, model);
     }
@@ -435,16 +785,31 @@ impl Activity for MainActivity {
         let app = self.app.as_mut().expect("app should be initialized");
         let model = &mut self.context.as_mut().expect("context should be set").model;
 
+        // Pick up a palette finished by the regeneration thread
+        if model.poll_generation() {
+            Self::sync_display_components(app, model);
+        }
+        let spinner = model.regeneration_spinner();
+
+        // Warnings logged while the log panel is hidden are flagged in the title
+        let log_warnings = log_buffer().warning_count();
+        if model.show_log {
+            model.log_warnings_seen = log_warnings;
+        }
+        let new_warnings = log_warnings - model.log_warnings_seen;
+
         // Draw UI
         terminal.draw(|frame| {
             let area = frame.area();
 
+            let log_height = if model.show_log { LOG_PANEL_HEIGHT } else { 0 };
             let main_rows = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(1), // Title
-                    Constraint::Min(10),   // Content
-                    Constraint::Length(1), // Status
+                    Constraint::Length(1),          // Title
+                    Constraint::Min(10),            // Content
+                    Constraint::Length(log_height), // Log panel (when shown)
+                    Constraint::Length(1),          // Status
                 ])
                 .split(area);
 
@@ -457,27 +822,75 @@ impl Activity for MainActivity {
                     VariantArg::Dark => "dark",
                     VariantArg::Light => "light",
                     VariantArg::Both => "both",
+                    VariantArg::Linked => "linked",
                 }
             );
+            let title = match model.replay {
+                Some(ref replay) => {
+                    let hint = match replay.mode() {
+                        ReplayMode::Step => " (n: next)",
+                        ReplayMode::Timed(_) => "",
+                    };
+                    format!(
+                        "{}[replay {}/{}{}] ",
+                        title,
+                        replay.applied(),
+                        replay.total(),
+                        hint
+                    )
+                }
+                None => title,
+            };
+            let title = if new_warnings > 0 {
+                format!("{title}[{new_warnings} new log warnings] ")
+            } else {
+                title
+            };
             let title_widget =
                 Paragraph::new(title).style(Style::default().add_modifier(Modifier::BOLD));
             frame.render_widget(title_widget, main_rows[0]);
 
-            // Content: 2 columns
+            // Content: 2 columns, split per the adjustable layout
+            let layout = model.layout;
             let cols = Layout::default()
                 .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .constraints([
+                    Constraint::Percentage(layout.left_column),
+                    Constraint::Percentage(100 - layout.left_column),
+                ])
                 .split(main_rows[1]);
 
             // Left column: Palette + Preview
             let left_rows = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Min(10), Constraint::Length(13)])
+                .constraints([Constraint::Min(10), Constraint::Percentage(layout.preview)])
                 .split(cols[0]);
 
-            // Render components
-            app.view(&Id::Palette, frame, left_rows[0]);
-            app.view(&Id::Preview, frame, left_rows[1]);
+            // Render components; the split view puts the opposite variant
+            // beside the edited one
+            let split = model.split_view && model.counterpart.is_some();
+            let halves = |area| {
+                Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Ratio(1, 2); 2])
+                    .split(area)
+            };
+            if split {
+                let palettes = halves(left_rows[0]);
+                app.view(&Id::Palette, frame, palettes[0]);
+                app.view(&Id::CounterpartPalette, frame, palettes[1]);
+            } else {
+                app.view(&Id::Palette, frame, left_rows[0]);
+            }
+            match model.gamut_slice() {
+                Some(slice) if model.show_gamut_slice => {
+                    render_gamut_slice(frame, left_rows[1], &slice);
+                }
+                _ if model.show_curve_plot => {
+                    render_curve_plot(frame, left_rows[1], &model.curve_plot());
+                }
+                _ => app.view(&Id::Preview, frame, left_rows[1]),
+            }
 
             // Parameters section layout - heights defined once, total computed automatically
             const PARAM_HEIGHTS: &[u16] = &[
@@ -485,12 +898,14 @@ impl Activity for MainActivity {
                 4, // 1: Foreground picker (header + J/M/h)
                 1, // 2: Spacer
                 3, // 3: Curve controls (3 rows, inline strength)
-                2, // 4: Weight controls (grouped)
+                3, // 4: Weight controls (grouped)
                 1, // 5: Spacer
                 5, // 6: Accent controls (grouped)
-                5, // 7: Extended accent controls (grouped)
+                7, // 7: Extended accent controls (grouped, with derivation)
                 1, // 8: Spacer
                 3, // 9: Hue overrides
+                1, // 10: Spacer
+                3, // 11: Lightness ramp
             ];
             const PARAMS_CONTENT_HEIGHT: u16 = const {
                 let mut sum = 0u16;
@@ -516,7 +931,7 @@ impl Activity for MainActivity {
             let params_inner = params_block.inner(params_area);
             frame.render_widget(params_block, params_area);
 
-            let param_constraints: [Constraint; 11] = [
+            let param_constraints: [Constraint; 13] = [
                 Constraint::Length(PARAM_HEIGHTS[0]),
                 Constraint::Length(PARAM_HEIGHTS[1]),
                 Constraint::Length(PARAM_HEIGHTS[2]),
@@ -527,6 +942,8 @@ impl Activity for MainActivity {
                 Constraint::Length(PARAM_HEIGHTS[7]),
                 Constraint::Length(PARAM_HEIGHTS[8]),
                 Constraint::Length(PARAM_HEIGHTS[9]),
+                Constraint::Length(PARAM_HEIGHTS[10]),
+                Constraint::Length(PARAM_HEIGHTS[11]),
                 Constraint::Min(0),
             ];
             let param_rows = Layout::default()
@@ -541,24 +958,54 @@ impl Activity for MainActivity {
             app.view(&Id::AccentControls, frame, param_rows[6]);
             app.view(&Id::ExtendedAccentControls, frame, param_rows[7]);
             app.view(&Id::HueOverrides, frame, param_rows[9]);
+            app.view(&Id::RampControls, frame, param_rows[11]);
+
+            // Hue wheel beside the validation panel
+            let bottom_cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(26), Constraint::Fill(1)])
+                .split(right_rows[1]);
+            app.view(&Id::HueWheel, frame, bottom_cols[0]);
 
             // Validation panel
-            app.view(&Id::Validation, frame, right_rows[1]);
+            if split {
+                let panels = halves(bottom_cols[1]);
+                app.view(&Id::Validation, frame, panels[0]);
+                app.view(&Id::CounterpartValidation, frame, panels[1]);
+            } else {
+                app.view(&Id::Validation, frame, bottom_cols[1]);
+            }
+
+            if model.show_log {
+                render_log(frame, main_rows[2], log_buffer());
+            }
 
             // Status bar
-            let status = model
-                .message
-                .clone()
-                .unwrap_or_else(|| format_footer(MAIN_FOOTER_ACTIONS, &[("adjust", "[]/{}")]));
+            let status = match (&model.message, spinner) {
+                (Some(message), _) => message.clone(),
+                (None, Some(tick)) => format!("{tick} Regenerating…"),
+                (None, None) => format_footer(MAIN_FOOTER_ACTIONS, &[("adjust", "[]/{}")]),
+            };
 
             let status_widget =
                 Paragraph::new(status).style(Style::default().add_modifier(Modifier::DIM));
-            frame.render_widget(status_widget, main_rows[2]);
+            frame.render_widget(status_widget, main_rows[3]);
 
             // Help modal overlay
             if model.show_help {
                 render_help(frame);
             }
+
+            // Shuffle picker overlay
+            if !model.explore_candidates.is_empty() {
+                render_explore(frame, &model.explore_candidates, model.explore_selected);
+            }
+
+            downsample_buffer(
+                frame.buffer_mut(),
+                model.color_depth,
+                model.current_scheme.as_ref(),
+            );
         })?;
 
         // Handle help modal events separately (intercepts all input when visible)
@@ -574,11 +1021,48 @@ impl Activity for MainActivity {
             return Ok(());
         }
 
+        // Handle the shuffle picker separately (intercepts all input while open)
+        if !model.explore_candidates.is_empty() {
+            if let Event::Key(key) = event::read()? {
+                let last = model.explore_candidates.len() - 1;
+                match key.code {
+                    KeyCode::Up => {
+                        model.explore_selected = model.explore_selected.saturating_sub(1)
+                    }
+                    KeyCode::Down => {
+                        model.explore_selected = (model.explore_selected + 1).min(last)
+                    }
+                    KeyCode::Enter => {
+                        let candidate =
+                            model.explore_candidates.swap_remove(model.explore_selected);
+                        model.explore_candidates.clear();
+                        let mut current = Some(Msg::ExplorePicked(candidate.params));
+                        while let Some(m) = current {
+                            current = model.update(Some(m));
+                        }
+                        Self::sync_all_components(app, model);
+                        let _ = app.active(&self.focus.current_focus());
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => model.explore_candidates.clear(),
+                    _ => {}
+                }
+            }
+            return Ok(());
+        }
+
         // Use tick() - the canonical tui-realm heartbeat
         match app.tick(PollStrategy::Once) {
-            Ok(messages) => {
+            Ok(mut messages) => {
                 let mut needs_sync = false;
                 let mut needs_full_sync = false;
+                let mut needs_hue_sync = false;
+
+                // Replayed changes bypass the editors, so remount them afterwards
+                if let Some(ref mut replay) = model.replay {
+                    let due = replay.due();
+                    needs_full_sync = !due.is_empty();
+                    messages.extend(due);
+                }
 
                 for msg in messages {
                     // Handle focus changes at activity level
@@ -595,9 +1079,33 @@ impl Activity for MainActivity {
                             self.exit_reason = Some(ExitReason::SwitchToCodePreview);
                             return Ok(());
                         }
-                        Msg::ToggleDarkLight => {
+                        Msg::SwitchToGallery => {
+                            self.exit_reason = Some(ExitReason::SwitchToGallery);
+                            return Ok(());
+                        }
+                        Msg::ToggleDarkLight
+                        | Msg::ReplayStep
+                        | Msg::TogglePerceptualHues
+                        | Msg::CycleHarmony
+                        | Msg::MaximizeContrast
+                        | Msg::ToggleFavoriteColor(_) => {
                             needs_full_sync = true;
                         }
+                        Msg::CycleCvdSimulation
+                        | Msg::CycleDisplaySimulation
+                        | Msg::ToggleContrastMatrix
+                        | Msg::ToggleSplitView
+                        | Msg::NextPreviewTab
+                        | Msg::SelectSwatch(_)
+                        | Msg::ToggleSwatchInspector => {
+                            needs_sync = true;
+                        }
+                        Msg::HueOverrideChanged(..)
+                        | Msg::HueOverridesRotated(_)
+                        | Msg::TogglePin(_)
+                        | Msg::AnchorChanged(..) => {
+                            needs_hue_sync = true;
+                        }
                         _ => {}
                     }
 
@@ -625,6 +1133,13 @@ impl Activity for MainActivity {
                     let _ = app.active(&self.focus.current_focus());
                 } else if needs_sync {
                     Self::sync_display_components(app, model);
+                    // The palette and validation panels are remounted
+                    if matches!(self.focus.current_focus(), Id::Palette | Id::Validation) {
+                        let _ = app.active(&self.focus.current_focus());
+                    }
+                }
+                if needs_hue_sync && !needs_full_sync {
+                    Self::sync_hue_editors(app, model, self.focus.current_focus());
                 }
             }
             Err(_) => {
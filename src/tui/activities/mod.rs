@@ -1,8 +1,10 @@
 //! Activity modules for the TUI.
 
 pub mod code_preview;
+pub mod gallery;
 pub mod main;
 
 pub use code_preview::CodePreviewActivity;
+pub use gallery::GalleryActivity;
 pub use main::MainActivity;
 pub use main::Msg;
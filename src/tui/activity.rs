@@ -9,7 +9,7 @@ use color_eyre::eyre::Result;
 use ratatui::{Terminal, prelude::CrosstermBackend};
 
 use super::Model;
-use super::activities::{CodePreviewActivity, MainActivity};
+use super::activities::{CodePreviewActivity, GalleryActivity, MainActivity};
 
 /// Shared context passed between activities.
 pub struct Context {
@@ -22,6 +22,7 @@ pub enum ExitReason {
     Quit,
     SwitchToMain,
     SwitchToCodePreview,
+    SwitchToGallery,
 }
 
 /// Activity lifecycle trait.
@@ -46,6 +47,7 @@ pub trait Activity {
 pub enum ActivityType {
     Main,
     CodePreview,
+    Gallery,
 }
 
 /// Manages activity lifecycle and transitions.
@@ -62,11 +64,17 @@ impl ActivityManager {
         }
     }
 
+    /// Shared context, available whenever no activity is running.
+    pub fn context_mut(&mut self) -> Option<&mut Context> {
+        self.context.as_mut()
+    }
+
     pub fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
         loop {
             let mut activity: Box<dyn Activity> = match self.current {
                 ActivityType::Main => Box::<MainActivity>::default(),
                 ActivityType::CodePreview => Box::<CodePreviewActivity>::default(),
+                ActivityType::Gallery => Box::<GalleryActivity>::default(),
             };
 
             activity.on_create(self.context.take().expect("context should be available"));
@@ -77,7 +85,7 @@ impl ActivityManager {
                 if let Some(reason) = activity.will_umount() {
                     match reason {
                         ExitReason::Quit => {
-                            activity.on_destroy();
+                            self.context = activity.on_destroy();
                             return Ok(());
                         }
                         ExitReason::SwitchToMain => {
@@ -90,6 +98,11 @@ impl ActivityManager {
                             self.current = ActivityType::CodePreview;
                             break;
                         }
+                        ExitReason::SwitchToGallery => {
+                            self.context = activity.on_destroy();
+                            self.current = ActivityType::Gallery;
+                            break;
+                        }
                     }
                 }
             }
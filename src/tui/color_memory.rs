@@ -0,0 +1,84 @@
+//! Recently used and favorite background/foreground colors.
+//!
+//! Theme families reuse a handful of anchor colors, so the TUI remembers the
+//! colors of each exported or closed session and lets the pickers recall
+//! them. Stored next to the pane layout in
+//! `$XDG_STATE_HOME/themalingadingdong/colors.toml`.
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use super::layout::state_dir;
+
+/// Most recent colors kept (favorites are not counted).
+const MAX_RECENT: usize = 12;
+
+/// Remembered colors as lowercase hex without `#`, most recent first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorMemory {
+    pub favorites: Vec<String>,
+    pub recent: Vec<String>,
+}
+
+impl ColorMemory {
+    /// Default location of the color memory file, if a home directory is known.
+    pub fn state_path() -> Option<PathBuf> {
+        Some(state_dir()?.join("colors.toml"))
+    }
+
+    /// Load remembered colors, starting empty if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).wrap_err_with(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save remembered colors, creating parent directories.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Move `hex` to the front of the recent list.
+    pub fn remember(&mut self, hex: &str) {
+        let hex = hex.to_lowercase();
+        self.recent.retain(|h| *h != hex);
+        self.recent.insert(0, hex);
+        self.recent.truncate(MAX_RECENT);
+    }
+
+    /// Add `hex` to the favorites, or remove it if already there.
+    /// Returns whether it is now a favorite.
+    pub fn toggle_favorite(&mut self, hex: &str) -> bool {
+        let hex = hex.to_lowercase();
+        if let Some(pos) = self.favorites.iter().position(|h| *h == hex) {
+            self.favorites.remove(pos);
+            false
+        } else {
+            self.favorites.insert(0, hex);
+            true
+        }
+    }
+
+    /// Favorites followed by recent colors that are not also favorites.
+    pub fn entries(&self) -> Vec<String> {
+        let mut entries = self.favorites.clone();
+        entries.extend(
+            self.recent
+                .iter()
+                .filter(|h| !self.favorites.contains(h))
+                .cloned(),
+        );
+        entries
+    }
+}
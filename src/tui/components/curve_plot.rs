@@ -0,0 +1,136 @@
+//! Plot of one interpolation curve, output (up) against input (right).
+//!
+//! The curve is drawn in braille with the eight surface samples
+//! (base00-base07) marked, and a cubic Bézier's handles shown with their
+//! guide lines so editing them in the curve controls can be followed.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::Span,
+    widgets::{
+        Block, Borders, Paragraph,
+        canvas::{Canvas, Line as CanvasLine},
+    },
+};
+
+use crate::curves::{CurveConfig, CurveType, compute_sample_positions, evaluate_curve};
+
+/// Surface slots sampled along a curve.
+const SAMPLES: usize = 8;
+
+/// Braille dots across the plot per terminal cell.
+const DOTS_PER_CELL: usize = 2;
+
+/// What the plot shows.
+#[derive(Debug, Clone)]
+pub struct CurvePlot {
+    /// "Lightness", "Colorfulness", or "Hue"
+    pub label: &'static str,
+    pub curve: CurveConfig,
+}
+
+/// Render `plot` into `area`.
+pub fn render_curve_plot(frame: &mut Frame, area: Rect, plot: &CurvePlot) {
+    let block = Block::default()
+        .title(format!(
+            " {} curve: {} ",
+            plot.label,
+            plot.curve.curve_type.display_name()
+        ))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height < 4 || inner.width < 10 {
+        return;
+    }
+    let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+
+    let samples = compute_sample_positions(SAMPLES, &plot.curve);
+    let steps = rows[0].width as usize * DOTS_PER_CELL;
+    let trace: Vec<(f64, f64)> = (0..=steps)
+        .map(|i| {
+            let t = i as f32 / steps as f32;
+            (f64::from(t), f64::from(evaluate_curve(&plot.curve, t)))
+        })
+        .collect();
+    let handles = (plot.curve.curve_type == CurveType::CubicBezier).then(|| {
+        plot.curve
+            .bezier_points()
+            .map(|(x, y)| (f64::from(x), f64::from(y)))
+    });
+
+    let canvas = Canvas::default()
+        .marker(Marker::Braille)
+        .x_bounds([0.0, 1.0])
+        .y_bounds([0.0, 1.0])
+        .paint(|ctx| {
+            // Identity diagonal for reference
+            ctx.draw(&CanvasLine {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 1.0,
+                y2: 1.0,
+                color: Color::DarkGray,
+            });
+            if let Some([p1, p2]) = handles {
+                for (from, to) in [((0.0, 0.0), p1), ((1.0, 1.0), p2)] {
+                    ctx.draw(&CanvasLine {
+                        x1: from.0,
+                        y1: from.1,
+                        x2: to.0,
+                        y2: to.1,
+                        color: Color::Yellow,
+                    });
+                }
+            }
+            ctx.layer();
+
+            // Consecutive points joined, so steep stretches have no gaps
+            for pair in trace.windows(2) {
+                ctx.draw(&CanvasLine {
+                    x1: pair[0].0,
+                    y1: pair[0].1,
+                    x2: pair[1].0,
+                    y2: pair[1].1,
+                    color: Color::Cyan,
+                });
+            }
+            ctx.layer();
+
+            for (i, &y) in samples.iter().enumerate() {
+                let x = i as f64 / (SAMPLES - 1) as f64;
+                ctx.print(
+                    x,
+                    f64::from(y),
+                    Span::styled(
+                        "●",
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                );
+            }
+            if let Some(handles) = handles {
+                for (x, y) in handles {
+                    ctx.print(x, y, Span::styled("○", Style::default().fg(Color::Yellow)));
+                }
+            }
+        });
+    frame.render_widget(canvas, rows[0]);
+
+    let values: Vec<String> = samples.iter().map(|t| format!("{t:.2}")).collect();
+    let mut legend = format!("● base00-07 at {}", values.join(" "));
+    if let Some([p1, p2]) = handles {
+        legend.push_str(&format!(
+            "  ○ ({:.2}, {:.2}) ({:.2}, {:.2})",
+            p1.0, p1.1, p2.0, p2.1
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(legend).style(Style::default().add_modifier(Modifier::DIM)),
+        rows[1],
+    );
+}
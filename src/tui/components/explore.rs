@@ -0,0 +1,65 @@
+//! Shuffle picker listing the best explored candidates.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::help::popup_area;
+use crate::explore::Candidate;
+
+/// Accent slots shown as swatches for each candidate.
+const SWATCH_SLOTS: [&str; 8] = [
+    "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+];
+
+/// Render the shuffle picker over the editor, with `selected` highlighted.
+pub fn render_explore(frame: &mut Frame, candidates: &[Candidate], selected: usize) {
+    let area = popup_area(frame.area(), 80, 50);
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Shuffle: best candidates ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let layout = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+
+    let mut lines = Vec::new();
+    for (i, candidate) in candidates.iter().enumerate() {
+        let marker = if i == selected { "> " } else { "  " };
+        let mut spans = vec![Span::raw(format!(
+            "{marker}{}. {:5.1}  ",
+            i + 1,
+            candidate.score.score
+        ))];
+        for slot in SWATCH_SLOTS {
+            if let Some(color) = candidate.scheme.palette.get(slot) {
+                let (r, g, b) = color.rgb;
+                spans.push(Span::styled("  ", Style::default().bg(Color::Rgb(r, g, b))));
+            }
+        }
+        spans.push(Span::raw(format!("  {}", candidate.params)));
+        let line = Line::from(spans);
+        lines.push(if i == selected {
+            line.style(Style::default().add_modifier(Modifier::BOLD))
+        } else {
+            line
+        });
+    }
+    frame.render_widget(Paragraph::new(lines), layout[0]);
+
+    let footer = Paragraph::new(Line::from(Span::styled(
+        "Up/Down: select | Enter: start from it | Esc: keep current",
+        Style::default()
+            .fg(Color::Gray)
+            .add_modifier(Modifier::ITALIC),
+    )))
+    .alignment(ratatui::layout::Alignment::Center);
+    frame.render_widget(footer, layout[1]);
+}
@@ -0,0 +1,127 @@
+//! Constant-hue slice of the sRGB gamut, plotted J' (up) against M (right).
+//!
+//! Shows the selected swatch's hue with the accent solver's target box and
+//! the swatch's own J'/M, so a box reaching past the gamut wall stands out.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::gamut_map::{Gamut, cusp_at_hue, gamut_slice};
+use crate::hellwig::HellwigJmh;
+use crate::viewing::{ViewingConditions, with_viewing_conditions};
+
+/// What the slice plots.
+#[derive(Debug, Clone)]
+pub struct GamutSlice {
+    pub slot: &'static str,
+    pub hue: f32,
+    /// The swatch's J' and M
+    pub point: (f32, f32),
+    /// Solver target box as (J' low, J' high, M low, M high), for accents
+    pub target: Option<(f32, f32, f32, f32)>,
+    pub viewing: ViewingConditions,
+}
+
+/// Render `slice` into `area`.
+pub fn render_gamut_slice(frame: &mut Frame, area: Rect, slice: &GamutSlice) {
+    with_viewing_conditions(&slice.viewing, || draw(frame, area, slice));
+}
+
+fn draw(frame: &mut Frame, area: Rect, slice: &GamutSlice) {
+    let cusp = cusp_at_hue(slice.hue);
+    let block = Block::default()
+        .title(format!(
+            " Gamut: {} at {:.0}° (cusp J' {:.0}, M {:.0}) ",
+            slice.slot, slice.hue, cusp.j, cusp.m
+        ))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height < 4 || inner.width < 10 {
+        return;
+    }
+    let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(inner);
+    let plot = rows[0];
+    let (width, height) = (plot.width as usize, plot.height as usize);
+
+    // Cell centers: J' falls from 100 at the top, M grows to the right
+    let j_at = |row: usize| 100.0 * (height - row) as f32 / (height + 1) as f32;
+    // Boundary at every whole J'
+    let wall = gamut_slice(slice.hue, Gamut::Srgb, 101);
+    let boundary: Vec<f32> = (0..height)
+        .map(|row| wall[j_at(row).round() as usize].1)
+        .collect();
+    let widest = slice
+        .target
+        .map_or(0.0, |(_, _, _, m_hi)| m_hi)
+        .max(slice.point.1)
+        .max(cusp.m);
+    let m_axis = (widest * 1.1 / 10.0).ceil() * 10.0;
+    let m_at = |col: usize| m_axis * (col as f32 + 0.5) / width as f32;
+
+    let in_box = |row: usize, col: usize| {
+        slice.target.is_some_and(|(j_lo, j_hi, m_lo, m_hi)| {
+            (j_lo..=j_hi).contains(&j_at(row)) && (m_lo..=m_hi).contains(&m_at(col))
+        })
+    };
+    // Nearest cell to the swatch
+    let point_row = ((100.0 - slice.point.0) / 100.0 * (height + 1) as f32 - 1.0)
+        .round()
+        .clamp(0.0, (height - 1) as f32) as usize;
+    let point_col = (slice.point.1 / m_axis * width as f32 - 0.5)
+        .round()
+        .clamp(0.0, (width - 1) as f32) as usize;
+
+    let lines: Vec<Line> = (0..height)
+        .map(|row| {
+            let spans: Vec<Span> = (0..width)
+                .map(|col| {
+                    let j = j_at(row);
+                    let mut style = Style::default();
+                    if m_at(col) <= boundary[row] {
+                        let c = HellwigJmh::new(j, m_at(col), slice.hue).into_srgb_u8();
+                        style = style.bg(Color::Rgb(c.red, c.green, c.blue));
+                    }
+                    style = style.fg(if j > 60.0 { Color::Black } else { Color::White });
+                    // Outline the target box: box cells beside a cell outside it
+                    let edge = in_box(row, col)
+                        && (row == 0
+                            || col == 0
+                            || row + 1 == height
+                            || col + 1 == width
+                            || !in_box(row - 1, col)
+                            || !in_box(row + 1, col)
+                            || !in_box(row, col - 1)
+                            || !in_box(row, col + 1));
+                    let symbol = if (row, col) == (point_row, point_col) {
+                        "●"
+                    } else if edge {
+                        "·"
+                    } else {
+                        " "
+                    };
+                    Span::styled(symbol, style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), plot);
+
+    let mut legend = format!(
+        "J' 0-100 ↑  M 0-{m_axis:.0} →  ● J' {:.1} M {:.1}",
+        slice.point.0, slice.point.1
+    );
+    if slice.target.is_some() {
+        legend.push_str("  · target box");
+    }
+    frame.render_widget(
+        Paragraph::new(legend).style(Style::default().add_modifier(Modifier::DIM)),
+        rows[1],
+    );
+}
@@ -31,8 +31,40 @@ const HELP_GROUPS: &[KeybindingGroup] = &[
         title: "Actions",
         actions: &[
             AppAction::CodePreview,
+            AppAction::Gallery,
             AppAction::Export,
+            AppAction::Screenshot,
             AppAction::ToggleDarkLight,
+            AppAction::CycleCvd,
+            AppAction::CycleDisplaySimulation,
+            AppAction::ToggleContrastMatrix,
+            AppAction::ToggleSplitView,
+            AppAction::ToggleLog,
+            AppAction::ToggleGamutSlice,
+            AppAction::ToggleCurvePlot,
+            AppAction::TogglePerceptualHues,
+            AppAction::CycleHarmony,
+            AppAction::MaximizeContrast,
+            AppAction::Shuffle,
+            AppAction::ReplayStep,
+        ],
+    },
+    KeybindingGroup {
+        title: "Color Pickers",
+        actions: &[AppAction::RecallColor, AppAction::ToggleFavoriteColor],
+    },
+    KeybindingGroup {
+        title: "Hue Overrides",
+        actions: &[AppAction::TogglePin, AppAction::EditAnchor],
+    },
+    KeybindingGroup {
+        title: "Layout",
+        actions: &[
+            AppAction::ShrinkLeftColumn,
+            AppAction::GrowLeftColumn,
+            AppAction::ShrinkPreview,
+            AppAction::GrowPreview,
+            AppAction::NextPreviewTab,
         ],
     },
     KeybindingGroup {
@@ -65,15 +97,30 @@ const HELP_GROUPS: &[KeybindingGroup] = &[
 /// Extra keybindings not in dispatcher (component-specific behaviors).
 /// Format: (description, keys)
 const EXTRA_BINDINGS: &[(&str, &str)] = &[
-    ("Edit hue value", "Enter"),
-    ("Quick edit", "0-9"),
+    ("Type a value", "Enter"),
+    ("Type a new value", "0-9"),
     ("Cancel edit", "Esc"),
     ("Delete character", "Backspace"),
 ];
 
+/// Hue wheel keybindings (component-specific behaviors).
+const HUE_WHEEL_BINDINGS: &[(&str, &str)] = &[
+    ("Select hue", "Up, Down"),
+    ("Rotate hue", "Left, Right"),
+    ("Rotate all hues", "Enter (toggle)"),
+];
+
+/// Palette keybindings (component-specific behaviors).
+const PALETTE_BINDINGS: &[(&str, &str)] = &[
+    ("Select swatch", "Arrows"),
+    ("Inspect swatch", "Enter (toggle)"),
+    ("Close inspector", "Esc"),
+];
+
 /// Actions shown in the main activity footer.
 pub const MAIN_FOOTER_ACTIONS: &[AppAction] = &[
     AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)),
+    AppAction::Tui(TuiEvent::App(AppEvent::Refresh)),
     AppAction::CodePreview,
     AppAction::Export,
     AppAction::ToggleDarkLight,
@@ -82,9 +129,17 @@ pub const MAIN_FOOTER_ACTIONS: &[AppAction] = &[
     AppAction::Tui(TuiEvent::App(AppEvent::Refresh)),
 ];
 
+/// Actions shown in the scheme gallery footer.
+pub const GALLERY_FOOTER_ACTIONS: &[AppAction] = &[
+    AppAction::Tui(TuiEvent::App(AppEvent::Refresh)),
+    AppAction::Gallery,
+    AppAction::Tui(TuiEvent::App(AppEvent::Quit)),
+];
+
 /// Actions shown in the code preview footer.
 pub const CODE_PREVIEW_FOOTER_ACTIONS: &[AppAction] = &[
     AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)),
+    AppAction::Tui(TuiEvent::App(AppEvent::Refresh)),
     AppAction::CodePreview,
     AppAction::Tui(TuiEvent::App(AppEvent::Help)),
     AppAction::Tui(TuiEvent::App(AppEvent::Quit)),
@@ -117,7 +172,7 @@ pub fn format_footer(actions: &[AppAction], extras: &[(&str, &str)]) -> String {
 }
 
 /// Calculate a centered popup area with given width/height percentages.
-fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+pub(crate) fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
     let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
     let [area] = vertical.areas(area);
@@ -175,14 +230,20 @@ pub fn render_help(frame: &mut Frame) {
     }
 
     // Extra component-specific bindings
-    lines.push(Line::from(Span::styled("Hue Override Editing", bold)));
-    for (description, keys) in EXTRA_BINDINGS {
-        lines.push(Line::from(vec![
-            Span::styled(format!("  {description:<20}"), Style::default()),
-            Span::styled(*keys, dim),
-        ]));
+    for (title, bindings) in [
+        ("Hue Override Editing", EXTRA_BINDINGS),
+        ("Hue Wheel", HUE_WHEEL_BINDINGS),
+        ("Palette", PALETTE_BINDINGS),
+    ] {
+        lines.push(Line::from(Span::styled(title, bold)));
+        for (description, keys) in bindings {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {description:<20}"), Style::default()),
+                Span::styled(*keys, dim),
+            ]));
+        }
+        lines.push(Line::from(""));
     }
-    lines.push(Line::from(""));
 
     let content = Paragraph::new(lines);
     frame.render_widget(content, content_area);
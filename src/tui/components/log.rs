@@ -0,0 +1,58 @@
+//! Log panel showing the latest tracing events as they are recorded.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use tracing::Level;
+
+use crate::logging::LogBuffer;
+
+/// Rows the expanded log panel takes, borders included.
+pub const LOG_PANEL_HEIGHT: u16 = 8;
+
+/// Render the newest events of `buffer` that fit in `area`, oldest at the
+/// top so the panel reads like a tailed log file.
+pub fn render_log(frame: &mut Frame, area: Rect, buffer: &LogBuffer) {
+    let block = Block::default()
+        .title(format!(" Log ({} events) ", buffer.len()))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let entries = buffer.tail(inner.height as usize);
+    if entries.is_empty() {
+        let placeholder =
+            Paragraph::new("No log events yet").style(Style::default().add_modifier(Modifier::DIM));
+        frame.render_widget(placeholder, inner);
+        return;
+    }
+
+    let dim = Style::default().add_modifier(Modifier::DIM);
+    let lines: Vec<Line> = entries
+        .into_iter()
+        .map(|entry| {
+            let level_style = match entry.level {
+                Level::ERROR => Style::default().fg(Color::Red),
+                Level::WARN => Style::default().fg(Color::Yellow),
+                Level::INFO => Style::default().fg(Color::Green),
+                _ => dim,
+            };
+            // Drop the crate prefix; every target starts with it
+            let target = entry
+                .target
+                .strip_prefix("themalingadingdong::")
+                .unwrap_or(&entry.target)
+                .to_string();
+            Line::from(vec![
+                Span::styled(format!("{:>5} ", entry.level), level_style),
+                Span::styled(format!("{target}: "), dim),
+                Span::raw(entry.message),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
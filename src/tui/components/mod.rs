@@ -1,12 +1,23 @@
 //! TUI components using tui-realm.
 
+pub mod curve_plot;
+pub mod explore;
+pub mod gamut_slice;
 pub mod help;
+pub mod log;
 pub mod palette;
 pub mod params;
 pub mod preview;
 pub mod validation;
 
-pub use help::{CODE_PREVIEW_FOOTER_ACTIONS, MAIN_FOOTER_ACTIONS, format_footer, render_help};
+pub use curve_plot::{CurvePlot, render_curve_plot};
+pub use explore::render_explore;
+pub use gamut_slice::{GamutSlice, render_gamut_slice};
+pub use help::{
+    CODE_PREVIEW_FOOTER_ACTIONS, GALLERY_FOOTER_ACTIONS, MAIN_FOOTER_ACTIONS, format_footer,
+    render_help,
+};
+pub use log::{LOG_PANEL_HEIGHT, render_log};
 pub use palette::Palette;
 pub use preview::Preview;
 pub use validation::Validation;
@@ -1,5 +1,11 @@
 //! Palette display Component showing 24 color swatches.
+//!
+//! When focused, the arrow keys select a swatch and Enter opens an
+//! inspector beside the swatches with the color's values, its contrast
+//! against base00/base01/base05, its nearest xterm-256 color, and the
+//! generation stage that produced it.
 
+use crossterm_actions::{InputEvent, NavigationEvent, SelectionEvent, TuiEvent};
 use palette::Srgb;
 use ratatui::Frame;
 use ratatui::{
@@ -15,8 +21,15 @@ use tuirealm::{
     props::{AttrValue, Attribute, Props},
 };
 
+use crate::apca::apca_contrast;
+use crate::convert::ColorReport;
 use crate::curves::{CurveConfig, compute_sample_positions};
+use crate::cvd::{CvdKind, DEFAULT_CVD_THRESHOLD, check_cvd, simulate_scheme};
+use crate::display_sim::DisplaySimulation;
+use crate::interpolation::srgb_to_f32;
 use crate::tui::activities::{Msg, main::UserEvent};
+use crate::tui::{AppAction, dispatcher, handle_global_app_events};
+use crate::xterm256::nearest_xterm;
 
 /// Color names in Base24 order.
 const COLOR_NAMES: [&str; 24] = [
@@ -25,6 +38,15 @@ const COLOR_NAMES: [&str; 24] = [
     "base12", "base13", "base14", "base15", "base16", "base17",
 ];
 
+/// Swatches per row.
+const ROW_LEN: usize = 8;
+
+/// Slots the inspector measures contrast against.
+const INSPECT_BACKGROUNDS: [&str; 3] = ["base00", "base01", "base05"];
+
+/// Width of the inspector pane, borders included.
+const INSPECTOR_WIDTH: u16 = 30;
+
 /// Palette display component showing gradient and 24 color swatches.
 pub struct Palette {
     props: Props,
@@ -32,6 +54,15 @@ pub struct Palette {
     background: Srgb<u8>,
     foreground: Srgb<u8>,
     curve: CurveConfig,
+    cvd: Option<CvdKind>,
+    cvd_threshold: f32,
+    simulation: Option<DisplaySimulation>,
+    /// Shown in the title, e.g. the variant in the split view
+    label: Option<String>,
+    /// Selected swatch (index in Base24 order)
+    selected: usize,
+    /// Generation stage of the selected swatch, when its inspector is open
+    inspector: Option<String>,
 }
 
 impl Palette {
@@ -42,9 +73,30 @@ impl Palette {
             background: Srgb::new(0, 0, 0),
             foreground: Srgb::new(255, 255, 255),
             curve: CurveConfig::default(),
+            cvd: None,
+            cvd_threshold: DEFAULT_CVD_THRESHOLD,
+            simulation: None,
+            label: None,
+            selected: 0,
+            inspector: None,
         }
     }
 
+    /// Slot name of the swatch at `index` in Base24 order.
+    pub fn slot_name(index: usize) -> &'static str {
+        COLOR_NAMES[index.min(COLOR_NAMES.len() - 1)]
+    }
+
+    /// Select a swatch, and open its inspector showing `stage` (None closes it).
+    pub fn set_selection(&mut self, index: usize, stage: Option<String>) {
+        self.selected = index.min(COLOR_NAMES.len() - 1);
+        self.inspector = stage;
+    }
+
+    pub fn scheme(&self) -> Option<&Base16Scheme> {
+        self.scheme.as_ref()
+    }
+
     pub fn set_scheme(&mut self, scheme: Option<Base16Scheme>) {
         self.scheme = scheme;
     }
@@ -55,7 +107,30 @@ impl Palette {
         self.curve = curve;
     }
 
-    fn draw_swatch(&self, frame: &mut Frame, area: Rect, name: &str, rgb: (u8, u8, u8)) {
+    /// Show swatches as seen with a color vision deficiency (None = normal vision).
+    pub fn set_cvd(&mut self, kind: Option<CvdKind>, threshold: f32) {
+        self.cvd = kind;
+        self.cvd_threshold = threshold;
+    }
+
+    /// Show swatches as seen on a simulated display (None = as is).
+    pub fn set_simulation(&mut self, simulation: Option<DisplaySimulation>) {
+        self.simulation = simulation;
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
+    fn draw_swatch(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        name: &str,
+        rgb: (u8, u8, u8),
+        flagged: bool,
+        selected: bool,
+    ) {
         let bg_color = Color::Rgb(rgb.0, rgb.1, rgb.2);
 
         // Choose contrasting text color
@@ -68,24 +143,97 @@ impl Palette {
         };
 
         let style = Style::default().bg(bg_color).fg(fg_color);
+        let name_style = if selected {
+            style.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            style
+        };
 
-        // Short name (last 2 chars)
-        let short_name = &name[4..];
+        // Short name (last 2 chars), marked when it collides under simulation
+        let short_name = if flagged {
+            format!("{}!", &name[4..])
+        } else {
+            name[4..].to_string()
+        };
         let hex = format!("{:02X}{:02X}{:02X}", rgb.0, rgb.1, rgb.2);
 
         let lines = if area.height >= 3 {
             vec![
-                Line::from(Span::styled(short_name, style)),
+                Line::from(Span::styled(short_name, name_style)),
                 Line::from(Span::styled(hex, style)),
             ]
         } else {
-            vec![Line::from(Span::styled(short_name, style))]
+            vec![Line::from(Span::styled(short_name, name_style))]
         };
 
         let paragraph = Paragraph::new(lines).style(style);
         frame.render_widget(paragraph, area);
     }
 
+    /// Values of the selected swatch, as generated (before any simulation).
+    fn draw_inspector(&self, frame: &mut Frame, area: Rect, stage: &str) {
+        let block = Block::default()
+            .title(format!(" {} ", Self::slot_name(self.selected)))
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rgb = |slot: &str| {
+            let (r, g, b) = self.scheme.as_ref()?.palette.get(slot)?.rgb;
+            Some(Srgb::new(r, g, b))
+        };
+        let Some(color) = rgb(Self::slot_name(self.selected)) else {
+            return;
+        };
+        let report = ColorReport::from_srgb(srgb_to_f32(color));
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let row = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label:<7}"), dim),
+                Span::raw(value),
+            ])
+        };
+
+        let (xterm, delta_e) = nearest_xterm(color);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    "  ",
+                    Style::default().bg(Color::Rgb(color.red, color.green, color.blue)),
+                ),
+                Span::raw(format!(" {}", report.hex)),
+            ]),
+            row("stage", stage.to_string()),
+            row(
+                "JMh",
+                format!(
+                    "{:.1} {:.1} {:.0}°",
+                    report.jmh.lightness, report.jmh.colorfulness, report.jmh.hue
+                ),
+            ),
+            row(
+                "OKLCH",
+                format!(
+                    "{:.1}% {:.3} {:.0}°",
+                    report.oklch.l * 100.0,
+                    report.oklch.chroma,
+                    report.oklch.hue.into_positive_degrees()
+                ),
+            ),
+            row("xterm", format!("{xterm} (ΔE {delta_e:.1})")),
+        ];
+        // Signed APCA Lc against each background, e.g. "00 +62 01 +58 05 -8"
+        let contrast: Vec<String> = INSPECT_BACKGROUNDS
+            .iter()
+            .filter_map(|background| {
+                let lc = apca_contrast(color, rgb(background)?);
+                Some(format!("{} {lc:+.0}", &background[4..]))
+            })
+            .collect();
+        lines.push(row("APCA", contrast.join(" ")));
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
     fn draw_gradient_with_markers(&self, frame: &mut Frame, area: Rect) {
         if area.height < 2 || area.width < 8 {
             return;
@@ -152,11 +300,61 @@ impl Default for Palette {
 
 impl MockComponent for Palette {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
-        let block = Block::default().title(" Palette ").borders(Borders::ALL);
-        let inner = block.inner(area);
+        // With a CVD overlay, draw the simulated palette and flag colliding accents
+        let mut flagged: Vec<&str> = Vec::new();
+        let name = match self.label {
+            Some(ref label) => format!("Palette: {label}"),
+            None => "Palette".to_string(),
+        };
+        let mut title = format!(" {name} ");
+        let simulated = match (self.cvd, &self.scheme) {
+            (Some(kind), Some(scheme)) => {
+                let conflicts: Vec<_> = check_cvd(scheme, self.cvd_threshold)
+                    .into_iter()
+                    .filter(|c| c.kind == kind)
+                    .collect();
+                flagged.extend(conflicts.iter().flat_map(|c| [c.first, c.second]));
+                title = format!(" {} [{}: {} conflicts] ", name, kind, conflicts.len());
+                Some(simulate_scheme(scheme, kind))
+            }
+            _ => None,
+        };
+        let simulated = match (self.simulation, simulated.as_ref().or(self.scheme.as_ref())) {
+            (Some(simulation), Some(scheme)) => {
+                title = format!("{}[{simulation} display] ", title);
+                Some(simulation.apply_scheme(scheme))
+            }
+            _ => simulated,
+        };
+
+        let focused = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+        let border_style = if focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let mut inner = block.inner(area);
         frame.render_widget(block, area);
 
-        let Some(scheme) = &self.scheme else {
+        if let Some(ref stage) = self.inspector
+            && self.scheme.is_some()
+        {
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(INSPECTOR_WIDTH)])
+                .split(inner);
+            self.draw_inspector(frame, cols[1], stage);
+            inner = cols[0];
+        }
+
+        let Some(scheme) = simulated.as_ref().or(self.scheme.as_ref()) else {
             let msg = Paragraph::new("No palette generated");
             frame.render_widget(msg, inner);
             return;
@@ -199,11 +397,21 @@ impl MockComponent for Palette {
                 .split(*row_area);
 
             for (col_idx, col_area) in cols.iter().enumerate() {
-                let color_idx = row_idx * 8 + col_idx;
+                let color_idx = row_idx * ROW_LEN + col_idx;
                 let color_name = COLOR_NAMES[color_idx];
 
                 if let Some(color) = scheme.palette.get(color_name) {
-                    self.draw_swatch(frame, *col_area, color_name, color.rgb);
+                    let is_flagged = flagged.contains(&color_name);
+                    let is_selected =
+                        color_idx == self.selected && (focused || self.inspector.is_some());
+                    self.draw_swatch(
+                        frame,
+                        *col_area,
+                        color_name,
+                        color.rgb,
+                        is_flagged,
+                        is_selected,
+                    );
                 }
             }
         }
@@ -227,9 +435,56 @@ impl MockComponent for Palette {
 }
 
 impl Component<Msg, UserEvent> for Palette {
-    fn on(&mut self, _ev: Event<UserEvent>) -> Option<Msg> {
-        // Read-only component, no events handled
-        None
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let focused = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+
+        if !focused {
+            return None;
+        }
+
+        let Event::Keyboard(key_event) = ev else {
+            return None;
+        };
+
+        let action = dispatcher().dispatch(&key_event)?;
+
+        if let Some(msg) = handle_global_app_events(&action) {
+            return Some(msg);
+        }
+
+        // Arrows wrap around the 3 x 8 grid
+        let count = COLOR_NAMES.len();
+        let select = |index: usize| Some(Msg::SelectSwatch(index % count));
+        match action {
+            AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
+            AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
+
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Left)) => {
+                select(self.selected + count - 1)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Right)) => {
+                select(self.selected + 1)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Up)) => {
+                select(self.selected + count - ROW_LEN)
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Down)) => {
+                select(self.selected + ROW_LEN)
+            }
+
+            // Enter opens or closes the inspector, Esc closes it
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+                Some(Msg::ToggleSwatchInspector)
+            }
+            AppAction::Tui(TuiEvent::Input(InputEvent::Cancel)) if self.inspector.is_some() => {
+                Some(Msg::ToggleSwatchInspector)
+            }
+
+            _ => None,
+        }
     }
 }
 
@@ -1,7 +1,7 @@
 //! Grouped accent color controls component.
 
 use crate::tui::AppAction;
-use crossterm_actions::{NavigationEvent, SelectionEvent, TuiEvent};
+use crossterm_actions::{InputEvent, NavigationEvent, SelectionEvent, TuiEvent};
 use ratatui::Frame;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -15,6 +15,8 @@ use tuirealm::{
     props::{AttrValue, Attribute, Props},
 };
 
+use super::numeric_input::{InputOutcome, NumericInput};
+use crate::extended_accents::{ExtendedAccentMode, ExtendedAccents};
 use crate::tui::activities::{Msg, main::UserEvent};
 use crate::tui::{dispatcher, handle_global_app_events};
 
@@ -27,27 +29,55 @@ pub enum AccentFocus {
     DeltaJ,
     TargetM,
     DeltaM,
+    Mode,
+    Amount,
 }
 
 impl AccentFocus {
-    fn next(self) -> Self {
-        match self {
-            Self::MinContrast => Self::TargetJ,
-            Self::TargetJ => Self::DeltaJ,
-            Self::DeltaJ => Self::TargetM,
-            Self::TargetM => Self::DeltaM,
-            Self::DeltaM => Self::MinContrast,
+    /// Controls in navigation order, for the given derivation (extended
+    /// controls only; the amount is skipped for independent optimization).
+    fn order(derivation: Option<ExtendedAccents>) -> &'static [Self] {
+        const BASE: &[AccentFocus] = &[
+            AccentFocus::MinContrast,
+            AccentFocus::TargetJ,
+            AccentFocus::DeltaJ,
+            AccentFocus::TargetM,
+            AccentFocus::DeltaM,
+        ];
+        const WITH_MODE: &[AccentFocus] = &[
+            AccentFocus::MinContrast,
+            AccentFocus::TargetJ,
+            AccentFocus::DeltaJ,
+            AccentFocus::TargetM,
+            AccentFocus::DeltaM,
+            AccentFocus::Mode,
+        ];
+        const WITH_AMOUNT: &[AccentFocus] = &[
+            AccentFocus::MinContrast,
+            AccentFocus::TargetJ,
+            AccentFocus::DeltaJ,
+            AccentFocus::TargetM,
+            AccentFocus::DeltaM,
+            AccentFocus::Mode,
+            AccentFocus::Amount,
+        ];
+        match derivation {
+            None => BASE,
+            Some(d) if d.mode == ExtendedAccentMode::Independent => WITH_MODE,
+            Some(_) => WITH_AMOUNT,
         }
     }
 
-    fn prev(self) -> Self {
-        match self {
-            Self::MinContrast => Self::DeltaM,
-            Self::TargetJ => Self::MinContrast,
-            Self::DeltaJ => Self::TargetJ,
-            Self::TargetM => Self::DeltaJ,
-            Self::DeltaM => Self::TargetM,
-        }
+    fn next(self, derivation: Option<ExtendedAccents>) -> Self {
+        let order = Self::order(derivation);
+        let i = order.iter().position(|f| *f == self).unwrap_or_default();
+        order[(i + 1) % order.len()]
+    }
+
+    fn prev(self, derivation: Option<ExtendedAccents>) -> Self {
+        let order = Self::order(derivation);
+        let i = order.iter().position(|f| *f == self).unwrap_or_default();
+        order[(i + order.len() - 1) % order.len()]
     }
 }
 
@@ -64,6 +94,8 @@ pub struct AccentValues {
     pub target_m: f32,
     /// Colorfulness tolerance (delta M), range 1-25
     pub delta_m: f32,
+    /// Derivation of base10-base17 (extended controls only)
+    pub derivation: Option<ExtendedAccents>,
 }
 
 /// Parameters for drawing a slider.
@@ -92,6 +124,8 @@ pub struct AccentControls {
     controls_type: AccentControlsType,
     values: AccentValues,
     sub_focus: AccentFocus,
+    /// Typed value for the focused slider
+    input: NumericInput,
 }
 
 impl AccentControls {
@@ -101,6 +135,7 @@ impl AccentControls {
             controls_type,
             values,
             sub_focus: AccentFocus::MinContrast,
+            input: NumericInput::default(),
         }
     }
 
@@ -111,26 +146,83 @@ impl AccentControls {
         }
     }
 
-    fn adjust_current(&mut self, delta: f64) {
+    /// Value of the focused slider (`None` for the derivation mode).
+    fn current(&self) -> Option<f64> {
+        match self.sub_focus {
+            AccentFocus::MinContrast => Some(self.values.min_contrast),
+            AccentFocus::TargetJ => Some(f64::from(self.values.target_j)),
+            AccentFocus::DeltaJ => Some(f64::from(self.values.delta_j)),
+            AccentFocus::TargetM => Some(f64::from(self.values.target_m)),
+            AccentFocus::DeltaM => Some(f64::from(self.values.delta_m)),
+            AccentFocus::Mode => None,
+            AccentFocus::Amount => self.values.derivation.map(|d| f64::from(d.amount())),
+        }
+    }
+
+    /// Set the focused slider, clamped to its range.
+    fn set_current(&mut self, value: f64) {
         match self.sub_focus {
             AccentFocus::MinContrast => {
-                self.values.min_contrast = (self.values.min_contrast + delta).clamp(30.0, 90.0);
+                self.values.min_contrast = value.clamp(30.0, 90.0);
             }
             AccentFocus::TargetJ => {
-                self.values.target_j = (self.values.target_j + delta as f32).clamp(20.0, 95.0);
+                self.values.target_j = (value as f32).clamp(20.0, 95.0);
             }
             AccentFocus::DeltaJ => {
-                self.values.delta_j = (self.values.delta_j + delta as f32).clamp(1.0, 30.0);
+                self.values.delta_j = (value as f32).clamp(1.0, 30.0);
             }
             AccentFocus::TargetM => {
-                self.values.target_m = (self.values.target_m + delta as f32).clamp(5.0, 50.0);
+                self.values.target_m = (value as f32).clamp(5.0, 50.0);
             }
             AccentFocus::DeltaM => {
-                self.values.delta_m = (self.values.delta_m + delta as f32).clamp(1.0, 25.0);
+                self.values.delta_m = (value as f32).clamp(1.0, 25.0);
+            }
+            AccentFocus::Mode => {}
+            AccentFocus::Amount => {
+                if let Some(ref mut derivation) = self.values.derivation {
+                    let max = derivation.mode.max_amount();
+                    derivation.amount = Some((value as f32).clamp(0.0, max));
+                }
             }
         }
     }
 
+    fn adjust_current(&mut self, delta: f64) {
+        if self.sub_focus == AccentFocus::Mode {
+            if let Some(ref mut derivation) = self.values.derivation {
+                // Amounts don't carry over between modes
+                let mode = if delta > 0.0 {
+                    derivation.mode.next()
+                } else {
+                    derivation.mode.prev()
+                };
+                *derivation = ExtendedAccents { mode, amount: None };
+            }
+        } else if let Some(current) = self.current() {
+            self.set_current(current + delta);
+        }
+    }
+
+    fn draw_mode(&self, frame: &mut Frame, area: Rect, mode: ExtendedAccentMode, focused: bool) {
+        let cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(17), Constraint::Min(10)])
+            .split(area);
+
+        let style = if focused {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        frame.render_widget(Paragraph::new("  Derivation:").style(style), cols[0]);
+        frame.render_widget(
+            Paragraph::new(format!("◀ {} ▶", mode.name())).style(style),
+            cols[1],
+        );
+    }
+
     fn draw_slider(&self, frame: &mut Frame, params: SliderParams) {
         let cols = Layout::default()
             .direction(Direction::Horizontal)
@@ -177,19 +269,24 @@ impl AccentControls {
             }
         }
 
-        let value_str = if params.precision == 0 {
-            format!(" {:.0}", params.value)
+        if params.focused && self.input.is_active() {
+            spans.push(Span::raw(" "));
+            spans.push(self.input.span());
         } else {
-            format!(" {:.prec$}", params.value, prec = params.precision)
-        };
-        spans.push(Span::styled(
-            value_str,
-            if params.focused {
-                Style::default().fg(Color::Cyan)
+            let value_str = if params.precision == 0 {
+                format!(" {:.0}", params.value)
             } else {
-                Style::default()
-            },
-        ));
+                format!(" {:.prec$}", params.value, prec = params.precision)
+            };
+            spans.push(Span::styled(
+                value_str,
+                if params.focused {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default()
+                },
+            ));
+        }
 
         let slider_line = Paragraph::new(Line::from(spans));
         frame.render_widget(slider_line, cols[1]);
@@ -205,7 +302,7 @@ impl MockComponent for AccentControls {
 
         let prefix = self.label_prefix();
 
-        // Split into 5 rows for all controls
+        // One row per control; extended controls add derivation mode and amount
         let rows = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -214,6 +311,8 @@ impl MockComponent for AccentControls {
                 Constraint::Length(1), // Delta lightness
                 Constraint::Length(1), // Target colorfulness
                 Constraint::Length(1), // Delta colorfulness
+                Constraint::Length(1), // Derivation mode
+                Constraint::Length(1), // Derivation amount
             ])
             .split(area);
 
@@ -281,6 +380,29 @@ impl MockComponent for AccentControls {
                 precision: 0,
             },
         );
+
+        if let Some(derivation) = self.values.derivation {
+            self.draw_mode(
+                frame,
+                rows[5],
+                derivation.mode,
+                focused && self.sub_focus == AccentFocus::Mode,
+            );
+            if derivation.mode != ExtendedAccentMode::Independent {
+                self.draw_slider(
+                    frame,
+                    SliderParams {
+                        area: rows[6],
+                        label: "  Amount",
+                        value: f64::from(derivation.amount()),
+                        min: 0.0,
+                        max: f64::from(derivation.mode.max_amount()),
+                        focused: focused && self.sub_focus == AccentFocus::Amount,
+                        precision: 0,
+                    },
+                );
+            }
+        }
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
@@ -298,11 +420,11 @@ impl MockComponent for AccentControls {
     fn perform(&mut self, cmd: Cmd) -> CmdResult {
         match cmd {
             Cmd::Move(CmdDirection::Up) => {
-                self.sub_focus = self.sub_focus.prev();
+                self.sub_focus = self.sub_focus.prev(self.values.derivation);
                 CmdResult::None
             }
             Cmd::Move(CmdDirection::Down) => {
-                self.sub_focus = self.sub_focus.next();
+                self.sub_focus = self.sub_focus.next(self.values.derivation);
                 CmdResult::None
             }
             Cmd::Move(CmdDirection::Left) => {
@@ -333,6 +455,22 @@ impl Component<Msg, UserEvent> for AccentControls {
             return None;
         };
 
+        // A typed value takes every key until it is committed or cancelled
+        if self.input.is_active() {
+            return match self.input.handle(key_event.code) {
+                InputOutcome::Committed(value) => {
+                    self.set_current(value);
+                    self.msg_for_change()
+                }
+                InputOutcome::Editing | InputOutcome::Cancelled => None,
+            };
+        }
+
+        // Digits start typing a value directly
+        if self.current().is_some() && self.input.start_from_key(key_event.code) {
+            return None;
+        }
+
         let action = dispatcher().dispatch(&key_event)?;
 
         if let Some(msg) = handle_global_app_events(&action) {
@@ -340,6 +478,14 @@ impl Component<Msg, UserEvent> for AccentControls {
         }
 
         match action {
+            // Enter starts typing with the current value pre-filled
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+                if let Some(value) = self.current() {
+                    self.input.start(value);
+                }
+                None
+            }
+
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
             AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Up)) => {
@@ -397,6 +543,7 @@ impl AccentControls {
                 AccentFocus::DeltaJ => Some(Msg::AccentDeltaJChanged(self.values.delta_j)),
                 AccentFocus::TargetM => Some(Msg::AccentTargetMChanged(self.values.target_m)),
                 AccentFocus::DeltaM => Some(Msg::AccentDeltaMChanged(self.values.delta_m)),
+                AccentFocus::Mode | AccentFocus::Amount => None,
             },
             AccentControlsType::Extended => match self.sub_focus {
                 AccentFocus::MinContrast => {
@@ -406,6 +553,9 @@ impl AccentControls {
                 AccentFocus::DeltaJ => Some(Msg::ExtendedDeltaJChanged(self.values.delta_j)),
                 AccentFocus::TargetM => Some(Msg::ExtendedTargetMChanged(self.values.target_m)),
                 AccentFocus::DeltaM => Some(Msg::ExtendedDeltaMChanged(self.values.delta_m)),
+                AccentFocus::Mode | AccentFocus::Amount => {
+                    self.values.derivation.map(Msg::ExtendedAccentsChanged)
+                }
             },
         }
     }
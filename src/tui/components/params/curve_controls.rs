@@ -1,7 +1,12 @@
 //! Grouped curve controls component for lightness/colorfulness/hue interpolation.
+//!
+//! A curve's second column holds its parameters: the strength slider of a
+//! sigmoid, the two handles of a cubic Bézier, or the eight stops of a
+//! manual curve (Left/Right pick a coordinate or stop, `[`/`]` and `{`/`}`
+//! move it).
 
 use crate::tui::AppAction;
-use crossterm_actions::{NavigationEvent, SelectionEvent, TuiEvent};
+use crossterm_actions::{InputEvent, NavigationEvent, SelectionEvent, TuiEvent};
 use ratatui::Frame;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -15,7 +20,8 @@ use tuirealm::{
     props::{AttrValue, Attribute, Props},
 };
 
-use crate::curves::CurveType;
+use super::numeric_input::{InputOutcome, NumericInput};
+use crate::curves::{CurveType, MANUAL_STOPS};
 use crate::tui::activities::{Msg, main::UserEvent};
 use crate::tui::{dispatcher, handle_global_app_events};
 
@@ -32,12 +38,40 @@ pub enum CurveFocus {
 }
 
 impl CurveFocus {
-    /// Whether this focus is a strength slider.
+    /// Whether this focus is a curve's parameters (strength or handles).
     fn is_strength(self) -> bool {
         matches!(self, Self::JStrength | Self::MStrength | Self::HStrength)
     }
+
+    /// Curve this focus belongs to: 0 lightness, 1 colorfulness, 2 hue.
+    fn curve(self) -> u8 {
+        match self {
+            Self::JType | Self::JStrength => 0,
+            Self::MType | Self::MStrength => 1,
+            Self::HType | Self::HStrength => 2,
+        }
+    }
+}
+
+/// Whether a curve type has a parameters column.
+fn has_parameters(curve_type: CurveType) -> bool {
+    curve_type.uses_strength() || coordinate_count(curve_type).is_some()
+}
+
+/// Coordinates picked with Left/Right in a curve's parameters column:
+/// x1, y1, x2, y2 of a Bézier, or base00-base07 of a manual curve.
+fn coordinate_count(curve_type: CurveType) -> Option<usize> {
+    match curve_type {
+        CurveType::CubicBezier => Some(4),
+        CurveType::Manual => Some(MANUAL_STOPS),
+        _ => None,
+    }
 }
 
+/// Bézier coordinate and manual stop steps for `[`/`]` and `{`/`}`.
+const POINT_STEP_SMALL: f32 = 0.01;
+const POINT_STEP_LARGE: f32 = 0.05;
+
 /// Values for curve controls.
 #[derive(Debug, Clone, Copy)]
 pub struct CurveValues {
@@ -47,6 +81,14 @@ pub struct CurveValues {
     pub m_strength: f32,
     pub h_type: CurveType,
     pub h_strength: f32,
+    /// Bézier handles of each curve, as (x, y)
+    pub j_points: [(f32, f32); 2],
+    pub m_points: [(f32, f32); 2],
+    pub h_points: [(f32, f32); 2],
+    /// Manual stops of each curve, base00-base07
+    pub j_stops: [f32; MANUAL_STOPS],
+    pub m_stops: [f32; MANUAL_STOPS],
+    pub h_stops: [f32; MANUAL_STOPS],
 }
 
 /// Grouped curve controls with sub-focus navigation.
@@ -54,6 +96,11 @@ pub struct CurveControls {
     props: Props,
     values: CurveValues,
     sub_focus: CurveFocus,
+    /// Coordinate selected when editing handles (x1, y1, x2, y2) or
+    /// manual stops (base00-base07)
+    handle: usize,
+    /// Typed value for the focused strength or coordinate
+    input: NumericInput,
 }
 
 impl CurveControls {
@@ -62,16 +109,109 @@ impl CurveControls {
             props: Props::default(),
             values,
             sub_focus: CurveFocus::JType,
+            handle: 0,
+            input: NumericInput::default(),
         }
     }
 
     /// Check if a strength focus should be visible.
     fn is_strength_visible(&self, focus: CurveFocus) -> bool {
-        match focus {
-            CurveFocus::JStrength => self.values.j_type.uses_strength(),
-            CurveFocus::MStrength => self.values.m_type.uses_strength(),
-            CurveFocus::HStrength => self.values.h_type.uses_strength(),
-            _ => true,
+        !focus.is_strength() || has_parameters(self.curve_type(focus))
+    }
+
+    fn curve_type(&self, focus: CurveFocus) -> CurveType {
+        match focus.curve() {
+            0 => self.values.j_type,
+            1 => self.values.m_type,
+            _ => self.values.h_type,
+        }
+    }
+
+    fn points_mut(&mut self, focus: CurveFocus) -> &mut [(f32, f32); 2] {
+        match focus.curve() {
+            0 => &mut self.values.j_points,
+            1 => &mut self.values.m_points,
+            _ => &mut self.values.h_points,
+        }
+    }
+
+    fn stops_mut(&mut self, focus: CurveFocus) -> &mut [f32; MANUAL_STOPS] {
+        match focus.curve() {
+            0 => &mut self.values.j_stops,
+            1 => &mut self.values.m_stops,
+            _ => &mut self.values.h_stops,
+        }
+    }
+
+    /// Number of coordinates when the focus is on a Bézier's handles or a
+    /// manual curve's stops.
+    fn editing_points(&self) -> Option<usize> {
+        if !self.sub_focus.is_strength() {
+            return None;
+        }
+        coordinate_count(self.curve_type(self.sub_focus))
+    }
+
+    /// Selected Bézier coordinate or manual stop at current focus.
+    fn current_point(&self) -> Option<f32> {
+        self.editing_points()?;
+        let curve = self.sub_focus.curve();
+        if self.curve_type(self.sub_focus) == CurveType::Manual {
+            let stops = match curve {
+                0 => self.values.j_stops,
+                1 => self.values.m_stops,
+                _ => self.values.h_stops,
+            };
+            return Some(stops[self.handle]);
+        }
+        let points = match curve {
+            0 => self.values.j_points,
+            1 => self.values.m_points,
+            _ => self.values.h_points,
+        };
+        let (x, y) = points[self.handle / 2];
+        Some(if self.handle.is_multiple_of(2) { x } else { y })
+    }
+
+    /// Set the selected Bézier coordinate or manual stop, clamped to 0.0-1.0.
+    fn set_point(&mut self, value: f32) {
+        let value = value.clamp(0.0, 1.0);
+        let handle = self.handle;
+        if self.curve_type(self.sub_focus) == CurveType::Manual {
+            self.stops_mut(self.sub_focus)[handle] = value;
+            return;
+        }
+        let point = &mut self.points_mut(self.sub_focus)[handle / 2];
+        if handle.is_multiple_of(2) {
+            point.0 = value;
+        } else {
+            point.1 = value;
+        }
+    }
+
+    /// Typed or pre-filled value at current focus.
+    fn current_value(&self) -> Option<f32> {
+        self.current_point().or_else(|| self.current_strength())
+    }
+
+    fn set_value(&mut self, value: f32) {
+        if self.editing_points().is_some() {
+            self.set_point(value);
+        } else {
+            self.set_strength(value);
+        }
+    }
+
+    /// Adjust the strength or selected coordinate by a small or large step.
+    fn step(&mut self, large: bool, up: bool) {
+        let delta = match (self.editing_points().is_some(), large) {
+            (true, false) => POINT_STEP_SMALL,
+            (true, true) => POINT_STEP_LARGE,
+            (false, false) => 0.05,
+            (false, true) => 0.25,
+        };
+        if let Some(current) = self.current_value() {
+            self.set_value(current + if up { delta } else { -delta });
         }
     }
 
@@ -92,6 +232,7 @@ impl CurveControls {
             let candidate = order[next_idx];
             if self.is_strength_visible(candidate) {
                 self.sub_focus = candidate;
+                self.handle = 0;
                 return;
             }
         }
@@ -114,6 +255,7 @@ impl CurveControls {
             let candidate = order[prev_idx];
             if self.is_strength_visible(candidate) {
                 self.sub_focus = candidate;
+                self.handle = 0;
                 return;
             }
         }
@@ -147,28 +289,40 @@ impl CurveControls {
         }
     }
 
-    /// Adjust the strength at current focus.
-    fn adjust_strength(&mut self, delta: f32) {
+    /// Strength at current focus (`None` on a curve type).
+    fn current_strength(&self) -> Option<f32> {
         match self.sub_focus {
-            CurveFocus::JStrength => {
-                self.values.j_strength = (self.values.j_strength + delta).clamp(0.1, 5.0);
-            }
-            CurveFocus::MStrength => {
-                self.values.m_strength = (self.values.m_strength + delta).clamp(0.1, 5.0);
-            }
-            CurveFocus::HStrength => {
-                self.values.h_strength = (self.values.h_strength + delta).clamp(0.1, 5.0);
-            }
+            CurveFocus::JStrength => Some(self.values.j_strength),
+            CurveFocus::MStrength => Some(self.values.m_strength),
+            CurveFocus::HStrength => Some(self.values.h_strength),
+            _ => None,
+        }
+    }
+
+    /// Set the strength at current focus, clamped to 0.1-5.0.
+    fn set_strength(&mut self, value: f32) {
+        let value = value.clamp(0.1, 5.0);
+        match self.sub_focus {
+            CurveFocus::JStrength => self.values.j_strength = value,
+            CurveFocus::MStrength => self.values.m_strength = value,
+            CurveFocus::HStrength => self.values.h_strength = value,
             _ => {}
         }
     }
 
     /// Handle left/right adjustment based on current focus.
-    fn adjust(&mut self, forward: bool) {
-        if self.sub_focus.is_strength() {
-            self.adjust_strength(if forward { 0.05 } else { -0.05 });
+    ///
+    /// Returns whether a value changed (picking a coordinate or stop doesn't).
+    fn adjust(&mut self, forward: bool) -> bool {
+        if let Some(count) = self.editing_points() {
+            self.handle = (self.handle + if forward { 1 } else { count - 1 }) % count;
+            false
+        } else if self.sub_focus.is_strength() {
+            self.step(false, forward);
+            true
         } else {
             self.cycle_type(forward);
+            true
         }
     }
 
@@ -181,6 +335,8 @@ impl CurveControls {
         label: &str,
         curve_type: CurveType,
         strength: f32,
+        points: [(f32, f32); 2],
+        stops: [f32; MANUAL_STOPS],
         type_focus: CurveFocus,
         strength_focus: CurveFocus,
         focused: bool,
@@ -235,9 +391,91 @@ impl CurveControls {
         // Strength slider (only if curve type uses strength)
         if shows_strength {
             self.draw_inline_strength(frame, cols[2], strength, strength_focused);
+        } else if curve_type == CurveType::CubicBezier {
+            self.draw_inline_points(frame, cols[2], points, strength_focused);
+        } else if curve_type == CurveType::Manual {
+            self.draw_inline_stops(frame, cols[2], stops, strength_focused);
         }
     }
 
+    /// Draw a manual curve's stops as bars, the selected stop highlighted
+    /// and its value alongside.
+    fn draw_inline_stops(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        stops: [f32; MANUAL_STOPS],
+        focused: bool,
+    ) {
+        const BARS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+        let plain = if focused {
+            Style::default()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let selected = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let mut spans: Vec<Span> = stops
+            .iter()
+            .enumerate()
+            .map(|(i, stop)| {
+                let bar = BARS[((stop * (BARS.len() - 1) as f32).round() as usize).min(7)];
+                let style = if focused && i == self.handle {
+                    selected
+                } else {
+                    plain
+                };
+                Span::styled(bar, style)
+            })
+            .collect();
+        if focused {
+            spans.push(Span::styled(format!(" base0{} ", self.handle), plain));
+            if self.input.is_active() {
+                spans.push(self.input.span());
+            } else {
+                spans.push(Span::styled(format!("{:.2}", stops[self.handle]), selected));
+            }
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Draw a Bézier's handles, the selected coordinate highlighted.
+    fn draw_inline_points(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        points: [(f32, f32); 2],
+        focused: bool,
+    ) {
+        let coords = [points[0].0, points[0].1, points[1].0, points[1].1];
+        let plain = if focused {
+            Style::default()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let mut spans = Vec::new();
+        for (i, value) in coords.iter().enumerate() {
+            spans.push(Span::styled(if i % 2 == 0 { "(" } else { ", " }, plain));
+            if focused && i == self.handle && self.input.is_active() {
+                spans.push(self.input.span());
+            } else if focused && i == self.handle {
+                spans.push(Span::styled(
+                    format!("{value:.2}"),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            } else {
+                spans.push(Span::styled(format!("{value:.2}"), plain));
+            }
+            if i % 2 == 1 {
+                spans.push(Span::styled(") ", plain));
+            }
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
     /// Draw an inline strength slider.
     fn draw_inline_strength(&self, frame: &mut Frame, area: Rect, value: f32, focused: bool) {
         let slider_width = area.width.saturating_sub(6) as usize;
@@ -275,15 +513,20 @@ impl CurveControls {
             }
         }
 
-        let value_str = format!(" {:.1}", value);
-        spans.push(Span::styled(
-            value_str,
-            if focused {
-                Style::default().fg(Color::Cyan)
-            } else {
-                Style::default()
-            },
-        ));
+        if focused && self.input.is_active() {
+            spans.push(Span::raw(" "));
+            spans.push(self.input.span());
+        } else {
+            let value_str = format!(" {:.1}", value);
+            spans.push(Span::styled(
+                value_str,
+                if focused {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default()
+                },
+            ));
+        }
 
         let slider_line = Paragraph::new(Line::from(spans));
         frame.render_widget(slider_line, area);
@@ -314,6 +557,8 @@ impl MockComponent for CurveControls {
             "Lightness Crv",
             self.values.j_type,
             self.values.j_strength,
+            self.values.j_points,
+            self.values.j_stops,
             CurveFocus::JType,
             CurveFocus::JStrength,
             focused,
@@ -326,6 +571,8 @@ impl MockComponent for CurveControls {
             "Colorful Crv",
             self.values.m_type,
             self.values.m_strength,
+            self.values.m_points,
+            self.values.m_stops,
             CurveFocus::MType,
             CurveFocus::MStrength,
             focused,
@@ -338,6 +585,8 @@ impl MockComponent for CurveControls {
             "Hue Curve",
             self.values.h_type,
             self.values.h_strength,
+            self.values.h_points,
+            self.values.h_stops,
             CurveFocus::HType,
             CurveFocus::HStrength,
             focused,
@@ -367,12 +616,18 @@ impl MockComponent for CurveControls {
                 CmdResult::None
             }
             Cmd::Move(CmdDirection::Left) => {
-                self.adjust(false);
-                CmdResult::Changed(self.state())
+                if self.adjust(false) {
+                    CmdResult::Changed(self.state())
+                } else {
+                    CmdResult::None
+                }
             }
             Cmd::Move(CmdDirection::Right) => {
-                self.adjust(true);
-                CmdResult::Changed(self.state())
+                if self.adjust(true) {
+                    CmdResult::Changed(self.state())
+                } else {
+                    CmdResult::None
+                }
             }
             _ => CmdResult::None,
         }
@@ -394,6 +649,22 @@ impl Component<Msg, UserEvent> for CurveControls {
             return None;
         };
 
+        // A typed strength takes every key until it is committed or cancelled
+        if self.input.is_active() {
+            return match self.input.handle(key_event.code) {
+                InputOutcome::Committed(value) => {
+                    self.set_value(value as f32);
+                    self.msg_for_change()
+                }
+                InputOutcome::Editing | InputOutcome::Cancelled => None,
+            };
+        }
+
+        // Digits start typing a strength directly
+        if self.sub_focus.is_strength() && self.input.start_from_key(key_event.code) {
+            return None;
+        }
+
         let action = dispatcher().dispatch(&key_event)?;
 
         if let Some(msg) = handle_global_app_events(&action) {
@@ -401,15 +672,24 @@ impl Component<Msg, UserEvent> for CurveControls {
         }
 
         match action {
+            // Enter starts typing with the current value pre-filled
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+                if let Some(value) = self.current_value() {
+                    self.input.start(f64::from(value));
+                }
+                None
+            }
+
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
+            // The curve plot follows the focused curve
             AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Up)) => {
                 self.perform(Cmd::Move(CmdDirection::Up));
-                None
+                Some(Msg::CurveFocused(self.sub_focus.curve()))
             }
             AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Down)) => {
                 self.perform(Cmd::Move(CmdDirection::Down));
-                None
+                Some(Msg::CurveFocused(self.sub_focus.curve()))
             }
             AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Left)) => {
                 if let CmdResult::Changed(_) = self.perform(Cmd::Move(CmdDirection::Left)) {
@@ -426,42 +706,27 @@ impl Component<Msg, UserEvent> for CurveControls {
                 }
             }
 
-            // Value adjustment: [/] for ±0.05, {/} for ±0.25 (strength sliders only)
-            AppAction::ValueDecrementSmall => {
+            // Value adjustment: [/] for ±0.05, {/} for ±0.25 on strength
+            // sliders (±0.01 and ±0.05 on Bézier handles and manual stops);
+            // types cycle
+            AppAction::ValueDecrementSmall
+            | AppAction::ValueIncrementSmall
+            | AppAction::ValueDecrementLarge
+            | AppAction::ValueIncrementLarge => {
+                let up = matches!(
+                    action,
+                    AppAction::ValueIncrementSmall | AppAction::ValueIncrementLarge
+                );
                 if self.sub_focus.is_strength() {
-                    self.adjust_strength(-0.05);
-                    self.msg_for_change()
+                    let large = matches!(
+                        action,
+                        AppAction::ValueDecrementLarge | AppAction::ValueIncrementLarge
+                    );
+                    self.step(large, up);
                 } else {
-                    self.cycle_type(false);
-                    self.msg_for_change()
-                }
-            }
-            AppAction::ValueIncrementSmall => {
-                if self.sub_focus.is_strength() {
-                    self.adjust_strength(0.05);
-                    self.msg_for_change()
-                } else {
-                    self.cycle_type(true);
-                    self.msg_for_change()
-                }
-            }
-            AppAction::ValueDecrementLarge => {
-                if self.sub_focus.is_strength() {
-                    self.adjust_strength(-0.25);
-                    self.msg_for_change()
-                } else {
-                    self.cycle_type(false);
-                    self.msg_for_change()
-                }
-            }
-            AppAction::ValueIncrementLarge => {
-                if self.sub_focus.is_strength() {
-                    self.adjust_strength(0.25);
-                    self.msg_for_change()
-                } else {
-                    self.cycle_type(true);
-                    self.msg_for_change()
+                    self.cycle_type(up);
                 }
+                self.msg_for_change()
             }
 
             _ => None,
@@ -471,6 +736,20 @@ impl Component<Msg, UserEvent> for CurveControls {
 
 impl CurveControls {
     fn msg_for_change(&self) -> Option<Msg> {
+        if self.curve_type(self.sub_focus) == CurveType::Manual && self.editing_points().is_some() {
+            return Some(match self.sub_focus.curve() {
+                0 => Msg::LightnessCurveStopsChanged(self.values.j_stops),
+                1 => Msg::ChromaCurveStopsChanged(self.values.m_stops),
+                _ => Msg::HueCurveStopsChanged(self.values.h_stops),
+            });
+        }
+        if self.editing_points().is_some() {
+            return Some(match self.sub_focus.curve() {
+                0 => Msg::LightnessCurvePointsChanged(self.values.j_points),
+                1 => Msg::ChromaCurvePointsChanged(self.values.m_points),
+                _ => Msg::HueCurvePointsChanged(self.values.h_points),
+            });
+        }
         match self.sub_focus {
             CurveFocus::JType => Some(Msg::LightnessCurveTypeChanged(self.values.j_type)),
             CurveFocus::JStrength => {
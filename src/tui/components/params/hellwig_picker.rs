@@ -1,7 +1,7 @@
 //! HellwigJmh color picker Component with J/M/h sliders.
 
 use crate::tui::AppAction;
-use crossterm_actions::{NavigationEvent, SelectionEvent, TuiEvent};
+use crossterm_actions::{InputEvent, NavigationEvent, SelectionEvent, TuiEvent};
 use palette::Srgb;
 use ratatui::Frame;
 use ratatui::{
@@ -16,8 +16,12 @@ use tuirealm::{
     props::{AttrValue, Attribute, Props},
 };
 
+use super::numeric_input::{InputOutcome, NumericInput};
+use crate::generate::parse_color;
 use crate::hellwig::HellwigJmh;
+use crate::interpolation::srgb_to_hex;
 use crate::tui::activities::{Msg, main::UserEvent};
+use crate::tui::color_memory::ColorMemory;
 use crate::tui::{dispatcher, handle_global_app_events};
 
 /// Which slider is focused within the picker.
@@ -85,6 +89,12 @@ pub struct HellwigPicker {
     values: HellwigValues,
     srgb_preview: Srgb<u8>,
     sub_focus: HellwigFocus,
+    /// Remembered colors (favorites first) and whether each is a favorite
+    memory: Vec<(Srgb<u8>, bool)>,
+    /// Index of the last recalled memory entry
+    recalled: Option<usize>,
+    /// Typed value for the focused slider
+    input: NumericInput,
 }
 
 impl HellwigPicker {
@@ -95,9 +105,45 @@ impl HellwigPicker {
             values,
             srgb_preview: srgb,
             sub_focus: HellwigFocus::Lightness,
+            memory: Vec::new(),
+            recalled: None,
+            input: NumericInput::default(),
         }
     }
 
+    /// Offer the remembered colors for recall.
+    pub fn with_memory(mut self, memory: &ColorMemory) -> Self {
+        self.memory = memory
+            .entries()
+            .iter()
+            .filter_map(|hex| {
+                let color = parse_color(&format!("#{hex}")).ok()?;
+                Some((color, memory.favorites.contains(hex)))
+            })
+            .collect();
+        self
+    }
+
+    /// Load the next remembered color and return its change message.
+    fn recall_next(&mut self) -> Option<Msg> {
+        if self.memory.is_empty() {
+            return None;
+        }
+        let index = self.recalled.map_or(0, |i| (i + 1) % self.memory.len());
+        self.recalled = Some(index);
+        let color = self.memory[index].0;
+        let hellwig = HellwigJmh::from_srgb_u8(color);
+        self.values.lightness = hellwig.lightness;
+        self.values.colorfulness = hellwig.colorfulness;
+        self.values.hue = hellwig.hue;
+        self.update_derived();
+        let hex = srgb_to_hex(color);
+        Some(match self.picker_type {
+            HellwigPickerType::Background => Msg::BackgroundRecalled(hex),
+            HellwigPickerType::Foreground => Msg::ForegroundRecalled(hex),
+        })
+    }
+
     fn label(&self) -> &'static str {
         match self.picker_type {
             HellwigPickerType::Background => "Background",
@@ -105,27 +151,31 @@ impl HellwigPicker {
         }
     }
 
-    fn adjust_current(&mut self, delta: f64) {
+    fn current(&self) -> f32 {
         match self.sub_focus {
-            HellwigFocus::Lightness => {
-                // Increment of 1.0 per keystroke (0-100 range)
-                self.values.lightness =
-                    (self.values.lightness + delta as f32 * 1.0).clamp(0.0, 100.0);
-            }
-            HellwigFocus::Colorfulness => {
-                // Increment of 1.0 per keystroke (0-105 range)
-                self.values.colorfulness =
-                    (self.values.colorfulness + delta as f32 * 1.0).clamp(0.0, 105.0);
-            }
-            HellwigFocus::Hue => {
-                // Increment of 1.0 degree per keystroke
-                self.values.hue = (self.values.hue + delta as f32).rem_euclid(360.0);
-            }
+            HellwigFocus::Lightness => self.values.lightness,
+            HellwigFocus::Colorfulness => self.values.colorfulness,
+            HellwigFocus::Hue => self.values.hue,
+        }
+    }
+
+    /// Set the focused slider, clamped to its range (hue wraps around).
+    fn set_current(&mut self, value: f32) {
+        match self.sub_focus {
+            HellwigFocus::Lightness => self.values.lightness = value.clamp(0.0, 100.0),
+            HellwigFocus::Colorfulness => self.values.colorfulness = value.clamp(0.0, 105.0),
+            HellwigFocus::Hue => self.values.hue = value.rem_euclid(360.0),
         }
         // Update gamut status and sRGB preview after any change
         self.update_derived();
     }
 
+    /// Adjust the focused slider by `delta` (1.0 per keystroke, in J, M,
+    /// or degrees).
+    fn adjust_current(&mut self, delta: f64) {
+        self.set_current(self.current() + delta as f32);
+    }
+
     /// Update derived values (out_of_gamut flag and sRGB preview) from current HellwigJmh.
     fn update_derived(&mut self) {
         let hellwig = HellwigJmh::new(
@@ -208,23 +258,28 @@ impl HellwigPicker {
             }
         }
 
-        // Format value - show integer for J and M, degrees for H
-        let value_str = if show_degrees {
-            format!(" {:.0}°", value)
-        } else if max > 10.0 {
-            // For J (0-100) and M (0-105), show as integer
-            format!(" {:.0}", value)
+        if focused && self.input.is_active() {
+            spans.push(Span::raw(" "));
+            spans.push(self.input.span());
         } else {
-            format!(" {:.2}", value)
-        };
-        spans.push(Span::styled(
-            value_str,
-            if focused {
-                Style::default().fg(Color::Cyan)
+            // Format value - show integer for J and M, degrees for H
+            let value_str = if show_degrees {
+                format!(" {:.0}°", value)
+            } else if max > 10.0 {
+                // For J (0-100) and M (0-105), show as integer
+                format!(" {:.0}", value)
             } else {
-                Style::default()
-            },
-        ));
+                format!(" {:.2}", value)
+            };
+            spans.push(Span::styled(
+                value_str,
+                if focused {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default()
+                },
+            ));
+        }
 
         let slider_line = Paragraph::new(Line::from(spans));
         frame.render_widget(slider_line, cols[1]);
@@ -249,10 +304,14 @@ impl MockComponent for HellwigPicker {
             ])
             .split(area);
 
-        // Header row with label, warning, and color swatch
+        // Header row with label, warning, remembered colors, and color swatch
         let header_cols = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Min(10), Constraint::Length(3)])
+            .constraints([
+                Constraint::Min(10),
+                Constraint::Length(self.memory.len() as u16 + 1),
+                Constraint::Length(3),
+            ])
             .split(rows[0]);
 
         let warning = if self.values.out_of_gamut { " !" } else { "" };
@@ -273,8 +332,24 @@ impl MockComponent for HellwigPicker {
             self.srgb_preview.green,
             self.srgb_preview.blue,
         ));
+        // Favorites as diamonds, recent colors as squares; the last recalled
+        // entry is underlined
+        let chips: Vec<Span> = self
+            .memory
+            .iter()
+            .enumerate()
+            .map(|(i, (color, favorite))| {
+                let mut style = Style::default().fg(Color::Rgb(color.red, color.green, color.blue));
+                if self.recalled == Some(i) {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                Span::styled(if *favorite { "◆" } else { "■" }, style)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(Line::from(chips)), header_cols[1]);
+
         let swatch = Paragraph::new("   ").style(swatch_style);
-        frame.render_widget(swatch, header_cols[1]);
+        frame.render_widget(swatch, header_cols[2]);
 
         // Lightness slider
         self.draw_slider(
@@ -369,6 +444,22 @@ impl Component<Msg, UserEvent> for HellwigPicker {
             return None;
         };
 
+        // A typed value takes every key until it is committed or cancelled
+        if self.input.is_active() {
+            return match self.input.handle(key_event.code) {
+                InputOutcome::Committed(value) => {
+                    self.set_current(value as f32);
+                    self.msg_for_change()
+                }
+                InputOutcome::Editing | InputOutcome::Cancelled => None,
+            };
+        }
+
+        // Digits start typing a value directly
+        if self.input.start_from_key(key_event.code) {
+            return None;
+        }
+
         // Use dispatcher to convert to semantic action
         let action = dispatcher().dispatch(&key_event)?;
 
@@ -377,6 +468,12 @@ impl Component<Msg, UserEvent> for HellwigPicker {
         }
 
         match action {
+            // Enter starts typing with the current value pre-filled
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+                self.input.start(f64::from(self.current()));
+                None
+            }
+
             // Focus navigation
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
@@ -405,6 +502,12 @@ impl Component<Msg, UserEvent> for HellwigPicker {
                 }
             }
 
+            // Remembered colors
+            AppAction::RecallColor => self.recall_next(),
+            AppAction::ToggleFavoriteColor => {
+                Some(Msg::ToggleFavoriteColor(srgb_to_hex(self.srgb_preview)))
+            }
+
             // Value adjustment: [/] for ±1, {/} for ±5
             AppAction::ValueDecrementSmall => {
                 self.adjust_current(-1.0);
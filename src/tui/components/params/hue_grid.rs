@@ -12,9 +12,11 @@ use ratatui::{
 use tuirealm::{
     Component, Event, MockComponent, State, StateValue,
     command::{Cmd, CmdResult, Direction as CmdDirection},
-    props::{AttrValue, Attribute, Props},
+    props::{AttrValue, Attribute, PropPayload, PropValue, Props},
 };
 
+use super::numeric_input::{InputOutcome, NumericInput};
+use crate::harmony::Harmony;
 use crate::interpolation::DEFAULT_BASE16_HUES;
 use crate::tui::activities::{Msg, main::UserEvent};
 use crate::tui::{dispatcher, handle_global_app_events};
@@ -24,15 +26,53 @@ const HUE_NAMES: [&str; 8] = [
     "Red", "Orange", "Yellow", "Green", "Cyan", "Blue", "Purple", "Magenta",
 ];
 
+/// Attribute carrying every hue override (`PropValue::F32`, base08 first,
+/// `PropValue::Bool(false)` when unset), pushed to the focused hue editor
+/// when an edit moves linked hues, so it keeps its selection.
+pub const HUES_ATTR: Attribute = Attribute::Custom("hues");
+
+/// [`HUES_ATTR`] payload for `hues`.
+pub fn hues_attr(hues: &[Option<f32>; 8]) -> AttrValue {
+    let values = hues
+        .iter()
+        .map(|hue| hue.map_or(PropValue::Bool(false), PropValue::F32))
+        .collect();
+    AttrValue::Payload(PropPayload::Vec(values))
+}
+
+/// Hue overrides carried by a [`HUES_ATTR`] payload.
+pub(super) fn hues_from_attr(value: &AttrValue) -> Option<[Option<f32>; 8]> {
+    let AttrValue::Payload(PropPayload::Vec(values)) = value else {
+        return None;
+    };
+    let mut hues = [None; 8];
+    for (hue, value) in hues.iter_mut().zip(values) {
+        *hue = match value {
+            PropValue::F32(h) => Some(*h),
+            _ => None,
+        };
+    }
+    Some(hues)
+}
+
 /// Hue overrides grid with 8 hue sliders in 2 rows of 4.
 pub struct HueGrid {
     props: Props,
     hues: [Option<f32>; 8],
+    /// Hue of each pinned accent; pinned hues cannot be adjusted
+    pinned: [Option<f32>; 8],
+    /// Brand color and hue of each anchored accent; anchored hues cannot be
+    /// adjusted either
+    anchors: [Option<(String, f32)>; 8],
+    /// Accents whose hue follows another's (see [`crate::hue_links`])
+    linked: [bool; 8],
+    /// Harmony rule the hues were placed by, shown in the header
+    harmony: Option<Harmony>,
     selected: usize,
-    /// Whether currently editing the selected hue value
-    editing: bool,
-    /// Buffer for typed input during editing
-    edit_buffer: String,
+    /// Typed hue for the selected accent
+    input: NumericInput,
+    /// Anchor color being typed for the selected accent
+    anchor_buffer: Option<String>,
 }
 
 impl HueGrid {
@@ -40,46 +80,103 @@ impl HueGrid {
         Self {
             props: Props::default(),
             hues,
+            pinned: [None; 8],
+            anchors: Default::default(),
+            linked: [false; 8],
+            harmony: None,
             selected: 0,
-            editing: false,
-            edit_buffer: String::new(),
+            input: NumericInput::default(),
+            anchor_buffer: None,
         }
     }
 
-    fn start_editing(&mut self) {
-        self.editing = true;
-        // Initialize buffer with current value
-        let current = self.hues[self.selected].unwrap_or(DEFAULT_BASE16_HUES[self.selected]);
-        self.edit_buffer = format!("{:.0}", current);
+    /// Mark accents pinned to an exact color.
+    pub fn with_pins(mut self, pinned: [Option<f32>; 8]) -> Self {
+        self.pinned = pinned;
+        self
     }
 
-    fn cancel_editing(&mut self) {
-        self.editing = false;
-        self.edit_buffer.clear();
+    /// Mark accents anchored to a brand color, as `(color, hue)`.
+    pub fn with_anchors(mut self, anchors: [Option<(String, f32)>; 8]) -> Self {
+        self.anchors = anchors;
+        self
     }
 
-    fn confirm_editing(&mut self) -> bool {
-        self.editing = false;
-        if let Ok(value) = self.edit_buffer.parse::<f32>() {
-            let normalized = value.rem_euclid(360.0);
-            self.hues[self.selected] = Some(normalized);
-            self.edit_buffer.clear();
-            true
-        } else {
-            self.edit_buffer.clear();
-            false
-        }
+    /// Mark accents linked to another accent's hue.
+    pub fn with_links(mut self, linked: [bool; 8]) -> Self {
+        self.linked = linked;
+        self
     }
 
-    fn type_char(&mut self, c: char) {
-        // Only allow digits and decimal point
-        if c.is_ascii_digit() || (c == '.' && !self.edit_buffer.contains('.')) {
-            self.edit_buffer.push(c);
+    /// Show the harmony rule the hues were placed by.
+    pub fn with_harmony(mut self, harmony: Option<Harmony>) -> Self {
+        self.harmony = harmony;
+        self
+    }
+
+    /// Hue shown for `index`: its pin, anchor, override, or default.
+    fn value(&self, index: usize) -> f32 {
+        self.pinned[index]
+            .or(self.anchors[index].as_ref().map(|(_, hue)| *hue))
+            .or(self.hues[index])
+            .unwrap_or(DEFAULT_BASE16_HUES[index])
+    }
+
+    /// Whether the selected hue is fixed by a pin or anchor.
+    fn is_fixed(&self) -> bool {
+        self.pinned[self.selected].is_some() || self.anchors[self.selected].is_some()
+    }
+
+    /// Adjust the selected hue unless it is pinned or anchored.
+    fn adjust_msg(&mut self, delta: f64) -> Option<Msg> {
+        if self.is_fixed() {
+            return None;
         }
+        self.adjust_current(delta);
+        Some(Msg::HueOverrideChanged(
+            self.selected as u8,
+            self.hues[self.selected],
+        ))
     }
 
-    fn delete_char(&mut self) {
-        self.edit_buffer.pop();
+    fn start_editing(&mut self) {
+        let current = self.hues[self.selected].unwrap_or(DEFAULT_BASE16_HUES[self.selected]);
+        self.input.start(f64::from(current));
+    }
+
+    /// Start entering a hex anchor color, pre-filled with the current one.
+    fn start_anchor_editing(&mut self) {
+        self.anchor_buffer = Some(match &self.anchors[self.selected] {
+            Some((color, _)) => color.clone(),
+            None => "#".to_string(),
+        });
+    }
+
+    /// Handle a key while entering an anchor color; an empty entry (or a
+    /// bare `#`) clears the anchor.
+    fn edit_anchor(&mut self, key: tuirealm::event::Key) -> Option<Msg> {
+        let buffer = self.anchor_buffer.as_mut()?;
+        match key {
+            tuirealm::event::Key::Enter => {
+                let color = buffer.trim_start_matches('#');
+                let color = (!color.is_empty()).then(|| format!("#{color}"));
+                self.anchor_buffer = None;
+                return Some(Msg::AnchorChanged(
+                    format!("base0{:X}", 8 + self.selected),
+                    color,
+                ));
+            }
+            tuirealm::event::Key::Esc => self.anchor_buffer = None,
+            tuirealm::event::Key::Backspace => {
+                buffer.pop();
+            }
+            // Hex digits after a leading '#'
+            tuirealm::event::Key::Char(c) if c.is_ascii_hexdigit() && buffer.len() < 7 => {
+                buffer.push(c.to_ascii_lowercase());
+            }
+            _ => {}
+        }
+        None
     }
 
     fn adjust_current(&mut self, delta: f64) {
@@ -91,35 +188,55 @@ impl HueGrid {
     fn draw_hue_input(&self, frame: &mut Frame, area: Rect, index: usize, focused: bool) {
         let name = HUE_NAMES[index];
         let is_override = self.hues[index].is_some();
-        let is_editing_this = self.editing && self.selected == index;
+        let is_pinned = self.pinned[index].is_some();
+        let is_anchored = self.anchors[index].is_some();
+        let is_selected = self.selected == index;
 
-        if is_editing_this {
-            // Show edit buffer with cursor
+        if is_selected && (self.input.is_active() || self.anchor_buffer.is_some()) {
+            // Show edit buffer
             let prefix = format!("{}:", &name[..3]);
-            let line = Line::from(vec![
-                Span::styled(&prefix, Style::default().fg(Color::Cyan)),
-                Span::styled(
-                    &self.edit_buffer,
-                    Style::default().fg(Color::White).bg(Color::DarkGray),
-                ),
-                Span::styled("°", Style::default().fg(Color::Cyan)),
-            ]);
+            let line = Line::from(match &self.anchor_buffer {
+                Some(buffer) => vec![
+                    Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        buffer.as_str(),
+                        Style::default().fg(Color::White).bg(Color::DarkGray),
+                    ),
+                ],
+                None => vec![
+                    Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                    self.input.span(),
+                    Span::styled("°", Style::default().fg(Color::Cyan)),
+                ],
+            });
             let para = Paragraph::new(line);
             frame.render_widget(para, area);
         } else {
-            let value = self.hues[index].unwrap_or(DEFAULT_BASE16_HUES[index]);
+            let value = self.value(index);
             let style = if focused {
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD)
+            } else if is_pinned || is_anchored {
+                Style::default().fg(Color::Yellow)
             } else if is_override {
                 Style::default().fg(Color::White)
             } else {
                 Style::default().fg(Color::DarkGray)
             };
 
-            // Compact format: "Red:25°"
-            let text = format!("{}:{:.0}°", &name[..3], value);
+            // Compact format: "Red:25°", with a trailing "*" when pinned,
+            // "~" when anchored, or "=" when linked
+            let pin = if is_pinned {
+                "*"
+            } else if is_anchored {
+                "~"
+            } else if self.linked[index] {
+                "="
+            } else {
+                ""
+            };
+            let text = format!("{}:{:.0}°{pin}", &name[..3], value);
             let para = Paragraph::new(text).style(style);
             frame.render_widget(para, area);
         }
@@ -144,8 +261,11 @@ impl MockComponent for HueGrid {
             .split(area);
 
         // Header
-        let header =
-            Paragraph::new("Hue Overrides:").style(Style::default().add_modifier(Modifier::DIM));
+        let header = match self.harmony {
+            Some(rule) => format!("Hue Overrides ({rule}):"),
+            None => "Hue Overrides:".to_string(),
+        };
+        let header = Paragraph::new(header).style(Style::default().add_modifier(Modifier::DIM));
         frame.render_widget(header, rows[0]);
 
         // Row 1 (hues 0-3)
@@ -185,6 +305,12 @@ impl MockComponent for HueGrid {
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if attr == HUES_ATTR
+            && let Some(hues) = hues_from_attr(&value)
+        {
+            self.hues = hues;
+            return;
+        }
         self.props.set(attr, value);
     }
 
@@ -192,9 +318,7 @@ impl MockComponent for HueGrid {
         // Return selected index and current hue value
         State::Tup2((
             StateValue::U8(self.selected as u8),
-            StateValue::F64(
-                self.hues[self.selected].unwrap_or(DEFAULT_BASE16_HUES[self.selected]) as f64,
-            ),
+            StateValue::F64(self.value(self.selected) as f64),
         ))
     }
 
@@ -243,42 +367,25 @@ impl Component<Msg, UserEvent> for HueGrid {
             return None;
         };
 
-        // Handle editing mode separately (raw key input)
-        if self.editing {
-            match key_event.code {
-                tuirealm::event::Key::Enter => {
-                    if self.confirm_editing() {
-                        return Some(Msg::HueOverrideChanged(
-                            self.selected as u8,
-                            self.hues[self.selected],
-                        ));
-                    }
-                    return None;
-                }
-                tuirealm::event::Key::Esc => {
-                    self.cancel_editing();
-                    return None;
-                }
-                tuirealm::event::Key::Backspace => {
-                    self.delete_char();
-                    return None;
-                }
-                tuirealm::event::Key::Char(c) => {
-                    self.type_char(c);
-                    return None;
+        // Typed input takes every key until it is committed or cancelled
+        if self.anchor_buffer.is_some() {
+            return self.edit_anchor(key_event.code);
+        }
+        if self.input.is_active() {
+            return match self.input.handle(key_event.code) {
+                InputOutcome::Committed(value) => {
+                    self.hues[self.selected] = Some((value as f32).rem_euclid(360.0));
+                    Some(Msg::HueOverrideChanged(
+                        self.selected as u8,
+                        self.hues[self.selected],
+                    ))
                 }
-                _ => return None,
-            }
+                InputOutcome::Editing | InputOutcome::Cancelled => None,
+            };
         }
 
-        // Check for digit keys to start editing directly
-        if let tuirealm::event::Key::Char(c) = key_event.code
-            && c.is_ascii_digit()
-        {
-            // Start editing and add the digit
-            self.editing = true;
-            self.edit_buffer.clear();
-            self.edit_buffer.push(c);
+        // Digits start editing directly
+        if !self.is_fixed() && self.input.start_from_key(key_event.code) {
             return None;
         }
 
@@ -291,11 +398,27 @@ impl Component<Msg, UserEvent> for HueGrid {
 
         match action {
             // Enter starts editing with current value pre-filled
-            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) if !self.is_fixed() => {
                 self.start_editing();
                 None
             }
 
+            AppAction::TogglePin => {
+                let pin = &mut self.pinned[self.selected];
+                *pin = match pin {
+                    Some(_) => None,
+                    None => {
+                        Some(self.hues[self.selected].unwrap_or(DEFAULT_BASE16_HUES[self.selected]))
+                    }
+                };
+                Some(Msg::TogglePin(format!("base0{:X}", 8 + self.selected)))
+            }
+
+            AppAction::EditAnchor => {
+                self.start_anchor_editing();
+                None
+            }
+
             // Tab bubbles up for component navigation
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
@@ -331,34 +454,10 @@ impl Component<Msg, UserEvent> for HueGrid {
             }
 
             // Value adjustment: [/] for ±1 degree, {/} for ±5 degrees
-            AppAction::ValueDecrementSmall => {
-                self.adjust_current(-1.0);
-                Some(Msg::HueOverrideChanged(
-                    self.selected as u8,
-                    self.hues[self.selected],
-                ))
-            }
-            AppAction::ValueIncrementSmall => {
-                self.adjust_current(1.0);
-                Some(Msg::HueOverrideChanged(
-                    self.selected as u8,
-                    self.hues[self.selected],
-                ))
-            }
-            AppAction::ValueDecrementLarge => {
-                self.adjust_current(-5.0);
-                Some(Msg::HueOverrideChanged(
-                    self.selected as u8,
-                    self.hues[self.selected],
-                ))
-            }
-            AppAction::ValueIncrementLarge => {
-                self.adjust_current(5.0);
-                Some(Msg::HueOverrideChanged(
-                    self.selected as u8,
-                    self.hues[self.selected],
-                ))
-            }
+            AppAction::ValueDecrementSmall => self.adjust_msg(-1.0),
+            AppAction::ValueIncrementSmall => self.adjust_msg(1.0),
+            AppAction::ValueDecrementLarge => self.adjust_msg(-5.0),
+            AppAction::ValueIncrementLarge => self.adjust_msg(5.0),
 
             _ => None,
         }
@@ -0,0 +1,342 @@
+//! Hue wheel Component showing the angular distribution of the 8 accent hues.
+
+use crate::tui::AppAction;
+use crossterm_actions::{InputEvent, NavigationEvent, SelectionEvent, TuiEvent};
+use ratatui::Frame;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Paragraph,
+        canvas::{Canvas, Line as CanvasLine, Points},
+    },
+};
+use tuirealm::{
+    Component, Event, MockComponent, State, StateValue,
+    command::{Cmd, CmdResult, Direction as CmdDirection},
+    props::{AttrValue, Attribute, Props},
+};
+
+use super::hue_grid::{HUES_ATTR, hues_from_attr};
+use crate::gamut_map::Gamut;
+use crate::hellwig::HellwigJmh;
+use crate::hue_spacing::{HueSpacing, optimize_hues};
+use crate::interpolation::DEFAULT_BASE16_HUES;
+use crate::tui::activities::{Msg, main::UserEvent};
+use crate::tui::{dispatcher, handle_global_app_events};
+
+/// Short marker labels, in base08-base0F order.
+const HUE_LABELS: [&str; 8] = ["R", "O", "Y", "G", "C", "B", "P", "M"];
+
+/// Hue color names.
+const HUE_NAMES: [&str; 8] = [
+    "Red", "Orange", "Yellow", "Green", "Cyan", "Blue", "Purple", "Magenta",
+];
+
+/// Number of samples drawn around the ring.
+const RING_SAMPLES: usize = 72;
+
+/// Lightness and colorfulness used to color the ring and markers.
+const SWATCH_J: f32 = 65.0;
+const SWATCH_M: f32 = 35.0;
+
+/// Perceptual hue spacing applied at generation time, with the accent
+/// J'/M and gamut the optimizer samples at.
+#[derive(Debug, Clone, Copy)]
+pub struct WheelSpacing {
+    pub spacing: HueSpacing,
+    pub target_j: f32,
+    pub target_m: f32,
+    pub gamut: Gamut,
+}
+
+/// Hue wheel with one marker per accent hue.
+///
+/// Up/Down select a marker, Left/Right (and `[]`/`{}`) rotate it. Enter
+/// toggles rotating all 8 hues together, which keeps their spacing. With
+/// perceptual spacing on, a dot marks where each hue actually lands.
+pub struct HueWheel {
+    props: Props,
+    hues: [Option<f32>; 8],
+    selected: usize,
+    /// Whether adjustments rotate every hue instead of the selected one
+    rotate_all: bool,
+    spacing: Option<WheelSpacing>,
+    /// Hues after perceptual spacing, recomputed when a hue moves
+    spaced: Option<[f32; 8]>,
+}
+
+impl HueWheel {
+    pub fn new(hues: [Option<f32>; 8], spacing: Option<WheelSpacing>) -> Self {
+        let mut wheel = Self {
+            props: Props::default(),
+            hues,
+            selected: 0,
+            rotate_all: false,
+            spacing,
+            spaced: None,
+        };
+        wheel.respace();
+        wheel
+    }
+
+    fn respace(&mut self) {
+        self.spaced = self.spacing.map(|s| {
+            let anchors: [f32; 8] = std::array::from_fn(|i| self.hue(i));
+            optimize_hues(&anchors, s.target_j, s.target_m, s.gamut, &s.spacing)
+        });
+    }
+
+    fn hue(&self, index: usize) -> f32 {
+        self.hues[index].unwrap_or(DEFAULT_BASE16_HUES[index])
+    }
+
+    /// Rotate the selected hue (or all of them) and return the change message.
+    fn rotate(&mut self, delta: f32) -> Msg {
+        let msg = if self.rotate_all {
+            for i in 0..8 {
+                self.hues[i] = Some((self.hue(i) + delta).rem_euclid(360.0));
+            }
+            Msg::HueOverridesRotated(delta)
+        } else {
+            self.hues[self.selected] = Some((self.hue(self.selected) + delta).rem_euclid(360.0));
+            Msg::HueOverrideChanged(self.selected as u8, self.hues[self.selected])
+        };
+        self.respace();
+        msg
+    }
+
+    /// Angular distance from the selected hue to its nearest neighbors
+    /// (counter-clockwise, clockwise).
+    fn neighbor_gaps(&self) -> (f32, f32) {
+        let current = self.hue(self.selected);
+        let mut below = 360.0f32;
+        let mut above = 360.0f32;
+        for i in (0..8).filter(|&i| i != self.selected) {
+            let up = (self.hue(i) - current).rem_euclid(360.0);
+            let down = (current - self.hue(i)).rem_euclid(360.0);
+            above = above.min(up);
+            below = below.min(down);
+        }
+        (below, above)
+    }
+
+    fn draw_wheel(&self, frame: &mut Frame, area: Rect, focused: bool) {
+        // Terminal cells are about twice as tall as wide; keep the wheel round
+        let side = area.height.min(area.width / 2);
+        let wheel = Rect {
+            x: area.x + (area.width - side * 2) / 2,
+            y: area.y + (area.height - side) / 2,
+            width: side * 2,
+            height: side,
+        };
+
+        let canvas = Canvas::default()
+            .marker(Marker::Braille)
+            .x_bounds([-1.2, 1.2])
+            .y_bounds([-1.2, 1.2])
+            .paint(|ctx| {
+                for i in 0..RING_SAMPLES {
+                    let hue = i as f32 * 360.0 / RING_SAMPLES as f32;
+                    let rad = (hue as f64).to_radians();
+                    ctx.draw(&Points {
+                        coords: &[(rad.cos(), rad.sin())],
+                        color: swatch(hue),
+                    });
+                }
+                ctx.layer();
+
+                for (i, label) in HUE_LABELS.iter().enumerate() {
+                    let hue = self.hue(i);
+                    let rad = (hue as f64).to_radians();
+                    let is_selected = self.rotate_all || i == self.selected;
+                    let reach = if is_selected { 0.95 } else { 0.6 };
+                    ctx.draw(&CanvasLine {
+                        x1: 0.0,
+                        y1: 0.0,
+                        x2: rad.cos() * reach,
+                        y2: rad.sin() * reach,
+                        color: swatch(hue),
+                    });
+
+                    let mut style = Style::default().fg(swatch(hue));
+                    if is_selected && focused {
+                        style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                    }
+                    ctx.print(
+                        rad.cos() * 1.12,
+                        rad.sin() * 1.12,
+                        Span::styled(*label, style),
+                    );
+                }
+
+                if let Some(spaced) = self.spaced {
+                    for hue in spaced {
+                        let rad = (hue as f64).to_radians();
+                        ctx.draw(&Points {
+                            coords: &[(rad.cos() * 0.85, rad.sin() * 0.85)],
+                            color: Color::White,
+                        });
+                    }
+                }
+            });
+        frame.render_widget(canvas, wheel);
+    }
+}
+
+/// Terminal color for a hue at the swatch lightness/colorfulness.
+fn swatch(hue: f32) -> Color {
+    let rgb = HellwigJmh::new(SWATCH_J, SWATCH_M, hue).into_srgb_u8_gamut_mapped();
+    Color::Rgb(rgb.red, rgb.green, rgb.blue)
+}
+
+impl MockComponent for HueWheel {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let focused = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+
+        let border_style = if focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        let title = if self.spaced.is_some() {
+            " Hue Wheel (perceptual) "
+        } else {
+            " Hue Wheel "
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [wheel_area, info_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        self.draw_wheel(frame, wheel_area, focused);
+
+        let info = if self.rotate_all {
+            Line::from(Span::styled("all hues", Style::default().fg(Color::Cyan)))
+        } else {
+            let (below, above) = self.neighbor_gaps();
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{} {:.0}° ",
+                        &HUE_NAMES[self.selected][..3],
+                        self.hue(self.selected)
+                    ),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("-{below:.0}° +{above:.0}°"),
+                    Style::default().add_modifier(Modifier::DIM),
+                ),
+            ])
+        };
+        frame.render_widget(Paragraph::new(info), info_area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if attr == HUES_ATTR
+            && let Some(hues) = hues_from_attr(&value)
+        {
+            self.hues = hues;
+            self.respace();
+            return;
+        }
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        State::Tup2((
+            StateValue::U8(self.selected as u8),
+            StateValue::F64(self.hue(self.selected) as f64),
+        ))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Move(CmdDirection::Up) => {
+                self.selected = (self.selected + 7) % 8;
+                CmdResult::None
+            }
+            Cmd::Move(CmdDirection::Down) => {
+                self.selected = (self.selected + 1) % 8;
+                CmdResult::None
+            }
+            Cmd::Move(CmdDirection::Left) => {
+                self.rotate(-1.0);
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(CmdDirection::Right) => {
+                self.rotate(1.0);
+                CmdResult::Changed(self.state())
+            }
+            _ => CmdResult::None,
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for HueWheel {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let focused = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+
+        if !focused {
+            return None;
+        }
+
+        let Event::Keyboard(key_event) = ev else {
+            return None;
+        };
+
+        let action = dispatcher().dispatch(&key_event)?;
+
+        if let Some(msg) = handle_global_app_events(&action) {
+            return Some(msg);
+        }
+
+        match action {
+            // Enter toggles rotating the whole set
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+                self.rotate_all = !self.rotate_all;
+                None
+            }
+
+            AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
+            AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
+
+            // Up/Down step through the markers
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Up)) => {
+                self.selected = (self.selected + 7) % 8;
+                None
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Down)) => {
+                self.selected = (self.selected + 1) % 8;
+                None
+            }
+
+            // Left/Right and [/] rotate by 1 degree, {/} by 5 degrees
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Left))
+            | AppAction::ValueDecrementSmall => Some(self.rotate(-1.0)),
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Right))
+            | AppAction::ValueIncrementSmall => Some(self.rotate(1.0)),
+            AppAction::ValueDecrementLarge => Some(self.rotate(-5.0)),
+            AppAction::ValueIncrementLarge => Some(self.rotate(5.0)),
+
+            _ => None,
+        }
+    }
+}
@@ -4,10 +4,15 @@ mod accent_controls;
 mod curve_controls;
 mod hellwig_picker;
 mod hue_grid;
+mod hue_wheel;
+mod numeric_input;
+mod ramp_controls;
 mod weight_controls;
 
 pub use accent_controls::{AccentControls, AccentControlsType, AccentValues};
 pub use curve_controls::{CurveControls, CurveValues};
 pub use hellwig_picker::{HellwigPicker, HellwigPickerType, HellwigValues};
-pub use hue_grid::HueGrid;
+pub use hue_grid::{HUES_ATTR, HueGrid, hues_attr};
+pub use hue_wheel::{HueWheel, WheelSpacing};
+pub use ramp_controls::{ACHIEVED_ATTR, RampControls};
 pub use weight_controls::{WeightControls, WeightValues};
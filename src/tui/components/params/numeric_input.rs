@@ -0,0 +1,112 @@
+//! Inline numeric entry shared by the slider and grid components.
+//!
+//! Enter opens the input pre-filled with the current value, and a digit
+//! opens it with just that digit. While open it takes every key: Enter
+//! commits, Esc cancels, and an entry that doesn't parse stays open and is
+//! shown in red. Components clamp the committed value to their own range.
+
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+use tuirealm::event::Key;
+
+/// Longest entry accepted, enough for `360.25` or `0.125`.
+const MAX_LEN: usize = 7;
+
+/// What a key did to an open [`NumericInput`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputOutcome {
+    /// Still editing (the key was typed, deleted, or ignored)
+    Editing,
+    /// Enter on a valid number; the input is closed
+    Committed(f64),
+    /// Esc; the input is closed
+    Cancelled,
+}
+
+/// Edit buffer for typing a value into a control.
+#[derive(Debug, Default)]
+pub struct NumericInput {
+    buffer: Option<String>,
+    /// Whether the last Enter hit an unparsable entry
+    invalid: bool,
+}
+
+impl NumericInput {
+    pub fn is_active(&self) -> bool {
+        self.buffer.is_some()
+    }
+
+    /// Open the input pre-filled with `value`, without trailing zeros.
+    pub fn start(&mut self, value: f64) {
+        let text = format!("{value:.2}");
+        let text = text.trim_end_matches('0').trim_end_matches('.');
+        self.open(text.to_string());
+    }
+
+    /// Open the input if `key` is a digit, starting from that digit.
+    pub fn start_from_key(&mut self, key: Key) -> bool {
+        match key {
+            Key::Char(c) if c.is_ascii_digit() => {
+                self.open(c.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn open(&mut self, text: String) {
+        self.buffer = Some(text);
+        self.invalid = false;
+    }
+
+    pub fn cancel(&mut self) {
+        self.buffer = None;
+        self.invalid = false;
+    }
+
+    /// Handle a key while the input is open.
+    pub fn handle(&mut self, key: Key) -> InputOutcome {
+        let Some(buffer) = self.buffer.as_mut() else {
+            return InputOutcome::Cancelled;
+        };
+        match key {
+            Key::Enter => match buffer.parse::<f64>() {
+                Ok(value) if value.is_finite() => {
+                    self.cancel();
+                    return InputOutcome::Committed(value);
+                }
+                _ => self.invalid = true,
+            },
+            Key::Esc => {
+                self.cancel();
+                return InputOutcome::Cancelled;
+            }
+            Key::Backspace => {
+                buffer.pop();
+                self.invalid = false;
+            }
+            Key::Char(c)
+                if buffer.len() < MAX_LEN
+                    && (c.is_ascii_digit() || (c == '.' && !buffer.contains('.'))) =>
+            {
+                buffer.push(c);
+                self.invalid = false;
+            }
+            _ => {}
+        }
+        InputOutcome::Editing
+    }
+
+    /// The entry so far, highlighted (red when it didn't parse).
+    pub fn span(&self) -> Span<'_> {
+        let bg = if self.invalid {
+            Color::Red
+        } else {
+            Color::DarkGray
+        };
+        Span::styled(
+            self.buffer.as_deref().unwrap_or_default(),
+            Style::default().fg(Color::White).bg(bg),
+        )
+    }
+}
@@ -0,0 +1,287 @@
+//! Lightness ramp designer for base01-base06.
+
+use crate::tui::AppAction;
+use crossterm_actions::{InputEvent, NavigationEvent, SelectionEvent, TuiEvent};
+use ratatui::Frame;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use tuirealm::{
+    Component, Event, MockComponent, State, StateValue,
+    command::{Cmd, CmdResult, Direction as CmdDirection},
+    props::{AttrValue, Attribute, PropPayload, PropValue, Props},
+};
+
+use super::numeric_input::{InputOutcome, NumericInput};
+use crate::ramp::{MAX_RAMP_LC, RAMP_SLOTS};
+use crate::tui::activities::{Msg, main::UserEvent};
+use crate::tui::{dispatcher, handle_global_app_events};
+
+/// Attribute carrying each slot's achieved contrast (`PropValue::F64`,
+/// base01 first), pushed after every regeneration so the focused editor
+/// keeps its selection.
+pub const ACHIEVED_ATTR: Attribute = Attribute::Custom("ramp-achieved");
+
+/// Smallest gap kept between neighboring targets (Lc).
+const MIN_STEP: f64 = 1.0;
+
+/// Target contrast for base01-base06 in 2 rows of 3.
+///
+/// Targeted slots show their Lc target; the rest follow the lightness curve
+/// and show the contrast they currently reach, dimmed.
+pub struct RampControls {
+    props: Props,
+    targets: [Option<f64>; 6],
+    selected: usize,
+    /// Typed target for the selected slot
+    input: NumericInput,
+}
+
+impl RampControls {
+    pub fn new(targets: [Option<f64>; 6]) -> Self {
+        Self {
+            props: Props::default(),
+            targets,
+            selected: 0,
+            input: NumericInput::default(),
+        }
+    }
+
+    /// Achieved contrast of `index`, if a palette has been generated.
+    fn achieved(&self, index: usize) -> Option<f64> {
+        match self.props.get(ACHIEVED_ATTR)? {
+            AttrValue::Payload(PropPayload::Vec(values)) => match values.get(index)? {
+                PropValue::F64(lc) => Some(*lc),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Range a target for `index` may take without overtaking its
+    /// targeted neighbors.
+    fn bounds(&self, index: usize) -> (f64, f64) {
+        let lower = self.targets[..index]
+            .iter()
+            .rev()
+            .find_map(|t| *t)
+            .map_or(MIN_STEP, |t| t + MIN_STEP);
+        let upper = self.targets[index + 1..]
+            .iter()
+            .find_map(|t| *t)
+            .map_or(MAX_RAMP_LC, |t| t - MIN_STEP);
+        (lower, upper)
+    }
+
+    /// Set the selected target, clamped between its neighbors.
+    fn set_target(&mut self, value: f64) -> Option<Msg> {
+        let (lower, upper) = self.bounds(self.selected);
+        if lower > upper {
+            return None;
+        }
+        self.targets[self.selected] = Some(value.clamp(lower, upper).round());
+        Some(self.changed())
+    }
+
+    /// Adjust the selected target, starting from the contrast the curve
+    /// gives it when untargeted.
+    fn adjust(&mut self, delta: f64) -> Option<Msg> {
+        let current = self.targets[self.selected]
+            .or_else(|| self.achieved(self.selected))
+            .unwrap_or(MIN_STEP);
+        self.set_target(current + delta)
+    }
+
+    fn changed(&self) -> Msg {
+        Msg::RampTargetChanged(self.selected as u8, self.targets[self.selected])
+    }
+
+    fn start_editing(&mut self) {
+        let current = self.targets[self.selected]
+            .or_else(|| self.achieved(self.selected))
+            .unwrap_or(MIN_STEP);
+        self.input.start(current.round());
+    }
+
+    fn draw_cell(&self, frame: &mut Frame, area: Rect, index: usize, focused: bool) {
+        let label = format!("{}:", &RAMP_SLOTS[index][4..]);
+
+        if self.input.is_active() && self.selected == index {
+            let line = Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::Cyan)),
+                self.input.span(),
+            ]);
+            frame.render_widget(Paragraph::new(line), area);
+            return;
+        }
+
+        // "02:25" for a target, "02:~18" for the curve's contrast
+        let (text, style) = match self.targets[index] {
+            Some(target) => (
+                format!("{label}{target:.0}"),
+                Style::default().fg(Color::White),
+            ),
+            None => (
+                match self.achieved(index) {
+                    Some(lc) => format!("{label}~{lc:.0}"),
+                    None => format!("{label}~"),
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        let style = if focused {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            style
+        };
+        frame.render_widget(Paragraph::new(text).style(style), area);
+    }
+}
+
+impl MockComponent for RampControls {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let focused = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+
+        // 3 rows: header + 2 rows of 3 slots
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(area);
+
+        let header = Paragraph::new("Lightness Ramp (Lc vs base00):")
+            .style(Style::default().add_modifier(Modifier::DIM));
+        frame.render_widget(header, rows[0]);
+
+        for (row, start) in [(rows[1], 0), (rows[2], 3)] {
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Ratio(1, 3); 3])
+                .split(row);
+            for (i, col) in cols.iter().enumerate() {
+                let index = start + i;
+                self.draw_cell(frame, *col, index, focused && self.selected == index);
+            }
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        State::Tup2((
+            StateValue::U8(self.selected as u8),
+            StateValue::F64(self.targets[self.selected].unwrap_or_default()),
+        ))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Move(CmdDirection::Up) => {
+                self.selected %= 3;
+                CmdResult::None
+            }
+            Cmd::Move(CmdDirection::Down) => {
+                self.selected = self.selected % 3 + 3;
+                CmdResult::None
+            }
+            _ => CmdResult::None,
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for RampControls {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let focused = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+
+        if !focused {
+            return None;
+        }
+
+        let Event::Keyboard(key_event) = ev else {
+            return None;
+        };
+
+        // A typed target takes every key until it is committed or cancelled
+        if self.input.is_active() {
+            return match self.input.handle(key_event.code) {
+                InputOutcome::Committed(value) => self.set_target(value),
+                InputOutcome::Editing | InputOutcome::Cancelled => None,
+            };
+        }
+
+        // Digits start editing directly
+        if self.input.start_from_key(key_event.code) {
+            return None;
+        }
+
+        let action = dispatcher().dispatch(&key_event)?;
+
+        if let Some(msg) = handle_global_app_events(&action) {
+            return Some(msg);
+        }
+
+        match action {
+            // Enter starts editing with the current value pre-filled
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+                self.start_editing();
+                None
+            }
+
+            // Backspace/Delete hand the slot back to the curve
+            AppAction::Tui(TuiEvent::Input(InputEvent::Backspace | InputEvent::Delete)) => {
+                self.targets[self.selected].take()?;
+                Some(self.changed())
+            }
+
+            // Tab bubbles up for component navigation
+            AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
+            AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
+
+            // Grid navigation: arrows move between cells
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Up)) => {
+                self.selected %= 3;
+                None
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Down)) => {
+                self.selected = self.selected % 3 + 3;
+                None
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Left)) => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Right)) => {
+                self.selected = (self.selected + 1).min(5);
+                None
+            }
+
+            // Value adjustment: [/] for ±1 Lc, {/} for ±5 Lc
+            AppAction::ValueDecrementSmall => self.adjust(-1.0),
+            AppAction::ValueIncrementSmall => self.adjust(1.0),
+            AppAction::ValueDecrementLarge => self.adjust(-5.0),
+            AppAction::ValueIncrementLarge => self.adjust(5.0),
+
+            _ => None,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Grouped optimization weight controls component.
 
 use crate::tui::AppAction;
-use crossterm_actions::{NavigationEvent, SelectionEvent, TuiEvent};
+use crossterm_actions::{InputEvent, NavigationEvent, SelectionEvent, TuiEvent};
 use ratatui::Frame;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -15,6 +15,7 @@ use tuirealm::{
     props::{AttrValue, Attribute, Props},
 };
 
+use super::numeric_input::{InputOutcome, NumericInput};
 use crate::tui::activities::{Msg, main::UserEvent};
 use crate::tui::{dispatcher, handle_global_app_events};
 
@@ -24,21 +25,38 @@ pub enum WeightFocus {
     #[default]
     ContrastWeight,
     JWeight,
+    PreferredContrast,
 }
 
 impl WeightFocus {
     fn next(self) -> Self {
         match self {
             Self::ContrastWeight => Self::JWeight,
-            Self::JWeight => Self::ContrastWeight,
+            Self::JWeight => Self::PreferredContrast,
+            Self::PreferredContrast => Self::ContrastWeight,
         }
     }
 
     fn prev(self) -> Self {
-        self.next() // Only 2 options, prev == next
+        match self {
+            Self::ContrastWeight => Self::PreferredContrast,
+            Self::JWeight => Self::ContrastWeight,
+            Self::PreferredContrast => Self::JWeight,
+        }
+    }
+
+    /// Largest value, and the small and large adjustment steps.
+    fn scale(self) -> (f32, f32, f32) {
+        match self {
+            Self::ContrastWeight | Self::JWeight => (1.0, 0.05, 0.25),
+            Self::PreferredContrast => (MAX_PREFERRED_LC, 1.0, 5.0),
+        }
     }
 }
 
+/// Highest preferred contrast the slider reaches (APCA's Lc range).
+const MAX_PREFERRED_LC: f32 = 108.0;
+
 /// Values for weight controls.
 #[derive(Debug, Clone, Copy)]
 pub struct WeightValues {
@@ -46,6 +64,8 @@ pub struct WeightValues {
     pub contrast_weight: f32,
     /// Lightness uniformity weight, range 0-1
     pub j_weight: f32,
+    /// Preferred contrast above the minimum (0 = off)
+    pub preferred_contrast: f32,
 }
 
 /// Grouped optimization weight controls with sub-focus navigation.
@@ -53,6 +73,8 @@ pub struct WeightControls {
     props: Props,
     values: WeightValues,
     sub_focus: WeightFocus,
+    /// Typed value for the focused weight
+    input: NumericInput,
 }
 
 impl WeightControls {
@@ -61,21 +83,47 @@ impl WeightControls {
             props: Props::default(),
             values,
             sub_focus: WeightFocus::ContrastWeight,
+            input: NumericInput::default(),
         }
     }
 
-    fn adjust_current(&mut self, delta: f32) {
+    fn current(&self) -> f32 {
         match self.sub_focus {
-            WeightFocus::ContrastWeight => {
-                self.values.contrast_weight = (self.values.contrast_weight + delta).clamp(0.0, 1.0);
-            }
-            WeightFocus::JWeight => {
-                self.values.j_weight = (self.values.j_weight + delta).clamp(0.0, 1.0);
-            }
+            WeightFocus::ContrastWeight => self.values.contrast_weight,
+            WeightFocus::JWeight => self.values.j_weight,
+            WeightFocus::PreferredContrast => self.values.preferred_contrast,
+        }
+    }
+
+    /// Set the focused value, clamped to its range.
+    fn set_current(&mut self, value: f32) {
+        let (max, _, _) = self.sub_focus.scale();
+        let value = value.clamp(0.0, max);
+        match self.sub_focus {
+            WeightFocus::ContrastWeight => self.values.contrast_weight = value,
+            WeightFocus::JWeight => self.values.j_weight = value,
+            WeightFocus::PreferredContrast => self.values.preferred_contrast = value,
         }
     }
 
-    fn draw_slider(&self, frame: &mut Frame, area: Rect, label: &str, value: f32, focused: bool) {
+    /// Move the focused value by `steps` small (or `large`) steps.
+    fn adjust_current(&mut self, steps: f32, large: bool) {
+        let (_, small_step, large_step) = self.sub_focus.scale();
+        let step = if large { large_step } else { small_step };
+        self.set_current(self.current() + steps * step);
+    }
+
+    /// Draw one slider; `fraction` places the handle and `text` is shown
+    /// after it.
+    fn draw_slider(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        label: &str,
+        fraction: f32,
+        text: String,
+        focused: bool,
+    ) {
         let cols = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Length(17), Constraint::Min(10)])
@@ -92,7 +140,7 @@ impl WeightControls {
         frame.render_widget(label_text, cols[0]);
 
         let slider_width = cols[1].width.saturating_sub(8) as usize;
-        let pos = (f64::from(value) * slider_width as f64).round() as usize;
+        let pos = (f64::from(fraction) * slider_width as f64).round() as usize;
         let pos = pos.min(slider_width.saturating_sub(1));
 
         let (filled_style, empty_style, handle_style) = if focused {
@@ -120,15 +168,19 @@ impl WeightControls {
             }
         }
 
-        let value_str = format!(" {:.2}", value);
-        spans.push(Span::styled(
-            value_str,
-            if focused {
-                Style::default().fg(Color::Cyan)
-            } else {
-                Style::default()
-            },
-        ));
+        if focused && self.input.is_active() {
+            spans.push(Span::raw(" "));
+            spans.push(self.input.span());
+        } else {
+            spans.push(Span::styled(
+                format!(" {text}"),
+                if focused {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default()
+                },
+            ));
+        }
 
         let slider_line = Paragraph::new(Line::from(spans));
         frame.render_widget(slider_line, cols[1]);
@@ -147,6 +199,7 @@ impl MockComponent for WeightControls {
             .constraints([
                 Constraint::Length(1), // Contrast weight
                 Constraint::Length(1), // Lightness weight
+                Constraint::Length(1), // Preferred contrast
             ])
             .split(area);
 
@@ -155,6 +208,7 @@ impl MockComponent for WeightControls {
             rows[0],
             "Lc Weight",
             self.values.contrast_weight,
+            format!("{:.2}", self.values.contrast_weight),
             focused && self.sub_focus == WeightFocus::ContrastWeight,
         );
 
@@ -163,8 +217,23 @@ impl MockComponent for WeightControls {
             rows[1],
             "Lightness Wt",
             self.values.j_weight,
+            format!("{:.2}", self.values.j_weight),
             focused && self.sub_focus == WeightFocus::JWeight,
         );
+
+        let preferred = self.values.preferred_contrast;
+        self.draw_slider(
+            frame,
+            rows[2],
+            "Preferred Lc",
+            preferred / MAX_PREFERRED_LC,
+            if preferred > 0.0 {
+                format!("{preferred:.0}")
+            } else {
+                "off".to_string()
+            },
+            focused && self.sub_focus == WeightFocus::PreferredContrast,
+        );
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
@@ -190,11 +259,11 @@ impl MockComponent for WeightControls {
                 CmdResult::None
             }
             Cmd::Move(CmdDirection::Left) => {
-                self.adjust_current(-0.05);
+                self.adjust_current(-1.0, false);
                 CmdResult::Changed(self.state())
             }
             Cmd::Move(CmdDirection::Right) => {
-                self.adjust_current(0.05);
+                self.adjust_current(1.0, false);
                 CmdResult::Changed(self.state())
             }
             _ => CmdResult::None,
@@ -217,6 +286,22 @@ impl Component<Msg, UserEvent> for WeightControls {
             return None;
         };
 
+        // A typed value takes every key until it is committed or cancelled
+        if self.input.is_active() {
+            return match self.input.handle(key_event.code) {
+                InputOutcome::Committed(value) => {
+                    self.set_current(value as f32);
+                    self.msg_for_change()
+                }
+                InputOutcome::Editing | InputOutcome::Cancelled => None,
+            };
+        }
+
+        // Digits start typing a value directly
+        if self.input.start_from_key(key_event.code) {
+            return None;
+        }
+
         let action = dispatcher().dispatch(&key_event)?;
 
         if let Some(msg) = handle_global_app_events(&action) {
@@ -224,6 +309,12 @@ impl Component<Msg, UserEvent> for WeightControls {
         }
 
         match action {
+            // Enter starts typing with the current value pre-filled
+            AppAction::Tui(TuiEvent::Input(InputEvent::Confirm)) => {
+                self.input.start(f64::from(self.current()));
+                None
+            }
+
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Next)) => Some(Msg::FocusNext),
             AppAction::Tui(TuiEvent::Selection(SelectionEvent::Prev)) => Some(Msg::FocusPrev),
             AppAction::Tui(TuiEvent::Navigation(NavigationEvent::Up)) => {
@@ -249,21 +340,21 @@ impl Component<Msg, UserEvent> for WeightControls {
                 }
             }
 
-            // Value adjustment: [/] for ±0.05, {/} for ±0.25
+            // Value adjustment: [/] for ±0.05 (±1 Lc), {/} for ±0.25 (±5 Lc)
             AppAction::ValueDecrementSmall => {
-                self.adjust_current(-0.05);
+                self.adjust_current(-1.0, false);
                 self.msg_for_change()
             }
             AppAction::ValueIncrementSmall => {
-                self.adjust_current(0.05);
+                self.adjust_current(1.0, false);
                 self.msg_for_change()
             }
             AppAction::ValueDecrementLarge => {
-                self.adjust_current(-0.25);
+                self.adjust_current(-1.0, true);
                 self.msg_for_change()
             }
             AppAction::ValueIncrementLarge => {
-                self.adjust_current(0.25);
+                self.adjust_current(1.0, true);
                 self.msg_for_change()
             }
 
@@ -279,6 +370,9 @@ impl WeightControls {
                 Some(Msg::ContrastWeightChanged(self.values.contrast_weight))
             }
             WeightFocus::JWeight => Some(Msg::JWeightChanged(self.values.j_weight)),
+            WeightFocus::PreferredContrast => Some(Msg::PreferredContrastChanged(
+                self.values.preferred_contrast,
+            )),
         }
     }
 }
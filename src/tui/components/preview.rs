@@ -1,12 +1,26 @@
 //! Sample text preview Component.
+//!
+//! Each tab mocks up a terminal program that leans on different slots:
+//! `git diff` on base08/base0B, htop's meters on the accents behind ANSI
+//! colors, markdown on headings and code spans, log output on the level
+//! colors, and fzf on the base02 selection. Only the selected tab is built.
+//!
+//! The small-text tab is for low-vision checks rather than a program: each
+//! text slot is drawn regular, bold, and dim (which terminals render like a
+//! thin weight), then downscaled to half blocks. A half block covers half
+//! its cell, so it reads like a glyph too small for its strokes to fill the
+//! pixels, and its APCA Lc is measured on that blend. Slots whose Lc the
+//! APCA font lookup table only allows for large text are flagged.
 
+use palette::{LinSrgb, Srgb};
 use ratatui::Frame;
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Tabs},
 };
+use serde::{Deserialize, Serialize};
 use tinted_builder::Base16Scheme;
 use tuirealm::{
     Component, Event, MockComponent, State,
@@ -14,12 +28,62 @@ use tuirealm::{
     props::{AttrValue, Attribute, Props},
 };
 
+use crate::apca::apca_contrast;
+use crate::display_sim::DisplaySimulation;
 use crate::tui::activities::{Msg, main::UserEvent};
+use crate::validation::TextUsage;
+
+/// Mockup shown in the preview.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PreviewTab {
+    #[default]
+    Samples,
+    GitDiff,
+    Htop,
+    Markdown,
+    Logs,
+    Fzf,
+    SmallText,
+}
+
+impl PreviewTab {
+    /// Every tab, in display order.
+    pub const ALL: [PreviewTab; 7] = [
+        PreviewTab::Samples,
+        PreviewTab::GitDiff,
+        PreviewTab::Htop,
+        PreviewTab::Markdown,
+        PreviewTab::Logs,
+        PreviewTab::Fzf,
+        PreviewTab::SmallText,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            PreviewTab::Samples => "Samples",
+            PreviewTab::GitDiff => "git diff",
+            PreviewTab::Htop => "htop",
+            PreviewTab::Markdown => "Markdown",
+            PreviewTab::Logs => "Logs",
+            PreviewTab::Fzf => "fzf",
+            PreviewTab::SmallText => "Small text",
+        }
+    }
+
+    /// The tab after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
 
 /// Preview component showing sample text with palette colors.
 pub struct Preview {
     props: Props,
     scheme: Option<Base16Scheme>,
+    tab: PreviewTab,
+    simulation: Option<DisplaySimulation>,
 }
 
 impl Preview {
@@ -27,12 +91,23 @@ impl Preview {
         Self {
             props: Props::default(),
             scheme: None,
+            tab: PreviewTab::default(),
+            simulation: None,
         }
     }
 
     pub fn set_scheme(&mut self, scheme: Option<Base16Scheme>) {
         self.scheme = scheme;
     }
+
+    pub fn set_tab(&mut self, tab: PreviewTab) {
+        self.tab = tab;
+    }
+
+    /// Render the mockups as seen on a simulated display (None = as is).
+    pub fn set_simulation(&mut self, simulation: Option<DisplaySimulation>) {
+        self.simulation = simulation;
+    }
 }
 
 impl Default for Preview {
@@ -41,9 +116,333 @@ impl Default for Preview {
     }
 }
 
+/// Palette colors by slot, falling back to the terminal default.
+struct Colors<'a>(&'a Base16Scheme);
+
+impl Colors<'_> {
+    fn get(&self, slot: &str) -> Color {
+        self.srgb(slot)
+            .map(|c| Color::Rgb(c.red, c.green, c.blue))
+            .unwrap_or(Color::Reset)
+    }
+
+    fn srgb(&self, slot: &str) -> Option<Srgb<u8>> {
+        self.0
+            .palette
+            .get(slot)
+            .map(|c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+    }
+
+    fn fg(&self, slot: &str) -> Style {
+        Style::default().fg(self.get(slot))
+    }
+
+    fn span<'s>(&self, text: impl Into<std::borrow::Cow<'s, str>>, slot: &str) -> Span<'s> {
+        Span::styled(text, self.fg(slot))
+    }
+}
+
+fn samples(c: &Colors) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(c.span("Normal text (base05 on base00)", "base05")),
+        Line::from(c.span("Bright text (base07 on base00)", "base07")),
+        Line::from(""),
+    ];
+
+    let accent_samples = [
+        ("base08", "Error: Something went wrong"),
+        ("base09", "Warning: Check this"),
+        ("base0A", "Note: Important info"),
+        ("base0B", "Success: Operation complete"),
+        ("base0C", "Info: Additional details"),
+        ("base0D", "Link: Click here"),
+        ("base0E", "Keyword: const let var"),
+        ("base0F", "Special: @#$%"),
+    ];
+    for (base, text) in accent_samples {
+        lines.push(Line::from(c.span(format!("{base}: {text}"), base)));
+    }
+    lines
+}
+
+fn git_diff(c: &Colors) -> Vec<Line<'static>> {
+    let bold = |slot| c.fg(slot).add_modifier(Modifier::BOLD);
+    vec![
+        Line::from(Span::styled(
+            "diff --git a/src/server.rs b/src/server.rs",
+            bold("base05"),
+        )),
+        Line::from(c.span("index 3f2a1c4..8be9d07 100644", "base03")),
+        Line::from(Span::styled("--- a/src/server.rs", bold("base08"))),
+        Line::from(Span::styled("+++ b/src/server.rs", bold("base0B"))),
+        Line::from(vec![
+            c.span("@@ -12,7 +12,8 @@", "base0C"),
+            c.span(" fn serve(config: &Config) {", "base05"),
+        ]),
+        Line::from(c.span("     let addr = config.listen_addr();", "base05")),
+        Line::from(c.span("-    let listener = bind(addr).unwrap();", "base08")),
+        Line::from(c.span("+    let listener = bind(addr)?;", "base0B")),
+        Line::from(c.span("+    info!(%addr, \"listening\");", "base0B")),
+        Line::from(c.span("     accept_loop(listener)", "base05")),
+        Line::from(c.span(" }", "base05")),
+    ]
+}
+
+/// An htop meter: `label[||||   value]`, the bar split into colored segments.
+fn meter(c: &Colors, label: &str, segments: &[(usize, &str)], value: &str) -> Line<'static> {
+    const WIDTH: usize = 24;
+    let mut spans = vec![
+        c.span(format!("{label:>4}"), "base0C"),
+        Span::styled("[", c.fg("base05").add_modifier(Modifier::BOLD)),
+    ];
+    let mut used = 0;
+    for &(len, slot) in segments {
+        spans.push(c.span("|".repeat(len), slot));
+        used += len;
+    }
+    let gap = WIDTH.saturating_sub(used + value.len());
+    spans.push(Span::raw(" ".repeat(gap)));
+    spans.push(c.span(value.to_string(), "base03"));
+    spans.push(Span::styled(
+        "]",
+        c.fg("base05").add_modifier(Modifier::BOLD),
+    ));
+    Line::from(spans)
+}
+
+fn htop(c: &Colors) -> Vec<Line<'static>> {
+    let header = Style::default()
+        .fg(c.get("base00"))
+        .bg(c.get("base0B"))
+        .add_modifier(Modifier::BOLD);
+    let selected = Style::default().fg(c.get("base07")).bg(c.get("base02"));
+    // Full-width rows, as htop highlights the whole line
+    let row = |pid: &str, user: &str, cpu: &str, command: &str| {
+        format!("{pid:>6} {user:<8} {cpu:>5} {command:<24}")
+    };
+    vec![
+        meter(
+            c,
+            "1",
+            &[(3, "base0D"), (9, "base0B"), (2, "base08")],
+            "58.2%",
+        ),
+        meter(
+            c,
+            "2",
+            &[(1, "base0D"), (4, "base0B"), (1, "base08")],
+            "24.9%",
+        ),
+        meter(
+            c,
+            "Mem",
+            &[(8, "base0B"), (2, "base0D"), (4, "base0A")],
+            "6.1G/16G",
+        ),
+        meter(c, "Swp", &[(1, "base08")], "112M/2G"),
+        Line::from(vec![
+            c.span("  Tasks: ", "base0C"),
+            Span::styled("143", c.fg("base0C").add_modifier(Modifier::BOLD)),
+            c.span(", ", "base0C"),
+            c.span("2 running", "base0B"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(row("PID", "USER", "CPU%", "Command"), header)),
+        Line::from(Span::styled(
+            row("1342", "mara", "42.0", "cargo build --release"),
+            selected,
+        )),
+        Line::from(c.span(row("987", "mara", "11.3", "nvim src/main.rs"), "base05")),
+        Line::from(c.span(row("1", "root", "0.1", "/sbin/init"), "base05")),
+        Line::from(c.span(row("2214", "mara", "0.0", "zsh"), "base03")),
+    ]
+}
+
+fn markdown(c: &Colors) -> Vec<Line<'static>> {
+    let heading = |text: &'static str, slot| {
+        Line::from(Span::styled(text, c.fg(slot).add_modifier(Modifier::BOLD)))
+    };
+    let code = Style::default().fg(c.get("base0B")).bg(c.get("base01"));
+    vec![
+        heading("# Release notes", "base0D"),
+        Line::from(""),
+        heading("## Fixes", "base0D"),
+        Line::from(vec![
+            c.span("- ", "base0E"),
+            Span::styled("Bold", c.fg("base05").add_modifier(Modifier::BOLD)),
+            c.span(" and ", "base05"),
+            Span::styled("italic", c.fg("base05").add_modifier(Modifier::ITALIC)),
+            c.span(" text", "base05"),
+        ]),
+        Line::from(vec![
+            c.span("- ", "base0E"),
+            c.span("Run ", "base05"),
+            Span::styled(" cargo test ", code),
+            c.span(" before tagging", "base05"),
+        ]),
+        Line::from(vec![
+            c.span("- ", "base0E"),
+            Span::styled(
+                "changelog",
+                c.fg("base0D").add_modifier(Modifier::UNDERLINED),
+            ),
+            c.span(" (https://example.com/changes)", "base03"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "> Quoted text stays readable but quiet",
+            c.fg("base03").add_modifier(Modifier::ITALIC),
+        )),
+        Line::from(c.span("---", "base02")),
+    ]
+}
+
+fn logs(c: &Colors) -> Vec<Line<'static>> {
+    let entries = [
+        ("TRACE", "base0E", "pool: checked out connection 7"),
+        ("DEBUG", "base0D", "http: GET /api/themes 200 in 4ms"),
+        ("INFO", "base0B", "server: listening on 0.0.0.0:8080"),
+        ("WARN", "base0A", "cache: 91% full, evicting oldest entries"),
+        ("ERROR", "base08", "db: connection refused (retrying in 5s)"),
+        ("INFO", "base0B", "db: reconnected after 2 attempts"),
+    ];
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, (level, slot, message))| {
+            let (target, text) = message.split_once(": ").unwrap_or(("", message));
+            Line::from(vec![
+                c.span(format!("09:14:0{i} "), "base03"),
+                Span::styled(
+                    format!("{level:>5} "),
+                    c.fg(slot).add_modifier(Modifier::BOLD),
+                ),
+                c.span(format!("{target}: "), "base04"),
+                c.span(text.to_string(), "base05"),
+            ])
+        })
+        .collect()
+}
+
+fn fzf(c: &Colors) -> Vec<Line<'static>> {
+    const QUERY: &str = "mod";
+    let items = [
+        "src/tui/model.rs",
+        "src/tui/mod.rs",
+        "src/tui/components/mod.rs",
+        "src/tui/activities/mod.rs",
+        "src/generated/mod.rs",
+    ];
+    let selected = 1;
+    // fzf lists the best match nearest the prompt, at the bottom
+    let mut lines: Vec<_> = items
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, path)| {
+            let is_selected = i == selected;
+            let bg = if is_selected {
+                c.get("base02")
+            } else {
+                Color::Reset
+            };
+            let text = c.fg(if is_selected { "base07" } else { "base05" }).bg(bg);
+            let start = path.find(QUERY).unwrap_or(0);
+            let end = start + QUERY.len();
+            Line::from(vec![
+                Span::styled(if is_selected { "▌ " } else { "  " }, c.fg("base08").bg(bg)),
+                Span::styled(&path[..start], text),
+                Span::styled(
+                    &path[start..end],
+                    c.fg("base0B").bg(bg).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&path[end..], text),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(vec![
+        c.span("  5/318 ", "base0A"),
+        c.span("─".repeat(20), "base02"),
+    ]));
+    lines.push(Line::from(vec![
+        c.span("> ", "base0D"),
+        c.span(QUERY, "base05"),
+    ]));
+    lines
+}
+
+/// Share of a cell a half block covers.
+const HALF_BLOCK_COVERAGE: f32 = 0.5;
+
+/// Slots checked on the small-text tab.
+const SMALL_TEXT_SLOTS: [&str; 11] = [
+    "base03", "base04", "base05", "base08", "base09", "base0A", "base0B", "base0C", "base0D",
+    "base0E", "base0F",
+];
+
+/// `fg` covering `coverage` of a `bg` cell, averaged in linear light as the
+/// eye sees it.
+fn blend(fg: Srgb<u8>, bg: Srgb<u8>, coverage: f32) -> Srgb<u8> {
+    let fg: LinSrgb<f32> = fg.into_format::<f32>().into_linear();
+    let bg: LinSrgb<f32> = bg.into_format::<f32>().into_linear();
+    Srgb::<f32>::from_linear(fg * coverage + bg * (1.0 - coverage)).into_format()
+}
+
+fn small_text(c: &Colors) -> Vec<Line<'static>> {
+    const SAMPLE: &str = "Sample";
+    let Some(bg) = c.srgb("base00") else {
+        return Vec::new();
+    };
+    let mut lines = vec![Line::from(c.span(
+        format!(
+            "{:<7}{:<7}{:<7}{:<7}{:<8}{:>6}{:>7}",
+            "", "Regular", "Bold", "Dim", "Small", "Lc", "small"
+        ),
+        "base04",
+    ))];
+    for slot in SMALL_TEXT_SLOTS {
+        let Some(fg) = c.srgb(slot) else { continue };
+        let lc = apca_contrast(fg, bg);
+        let small_lc = apca_contrast(blend(fg, bg, HALF_BLOCK_COVERAGE), bg);
+        let usage = TextUsage::of(lc);
+        let mut spans = vec![
+            c.span(format!("{slot:<7}"), "base04"),
+            c.span(format!("{SAMPLE:<7}"), slot),
+            Span::styled(
+                format!("{SAMPLE:<7}"),
+                c.fg(slot).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{SAMPLE:<7}"),
+                c.fg(slot).add_modifier(Modifier::DIM),
+            ),
+            c.span(format!("{:<8}", "▄".repeat(SAMPLE.len())), slot),
+            c.span(
+                format!("{:>6.0}{:>7.0}  ", lc.abs(), small_lc.abs()),
+                "base05",
+            ),
+        ];
+        spans.push(if usage <= TextUsage::LargeText {
+            Span::styled(
+                format!("{usage}!"),
+                c.fg("base05")
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            )
+        } else {
+            c.span(usage.to_string(), "base04")
+        });
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
 impl MockComponent for Preview {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
-        let block = Block::default().title(" Preview ").borders(Borders::ALL);
+        let title = match self.simulation {
+            Some(simulation) => format!(" Preview [{simulation} display] "),
+            None => " Preview ".to_string(),
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
@@ -52,60 +451,35 @@ impl MockComponent for Preview {
             frame.render_widget(msg, inner);
             return;
         };
+        let simulated = self.simulation.map(|s| s.apply_scheme(scheme));
+        let c = Colors(simulated.as_ref().unwrap_or(scheme));
+        let bg = c.get("base00");
 
-        let bg = scheme
-            .palette
-            .get("base00")
-            .map(|c| Color::Rgb(c.rgb.0, c.rgb.1, c.rgb.2))
-            .unwrap_or(Color::Black);
-
-        let mut lines = Vec::new();
-
-        // Normal text
-        if let Some(fg_color) = scheme.palette.get("base05") {
-            let fg = Color::Rgb(fg_color.rgb.0, fg_color.rgb.1, fg_color.rgb.2);
-            lines.push(Line::from(Span::styled(
-                "Normal text (base05 on base00)",
-                Style::default().fg(fg).bg(bg),
-            )));
-        }
-
-        // Bright text
-        if let Some(fg_color) = scheme.palette.get("base07") {
-            let fg = Color::Rgb(fg_color.rgb.0, fg_color.rgb.1, fg_color.rgb.2);
-            lines.push(Line::from(Span::styled(
-                "Bright text (base07 on base00)",
-                Style::default().fg(fg).bg(bg),
-            )));
-        }
-
-        lines.push(Line::from(Span::raw("")));
-
-        // Accent colors
-        let accent_samples = [
-            ("base08", "Error: Something went wrong"),
-            ("base09", "Warning: Check this"),
-            ("base0A", "Note: Important info"),
-            ("base0B", "Success: Operation complete"),
-            ("base0C", "Info: Additional details"),
-            ("base0D", "Link: Click here"),
-            ("base0E", "Keyword: const let var"),
-            ("base0F", "Special: @#$%"),
-        ];
-
-        for (base, text) in accent_samples {
-            if let Some(color) = scheme.palette.get(base) {
-                let fg = Color::Rgb(color.rgb.0, color.rgb.1, color.rgb.2);
-                let label = format!("{base}: {text}");
-                lines.push(Line::from(Span::styled(
-                    label,
-                    Style::default().fg(fg).bg(bg),
-                )));
-            }
-        }
+        let [tabs_area, content] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+        let selected = PreviewTab::ALL.iter().position(|t| *t == self.tab);
+        let tabs = Tabs::new(PreviewTab::ALL.map(PreviewTab::title))
+            .select(selected)
+            .style(c.fg("base04").bg(c.get("base01")))
+            .highlight_style(
+                Style::default()
+                    .fg(c.get("base07"))
+                    .bg(c.get("base02"))
+                    .add_modifier(Modifier::BOLD),
+            );
+        frame.render_widget(tabs, tabs_area);
 
+        let lines = match self.tab {
+            PreviewTab::Samples => samples(&c),
+            PreviewTab::GitDiff => git_diff(&c),
+            PreviewTab::Htop => htop(&c),
+            PreviewTab::Markdown => markdown(&c),
+            PreviewTab::Logs => logs(&c),
+            PreviewTab::Fzf => fzf(&c),
+            PreviewTab::SmallText => small_text(&c),
+        };
         let paragraph = Paragraph::new(lines).style(Style::default().bg(bg));
-        frame.render_widget(paragraph, inner);
+        frame.render_widget(paragraph, content);
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
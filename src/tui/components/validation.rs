@@ -15,17 +15,22 @@ use tuirealm::{
     props::{AttrValue, Attribute, Props},
 };
 
+use crate::contrast_matrix::ContrastMatrix;
 use crate::tui::activities::{Msg, main::UserEvent};
 use crate::tui::{dispatcher, handle_global_app_events};
-use crate::validation::{ValidationResult, ValidationResults};
+use crate::validation::{TextUsage, ValidationResult, ValidationResults};
 
 /// Validation results display with scrolling.
 pub struct Validation {
     props: Props,
     results: Option<ValidationResults>,
     warnings: Vec<String>,
+    /// When set, a heatmap of the full matrix replaces the results list
+    matrix: Option<ContrastMatrix>,
     scroll: u16,
     has_scheme: bool,
+    /// Shown in the title, e.g. the variant in the split view
+    label: Option<String>,
 }
 
 impl Validation {
@@ -34,8 +39,10 @@ impl Validation {
             props: Props::default(),
             results: None,
             warnings: Vec::new(),
+            matrix: None,
             scroll: 0,
             has_scheme: false,
+            label: None,
         }
     }
 
@@ -52,6 +59,14 @@ impl Validation {
         self.scroll = 0;
     }
 
+    pub fn set_matrix(&mut self, matrix: Option<ContrastMatrix>) {
+        self.matrix = matrix;
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
     fn content_lines(&self) -> Vec<Line<'static>> {
         use std::collections::HashMap;
 
@@ -61,6 +76,10 @@ impl Validation {
             return lines;
         }
 
+        if let Some(ref matrix) = self.matrix {
+            return Self::matrix_lines(matrix);
+        }
+
         let Some(ref results) = self.results else {
             return lines;
         };
@@ -70,41 +89,55 @@ impl Validation {
             result: &'a ValidationResult,
             lc00: Option<f64>,
             lc01: Option<f64>,
+            lc02: Option<f64>,
+            cr00: Option<f64>,
+            cr01: Option<f64>,
             passes: bool,
+            apca_fail: bool,
+            /// Smallest usage among the required pairs
+            usage: TextUsage,
         }
 
-        let mut fg_data: HashMap<&str, ColorData> = HashMap::new();
+        impl ColorData<'_> {
+            fn record(&mut self, result: &ValidationResult) {
+                let lc = Some(result.contrast.abs());
+                let cr = Some(result.wcag_ratio);
+                match result.pair.background {
+                    "base00" => (self.lc00, self.cr00) = (lc, cr),
+                    "base01" => (self.lc01, self.cr01) = (lc, cr),
+                    "base02" => self.lc02 = lc,
+                    _ => {}
+                }
+            }
+        }
 
-        // Process required results (these determine pass/fail)
-        for result in &results.required {
-            let fg = result.pair.foreground;
-            let bg = result.pair.background;
-            let abs_contrast = result.contrast.abs();
+        let standard = results.standard;
+
+        let mut fg_data: HashMap<&str, ColorData> = HashMap::new();
 
-            let entry = fg_data.entry(fg).or_insert(ColorData {
+        // Process required results (these determine pass/fail; for accents
+        // this is the worst of base00-base02)
+        for result in results.required.iter().filter(|r| !r.pair.is_custom()) {
+            let entry = fg_data.entry(result.pair.foreground).or_insert(ColorData {
                 result,
                 lc00: None,
                 lc01: None,
+                lc02: None,
+                cr00: None,
+                cr01: None,
                 passes: true,
+                apca_fail: false,
+                usage: result.usage,
             });
+            entry.usage = entry.usage.min(result.usage);
 
-            match bg {
-                "base00" => {
-                    entry.lc00 = Some(abs_contrast);
-                    // For UI colors, track worst case; for accents, only base00 matters
-                    if !result.passes {
-                        entry.passes = false;
-                    }
-                }
-                "base01" => {
-                    entry.lc01 = Some(abs_contrast);
-                    // For UI colors, both must pass
-                    if !result.passes {
-                        entry.passes = false;
-                    }
-                }
-                _ => {}
+            if standard.checks_apca() && !result.apca_passes {
+                entry.apca_fail = true;
+            }
+            if !result.passes {
+                entry.passes = false;
             }
+            entry.record(result);
 
             // Keep the first result for HellwigJmh data
             if entry.result.fg_hellwig.is_none() && result.fg_hellwig.is_some() {
@@ -112,14 +145,10 @@ impl Validation {
             }
         }
 
-        // Process reference results (informational only, for Lc01 display on accents)
+        // Process reference results (every accent on every background)
         for result in &results.reference {
-            let fg = result.pair.foreground;
-            let abs_contrast = result.contrast.abs();
-
-            if let Some(entry) = fg_data.get_mut(fg) {
-                // Reference results are always base01
-                entry.lc01 = Some(abs_contrast);
+            if let Some(entry) = fg_data.get_mut(result.pair.foreground) {
+                entry.record(result);
             }
         }
 
@@ -133,14 +162,19 @@ impl Validation {
         for fg in ui_colors {
             if let Some(data) = fg_data.get(fg) {
                 // UI colors don't have HellwigJmh or M bounds data
-                let (icon, style) = self.status_style(data.passes, false, true);
+                let (icon, style) = self.status_style(data.passes, data.apca_fail, false, true);
                 let lc00_str = data.lc00.map(|v| format!("{:.0}", v)).unwrap_or_default();
                 let lc01_str = data.lc01.map(|v| format!("{:.0}", v)).unwrap_or_default();
+                let cr00_str = data.cr00.map(|v| format!("{:.1}", v)).unwrap_or_default();
+                let cr01_str = data.cr01.map(|v| format!("{:.1}", v)).unwrap_or_default();
                 let text = format!(
-                    "  {}: Lc00={:>3} Lc01={:>3}{}",
+                    "  {}: Lc00={:>3} Lc01={:>3} CR00={:>4} CR01={:>4} {:<8}{}",
                     &fg[4..],
                     lc00_str,
                     lc01_str,
+                    cr00_str,
+                    cr01_str,
+                    usage_label(data.usage),
                     icon
                 );
                 lines.push(Line::from(Span::styled(text, style)));
@@ -154,7 +188,7 @@ impl Validation {
             Style::default().add_modifier(Modifier::BOLD),
         )));
         lines.push(Line::from(Span::styled(
-            "       J     M     h   Lc00 Lc01".to_string(),
+            "       J     M     h   Lc00 Lc01 Lc02  CR00 CR01 Use".to_string(),
             Style::default().add_modifier(Modifier::DIM),
         )));
 
@@ -167,9 +201,10 @@ impl Validation {
                 lines.push(self.format_accent_row(
                     fg,
                     data.result,
-                    data.lc00,
-                    data.lc01,
+                    [data.lc00, data.lc01, data.lc02],
+                    [data.cr00, data.cr01],
                     data.passes,
+                    data.apca_fail,
                 ));
             }
         }
@@ -183,13 +218,74 @@ impl Validation {
                 lines.push(self.format_accent_row(
                     fg,
                     data.result,
-                    data.lc00,
-                    data.lc01,
+                    [data.lc00, data.lc01, data.lc02],
+                    [data.cr00, data.cr01],
                     data.passes,
+                    data.apca_fail,
                 ));
             }
         }
 
+        // Pairs from [validation] pairs
+        let custom: Vec<&ValidationResult> = results
+            .required
+            .iter()
+            .filter(|r| r.pair.is_custom())
+            .collect();
+        if !custom.is_empty() {
+            lines.push(Line::from(Span::raw("")));
+            lines.push(Line::from(Span::styled(
+                "Custom pairs:".to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for result in custom {
+                let apca_fail = standard.checks_apca() && !result.apca_passes;
+                let (icon, style) = self.status_style(result.passes, apca_fail, false, true);
+                let text = format!(
+                    "  {} on {}: Lc={:>3.0} (min {:.0}) CR={:>4.1} {:<8}{}",
+                    &result.pair.foreground[4..],
+                    &result.pair.background[4..],
+                    result.contrast.abs(),
+                    result.pair.threshold.min_lc,
+                    result.wcag_ratio,
+                    usage_label(result.usage),
+                    icon
+                );
+                lines.push(Line::from(Span::styled(text, style)));
+            }
+        }
+
+        // Pairs from [validation.semantic], at their worst vision
+        if !results.semantic.is_empty() {
+            lines.push(Line::from(Span::raw("")));
+            lines.push(Line::from(Span::styled(
+                "Semantic pairs:".to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for result in &results.semantic {
+                let (icon, style) = if result.passes {
+                    ("", Style::default().fg(Color::Green))
+                } else {
+                    (" indistinct", Style::default().fg(Color::Red))
+                };
+                let vision = result.vision.map_or("normal", |kind| kind.name());
+                let hue = result
+                    .separation
+                    .hue
+                    .map_or_else(|| "-".to_string(), |h| format!("{h:.0}"));
+                let text = format!(
+                    "  {}/{} {:<12} dh={:>3} dJ={:>4.1}{}",
+                    &result.first[4..],
+                    &result.second[4..],
+                    vision,
+                    hue,
+                    result.separation.lightness,
+                    icon
+                );
+                lines.push(Line::from(Span::styled(text, style)));
+            }
+        }
+
         // Warnings
         if !self.warnings.is_empty() {
             lines.push(Line::from(Span::raw("")));
@@ -210,18 +306,81 @@ impl Validation {
         lines
     }
 
+    /// Heatmap of the contrast matrix: one two-column cell per pair showing
+    /// |Lc| / 10, shaded from gray (no contrast) to green (Lc 90+). Validated
+    /// pairs below their threshold are drawn red.
+    fn matrix_lines(matrix: &ContrastMatrix) -> Vec<Line<'static>> {
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let mut lines = Vec::new();
+
+        // Alternate label styles so adjacent two-digit columns stay readable
+        let mut header = vec![Span::styled("fg\\bg ".to_string(), dim)];
+        header.extend(matrix.slots.iter().enumerate().map(|(i, bg)| {
+            let style = if i % 2 == 0 {
+                dim
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            Span::styled(bg[4..].to_string(), style)
+        }));
+        lines.push(Line::from(header));
+
+        for &fg in &matrix.slots {
+            let mut spans = vec![Span::styled(format!("  {}  ", &fg[4..]), dim)];
+            for &bg in &matrix.slots {
+                let lc = matrix.get(fg, bg).unwrap_or_default().abs();
+                let style = if matrix.is_below_threshold(fg, bg) {
+                    Style::default()
+                        .bg(Color::Red)
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    let t = (lc / 90.0).clamp(0.0, 1.0);
+                    let lerp = |a: f64, b: f64| (a + (b - a) * t).round() as u8;
+                    let text = if t < 0.6 { Color::White } else { Color::Black };
+                    Style::default()
+                        .bg(Color::Rgb(
+                            lerp(40.0, 40.0),
+                            lerp(40.0, 170.0),
+                            lerp(40.0, 70.0),
+                        ))
+                        .fg(text)
+                };
+                spans.push(Span::styled(format!("{:>2}", (lc / 10.0) as u32), style));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(Span::raw("")));
+        lines.push(Line::from(Span::styled(
+            "Cells show APCA |Lc| / 10; red cells are validated pairs below threshold".to_string(),
+            dim,
+        )));
+        for failure in matrix.failures() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {} on {}: Lc {:.1} < {:.0}",
+                    failure.foreground, failure.background, failure.lc, failure.min_lc
+                ),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        lines
+    }
+
     fn format_accent_row(
         &self,
         fg: &str,
         result: &ValidationResult,
-        lc00: Option<f64>,
-        lc01: Option<f64>,
+        [lc00, lc01, lc02]: [Option<f64>; 3],
+        [cr00, cr01]: [Option<f64>; 2],
         passes: bool,
+        apca_fail: bool,
     ) -> Line<'static> {
         // Check gamut mapping and M bounds status
         let was_gamut_mapped = result.was_gamut_mapped;
         let m_in_bounds = result.m_in_bounds;
-        let (icon, style) = self.status_style(passes, was_gamut_mapped, m_in_bounds);
+        let (icon, style) = self.status_style(passes, apca_fail, was_gamut_mapped, m_in_bounds);
 
         let (j, m, h) = result
             .fg_hellwig
@@ -234,15 +393,28 @@ impl Validation {
         let lc01_str = lc01
             .map(|v| format!("{:>3.0}", v))
             .unwrap_or_else(|| "  -".to_string());
+        let lc02_str = lc02
+            .map(|v| format!("{:>3.0}", v))
+            .unwrap_or_else(|| "  -".to_string());
+        let cr00_str = cr00
+            .map(|v| format!("{:>4.1}", v))
+            .unwrap_or_else(|| "   -".to_string());
+        let cr01_str = cr01
+            .map(|v| format!("{:>4.1}", v))
+            .unwrap_or_else(|| "   -".to_string());
 
         let text = format!(
-            "  {} {:>5.1} {:>5.1} {:>5.1}  {} {}{}",
+            "  {} {:>5.1} {:>5.1} {:>5.1}  {}  {}  {}  {} {} {:<8}{}",
             &fg[4..],
             j,
             m,
             h,
             lc00_str,
             lc01_str,
+            lc02_str,
+            cr00_str,
+            cr01_str,
+            usage_label(result.usage),
             icon
         );
         Line::from(Span::styled(text, style))
@@ -252,12 +424,16 @@ impl Validation {
     fn status_style(
         &self,
         passes: bool,
+        apca_fail: bool,
         was_gamut_mapped: bool,
         m_in_bounds: bool,
     ) -> (&'static str, Style) {
-        if !passes {
-            // Failing - contrast too low
+        if !passes && apca_fail {
+            // Failing - APCA contrast too low
             (" Lc unreachable", Style::default().fg(Color::Red))
+        } else if !passes {
+            // Failing - WCAG 2.x ratio below AA
+            (" below WCAG AA", Style::default().fg(Color::Red))
         } else if !m_in_bounds {
             // M is outside the specified delta_m bounds
             (" M out of bounds", Style::default().fg(Color::Red))
@@ -279,6 +455,17 @@ impl Validation {
     }
 }
 
+/// Short form of a [`TextUsage`] for the results table.
+fn usage_label(usage: TextUsage) -> &'static str {
+    match usage {
+        TextUsage::BodyText => "body",
+        TextUsage::ContentText => "content",
+        TextUsage::LargeText => "large",
+        TextUsage::NonText => "non-text",
+        TextUsage::NotReadable => "none",
+    }
+}
+
 impl Default for Validation {
     fn default() -> Self {
         Self::new()
@@ -298,8 +485,18 @@ impl MockComponent for Validation {
             Style::default()
         };
 
+        let label = self
+            .label
+            .as_ref()
+            .map(|label| format!(": {label}"))
+            .unwrap_or_default();
+        let title = match (&self.matrix, &self.results) {
+            (Some(_), _) => format!(" Contrast Matrix{label} (APCA) "),
+            (None, Some(results)) => format!(" Validation{label} ({}) ", results.standard),
+            (None, None) => format!(" Validation{label} "),
+        };
         let block = Block::default()
-            .title(" Validation ")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(border_style);
 
@@ -0,0 +1,273 @@
+//! Applying the config's `[keys]` table to the dispatcher bindings.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use color_eyre::eyre::{Result, eyre};
+use crossterm_actions::{
+    ActionBinding, ActionConfig, AppEvent, InputEvent, KeyPress, NavigationEvent, SelectionEvent,
+    TuiEvent,
+};
+use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+use super::{AppAction, default_bindings};
+use crate::keys::{ACTIONS, KeyChord, KeyName, KeysConfig};
+
+/// Every action that can be bound, in `--print-keys` order.
+fn all_actions() -> Vec<AppAction> {
+    use AppEvent::{Filter, Help, Quit, Refresh, Search};
+    use InputEvent::{
+        Backspace, Cancel, Clear, Confirm, Delete, DeleteWord, DeleteWordBack, KillLine, WordLeft,
+        WordRight,
+    };
+    use NavigationEvent::{Back, Down, End, Forward, Home, Left, PageDown, PageUp, Right, Up};
+    use SelectionEvent::{First, Last, Next, Prev, SelectAll, Toggle};
+
+    let mut actions = vec![
+        AppAction::CodePreview,
+        AppAction::Gallery,
+        AppAction::Export,
+        AppAction::Screenshot,
+        AppAction::ValueDecrementSmall,
+        AppAction::ValueIncrementSmall,
+        AppAction::ValueDecrementLarge,
+        AppAction::ValueIncrementLarge,
+        AppAction::ToggleDarkLight,
+        AppAction::CycleCvd,
+        AppAction::CycleDisplaySimulation,
+        AppAction::ToggleContrastMatrix,
+        AppAction::ToggleSplitView,
+        AppAction::ToggleLog,
+        AppAction::TogglePerceptualHues,
+        AppAction::CycleHarmony,
+        AppAction::MaximizeContrast,
+        AppAction::Shuffle,
+        AppAction::ToggleGamutSlice,
+        AppAction::ToggleCurvePlot,
+        AppAction::RecallColor,
+        AppAction::ToggleFavoriteColor,
+        AppAction::TogglePin,
+        AppAction::EditAnchor,
+        AppAction::ReplayStep,
+        AppAction::ShrinkLeftColumn,
+        AppAction::GrowLeftColumn,
+        AppAction::ShrinkPreview,
+        AppAction::GrowPreview,
+        AppAction::NextPreviewTab,
+    ];
+    let tui = [
+        Left, Right, Up, Down, Home, End, PageUp, PageDown, Back, Forward,
+    ]
+    .map(TuiEvent::Navigation)
+    .into_iter()
+    .chain(
+        [
+            Confirm,
+            Cancel,
+            Delete,
+            Backspace,
+            Clear,
+            WordLeft,
+            WordRight,
+            DeleteWord,
+            DeleteWordBack,
+            KillLine,
+        ]
+        .map(TuiEvent::Input),
+    )
+    .chain([Next, Prev, Toggle, SelectAll, First, Last].map(TuiEvent::Selection))
+    .chain([Quit, Help, Refresh, Search, Filter].map(TuiEvent::App));
+    actions.extend(tui.map(AppAction::Tui));
+    actions
+}
+
+/// Config name of `action` (see [`ACTIONS`]).
+fn action_name(action: &AppAction) -> String {
+    match action {
+        AppAction::Tui(TuiEvent::Navigation(e)) => e.to_string(),
+        AppAction::Tui(TuiEvent::Input(e)) => e.to_string(),
+        AppAction::Tui(TuiEvent::Selection(e)) => e.to_string(),
+        AppAction::Tui(TuiEvent::App(e)) => e.to_string(),
+        AppAction::CodePreview => "code_preview".into(),
+        AppAction::Gallery => "gallery".into(),
+        AppAction::Export => "export".into(),
+        AppAction::Screenshot => "screenshot".into(),
+        AppAction::ValueIncrementSmall => "value_increment".into(),
+        AppAction::ValueDecrementSmall => "value_decrement".into(),
+        AppAction::ValueIncrementLarge => "value_increment_large".into(),
+        AppAction::ValueDecrementLarge => "value_decrement_large".into(),
+        AppAction::ToggleDarkLight => "toggle_dark_light".into(),
+        AppAction::ReplayStep => "replay_step".into(),
+        AppAction::CycleCvd => "cycle_cvd".into(),
+        AppAction::CycleDisplaySimulation => "cycle_display_simulation".into(),
+        AppAction::ToggleContrastMatrix => "toggle_contrast_matrix".into(),
+        AppAction::ToggleSplitView => "toggle_split_view".into(),
+        AppAction::ToggleLog => "toggle_log".into(),
+        AppAction::TogglePerceptualHues => "toggle_perceptual_hues".into(),
+        AppAction::CycleHarmony => "cycle_harmony".into(),
+        AppAction::MaximizeContrast => "maximize_contrast".into(),
+        AppAction::Shuffle => "shuffle".into(),
+        AppAction::ToggleGamutSlice => "toggle_gamut_slice".into(),
+        AppAction::ToggleCurvePlot => "toggle_curve_plot".into(),
+        AppAction::RecallColor => "recall_color".into(),
+        AppAction::ToggleFavoriteColor => "toggle_favorite_color".into(),
+        AppAction::TogglePin => "toggle_pin".into(),
+        AppAction::EditAnchor => "edit_anchor".into(),
+        AppAction::ShrinkLeftColumn => "shrink_left_column".into(),
+        AppAction::GrowLeftColumn => "grow_left_column".into(),
+        AppAction::ShrinkPreview => "shrink_preview".into(),
+        AppAction::GrowPreview => "grow_preview".into(),
+        AppAction::NextPreviewTab => "next_preview_tab".into(),
+    }
+}
+
+fn to_key_press(chord: KeyChord) -> KeyPress {
+    let code = match chord.key {
+        KeyName::Char(c) => KeyCode::Char(c),
+        KeyName::F(n) => KeyCode::F(n),
+        KeyName::Enter => KeyCode::Enter,
+        KeyName::Esc => KeyCode::Esc,
+        KeyName::Tab => KeyCode::Tab,
+        KeyName::BackTab => KeyCode::BackTab,
+        KeyName::Backspace => KeyCode::Backspace,
+        KeyName::Delete => KeyCode::Delete,
+        KeyName::Left => KeyCode::Left,
+        KeyName::Right => KeyCode::Right,
+        KeyName::Up => KeyCode::Up,
+        KeyName::Down => KeyCode::Down,
+        KeyName::Home => KeyCode::Home,
+        KeyName::End => KeyCode::End,
+        KeyName::PageUp => KeyCode::PageUp,
+        KeyName::PageDown => KeyCode::PageDown,
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for (held, modifier) in [
+        (chord.ctrl, KeyModifiers::CONTROL),
+        (chord.alt, KeyModifiers::ALT),
+        (chord.shift, KeyModifiers::SHIFT),
+    ] {
+        if held {
+            modifiers |= modifier;
+        }
+    }
+    KeyPress { code, modifiers }
+}
+
+/// `key` in the `[keys]` syntax, if it has one.
+fn key_name(key: &KeyPress) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(c) => KeyName::Char(c),
+        KeyCode::F(n) => KeyName::F(n),
+        KeyCode::Enter => KeyName::Enter,
+        KeyCode::Esc => KeyName::Esc,
+        KeyCode::Tab => KeyName::Tab,
+        KeyCode::BackTab => KeyName::BackTab,
+        KeyCode::Backspace => KeyName::Backspace,
+        KeyCode::Delete => KeyName::Delete,
+        KeyCode::Left => KeyName::Left,
+        KeyCode::Right => KeyName::Right,
+        KeyCode::Up => KeyName::Up,
+        KeyCode::Down => KeyName::Down,
+        KeyCode::Home => KeyName::Home,
+        KeyCode::End => KeyName::End,
+        KeyCode::PageUp => KeyName::PageUp,
+        KeyCode::PageDown => KeyName::PageDown,
+        _ => return None,
+    };
+    let chord = KeyChord {
+        key: name,
+        ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+        alt: key.modifiers.contains(KeyModifiers::ALT),
+        shift: key.modifiers.contains(KeyModifiers::SHIFT),
+    };
+    Some(chord.to_string())
+}
+
+/// The default bindings with the actions in `keys` rebound.
+///
+/// A rebound action loses its default keys, and every key it is given is
+/// taken from the action that had it by default.
+pub fn key_bindings(keys: &KeysConfig) -> Result<ActionConfig<AppAction>> {
+    let actions: HashMap<String, AppAction> = all_actions()
+        .into_iter()
+        .map(|action| (action_name(&action), action))
+        .collect();
+    let mut rebound: HashMap<AppAction, Vec<KeyPress>> = HashMap::new();
+    for (name, chords) in keys.bindings().map_err(|e| eyre!(e))? {
+        let action = actions
+            .get(name)
+            .ok_or_else(|| eyre!("keys.{name}: unknown action"))?;
+        rebound.insert(
+            action.clone(),
+            chords.into_iter().map(to_key_press).collect(),
+        );
+    }
+    let taken: HashSet<KeyPress> = rebound.values().flatten().copied().collect();
+
+    let defaults = default_bindings();
+    let mut config = ActionConfig::new(defaults.editing_mode);
+    let mut descriptions: HashMap<AppAction, String> = HashMap::new();
+    for binding in defaults.bindings() {
+        if rebound.contains_key(&binding.action) {
+            if let Some(ref description) = binding.description {
+                descriptions
+                    .entry(binding.action.clone())
+                    .or_insert_with(|| description.clone());
+            }
+            continue;
+        }
+        config.bind(ActionBinding {
+            keys: binding
+                .keys
+                .iter()
+                .filter(|key| !taken.contains(key))
+                .copied()
+                .collect(),
+            action: binding.action.clone(),
+            description: binding.description.clone(),
+        });
+    }
+    for action in all_actions() {
+        if let Some(keys) = rebound.remove(&action) {
+            let description = descriptions.remove(&action);
+            config.bind(ActionBinding {
+                keys,
+                action,
+                description,
+            });
+        }
+    }
+    config.compile();
+    Ok(config)
+}
+
+/// The effective bindings as a `[keys]` table (for `--print-keys`), with
+/// each action's description as a comment.
+pub fn key_bindings_table(keys: &KeysConfig) -> Result<String> {
+    let config = key_bindings(keys)?;
+    let help = config.help_entries();
+    let mut out = String::from("[keys]\n");
+    for action in all_actions() {
+        let name = action_name(&action);
+        debug_assert!(
+            ACTIONS.contains(&name.as_str()),
+            "{name} missing from ACTIONS"
+        );
+        let keys: Vec<String> = config
+            .bindings_for(&action)
+            .into_iter()
+            .filter_map(key_name)
+            .map(|key| toml::Value::String(key).to_string())
+            .collect();
+        let value = match keys.as_slice() {
+            [key] => key.clone(),
+            keys => format!("[{}]", keys.join(", ")),
+        };
+        let _ = write!(out, "{name} = {value}");
+        if let Some(description) = help.get(&action).and_then(|entry| entry.description) {
+            let _ = write!(out, "  # {description}");
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
@@ -0,0 +1,101 @@
+//! Adjustable pane sizes for the main screen.
+//!
+//! The ratios are saved to a small TOML state file on exit
+//! (`$XDG_STATE_HOME/themalingadingdong/tui.toml`, falling back to
+//! `~/.local/state`) and restored on the next launch. Session logs carry the
+//! layout in their `start` entry so a replay opens with the recorded panes.
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+/// Percentage step for one resize keypress.
+pub const RESIZE_STEP: i16 = 5;
+
+/// Smallest share either side of a split may shrink to.
+const MIN_PERCENT: u16 = 20;
+
+/// Largest share either side of a split may grow to.
+const MAX_PERCENT: u16 = 80;
+
+/// Split ratios of the main screen, in percent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaneLayout {
+    /// Width of the left (palette/preview) column
+    pub left_column: u16,
+    /// Height of the preview within the left column
+    pub preview: u16,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            left_column: 50,
+            preview: 30,
+        }
+    }
+}
+
+impl PaneLayout {
+    /// Grow (positive) or shrink (negative) the left column.
+    pub fn resize_columns(&mut self, delta: i16) {
+        self.left_column = step(self.left_column, delta);
+    }
+
+    /// Grow (positive) or shrink (negative) the preview.
+    pub fn resize_preview(&mut self, delta: i16) {
+        self.preview = step(self.preview, delta);
+    }
+
+    /// Clamp ratios read from disk into the supported range.
+    fn clamped(self) -> Self {
+        Self {
+            left_column: step(self.left_column, 0),
+            preview: step(self.preview, 0),
+        }
+    }
+
+    /// Default location of the TUI state file, if a home directory is known.
+    pub fn state_path() -> Option<PathBuf> {
+        Some(state_dir()?.join("tui.toml"))
+    }
+
+    /// Load the layout from a state file, using defaults if it does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        let layout: Self = toml::from_str(&content)
+            .wrap_err_with(|| format!("Failed to parse {}", path.display()))?;
+        Ok(layout.clamped())
+    }
+
+    /// Save the layout to a state file, creating parent directories.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Directory for TUI state files, if a home directory is known.
+pub(super) fn state_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state"))
+        })?;
+    Some(base.join("themalingadingdong"))
+}
+
+fn step(percent: u16, delta: i16) -> u16 {
+    (percent as i16 + delta).clamp(MIN_PERCENT as i16, MAX_PERCENT as i16) as u16
+}
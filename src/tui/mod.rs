@@ -5,34 +5,54 @@
 
 mod activities;
 mod activity;
+mod color_memory;
 mod components;
 mod highlighting;
+mod keymap;
+mod layout;
 mod model;
+mod resume;
+mod screenshot;
+mod session;
 mod snippets;
+mod worker;
 
 use std::io::stdout;
-use std::sync::LazyLock;
+use std::path::Path;
+use std::sync::OnceLock;
 
 use color_eyre::eyre::Result;
 use crossterm_actions::{
-    AppEvent, TuiEvent, TuiRealmDispatcher, bind_action, emacs_defaults, keys,
+    ActionConfig, AppEvent, TuiEvent, TuiRealmDispatcher, bind_action, emacs_defaults, keys,
 };
+use palette::Srgb;
 use ratatui::{
     Terminal,
+    buffer::Buffer,
     crossterm::ExecutableCommand,
     crossterm::terminal::{
         EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
     },
     prelude::CrosstermBackend,
+    style::Color,
 };
+use tinted_builder::Base16Scheme;
 
 use crate::cli::Cli;
+use crate::keys::KeysConfig;
+use crate::term_color::{ColorDepth, TermColor, TermPalette};
+use crate::viewing::with_viewing_conditions;
 
 pub use highlighting::Highlighter;
+pub use keymap::key_bindings_table;
 pub use model::Model;
 
 use activities::Msg;
 use activity::{ActivityManager, Context};
+use color_memory::ColorMemory;
+use layout::{PaneLayout, RESIZE_STEP};
+use resume::SavedSession;
+use worker::RegenWorker;
 
 // ============================================================================
 // Event handling (shared across activities)
@@ -45,8 +65,12 @@ pub enum AppAction {
     Tui(TuiEvent),
     /// Switch to code preview screen
     CodePreview,
+    /// Switch to the scheme gallery
+    Gallery,
     /// Export the current palette to a file
     Export,
+    /// Save an image of the palette and preview
+    Screenshot,
     /// Increment value by small step (1)
     ValueIncrementSmall,
     /// Decrement value by small step (1)
@@ -57,11 +81,58 @@ pub enum AppAction {
     ValueDecrementLarge,
     /// Toggle between dark and light variants
     ToggleDarkLight,
+    /// Apply the next change of a stepwise replay
+    ReplayStep,
+    /// Cycle the colorblindness simulation overlay
+    CycleCvd,
+    /// Cycle the simulated display for the palette and preview
+    CycleDisplaySimulation,
+    /// Toggle the contrast matrix heatmap in the validation pane
+    ToggleContrastMatrix,
+    /// Toggle the linked dark/light split view
+    ToggleSplitView,
+    /// Show or hide the log panel
+    ToggleLog,
+    /// Toggle perceptual accent hue spacing
+    TogglePerceptualHues,
+    /// Cycle the color-harmony rule placing the accent hues
+    CycleHarmony,
+    /// Raise the contrast floors to the highest feasible values
+    MaximizeContrast,
+    /// Sample random parameter sets and pick one to start from
+    Shuffle,
+    /// Show the selected swatch's gamut slice in place of the preview
+    ToggleGamutSlice,
+    /// Show the focused interpolation curve in place of the preview
+    ToggleCurvePlot,
+    /// Load the next remembered color into the focused picker
+    RecallColor,
+    /// Star or unstar the focused picker's color
+    ToggleFavoriteColor,
+    /// Pin or unpin the selected accent at its current color
+    TogglePin,
+    /// Anchor the selected accent near a hex brand color
+    EditAnchor,
+    /// Narrow the palette/preview column
+    ShrinkLeftColumn,
+    /// Widen the palette/preview column
+    GrowLeftColumn,
+    /// Shrink the preview pane
+    ShrinkPreview,
+    /// Grow the preview pane
+    GrowPreview,
+    /// Show the next preview mockup
+    NextPreviewTab,
 }
 
 /// Global dispatcher instance - shared by all components.
-/// Using LazyLock for zero-cost lazy initialization.
-pub static DISPATCHER: LazyLock<TuiRealmDispatcher<AppAction>> = LazyLock::new(|| {
+///
+/// Set from the config's `[keys]` table when the TUI starts; the default
+/// bindings otherwise.
+static DISPATCHER: OnceLock<TuiRealmDispatcher<AppAction>> = OnceLock::new();
+
+/// Built-in key bindings, before `[keys]` overrides.
+fn default_bindings() -> ActionConfig<AppAction> {
     // Transform emacs defaults to wrap in AppAction::Tui
     let mut config = emacs_defaults().map_actions(AppAction::Tui);
 
@@ -72,7 +143,19 @@ pub static DISPATCHER: LazyLock<TuiRealmDispatcher<AppAction>> = LazyLock::new(|
         keys::char('c'),
         "View code preview"
     );
+    bind_action!(
+        config,
+        AppAction::Gallery,
+        keys::char('o'),
+        "Open scheme gallery"
+    );
     bind_action!(config, AppAction::Export, keys::char('e'), "Export palette");
+    bind_action!(
+        config,
+        AppAction::Screenshot,
+        keys::char('z'),
+        "Save palette/preview screenshot"
+    );
     bind_action!(
         config,
         AppAction::ValueDecrementSmall,
@@ -103,14 +186,153 @@ pub static DISPATCHER: LazyLock<TuiRealmDispatcher<AppAction>> = LazyLock::new(|
         keys::char('t'),
         "Toggle dark/light variant"
     );
+    bind_action!(
+        config,
+        AppAction::CycleCvd,
+        keys::char('v'),
+        "Cycle colorblindness simulation"
+    );
+    bind_action!(
+        config,
+        AppAction::CycleDisplaySimulation,
+        keys::char('b'),
+        "Cycle display simulation"
+    );
+    bind_action!(
+        config,
+        AppAction::ToggleContrastMatrix,
+        keys::char('g'),
+        "Toggle contrast matrix"
+    );
+    bind_action!(
+        config,
+        AppAction::ToggleSplitView,
+        keys::char('d'),
+        "Toggle dark+light split view"
+    );
+    bind_action!(
+        config,
+        AppAction::ToggleLog,
+        keys::char('l'),
+        "Toggle log panel"
+    );
+    bind_action!(
+        config,
+        AppAction::TogglePerceptualHues,
+        keys::char('p'),
+        "Toggle perceptual hue spacing"
+    );
+    bind_action!(
+        config,
+        AppAction::CycleHarmony,
+        keys::char('y'),
+        "Cycle hue harmony"
+    );
+    bind_action!(
+        config,
+        AppAction::MaximizeContrast,
+        keys::char('a'),
+        "Autotune contrast floors"
+    );
+    bind_action!(
+        config,
+        AppAction::Shuffle,
+        keys::char('x'),
+        "Shuffle parameters"
+    );
+    bind_action!(
+        config,
+        AppAction::ToggleGamutSlice,
+        keys::char('j'),
+        "Toggle gamut slice"
+    );
+    bind_action!(
+        config,
+        AppAction::ToggleCurvePlot,
+        keys::char('u'),
+        "Toggle curve plot"
+    );
+    bind_action!(
+        config,
+        AppAction::RecallColor,
+        keys::char('m'),
+        "Recall remembered color"
+    );
+    bind_action!(
+        config,
+        AppAction::ToggleFavoriteColor,
+        keys::char('s'),
+        "Star/unstar color"
+    );
+    bind_action!(
+        config,
+        AppAction::TogglePin,
+        keys::char('i'),
+        "Pin/unpin accent"
+    );
+    bind_action!(
+        config,
+        AppAction::EditAnchor,
+        keys::char('#'),
+        "Anchor accent to a color"
+    );
+    bind_action!(
+        config,
+        AppAction::ReplayStep,
+        keys::char('n'),
+        "Next replay step"
+    );
+
+    bind_action!(
+        config,
+        AppAction::ShrinkLeftColumn,
+        keys::char('<'),
+        "Narrow left column"
+    );
+    bind_action!(
+        config,
+        AppAction::GrowLeftColumn,
+        keys::char('>'),
+        "Widen left column"
+    );
+    bind_action!(
+        config,
+        AppAction::ShrinkPreview,
+        keys::char('-'),
+        "Shrink preview"
+    );
+    bind_action!(
+        config,
+        AppAction::GrowPreview,
+        keys::char('+'),
+        "Grow preview"
+    );
+    bind_action!(
+        config,
+        AppAction::NextPreviewTab,
+        keys::char('w'),
+        "Next preview tab"
+    );
 
-    config.compile();
-    TuiRealmDispatcher::new(config)
-});
+    config
+}
 
 /// Convenience function for components to access the dispatcher.
 pub fn dispatcher() -> &'static TuiRealmDispatcher<AppAction> {
-    &DISPATCHER
+    DISPATCHER.get_or_init(|| {
+        TuiRealmDispatcher::new(
+            keymap::key_bindings(&KeysConfig::default()).expect("default bindings are valid"),
+        )
+    })
+}
+
+/// Install the key bindings of `keys` for this session.
+///
+/// Has no effect once a component has dispatched a key.
+pub fn init_key_bindings(keys: &KeysConfig) -> Result<()> {
+    let config = keymap::key_bindings(keys)?;
+    let _ = DISPATCHER.set(TuiRealmDispatcher::new(config));
+    Ok(())
 }
 
 /// Handle global application events that are common across all components.
@@ -121,33 +343,143 @@ pub fn handle_global_app_events(action: &AppAction) -> Option<Msg> {
         AppAction::Tui(TuiEvent::App(AppEvent::Help)) => Some(Msg::ShowHelp),
         AppAction::Tui(TuiEvent::App(AppEvent::Refresh)) => Some(Msg::Regenerate),
         AppAction::CodePreview => Some(Msg::SwitchToCodePreview),
+        AppAction::Gallery => Some(Msg::SwitchToGallery),
         AppAction::Export => Some(Msg::DoExport),
+        AppAction::Screenshot => Some(Msg::Screenshot),
         AppAction::ToggleDarkLight => Some(Msg::ToggleDarkLight),
+        AppAction::ReplayStep => Some(Msg::ReplayStep),
+        AppAction::CycleCvd => Some(Msg::CycleCvdSimulation),
+        AppAction::CycleDisplaySimulation => Some(Msg::CycleDisplaySimulation),
+        AppAction::ToggleContrastMatrix => Some(Msg::ToggleContrastMatrix),
+        AppAction::ToggleSplitView => Some(Msg::ToggleSplitView),
+        AppAction::ToggleLog => Some(Msg::ToggleLog),
+        AppAction::TogglePerceptualHues => Some(Msg::TogglePerceptualHues),
+        AppAction::CycleHarmony => Some(Msg::CycleHarmony),
+        AppAction::MaximizeContrast => Some(Msg::MaximizeContrast),
+        AppAction::Shuffle => Some(Msg::Shuffle),
+        AppAction::ToggleGamutSlice => Some(Msg::ToggleGamutSlice),
+        AppAction::ToggleCurvePlot => Some(Msg::ToggleCurvePlot),
+        AppAction::ShrinkLeftColumn => Some(Msg::ResizeColumns(-RESIZE_STEP)),
+        AppAction::GrowLeftColumn => Some(Msg::ResizeColumns(RESIZE_STEP)),
+        AppAction::ShrinkPreview => Some(Msg::ResizePreview(-RESIZE_STEP)),
+        AppAction::GrowPreview => Some(Msg::ResizePreview(RESIZE_STEP)),
+        AppAction::NextPreviewTab => Some(Msg::NextPreviewTab),
         _ => None,
     }
 }
 
+// ============================================================================
+// Color depth
+// ============================================================================
+
+/// ANSI colors 0-15 by index, as ratatui names them (so 16-color terminals
+/// get the basic and bright codes rather than 256-color ones).
+const ANSI16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Replace the 24-bit colors drawn into `buffer` with the closest a terminal
+/// of `depth` can show; at 16 colors, `scheme`'s slots become the ANSI
+/// colors they back.
+pub(crate) fn downsample_buffer(
+    buffer: &mut Buffer,
+    depth: ColorDepth,
+    scheme: Option<&Base16Scheme>,
+) {
+    if depth == ColorDepth::TrueColor {
+        return;
+    }
+    let mut palette = TermPalette::new(depth, scheme);
+    let mut map = |color: Color| match color {
+        Color::Rgb(r, g, b) => match palette.map(Srgb::new(r, g, b)) {
+            TermColor::Indexed(i) if i < 16 => ANSI16[usize::from(i)],
+            TermColor::Indexed(i) => Color::Indexed(i),
+            TermColor::Rgb(c) => Color::Rgb(c.red, c.green, c.blue),
+        },
+        other => other,
+    };
+    for cell in &mut buffer.content {
+        cell.fg = map(cell.fg);
+        cell.bg = map(cell.bg);
+    }
+}
+
 // ============================================================================
 // TUI entry point
 // ============================================================================
 
+/// Write a screenshot of the palette and preview panels for the CLI
+/// parameters, without starting the TUI.
+pub fn screenshot(cli: &Cli, path: &Path) -> Result<()> {
+    let mut model = Model::from_cli(cli)?;
+    model.regenerate();
+    screenshot::save_screenshot(&model, path)
+}
+
 /// Run the interactive TUI using activity-based architecture.
 pub fn run(cli: &Cli) -> Result<()> {
+    // Initialize model from CLI args (before the terminal is taken over, so
+    // errors such as a missing --resume session print normally)
+    let mut model = Model::from_cli(cli)?;
+    if let Some(warning) = model.color_depth.warning() {
+        model.message = Some(format!("Warning: {warning}"));
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
 
-    // Initialize model from CLI args
-    let mut model = Model::from_cli(cli)?;
     model.regenerate();
+    model.worker = Some(RegenWorker::spawn());
 
     // Create context and activity manager
+    let viewing = model.viewing;
     let context = Context { model };
     let mut manager = ActivityManager::new(context);
 
-    // Run the activity loop
-    let result = manager.run(&mut terminal);
+    // Run the activity loop, converting colors under the session's
+    // viewing conditions
+    let result = with_viewing_conditions(&viewing, || manager.run(&mut terminal));
+
+    // Persist pane sizes, used colors, and the editing state for the next
+    // launch (replays keep the recorded layout and don't count as use)
+    if let Some(context) = manager.context_mut()
+        && context.model.replay.is_none()
+    {
+        let model = &mut context.model;
+        if let Some(path) = PaneLayout::state_path()
+            && let Err(e) = model.layout.save(&path)
+        {
+            tracing::warn!(error = %e, "failed to save TUI state");
+        }
+        model.remember_colors();
+        if let Some(path) = ColorMemory::state_path()
+            && let Err(e) = model.color_memory.save(&path)
+        {
+            tracing::warn!(error = %e, "failed to save remembered colors");
+        }
+        if let Some(path) = SavedSession::state_path()
+            && let Err(e) = model.saved_session().save(&path)
+        {
+            tracing::warn!(error = %e, "failed to save session");
+        }
+    }
 
     // Cleanup terminal
     disable_raw_mode()?;
@@ -1,21 +1,55 @@
 //! Application model for the TUI.
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use color_eyre::eyre::{Result, WrapErr};
 use palette::Srgb;
 use tinted_builder::{Base16Scheme, SchemeVariant};
+use tracing::warn;
 use tuirealm::Update;
 
+use crate::ansi::AnsiConfig;
+use crate::autotune::{max_feasible_contrast, max_feasible_linked_contrast};
 use crate::cli::{Cli, OutputFormat, VariantArg};
-use crate::config::{AccentOptSettings, ThemeConfig, load_config};
+use crate::config::{
+    AccentAnchor, AccentOptSettings, CommentContrast, ThemeConfig, load_config_with_profile,
+};
+use crate::contrast::default_model;
 use crate::curves::InterpolationConfig;
-use crate::generate::{GenerateConfig, generate_for_variant, parse_color};
+use crate::cvd::{CvdKind, DEFAULT_CVD_THRESHOLD};
+use crate::display_sim::DisplaySimulation;
+use crate::explore::{Candidate, EXPLORE_SHOWN, ExploreConfig, explore, random_seed};
+use crate::extended_accents::{ExtendedAccentMode, ExtendedAccents};
+use crate::fit::fit_scheme;
+use crate::gamut_map::Gamut;
+use crate::generate::{GenerateConfig, GenerationCache, parse_color};
+use crate::harmony::{Harmony, HarmonyConfig};
 use crate::hellwig::HellwigJmh;
+use crate::hue_links::HueLinks;
+use crate::hue_spacing::HueSpacing;
 use crate::import::import_scheme;
-use crate::validation::{ValidationResults, validate_with_accent_data};
+use crate::interpolation::{DEFAULT_BASE16_HUES, srgb_to_hex};
+use crate::keys::KeysConfig;
+use crate::logging::log_buffer;
+use crate::metadata::check_metadata;
+use crate::ramp::LightnessRamp;
+use crate::term_color::ColorDepth;
+use crate::validation::{
+    ContrastStandard, ValidationConfig, ValidationResults, validate_with_config,
+};
+use crate::viewing::{ViewingConditions, with_viewing_conditions};
+use crate::wide_gamut::WidePalette;
 
 use super::activities::Msg;
+use super::color_memory::ColorMemory;
+use super::components::preview::PreviewTab;
+use super::components::{CurvePlot, GamutSlice, Palette};
+use super::layout::PaneLayout;
+use super::resume::SavedSession;
+use super::screenshot::save_screenshot;
+use super::session::{ReplayMode, SessionRecorder, SessionReplay};
+use super::worker::{Counterpart, Generation, GenerationJob, RegenWorker};
 
 /// HellwigJmh color components for editing.
 #[derive(Debug, Clone, Copy)]
@@ -70,13 +104,35 @@ pub struct Model {
     pub min_contrast: f64,
     pub extended_min_contrast: f64,
     pub max_lightness_adjustment: f32,
+    pub comment_contrast: Option<CommentContrast>,
+    pub ramp: LightnessRamp,
+    pub viewing: ViewingConditions,
+    pub ansi: AnsiConfig,
+    pub contrast_standard: ContrastStandard,
+    pub validation_thresholds: ValidationConfig,
+    pub gamut: Gamut,
+    pub foreground_headroom: f32,
     pub hue_overrides: [Option<f32>; 8],
+    /// Hues held at an offset from another accent's
+    pub hue_links: HueLinks,
+    /// Harmony rule the hues were last placed by
+    pub harmony: Option<Harmony>,
+    /// Hue the harmony rule is anchored on (follows rotations)
+    pub harmony_seed: f32,
+    pub adaptive_hues: bool,
+    pub hue_spacing: HueSpacing,
+    pub perceptual_hues: bool,
+    pub pins: BTreeMap<String, Srgb<u8>>,
     pub variant: VariantArg,
     pub name: String,
     pub author: String,
+    pub description: Option<String>,
     pub interpolation: InterpolationConfig,
     pub accent_opt: AccentOptSettings,
     pub extended_accent_opt: AccentOptSettings,
+    pub extended_accents: ExtendedAccents,
+    /// Bounds the shuffle key samples within
+    pub explore: ExploreConfig,
 
     // Derived sRGB colors
     pub background: Srgb<u8>,
@@ -84,15 +140,66 @@ pub struct Model {
 
     // Generated output
     pub current_scheme: Option<Base16Scheme>,
+    pub wide_palette: Option<WidePalette>,
     pub generation_warnings: Vec<String>,
     pub validation_results: Option<ValidationResults>,
+    /// Opposite variant shown in the split view
+    pub counterpart: Option<Counterpart>,
 
     // UI state
     pub quit: bool,
     pub show_help: bool,
     pub message: Option<String>,
+    pub cvd_overlay: Option<CvdKind>,
+    pub cvd_threshold: f32,
+    /// Display simulation applied to the palette and preview panes
+    pub display_simulation: Option<DisplaySimulation>,
+    /// Colors the terminal can show; the UI is downsampled to them
+    pub color_depth: ColorDepth,
+    pub show_contrast_matrix: bool,
+    /// Show the dark and light variants side by side, generated as a pair
+    pub split_view: bool,
+    /// Show the log panel under the editor
+    pub show_log: bool,
+    /// Log warnings already seen, so the title can flag new ones while the
+    /// panel is hidden
+    pub log_warnings_seen: u64,
+    /// Best shuffled candidates, listed in the picker while non-empty
+    pub explore_candidates: Vec<Candidate>,
+    /// Candidate highlighted in the shuffle picker
+    pub explore_selected: usize,
+    /// Mockup shown in the preview pane
+    pub preview_tab: PreviewTab,
+    /// Palette swatch selected for inspection (index in Base24 order)
+    pub selected_swatch: usize,
+    /// Show the inspector for the selected swatch
+    pub inspect_swatch: bool,
+    /// Show the selected swatch's gamut slice in place of the preview
+    pub show_gamut_slice: bool,
+    /// Show `plotted_curve` in place of the preview
+    pub show_curve_plot: bool,
+    /// Curve focused in the curve controls: 0 lightness, 1 colorfulness, 2 hue
+    pub plotted_curve: u8,
+    pub layout: PaneLayout,
+    pub color_memory: ColorMemory,
+    pub snippets_dir: Option<PathBuf>,
+    pub gallery_dir: Option<PathBuf>,
     pub export_path: String,
     pub output_format: OutputFormat,
+    /// Text of the scheme file being edited, so a YAML export keeps its
+    /// comments and layout
+    pub imported_source: Option<String>,
+    /// Variable-name prefix for web formats (`--var-prefix`)
+    pub var_prefix: String,
+
+    // Session recording/replay
+    pub recorder: Option<SessionRecorder>,
+    pub replay: Option<SessionReplay>,
+
+    // Background regeneration (None regenerates inline)
+    pub worker: Option<RegenWorker>,
+    /// Stages reused by inline regeneration
+    generation_cache: GenerationCache,
 }
 
 impl Model {
@@ -102,46 +209,162 @@ impl Model {
     /// defaults < TOML file < CLI args
     ///
     /// If `--input` is specified, loads the scheme file for editing and validates it.
+    /// If `--replay` is specified, starts from the session log's initial state instead.
+    /// If `--resume` is specified, restores the state saved when the TUI last closed.
     pub fn from_cli(cli: &Cli) -> Result<Self> {
-        use crate::validation::{validate, validate_with_warnings};
+        use crate::validation::validation_warnings;
 
-        // Handle import if --input is specified
-        let (theme_config, imported_scheme, validation_results) =
-            if let Some(ref input_path) = cli.input {
-                let import_result = import_scheme(input_path)?;
-
-                // Validate the imported scheme (for stderr output)
-                let warnings = validate_with_warnings(&import_result.scheme);
-                if !warnings.is_empty() {
-                    eprintln!("Imported scheme validation:");
-                    for warning in &warnings {
-                        eprintln!("  {}", warning);
-                    }
-                }
+        if let Some(ref replay_path) = cli.replay {
+            let mode = if cli.replay_step {
+                ReplayMode::Step
+            } else if cli.replay_speed > 0.0 {
+                ReplayMode::Timed(cli.replay_speed)
+            } else {
+                color_eyre::eyre::bail!("--replay-speed must be positive");
+            };
+            let (config, variant, replay) = SessionReplay::load(replay_path, mode)?;
+            super::init_key_bindings(&user_keys(cli)?)?;
+            let mut model = Self::from_theme_config(&config, variant, cli.format)?;
+            model.layout = replay.layout();
+            model.replay = Some(replay);
+            model.var_prefix = cli.var_prefix.clone();
+            model.cvd_threshold = cli.cvd_threshold;
+            model.display_simulation = cli.simulate.map(Into::into);
+            model.color_depth = cli.color_depth(true);
+            return Ok(model);
+        }
 
-                // Also get full validation results for TUI display
-                let results = validate(&import_result.scheme);
+        let resumed = if cli.resume {
+            let path = SavedSession::state_path()
+                .ok_or_else(|| color_eyre::eyre::eyre!("No state directory for --resume"))?;
+            Some(SavedSession::load(&path)?)
+        } else {
+            None
+        };
 
-                (
-                    import_result.config,
-                    Some(import_result.scheme),
-                    Some(results),
-                )
+        // Handle import if --input is specified
+        let (theme_config, imported_scheme, validation_results) = if let Some(ref session) = resumed
+        {
+            (session.config.clone(), None, None)
+        } else if let Some(ref input_path) = cli.input {
+            let import_result = import_scheme(input_path)?;
+            let standard = cli.contrast_standard.map(Into::into).unwrap_or_default();
+
+            // With --fit, start editing from the recovered parameters
+            let mut config = if cli.fit {
+                let fit = fit_scheme(&import_result.scheme)?;
+                eprintln!(
+                    "Fitted parameters: mean delta E {:.1}, max {:.1}",
+                    fit.mean_error(),
+                    fit.max_error()
+                );
+                let mut fitted = ThemeConfig::from_generate_config(&fit.config);
+                fitted.theme.variant = import_result.config.theme.variant;
+                fitted
             } else {
-                // Normal flow: load configuration with Figment layering
-                let config = load_config(cli.config.as_deref(), &cli.to_config_overrides())
-                    .map_err(|e| color_eyre::eyre::eyre!("Configuration error: {}", e))?;
-                (config, None, None)
+                import_result.config
             };
+            config.contrast.standard = standard;
+            if cli.config.is_some() {
+                let loaded = load_config_with_profile(
+                    cli.config.as_deref(),
+                    cli.profile.as_deref(),
+                    &cli.to_config_overrides(),
+                )
+                .map_err(|e| color_eyre::eyre::eyre!("Configuration error: {}", e))?;
+                config.preview = loaded.preview;
+                config.validation = loaded.validation;
+                config.keys = loaded.keys;
+            }
+
+            // Validate the imported scheme (for stderr output)
+            let warnings = validation_warnings(&import_result.scheme, standard, &config.validation);
+            if !warnings.is_empty() {
+                eprintln!("Imported scheme validation:");
+                for warning in &warnings {
+                    eprintln!("  {}", warning);
+                }
+            }
+            for issue in check_metadata(&import_result.scheme) {
+                eprintln!("Metadata warning: {issue}");
+            }
+
+            // Also get full validation results for TUI display
+            let results = validate_with_config(
+                &import_result.scheme,
+                &[],
+                &[],
+                standard,
+                &config.validation,
+            );
 
-        let mut model = Self::from_theme_config(&theme_config, cli.variant, cli.format)?;
+            (
+                config,
+                Some((import_result.scheme, import_result.source)),
+                Some(results),
+            )
+        } else {
+            // Normal flow: load configuration with Figment layering
+            let config = load_config_with_profile(
+                cli.config.as_deref(),
+                cli.profile.as_deref(),
+                &cli.to_config_overrides(),
+            )
+            .map_err(|e| color_eyre::eyre::eyre!("Configuration error: {}", e))?;
+            (config, None, None)
+        };
+
+        if resumed.is_some() {
+            // Sessions hold the theme, not the user's bindings
+            super::init_key_bindings(&user_keys(cli)?)?;
+        } else {
+            super::init_key_bindings(&theme_config.keys)?;
+        }
+
+        let variant = resumed.as_ref().map_or(cli.variant, |s| s.variant);
+        let mut model = Self::from_theme_config(&theme_config, variant, cli.format)?;
+        model.var_prefix = cli.var_prefix.clone();
+        model.cvd_threshold = cli.cvd_threshold;
+        model.display_simulation = cli.simulate.map(Into::into);
+        model.color_depth = cli.color_depth(true);
+        if let Some(session) = resumed {
+            model.background_hellwig = session.background.into();
+            model.foreground_hellwig = session.foreground.into();
+            model.hue_spacing = session.hue_spacing;
+            model.export_path = session.export_path;
+            model.show_contrast_matrix = session.show_contrast_matrix;
+            model.split_view = session.split_view;
+            model.preview_tab = session.preview_tab;
+        }
+        if let Some(path) = PaneLayout::state_path() {
+            model.layout = PaneLayout::load(&path).unwrap_or_else(|e| {
+                warn!(error = %e, "ignoring unreadable TUI state");
+                PaneLayout::default()
+            });
+        }
+        if let Some(path) = ColorMemory::state_path() {
+            model.color_memory = ColorMemory::load(&path).unwrap_or_else(|e| {
+                warn!(error = %e, "ignoring unreadable color memory");
+                ColorMemory::default()
+            });
+        }
 
         // If we imported a scheme, store it and its validation results
-        if let Some(scheme) = imported_scheme {
+        if let Some((scheme, source)) = imported_scheme {
             model.current_scheme = Some(scheme);
+            model.imported_source = Some(source);
             model.validation_results = validation_results;
         }
 
+        if let Some(ref record_path) = cli.record {
+            model.recorder = Some(SessionRecorder::create(
+                record_path,
+                model.theme_config(),
+                model.variant,
+                model.layout,
+            )?);
+        }
+
         Ok(model)
     }
 
@@ -172,8 +395,13 @@ impl Model {
         let foreground = parse_color(&fg_str)
             .map_err(|e| color_eyre::eyre::eyre!("Invalid foreground color: {}", e))?;
 
-        let background_hellwig = HellwigComponents::from_srgb(background);
-        let foreground_hellwig = HellwigComponents::from_srgb(foreground);
+        let (background_hellwig, foreground_hellwig) =
+            with_viewing_conditions(&config.viewing, || {
+                (
+                    HellwigComponents::from_srgb(background),
+                    HellwigComponents::from_srgb(foreground),
+                )
+            });
 
         let hue_overrides = config
             .colors
@@ -181,11 +409,21 @@ impl Model {
             .as_ref()
             .map(|h| h.to_array())
             .unwrap_or([None; 8]);
+        let hue_links = config
+            .hue_links()
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid hue links: {}", e))?;
+        let hue_overrides = hue_links.resolve(config.with_harmony(hue_overrides));
 
-        let export_path = match format {
-            OutputFormat::Yaml => String::from("scheme.yaml"),
-            OutputFormat::Json => String::from("scheme.json"),
-        };
+        let pins = config
+            .colors
+            .pins
+            .iter()
+            .flatten()
+            .map(|(slot, color)| Ok((slot.clone(), parse_color(color)?)))
+            .collect::<Result<BTreeMap<_, _>, String>>()
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid pinned color: {}", e))?;
+
+        let export_path = format!("scheme.{}", format.extension());
 
         Ok(Self {
             background_hellwig,
@@ -193,26 +431,79 @@ impl Model {
             min_contrast: config.contrast.minimum,
             extended_min_contrast: config.contrast.extended_minimum,
             max_lightness_adjustment: config.contrast.max_adjustment,
+            comment_contrast: config.contrast.comment,
+            ramp: config.ramp,
+            viewing: config.viewing,
+            ansi: config.ansi.clone().unwrap_or_default(),
+            contrast_standard: config.contrast.standard,
+            validation_thresholds: config.validation.clone(),
+            gamut: config.colors.gamut,
+            foreground_headroom: config.colors.foreground_headroom.unwrap_or(0.0),
             hue_overrides,
+            hue_links,
+            harmony: config.colors.harmony.map(|h| h.rule),
+            harmony_seed: config
+                .colors
+                .harmony
+                .map_or(DEFAULT_BASE16_HUES[0], |h| h.seed()),
+            adaptive_hues: config.colors.adaptive_hues.unwrap_or(true),
+            hue_spacing: config.colors.hue_spacing.unwrap_or_default(),
+            perceptual_hues: config.colors.hue_spacing.is_some(),
+            pins,
             variant,
             name,
             author: config.theme.author.clone().unwrap_or_default(),
+            description: config.theme.description.clone(),
             interpolation: config.curves.clone(),
-            accent_opt: config.optimization.clone(),
-            extended_accent_opt: config.extended_optimization.clone(),
+            accent_opt: config.validation.semantic.apply(&config.optimization, 0),
+            extended_accent_opt: config
+                .validation
+                .semantic
+                .apply(&config.extended_optimization, 1),
+            extended_accents: config.extended_accents,
+            explore: config.explore.clone(),
 
             background,
             foreground,
 
             current_scheme: None,
+            wide_palette: None,
             generation_warnings: Vec::new(),
             validation_results: None,
+            counterpart: None,
 
             quit: false,
             show_help: false,
             message: None,
+            cvd_overlay: None,
+            cvd_threshold: DEFAULT_CVD_THRESHOLD,
+            display_simulation: None,
+            color_depth: ColorDepth::default(),
+            show_contrast_matrix: false,
+            split_view: variant == VariantArg::Linked,
+            show_log: false,
+            log_warnings_seen: log_buffer().warning_count(),
+            explore_candidates: Vec::new(),
+            explore_selected: 0,
+            preview_tab: PreviewTab::default(),
+            selected_swatch: 0,
+            inspect_swatch: false,
+            show_gamut_slice: false,
+            show_curve_plot: false,
+            plotted_curve: 0,
+            layout: PaneLayout::default(),
+            color_memory: ColorMemory::default(),
+            snippets_dir: config.preview.snippets_dir.clone(),
+            gallery_dir: config.preview.gallery_dir.clone(),
             export_path,
             output_format: format,
+            imported_source: None,
+            var_prefix: String::new(),
+
+            recorder: None,
+            replay: None,
+            worker: None,
+            generation_cache: GenerationCache::new(),
         })
     }
 
@@ -221,24 +512,271 @@ impl Model {
         GenerateConfig {
             background: self.background,
             foreground: self.foreground,
+            foreground_headroom: self.foreground_headroom,
             hue_overrides: self.hue_overrides,
+            adaptive_hues: self.adaptive_hues,
+            hue_spacing: self.perceptual_hues.then_some(self.hue_spacing),
             min_contrast: self.min_contrast,
             extended_min_contrast: self.extended_min_contrast,
+            contrast_model: default_model(),
             max_lightness_adjustment: self.max_lightness_adjustment,
+            comment_contrast: self.comment_contrast,
+            ramp: self.ramp,
+            viewing: self.viewing,
+            ansi: self.ansi.clone(),
             name: self.name.clone(),
             author: if self.author.is_empty() {
                 None
             } else {
                 Some(self.author.clone())
             },
+            description: self.description.clone(),
             interpolation: self.interpolation.clone(),
             accent_opt: self.accent_opt.clone(),
             extended_accent_opt: self.extended_accent_opt.clone(),
+            extended_accents: self.extended_accents,
+            gamut: self.gamut,
+            pins: self.pins.clone(),
+        }
+    }
+
+    /// Current parameters as a configuration file would hold them.
+    fn theme_config(&self) -> ThemeConfig {
+        let mut config = ThemeConfig::from_generate_config(&self.to_generate_config());
+        config.contrast.standard = self.contrast_standard;
+        config.validation = self.validation_thresholds.clone();
+        config.colors.harmony = self.harmony.map(|rule| HarmonyConfig {
+            rule,
+            seed_hue: Some(self.harmony_seed),
+        });
+        config.colors.hue_links = (!self.hue_links.is_empty()).then(|| self.hue_links.to_map());
+        config.explore = self.explore.clone();
+        config
+    }
+
+    /// Editing state to restore with `--resume`.
+    pub fn saved_session(&self) -> SavedSession {
+        let mut config = self.theme_config();
+        config.preview.snippets_dir = self.snippets_dir.clone();
+        config.preview.gallery_dir = self.gallery_dir.clone();
+        SavedSession {
+            variant: self.variant,
+            background: self.background_hellwig.into(),
+            foreground: self.foreground_hellwig.into(),
+            hue_spacing: self.hue_spacing,
+            export_path: self.export_path.clone(),
+            show_contrast_matrix: self.show_contrast_matrix,
+            split_view: self.split_view,
+            preview_tab: self.preview_tab,
+            config,
+        }
+    }
+
+    /// Whether each accent (base08-base0F) follows a linked hue, for the
+    /// hue grid.
+    pub fn linked_hues(&self) -> [bool; 8] {
+        self.hue_links.links.map(|link| link.is_some())
+    }
+
+    /// Hue of each pinned accent (base08-base0F), for the hue grid.
+    pub fn pinned_hues(&self) -> [Option<f32>; 8] {
+        std::array::from_fn(|i| {
+            self.pins
+                .get(&format!("base0{:X}", 8 + i))
+                .map(|c| HellwigJmh::from_srgb_u8(*c).hue)
+        })
+    }
+
+    /// Brand color and hue of each anchored base accent (base08-base0F).
+    pub fn accent_anchors(&self) -> [Option<(String, f32)>; 8] {
+        std::array::from_fn(|i| {
+            let anchor = self.accent_opt.anchor(i)?;
+            Some((anchor.color.clone(), anchor.jmh()?.hue))
+        })
+    }
+
+    /// Gamut slice at the selected swatch's hue, with the solver's target
+    /// box when the swatch is an accent.
+    pub fn gamut_slice(&self) -> Option<GamutSlice> {
+        let slot = Palette::slot_name(self.selected_swatch);
+        let (r, g, b) = self.current_scheme.as_ref()?.palette.get(slot)?.rgb;
+        let jmh = with_viewing_conditions(&self.viewing, || {
+            HellwigJmh::from_srgb_u8(Srgb::new(r, g, b))
+        });
+        let settings = match self.selected_swatch {
+            8..=15 => Some((&self.accent_opt, self.selected_swatch - 8)),
+            16..=23 => Some((&self.extended_accent_opt, self.selected_swatch - 16)),
+            _ => None,
+        };
+        // An anchored accent's box is centered on its brand color
+        let target = settings.map(|(settings, index)| {
+            let (j, m, delta_j, delta_m) = match settings
+                .anchor(index)
+                .and_then(|a| Some((a.jmh()?, a.tolerance)))
+            {
+                Some((anchor, tolerance)) => {
+                    (anchor.lightness, anchor.colorfulness, tolerance, tolerance)
+                }
+                None => (
+                    settings.target_j,
+                    settings.target_m,
+                    settings.delta_j,
+                    settings.delta_m,
+                ),
+            };
+            (
+                (j - delta_j).max(0.0),
+                (j + delta_j).min(100.0),
+                (m - delta_m).max(0.0),
+                m + delta_m,
+            )
+        });
+        Some(GamutSlice {
+            slot,
+            hue: jmh.hue,
+            point: (jmh.lightness, jmh.colorfulness),
+            target,
+            viewing: self.viewing,
+        })
+    }
+
+    /// The curve focused in the curve controls, for the curve plot.
+    pub fn curve_plot(&self) -> CurvePlot {
+        let (label, curve) = match self.plotted_curve {
+            0 => ("Lightness", &self.interpolation.lightness),
+            1 => ("Colorfulness", &self.interpolation.chroma),
+            _ => ("Hue", &self.interpolation.hue),
+        };
+        CurvePlot {
+            label,
+            curve: curve.clone(),
+        }
+    }
+
+    /// Which generation stage produced `slot`, for the swatch inspector.
+    pub fn slot_stage(&self, slot: &str) -> String {
+        if self.pins.contains_key(slot) {
+            return "pinned".to_string();
+        }
+        let index = usize::from_str_radix(slot.trim_start_matches("base"), 16).unwrap_or(0);
+        match index {
+            0 => "background".to_string(),
+            7 if self.foreground_headroom > 0.0 => "foreground headroom".to_string(),
+            7 => "foreground".to_string(),
+            3 if self.comment_contrast.is_some() => "comment contrast".to_string(),
+            1..=6 if self.ramp.targets()[index - 1].is_some() => "lightness ramp".to_string(),
+            1..=6 => "interpolation curve".to_string(),
+            8..=15 if self.accent_opt.anchors.contains_key(slot) => "brand anchor".to_string(),
+            8..=15 => "accent solver".to_string(),
+            _ if self.extended_accent_opt.anchors.contains_key(slot) => "brand anchor".to_string(),
+            _ => match self.extended_accents.mode {
+                ExtendedAccentMode::Independent => "extended accent solver".to_string(),
+                ExtendedAccentMode::Paired => "paired with base accents".to_string(),
+                mode => format!("{} base0{:X}", mode.name(), index - 8),
+            },
         }
     }
 
     /// Regenerate the palette from current state.
     pub fn regenerate(&mut self) {
+        let generation = self.generation_job().run(&mut self.generation_cache);
+        self.apply_generation(generation);
+    }
+
+    /// Regenerate on the worker thread when one is running, otherwise inline.
+    ///
+    /// The displayed palette stays until the result arrives through
+    /// `poll_generation`.
+    pub fn request_regenerate(&mut self) {
+        let job = self.generation_job();
+        let job = match self.worker {
+            Some(ref mut worker) => worker.request(job),
+            None => Some(job),
+        };
+        if let Some(job) = job {
+            let generation = job.run(&mut self.generation_cache);
+            self.apply_generation(generation);
+        }
+    }
+
+    /// Apply the latest finished background regeneration, if one arrived.
+    /// Returns whether the palette changed.
+    pub fn poll_generation(&mut self) -> bool {
+        let Some(generation) = self.worker.as_mut().and_then(RegenWorker::poll) else {
+            return false;
+        };
+        self.apply_generation(generation);
+        true
+    }
+
+    /// Wait for a pending background regeneration so the palette reflects
+    /// the current parameters.
+    pub fn finish_regeneration(&mut self) {
+        if let Some(generation) = self.worker.as_mut().and_then(RegenWorker::wait) {
+            self.apply_generation(generation);
+        }
+    }
+
+    /// Spinner frame while a background regeneration is pending.
+    pub fn regeneration_spinner(&mut self) -> Option<char> {
+        let worker = self.worker.as_mut()?;
+        worker.is_busy().then(|| worker.spinner())
+    }
+
+    /// Raise both contrast floors to the highest values every accent can
+    /// meet, then regenerate. Runs the search inline.
+    fn maximize_contrast(&mut self) {
+        let config = self.to_generate_config();
+        let ceiling = if self.split_view {
+            max_feasible_linked_contrast(&config)
+        } else {
+            let variants = match self.variant {
+                VariantArg::Auto => vec![None],
+                VariantArg::Dark => vec![Some(SchemeVariant::Dark)],
+                VariantArg::Light => vec![Some(SchemeVariant::Light)],
+                VariantArg::Both | VariantArg::Linked => {
+                    vec![Some(SchemeVariant::Dark), Some(SchemeVariant::Light)]
+                }
+            };
+            max_feasible_contrast(&config, &variants)
+        };
+        let Some(min_contrast) = ceiling.min_contrast else {
+            self.message = Some("No feasible contrast floor; widen the J'/M bounds".into());
+            return;
+        };
+        self.min_contrast = min_contrast;
+        if let Some(extended) = ceiling.extended_min_contrast {
+            self.extended_min_contrast = extended;
+        }
+        self.request_regenerate();
+        self.message = Some(format!(
+            "Contrast floors: Lc {:.1} / extended Lc {:.1}",
+            self.min_contrast, self.extended_min_contrast
+        ));
+    }
+
+    /// Sample parameter sets around the current ones and list the best in
+    /// the shuffle picker. Runs the search inline.
+    fn shuffle(&mut self) {
+        let seed = random_seed();
+        let mut candidates = explore(
+            &self.to_generate_config(),
+            &self.validation_thresholds,
+            &self.explore,
+            self.explore.count,
+            seed,
+        );
+        candidates.truncate(EXPLORE_SHOWN);
+        self.message = Some(format!(
+            "Shuffled {} candidates (seed {seed})",
+            self.explore.count
+        ));
+        self.explore_candidates = candidates;
+        self.explore_selected = 0;
+    }
+
+    /// Update the derived colors and describe the generation to run.
+    fn generation_job(&mut self) -> GenerationJob {
         // Recompute sRGB from HellwigJmh
         self.background = self.background_hellwig.to_srgb();
         self.foreground = self.foreground_hellwig.to_srgb();
@@ -246,55 +784,178 @@ impl Model {
         // Check gamut
         self.background_hellwig.check_gamut();
         self.foreground_hellwig.check_gamut();
+        self.message = None;
 
-        let config = self.to_generate_config();
-        let forced = match self.variant {
-            VariantArg::Auto => None,
-            VariantArg::Dark => Some(SchemeVariant::Dark),
-            VariantArg::Light => Some(SchemeVariant::Light),
-            VariantArg::Both => None,
+        GenerationJob {
+            id: 0,
+            config: self.to_generate_config(),
+            variant: match self.variant {
+                VariantArg::Auto | VariantArg::Both | VariantArg::Linked => None,
+                VariantArg::Dark => Some(SchemeVariant::Dark),
+                VariantArg::Light => Some(SchemeVariant::Light),
+            },
+            standard: self.contrast_standard,
+            thresholds: self.validation_thresholds.clone(),
+            linked: self.split_view,
+        }
+    }
+
+    fn apply_generation(&mut self, generation: Generation) {
+        self.validation_results = Some(generation.validation);
+        self.generation_warnings = generation.warnings;
+        self.current_scheme = Some(generation.scheme);
+        self.wide_palette = Some(generation.wide_palette);
+        self.counterpart = generation.counterpart;
+    }
+
+    /// Start editing from the scheme file at `path`, as with `--input`.
+    ///
+    /// Every parameter is replaced by those recovered from the scheme, which
+    /// is shown as imported until the next edit. The contrast standard,
+    /// layout, overlays, and preview settings are kept.
+    pub fn load_scheme(&mut self, path: &Path) -> Result<()> {
+        // A palette still regenerating would replace the loaded one
+        self.finish_regeneration();
+
+        let import = import_scheme(path)?;
+        let variant = match import.config.theme.variant.as_deref() {
+            Some("light") => VariantArg::Light,
+            Some("dark") => VariantArg::Dark,
+            _ => VariantArg::Auto,
         };
+        let mut loaded = Self::from_theme_config(&import.config, variant, self.output_format)?;
+
+        loaded.var_prefix = self.var_prefix.clone();
+        loaded.contrast_standard = self.contrast_standard;
+        loaded.validation_thresholds = self.validation_thresholds.clone();
+        loaded.explore = self.explore.clone();
+        loaded.cvd_overlay = self.cvd_overlay;
+        loaded.cvd_threshold = self.cvd_threshold;
+        loaded.display_simulation = self.display_simulation;
+        loaded.color_depth = self.color_depth;
+        loaded.show_contrast_matrix = self.show_contrast_matrix;
+        loaded.split_view = self.split_view;
+        loaded.preview_tab = self.preview_tab;
+        loaded.layout = self.layout;
+        loaded.color_memory = std::mem::take(&mut self.color_memory);
+        loaded.snippets_dir = self.snippets_dir.take();
+        loaded.gallery_dir = self.gallery_dir.take();
+        loaded.export_path = std::mem::take(&mut self.export_path);
+        loaded.recorder = self.recorder.take();
+        loaded.replay = self.replay.take();
+        loaded.worker = self.worker.take();
+        loaded.generation_cache = std::mem::take(&mut self.generation_cache);
 
-        let result = generate_for_variant(&config, forced);
-        self.validation_results = Some(validate_with_accent_data(
-            &result.scheme,
-            &result.base_accent_results,
-            &result.extended_accent_results,
+        loaded.validation_results = Some(validate_with_config(
+            &import.scheme,
+            &[],
+            &[],
+            loaded.contrast_standard,
+            &loaded.validation_thresholds,
         ));
-        self.generation_warnings = result.warnings;
-        self.current_scheme = Some(result.scheme);
-        self.message = None;
+        loaded.current_scheme = Some(import.scheme);
+        loaded.imported_source = Some(import.source);
+        loaded.message = Some(format!("Loaded {}", path.display()));
+        *self = loaded;
+        Ok(())
+    }
+
+    /// Add the current background and foreground to the recent colors.
+    pub fn remember_colors(&mut self) {
+        self.color_memory.remember(&srgb_to_hex(self.foreground));
+        self.color_memory.remember(&srgb_to_hex(self.background));
     }
 
     /// Export the current scheme to a file.
     ///
     /// Uses the output format specified at model creation.
     pub fn export(&mut self) -> Result<()> {
+        self.finish_regeneration();
         if let Some(ref scheme) = self.current_scheme {
-            let output = match self.output_format {
-                OutputFormat::Yaml => {
-                    serde_yaml::to_string(scheme).wrap_err("Failed to serialize scheme to YAML")?
-                }
-                OutputFormat::Json => serde_json::to_string_pretty(scheme)
-                    .wrap_err("Failed to serialize scheme to JSON")?,
+            let patched = self
+                .imported_source
+                .as_deref()
+                .and_then(|source| self.output_format.patch_source(scheme, source));
+            let output = match patched {
+                Some(output) => output,
+                None => self.output_format.serialize_prefixed(
+                    scheme,
+                    self.wide_palette.as_ref(),
+                    None,
+                    None,
+                    None,
+                    &self.var_prefix,
+                )?,
             };
 
             let path = PathBuf::from(&self.export_path);
             std::fs::write(&path, &output)
                 .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+            let mut message = format!("Exported to {}", path.display());
+
+            // The split view's linked variant goes next to it
+            if let Some(ref counterpart) = self.counterpart {
+                let output = self.output_format.serialize_prefixed(
+                    &counterpart.scheme,
+                    Some(&counterpart.wide_palette),
+                    None,
+                    None,
+                    None,
+                    &self.var_prefix,
+                )?;
+                let path = counterpart_path(&path, &counterpart.scheme.variant);
+                std::fs::write(&path, &output)
+                    .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+                message.push_str(&format!(" and {}", path.display()));
+            }
 
-            self.message = Some(format!("Exported to {}", path.display()));
+            self.message = Some(message);
+            self.remember_colors();
         } else {
             self.message = Some("No scheme to export".to_string());
         }
         Ok(())
     }
+
+    /// Save the palette and preview as an SVG next to the export path.
+    pub fn screenshot(&mut self) -> Result<()> {
+        self.finish_regeneration();
+        let path = Path::new(&self.export_path).with_extension("svg");
+        save_screenshot(self, &path)?;
+        self.message = Some(format!("Saved screenshot to {}", path.display()));
+        Ok(())
+    }
+}
+
+/// The `[keys]` table of the configuration `cli` points at.
+fn user_keys(cli: &Cli) -> Result<KeysConfig> {
+    let config = load_config_with_profile(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        &cli.to_config_overrides(),
+    )
+    .map_err(|e| color_eyre::eyre::eyre!("Configuration error: {}", e))?;
+    Ok(config.keys)
+}
+
+/// `path` with the variant appended to its file stem, e.g. `scheme-light.yaml`.
+fn counterpart_path(path: &Path, variant: &SchemeVariant) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut name = format!("{stem}-{variant}");
+    if let Some(extension) = path.extension() {
+        name = format!("{name}.{}", extension.to_string_lossy());
+    }
+    path.with_file_name(name)
 }
 
 impl Update<Msg> for Model {
     fn update(&mut self, msg: Option<Msg>) -> Option<Msg> {
         let msg = msg?;
 
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.record(&msg);
+        }
+
         match msg {
             Msg::Quit => {
                 self.quit = true;
@@ -314,6 +975,12 @@ impl Update<Msg> for Model {
                 self.background_hellwig.hue = v;
                 Some(Msg::Regenerate)
             }
+            Msg::BackgroundRecalled(hex) => {
+                if let Ok(color) = parse_color(&format!("#{hex}")) {
+                    self.background_hellwig = HellwigComponents::from_srgb(color);
+                }
+                Some(Msg::Regenerate)
+            }
 
             // Foreground changes (HellwigJmh J', M, h)
             Msg::ForegroundJChanged(v) => {
@@ -328,6 +995,12 @@ impl Update<Msg> for Model {
                 self.foreground_hellwig.hue = v;
                 Some(Msg::Regenerate)
             }
+            Msg::ForegroundRecalled(hex) => {
+                if let Ok(color) = parse_color(&format!("#{hex}")) {
+                    self.foreground_hellwig = HellwigComponents::from_srgb(color);
+                }
+                Some(Msg::Regenerate)
+            }
 
             // Numeric parameters
             Msg::MinContrastChanged(v) => {
@@ -368,15 +1041,129 @@ impl Update<Msg> for Model {
                 self.interpolation.hue.strength = v;
                 Some(Msg::Regenerate)
             }
+            Msg::LightnessCurvePointsChanged(points) => {
+                self.interpolation.lightness.control_points = Some(points.to_vec());
+                Some(Msg::Regenerate)
+            }
+            Msg::ChromaCurvePointsChanged(points) => {
+                self.interpolation.chroma.control_points = Some(points.to_vec());
+                Some(Msg::Regenerate)
+            }
+            Msg::HueCurvePointsChanged(points) => {
+                self.interpolation.hue.control_points = Some(points.to_vec());
+                Some(Msg::Regenerate)
+            }
+            Msg::LightnessCurveStopsChanged(stops) => {
+                self.interpolation.lightness.stops = Some(stops.to_vec());
+                Some(Msg::Regenerate)
+            }
+            Msg::ChromaCurveStopsChanged(stops) => {
+                self.interpolation.chroma.stops = Some(stops.to_vec());
+                Some(Msg::Regenerate)
+            }
+            Msg::HueCurveStopsChanged(stops) => {
+                self.interpolation.hue.stops = Some(stops.to_vec());
+                Some(Msg::Regenerate)
+            }
+            Msg::CurveFocused(curve) => {
+                self.plotted_curve = curve;
+                None
+            }
 
             // Hue overrides
             Msg::HueOverrideChanged(idx, val) => {
                 if (idx as usize) < 8 {
                     self.hue_overrides[idx as usize] = val;
+                    self.hue_links
+                        .propagate(&mut self.hue_overrides, idx as usize);
+                }
+                Some(Msg::Regenerate)
+            }
+            Msg::HueOverridesRotated(delta) => {
+                for (hue, default) in self.hue_overrides.iter_mut().zip(DEFAULT_BASE16_HUES) {
+                    *hue = Some((hue.unwrap_or(default) + delta).rem_euclid(360.0));
+                }
+                self.harmony_seed = (self.harmony_seed + delta).rem_euclid(360.0);
+                Some(Msg::Regenerate)
+            }
+            // Harmony hues replace any overrides; cycling past the last
+            // rule returns to the default hues
+            Msg::CycleHarmony => {
+                self.harmony = Harmony::cycle(self.harmony);
+                let hues = match self.harmony {
+                    Some(rule) => rule.hues(self.harmony_seed).map(Some),
+                    None => [None; 8],
+                };
+                self.hue_overrides = self.hue_links.resolve(hues);
+                self.request_regenerate();
+                self.message = Some(match self.harmony {
+                    Some(rule) => format!("Hue harmony: {rule} from {:.0}°", self.harmony_seed),
+                    None => "Hue harmony off: default hues".to_string(),
+                });
+                None
+            }
+            Msg::TogglePerceptualHues => {
+                self.perceptual_hues = !self.perceptual_hues;
+                Some(Msg::Regenerate)
+            }
+            Msg::MaximizeContrast => {
+                self.maximize_contrast();
+                None
+            }
+            Msg::Shuffle => {
+                self.shuffle();
+                None
+            }
+            // A picked candidate replaces the hues, so any harmony is dropped
+            Msg::ExplorePicked(params) => {
+                self.hue_overrides = self.hue_links.resolve(params.hues.map(Some));
+                self.harmony = None;
+                self.accent_opt.target_m = params.target_m;
+                self.interpolation.lightness.curve_type = params.lightness_curve;
+                self.interpolation.chroma.curve_type = params.chroma_curve;
+                Some(Msg::Regenerate)
+            }
+            Msg::TogglePin(slot) => {
+                if self.pins.remove(&slot).is_none() {
+                    let color = self
+                        .current_scheme
+                        .as_ref()
+                        .and_then(|s| s.palette.get(&slot))
+                        .map(|c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))?;
+                    self.pins.insert(slot, color);
+                }
+                Some(Msg::Regenerate)
+            }
+            Msg::AnchorChanged(slot, color) => {
+                match color {
+                    Some(color) => {
+                        if parse_color(&color).is_err() {
+                            warn!("invalid anchor color {color}");
+                            return None;
+                        }
+                        self.accent_opt
+                            .anchors
+                            .entry(slot)
+                            .and_modify(|anchor| anchor.color = color.clone())
+                            .or_insert_with(|| AccentAnchor::new(color));
+                    }
+                    None => {
+                        self.accent_opt.anchors.remove(&slot)?;
+                    }
                 }
                 Some(Msg::Regenerate)
             }
 
+            // Lightness ramp
+            Msg::RampTargetChanged(idx, val) => {
+                let mut targets = self.ramp.targets();
+                if let Some(target) = targets.get_mut(idx as usize) {
+                    *target = val;
+                }
+                self.ramp = LightnessRamp::from_targets(targets);
+                Some(Msg::Regenerate)
+            }
+
             // Accent optimization settings
             Msg::AccentTargetJChanged(v) => {
                 self.accent_opt.target_j = v;
@@ -410,6 +1197,10 @@ impl Update<Msg> for Model {
                 self.extended_accent_opt.delta_m = v;
                 Some(Msg::Regenerate)
             }
+            Msg::ExtendedAccentsChanged(v) => {
+                self.extended_accents = v;
+                Some(Msg::Regenerate)
+            }
 
             // Optimization weight changes (shared between base and extended)
             Msg::ContrastWeightChanged(v) => {
@@ -422,6 +1213,10 @@ impl Update<Msg> for Model {
                 self.extended_accent_opt.j_weight = v;
                 Some(Msg::Regenerate)
             }
+            Msg::PreferredContrastChanged(v) => {
+                self.accent_opt.preferred_contrast = (v > 0.0).then_some(f64::from(v));
+                Some(Msg::Regenerate)
+            }
 
             // Metadata
             Msg::NameChanged(v) => {
@@ -433,9 +1228,16 @@ impl Update<Msg> for Model {
                 None
             }
 
+            Msg::LoadScheme(path) => {
+                if let Err(e) = self.load_scheme(&path) {
+                    self.message = Some(format!("Load failed: {e}"));
+                }
+                None
+            }
+
             // Regenerate
             Msg::Regenerate => {
-                self.regenerate();
+                self.request_regenerate();
                 None
             }
 
@@ -450,6 +1252,12 @@ impl Update<Msg> for Model {
                 }
                 None
             }
+            Msg::Screenshot => {
+                if let Err(e) = self.screenshot() {
+                    self.message = Some(format!("Screenshot failed: {}", e));
+                }
+                None
+            }
             Msg::ExportSuccess(path) => {
                 self.message = Some(format!("Exported to {}", path));
                 None
@@ -489,12 +1297,98 @@ impl Update<Msg> for Model {
                 Some(Msg::Regenerate)
             }
 
+            // Colorblindness overlay: off -> protanopia -> deuteranopia -> tritanopia -> off
+            Msg::ToggleFavoriteColor(hex) => {
+                self.message = Some(if self.color_memory.toggle_favorite(&hex) {
+                    format!("Starred #{hex}")
+                } else {
+                    format!("Unstarred #{hex}")
+                });
+                None
+            }
+
+            Msg::CycleCvdSimulation => {
+                self.cvd_overlay = match self.cvd_overlay {
+                    None => Some(CvdKind::Protanopia),
+                    Some(CvdKind::Protanopia) => Some(CvdKind::Deuteranopia),
+                    Some(CvdKind::Deuteranopia) => Some(CvdKind::Tritanopia),
+                    Some(CvdKind::Tritanopia) => None,
+                };
+                None
+            }
+
+            // Display simulation: off -> dim -> bright -> gamma 2.4 -> off
+            Msg::CycleDisplaySimulation => {
+                self.display_simulation = DisplaySimulation::cycle(self.display_simulation);
+                None
+            }
+
+            Msg::ToggleContrastMatrix => {
+                self.show_contrast_matrix = !self.show_contrast_matrix;
+                None
+            }
+
+            Msg::ToggleLog => {
+                self.show_log = !self.show_log;
+                None
+            }
+
+            // The gamut slice and curve plot share the preview pane
+            Msg::ToggleGamutSlice => {
+                self.show_gamut_slice = !self.show_gamut_slice;
+                self.show_curve_plot = false;
+                None
+            }
+
+            Msg::ToggleCurvePlot => {
+                self.show_curve_plot = !self.show_curve_plot;
+                self.show_gamut_slice = false;
+                None
+            }
+
+            // Split view: generate the opposite variant alongside
+            Msg::ToggleSplitView => {
+                self.split_view = !self.split_view;
+                if self.split_view {
+                    Some(Msg::Regenerate)
+                } else {
+                    self.counterpart = None;
+                    None
+                }
+            }
+
+            Msg::ResizeColumns(delta) => {
+                self.layout.resize_columns(delta);
+                None
+            }
+            Msg::ResizePreview(delta) => {
+                self.layout.resize_preview(delta);
+                None
+            }
+            Msg::NextPreviewTab => {
+                self.preview_tab = self.preview_tab.next();
+                None
+            }
+
+            Msg::SelectSwatch(index) => {
+                self.selected_swatch = index;
+                None
+            }
+            Msg::ToggleSwatchInspector => {
+                self.inspect_swatch = !self.inspect_swatch;
+                None
+            }
+
+            // Stepwise replay: chain the next recorded message
+            Msg::ReplayStep => self.replay.as_mut().and_then(|r| r.step()),
+
             // These messages don't need model updates
             Msg::FocusNext
             | Msg::FocusPrev
             | Msg::ValidationScrollUp
             | Msg::ValidationScrollDown
-            | Msg::SwitchToCodePreview => None,
+            | Msg::SwitchToCodePreview
+            | Msg::SwitchToGallery => None,
         }
     }
 }
@@ -0,0 +1,95 @@
+//! Editing state saved on exit and restored with `--resume`.
+//!
+//! Unlike an exported scheme, the saved session keeps the parameters the
+//! palette was generated from (curves, weights, accent targets, pins, ...),
+//! so editing picks up where it stopped. Stored next to the pane layout in
+//! `$XDG_STATE_HOME/themalingadingdong/session.toml`.
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::VariantArg;
+use crate::config::ThemeConfig;
+use crate::hue_spacing::HueSpacing;
+
+use super::components::preview::PreviewTab;
+use super::layout::state_dir;
+use super::model::HellwigComponents;
+
+/// Everything needed to rebuild the editing model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSession {
+    /// Variant selection
+    pub variant: VariantArg,
+    /// Background as edited, J'/M/h (the config holds its hex rounding)
+    pub background: [f32; 3],
+    /// Foreground as edited, J'/M/h
+    pub foreground: [f32; 3],
+    /// Export destination
+    pub export_path: String,
+    /// Whether the contrast matrix was showing
+    #[serde(default)]
+    pub show_contrast_matrix: bool,
+    /// Whether the dark/light split view was showing
+    #[serde(default)]
+    pub split_view: bool,
+    /// Mockup shown in the preview pane
+    #[serde(default)]
+    pub preview_tab: PreviewTab,
+    /// Hue spacing settings, kept while perceptual spacing is off
+    #[serde(default)]
+    pub hue_spacing: HueSpacing,
+    /// Generation, validation, and preview settings
+    pub config: ThemeConfig,
+}
+
+impl SavedSession {
+    /// Default location of the saved session, if a home directory is known.
+    pub fn state_path() -> Option<PathBuf> {
+        Some(state_dir()?.join("session.toml"))
+    }
+
+    /// Load a saved session; unlike other state files, a missing one is an error.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(eyre!(
+                "No saved session at {} (quit the TUI once to create one)",
+                path.display()
+            ));
+        }
+        let content = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).wrap_err_with(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save the session, creating parent directories.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+impl From<HellwigComponents> for [f32; 3] {
+    fn from(c: HellwigComponents) -> Self {
+        [c.lightness, c.colorfulness, c.hue]
+    }
+}
+
+impl From<[f32; 3]> for HellwigComponents {
+    fn from([lightness, colorfulness, hue]: [f32; 3]) -> Self {
+        let mut components = Self {
+            lightness,
+            colorfulness,
+            hue,
+            out_of_gamut: false,
+        };
+        components.check_gamut();
+        components
+    }
+}
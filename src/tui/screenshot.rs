@@ -0,0 +1,312 @@
+//! Screenshots of the palette and preview panels (`--screenshot`, `z`).
+//!
+//! The panels are drawn off-screen at a fixed size and written as SVG: each
+//! cell's background becomes a rectangle and its text is set in a monospace
+//! font, so the image looks the same wherever it is shown. Block glyphs
+//! (`█▀▄`) are drawn as rectangles so swatches line up exactly. Terminal
+//! default and ANSI colors take the scheme's own slots, as they would in a
+//! terminal running the theme.
+//!
+//! PNG screenshots are converted from the SVG by the first of
+//! `rsvg-convert`, `resvg`, or ImageMagick found on `PATH`.
+
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+
+use color_eyre::eyre::{Result, WrapErr, bail, eyre};
+use palette::Srgb;
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    buffer::{Buffer, Cell},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier},
+};
+use tinted_builder::Base16Scheme;
+use tuirealm::MockComponent;
+
+use super::Model;
+use super::activities::main::MainActivity;
+use super::components::Preview;
+use crate::ansi::ansi_slots;
+use crate::xterm256::xterm_color;
+
+/// Screenshot size in cells.
+const WIDTH: u16 = 80;
+const PALETTE_HEIGHT: u16 = 10;
+const PREVIEW_HEIGHT: u16 = 18;
+
+/// Cell size in SVG pixels, and the font size text is set in.
+const CELL_WIDTH: u32 = 9;
+const CELL_HEIGHT: u32 = 18;
+const FONT_SIZE: u32 = 15;
+
+const FONT_FAMILY: &str = "'JetBrains Mono', 'DejaVu Sans Mono', Menlo, Consolas, monospace";
+
+/// Opacity of text drawn with the dim modifier.
+const DIM_OPACITY: f32 = 0.6;
+
+/// Draw the palette above the preview, as the main screen shows them.
+pub(crate) fn render_panels(model: &Model) -> Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, PALETTE_HEIGHT + PREVIEW_HEIGHT))?;
+    let mut palette = MainActivity::palette(model);
+    let mut preview = Preview::new();
+    preview.set_scheme(model.current_scheme.clone());
+    preview.set_tab(model.preview_tab);
+    preview.set_simulation(model.display_simulation);
+    terminal.draw(|frame| {
+        let [top, bottom] = Layout::vertical([
+            Constraint::Length(PALETTE_HEIGHT),
+            Constraint::Length(PREVIEW_HEIGHT),
+        ])
+        .areas(frame.area());
+        palette.view(frame, top);
+        preview.view(frame, bottom);
+    })?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Write the model's palette and preview to `path`, as SVG or (by
+/// extension) PNG.
+pub(crate) fn save_screenshot(model: &Model, path: &Path) -> Result<()> {
+    let Some(ref scheme) = model.current_scheme else {
+        bail!("no palette generated");
+    };
+    let svg = buffer_to_svg(&render_panels(model)?, scheme);
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("svg") => std::fs::write(path, svg)
+            .wrap_err_with(|| format!("Failed to write {}", path.display())),
+        Some("png") => svg_to_png(&svg, path),
+        _ => bail!(
+            "screenshots are written as .svg or .png (got {})",
+            path.display()
+        ),
+    }
+}
+
+/// Convert `svg` to a PNG at `path` with the first converter on `PATH`.
+fn svg_to_png(svg: &str, path: &Path) -> Result<()> {
+    let source = std::env::temp_dir().join(format!(
+        "themalingadingdong-screenshot-{}.svg",
+        std::process::id()
+    ));
+    std::fs::write(&source, svg)
+        .wrap_err_with(|| format!("Failed to write {}", source.display()))?;
+    let (svg_arg, png_arg) = (source.as_os_str(), path.as_os_str());
+    let converters: [(&str, Vec<&OsStr>); 3] = [
+        ("rsvg-convert", vec![OsStr::new("-o"), png_arg, svg_arg]),
+        ("resvg", vec![svg_arg, png_arg]),
+        ("magick", vec![svg_arg, png_arg]),
+    ];
+    // The first converter that runs decides; missing ones are skipped
+    let result = converters.iter().find_map(|(program, args)| {
+        let output = Command::new(program).args(args).output().ok()?;
+        Some(if output.status.success() {
+            Ok(())
+        } else {
+            Err(eyre!(
+                "{program} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        })
+    });
+    let _ = std::fs::remove_file(&source);
+    result.unwrap_or_else(|| {
+        bail!(
+            "PNG screenshots need rsvg-convert, resvg, or ImageMagick on PATH; \
+             save as .svg instead"
+        )
+    })
+}
+
+/// The buffer as an SVG image, resolving terminal colors against `scheme`.
+pub(crate) fn buffer_to_svg(buffer: &Buffer, scheme: &Base16Scheme) -> String {
+    let colors = TerminalColors::new(scheme);
+    let area = buffer.area;
+    let (width, height) = (
+        u32::from(area.width) * CELL_WIDTH,
+        u32::from(area.height) * CELL_HEIGHT,
+    );
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(svg, "<title>{}</title>", escape(&scheme.name));
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(colors.background)
+    );
+
+    // Backgrounds, merged into runs of one color per row
+    for y in 0..area.height {
+        let mut x = 0;
+        while x < area.width {
+            let bg = colors.cell_bg(&buffer[(x, y)]);
+            let start = x;
+            while x < area.width && colors.cell_bg(&buffer[(x, y)]) == bg {
+                x += 1;
+            }
+            if bg != colors.background {
+                rect(&mut svg, start, y, x - start, 1, bg);
+            }
+        }
+    }
+
+    // Block glyphs as rectangles, everything else as text
+    let _ = writeln!(
+        svg,
+        r#"<g font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" xml:space="preserve">"#
+    );
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let cell = &buffer[(x, y)];
+            let symbol = cell.symbol();
+            if symbol.trim().is_empty() {
+                continue;
+            }
+            let fg = colors.cell_fg(cell);
+            match symbol {
+                "█" => rect(&mut svg, x, y, 1, 1, fg),
+                "▀" => half_rect(&mut svg, x, y, false, fg),
+                "▄" => half_rect(&mut svg, x, y, true, fg),
+                _ => text(&mut svg, x, y, symbol, cell.modifier, fg),
+            }
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// Colors a terminal running the scheme would show.
+struct TerminalColors {
+    background: Srgb<u8>,
+    foreground: Srgb<u8>,
+    ansi: [Srgb<u8>; 16],
+}
+
+impl TerminalColors {
+    fn new(scheme: &Base16Scheme) -> Self {
+        let slot = |name: &str| {
+            scheme
+                .palette
+                .get(name)
+                .map_or(Srgb::new(0, 0, 0), |c| Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+        };
+        Self {
+            background: slot("base00"),
+            foreground: slot("base05"),
+            ansi: ansi_slots(scheme).map(slot),
+        }
+    }
+
+    fn resolve(&self, color: Color, default: Srgb<u8>) -> Srgb<u8> {
+        let index = match color {
+            Color::Reset => return default,
+            Color::Rgb(r, g, b) => return Srgb::new(r, g, b),
+            Color::Indexed(i) if i >= 16 => return xterm_color(i),
+            Color::Indexed(i) => i,
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::Gray => 7,
+            Color::DarkGray => 8,
+            Color::LightRed => 9,
+            Color::LightGreen => 10,
+            Color::LightYellow => 11,
+            Color::LightBlue => 12,
+            Color::LightMagenta => 13,
+            Color::LightCyan => 14,
+            Color::White => 15,
+        };
+        self.ansi[usize::from(index)]
+    }
+
+    fn cell_fg(&self, cell: &Cell) -> Srgb<u8> {
+        if cell.modifier.contains(Modifier::REVERSED) {
+            self.resolve(cell.bg, self.background)
+        } else {
+            self.resolve(cell.fg, self.foreground)
+        }
+    }
+
+    fn cell_bg(&self, cell: &Cell) -> Srgb<u8> {
+        if cell.modifier.contains(Modifier::REVERSED) {
+            self.resolve(cell.fg, self.foreground)
+        } else {
+            self.resolve(cell.bg, self.background)
+        }
+    }
+}
+
+fn hex(color: Srgb<u8>) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue)
+}
+
+fn rect(svg: &mut String, x: u16, y: u16, width: u16, height: u16, fill: Srgb<u8>) {
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+        u32::from(x) * CELL_WIDTH,
+        u32::from(y) * CELL_HEIGHT,
+        u32::from(width) * CELL_WIDTH,
+        u32::from(height) * CELL_HEIGHT,
+        hex(fill)
+    );
+}
+
+/// The upper or lower half of a cell.
+fn half_rect(svg: &mut String, x: u16, y: u16, lower: bool, fill: Srgb<u8>) {
+    let top = u32::from(y) * CELL_HEIGHT + if lower { CELL_HEIGHT / 2 } else { 0 };
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{top}" width="{CELL_WIDTH}" height="{}" fill="{}"/>"#,
+        u32::from(x) * CELL_WIDTH,
+        CELL_HEIGHT / 2,
+        hex(fill)
+    );
+}
+
+fn text(svg: &mut String, x: u16, y: u16, symbol: &str, modifier: Modifier, fill: Srgb<u8>) {
+    let mut attributes = String::new();
+    if modifier.contains(Modifier::BOLD) {
+        attributes.push_str(r#" font-weight="bold""#);
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        attributes.push_str(r#" font-style="italic""#);
+    }
+    if modifier.contains(Modifier::UNDERLINED) {
+        attributes.push_str(r#" text-decoration="underline""#);
+    }
+    if modifier.contains(Modifier::DIM) {
+        let _ = write!(attributes, r#" fill-opacity="{DIM_OPACITY}""#);
+    }
+    // Baseline about three quarters down the cell
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" fill="{}"{attributes}>{}</text>"#,
+        u32::from(x) * CELL_WIDTH,
+        u32::from(y) * CELL_HEIGHT + CELL_HEIGHT * 3 / 4,
+        hex(fill),
+        escape(symbol)
+    );
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
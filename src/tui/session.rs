@@ -0,0 +1,194 @@
+//! Session recording and replay of TUI parameter changes.
+//!
+//! A session log is JSON Lines: a `start` entry holding the initial
+//! configuration, followed by one `msg` entry per parameter change with the
+//! milliseconds elapsed since recording began.
+//!
+//! ```text
+//! {"type":"start","config":{...},"variant":"auto","layout":{"left_column":50,"preview":30}}
+//! {"type":"msg","elapsed_ms":1520,"msg":{"BackgroundJChanged":12.0}}
+//! ```
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use color_eyre::eyre::{Result, WrapErr, bail, eyre};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::cli::VariantArg;
+use crate::config::ThemeConfig;
+
+use super::activities::Msg;
+use super::layout::PaneLayout;
+
+/// One line of a session log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SessionEntry {
+    /// Initial state the session started from
+    Start {
+        config: Box<ThemeConfig>,
+        variant: VariantArg,
+        #[serde(default)]
+        layout: PaneLayout,
+    },
+    /// A parameter change applied during the session
+    Msg { elapsed_ms: u64, msg: Msg },
+}
+
+/// Appends parameter-change messages to a session log as they happen.
+pub struct SessionRecorder {
+    writer: BufWriter<File>,
+    started: Instant,
+}
+
+impl SessionRecorder {
+    /// Create a session log, writing the `start` entry immediately.
+    pub fn create(
+        path: &Path,
+        config: ThemeConfig,
+        variant: VariantArg,
+        layout: PaneLayout,
+    ) -> Result<Self> {
+        let file = File::create(path)
+            .wrap_err_with(|| format!("Failed to create session log {}", path.display()))?;
+        let mut recorder = Self {
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+        };
+        recorder.write(&SessionEntry::Start {
+            config: Box::new(config),
+            variant,
+            layout,
+        })?;
+        Ok(recorder)
+    }
+
+    /// Record a message if it changes a parameter. Write failures are logged, not fatal.
+    pub fn record(&mut self, msg: &Msg) {
+        if !msg.is_parameter_change() {
+            return;
+        }
+        let entry = SessionEntry::Msg {
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            msg: msg.clone(),
+        };
+        if let Err(e) = self.write(&entry) {
+            warn!(error = %e, "failed to write session log entry");
+        }
+    }
+
+    fn write(&mut self, entry: &SessionEntry) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, entry)?;
+        self.writer.write_all(b"\n")?;
+        // Flush per entry so the log survives a crash mid-session
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// How a replay advances through the recorded messages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplayMode {
+    /// Follow recorded timing, scaled by a speed factor (2.0 = twice as fast)
+    Timed(f64),
+    /// Apply one message per keypress
+    Step,
+}
+
+/// Recorded session being replayed into the model.
+pub struct SessionReplay {
+    pending: VecDeque<(u64, Msg)>,
+    total: usize,
+    layout: PaneLayout,
+    mode: ReplayMode,
+    started: Instant,
+}
+
+impl SessionReplay {
+    /// Load a session log, returning its start state and the replay queue.
+    pub fn load(path: &Path, mode: ReplayMode) -> Result<(ThemeConfig, VariantArg, Self)> {
+        let file = File::open(path)
+            .wrap_err_with(|| format!("Failed to open session log {}", path.display()))?;
+
+        let mut start = None;
+        let mut pending = VecDeque::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: SessionEntry = serde_json::from_str(&line)
+                .map_err(|e| eyre!("{}:{}: {}", path.display(), i + 1, e))?;
+            match entry {
+                SessionEntry::Start {
+                    config,
+                    variant,
+                    layout,
+                } if start.is_none() => {
+                    start = Some((*config, variant, layout));
+                }
+                SessionEntry::Start { .. } => {
+                    bail!("{}:{}: duplicate start entry", path.display(), i + 1)
+                }
+                SessionEntry::Msg { elapsed_ms, msg } => pending.push_back((elapsed_ms, msg)),
+            }
+        }
+
+        let (config, variant, layout) =
+            start.ok_or_else(|| eyre!("Session log {} has no start entry", path.display()))?;
+        let replay = Self {
+            total: pending.len(),
+            layout,
+            pending,
+            mode,
+            started: Instant::now(),
+        };
+        Ok((config, variant, replay))
+    }
+
+    /// Messages whose recorded time has passed (timed mode only).
+    pub fn due(&mut self) -> Vec<Msg> {
+        let ReplayMode::Timed(speed) = self.mode else {
+            return Vec::new();
+        };
+        let now_ms = self.started.elapsed().as_secs_f64() * 1000.0 * speed;
+        let mut msgs = Vec::new();
+        while let Some((elapsed_ms, _)) = self.pending.front() {
+            if *elapsed_ms as f64 > now_ms {
+                break;
+            }
+            msgs.extend(self.pending.pop_front().map(|(_, msg)| msg));
+        }
+        msgs
+    }
+
+    /// Next message regardless of timing (stepwise mode).
+    pub fn step(&mut self) -> Option<Msg> {
+        self.pending.pop_front().map(|(_, msg)| msg)
+    }
+
+    /// Pane layout the session was recorded with.
+    pub fn layout(&self) -> PaneLayout {
+        self.layout
+    }
+
+    /// Replay mode.
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    /// Number of messages applied so far.
+    pub fn applied(&self) -> usize {
+        self.total - self.pending.len()
+    }
+
+    /// Total number of recorded messages.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
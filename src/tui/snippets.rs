@@ -1,35 +1,105 @@
 //! Code snippets for syntax highlighting preview.
+//!
+//! The built-in samples are compiled in; a user snippets directory
+//! (`[preview] snippets_dir`) is read from disk each time the code preview
+//! opens or is refreshed, so edits show up without restarting.
+
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+use tracing::warn;
+
+use crate::tui::highlighting::SYNTAX_SET;
+
+/// Largest user snippet that will be loaded (bytes).
+const MAX_SNIPPET_BYTES: u64 = 256 * 1024;
 
 pub struct CodeSnippet {
-    pub extension: &'static str,
-    pub display_name: &'static str,
-    pub code: &'static str,
+    pub extension: Cow<'static, str>,
+    pub display_name: Cow<'static, str>,
+    pub code: Cow<'static, str>,
+}
+
+impl CodeSnippet {
+    const fn builtin(
+        extension: &'static str,
+        display_name: &'static str,
+        code: &'static str,
+    ) -> Self {
+        Self {
+            extension: Cow::Borrowed(extension),
+            display_name: Cow::Borrowed(display_name),
+            code: Cow::Borrowed(code),
+        }
+    }
 }
 
 pub const SNIPPETS: &[CodeSnippet] = &[
-    CodeSnippet {
-        extension: "rs",
-        display_name: "Rust",
-        code: include_str!("snippets/sample.rs"),
-    },
-    CodeSnippet {
-        extension: "py",
-        display_name: "Python",
-        code: include_str!("snippets/sample.py"),
-    },
-    CodeSnippet {
-        extension: "js",
-        display_name: "JavaScript",
-        code: include_str!("snippets/sample.js"),
-    },
-    CodeSnippet {
-        extension: "go",
-        display_name: "Go",
-        code: include_str!("snippets/sample.go"),
-    },
-    CodeSnippet {
-        extension: "sh",
-        display_name: "Shell",
-        code: include_str!("snippets/sample.sh"),
-    },
+    CodeSnippet::builtin("rs", "Rust", include_str!("snippets/sample.rs")),
+    CodeSnippet::builtin("py", "Python", include_str!("snippets/sample.py")),
+    CodeSnippet::builtin("js", "JavaScript", include_str!("snippets/sample.js")),
+    CodeSnippet::builtin("go", "Go", include_str!("snippets/sample.go")),
+    CodeSnippet::builtin("sh", "Shell", include_str!("snippets/sample.sh")),
 ];
+
+/// Load the snippets to preview: files from `dir` (sorted by name) followed
+/// by the built-in samples.
+///
+/// The language of each file is inferred from its extension; files with no
+/// known syntax, unreadable or non-UTF-8 files, and files larger than
+/// `MAX_SNIPPET_BYTES` are skipped with a warning.
+pub fn load_snippets(dir: Option<&Path>) -> Vec<CodeSnippet> {
+    let mut snippets = dir.map(load_dir).unwrap_or_default();
+    snippets.extend(SNIPPETS.iter().map(|s| CodeSnippet {
+        extension: s.extension.clone(),
+        display_name: s.display_name.clone(),
+        code: s.code.clone(),
+    }));
+    snippets
+}
+
+fn load_dir(dir: &Path) -> Vec<CodeSnippet> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!(dir = %dir.display(), error = %e, "cannot read snippets directory");
+            return Vec::new();
+        }
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let extension = path.extension()?.to_str()?.to_string();
+            let Some(syntax) = SYNTAX_SET.find_syntax_by_extension(&extension) else {
+                warn!(path = %path.display(), "no syntax for snippet extension, skipping");
+                return None;
+            };
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if size > MAX_SNIPPET_BYTES {
+                warn!(path = %path.display(), size, "snippet too large, skipping");
+                return None;
+            }
+            let code = match fs::read_to_string(&path) {
+                Ok(code) => code,
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e, "cannot read snippet, skipping");
+                    return None;
+                }
+            };
+            let file_name = path.file_name()?.to_string_lossy();
+            Some(CodeSnippet {
+                display_name: Cow::Owned(format!("{}: {file_name}", syntax.name)),
+                extension: Cow::Owned(extension),
+                code: Cow::Owned(code),
+            })
+        })
+        .collect()
+}
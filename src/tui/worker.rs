@@ -0,0 +1,216 @@
+//! Background palette regeneration.
+//!
+//! Generation (an accent solve per hue plus gamut mapping) is too slow to run
+//! inside the event loop on every keypress, so the model hands each request
+//! to a worker thread and keeps drawing. Requests are numbered; the worker
+//! skips queued requests that a newer one has superseded, and results that
+//! arrive for a superseded request are discarded.
+//!
+//! Each job runs through a [`GenerationCache`], so a change to one parameter
+//! group only re-solves the stages downstream of it.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use tinted_builder::{Base16Scheme, SchemeVariant};
+use tracing::{debug, warn};
+
+use crate::gamut_map::gamut_cache_stats;
+use crate::generate::{GenerateConfig, GenerationCache, GenerationResult};
+use crate::linked::generate_counterpart;
+use crate::metadata::check_generated_metadata;
+use crate::validation::{
+    ContrastStandard, ValidationConfig, ValidationResults, validate_with_config,
+};
+use crate::wide_gamut::WidePalette;
+
+/// Braille spinner frames shown while a regeneration is running.
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Everything needed to generate and validate one palette.
+#[derive(Debug, Clone)]
+pub struct GenerationJob {
+    pub id: u64,
+    pub config: GenerateConfig,
+    pub variant: Option<SchemeVariant>,
+    pub standard: ContrastStandard,
+    pub thresholds: ValidationConfig,
+    /// Also generate the opposite variant, linked to this one
+    pub linked: bool,
+}
+
+/// A generated palette with its validation results.
+#[derive(Debug, Clone)]
+pub struct Generation {
+    pub id: u64,
+    pub scheme: Base16Scheme,
+    pub wide_palette: WidePalette,
+    pub validation: ValidationResults,
+    pub warnings: Vec<String>,
+    /// The opposite variant, for linked jobs
+    pub counterpart: Option<Counterpart>,
+}
+
+/// The opposite variant of a linked generation (see `crate::linked`).
+#[derive(Debug, Clone)]
+pub struct Counterpart {
+    pub scheme: Base16Scheme,
+    pub wide_palette: WidePalette,
+    pub validation: ValidationResults,
+    pub warnings: Vec<String>,
+}
+
+impl GenerationJob {
+    /// Generate and validate the palette (on the calling thread), reusing
+    /// the stages `cache` holds from earlier jobs.
+    pub fn run(self, cache: &mut GenerationCache) -> Generation {
+        let result = cache.generate_for_variant(&self.config, self.variant.clone());
+        debug!(stages = ?cache.recomputed(), "recomputed generation stages");
+        let counterpart = self.linked.then(|| {
+            let (_, other) = generate_counterpart(&self.config, &result);
+            let (validation, warnings) = self.check(&other);
+            Counterpart {
+                scheme: other.scheme,
+                wide_palette: other.wide_palette,
+                validation,
+                warnings,
+            }
+        });
+        let cache = gamut_cache_stats();
+        debug!(
+            hits = cache.hits,
+            misses = cache.misses,
+            hit_rate = cache.hit_rate(),
+            bytes = cache.bytes,
+            "gamut cache"
+        );
+        let (validation, warnings) = self.check(&result);
+        Generation {
+            id: self.id,
+            scheme: result.scheme,
+            wide_palette: result.wide_palette,
+            validation,
+            warnings,
+            counterpart,
+        }
+    }
+
+    /// Validation results and warnings (generation and metadata) for `result`.
+    fn check(&self, result: &GenerationResult) -> (ValidationResults, Vec<String>) {
+        let validation = validate_with_config(
+            &result.scheme,
+            &result.base_accent_results,
+            &result.extended_accent_results,
+            self.standard,
+            &self.thresholds,
+        );
+        let mut warnings = result.warnings.clone();
+        warnings.extend(
+            check_generated_metadata(&result.scheme, self.config.author.is_some())
+                .iter()
+                .map(|issue| format!("metadata: {issue}")),
+        );
+        (validation, warnings)
+    }
+}
+
+/// Handle to the regeneration thread.
+///
+/// The thread exits when the handle is dropped.
+pub struct RegenWorker {
+    jobs: Sender<GenerationJob>,
+    results: Receiver<Generation>,
+    /// Id of the most recent request; older work is stale
+    latest: Arc<AtomicU64>,
+    /// Id of the last result applied
+    applied: u64,
+    spinner: usize,
+}
+
+impl RegenWorker {
+    /// Start the worker thread.
+    pub fn spawn() -> Self {
+        let (jobs, job_rx) = mpsc::channel::<GenerationJob>();
+        let (result_tx, results) = mpsc::channel();
+        let latest = Arc::new(AtomicU64::new(0));
+
+        let current = Arc::clone(&latest);
+        thread::Builder::new()
+            .name("regenerate".into())
+            .spawn(move || {
+                let mut cache = GenerationCache::new();
+                while let Ok(mut job) = job_rx.recv() {
+                    // Only the newest queued request matters
+                    loop {
+                        match job_rx.try_recv() {
+                            Ok(newer) => job = newer,
+                            Err(TryRecvError::Empty) => break,
+                            Err(TryRecvError::Disconnected) => return,
+                        }
+                    }
+                    if job.id < current.load(Ordering::Acquire) {
+                        continue;
+                    }
+                    let generation = job.run(&mut cache);
+                    if generation.id < current.load(Ordering::Acquire) {
+                        debug!(id = generation.id, "dropping stale regeneration");
+                        continue;
+                    }
+                    if result_tx.send(generation).is_err() {
+                        return;
+                    }
+                }
+            })
+            .unwrap_or_else(|e| panic!("failed to spawn regeneration thread: {e}"));
+
+        Self {
+            jobs,
+            results,
+            latest,
+            applied: 0,
+            spinner: 0,
+        }
+    }
+
+    /// Queue `job`, superseding any pending request. Hands the job back if
+    /// the worker thread has stopped, so it can run inline instead.
+    pub fn request(&mut self, mut job: GenerationJob) -> Option<GenerationJob> {
+        job.id = self.latest.fetch_add(1, Ordering::AcqRel) + 1;
+        let e = self.jobs.send(job).err()?;
+        warn!("regeneration thread stopped");
+        Some(e.0)
+    }
+
+    /// The newest finished generation for the latest request, if it arrived.
+    pub fn poll(&mut self) -> Option<Generation> {
+        let latest = self.latest.load(Ordering::Acquire);
+        let newest = self.results.try_iter().filter(|g| g.id == latest).last()?;
+        self.applied = newest.id;
+        Some(newest)
+    }
+
+    /// Block until the latest request's result arrives. Returns `None` if
+    /// nothing is pending or the worker thread has stopped.
+    pub fn wait(&mut self) -> Option<Generation> {
+        let latest = self.latest.load(Ordering::Acquire);
+        if self.applied >= latest {
+            return None;
+        }
+        let newest = self.results.iter().find(|g| g.id == latest)?;
+        self.applied = newest.id;
+        Some(newest)
+    }
+
+    /// Whether a requested regeneration hasn't been applied yet.
+    pub fn is_busy(&self) -> bool {
+        self.applied < self.latest.load(Ordering::Acquire)
+    }
+
+    /// Advance and return the spinner frame.
+    pub fn spinner(&mut self) -> char {
+        self.spinner = (self.spinner + 1) % SPINNER.len();
+        SPINNER[self.spinner]
+    }
+}
@@ -1,16 +1,342 @@
-//! Palette validation with APCA contrast checking.
+//! Palette validation with APCA and WCAG 2.x contrast checking.
+//!
+//! Every pair is measured with both metrics; the `ContrastStandard` decides
+//! which of them a pair must meet to pass.
+//!
+//! Accents are checked against base00, base01, and base02, each with its own
+//! threshold (`[validation]`), and only the worst of the three counts toward
+//! passing. APCA thresholds can be raised or lowered per polarity. Further
+//! required pairs can be listed in `[validation] pairs` as rules such as
+//! `"base08 on base02 >= 45"`.
+//!
+//! [`validate_with_model`] measures with another [`ContrastModel`] in place
+//! of APCA; thresholds are then read in that model's units.
+//!
+//! Each result is also annotated with the smallest text its APCA Lc is fit
+//! for ([`TextUsage`]), read from the APCA font size/weight lookup table.
+//!
+//! Accent pairs declared in `[validation.semantic]` are required checks too:
+//! each must stay distinguishable in hue or lightness with and without
+//! simulated color vision deficiency (see [`crate::semantic_pairs`]).
+
+use std::fmt;
 
 use float_cmp::approx_eq;
 use palette::Srgb;
+use serde::{Deserialize, Serialize};
 use tinted_builder::Base16Scheme;
 use tracing::warn;
 
 #[cfg(debug_assertions)]
 use tracing::instrument;
 
-use crate::apca::{Threshold, apca_contrast, thresholds};
+use crate::apca::{Threshold, allows_non_text, apca_contrast, min_font_size, thresholds};
+use crate::config::{ConfigError, TerminalAttributes};
+use crate::contrast::{Apca, ContrastModel};
+use crate::contrast_matrix::SLOTS;
 use crate::hellwig::HellwigJmh;
 use crate::interpolation::AccentResult;
+use crate::semantic_pairs::{SemanticPairResult, SemanticPairs, check_semantic_pairs};
+use crate::variant_match::VariantMatch;
+use crate::wcag::{TextSize, WcagLevel, contrast_ratio};
+
+/// Which contrast metric(s) a pair must meet to pass validation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContrastStandard {
+    /// APCA Lc thresholds (WCAG 3.0 draft)
+    #[default]
+    Apca,
+    /// WCAG 2.x contrast ratio at level AA
+    Wcag,
+    /// Both APCA and WCAG 2.x AA
+    Both,
+}
+
+impl ContrastStandard {
+    /// Whether this is the default (APCA-only) standard.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether APCA failures fail validation.
+    pub fn checks_apca(self) -> bool {
+        matches!(self, ContrastStandard::Apca | ContrastStandard::Both)
+    }
+
+    /// Whether WCAG 2.x failures fail validation.
+    pub fn checks_wcag(self) -> bool {
+        matches!(self, ContrastStandard::Wcag | ContrastStandard::Both)
+    }
+}
+
+impl fmt::Display for ContrastStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ContrastStandard::Apca => "APCA",
+            ContrastStandard::Wcag => "WCAG 2.x AA",
+            ContrastStandard::Both => "APCA + WCAG 2.x AA",
+        })
+    }
+}
+
+/// Which way an APCA contrast runs.
+///
+/// APCA is asymmetric: the same |Lc| is computed differently for dark text
+/// on a light background (positive Lc) and light text on a dark background
+/// (negative Lc), so thresholds can be adjusted per polarity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    /// Text darker than its background (positive Lc)
+    DarkOnLight,
+    /// Text lighter than its background (negative Lc)
+    LightOnDark,
+}
+
+impl Polarity {
+    /// Polarity of a signed APCA Lc.
+    pub fn of(lc: f64) -> Self {
+        if lc >= 0.0 {
+            Polarity::DarkOnLight
+        } else {
+            Polarity::LightOnDark
+        }
+    }
+}
+
+impl fmt::Display for Polarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Polarity::DarkOnLight => "dark on light",
+            Polarity::LightOnDark => "light on dark",
+        })
+    }
+}
+
+/// Body text size (px) at regular weight.
+const BODY_TEXT_PX: f32 = 18.0;
+
+/// Largest size (px) at regular weight still counted as content text.
+const CONTENT_TEXT_PX: f32 = 24.0;
+
+/// Regular font weight.
+const REGULAR_WEIGHT: u16 = 400;
+
+/// The smallest use an APCA Lc allows, from the APCA font lookup table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextUsage {
+    /// Too little contrast for anything
+    NotReadable,
+    /// Dividers, icons, and other non-text elements
+    NonText,
+    /// Headlines and other large or heavy text
+    LargeText,
+    /// Text up to 24 px at regular weight, but not body copy
+    ContentText,
+    /// Body text, 18 px at regular weight or smaller
+    BodyText,
+}
+
+impl TextUsage {
+    /// Usage allowed by an APCA Lc (either polarity).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use themalingadingdong::validation::TextUsage;
+    ///
+    /// assert_eq!(TextUsage::of(-90.0), TextUsage::BodyText);
+    /// assert_eq!(TextUsage::of(48.0), TextUsage::LargeText);
+    /// assert_eq!(TextUsage::of(5.0), TextUsage::NotReadable);
+    /// ```
+    pub fn of(lc: f64) -> Self {
+        match min_font_size(lc, REGULAR_WEIGHT) {
+            Some(px) if px <= BODY_TEXT_PX => TextUsage::BodyText,
+            Some(px) if px <= CONTENT_TEXT_PX => TextUsage::ContentText,
+            _ if (100..=900)
+                .step_by(100)
+                .any(|weight| min_font_size(lc, weight).is_some()) =>
+            {
+                TextUsage::LargeText
+            }
+            _ if allows_non_text(lc) => TextUsage::NonText,
+            _ => TextUsage::NotReadable,
+        }
+    }
+}
+
+impl fmt::Display for TextUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TextUsage::BodyText => "ok for body text",
+            TextUsage::ContentText => "content text",
+            TextUsage::LargeText => "large text only",
+            TextUsage::NonText => "non-text only",
+            TextUsage::NotReadable => "not readable",
+        })
+    }
+}
+
+/// Backgrounds every accent is checked against, with the threshold description.
+const ACCENT_BACKGROUNDS: [(&str, &str); 3] = [
+    ("base00", "Accent text"),
+    ("base01", "Accent text on base01"),
+    ("base02", "Accent text on selection"),
+];
+
+/// Accent thresholds per background, per-polarity adjustments, and extra
+/// required pairs (the `[validation]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    /// Minimum accent Lc on base00, the default background
+    pub base00: f64,
+    /// Minimum accent Lc on base01 (status bars, gutters)
+    pub base01: f64,
+    /// Minimum accent Lc on base02 (selections)
+    pub base02: f64,
+    /// Lc added to every threshold for light text on a dark background
+    pub light_on_dark: f64,
+    /// Lc added to every threshold for dark text on a light background
+    pub dark_on_light: f64,
+    /// Extra required pairs, e.g. `"base08 on base02 >= 45"`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pairs: Vec<String>,
+    /// Accent pairs that must stay distinguishable
+    #[serde(skip_serializing_if = "SemanticPairs::is_default")]
+    pub semantic: SemanticPairs,
+    /// How far accents may drift between the dark and light variants
+    #[serde(skip_serializing_if = "VariantMatch::is_default")]
+    pub variants: VariantMatch,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            base00: thresholds::CONTENT_TEXT.min_lc,
+            base01: thresholds::LARGE_TEXT.min_lc,
+            base02: thresholds::SPOT_TEXT.min_lc,
+            light_on_dark: 0.0,
+            dark_on_light: 0.0,
+            pairs: Vec::new(),
+            semantic: SemanticPairs::default(),
+            variants: VariantMatch::default(),
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Whether these are the default thresholds.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Minimum accent Lc on `background`, if accents are checked against it.
+    pub fn accent_threshold(&self, background: &str) -> Option<f64> {
+        match background {
+            "base00" => Some(self.base00),
+            "base01" => Some(self.base01),
+            "base02" => Some(self.base02),
+            _ => None,
+        }
+    }
+
+    /// Lc added to thresholds for pairs of the given polarity.
+    pub fn offset(&self, polarity: Polarity) -> f64 {
+        match polarity {
+            Polarity::DarkOnLight => self.dark_on_light,
+            Polarity::LightOnDark => self.light_on_dark,
+        }
+    }
+
+    /// Check that every threshold is within the APCA Lc range.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (name, value) in [
+            ("base00", self.base00),
+            ("base01", self.base01),
+            ("base02", self.base02),
+        ] {
+            if !(0.0..=108.0).contains(&value) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "validation.{name} must be between 0 and 108 (got {value})"
+                )));
+            }
+        }
+        for (name, value) in [
+            ("light_on_dark", self.light_on_dark),
+            ("dark_on_light", self.dark_on_light),
+        ] {
+            if !(-50.0..=50.0).contains(&value) {
+                return Err(ConfigError::InvalidValue(format!(
+                    "validation.{name} must be between -50 and 50 (got {value})"
+                )));
+            }
+        }
+        for rule in &self.pairs {
+            parse_pair_rule(rule)
+                .map_err(|e| ConfigError::InvalidValue(format!("validation.pairs: {e}")))?;
+        }
+        self.semantic.validate()?;
+        self.variants.validate()
+    }
+}
+
+/// Description of the thresholds of pairs from `[validation] pairs`.
+const CUSTOM_PAIR: &str = "Custom pair";
+
+/// Parse a pair rule: `"<foreground> on <background> >= <Lc>"` (`≥` also
+/// works), with slot names in either case.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::validation::parse_pair_rule;
+///
+/// let pair = parse_pair_rule("base0a on base02 ≥ 45").unwrap();
+/// assert_eq!((pair.foreground, pair.background), ("base0A", "base02"));
+/// assert_eq!(pair.threshold.min_lc, 45.0);
+///
+/// assert!(parse_pair_rule("base08 over base02 >= 45").is_err());
+/// ```
+pub fn parse_pair_rule(rule: &str) -> Result<ValidationPair, String> {
+    let invalid =
+        |reason: &str| format!("{reason} (got '{rule}', expected e.g. \"base08 on base02 >= 45\")");
+    let slot = |name: &str| {
+        SLOTS
+            .into_iter()
+            .find(|slot| slot.eq_ignore_ascii_case(name.trim()))
+    };
+    let (pair, min_lc) = rule
+        .split_once(">=")
+        .or_else(|| rule.split_once('≥'))
+        .ok_or_else(|| invalid("missing '>='"))?;
+    let min_lc: f64 = min_lc
+        .trim()
+        .parse()
+        .ok()
+        .filter(|lc| (0.0..=108.0).contains(lc))
+        .ok_or_else(|| invalid("the threshold is not an Lc between 0 and 108"))?;
+    let (foreground, background) = pair
+        .split_once(" on ")
+        .ok_or_else(|| invalid("missing 'on' between the slots"))?;
+    let foreground = slot(foreground).ok_or_else(|| invalid("the foreground is not a slot"))?;
+    let background = slot(background).ok_or_else(|| invalid("the background is not a slot"))?;
+    Ok(ValidationPair {
+        foreground,
+        background,
+        threshold: Threshold {
+            min_lc,
+            description: CUSTOM_PAIR,
+        },
+        // Rules demanding body-text contrast get the body-text WCAG ratio
+        text_size: if min_lc >= thresholds::BODY_TEXT_MIN.min_lc {
+            TextSize::Normal
+        } else {
+            TextSize::Large
+        },
+    })
+}
 
 /// A color pair that should be validated for contrast.
 #[derive(Debug, Clone)]
@@ -18,13 +344,43 @@ pub struct ValidationPair {
     pub foreground: &'static str,
     pub background: &'static str,
     pub threshold: Threshold,
+    /// Text size used to pick the WCAG 2.x threshold
+    pub text_size: TextSize,
+}
+
+impl ValidationPair {
+    /// Minimum WCAG 2.x contrast ratio (level AA) for this pair.
+    pub fn min_ratio(&self) -> f64 {
+        WcagLevel::Aa.min_ratio(self.text_size)
+    }
+
+    /// Whether the pair comes from `[validation] pairs`.
+    pub fn is_custom(&self) -> bool {
+        self.threshold.description == CUSTOM_PAIR
+    }
 }
 
 /// Result of validating a single color pair.
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
+    /// The pair, with its threshold adjusted for the measured polarity
     pub pair: ValidationPair,
+    /// APCA contrast (Lc, signed), or the validation's contrast model
     pub contrast: f64,
+    /// Whether the foreground is darker or lighter than the background
+    pub polarity: Polarity,
+    /// Smallest text the pair's APCA Lc is fit for (measured with APCA even
+    /// under another contrast model)
+    pub usage: TextUsage,
+    /// WCAG 2.x contrast ratio (1-21)
+    pub wcag_ratio: f64,
+    /// Highest WCAG 2.x level the ratio meets, if any
+    pub wcag_level: Option<WcagLevel>,
+    /// Whether the APCA (or contrast model) threshold is met
+    pub apca_passes: bool,
+    /// Whether the WCAG 2.x AA threshold is met
+    pub wcag_passes: bool,
+    /// Whether the pair passes under the selected standard
     pub passes: bool,
     /// HellwigJmh values of the foreground color (only for accent colors base08-base17).
     pub fg_hellwig: Option<HellwigJmh>,
@@ -34,78 +390,59 @@ pub struct ValidationResult {
     pub m_in_bounds: bool,
 }
 
-/// Get required validation pairs (must pass for scheme to be valid).
-///
-/// - UI colors (base06-base07) must pass on both base00 and base01
-/// - Accent colors only need to pass on base00 (solver targets base00 only)
-pub fn required_validation_pairs() -> Vec<ValidationPair> {
-    let mut pairs = Vec::new();
+impl ValidationResult {
+    /// APCA |Lc| above (positive) or below (negative) the threshold.
+    pub fn margin(&self) -> f64 {
+        self.contrast.abs() - self.pair.threshold.min_lc
+    }
+}
 
-    // UI colors must pass on both backgrounds
+/// UI color pairs: base06-base07 must pass body-text contrast on both base00
+/// and base01.
+pub fn ui_validation_pairs() -> Vec<ValidationPair> {
+    let mut pairs = Vec::new();
     for fg in ["base06", "base07"] {
         for bg in ["base00", "base01"] {
             pairs.push(ValidationPair {
                 foreground: fg,
                 background: bg,
                 threshold: thresholds::BODY_TEXT_MIN,
+                text_size: TextSize::Normal,
             });
         }
     }
-
-    // Accent colors only need to pass on base00
-    for fg in [
-        "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
-    ] {
-        pairs.push(ValidationPair {
-            foreground: fg,
-            background: "base00",
-            threshold: thresholds::CONTENT_TEXT,
-        });
-    }
-
-    // Extended accent colors only need to pass on base00
-    for fg in [
-        "base10", "base11", "base12", "base13", "base14", "base15", "base16", "base17",
-    ] {
-        pairs.push(ValidationPair {
-            foreground: fg,
-            background: "base00",
-            threshold: thresholds::CONTENT_TEXT,
-        });
-    }
-
     pairs
 }
 
-/// Get reference validation pairs (informational, shown but not required).
-///
-/// These show contrast against base01 for accent colors.
-pub fn reference_validation_pairs() -> Vec<ValidationPair> {
-    let mut pairs = Vec::new();
-
-    // Accent colors on base01 (reference only)
-    for fg in [
-        "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
-    ] {
-        pairs.push(ValidationPair {
-            foreground: fg,
-            background: "base01",
-            threshold: thresholds::CONTENT_TEXT,
-        });
-    }
-
-    // Extended accent colors on base01 (reference only)
-    for fg in [
-        "base10", "base11", "base12", "base13", "base14", "base15", "base16", "base17",
-    ] {
-        pairs.push(ValidationPair {
-            foreground: fg,
-            background: "base01",
-            threshold: thresholds::CONTENT_TEXT,
-        });
-    }
+/// Every accent (base08-base17) on base00, base01, and base02, with the
+/// thresholds from `config`. Ordered by accent, then background.
+pub fn accent_validation_pairs(config: &ValidationConfig) -> Vec<ValidationPair> {
+    ACCENT_COLORS
+        .iter()
+        .flat_map(|fg| {
+            ACCENT_BACKGROUNDS
+                .iter()
+                .map(move |(bg, description)| ValidationPair {
+                    foreground: fg,
+                    background: bg,
+                    threshold: Threshold {
+                        min_lc: config.accent_threshold(bg).unwrap_or_default(),
+                        description,
+                    },
+                    text_size: TextSize::Large,
+                })
+        })
+        .collect()
+}
 
-    pairs
+/// The pairs listed in `config`'s `pairs`, skipping rules that don't parse
+/// (`ValidationConfig::validate` reports those).
+pub fn custom_validation_pairs(config: &ValidationConfig) -> Vec<ValidationPair> {
+    config
+        .pairs
+        .iter()
+        .filter_map(|rule| parse_pair_rule(rule).ok())
+        .collect()
 }
 
 /// Accent color names (base08-base0F and base10-base17).
@@ -122,16 +459,49 @@ fn is_accent_color(name: &str) -> bool {
 /// Combined validation results with required and reference checks separated.
 #[derive(Debug, Clone)]
 pub struct ValidationResults {
-    /// Results that must pass for scheme to be valid.
+    /// Standard the pass/fail results were judged against.
+    pub standard: ContrastStandard,
+    /// Results that must pass for scheme to be valid: the UI pairs, then
+    /// each accent's worst case across base00, base01, and base02, then the
+    /// pairs from `[validation] pairs`.
     pub required: Vec<ValidationResult>,
-    /// Results shown for reference only (informational).
+    /// Every accent on every checked background (informational).
     pub reference: Vec<ValidationResult>,
+    /// Worst case of each semantic pair from `[validation.semantic]`; these
+    /// are required checks as well.
+    pub semantic: Vec<SemanticPairResult>,
+}
+
+impl ValidationResults {
+    /// Number of required checks, contrast and semantic.
+    pub fn required_count(&self) -> usize {
+        self.required.len() + self.semantic.len()
+    }
+
+    /// Number of required checks that pass.
+    pub fn required_passed(&self) -> usize {
+        self.required.iter().filter(|r| r.passes).count()
+            + self.semantic.iter().filter(|r| r.passes).count()
+    }
+
+    /// Whether every required check passes.
+    pub fn passes(&self) -> bool {
+        self.required_passed() == self.required_count()
+    }
 }
 
-/// Validate a scheme and return separated required/reference results.
+/// Validate a scheme against APCA and return separated required/reference results.
 #[cfg_attr(debug_assertions, instrument(skip(scheme), fields(scheme_name = %scheme.name)))]
 pub fn validate(scheme: &Base16Scheme) -> ValidationResults {
-    validate_with_accent_data(scheme, &[], &[])
+    validate_with_standard(scheme, ContrastStandard::default())
+}
+
+/// Validate a scheme against the given contrast standard.
+pub fn validate_with_standard(
+    scheme: &Base16Scheme,
+    standard: ContrastStandard,
+) -> ValidationResults {
+    validate_with_accent_data(scheme, &[], &[], standard)
 }
 
 /// Validate a scheme with accent result data for gamut mapping detection.
@@ -139,6 +509,87 @@ pub fn validate_with_accent_data(
     scheme: &Base16Scheme,
     base_accent_results: &[AccentResult],
     extended_accent_results: &[AccentResult],
+    standard: ContrastStandard,
+) -> ValidationResults {
+    validate_with_config(
+        scheme,
+        base_accent_results,
+        extended_accent_results,
+        standard,
+        &ValidationConfig::default(),
+    )
+}
+
+/// Validate a scheme with the accent thresholds and polarity adjustments
+/// from `config`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::validation::{ContrastStandard, ValidationConfig, validate_with_config};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let strict = ValidationConfig {
+///     base02: 60.0,
+///     ..Default::default()
+/// };
+/// let results = validate_with_config(&scheme, &[], &[], ContrastStandard::Apca, &strict);
+///
+/// // One worst case per accent, after the four UI pairs
+/// assert_eq!(results.required.len(), 4 + 16);
+/// assert_eq!(results.reference.len(), 16 * 3);
+/// assert!(results.required.iter().any(|r| r.pair.background == "base02"));
+/// ```
+pub fn validate_with_config(
+    scheme: &Base16Scheme,
+    base_accent_results: &[AccentResult],
+    extended_accent_results: &[AccentResult],
+    standard: ContrastStandard,
+    config: &ValidationConfig,
+) -> ValidationResults {
+    validate_with_model(
+        scheme,
+        base_accent_results,
+        extended_accent_results,
+        standard,
+        config,
+        &Apca,
+    )
+}
+
+/// Validate a scheme measuring contrast with `model` instead of APCA.
+///
+/// `model` takes APCA's place in `standard`, and every threshold (the UI
+/// pairs' and `config`'s) is compared against its contrast as is.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::contrast::Michelson;
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::validation::{ContrastStandard, ValidationConfig, validate_with_model};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let results = validate_with_model(
+///     &scheme,
+///     &[],
+///     &[],
+///     ContrastStandard::Apca,
+///     &ValidationConfig::default(),
+///     &Michelson,
+/// );
+///
+/// // Michelson contrast never exceeds 100%
+/// assert!(results.required.iter().all(|r| r.contrast.abs() <= 100.0));
+/// ```
+pub fn validate_with_model(
+    scheme: &Base16Scheme,
+    base_accent_results: &[AccentResult],
+    extended_accent_results: &[AccentResult],
+    standard: ContrastStandard,
+    config: &ValidationConfig,
+    model: &dyn ContrastModel,
 ) -> ValidationResults {
     // Helper to get AccentResult for a color name
     let get_accent_result = |fg_name: &str| -> Option<&AccentResult> {
@@ -166,7 +617,7 @@ pub fn validate_with_accent_data(
     let validate_pairs = |pairs: Vec<ValidationPair>| -> Vec<ValidationResult> {
         pairs
             .into_iter()
-            .map(|pair| {
+            .map(|mut pair| {
                 let fg_color = scheme.palette.get(pair.foreground);
                 let bg_color = scheme.palette.get(pair.background);
 
@@ -174,12 +625,23 @@ pub fn validate_with_accent_data(
                     (Some(fg), Some(bg)) => {
                         let fg_srgb = Srgb::new(fg.rgb.0, fg.rgb.1, fg.rgb.2);
                         let bg_srgb = Srgb::new(bg.rgb.0, bg.rgb.1, bg.rgb.2);
-                        let contrast = apca_contrast(fg_srgb, bg_srgb);
+                        let contrast = model.contrast(fg_srgb, bg_srgb);
+                        let polarity = Polarity::of(contrast);
+                        let usage = TextUsage::of(apca_contrast(fg_srgb, bg_srgb));
+                        pair.threshold.min_lc += config.offset(polarity);
+
                         let abs_contrast = contrast.abs();
                         let threshold = pair.threshold.min_lc;
-                        let passes = abs_contrast > threshold
+                        let apca_passes = abs_contrast > threshold
                             || approx_eq!(f64, abs_contrast, threshold, epsilon = 0.5);
 
+                        let wcag_ratio = contrast_ratio(fg_srgb, bg_srgb);
+                        let wcag_level = WcagLevel::achieved(wcag_ratio, pair.text_size);
+                        let wcag_passes = wcag_level.is_some();
+
+                        let passes = (apca_passes || !standard.checks_apca())
+                            && (wcag_passes || !standard.checks_wcag());
+
                         let fg_hellwig = if is_accent_color(pair.foreground) {
                             Some(HellwigJmh::from_srgb_u8(fg_srgb))
                         } else {
@@ -194,6 +656,12 @@ pub fn validate_with_accent_data(
                         ValidationResult {
                             pair,
                             contrast,
+                            polarity,
+                            usage,
+                            wcag_ratio,
+                            wcag_level,
+                            apca_passes,
+                            wcag_passes,
                             passes,
                             fg_hellwig,
                             was_gamut_mapped,
@@ -209,6 +677,12 @@ pub fn validate_with_accent_data(
                         ValidationResult {
                             pair,
                             contrast: 0.0,
+                            polarity: Polarity::of(0.0),
+                            usage: TextUsage::NotReadable,
+                            wcag_ratio: 1.0,
+                            wcag_level: None,
+                            apca_passes: false,
+                            wcag_passes: false,
                             passes: false,
                             fg_hellwig: None,
                             was_gamut_mapped: false,
@@ -220,26 +694,202 @@ pub fn validate_with_accent_data(
             .collect()
     };
 
+    let reference = validate_pairs(accent_validation_pairs(config));
+
+    // Each accent's worst case: a failing pair first, then the smallest margin
+    let mut required = validate_pairs(ui_validation_pairs());
+    required.extend(
+        reference
+            .chunks(ACCENT_BACKGROUNDS.len())
+            .filter_map(|checks| {
+                checks.iter().min_by(|a, b| {
+                    a.passes
+                        .cmp(&b.passes)
+                        .then(a.margin().total_cmp(&b.margin()))
+                })
+            })
+            .cloned(),
+    );
+    required.extend(validate_pairs(custom_validation_pairs(config)));
+
     ValidationResults {
-        required: validate_pairs(required_validation_pairs()),
-        reference: validate_pairs(reference_validation_pairs()),
+        standard,
+        required,
+        reference,
+        semantic: check_semantic_pairs(scheme, &config.semantic),
     }
 }
 
-/// Validate a scheme and return warnings for any failing required pairs.
+/// Validate a scheme against APCA and return warnings for any failing required pairs.
 pub fn validate_with_warnings(scheme: &Base16Scheme) -> Vec<String> {
-    validate(scheme)
-        .required
-        .into_iter()
-        .filter(|r| !r.passes)
-        .map(|r| {
-            format!(
-                "{} on {}: Lc={:.1} (required: {:.0} for {})",
+    validate_with_warnings_for(scheme, ContrastStandard::default())
+}
+
+/// Validate a scheme against the given standard and return warnings for failing pairs.
+///
+/// A pair failing both metrics under `ContrastStandard::Both` yields two warnings.
+pub fn validate_with_warnings_for(
+    scheme: &Base16Scheme,
+    standard: ContrastStandard,
+) -> Vec<String> {
+    validation_warnings(scheme, standard, &ValidationConfig::default())
+}
+
+/// Warnings for failing required pairs under `standard` and `config`.
+pub fn validation_warnings(
+    scheme: &Base16Scheme,
+    standard: ContrastStandard,
+    config: &ValidationConfig,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let results = validate_with_config(scheme, &[], &[], standard, config);
+    for r in results.required {
+        if standard.checks_apca() && !r.apca_passes {
+            warnings.push(format!(
+                "{} on {}: Lc={:.1} {} (required: {:.0} for {})",
                 r.pair.foreground,
                 r.pair.background,
                 r.contrast.abs(),
+                r.polarity,
                 r.pair.threshold.min_lc,
                 r.pair.threshold.description
+            ));
+        }
+        if standard.checks_wcag() && !r.wcag_passes {
+            warnings.push(format!(
+                "{} on {}: ratio={:.2}:1 (required: {:.1}:1 for WCAG AA {} text)",
+                r.pair.foreground,
+                r.pair.background,
+                r.wcag_ratio,
+                r.pair.min_ratio(),
+                match r.pair.text_size {
+                    TextSize::Normal => "normal",
+                    TextSize::Large => "large",
+                }
+            ));
+        }
+    }
+    for r in results.semantic.iter().filter(|r| !r.passes) {
+        warnings.push(format!("Semantic pair {r}"));
+    }
+    warnings
+}
+
+/// Terminal text attribute that changes how a foreground color renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAttribute {
+    /// `dim` / faint text (SGR 2)
+    Dim,
+    /// Bold text rendered as bright (SGR 1)
+    Bold,
+}
+
+impl fmt::Display for TextAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TextAttribute::Dim => "dim",
+            TextAttribute::Bold => "bold",
+        })
+    }
+}
+
+/// Contrast of one accent on base00 as rendered with a text attribute.
+#[derive(Debug, Clone)]
+pub struct AttributeResult {
+    pub attribute: TextAttribute,
+    pub foreground: &'static str,
+    /// Foreground after applying the attribute's luminance multiplier
+    pub rendered: Srgb<u8>,
+    /// APCA contrast (Lc, signed) of the rendered foreground on base00
+    pub contrast: f64,
+    /// Lowest acceptable Lc for this attribute
+    pub min_lc: f64,
+    pub passes: bool,
+}
+
+/// Scale a color's luminance by `factor` in linear light, clamping to sRGB.
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::validation::scale_luminance;
+///
+/// let gray = Srgb::new(128u8, 128, 128);
+/// assert!(scale_luminance(gray, 0.5).red < 128);
+/// assert_eq!(scale_luminance(Srgb::new(255u8, 255, 255), 2.0), Srgb::new(255, 255, 255));
+/// ```
+pub fn scale_luminance(color: Srgb<u8>, factor: f64) -> Srgb<u8> {
+    let linear = color.into_format::<f32>().into_linear();
+    let factor = factor as f32;
+    let scaled = palette::LinSrgb::new(
+        (linear.red * factor).clamp(0.0, 1.0),
+        (linear.green * factor).clamp(0.0, 1.0),
+        (linear.blue * factor).clamp(0.0, 1.0),
+    );
+    Srgb::<f32>::from_linear(scaled).into_format()
+}
+
+/// Check every accent on base00 as dim and as bold text.
+///
+/// Dim text must reach `attrs.dim_min_contrast`; bold text must still meet
+/// the content-text threshold accents are held to. Accents missing from the
+/// palette are skipped.
+pub fn validate_terminal_attributes(
+    scheme: &Base16Scheme,
+    attrs: &TerminalAttributes,
+) -> Vec<AttributeResult> {
+    let Some(bg) = scheme.palette.get("base00") else {
+        warn!(bg = "base00", "missing color in palette");
+        return Vec::new();
+    };
+    let bg = Srgb::new(bg.rgb.0, bg.rgb.1, bg.rgb.2);
+
+    let mut results = Vec::new();
+    for (attribute, factor, min_lc) in [
+        (TextAttribute::Dim, attrs.dim, attrs.dim_min_contrast),
+        (
+            TextAttribute::Bold,
+            attrs.bold,
+            thresholds::CONTENT_TEXT.min_lc,
+        ),
+    ] {
+        for name in ACCENT_COLORS {
+            let Some(fg) = scheme.palette.get(name) else {
+                continue;
+            };
+            let rendered = scale_luminance(Srgb::new(fg.rgb.0, fg.rgb.1, fg.rgb.2), factor);
+            let contrast = apca_contrast(rendered, bg);
+            let passes =
+                contrast.abs() > min_lc || approx_eq!(f64, contrast.abs(), min_lc, epsilon = 0.5);
+            results.push(AttributeResult {
+                attribute,
+                foreground: name,
+                rendered,
+                contrast,
+                min_lc,
+                passes,
+            });
+        }
+    }
+    results
+}
+
+/// Warnings for accents that become unreadable as dim or bold terminal text.
+pub fn terminal_attribute_warnings(
+    scheme: &Base16Scheme,
+    attrs: &TerminalAttributes,
+) -> Vec<String> {
+    validate_terminal_attributes(scheme, attrs)
+        .into_iter()
+        .filter(|r| !r.passes)
+        .map(|r| {
+            format!(
+                "{} as {} text on base00: Lc={:.1} (required: {:.0})",
+                r.foreground,
+                r.attribute,
+                r.contrast.abs(),
+                r.min_lc
             )
         })
         .collect()
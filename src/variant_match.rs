@@ -0,0 +1,187 @@
+//! Appearance matching between dark and light variants
+//! (`[validation.variants]`).
+//!
+//! A theme's variants should read as the same brand: base08 should be the
+//! same red on either background, even though its lightness flips. Each
+//! accent slot (base08-base0F, and base10-base17 in Base24) is compared in
+//! HellwigJmh, and a pair is flagged when its hue or colorfulness drifts
+//! further apart than allowed:
+//!
+//! ```toml
+//! [validation.variants]
+//! max_hue_shift = 10
+//! max_colorfulness_shift = 8
+//! ```
+//!
+//! Lightness isn't compared; it is meant to differ. Hues of accents too
+//! faint to have one (M below [`ACHROMATIC_M`]) aren't compared either.
+
+use std::fmt;
+
+use palette::Srgb;
+use serde::{Deserialize, Serialize};
+use tinted_builder::Base16Scheme;
+
+use crate::config::ConfigError;
+use crate::hellwig::HellwigJmh;
+use crate::semantic_pairs::ACHROMATIC_M;
+
+/// Default hue difference (degrees) allowed between variants.
+pub const DEFAULT_MAX_HUE_SHIFT: f32 = 10.0;
+
+/// Default colorfulness (M) difference allowed between variants.
+pub const DEFAULT_MAX_COLORFULNESS_SHIFT: f32 = 8.0;
+
+/// Accent slots compared between variants.
+const ACCENT_SLOTS: [&str; 16] = [
+    "base08", "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F", "base10",
+    "base11", "base12", "base13", "base14", "base15", "base16", "base17",
+];
+
+/// How far apart a slot's appearance may drift between variants.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VariantMatch {
+    /// Hue difference (degrees) allowed between variants
+    pub max_hue_shift: f32,
+    /// Colorfulness (M) difference allowed between variants
+    pub max_colorfulness_shift: f32,
+}
+
+impl Default for VariantMatch {
+    fn default() -> Self {
+        Self {
+            max_hue_shift: DEFAULT_MAX_HUE_SHIFT,
+            max_colorfulness_shift: DEFAULT_MAX_COLORFULNESS_SHIFT,
+        }
+    }
+}
+
+impl VariantMatch {
+    /// Whether these are the default thresholds.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that the thresholds are in range.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.max_hue_shift > 0.0 && self.max_hue_shift <= 180.0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "validation.variants.max_hue_shift must be in (0, 180] (got {})",
+                self.max_hue_shift
+            )));
+        }
+        if !(self.max_colorfulness_shift > 0.0 && self.max_colorfulness_shift <= 100.0) {
+            return Err(ConfigError::InvalidValue(format!(
+                "validation.variants.max_colorfulness_shift must be in (0, 100] (got {})",
+                self.max_colorfulness_shift
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One accent slot in both variants.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SlotComparison {
+    pub slot: &'static str,
+    pub dark_hue: f32,
+    pub light_hue: f32,
+    pub dark_colorfulness: f32,
+    pub light_colorfulness: f32,
+    /// Light hue minus dark, in -180 to 180 (`None` when either is too
+    /// faint to have a hue)
+    pub hue_shift: Option<f32>,
+    /// Light M minus dark
+    pub colorfulness_shift: f32,
+    /// Whether the hue shift is past `max_hue_shift`
+    pub hue_diverges: bool,
+    /// Whether the colorfulness shift is past `max_colorfulness_shift`
+    pub colorfulness_diverges: bool,
+}
+
+impl SlotComparison {
+    /// Whether the slot drifts past either threshold.
+    pub fn diverges(&self) -> bool {
+        self.hue_diverges || self.colorfulness_diverges
+    }
+}
+
+impl fmt::Display for SlotComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.slot)?;
+        match self.hue_shift {
+            Some(shift) => write!(
+                f,
+                "h {:.1} -> {:.1} ({shift:+.1})",
+                self.dark_hue, self.light_hue
+            )?,
+            None => write!(f, "h n/a (achromatic)")?,
+        }
+        if self.hue_diverges {
+            write!(f, " !")?;
+        }
+        write!(
+            f,
+            ", M {:.1} -> {:.1} ({:+.1})",
+            self.dark_colorfulness, self.light_colorfulness, self.colorfulness_shift
+        )?;
+        if self.colorfulness_diverges {
+            write!(f, " !")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare the accent slots `dark` and `light` both have.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::GenerateConfig;
+/// use themalingadingdong::linked::generate_linked;
+/// use themalingadingdong::variant_match::{VariantMatch, compare_variants};
+///
+/// let linked = generate_linked(&GenerateConfig::default());
+/// let slots = compare_variants(
+///     &linked.dark.scheme,
+///     &linked.light.scheme,
+///     &VariantMatch::default(),
+/// );
+/// assert_eq!(slots[0].slot, "base08");
+/// // Linked variants share their hues
+/// assert!(slots.iter().all(|s| !s.hue_diverges));
+/// ```
+pub fn compare_variants(
+    dark: &Base16Scheme,
+    light: &Base16Scheme,
+    thresholds: &VariantMatch,
+) -> Vec<SlotComparison> {
+    let jmh = |scheme: &Base16Scheme, slot: &str| {
+        scheme
+            .palette
+            .get(slot)
+            .map(|c| HellwigJmh::from_srgb_u8(Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2)))
+    };
+    ACCENT_SLOTS
+        .iter()
+        .filter_map(|&slot| {
+            let (dark, light) = (jmh(dark, slot)?, jmh(light, slot)?);
+            let hue_shift = (dark.colorfulness >= ACHROMATIC_M
+                && light.colorfulness >= ACHROMATIC_M)
+                .then(|| (light.hue - dark.hue + 180.0).rem_euclid(360.0) - 180.0);
+            let colorfulness_shift = light.colorfulness - dark.colorfulness;
+            Some(SlotComparison {
+                slot,
+                dark_hue: dark.hue,
+                light_hue: light.hue,
+                dark_colorfulness: dark.colorfulness,
+                light_colorfulness: light.colorfulness,
+                hue_shift,
+                colorfulness_shift,
+                hue_diverges: hue_shift.is_some_and(|s| s.abs() > thresholds.max_hue_shift),
+                colorfulness_diverges: colorfulness_shift.abs() > thresholds.max_colorfulness_shift,
+            })
+        })
+        .collect()
+}
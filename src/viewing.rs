@@ -0,0 +1,358 @@
+//! Viewing conditions for the Hellwig-Fairchild model.
+//!
+//! `HellwigJmh` is defined relative to an observer: how bright the
+//! surroundings are (adapting luminance L_A), how the display compares to
+//! its surround, and which white the eye is adapted to. The defaults model
+//! the sRGB reference (64 lux, average surround, D65). A theme for a dim
+//! terminal or a laptop in daylight can be optimized under matching
+//! conditions instead: the same J'/M targets then describe how the colors
+//! look there.
+//!
+//! Conditions apply per thread, for the duration of
+//! `with_viewing_conditions` (`generate` does this with
+//! `GenerateConfig::viewing`). Each distinct set of conditions gets a
+//! model with baked CAM16 parameters, a cusp LUT computed at runtime, and
+//! its own gamut boundary caches. The [`MODEL_CAPACITY`] most recently used
+//! models are kept for later calls; older ones are freed once no thread
+//! has them in effect. The
+//! eccentricity and Helmholtz-Kohlrausch LUTs depend only on hue, so the
+//! build-time tables serve every model; the defaults use the build-time
+//! cusp LUT too, as do conditions baked in with
+//! `THEMALINGADINGDONG_LUT_CONDITIONS` (see build.rs).
+
+use std::cell::RefCell;
+use std::f32::consts::PI;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use palette::cam16::{BakedParameters, Parameters, Surround as Cam16Surround};
+use palette::convert::FromColorUnclamped;
+use palette::white_point::{A, Any, D50, D55, D65, D75, F2, F11, WhitePoint as _};
+use palette::{LinSrgb, Srgb, Xyz};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+use crate::gamut_map::GamutCache;
+use crate::generated::{BAKED_CUSP_LUTS, BakedCuspLut};
+use crate::hellwig::HellwigJmh;
+
+/// Adapting luminance of the sRGB reference viewing conditions (cd/m²):
+/// 64 lux, with the adapting field at 20% of the white.
+pub const DEFAULT_ADAPTING_LUMINANCE: f32 = 64.0 / PI * 0.2;
+
+/// Largest accepted adapting luminance (cd/m², bright daylight).
+const MAX_ADAPTING_LUMINANCE: f32 = 10_000.0;
+
+/// White point the observer is adapted to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhitePoint {
+    /// Daylight, 6504 K (the sRGB white)
+    #[default]
+    D65,
+    /// Horizon light, 5003 K
+    D50,
+    /// Mid-morning daylight, 5503 K
+    D55,
+    /// North sky daylight, 7504 K
+    D75,
+    /// Incandescent light, 2856 K
+    A,
+    /// Cool white fluorescent, 4230 K
+    F2,
+    /// Narrow-band white fluorescent, 4000 K
+    F11,
+}
+
+impl WhitePoint {
+    /// Name as written in `[viewing]`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::D65 => "d65",
+            Self::D50 => "d50",
+            Self::D55 => "d55",
+            Self::D75 => "d75",
+            Self::A => "a",
+            Self::F2 => "f2",
+            Self::F11 => "f11",
+        }
+    }
+
+    /// The white at the brightest level an sRGB display can show it, so
+    /// neutrals stay reproducible up to J' = 100 (the sRGB white for D65).
+    fn xyz(self) -> Xyz<Any, f32> {
+        let white = self.unscaled_xyz();
+        let rgb = LinSrgb::from_color_unclamped(Xyz::<D65, f32>::new(white.x, white.y, white.z));
+        white / rgb.red.max(rgb.green).max(rgb.blue)
+    }
+
+    /// The white in linear sRGB, scaled so its largest channel is 1.
+    pub(crate) fn linear_srgb(self) -> LinSrgb<f32> {
+        let white = self.unscaled_xyz();
+        let rgb = LinSrgb::from_color_unclamped(Xyz::<D65, f32>::new(white.x, white.y, white.z));
+        rgb / rgb.red.max(rgb.green).max(rgb.blue)
+    }
+
+    fn unscaled_xyz(self) -> Xyz<Any, f32> {
+        match self {
+            Self::D65 => D65::get_xyz(),
+            Self::D50 => D50::get_xyz(),
+            Self::D55 => D55::get_xyz(),
+            Self::D75 => D75::get_xyz(),
+            Self::A => A::get_xyz(),
+            Self::F2 => F2::get_xyz(),
+            Self::F11 => F11::get_xyz(),
+        }
+    }
+}
+
+/// Brightness of the area around the display, relative to it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Surround {
+    /// Dark room, e.g. a terminal with the lights off
+    Dark,
+    /// Dimly lit room
+    Dim,
+    /// Lit office or daylight
+    #[default]
+    Average,
+}
+
+impl Surround {
+    /// Name as written in `[viewing]`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Dim => "dim",
+            Self::Average => "average",
+        }
+    }
+}
+
+/// Observer viewing conditions (the `[viewing]` table).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewingConditions {
+    /// White point the observer is adapted to
+    pub white_point: WhitePoint,
+    /// Adapting luminance L_A in cd/m² (about ambient lux / π × 0.2)
+    pub adapting_luminance: f32,
+    /// "dark", "dim", or "average"
+    pub surround: Surround,
+}
+
+impl Default for ViewingConditions {
+    fn default() -> Self {
+        Self {
+            white_point: WhitePoint::D65,
+            adapting_luminance: DEFAULT_ADAPTING_LUMINANCE,
+            surround: Surround::Average,
+        }
+    }
+}
+
+impl ViewingConditions {
+    /// Whether these are the sRGB reference conditions.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that the adapting luminance is positive and plausible.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.adapting_luminance > 0.0 && self.adapting_luminance <= MAX_ADAPTING_LUMINANCE) {
+            return Err(ConfigError::InvalidValue(format!(
+                "viewing.adapting_luminance must be in (0, {MAX_ADAPTING_LUMINANCE}] cd/m² (got {})",
+                self.adapting_luminance
+            )));
+        }
+        Ok(())
+    }
+
+    /// The cusp table baked into the binary for these conditions, if any.
+    pub fn baked_cusps(&self) -> Option<&'static BakedCuspLut> {
+        BAKED_CUSP_LUTS.iter().find(|lut| {
+            lut.surround == self.surround.name()
+                && lut.white_point == self.white_point.name()
+                && (lut.adapting_luminance - self.adapting_luminance).abs() < 1e-3
+        })
+    }
+
+    fn params(&self) -> BakedParameters<Xyz<Any, f32>, f32> {
+        let mut params =
+            Parameters::default_dynamic_wp(self.white_point.xyz(), self.adapting_luminance);
+        params.surround = match self.surround {
+            Surround::Dark => Cam16Surround::Dark,
+            Surround::Dim => Cam16Surround::Dim,
+            Surround::Average => Cam16Surround::Average,
+        };
+        params.bake()
+    }
+}
+
+/// CAM16 parameters and gamut data for one set of non-default conditions.
+pub(crate) struct ViewingModel {
+    pub(crate) conditions: ViewingConditions,
+    pub(crate) params: BakedParameters<Xyz<Any, f32>, f32>,
+    cusps: OnceLock<Box<[(f32, f32)]>>,
+    /// Boundary caches, one per `Gamut`
+    pub(crate) gamut_caches: [OnceLock<GamutCache>; 3],
+}
+
+impl ViewingModel {
+    /// (J'_cusp, M_cusp) for hue angles 0..360 degrees, baked at build time
+    /// or computed on first use.
+    ///
+    /// Computed serially: callers are often rayon workers, and a nested
+    /// parallel loop could steal work that waits on this same cell.
+    pub(crate) fn cusps(&self) -> &[(f32, f32)] {
+        if let Some(lut) = self.conditions.baked_cusps() {
+            return &lut.cusps;
+        }
+        self.cusps
+            .get_or_init(|| (0..360).map(|hue| compute_cusp(self, hue as f32)).collect())
+    }
+}
+
+/// Non-default viewing models kept between calls. Each holds up to three
+/// gamut caches, so conditions that change over a long-running process
+/// (`--watch`, the daemon, the FFI) can't pile up models.
+pub const MODEL_CAPACITY: usize = 8;
+
+/// Recently used models, least recently used first.
+static MODELS: Mutex<Vec<Arc<ViewingModel>>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Model in effect on this thread; `None` for the default conditions.
+    static ACTIVE: RefCell<Option<Arc<ViewingModel>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `conditions` in effect for color conversions and gamut
+/// mapping on this thread.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::hellwig::HellwigJmh;
+/// use themalingadingdong::viewing::{Surround, ViewingConditions, with_viewing_conditions};
+///
+/// let gray = palette::Srgb::new(0.5f32, 0.5, 0.5);
+/// let dark = ViewingConditions {
+///     surround: Surround::Dark,
+///     ..ViewingConditions::default()
+/// };
+///
+/// // The same gray looks lighter against a dark surround
+/// let average = HellwigJmh::from_srgb(gray);
+/// let in_dark = with_viewing_conditions(&dark, || HellwigJmh::from_srgb(gray));
+/// assert!(in_dark.lightness > average.lightness);
+/// ```
+pub fn with_viewing_conditions<R>(conditions: &ViewingConditions, f: impl FnOnce() -> R) -> R {
+    with_model(model_for(conditions), f)
+}
+
+/// Conditions in effect on this thread.
+pub fn active_conditions() -> ViewingConditions {
+    read_active_model(|model| model.map_or_else(ViewingConditions::default, |m| m.conditions))
+}
+
+/// Model in effect on this thread, `None` for the default conditions.
+pub(crate) fn active_model() -> Option<Arc<ViewingModel>> {
+    ACTIVE.with(|active| active.borrow().clone())
+}
+
+/// Call `f` with the model in effect on this thread, without taking a
+/// reference count (for per-color conversions).
+///
+/// `f` must not change the model in effect, e.g. by running rayon jobs
+/// that call [`with_model`].
+#[inline]
+pub(crate) fn read_active_model<R>(f: impl FnOnce(Option<&ViewingModel>) -> R) -> R {
+    ACTIVE.with(|active| f(active.borrow().as_deref()))
+}
+
+/// Run `f` with `model` in effect on this thread, e.g. to carry the
+/// caller's conditions into rayon workers.
+pub(crate) fn with_model<R>(model: Option<Arc<ViewingModel>>, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous model, also when `f` panics.
+    struct Restore(Option<Arc<ViewingModel>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            ACTIVE.with(|active| *active.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(ACTIVE.with(|active| active.replace(model)));
+    f()
+}
+
+/// Models kept between calls, least recently used first.
+pub(crate) fn models() -> Vec<Arc<ViewingModel>> {
+    MODELS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn model_for(conditions: &ViewingConditions) -> Option<Arc<ViewingModel>> {
+    if conditions.is_default() {
+        return None;
+    }
+    let mut models = MODELS.lock().unwrap_or_else(|e| e.into_inner());
+    let model = match models.iter().position(|m| m.conditions == *conditions) {
+        Some(i) => models.remove(i),
+        None => Arc::new(ViewingModel {
+            conditions: *conditions,
+            params: conditions.params(),
+            cusps: OnceLock::new(),
+            gamut_caches: [const { OnceLock::new() }; 3],
+        }),
+    };
+    if models.len() >= MODEL_CAPACITY {
+        models.remove(0);
+    }
+    models.push(Arc::clone(&model));
+    Some(model)
+}
+
+/// Whether J'/M/h is inside sRGB under `model`.
+fn in_srgb(model: &ViewingModel, j: f32, m: f32, hue: f32) -> bool {
+    let rgb: Srgb<f32> =
+        Srgb::from_linear(HellwigJmh::new(j, m, hue).into_linear_srgb_in(Some(model)));
+    [rgb.red, rgb.green, rgb.blue]
+        .iter()
+        .all(|c| (0.0..=1.0).contains(c))
+}
+
+/// Largest in-gamut M at (J', h), to 0.01.
+fn max_m(model: &ViewingModel, j: f32, hue: f32) -> f32 {
+    let (mut lo, mut hi) = (0.0, 150.0);
+    while hi - lo > 0.01 {
+        let mid = (lo + hi) / 2.0;
+        if in_srgb(model, j, mid, hue) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Cusp (J', M) of the sRGB gamut at `hue`: a coarse J' scan, then a fine
+/// one around the best step (as the build-time LUT is made).
+fn compute_cusp(model: &ViewingModel, hue: f32) -> (f32, f32) {
+    let mut best = (50.0, 0.0);
+    for j in (10..=95).map(|j| j as f32) {
+        let m = max_m(model, j, hue);
+        if m > best.1 {
+            best = (j, m);
+        }
+    }
+
+    let (lo, hi) = ((best.0 - 2.0).max(5.0), (best.0 + 2.0).min(98.0));
+    for i in 0..=40 {
+        let j = lo + (hi - lo) * (i as f32 / 40.0);
+        let m = max_m(model, j, hue);
+        if m > best.1 {
+            best = (j, m);
+        }
+    }
+    best
+}
@@ -0,0 +1,137 @@
+//! JavaScript bindings for wasm32 (`wasm` feature).
+//!
+//! Exposes the same generation, validation, and gamut mapping the CLI uses,
+//! so a web playground produces identical palettes. Values cross the
+//! boundary as JSON strings; `JSON.parse` the results on the JavaScript side.
+//!
+//! On wasm32 accent hues are solved one after another (there are no
+//! threads) and with a pattern search instead of COBYLA, which does not
+//! build for the target; palettes match the CLI's to within a couple of
+//! 8-bit sRGB steps. Build without the terminal UI, then run `wasm-bindgen`:
+//!
+//! ```sh
+//! cargo rustc --lib --release --target wasm32-unknown-unknown \
+//!     --no-default-features --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/themalingadingdong.wasm
+//! ```
+//!
+//! ```js
+//! import init, { generate, validate } from "./pkg/themalingadingdong.js";
+//!
+//! await init();
+//! const theme = JSON.parse(generate('[colors]\nbackground = "#1a1a2e"\nforeground = "#eaeaea"'));
+//! const report = JSON.parse(validate(JSON.stringify(theme.scheme)));
+//! ```
+
+use palette::Srgb;
+use serde::Serialize;
+use serde_json::json;
+use tinted_builder::Base16Scheme;
+use wasm_bindgen::prelude::*;
+
+use crate::batch::forced_variant;
+use crate::config::{ThemeConfig, validate_config};
+use crate::gamut_map::{Gamut, gamut_map_to};
+use crate::generate::generate_for_variant;
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::srgb_to_hex;
+use crate::report::ValidationReport;
+use crate::validation::validate_with_config;
+
+fn to_json(value: &impl Serialize) -> Result<String, JsError> {
+    serde_json::to_string(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn parse_config(source: &str) -> Result<ThemeConfig, JsError> {
+    let config: ThemeConfig = toml::from_str(source).map_err(|e| JsError::new(&e.to_string()))?;
+    validate_config(&config).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(config)
+}
+
+fn validation_report(scheme: &Base16Scheme, config: &ThemeConfig) -> ValidationReport {
+    ValidationReport::new(&validate_with_config(
+        scheme,
+        &[],
+        &[],
+        config.contrast.standard,
+        &config.validation,
+    ))
+}
+
+/// Generate a scheme from a configuration in the config file's TOML format.
+///
+/// Returns `{ scheme, warnings, validation }` as JSON, where `scheme` is the
+/// CLI's JSON output and `validation` the report `validate` returns.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::wasm::generate;
+///
+/// let theme = generate("[colors]\nbackground = \"#1a1a2e\"\nforeground = \"#eaeaea\"").unwrap();
+/// let theme: serde_json::Value = serde_json::from_str(&theme).unwrap();
+/// assert_eq!(theme["scheme"]["palette"]["base00"], "1a1a2e");
+/// ```
+#[wasm_bindgen]
+pub fn generate(config_toml: &str) -> Result<String, JsError> {
+    let config = parse_config(config_toml)?;
+    let variant = forced_variant(config.theme.variant.as_deref())
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    let generate_config = config
+        .to_generate_config()
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    let result = generate_for_variant(&generate_config, variant);
+    to_json(&json!({
+        "validation": validation_report(&result.scheme, &config),
+        "scheme": result.scheme,
+        "warnings": result.warnings,
+    }))
+}
+
+/// Check a scheme's contrast.
+///
+/// `scheme` is a Base16/Base24 scheme as JSON or YAML (such as `generate`'s
+/// `scheme`). Returns `{ standard, required, required_passed, checks }` as
+/// JSON, with required checks first.
+#[wasm_bindgen]
+pub fn validate(scheme: &str) -> Result<String, JsError> {
+    let scheme: Base16Scheme =
+        serde_yaml::from_str(scheme).map_err(|e| JsError::new(&e.to_string()))?;
+    to_json(&validation_report(&scheme, &ThemeConfig::default()))
+}
+
+/// Map a Hellwig JMh color into `gamut` (`srgb`, `display-p3`, or
+/// `rec2020`), reducing colorfulness at constant lightness and hue.
+///
+/// Returns `{ lightness, colorfulness, hue, hex }` as JSON; `hex` is the
+/// mapped color's sRGB value.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::wasm::gamut_map;
+///
+/// let mapped: serde_json::Value =
+///     serde_json::from_str(&gamut_map(60.0, 120.0, 145.0, "srgb").unwrap()).unwrap();
+/// assert!(mapped["colorfulness"].as_f64().unwrap() < 120.0);
+/// assert_eq!(mapped["hue"], 145.0);
+/// ```
+#[wasm_bindgen]
+pub fn gamut_map(
+    lightness: f32,
+    colorfulness: f32,
+    hue: f32,
+    gamut: &str,
+) -> Result<String, JsError> {
+    let gamut: Gamut = serde_json::from_value(gamut.into())
+        .map_err(|_| JsError::new(&format!("unknown gamut '{gamut}'")))?;
+    let mapped = gamut_map_to(HellwigJmh::new(lightness, colorfulness, hue), gamut);
+    let srgb: Srgb<u8> = mapped.into_srgb_u8_gamut_mapped();
+    to_json(&json!({
+        "lightness": mapped.lightness,
+        "colorfulness": mapped.colorfulness,
+        "hue": mapped.hue,
+        "hex": format!("#{}", srgb_to_hex(srgb)),
+    }))
+}
@@ -0,0 +1,74 @@
+//! File watching for `--watch`: re-run generation when the config changes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{DebounceEventResult, new_debouncer};
+use tracing::{debug, warn};
+
+/// How long the file must stay quiet before a change is acted on.
+///
+/// Editors often write a file in several steps (truncate, write, rename);
+/// this collapses them into one rebuild.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Block forever, calling `on_change` each time the contents of `path` change.
+///
+/// The parent directory is watched rather than the file itself, so editors
+/// that save by renaming a temporary file over the original are still seen.
+/// Only returns if the watcher cannot be set up or its channel closes.
+pub fn watch_file(path: &Path, debounce: Duration, mut on_change: impl FnMut()) -> Result<()> {
+    let target = absolute(path)?;
+    let dir = target
+        .parent()
+        .ok_or_else(|| eyre!("{} has no parent directory", target.display()))?;
+
+    let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+    let mut debouncer = new_debouncer(debounce, tx).wrap_err("Failed to start file watcher")?;
+    debouncer
+        .watcher()
+        .watch(dir, RecursiveMode::NonRecursive)
+        .wrap_err_with(|| format!("Failed to watch {}", dir.display()))?;
+
+    // Events also fire when the file is merely read (including by
+    // `on_change` itself), so only act when the contents differ
+    let mut last = fs::read(&target).ok();
+    for result in rx {
+        match result {
+            Ok(events) => {
+                if !events.iter().any(|e| e.path == target) {
+                    continue;
+                }
+                let current = fs::read(&target).ok();
+                if current != last {
+                    debug!(path = %target.display(), "watched file changed");
+                    last = current;
+                    on_change();
+                }
+            }
+            Err(e) => warn!(error = %e, "file watcher error"),
+        }
+    }
+
+    Err(eyre!("file watcher stopped unexpectedly"))
+}
+
+/// Absolute form of `path`, canonicalizing the directory (which must exist)
+/// so it matches the paths reported by the watcher.
+fn absolute(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| eyre!("{} is not a file path", path.display()))?;
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let dir = dir
+        .canonicalize()
+        .wrap_err_with(|| format!("Failed to resolve {}", dir.display()))?;
+    Ok(dir.join(file_name))
+}
@@ -0,0 +1,89 @@
+//! WCAG 2.x contrast ratio.
+//!
+//! Implements the WCAG 2.1 relative luminance and contrast ratio
+//! definitions, with the AA/AAA success-criterion thresholds for normal and
+//! large text (1.4.3 and 1.4.6).
+
+use std::fmt;
+
+use palette::Srgb;
+
+/// Rec. 709 luminance coefficients used by WCAG 2.x.
+const COEF_R: f64 = 0.2126;
+const COEF_G: f64 = 0.7152;
+const COEF_B: f64 = 0.0722;
+
+/// Flare term added to both luminances in the ratio.
+const FLARE: f64 = 0.05;
+
+/// Text size category, which selects the threshold at each level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSize {
+    /// Body text
+    Normal,
+    /// 18pt regular / 14pt bold and larger
+    Large,
+}
+
+/// WCAG conformance level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WcagLevel {
+    /// Level AA (4.5:1 normal, 3:1 large)
+    Aa,
+    /// Level AAA (7:1 normal, 4.5:1 large)
+    Aaa,
+}
+
+impl WcagLevel {
+    /// Minimum contrast ratio for text of the given size at this level.
+    pub fn min_ratio(self, size: TextSize) -> f64 {
+        match (self, size) {
+            (WcagLevel::Aa, TextSize::Normal) => 4.5,
+            (WcagLevel::Aa, TextSize::Large) => 3.0,
+            (WcagLevel::Aaa, TextSize::Normal) => 7.0,
+            (WcagLevel::Aaa, TextSize::Large) => 4.5,
+        }
+    }
+
+    /// Highest level a ratio meets for text of the given size.
+    pub fn achieved(ratio: f64, size: TextSize) -> Option<Self> {
+        [WcagLevel::Aaa, WcagLevel::Aa]
+            .into_iter()
+            .find(|level| ratio >= level.min_ratio(size))
+    }
+}
+
+impl fmt::Display for WcagLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WcagLevel::Aa => "AA",
+            WcagLevel::Aaa => "AAA",
+        })
+    }
+}
+
+/// WCAG 2.x relative luminance of an sRGB color (0.0-1.0).
+pub fn relative_luminance(color: Srgb<u8>) -> f64 {
+    let lin = color.into_format::<f64>().into_linear();
+    COEF_R * lin.red + COEF_G * lin.green + COEF_B * lin.blue
+}
+
+/// WCAG 2.x contrast ratio between two colors (1.0-21.0, order-independent).
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::wcag::contrast_ratio;
+///
+/// let black = Srgb::new(0u8, 0, 0);
+/// let white = Srgb::new(255u8, 255, 255);
+///
+/// assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-9);
+/// assert!((contrast_ratio(white, white) - 1.0).abs() < 1e-9);
+/// ```
+pub fn contrast_ratio(fg: Srgb<u8>, bg: Srgb<u8>) -> f64 {
+    let a = relative_luminance(fg);
+    let b = relative_luminance(bg);
+    (a.max(b) + FLARE) / (a.min(b) + FLARE)
+}
@@ -0,0 +1,163 @@
+//! SCSS and Tailwind output (`--format scss`, `--format tailwind`).
+//!
+//! Both carry the sRGB hex value of every slot under its lowercase name
+//! (`base00` ... `base17`), with `--var-prefix` prepended. Roles and the
+//! ANSI mapping follow as extra variables: in SCSS they refer to the slot
+//! variables, as the CSS output does; Tailwind colors are plain values, so
+//! there they repeat the slot's hex. Extended surfaces (`surface-0` ...)
+//! come last, as hex values in both. Wide-gamut values are only written to
+//! CSS.
+
+use std::fmt::Write;
+
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+use crate::ansi::AnsiMap;
+use crate::roles::SemanticRoles;
+use crate::surfaces::SurfaceRamp;
+
+/// Every slot as `(lowercase name, hex)`, in slot order.
+fn slot_hexes(scheme: &Base16Scheme) -> Vec<(String, String)> {
+    let mut slots: Vec<(String, String)> = scheme
+        .palette
+        .iter()
+        .map(|(slot, color)| {
+            let (r, g, b) = color.rgb;
+            (slot.to_lowercase(), format!("#{r:02x}{g:02x}{b:02x}"))
+        })
+        .collect();
+    slots.sort();
+    slots
+}
+
+/// `name (variant)`, as the first comment line.
+fn title(scheme: &Base16Scheme) -> String {
+    let variant = match scheme.variant {
+        SchemeVariant::Dark => "dark",
+        SchemeVariant::Light => "light",
+        _ => "other",
+    };
+    format!("{} ({variant})", scheme.name)
+}
+
+/// Render a scheme as SCSS variables (`$base00` ... `$base17`), with
+/// `prefix` after the `$`, followed by `roles` and `ansi` variables that
+/// refer to them and the `surfaces` shades.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::web_vars::to_scss;
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let scss = to_scss(&scheme, None, None, None, "theme-");
+///
+/// assert!(scss.contains("$theme-base00: #"));
+/// ```
+pub fn to_scss(
+    scheme: &Base16Scheme,
+    roles: Option<&SemanticRoles>,
+    ansi: Option<&AnsiMap>,
+    surfaces: Option<&SurfaceRamp>,
+    prefix: &str,
+) -> String {
+    let mut scss = String::new();
+    let _ = writeln!(scss, "// {}", title(scheme));
+    for (slot, hex) in slot_hexes(scheme) {
+        let _ = writeln!(scss, "${prefix}{slot}: {hex};");
+    }
+    if let Some(roles) = roles {
+        scss.push('\n');
+        for role in &roles.roles {
+            let _ = writeln!(
+                scss,
+                "${prefix}{}: ${prefix}{};",
+                role.name.replace('_', "-"),
+                role.slot.to_lowercase()
+            );
+        }
+    }
+    if let Some(ansi) = ansi {
+        scss.push('\n');
+        for (index, slot) in ansi.slots.iter().enumerate() {
+            let _ = writeln!(
+                scss,
+                "${prefix}ansi-{index}: ${prefix}{};",
+                slot.to_lowercase()
+            );
+        }
+    }
+    if let Some(surfaces) = surfaces {
+        scss.push('\n');
+        for (name, hex) in surfaces.named(prefix) {
+            let _ = writeln!(scss, "${name}: {hex};");
+        }
+    }
+    scss
+}
+
+/// Render a scheme as a Tailwind config (usable as a preset) that extends
+/// the theme's colors with every slot, plus `roles`, `ansi`, and `surfaces`
+/// colors, each named with `prefix`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::web_vars::to_tailwind;
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let js = to_tailwind(&scheme, None, None, None, "");
+///
+/// assert!(js.contains("module.exports = {"));
+/// assert!(js.contains("        \"base0d\": \"#"));
+/// ```
+pub fn to_tailwind(
+    scheme: &Base16Scheme,
+    roles: Option<&SemanticRoles>,
+    ansi: Option<&AnsiMap>,
+    surfaces: Option<&SurfaceRamp>,
+    prefix: &str,
+) -> String {
+    let slots = slot_hexes(scheme);
+    let mut colors: Vec<(String, String)> = slots
+        .iter()
+        .map(|(slot, hex)| (format!("{prefix}{slot}"), hex.clone()))
+        .collect();
+    if let Some(roles) = roles {
+        colors.extend(roles.roles.iter().map(|role| {
+            (
+                format!("{prefix}{}", role.name.replace('_', "-")),
+                format!("#{}", role.hex.to_lowercase()),
+            )
+        }));
+    }
+    if let Some(ansi) = ansi {
+        let hex_of = |slot: &str| {
+            slots
+                .iter()
+                .find(|(s, _)| s.eq_ignore_ascii_case(slot))
+                .map(|(_, hex)| hex.clone())
+        };
+        colors.extend(
+            ansi.slots
+                .iter()
+                .enumerate()
+                .filter_map(|(index, slot)| Some((format!("{prefix}ansi-{index}"), hex_of(slot)?))),
+        );
+    }
+    if let Some(surfaces) = surfaces {
+        colors.extend(surfaces.named(prefix));
+    }
+
+    let mut js = String::new();
+    let _ = writeln!(js, "// {}", title(scheme));
+    js.push_str("/** @type {import('tailwindcss').Config} */\n");
+    js.push_str("module.exports = {\n  theme: {\n    extend: {\n      colors: {\n");
+    for (name, hex) in &colors {
+        let _ = writeln!(js, "        \"{name}\": \"{hex}\",");
+    }
+    js.push_str("      },\n    },\n  },\n};\n");
+    js
+}
@@ -0,0 +1,102 @@
+//! Wide-gamut palette values and CSS output.
+//!
+//! Hex colors in a scheme are always sRGB. When accents are optimized for a
+//! wider gamut (Display-P3, Rec.2020), the generator also records every slot
+//! as float channels in that gamut; `to_css` emits them as CSS `color()`
+//! values behind an `@supports` check, with the hex colors as the fallback.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+use crate::gamut_map::Gamut;
+
+/// Every palette slot as encoded RGB channels in `gamut`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WidePalette {
+    /// Gamut the channels are expressed in
+    pub gamut: Gamut,
+    /// Slot name ("base00"-"base17") to encoded RGB (0.0-1.0)
+    pub colors: BTreeMap<String, [f32; 3]>,
+}
+
+impl WidePalette {
+    /// CSS `color()` value for a slot, e.g. `color(display-p3 0.8215 0.2867 0.3021)`.
+    pub fn css_color(&self, slot: &str) -> Option<String> {
+        let [r, g, b] = self.colors.get(slot)?;
+        Some(format!(
+            "color({} {:.4} {:.4} {:.4})",
+            self.gamut.css_name(),
+            r.clamp(0.0, 1.0),
+            g.clamp(0.0, 1.0),
+            b.clamp(0.0, 1.0)
+        ))
+    }
+}
+
+/// Render a scheme as CSS custom properties (`--base00` ... `--base17`),
+/// with `prefix` inserted after the dashes.
+///
+/// Hex values are always emitted. A non-sRGB `wide` palette adds an
+/// `@supports` block overriding each slot with its wide-gamut value.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::gamut_map::Gamut;
+/// use themalingadingdong::wide_gamut::to_css;
+///
+/// let config = GenerateConfig {
+///     gamut: Gamut::DisplayP3,
+///     ..Default::default()
+/// };
+/// let result = generate(&config);
+/// let css = to_css(&result.scheme, Some(&result.wide_palette), "");
+///
+/// assert!(css.contains("--base00: #"));
+/// assert!(css.contains("@supports (color: color(display-p3 0 0 0))"));
+/// ```
+pub fn to_css(scheme: &Base16Scheme, wide: Option<&WidePalette>, prefix: &str) -> String {
+    let mut slots: Vec<(&String, String)> = scheme
+        .palette
+        .iter()
+        .map(|(slot, color)| {
+            let (r, g, b) = color.rgb;
+            (slot, format!("#{r:02x}{g:02x}{b:02x}"))
+        })
+        .collect();
+    slots.sort_by_key(|(slot, _)| slot.to_lowercase());
+
+    let variant = match scheme.variant {
+        SchemeVariant::Dark => "dark",
+        SchemeVariant::Light => "light",
+        _ => "other",
+    };
+
+    let mut css = String::new();
+    let _ = writeln!(css, "/* {} ({variant}) */", scheme.name);
+    css.push_str(":root {\n");
+    for (slot, hex) in &slots {
+        let _ = writeln!(css, "  --{prefix}{}: {hex};", slot.to_lowercase());
+    }
+    css.push_str("}\n");
+
+    if let Some(wide) = wide.filter(|w| !w.gamut.is_srgb()) {
+        let _ = writeln!(
+            css,
+            "\n@supports (color: color({} 0 0 0)) {{",
+            wide.gamut.css_name()
+        );
+        css.push_str("  :root {\n");
+        for (slot, _) in &slots {
+            if let Some(value) = wide.css_color(slot) {
+                let _ = writeln!(css, "    --{prefix}{}: {value};", slot.to_lowercase());
+            }
+        }
+        css.push_str("  }\n}\n");
+    }
+
+    css
+}
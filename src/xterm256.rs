@@ -0,0 +1,341 @@
+//! xterm 256-color approximations for truecolor palettes.
+//!
+//! Maps each palette slot to the nearest index in the fixed part of the xterm
+//! palette (the 6x6x6 cube, 16-231, and the grayscale ramp, 232-255) using
+//! `HellwigJmh::delta_e`. Indices 0-15 are skipped there: terminals let
+//! themes redefine them, so they make no reliable fallback.
+//!
+//! For tools limited to the 16 ANSI colors, each slot is also matched
+//! against xterm's default values for those indices. That mapping is only
+//! as accurate as the terminal's palette is close to the xterm defaults.
+//!
+//! Matching slots one at a time can swap the lightness order of two slots
+//! (base02 ending up lighter than base03) or map distinct slots onto one
+//! index. [`Quantization::Joint`] instead picks the indices for all slots
+//! together, trading a little per-slot error for an order-preserving
+//! assignment.
+
+use std::sync::LazyLock;
+
+use palette::Srgb;
+use serde::{Deserialize, Serialize};
+use tinted_builder::Base16Scheme;
+
+use crate::hellwig::HellwigJmh;
+use crate::interpolation::srgb_to_hex;
+use crate::wcag::relative_luminance;
+
+/// Channel levels of the 6x6x6 color cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// sRGB value of an xterm palette index (16-255).
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::xterm256::xterm_color;
+///
+/// assert_eq!(xterm_color(196), Srgb::new(255, 0, 0));
+/// assert_eq!(xterm_color(232), Srgb::new(8, 8, 8));
+/// ```
+pub fn xterm_color(index: u8) -> Srgb<u8> {
+    assert!(index >= 16, "indices 0-15 are theme-defined");
+    if index >= 232 {
+        let v = 8 + 10 * (index - 232);
+        return Srgb::new(v, v, v);
+    }
+    let i = index - 16;
+    Srgb::new(
+        CUBE_LEVELS[(i / 36) as usize],
+        CUBE_LEVELS[(i / 6 % 6) as usize],
+        CUBE_LEVELS[(i % 6) as usize],
+    )
+}
+
+/// xterm's default values for the 16 ANSI colors (indices 0-15).
+const ANSI16_DEFAULTS: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// xterm's default sRGB value for an ANSI color index (0-15).
+///
+/// # Example
+///
+/// ```
+/// use palette::Srgb;
+/// use themalingadingdong::xterm256::ansi16_color;
+///
+/// assert_eq!(ansi16_color(1), Srgb::new(205, 0, 0));
+/// assert_eq!(ansi16_color(12), Srgb::new(92, 92, 255));
+/// ```
+pub fn ansi16_color(index: u8) -> Srgb<u8> {
+    let (r, g, b) = ANSI16_DEFAULTS[index as usize];
+    Srgb::new(r, g, b)
+}
+
+/// HellwigJmh values of indices 16-255, computed once.
+static XTERM_JMH: LazyLock<Vec<(u8, HellwigJmh)>> = LazyLock::new(|| {
+    (16..=255u8)
+        .map(|i| (i, HellwigJmh::from_srgb_u8(xterm_color(i))))
+        .collect()
+});
+
+/// Nearest xterm index (16-255) to `color`, with its ΔE.
+pub fn nearest_xterm(color: Srgb<u8>) -> (u8, f32) {
+    nearest_in(&XTERM_JMH, color)
+}
+
+/// HellwigJmh values of the default ANSI colors, computed once.
+static ANSI16_JMH: LazyLock<Vec<(u8, HellwigJmh)>> = LazyLock::new(|| {
+    (0..16u8)
+        .map(|i| (i, HellwigJmh::from_srgb_u8(ansi16_color(i))))
+        .collect()
+});
+
+/// Nearest ANSI color index (0-15, xterm defaults) to `color`, with its ΔE.
+pub fn nearest_ansi16(color: Srgb<u8>) -> (u8, f32) {
+    nearest_in(&ANSI16_JMH, color)
+}
+
+/// How slots are mapped onto a limited palette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Quantization {
+    /// Each slot independently to its nearest index
+    #[default]
+    Nearest,
+    /// All slots together, keeping their luminance order and keeping
+    /// distinct slots on distinct indices where the palette allows
+    Joint,
+}
+
+impl Quantization {
+    /// Whether this is the default, for skipping it when serializing.
+    pub fn is_nearest(&self) -> bool {
+        *self == Quantization::Nearest
+    }
+}
+
+/// Candidate indices considered per slot by the joint quantizer.
+const JOINT_CANDIDATES: usize = 8;
+
+/// Cost, in ΔE units, of two slots swapping their luminance order.
+const INVERSION_PENALTY: f32 = 8.0;
+
+/// Cost, in ΔE units, of two distinct slots sharing an index.
+const MERGE_PENALTY: f32 = 4.0;
+
+/// Maximum improvement passes over the slots.
+const JOINT_PASSES: usize = 32;
+
+/// Indices for `colors` from `table` minimizing the summed ΔE plus
+/// penalties for luminance-order inversions and merged slots, with each
+/// index's ΔE.
+///
+/// Starts from the nearest-neighbor assignment and repeatedly moves single
+/// slots to one of their nearest candidates while that lowers the total, so
+/// the result is never worse than nearest-neighbor by that measure.
+fn joint_in(table: &[(u8, HellwigJmh)], colors: &[Srgb<u8>]) -> Vec<(u8, f32)> {
+    let palette: Vec<Srgb<u8>> = table
+        .iter()
+        .map(|(i, _)| {
+            if *i < 16 {
+                ansi16_color(*i)
+            } else {
+                xterm_color(*i)
+            }
+        })
+        .collect();
+    // (table position, ΔE) of the nearest candidates, nearest first
+    let candidates: Vec<Vec<(usize, f32)>> = colors
+        .iter()
+        .map(|color| {
+            let target = HellwigJmh::from_srgb_u8(*color);
+            let mut ranked: Vec<(usize, f32)> = table
+                .iter()
+                .enumerate()
+                .map(|(pos, (_, candidate))| (pos, target.delta_e(candidate)))
+                .collect();
+            ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+            ranked.truncate(JOINT_CANDIDATES);
+            ranked
+        })
+        .collect();
+    let luminance: Vec<f64> = colors.iter().map(|c| relative_luminance(*c)).collect();
+    let palette_luminance: Vec<f64> = palette.iter().map(|c| relative_luminance(*c)).collect();
+
+    // Penalty between slot i at table position p and slot j at position q
+    let pair_cost = |i: usize, p: usize, j: usize, q: usize| {
+        if colors[i] == colors[j] {
+            return 0.0;
+        }
+        if p == q {
+            return MERGE_PENALTY;
+        }
+        let before = luminance[i].total_cmp(&luminance[j]);
+        let after = palette_luminance[p].total_cmp(&palette_luminance[q]);
+        if before.is_ne() && after.is_ne() && before != after {
+            INVERSION_PENALTY
+        } else {
+            0.0
+        }
+    };
+
+    let mut chosen: Vec<usize> = vec![0; colors.len()];
+    for _ in 0..JOINT_PASSES {
+        let mut improved = false;
+        for i in 0..colors.len() {
+            let cost_of = |k: usize| {
+                let (p, delta_e) = candidates[i][k];
+                delta_e
+                    + (0..colors.len())
+                        .filter(|&j| j != i)
+                        .map(|j| pair_cost(i, p, j, candidates[j][chosen[j]].0))
+                        .sum::<f32>()
+            };
+            let current = cost_of(chosen[i]);
+            let (best, best_cost) = (0..candidates[i].len())
+                .map(|k| (k, cost_of(k)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .expect("non-empty candidates");
+            if best_cost < current - 1e-4 {
+                chosen[i] = best;
+                improved = true;
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    chosen
+        .iter()
+        .enumerate()
+        .map(|(i, &k)| {
+            let (p, delta_e) = candidates[i][k];
+            (table[p].0, delta_e)
+        })
+        .collect()
+}
+
+fn nearest_in(table: &[(u8, HellwigJmh)], color: Srgb<u8>) -> (u8, f32) {
+    let target = HellwigJmh::from_srgb_u8(color);
+    table
+        .iter()
+        .map(|(i, candidate)| (*i, target.delta_e(candidate)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("non-empty table")
+}
+
+/// One row of the approximation table.
+#[derive(Debug, Clone, Serialize)]
+pub struct Xterm256Entry {
+    /// Palette slot (e.g. "base08")
+    pub slot: String,
+    /// Truecolor value (hex, no `#`)
+    pub hex: String,
+    /// Nearest xterm-256 index
+    pub index: u8,
+    /// Color of that index (hex, no `#`)
+    pub approx_hex: String,
+    /// Color difference between the two
+    pub delta_e: f32,
+    /// Nearest ANSI color index (0-15, xterm defaults)
+    pub ansi16: u8,
+    /// Default color of that ANSI index (hex, no `#`)
+    pub ansi16_hex: String,
+    /// Color difference to the ANSI color
+    pub ansi16_delta_e: f32,
+}
+
+/// Build the approximation table for every slot in the scheme, in slot order.
+pub fn approximation_table(scheme: &Base16Scheme) -> Vec<Xterm256Entry> {
+    approximation_table_with(scheme, Quantization::Nearest)
+}
+
+/// Build the approximation table, mapping slots as `quantization` says.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::generate::{GenerateConfig, generate};
+/// use themalingadingdong::xterm256::{Quantization, approximation_table_with};
+///
+/// let scheme = generate(&GenerateConfig::default()).scheme;
+/// let table = approximation_table_with(&scheme, Quantization::Joint);
+///
+/// assert_eq!(table.len(), 24);
+/// assert!(table.iter().all(|e| e.index >= 16 && e.ansi16 < 16));
+/// ```
+pub fn approximation_table_with(
+    scheme: &Base16Scheme,
+    quantization: Quantization,
+) -> Vec<Xterm256Entry> {
+    let mut slots: Vec<&String> = scheme.palette.keys().collect();
+    slots.sort_by_key(|s| s.to_lowercase());
+    let colors: Vec<Srgb<u8>> = slots
+        .iter()
+        .map(|slot| {
+            let (r, g, b) = scheme.palette[*slot].rgb;
+            Srgb::new(r, g, b)
+        })
+        .collect();
+    let (xterm, ansi16): (Vec<_>, Vec<_>) = match quantization {
+        Quantization::Nearest => (
+            colors.iter().map(|c| nearest_xterm(*c)).collect(),
+            colors.iter().map(|c| nearest_ansi16(*c)).collect(),
+        ),
+        Quantization::Joint => (
+            joint_in(&XTERM_JMH, &colors),
+            joint_in(&ANSI16_JMH, &colors),
+        ),
+    };
+
+    slots
+        .into_iter()
+        .zip(colors)
+        .zip(xterm.into_iter().zip(ansi16))
+        .map(
+            |((slot, color), ((index, delta_e), (ansi16, ansi16_delta_e)))| Xterm256Entry {
+                slot: slot.clone(),
+                hex: srgb_to_hex(color),
+                index,
+                approx_hex: srgb_to_hex(xterm_color(index)),
+                delta_e,
+                ansi16,
+                ansi16_hex: srgb_to_hex(ansi16_color(ansi16)),
+                ansi16_delta_e,
+            },
+        )
+        .collect()
+}
+
+/// Render the table as a YAML/shell-style `#` comment block.
+pub fn comment_block(table: &[Xterm256Entry]) -> String {
+    let mut out = String::from(
+        "# xterm-256 approximations (slot: index #approx, delta E | ANSI-16 index #approx, delta E)\n",
+    );
+    for e in table {
+        out.push_str(&format!(
+            "# {}: {:3} #{} ({:4.1}) | {:2} #{} ({:4.1})\n",
+            e.slot, e.index, e.approx_hex, e.delta_e, e.ansi16, e.ansi16_hex, e.ansi16_delta_e
+        ));
+    }
+    out
+}
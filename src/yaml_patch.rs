@@ -0,0 +1,303 @@
+//! Re-exporting an imported YAML scheme with its comments kept.
+//!
+//! Serializing a scheme from scratch drops the comments, key order, and
+//! quoting of the file it was imported from, so a one-color edit rewrites
+//! the whole file. [`patch_scheme_yaml`] instead edits the original text
+//! line by line: only values that changed are rewritten (in the quoting,
+//! `#` prefix, and hex case they had), slots the original lacks are added
+//! after its last palette entry, and every other line is left as it was.
+//!
+//! The editor understands the block layout tinted-theming schemes use. A
+//! file it can't edit safely (flow mappings, multi-line strings, the legacy
+//! Base16 layout), or an edit that wouldn't read back as the same scheme,
+//! yields `None` and the caller serializes normally.
+
+use serde_yaml::{Mapping, Value};
+use tinted_builder::Base16Scheme;
+
+/// Top-level keys of a scheme, besides `palette`.
+const SCHEME_KEYS: [&str; 6] = ["system", "name", "slug", "author", "description", "variant"];
+
+/// One `key: value  # comment` line.
+struct Entry<'a> {
+    indent: &'a str,
+    key: &'a str,
+    /// Text after `key:` up to the value
+    gap: &'a str,
+    /// The value as written, quotes included
+    raw: &'a str,
+    /// Whitespace and comment after the value
+    tail: &'a str,
+}
+
+impl<'a> Entry<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let body = line.trim_start_matches(' ');
+        let indent = &line[..line.len() - body.len()];
+        let colon = body.find(':')?;
+        let key = &body[..colon];
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return None;
+        }
+        let after = &body[colon + 1..];
+        if !(after.is_empty() || after.starts_with([' ', '\t'])) {
+            return None;
+        }
+        let value = after.trim_start();
+        let gap = &after[..after.len() - value.len()];
+        let end = value_end(value)?;
+        Some(Self {
+            indent,
+            key,
+            gap,
+            raw: &value[..end],
+            tail: &value[end..],
+        })
+    }
+
+    /// The value as a string, or `None` for anything but a plain scalar.
+    fn scalar(&self) -> Option<String> {
+        if self.raw.starts_with(['|', '>', '&', '*', '!', '[', '{']) {
+            return None;
+        }
+        match serde_yaml::from_str::<Value>(self.raw).ok()? {
+            Value::String(s) => Some(s),
+            Value::Null => Some(String::new()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        }
+    }
+
+    fn with_raw(&self, raw: &str) -> String {
+        format!("{}{}:{}{raw}{}", self.indent, self.key, self.gap, self.tail)
+    }
+}
+
+/// Length of the value at the start of `text`, before any comment.
+fn value_end(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    match bytes.first() {
+        Some(b'"') => {
+            let mut i = 1;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'\\' => i += 2,
+                    b'"' => return Some(i + 1),
+                    _ => i += 1,
+                }
+            }
+            None
+        }
+        Some(b'\'') => {
+            let mut i = 1;
+            while i < bytes.len() {
+                if bytes[i] == b'\'' {
+                    if bytes.get(i + 1) == Some(&b'\'') {
+                        i += 2;
+                        continue;
+                    }
+                    return Some(i + 1);
+                }
+                i += 1;
+            }
+            None
+        }
+        Some(b'#') | None => Some(0),
+        Some(_) => {
+            let end = text
+                .find(" #")
+                .or_else(|| text.find("\t#"))
+                .unwrap_or(text.len());
+            Some(text[..end].trim_end().len())
+        }
+    }
+}
+
+/// `value` written in the quoting of `like`.
+fn quote_like(like: &str, value: &str) -> String {
+    match like.chars().next() {
+        Some('"') => serde_json::to_string(value).unwrap_or_default(),
+        Some('\'') => single_quoted(value),
+        _ => plain_or_quoted(value),
+    }
+}
+
+fn single_quoted(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// `value` unquoted when it reads back as the same string.
+fn plain_or_quoted(value: &str) -> String {
+    let reads_back =
+        serde_yaml::from_str::<Value>(value).is_ok_and(|v| v == Value::String(value.to_string()));
+    if reads_back && !value.contains(" #") && value.trim() == value {
+        value.to_string()
+    } else {
+        single_quoted(value)
+    }
+}
+
+/// Hex color `hex` (six digits, no `#`) written like the color `like`.
+fn color_like(like: &str, hex: &str) -> String {
+    let unquoted = like.trim_matches(['"', '\'']);
+    let digits = unquoted.trim_start_matches('#');
+    let upper = digits.chars().any(|c| c.is_ascii_uppercase())
+        && !digits.chars().any(|c| c.is_ascii_lowercase());
+    let hex = if upper {
+        hex.to_ascii_uppercase()
+    } else {
+        hex.to_ascii_lowercase()
+    };
+    let color = if unquoted.starts_with('#') {
+        format!("#{hex}")
+    } else {
+        hex
+    };
+    match like.chars().next() {
+        Some(quote @ ('"' | '\'')) => format!("{quote}{color}{quote}"),
+        _ => color,
+    }
+}
+
+/// Same color, ignoring `#` and case.
+fn same_color(a: &str, b: &str) -> bool {
+    a.trim_start_matches('#')
+        .eq_ignore_ascii_case(b.trim_start_matches('#'))
+}
+
+fn string_entries(mapping: &Mapping) -> Vec<(String, String)> {
+    mapping
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.as_str()?.to_string())))
+        .collect()
+}
+
+/// `original` (the YAML `scheme` was imported from) edited to hold `scheme`,
+/// with its comments, key order, and quoting kept.
+///
+/// Returns `None` when `original` isn't a block-style tinted-theming scheme
+/// or the edited text wouldn't read back as `scheme`.
+///
+/// # Example
+///
+/// ```
+/// use themalingadingdong::yaml_patch::patch_scheme_yaml;
+/// use tinted_builder::Base16Scheme;
+///
+/// let mut original = String::from(
+///     "# Upstream header\nsystem: \"base16\"\nname: \"Demo\"\nauthor: \"me\"\nvariant: \"dark\"\npalette:\n",
+/// );
+/// for i in 0..16 {
+///     original += &format!("  base{i:02X}: \"#1D2021\" # slot {i}\n");
+/// }
+/// let mut scheme: Base16Scheme = serde_yaml::from_str(&original).unwrap();
+/// scheme.name = "Demo Edited".to_string();
+///
+/// let patched = patch_scheme_yaml(&original, &scheme).unwrap();
+/// assert!(patched.starts_with("# Upstream header\n"));
+/// assert!(patched.contains("\nname: \"Demo Edited\"\n"));
+/// assert!(patched.contains("  base00: \"#1D2021\" # slot 0\n"));
+/// ```
+pub fn patch_scheme_yaml(original: &str, scheme: &Base16Scheme) -> Option<String> {
+    let Value::Mapping(target) = serde_yaml::to_value(scheme).ok()? else {
+        return None;
+    };
+    let fields = string_entries(&target);
+    let palette = match target.get("palette")? {
+        Value::Mapping(palette) => string_entries(palette),
+        _ => return None,
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut seen_fields = Vec::new();
+    let mut seen_slots = Vec::new();
+    // Index in `lines` of the palette header and of its last entry, with
+    // that entry's indent and value for styling added slots
+    let mut palette_line = None;
+    let mut last_slot: Option<(usize, String, String)> = None;
+    let mut in_palette = false;
+
+    for line in original.lines() {
+        let trimmed = line.trim_start();
+        // Comments, blank lines, and the document start stay as they are
+        if trimmed.is_empty() || trimmed.starts_with('#') || line == "---" {
+            lines.push(line.to_string());
+            continue;
+        }
+        let entry = Entry::parse(line)?;
+        if entry.indent.is_empty() {
+            in_palette = entry.key == "palette";
+            if in_palette {
+                if !entry.raw.is_empty() {
+                    return None;
+                }
+                palette_line = Some(lines.len());
+                lines.push(line.to_string());
+                continue;
+            }
+            if !SCHEME_KEYS.contains(&entry.key) {
+                lines.push(line.to_string());
+                continue;
+            }
+            seen_fields.push(entry.key.to_string());
+            match fields.iter().find(|(key, _)| key == entry.key) {
+                // Dropped from the scheme
+                None => {}
+                Some((_, value)) => {
+                    let old = entry.scalar()?;
+                    if old == *value {
+                        lines.push(line.to_string());
+                    } else {
+                        lines.push(entry.with_raw(&quote_like(entry.raw, value)));
+                    }
+                }
+            }
+        } else if in_palette {
+            seen_slots.push(entry.key.to_string());
+            let Some((_, hex)) = palette.iter().find(|(slot, _)| slot == entry.key) else {
+                continue;
+            };
+            let old = entry.scalar()?;
+            let raw = if same_color(&old, hex) {
+                entry.raw.to_string()
+            } else {
+                color_like(entry.raw, hex)
+            };
+            lines.push(entry.with_raw(&raw));
+            last_slot = Some((
+                lines.len() - 1,
+                entry.indent.to_string(),
+                entry.raw.to_string(),
+            ));
+        } else {
+            // Nested under a key the scheme doesn't have
+            lines.push(line.to_string());
+        }
+    }
+
+    let palette_line = palette_line?;
+    let (last_index, indent, style) = last_slot?;
+
+    // New slots go after the last palette entry, in the scheme's order
+    let added: Vec<String> = palette
+        .iter()
+        .filter(|(slot, _)| !seen_slots.contains(slot))
+        .map(|(slot, hex)| format!("{indent}{slot}: {}", color_like(&style, hex)))
+        .collect();
+    lines.splice(last_index + 1..last_index + 1, added);
+
+    // New fields go before the palette
+    let added: Vec<String> = fields
+        .iter()
+        .filter(|(key, _)| SCHEME_KEYS.contains(&key.as_str()) && !seen_fields.contains(key))
+        .map(|(key, value)| format!("{key}: {}", plain_or_quoted(value)))
+        .collect();
+    lines.splice(palette_line..palette_line, added);
+
+    let mut patched = lines.join("\n");
+    patched.push('\n');
+
+    let read_back: Base16Scheme = serde_yaml::from_str(&patched).ok()?;
+    (serde_yaml::to_value(&read_back).ok()? == Value::Mapping(target)).then_some(patched)
+}
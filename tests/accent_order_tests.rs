@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use themalingadingdong::accent_order::match_accent_order;
+use themalingadingdong::config::ThemeConfig;
+use themalingadingdong::generate::generate_for_variant;
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+fn config() -> ThemeConfig {
+    let mut config = ThemeConfig::default();
+    config.colors.background = Some("#1d2021".to_string());
+    config.colors.foreground = Some("#ebdbb2".to_string());
+    config
+}
+
+fn generate(config: &ThemeConfig) -> Base16Scheme {
+    generate_for_variant(
+        &config.to_generate_config().unwrap(),
+        Some(SchemeVariant::Dark),
+    )
+    .scheme
+}
+
+/// `scheme` with the colors of `a` and `b` swapped.
+fn swapped(scheme: &Base16Scheme, a: &str, b: &str) -> Base16Scheme {
+    let mut swapped = scheme.clone();
+    swapped
+        .palette
+        .insert(a.to_string(), scheme.palette[b].clone());
+    swapped
+        .palette
+        .insert(b.to_string(), scheme.palette[a].clone());
+    swapped
+}
+
+#[test]
+fn test_reorders_accents_to_match_reference() {
+    let mut config = config();
+    let original = generate(&config);
+    let reference = swapped(&original, "base08", "base0D");
+
+    let order = match_accent_order(&mut config, &reference, &[Some(SchemeVariant::Dark)]).unwrap();
+    assert_eq!(order.order, [5, 1, 2, 3, 4, 0, 6, 7]);
+    assert!(order.delta_e < order.original_delta_e);
+    assert!(
+        order
+            .to_string()
+            .starts_with("base08 <- base0D, base0D <- base08")
+    );
+
+    let matched = generate(&config);
+    for slot in ["base08", "base09", "base0D"] {
+        assert_eq!(
+            matched.palette[slot].rgb, reference.palette[slot].rgb,
+            "{slot}"
+        );
+    }
+}
+
+#[test]
+fn test_pinned_accents_keep_their_slot() {
+    let mut config = config();
+    let original = generate(&config);
+    let reference = swapped(&swapped(&original, "base08", "base0D"), "base0B", "base0E");
+    config.colors.pins = Some(BTreeMap::from([(
+        "base08".to_string(),
+        format!("#{}", original.palette["base08"].to_hex()),
+    )]));
+
+    let order = match_accent_order(&mut config, &reference, &[Some(SchemeVariant::Dark)]).unwrap();
+    assert_eq!(order.order[0], 0);
+    assert_eq!(order.order[3], 6);
+    assert_eq!(order.order[6], 3);
+}
@@ -1,8 +1,14 @@
-//! Tests for COBYLA-based accent solver edge cases.
+//! Tests for accent solver edge cases.
 
 use palette::Srgb;
 use themalingadingdong::accent_solver::optimize_accents;
-use themalingadingdong::config::AccentOptSettings;
+use themalingadingdong::config::{
+    AccentOptSettings, JointOptimization, SolverMethod, SolverSettings, ThemeConfig,
+    validate_config,
+};
+use themalingadingdong::cvd::{CvdKind, CvdSeparation, simulate};
+use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::interpolation::srgb_to_u8;
 
 #[test]
 fn infeasible_high_contrast_produces_warning() {
@@ -17,6 +23,12 @@ fn infeasible_high_contrast_produces_warning() {
         delta_m: 5.0,
         j_weight: 0.5,
         contrast_weight: 0.8,
+        preferred_contrast: None,
+        cvd: None,
+        anchors: Default::default(),
+        solver: Default::default(),
+        joint: None,
+        semantic: None,
     };
 
     let result = optimize_accents(bg, &hues, &settings, 100.0); // Lc 100 is impossible
@@ -52,6 +64,12 @@ fn high_contrast_dark_theme_meets_target() {
         delta_m: 15.0,
         j_weight: 0.7,
         contrast_weight: 0.8,
+        preferred_contrast: None,
+        cvd: None,
+        anchors: Default::default(),
+        solver: Default::default(),
+        joint: None,
+        semantic: None,
     };
 
     let result = optimize_accents(bg, &hues, &settings, 60.0);
@@ -83,6 +101,12 @@ fn high_contrast_light_theme_meets_target() {
         delta_m: 15.0,
         j_weight: 0.7,
         contrast_weight: 0.8,
+        preferred_contrast: None,
+        cvd: None,
+        anchors: Default::default(),
+        solver: Default::default(),
+        joint: None,
+        semantic: None,
     };
 
     let result = optimize_accents(bg, &hues, &settings, 60.0);
@@ -115,6 +139,12 @@ fn j_weight_affects_uniformity() {
         delta_m: 20.0,
         j_weight: 0.9,
         contrast_weight: 0.8,
+        preferred_contrast: None,
+        cvd: None,
+        anchors: Default::default(),
+        solver: Default::default(),
+        joint: None,
+        semantic: None,
     };
     let uniform_result = optimize_accents(bg, &hues, &uniform_settings, 45.0);
 
@@ -126,6 +156,12 @@ fn j_weight_affects_uniformity() {
         delta_m: 20.0,
         j_weight: 0.1,
         contrast_weight: 0.8,
+        preferred_contrast: None,
+        cvd: None,
+        anchors: Default::default(),
+        solver: Default::default(),
+        joint: None,
+        semantic: None,
     };
     let vibrant_result = optimize_accents(bg, &hues, &vibrant_settings, 45.0);
 
@@ -149,3 +185,270 @@ fn variance(values: &[f32]) -> f32 {
     let mean = values.iter().sum::<f32>() / values.len() as f32;
     values.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / values.len() as f32
 }
+
+#[test]
+fn saturated_contrast_optimizes_uniformity() {
+    // Near-black background: every point in the box clears Lc 30 by a wide
+    // margin, so a pure-contrast objective would be flat
+    let bg = Srgb::new(4u8, 4, 6);
+    let hues = [60.0, 180.0, 250.0];
+    let settings = AccentOptSettings {
+        target_j: 75.0,
+        target_m: 25.0,
+        delta_j: 10.0,
+        delta_m: 10.0,
+        j_weight: 0.5,
+        contrast_weight: 1.0,
+        preferred_contrast: None,
+        cvd: None,
+        anchors: Default::default(),
+        solver: Default::default(),
+        joint: None,
+        semantic: None,
+    };
+
+    let result = optimize_accents(bg, &hues, &settings, 30.0);
+
+    for hr in &result.hue_results {
+        assert!(hr.saturated, "Hue {:.0} should be saturated", hr.hue);
+        assert!(hr.achieved_contrast >= 30.0);
+        assert!(
+            (hr.j - settings.target_j).abs() < 1.0,
+            "Hue {:.0}: J' {:.1} should settle on target",
+            hr.hue,
+            hr.j
+        );
+    }
+}
+
+#[test]
+fn tight_contrast_is_not_saturated() {
+    let bg = Srgb::new(128u8, 128, 128);
+    let settings = AccentOptSettings::default();
+
+    let result = optimize_accents(bg, &[25.0, 145.0], &settings, 60.0);
+
+    assert!(result.hue_results.iter().all(|hr| !hr.saturated));
+}
+
+#[test]
+fn cvd_separation_pushes_confusable_pair_apart() {
+    let bg = Srgb::new(26u8, 26, 46);
+    let hues = [25.0, 145.0]; // Red and green collapse under deuteranopia
+    let settings = AccentOptSettings {
+        delta_j: 10.0,
+        delta_m: 10.0,
+        ..AccentOptSettings::default()
+    };
+    let separated = AccentOptSettings {
+        cvd: Some(CvdSeparation {
+            min_delta_e: 15.0,
+            protanopia: 0.0,
+            deuteranopia: 1.0,
+            tritanopia: 0.0,
+        }),
+        ..settings.clone()
+    };
+
+    let deuteranopia_delta_e = |settings: &AccentOptSettings| {
+        let result = optimize_accents(bg, &hues, settings, 60.0);
+        let [a, b] = [0, 1].map(|i| {
+            let color = srgb_to_u8(result.hue_results[i].color);
+            HellwigJmh::from_srgb_u8(simulate(color, CvdKind::Deuteranopia))
+        });
+        (a.delta_e(&b), result)
+    };
+
+    let (before, plain) = deuteranopia_delta_e(&settings);
+    let (after, result) = deuteranopia_delta_e(&separated);
+
+    assert!(plain.hue_results.iter().all(|hr| hr.cvd_delta_e.is_none()));
+    assert!(
+        after > before + 1.0,
+        "deuteranopia ΔE should grow: {before:.1} -> {after:.1}"
+    );
+    for hr in &result.hue_results {
+        assert!(hr.met_constraints, "Hue {:.0} left its box", hr.hue);
+        assert!((hr.cvd_delta_e.unwrap() - after).abs() < 0.01);
+        // The 15 ΔE target is out of reach inside the box
+        assert!(hr.warning.as_ref().unwrap().contains("deuteranopia"));
+    }
+}
+
+#[test]
+fn every_solver_meets_constraints() {
+    let bg = Srgb::new(26u8, 26, 46);
+    let hues = [25.0, 60.0, 120.0, 180.0, 240.0, 285.0, 320.0, 350.0];
+    let cobyla = optimize_accents(bg, &hues, &AccentOptSettings::default(), 60.0);
+    for method in [SolverMethod::NelderMead, SolverMethod::Grid] {
+        let settings = AccentOptSettings {
+            solver: SolverSettings {
+                method,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = optimize_accents(bg, &hues, &settings, 60.0);
+        for (r, c) in result.hue_results.iter().zip(&cobyla.hue_results) {
+            assert!(
+                r.met_constraints,
+                "{method} at hue {}: {:?}",
+                r.hue, r.warning
+            );
+            assert!(r.achieved_contrast >= 59.5, "{method}: {r:?}");
+            // Same box, so close to COBYLA's answer
+            assert!((r.j - c.j).abs() < 2.0, "{method}: J' {} vs {}", r.j, c.j);
+        }
+    }
+}
+
+#[test]
+fn solver_config() {
+    let mut config: ThemeConfig = toml::from_str(
+        r##"
+[colors]
+background = "#1d2021"
+foreground = "#ebdbb2"
+
+[optimization.solver]
+method = "nelder-mead"
+max_iters = 50
+rhoend = 1e-4
+
+[optimization.joint]
+group_weight = 2.0
+"##,
+    )
+    .unwrap();
+    assert!(validate_config(&config).is_ok());
+    let solver = config.optimization.solver;
+    assert_eq!(solver.method, SolverMethod::NelderMead);
+    assert_eq!(solver.max_iters(), 50);
+    assert_eq!(SolverSettings::default().max_iters(), 200);
+    assert_eq!(
+        config.optimization.joint,
+        Some(JointOptimization { group_weight: 2.0 })
+    );
+
+    // Defaults are left out of saved configs
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("solver"), "{saved}");
+
+    config.optimization.solver.rhoend = Some(0.0);
+    assert!(validate_config(&config).is_err());
+    config.optimization.solver.rhoend = None;
+    config.extended_optimization.solver.max_iters = Some(0);
+    assert!(validate_config(&config).is_err());
+    config.extended_optimization.solver.max_iters = None;
+    config.optimization.joint = Some(JointOptimization { group_weight: -1.0 });
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn joint_optimization_evens_lightness() {
+    let bg = Srgb::new(26u8, 26, 46);
+    let hues = [25.0, 60.0, 120.0, 180.0, 240.0, 285.0, 320.0, 350.0];
+    // Colorfulness priority lets each accent's J' drift on its own
+    let independent = AccentOptSettings {
+        delta_j: 15.0,
+        delta_m: 20.0,
+        j_weight: 0.1,
+        ..Default::default()
+    };
+    let joint = AccentOptSettings {
+        joint: Some(JointOptimization { group_weight: 5.0 }),
+        ..independent.clone()
+    };
+
+    let spread = |settings: &AccentOptSettings| {
+        let result = optimize_accents(bg, &hues, settings, 45.0);
+        assert!(result.hue_results.iter().all(|r| r.met_constraints));
+        let js: Vec<f32> = result.hue_results.iter().map(|r| r.j).collect();
+        variance(&js)
+    };
+    let (before, after) = (spread(&independent), spread(&joint));
+    assert!(
+        after < before * 0.5,
+        "J' variance {before:.2} -> {after:.2}"
+    );
+}
+
+#[test]
+fn preferred_contrast_raises_contrast() {
+    let bg = Srgb::new(26u8, 26, 46);
+    let hues = [25.0, 145.0, 250.0];
+    let plain = AccentOptSettings::default();
+    let preferring = AccentOptSettings {
+        preferred_contrast: Some(90.0),
+        ..plain.clone()
+    };
+
+    let plain = optimize_accents(bg, &hues, &plain, 45.0);
+    let preferring = optimize_accents(bg, &hues, &preferring, 45.0);
+    for (a, b) in plain.hue_results.iter().zip(&preferring.hue_results) {
+        assert!(b.met_constraints);
+        assert!(
+            b.achieved_contrast >= a.achieved_contrast - 0.5,
+            "Hue {:.0}: Lc {:.1} -> {:.1}",
+            a.hue,
+            a.achieved_contrast,
+            b.achieved_contrast
+        );
+    }
+    let total = |r: &themalingadingdong::accent_solver::AccentOptResult| {
+        r.hue_results
+            .iter()
+            .map(|hr| hr.achieved_contrast)
+            .sum::<f64>()
+    };
+    assert!(total(&preferring) > total(&plain));
+}
+
+#[test]
+fn preferred_contrast_must_exceed_minimum() {
+    let mut config = ThemeConfig::default();
+    config.colors.background = Some("#1d2021".to_string());
+    config.colors.foreground = Some("#ebdbb2".to_string());
+    config.optimization.preferred_contrast = Some(config.contrast.minimum + 10.0);
+    assert!(validate_config(&config).is_ok());
+    config.optimization.preferred_contrast = Some(config.contrast.minimum);
+    assert!(validate_config(&config).is_err());
+    config.optimization.preferred_contrast = None;
+    config.extended_optimization.preferred_contrast = Some(30.0);
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn changed_hue_is_the_only_one_resolved() {
+    // A background no other test uses, so earlier solutions can't be cached
+    let bg = Srgb::new(31u8, 29, 47);
+    let settings = AccentOptSettings::default();
+    let mut hues = [25.0, 60.0, 120.0, 180.0, 240.0, 285.0, 320.0, 350.0];
+    let first = optimize_accents(bg, &hues, &settings, 60.0);
+    assert_eq!(first.cached_hues, 0);
+
+    hues[3] = 190.0;
+    let second = optimize_accents(bg, &hues, &settings, 60.0);
+    assert_eq!(second.cached_hues, 7);
+    for (i, (a, b)) in first
+        .hue_results
+        .iter()
+        .zip(&second.hue_results)
+        .enumerate()
+    {
+        if i != 3 {
+            assert_eq!((a.j, a.m), (b.j, b.m), "hue {}", a.hue);
+        }
+    }
+    assert_eq!(second.hue_results[3].hue, 190.0);
+}
+
+#[test]
+fn changed_min_contrast_resolves_every_hue() {
+    let bg = Srgb::new(33u8, 27, 41);
+    let settings = AccentOptSettings::default();
+    let hues = [25.0, 120.0, 240.0];
+    optimize_accents(bg, &hues, &settings, 60.0);
+    assert_eq!(optimize_accents(bg, &hues, &settings, 60.0).cached_hues, 3);
+    assert_eq!(optimize_accents(bg, &hues, &settings, 65.0).cached_hues, 0);
+}
@@ -0,0 +1,119 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::animate::{css_keyframes, interpolate_schemes};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::hellwig_lightness;
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+fn night() -> Base16Scheme {
+    generate(&GenerateConfig {
+        name: "Night".to_string(),
+        ..Default::default()
+    })
+    .scheme
+}
+
+fn day() -> Base16Scheme {
+    generate(&GenerateConfig {
+        name: "Day".to_string(),
+        background: Srgb::new(250, 248, 240),
+        foreground: Srgb::new(40, 40, 48),
+        ..Default::default()
+    })
+    .scheme
+}
+
+fn lightness(scheme: &Base16Scheme, slot: &str) -> f32 {
+    let (r, g, b) = scheme.palette[slot].rgb;
+    hellwig_lightness(Srgb::new(r, g, b))
+}
+
+#[test]
+fn test_frames_step_lightness_evenly() {
+    let (night, day) = (night(), day());
+    let frames = interpolate_schemes(&night, &day, 5).unwrap();
+
+    assert_eq!(frames.len(), 5);
+    for (slot, color) in &night.palette {
+        assert_eq!(frames[0].palette[slot].rgb, color.rgb, "{slot}");
+        assert_eq!(frames[4].palette[slot].rgb, day.palette[slot].rgb, "{slot}");
+    }
+
+    let steps: Vec<f32> = frames
+        .windows(2)
+        .map(|pair| lightness(&pair[1], "base00") - lightness(&pair[0], "base00"))
+        .collect();
+    let expected = (lightness(&day, "base00") - lightness(&night, "base00")) / 4.0;
+    for step in steps {
+        assert!((step - expected).abs() < 1.5, "{step} vs {expected}");
+    }
+
+    assert!(matches!(frames[0].variant, SchemeVariant::Dark));
+    assert!(matches!(frames[4].variant, SchemeVariant::Light));
+    assert_eq!(
+        frames[2].name,
+        format!("{} to {} (3 of 5)", night.name, day.name)
+    );
+    assert!(frames[2].slug.ends_with("-3"), "{}", frames[2].slug);
+}
+
+#[test]
+fn test_mismatched_systems_are_rejected() {
+    let night = night();
+    let mut base16 = day();
+    base16.palette.retain(|slot, _| slot.as_str() < "base10");
+
+    let err = interpolate_schemes(&night, &base16, 3).unwrap_err();
+    assert!(err.to_string().contains("base10"), "{err}");
+    assert!(interpolate_schemes(&night, &night, 1).is_err());
+}
+
+#[test]
+fn test_css_keyframes() {
+    let frames = interpolate_schemes(&night(), &day(), 4).unwrap();
+    let css = css_keyframes(&frames, "night-to-day");
+
+    assert!(css.contains("@property --base00 {"));
+    assert!(css.contains("@keyframes night-to-day {"));
+    for selector in ["  0% {", "  33.333% {", "  66.667% {", "  100% {"] {
+        assert!(css.contains(selector), "missing {selector} in\n{css}");
+    }
+    assert_eq!(css.matches("--base0d:").count(), 4);
+}
+
+#[test]
+fn test_animate_command_writes_frames() {
+    let dir =
+        std::env::temp_dir().join(format!("themalingadingdong-animate-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (from, to) = (dir.join("night.yaml"), dir.join("day.yaml"));
+    std::fs::write(&from, serde_yaml::to_string(&night()).unwrap()).unwrap();
+    std::fs::write(&to, serde_yaml::to_string(&day()).unwrap()).unwrap();
+
+    let frames = dir.join("frames");
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("animate")
+        .arg(&from)
+        .arg(&to)
+        .args(["--steps", "3", "--output-dir"])
+        .arg(&frames)
+        .assert()
+        .success();
+    let mut written: Vec<_> = std::fs::read_dir(&frames)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    written.sort();
+    assert_eq!(written.len(), 3);
+    assert!(written[0].ends_with("-1.yaml"), "{written:?}");
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("animate")
+        .arg(&from)
+        .arg(&to)
+        .args(["--steps", "1"])
+        .assert()
+        .failure();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
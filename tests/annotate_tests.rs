@@ -0,0 +1,92 @@
+//! Tests for per-slot diagnostic comments (`--annotate`).
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use themalingadingdong::annotate::slot_notes;
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::generate::{GenerateConfig, generate_for_variant};
+use themalingadingdong::provenance::{Provenance, reproduce};
+use themalingadingdong::report::SolverReport;
+
+#[test]
+fn test_cli_annotates_every_palette_entry() {
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Annotated"])
+        .args(["--min-contrast", "80", "--annotate"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let yaml = String::from_utf8(output.stdout).unwrap();
+
+    let entries: Vec<&str> = yaml
+        .lines()
+        .filter(|l| l.trim_start().starts_with("base"))
+        .collect();
+    assert_eq!(entries.len(), 24);
+    assert!(
+        entries
+            .iter()
+            .all(|l| l.contains("  # ") && l.contains("J' "))
+    );
+    // base00 is the reference, so it has no Lc; the UI ramp has no solver
+    assert!(!entries[0].contains("Lc"));
+    assert!(entries[5].contains("Lc ") && !entries[5].contains("relaxed"));
+    // An unreachable floor is reported on the accents that miss it
+    assert!(
+        entries[8..16]
+            .iter()
+            .any(|l| l.contains("below contrast floor"))
+    );
+    assert!(
+        entries[8..]
+            .iter()
+            .all(|l| l.contains("constraints met") || l.contains("relaxed: "))
+    );
+
+    // The comments leave the scheme as it was
+    let plain = cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Annotated"])
+        .args(["--min-contrast", "80"])
+        .output()
+        .unwrap();
+    let read = |s: &[u8]| serde_yaml::from_slice::<serde_yaml::Value>(s).unwrap();
+    assert_eq!(read(yaml.as_bytes()), read(&plain.stdout));
+}
+
+#[test]
+fn test_notes_follow_solver_diagnostics() {
+    let result = generate_for_variant(&GenerateConfig::default(), None);
+    let solver = SolverReport::new(&result);
+    let notes = slot_notes(&result.scheme, Some(&solver));
+    assert_eq!(notes.len(), result.scheme.palette.len());
+
+    for note in &notes {
+        let accent = solver.accents.iter().find(|a| a.slot == note.slot);
+        assert_eq!(note.relaxed.is_some(), accent.is_some(), "{}", note.slot);
+        if let (Some(accent), Some(relaxed)) = (accent, &note.relaxed) {
+            assert_eq!(
+                relaxed.iter().any(|r| r == "below contrast floor"),
+                !accent.met_minimum
+            );
+            assert!((note.lc.unwrap().abs() - accent.contrast.abs()).abs() < 1.0);
+        }
+    }
+    assert!(notes[0].comment().starts_with("J' "));
+}
+
+#[test]
+fn test_annotate_needs_yaml_and_reproduces() {
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Annotated"])
+        .args(["--annotate", "--format", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--annotate requires YAML output"));
+
+    let mut provenance = Provenance::new(&GenerateConfig::default(), None, OutputFormat::Yaml);
+    provenance.annotate = true;
+    let output = provenance.regenerate().unwrap();
+    assert!(output.contains("  # Lc "));
+    assert!(Provenance::extract(&output).unwrap().annotate);
+    assert!(reproduce(&output).unwrap().is_identical());
+}
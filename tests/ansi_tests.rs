@@ -0,0 +1,158 @@
+//! Tests for the ANSI accent mapping and hue bands.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::ansi::{ACCENTS, AnsiConfig, AnsiMap, HueBand, semantic_warnings};
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::config::{ThemeConfig, validate_config};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use tinted_builder::Base16Scheme;
+
+#[test]
+fn test_accents_map_to_ansi_colors() {
+    let normal: Vec<_> = ACCENTS.iter().filter_map(|a| a.normal).collect();
+    assert_eq!(normal, [1, 3, 2, 6, 4, 5]);
+
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let map = AnsiMap::new(&scheme);
+    for accent in &ACCENTS {
+        if let (Some(normal), Some((bright, slot))) = (accent.normal, accent.bright) {
+            assert_eq!(map.slots[usize::from(normal)], accent.slot);
+            assert_eq!(map.slots[usize::from(bright)], slot);
+        }
+    }
+}
+
+#[test]
+fn test_default_hues_keep_their_meaning() {
+    for (bg, fg) in [
+        (Srgb::new(0x1a, 0x1a, 0x2e), Srgb::new(0xea, 0xea, 0xea)),
+        (Srgb::new(0x1d, 0x20, 0x21), Srgb::new(0xeb, 0xdb, 0xb2)),
+        (Srgb::new(0xfa, 0xf4, 0xed), Srgb::new(0x28, 0x24, 0x30)),
+        (Srgb::new(0x0a, 0x2a, 0x1a), Srgb::new(0xe0, 0xf0, 0xe8)),
+    ] {
+        let result = generate(&GenerateConfig {
+            background: bg,
+            foreground: fg,
+            ..GenerateConfig::default()
+        });
+        let warnings = semantic_warnings(&result.scheme, &AnsiConfig::default());
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+}
+
+#[test]
+fn test_override_breaking_semantics_warns() {
+    let mut config = GenerateConfig::default();
+    config.hue_overrides[0] = Some(190.0);
+    let result = generate(&config);
+    let warning = result
+        .warnings
+        .iter()
+        .find(|w| w.starts_with("base08 (red, ANSI 1/9)"))
+        .expect("base08 warning");
+    assert!(warning.contains("will not look red"), "{warning}");
+
+    // A band that admits the override silences it
+    config
+        .ansi
+        .bands
+        .insert("red".into(), HueBand::new(170.0, 210.0));
+    let result = generate(&config);
+    assert!(!result.warnings.iter().any(|w| w.starts_with("base08")));
+
+    // Accents without an ANSI color are only checked when given a band
+    let mut config = GenerateConfig::default();
+    config.hue_overrides[1] = Some(250.0);
+    assert!(
+        !generate(&config)
+            .warnings
+            .iter()
+            .any(|w| w.contains("orange"))
+    );
+    config
+        .ansi
+        .bands
+        .insert("orange".into(), HueBand::new(40.0, 70.0));
+    assert!(
+        generate(&config)
+            .warnings
+            .iter()
+            .any(|w| w.contains("orange"))
+    );
+}
+
+#[test]
+fn test_ansi_config_validation() {
+    let config: ThemeConfig = toml::from_str(
+        r##"
+[colors]
+background = "#1a1a2e"
+foreground = "#eaeaea"
+
+[ansi.bands]
+green = { from = 100.0, to = 180.0 }
+"##,
+    )
+    .unwrap();
+    let ansi = config.ansi.clone().unwrap();
+    assert_eq!(ansi.band("green"), Some(HueBand::new(100.0, 180.0)));
+    assert_eq!(ansi.band("red"), AnsiConfig::default().band("red"));
+    assert!(validate_config(&config).is_ok());
+
+    let mut bad = config.clone();
+    bad.ansi = Some(AnsiConfig {
+        bands: [("teal".to_string(), HueBand::new(170.0, 200.0))].into(),
+    });
+    let err = validate_config(&bad).unwrap_err().to_string();
+    assert!(err.contains("'teal' is not an accent"), "{err}");
+
+    bad.ansi = Some(AnsiConfig {
+        bands: [("green".to_string(), HueBand::new(100.0, 400.0))].into(),
+    });
+    let err = validate_config(&bad).unwrap_err().to_string();
+    assert!(err.contains("ansi.bands.green.to"), "{err}");
+}
+
+#[test]
+fn test_ansi_section_in_output() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let map = AnsiMap::new(&scheme);
+    let yaml = OutputFormat::Yaml
+        .serialize_with(&scheme, None, None, Some(&map), None)
+        .unwrap();
+    assert!(
+        yaml.contains("\nansi:\n  0: base00\n  1: base08\n"),
+        "{yaml}"
+    );
+    assert!(yaml.contains("  9: base12\n"));
+    let parsed: Base16Scheme = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(parsed.palette.len(), scheme.palette.len());
+
+    let css = OutputFormat::Css
+        .serialize_with(&scheme, None, None, Some(&map), None)
+        .unwrap();
+    assert!(css.contains("--ansi-1: var(--base08);"));
+
+    let json: serde_json::Value = serde_json::from_slice(
+        &cargo_bin_cmd!("themalingadingdong")
+            .args([
+                "-b",
+                "#1a1a2e",
+                "-f",
+                "#eaeaea",
+                "--name",
+                "Mapped",
+                "--format",
+                "json",
+                "--ansi-map",
+            ])
+            .assert()
+            .success()
+            .get_output()
+            .stdout,
+    )
+    .unwrap();
+    assert_eq!(json["ansi"]["2"], "base0B");
+    assert_eq!(json["ansi"]["10"], "base14");
+}
@@ -1,5 +1,5 @@
 use palette::Srgb;
-use themalingadingdong::apca::apca_contrast;
+use themalingadingdong::apca::{apca_contrast, min_font_size, text_usage, thresholds};
 
 #[test]
 fn test_mid_gray_on_white() {
@@ -57,3 +57,23 @@ fn test_colored_text() {
     // Red on white should have decent contrast
     assert!(lc > 30.0, "Red on white should be readable");
 }
+
+#[test]
+fn test_text_usage_picks_most_demanding_threshold() {
+    assert_eq!(text_usage(75.0), Some(thresholds::BODY_TEXT_MIN));
+    assert_eq!(text_usage(-74.9), Some(thresholds::CONTENT_TEXT));
+    assert_eq!(text_usage(45.0), Some(thresholds::LARGE_TEXT));
+    assert_eq!(text_usage(30.0), Some(thresholds::SPOT_TEXT));
+    assert_eq!(text_usage(29.9), None);
+}
+
+#[test]
+fn test_min_font_size_lookup() {
+    assert_eq!(min_font_size(90.0, 400), Some(16.0));
+    // Rounded down to the row below
+    assert_eq!(min_font_size(-89.9, 400), Some(16.5));
+    // Weights round to the nearest hundred
+    assert_eq!(min_font_size(45.0, 650), Some(24.0));
+    assert_eq!(min_font_size(30.0, 100), None);
+    assert_eq!(min_font_size(150.0, 400), Some(14.5));
+}
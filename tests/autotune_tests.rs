@@ -0,0 +1,38 @@
+//! Tests for contrast-target autotuning.
+
+use themalingadingdong::autotune::{CONTRAST_TOLERANCE, max_feasible_contrast};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use tinted_builder::SchemeVariant;
+
+#[test]
+fn test_ceiling_is_feasible_and_tight() {
+    let config = GenerateConfig::default();
+    let ceiling = max_feasible_contrast(&config, &[None]);
+    let min_contrast = ceiling.min_contrast.unwrap();
+    assert!(ceiling.extended_min_contrast.is_some());
+
+    let tuned = generate(&ceiling.apply(&config));
+    assert!(tuned.base_accent_results.iter().all(|r| r.met_minimum));
+    assert!(tuned.extended_accent_results.iter().all(|r| r.met_minimum));
+
+    // Just past the ceiling, some accent falls short
+    let over = generate(&GenerateConfig {
+        min_contrast: min_contrast + CONTRAST_TOLERANCE + 0.1,
+        ..config.clone()
+    });
+    assert!(over.base_accent_results.iter().any(|r| !r.met_minimum));
+}
+
+#[test]
+fn test_ceiling_over_several_variants_is_the_lowest() {
+    let config = GenerateConfig::default();
+    let dark = max_feasible_contrast(&config, &[Some(SchemeVariant::Dark)]);
+    let light = max_feasible_contrast(&config, &[Some(SchemeVariant::Light)]);
+    let both = max_feasible_contrast(
+        &config,
+        &[Some(SchemeVariant::Dark), Some(SchemeVariant::Light)],
+    );
+
+    let lowest = dark.min_contrast.unwrap().min(light.min_contrast.unwrap());
+    assert!((both.min_contrast.unwrap() - lowest).abs() <= CONTRAST_TOLERANCE);
+}
@@ -0,0 +1,100 @@
+//! Tests for the ThemeBuilder embedding API.
+
+use palette::Srgb;
+use themalingadingdong::builder::ThemeBuilder;
+use themalingadingdong::config::ConfigError;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::validation::ContrastStandard;
+use tinted_builder::SchemeVariant;
+
+#[test]
+fn builder_matches_direct_generation() {
+    let theme = ThemeBuilder::new()
+        .name("Direct")
+        .background("#1a1a2e")
+        .foreground("#eaeaea")
+        .min_contrast(70.0)
+        .build()
+        .unwrap();
+
+    let direct = generate(&GenerateConfig {
+        name: "Direct".to_string(),
+        min_contrast: 70.0,
+        ..Default::default()
+    })
+    .scheme;
+
+    assert_eq!(theme.scheme().palette.len(), direct.palette.len());
+    for (slot, color) in &direct.palette {
+        let (r, g, b) = color.rgb;
+        assert_eq!(theme.color(slot), Some(Srgb::new(r, g, b)), "{slot}");
+    }
+}
+
+#[test]
+fn hue_override_moves_accent() {
+    let theme = ThemeBuilder::new()
+        .background("#000000")
+        .foreground("#ffffff")
+        .hue_override(3, 300.0)
+        .build()
+        .unwrap();
+
+    assert_eq!(theme.config().hue_overrides[3], Some(300.0));
+    let hue = HellwigJmh::from_srgb_u8(theme.accents()[3]).hue;
+    assert!((hue - 300.0).abs() < 15.0, "hue {hue}");
+}
+
+#[test]
+fn accessors_cover_all_slots() {
+    let theme = ThemeBuilder::new()
+        .variant(SchemeVariant::Light)
+        .contrast_standard(ContrastStandard::Both)
+        .build()
+        .unwrap();
+
+    assert!(!theme.is_dark());
+    assert_eq!(theme.background(), theme.ui()[0]);
+    assert_eq!(theme.foreground(), theme.color("base07").unwrap());
+    assert_eq!(theme.color("base0a"), theme.color("base0A"));
+    assert_eq!(theme.extended_accents()[7], theme.color("base17").unwrap());
+    assert_eq!(theme.validation().standard, ContrastStandard::Both);
+    assert!(theme.color("base18").is_none());
+}
+
+#[test]
+fn invalid_input_is_reported_by_build() {
+    let err = ThemeBuilder::new().background("not a color").build();
+    assert!(matches!(err, Err(ConfigError::InvalidColor(_))));
+
+    let err = ThemeBuilder::new().hue_override(8, 10.0).build();
+    assert!(matches!(err, Err(ConfigError::InvalidValue(_))));
+
+    let err = ThemeBuilder::new().comment_contrast(60.0, 40.0).build();
+    assert!(matches!(err, Err(ConfigError::InvalidValue(_))));
+}
+
+#[test]
+fn from_config_keeps_settings() {
+    let config = GenerateConfig {
+        background: Srgb::new(10, 20, 30),
+        ..Default::default()
+    };
+    let theme = ThemeBuilder::from_config(config).build().unwrap();
+    assert_eq!(theme.hex("base00").as_deref(), Some("0a141e"));
+}
+
+#[test]
+fn pin_holds_slot_color() {
+    let theme = ThemeBuilder::new()
+        .background("#1d2021")
+        .foreground("#ebdbb2")
+        .pin("base0D", "#82aaff")
+        .build()
+        .unwrap();
+    assert_eq!(theme.hex("base0D").as_deref(), Some("82aaff"));
+
+    let err = ThemeBuilder::new().pin("base24", "#82aaff").build();
+    assert!(matches!(err, Err(ConfigError::InvalidValue(_))));
+}
@@ -0,0 +1,123 @@
+use themalingadingdong::config_schema::{check_config, json_schema};
+
+#[test]
+fn test_example_config_is_clean() {
+    let source = include_str!("../example.toml");
+    assert_eq!(check_config(source), vec![]);
+}
+
+#[test]
+fn test_reports_every_problem_with_position() {
+    let source = r##"
+[colors]
+background = "#1a1a2e"
+foregrund = "#e0e0e0"
+gamut = "p4"
+
+[colors.pins]
+base0D = "notacolor"
+base0d = "#82aaff"
+
+[contrast]
+target = 120
+comment = { min = 40, max = 55 }
+
+[curves.lightness]
+type = "sigmoid"
+strength = "strong"
+
+[profile.hc.contrast]
+extnded_minimum = 70
+"##;
+    let diagnostics = check_config(source);
+    let summary: Vec<(usize, &str)> = diagnostics
+        .iter()
+        .map(|d| (d.line, d.key.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (4, "colors.foregrund"),
+            (5, "colors.gamut"),
+            (8, "colors.pins.base0D"),
+            (9, "colors.pins.base0d"),
+            (12, "contrast.target"),
+            (17, "curves.lightness.strength"),
+            (20, "profile.hc.contrast.extnded_minimum"),
+        ]
+    );
+
+    assert!(diagnostics[0].message.contains("did you mean 'foreground'"));
+    assert!(diagnostics[1].message.contains("did you mean 'p3'"));
+    assert_eq!(diagnostics[4].column, 10);
+    assert!(diagnostics[4].message.contains("between 0 and 108"));
+    assert_eq!(diagnostics[5].message, "expected a number, found a string");
+    assert!(
+        diagnostics[6]
+            .message
+            .contains("did you mean 'extended_minimum'")
+    );
+}
+
+#[test]
+fn test_manual_curve_stops_are_checked() {
+    let diagnostics =
+        check_config("[curves.hue]\ntype = \"manual\"\nstops = [0, 0.2, 0.4, 1.2, 1]\n");
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec!["expected 8 stops (got 5)", "expected a number from 0 to 1"]
+    );
+    assert_eq!(diagnostics[1].column, 23);
+}
+
+#[test]
+fn test_syntax_errors_are_located() {
+    let diagnostics = check_config("[colors]\nbackground = \n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 2);
+    assert!(diagnostics[0].key.is_empty());
+}
+
+#[test]
+fn test_load_config_reports_all_diagnostics() {
+    use themalingadingdong::config::{ConfigOverrides, load_config};
+
+    let path = std::env::temp_dir().join(format!(
+        "themalingadingdong-schema-{}.toml",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        "[colors]\nbackround = \"#000000\"\n\n[ramp]\nbase02 = 200\n",
+    )
+    .unwrap();
+
+    let err = load_config(Some(&path), &ConfigOverrides::default())
+        .unwrap_err()
+        .to_string();
+    let lines: Vec<&str> = err.lines().collect();
+    assert_eq!(lines.len(), 2, "{err}");
+    assert!(lines[0].ends_with(":2:1: colors.backround: unknown key (did you mean 'background'?)"));
+    assert!(lines[1].contains(":5:10: ramp.base02: must be in (0, 106] (got 200)"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_json_schema_matches_checker() {
+    let schema = json_schema();
+    let colors = &schema["properties"]["colors"];
+    assert_eq!(colors["additionalProperties"], false);
+    assert_eq!(
+        colors["properties"]["pins"]["propertyNames"]["pattern"],
+        "^base(0[0-9A-F]|1[0-7])$"
+    );
+
+    // Aliases and profiles are described too
+    let contrast = &schema["properties"]["contrast"]["properties"];
+    assert_eq!(contrast["target"]["maximum"], 108.0);
+    let profile = &schema["properties"]["profile"]["additionalProperties"];
+    assert!(profile["properties"]["contrast"].is_object());
+    assert!(profile["properties"]["profile"].is_null());
+}
@@ -40,3 +40,357 @@ fn test_hue_overrides_roundtrip() {
     assert_eq!(restored.base0d, Some(220.0));
     assert_eq!(restored.base09, None);
 }
+
+#[test]
+fn test_comment_contrast_range_validation() {
+    use themalingadingdong::config::{CommentContrast, validate_config};
+
+    let toml_str = r##"
+[colors]
+background = "#1a1a2e"
+foreground = "#eaeaea"
+
+[contrast.comment]
+min = 40.0
+max = 55.0
+"##;
+
+    let mut config: ThemeConfig = toml::from_str(toml_str).unwrap();
+    assert_eq!(
+        config.contrast.comment,
+        Some(CommentContrast {
+            min: 40.0,
+            max: 55.0
+        })
+    );
+    assert!(validate_config(&config).is_ok());
+
+    config.contrast.comment = Some(CommentContrast {
+        min: 60.0,
+        max: 45.0,
+    });
+    assert!(validate_config(&config).is_err());
+
+    config.contrast.comment = Some(CommentContrast {
+        min: 5.0,
+        max: 45.0,
+    });
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn test_terminal_attributes_key() {
+    use themalingadingdong::config::{TerminalAttributes, validate_config};
+
+    let toml_str = r##"
+[colors]
+background = "#1a1a2e"
+foreground = "#eaeaea"
+
+[contrast.terminal]
+dim = 0.6
+"##;
+
+    let mut config: ThemeConfig = toml::from_str(toml_str).unwrap();
+    assert_eq!(
+        config.contrast.terminal,
+        Some(TerminalAttributes {
+            dim: 0.6,
+            ..TerminalAttributes::default()
+        })
+    );
+    assert!(validate_config(&config).is_ok());
+
+    config.contrast.terminal = Some(TerminalAttributes {
+        dim: 1.5,
+        ..TerminalAttributes::default()
+    });
+    assert!(validate_config(&config).is_err());
+
+    config.contrast.terminal = Some(TerminalAttributes {
+        bold: 0.5,
+        ..TerminalAttributes::default()
+    });
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn test_foreground_headroom_key() {
+    use themalingadingdong::config::validate_config;
+
+    let toml_str = r##"
+[colors]
+background = "#1d2021"
+foreground = "#a89984"
+foreground_headroom = 12.0
+"##;
+
+    let mut config: ThemeConfig = toml::from_str(toml_str).unwrap();
+    assert_eq!(config.colors.foreground_headroom, Some(12.0));
+    assert!(validate_config(&config).is_ok());
+    assert_eq!(
+        config.to_generate_config().unwrap().foreground_headroom,
+        12.0
+    );
+
+    config.colors.foreground_headroom = Some(-3.0);
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn test_contrast_standard_key() {
+    use themalingadingdong::validation::ContrastStandard;
+
+    let config: ThemeConfig = toml::from_str("[contrast]\nstandard = \"both\"\n").unwrap();
+    assert_eq!(config.contrast.standard, ContrastStandard::Both);
+
+    let config: ThemeConfig = toml::from_str("[contrast]\ncontrast_standard = \"wcag\"\n").unwrap();
+    assert_eq!(config.contrast.standard, ContrastStandard::Wcag);
+
+    // The default is omitted when saving
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("standard"));
+}
+
+#[test]
+fn test_extended_accents_key() {
+    use themalingadingdong::config::validate_config;
+    use themalingadingdong::extended_accents::ExtendedAccentMode;
+
+    let toml_str = r##"
+[colors]
+background = "#1d2021"
+foreground = "#ebdbb2"
+
+[extended_accents]
+mode = "desaturated"
+"##;
+
+    let mut config: ThemeConfig = toml::from_str(toml_str).unwrap();
+    assert_eq!(
+        config.extended_accents.mode,
+        ExtendedAccentMode::Desaturated
+    );
+    assert!(validate_config(&config).is_ok());
+    let generate = config.to_generate_config().unwrap();
+    assert_eq!(generate.extended_accents.amount(), 40.0);
+
+    config.extended_accents.amount = Some(120.0);
+    assert!(validate_config(&config).is_err());
+
+    // Independent optimization is the default and is omitted when saving
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("extended_accents"));
+}
+
+#[test]
+fn test_validation_thresholds() {
+    use themalingadingdong::config::validate_config;
+
+    let config: ThemeConfig =
+        toml::from_str("[validation]\nbase02 = 45.0\nlight_on_dark = 5.0\n").unwrap();
+    assert_eq!(config.validation.base02, 45.0);
+    assert_eq!(config.validation.base00, 60.0);
+    assert_eq!(config.validation.light_on_dark, 5.0);
+
+    let bad: ThemeConfig = toml::from_str(
+        "[colors]\nbackground = \"#000000\"\nforeground = \"#ffffff\"\n\
+         [validation]\nbase01 = 120.0\n",
+    )
+    .unwrap();
+    let err = validate_config(&bad).unwrap_err().to_string();
+    assert!(err.contains("validation.base01"), "{err}");
+
+    // Default thresholds are omitted when saving
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("[validation]"));
+}
+
+#[test]
+fn test_cvd_separation_settings() {
+    use themalingadingdong::config::validate_config;
+
+    let config: ThemeConfig = toml::from_str("[optimization.cvd]\ndeuteranopia = 2.0\n").unwrap();
+    let cvd = config.optimization.cvd.unwrap();
+    assert_eq!(cvd.deuteranopia, 2.0);
+    assert_eq!(cvd.protanopia, 1.0);
+    assert_eq!(cvd.min_delta_e, 10.0);
+    assert!(config.extended_optimization.cvd.is_none());
+
+    let bad: ThemeConfig = toml::from_str(
+        "[colors]\nbackground = \"#000000\"\nforeground = \"#ffffff\"\n\
+         [extended_optimization.cvd]\ntritanopia = -1.0\n",
+    )
+    .unwrap();
+    let err = validate_config(&bad).unwrap_err().to_string();
+    assert!(err.contains("cvd.tritanopia"), "{err}");
+
+    // Separation is off by default and omitted when saving
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("cvd"));
+}
+
+#[test]
+fn test_preview_snippets_dir() {
+    use std::path::Path;
+
+    let config: ThemeConfig = toml::from_str("[preview]\nsnippets_dir = \"snippets\"\n").unwrap();
+    assert_eq!(
+        config.preview.snippets_dir.as_deref(),
+        Some(Path::new("snippets"))
+    );
+
+    let config: ThemeConfig = toml::from_str("[preview]\ngallery_dir = \"schemes\"\n").unwrap();
+    assert_eq!(
+        config.preview.gallery_dir.as_deref(),
+        Some(Path::new("schemes"))
+    );
+
+    // An unset preview section is omitted when saving
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("[preview]"));
+}
+
+#[test]
+fn test_color_pins() {
+    use themalingadingdong::config::validate_config;
+
+    let config: ThemeConfig = toml::from_str(
+        "[colors]\nbackground = \"#1d2021\"\nforeground = \"#ebdbb2\"\n\n[colors.pins]\nbase0D = \"#82aaff\"\n",
+    )
+    .unwrap();
+    assert!(validate_config(&config).is_ok());
+    let generate = config.to_generate_config().unwrap();
+    assert_eq!(
+        generate.pins["base0D"],
+        palette::Srgb::new(0x82, 0xaa, 0xff)
+    );
+
+    // Round-trips through a saved config
+    let saved = ThemeConfig::from_generate_config(&generate);
+    assert_eq!(
+        saved.colors.pins.unwrap()["base0D"].to_lowercase(),
+        "#82aaff"
+    );
+
+    let bad: ThemeConfig = toml::from_str(
+        "[colors]\nbackground = \"#000\"\nforeground = \"#fff\"\n\n[colors.pins]\nbase0d = \"#82aaff\"\n",
+    )
+    .unwrap();
+    assert!(validate_config(&bad).is_err());
+}
+
+#[test]
+fn test_accent_anchors() {
+    use themalingadingdong::config::validate_config;
+
+    let config: ThemeConfig = toml::from_str(
+        "[colors]\nbackground = \"#ffffff\"\nforeground = \"#1f2328\"\n\n[optimization.anchors]\nbase0D = { color = \"#0969da\", tolerance = 3.0 }\nbase08 = { color = \"#cf222e\" }\n",
+    )
+    .unwrap();
+    assert!(validate_config(&config).is_ok());
+    let anchor = config.optimization.anchor(5).unwrap();
+    assert_eq!(anchor.color, "#0969da");
+    assert_eq!(anchor.tolerance, 3.0);
+    assert_eq!(config.optimization.anchor(0).unwrap().tolerance, 5.0);
+
+    for bad in [
+        // Not a base accent
+        "[optimization.anchors]\nbase03 = { color = \"#0969da\" }\n",
+        // Extended slots belong in extended_optimization
+        "[optimization.anchors]\nbase15 = { color = \"#0969da\" }\n",
+        "[optimization.anchors]\nbase0D = { color = \"nope\" }\n",
+        "[optimization.anchors]\nbase0D = { color = \"#0969da\", tolerance = 0.0 }\n",
+    ] {
+        let config: ThemeConfig = toml::from_str(bad).unwrap();
+        assert!(validate_config(&config).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn test_profiles_layer_over_shared_settings() {
+    use themalingadingdong::config::{ConfigOverrides, load_config, load_config_with_profile};
+
+    let path = std::env::temp_dir().join(format!(
+        "themalingadingdong-profiles-{}.toml",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        r##"
+[theme]
+name = "Gruvbox"
+
+[colors]
+background = "#1d2021"
+foreground = "#ebdbb2"
+
+[contrast]
+minimum = 70.0
+
+[profile.high-contrast.theme]
+name = "Gruvbox High Contrast"
+
+[profile.high-contrast.contrast]
+minimum = 90.0
+
+[profile.pastel.optimization]
+target_m = 20.0
+"##,
+    )
+    .unwrap();
+
+    let shared = load_config(Some(&path), &ConfigOverrides::default()).unwrap();
+    assert_eq!(shared.theme.name, "Gruvbox");
+    assert_eq!(shared.contrast.minimum, 70.0);
+
+    let high = load_config_with_profile(
+        Some(&path),
+        Some("high-contrast"),
+        &ConfigOverrides::default(),
+    )
+    .unwrap();
+    assert_eq!(high.theme.name, "Gruvbox High Contrast");
+    assert_eq!(high.contrast.minimum, 90.0);
+    // Settings the profile doesn't mention come from the shared tables
+    assert_eq!(high.colors.background.as_deref(), Some("#1d2021"));
+
+    let pastel =
+        load_config_with_profile(Some(&path), Some("pastel"), &ConfigOverrides::default()).unwrap();
+    assert_eq!(pastel.optimization.target_m, 20.0);
+    assert_eq!(pastel.contrast.minimum, 70.0);
+
+    let err = load_config_with_profile(Some(&path), Some("neon"), &ConfigOverrides::default())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("high-contrast, pastel"), "{err}");
+
+    // An explicit override wins even when it equals the built-in default
+    let mut overrides = ConfigOverrides::default();
+    overrides.set("contrast.minimum", Some(75.0));
+    let flagged = load_config_with_profile(Some(&path), Some("high-contrast"), &overrides).unwrap();
+    assert_eq!(flagged.contrast.minimum, 75.0);
+    assert_eq!(flagged.theme.name, "Gruvbox High Contrast");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_validate_config_reports_every_error() {
+    use themalingadingdong::config::{CommentContrast, ConfigError, validate_config};
+
+    let mut config = ThemeConfig::default();
+    config.colors.background = Some("#1a1a2e".to_string());
+    config.contrast.comment = Some(CommentContrast {
+        min: 60.0,
+        max: 50.0,
+    });
+    config.colors.foreground_headroom = Some(150.0);
+
+    let err = validate_config(&config).unwrap_err();
+    assert!(matches!(&err, ConfigError::Multiple(errors) if errors.len() == 3));
+    let message = err.to_string();
+    assert!(message.contains("colors.foreground"), "{message}");
+    assert!(message.contains("contrast.comment"), "{message}");
+    assert!(message.contains("foreground_headroom"), "{message}");
+}
@@ -0,0 +1,72 @@
+//! Tests for the full-palette APCA contrast matrix.
+
+use palette::Srgb;
+use themalingadingdong::apca::apca_contrast;
+use themalingadingdong::contrast_matrix::{ContrastMatrix, SLOTS, render_table};
+use themalingadingdong::generate::{GenerateConfig, generate};
+
+fn gruvbox() -> GenerateConfig {
+    GenerateConfig {
+        background: Srgb::new(0x1d, 0x20, 0x21),
+        foreground: Srgb::new(0xeb, 0xdb, 0xb2),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_matrix_covers_every_slot_pair() {
+    let scheme = generate(&gruvbox()).scheme;
+    let matrix = ContrastMatrix::new(&scheme);
+    assert_eq!(matrix.slots, SLOTS);
+
+    let rgb = |slot: &str| {
+        let c = &scheme.palette[slot];
+        Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2)
+    };
+    for fg in SLOTS {
+        for bg in SLOTS {
+            assert_eq!(matrix.get(fg, bg), Some(apca_contrast(rgb(fg), rgb(bg))));
+        }
+    }
+    assert_eq!(matrix.get("base05", "base18"), None);
+}
+
+#[test]
+fn test_thresholds_follow_validation_pairs() {
+    assert_eq!(ContrastMatrix::threshold("base07", "base01"), Some(75.0));
+    assert_eq!(ContrastMatrix::threshold("base0D", "base00"), Some(60.0));
+    // Accents are checked on base01 and base02 too, at lower defaults
+    assert_eq!(ContrastMatrix::threshold("base12", "base01"), Some(45.0));
+    assert_eq!(ContrastMatrix::threshold("base0D", "base02"), Some(30.0));
+    assert_eq!(ContrastMatrix::threshold("base0D", "base03"), None);
+}
+
+#[test]
+fn test_failures_are_marked_in_table() {
+    // A mid-gray background leaves little room for contrast
+    let scheme = generate(&GenerateConfig {
+        background: Srgb::new(0x60, 0x60, 0x60),
+        foreground: Srgb::new(0xa0, 0xa0, 0xa0),
+        ..Default::default()
+    })
+    .scheme;
+    let matrix = ContrastMatrix::new(&scheme);
+    let failures = matrix.failures();
+    assert!(!failures.is_empty());
+    for f in &failures {
+        assert!(f.lc < f.min_lc);
+        assert!(matrix.is_below_threshold(f.foreground, f.background));
+    }
+
+    let table = render_table(&matrix);
+    let first = &failures[0];
+    let row = table
+        .lines()
+        .find(|l| l.starts_with(first.foreground))
+        .unwrap();
+    assert!(row.contains('!'));
+    assert!(table.contains(&format!(
+        "{} on {}: Lc {:.1}",
+        first.foreground, first.background, first.lc
+    )));
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use palette::Srgb;
+use themalingadingdong::apca::apca_contrast;
+use themalingadingdong::builder::ThemeBuilder;
+use themalingadingdong::config::AccentOptSettings;
+use themalingadingdong::contrast::{Apca, ContrastModel, Michelson, Weber};
+use themalingadingdong::generate::{GenerateConfig, GenerationCache, generate};
+use themalingadingdong::validation::{
+    ContrastStandard, ValidationConfig, validate_with_config, validate_with_model,
+};
+
+#[test]
+fn test_apca_model_matches_apca_contrast() {
+    let pairs = [
+        (Srgb::new(0u8, 0, 0), Srgb::new(255u8, 255, 255)),
+        (Srgb::new(0x82, 0xaa, 0xff), Srgb::new(0x1a, 0x1a, 0x2e)),
+        (Srgb::new(0x40, 0x40, 0x40), Srgb::new(0x44, 0x44, 0x44)),
+    ];
+    for (fg, bg) in pairs {
+        assert_eq!(Apca.contrast(fg, bg), apca_contrast(fg, bg));
+    }
+}
+
+#[test]
+fn test_michelson_and_weber_formulas() {
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    assert!(close(Michelson.contrast_from_luminances(0.2, 0.6), 50.0));
+    assert!(close(Michelson.contrast_from_luminances(0.6, 0.2), -50.0));
+    assert!(close(Weber.contrast_from_luminances(0.2, 0.8), 75.0));
+    assert!(close(Weber.contrast_from_luminances(0.8, 0.2), -300.0));
+    // Same color, no contrast
+    let gray = Srgb::new(0x80u8, 0x80, 0x80);
+    assert_eq!(Michelson.contrast(gray, gray), 0.0);
+    assert_eq!(Weber.contrast(gray, gray), 0.0);
+}
+
+/// Light background with accents allowed dark enough for the floor to bind.
+fn light_config(model: Arc<dyn ContrastModel>) -> GenerateConfig {
+    GenerateConfig {
+        background: Srgb::new(250u8, 250, 250),
+        foreground: Srgb::new(32u8, 32, 32),
+        accent_opt: AccentOptSettings {
+            target_j: 55.0,
+            delta_j: 20.0,
+            ..Default::default()
+        },
+        contrast_model: model,
+        min_contrast: 60.0,
+        extended_min_contrast: 50.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_generation_reports_model_units() {
+    let result = generate(&light_config(Arc::new(Weber)));
+    let background = Srgb::new(250u8, 250, 250);
+    for accent in &result.base_accent_results {
+        let color = accent.color.into_format::<u8>();
+        assert!((Weber.contrast(color, background) - accent.achieved_contrast).abs() < 1.0);
+        assert!(accent.met_minimum, "{}", accent.achieved_contrast);
+    }
+
+    // Weber 60 is far easier to reach than Lc 60
+    let apca = generate(&light_config(Arc::new(Apca)));
+    assert!(apca.base_accent_results.iter().all(|r| !r.met_minimum));
+    assert!(apca.warnings.iter().any(|w| w.contains("Lc")));
+    assert_ne!(
+        apca.scheme.palette["base08"].rgb,
+        result.scheme.palette["base08"].rgb
+    );
+}
+
+#[test]
+fn test_validation_with_model() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let thresholds = ValidationConfig::default();
+    let apca = validate_with_config(&scheme, &[], &[], ContrastStandard::Apca, &thresholds);
+    let same = validate_with_model(
+        &scheme,
+        &[],
+        &[],
+        ContrastStandard::Apca,
+        &thresholds,
+        &Apca,
+    );
+    for (a, b) in apca.required.iter().zip(&same.required) {
+        assert_eq!(a.contrast, b.contrast);
+        assert_eq!(a.passes, b.passes);
+    }
+
+    // Weber contrast of light text on a dark background is huge, so every
+    // pair clears the Lc-sized thresholds
+    let weber = validate_with_model(
+        &scheme,
+        &[],
+        &[],
+        ContrastStandard::Apca,
+        &thresholds,
+        &Weber,
+    );
+    assert!(weber.required.iter().all(|r| r.passes));
+}
+
+#[test]
+fn test_builder_contrast_model() {
+    let theme = ThemeBuilder::from_config(light_config(Arc::new(Apca)))
+        .contrast_model(Weber)
+        .build()
+        .unwrap();
+    assert_eq!(theme.config().contrast_model.name(), "Weber");
+    for accent in theme.accents() {
+        assert!(Weber.contrast(accent, theme.background()) >= 59.0);
+    }
+}
+
+#[test]
+fn test_cache_resolves_accents_for_a_new_model() {
+    let mut cache = GenerationCache::new();
+    let mut config = GenerateConfig::default();
+    cache.generate(&config);
+
+    // A clone shares the model, so nothing is recomputed
+    cache.generate(&config.clone());
+    assert!(cache.recomputed().is_empty());
+
+    config.contrast_model = Arc::new(Weber);
+    cache.generate(&config);
+    assert_eq!(cache.recomputed(), ["accents", "extended accents"]);
+}
@@ -0,0 +1,45 @@
+use themalingadingdong::convert::convert_color;
+use themalingadingdong::gamut_map::Gamut;
+
+#[test]
+fn test_convert_hex_round_trips_exactly() {
+    for hex in ["#000000", "#ffffff", "#ff0000", "#1d2021", "#ebdbb2"] {
+        let report = convert_color(hex).unwrap();
+        assert_eq!(report.hex, hex);
+        assert!(report.in_gamut.iter().all(|(_, inside)| *inside));
+    }
+}
+
+#[test]
+fn test_convert_jmh_reports_gamut_membership() {
+    // Saturated green beyond sRGB and Display-P3 but inside Rec.2020
+    let report = convert_color("jmh(60, 90, 150)").unwrap();
+    assert_eq!(
+        report.in_gamut,
+        [
+            (Gamut::Srgb, false),
+            (Gamut::DisplayP3, false),
+            (Gamut::Rec2020, true)
+        ]
+    );
+    assert!(report.to_string().contains("(gamut-mapped)"));
+
+    // Wider gamuts allow more colorfulness at the same J' and hue
+    let [srgb, p3, rec2020] = report.max_colorfulness.map(|(_, m)| m);
+    assert!(srgb < p3 && p3 < rec2020);
+}
+
+#[test]
+fn test_convert_oklch_matches_hex() {
+    let from_oklch = convert_color("oklch(62.8% 0.2577 29.23)").unwrap();
+    let from_hex = convert_color("#ff0000").unwrap();
+    assert!(from_oklch.jmh.delta_e(&from_hex.jmh) < 1.0);
+    assert!((from_hex.oklch.l - 0.628).abs() < 1e-3);
+}
+
+#[test]
+fn test_convert_rejects_malformed_input() {
+    assert!(convert_color("jmh(60 30)").is_err());
+    assert!(convert_color("jmh(60 -5 30)").is_err());
+    assert!(convert_color("not a color").is_err());
+}
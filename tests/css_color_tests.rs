@@ -0,0 +1,87 @@
+//! Tests for CSS color input parsing.
+
+use palette::Srgb;
+use themalingadingdong::config::ThemeConfig;
+use themalingadingdong::css_color::{parse_color_in, parse_linear, parse_srgb};
+use themalingadingdong::gamut_map::Gamut;
+use themalingadingdong::hellwig::HellwigJmh;
+
+#[test]
+fn test_css_syntaxes_agree() {
+    let expected = Srgb::new(102u8, 51, 153);
+    for input in [
+        "#663399",
+        "rebeccapurple",
+        "RebeccaPurple",
+        "rgb(102 51 153)",
+        "rgb(102, 51, 153)",
+        "hsl(270 50% 40%)",
+        "color(srgb 0.4 0.2 0.6)",
+        "color(srgb 40% 20% 60% / 0.5)",
+        "  COLOR(sRGB 0.4 0.2 0.6)  ",
+    ] {
+        assert_eq!(parse_srgb(input).unwrap(), expected, "{input}");
+    }
+}
+
+#[test]
+fn test_in_gamut_oklch_matches_hex() {
+    // oklch() of #1a1a2e, rounded to 4 places
+    let color = parse_srgb("oklch(0.2284 0.0384 282.93)").unwrap();
+    let expected = Srgb::new(0x1au8, 0x1a, 0x2e);
+    for (a, b) in [
+        (color.red, expected.red),
+        (color.green, expected.green),
+        (color.blue, expected.blue),
+    ] {
+        assert!(a.abs_diff(b) <= 1, "{color:?} vs {expected:?}");
+    }
+}
+
+#[test]
+fn test_wide_gamut_input_keeps_hue() {
+    let input = "color(display-p3 0 1 0)";
+    let linear = parse_linear(input).unwrap();
+    let original = HellwigJmh::from_linear_srgb(linear);
+
+    let srgb = HellwigJmh::from_srgb_u8(parse_srgb(input).unwrap());
+    assert!(
+        (srgb.hue - original.hue).abs() < 2.0,
+        "hue {:.1} -> {:.1}",
+        original.hue,
+        srgb.hue
+    );
+    assert!(srgb.colorfulness < original.colorfulness);
+
+    // Display-P3 holds the color, so nothing is mapped away
+    let p3 = parse_color_in(input, Gamut::DisplayP3).unwrap();
+    assert!((p3.colorfulness - original.colorfulness).abs() < 0.5);
+    assert!(Gamut::DisplayP3.contains(p3));
+}
+
+#[test]
+fn test_invalid_color_function() {
+    for input in [
+        "color(display-p3 1 0)",
+        "color(prophoto-rgb 1 0 0)",
+        "color(srgb a b c)",
+        "color()",
+        "notacolor",
+    ] {
+        let err = parse_srgb(input).unwrap_err();
+        assert!(err.contains(input), "{err}");
+    }
+}
+
+#[test]
+fn test_config_accepts_css_colors() {
+    let config: ThemeConfig = toml::from_str(
+        "[colors]\nbackground = \"oklch(0.2 0.02 280)\"\nforeground = \"whitesmoke\"\n",
+    )
+    .unwrap();
+    themalingadingdong::config::validate_config(&config).unwrap();
+
+    let generate = config.to_generate_config().unwrap();
+    assert_eq!(generate.foreground, Srgb::new(245u8, 245, 245));
+    assert!(HellwigJmh::from_srgb_u8(generate.background).lightness < 25.0);
+}
@@ -0,0 +1,123 @@
+use themalingadingdong::config::ThemeConfig;
+use themalingadingdong::curves::{
+    CurveConfig, CurveType, compute_sample_positions, evaluate_curve, invert,
+};
+
+fn curve(curve_type: CurveType) -> CurveConfig {
+    CurveConfig {
+        curve_type,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_invert_round_trips_every_curve() {
+    let mut curves: Vec<CurveConfig> = [
+        CurveType::Linear,
+        CurveType::Smoothstep,
+        CurveType::Smootherstep,
+        CurveType::SmoothStart,
+        CurveType::SmoothEnd,
+        CurveType::Sigmoid,
+    ]
+    .into_iter()
+    .map(curve)
+    .collect();
+    curves.push(CurveConfig {
+        curve_type: CurveType::BSpline,
+        strength: 1.0,
+        control_points: Some(vec![(0.0, 0.0), (0.3, 0.1), (0.7, 0.6), (1.0, 1.0)]),
+        stops: None,
+    });
+    curves.push(curve(CurveType::CubicBezier));
+    curves.push(CurveConfig {
+        curve_type: CurveType::CubicBezier,
+        strength: 1.0,
+        control_points: Some(vec![(0.7, 0.0), (0.3, 1.0)]),
+        stops: None,
+    });
+
+    for config in &curves {
+        for i in 0..=20 {
+            let t = i as f32 / 20.0;
+            let y = evaluate_curve(config, t);
+            let recovered = invert(config, y);
+            assert!(
+                (evaluate_curve(config, recovered) - y).abs() < 1e-4,
+                "{:?}: t={t} y={y} recovered={recovered}",
+                config.curve_type
+            );
+        }
+    }
+}
+
+#[test]
+fn test_invert_clamps_and_hits_endpoints() {
+    let config = curve(CurveType::Smoothstep);
+    assert!(invert(&config, -0.5) < 1e-6);
+    assert!(invert(&config, 1.5) > 1.0 - 1e-6);
+    assert!((invert(&config, 0.5) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_cubic_bezier_matches_css_easings() {
+    // CSS `ease` (the default handles) is about 0.8024 halfway
+    let ease = curve(CurveType::CubicBezier);
+    assert!((evaluate_curve(&ease, 0.5) - 0.8024).abs() < 1e-3);
+
+    // Handles on the diagonal give a straight line
+    let linear = CurveConfig {
+        curve_type: CurveType::CubicBezier,
+        strength: 1.0,
+        control_points: Some(vec![(0.25, 0.25), (0.75, 0.75)]),
+        stops: None,
+    };
+    let samples = compute_sample_positions(8, &linear);
+    for (i, t) in samples.iter().enumerate() {
+        assert!((t - i as f32 / 7.0).abs() < 1e-4, "{samples:?}");
+    }
+    assert!(samples[0].abs() < 1e-6 && (samples[7] - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_cubic_bezier_reads_from_config() {
+    let config: ThemeConfig = toml::from_str(
+        "[curves.lightness]\ntype = \"cubic_bezier\"\ncontrol_points = [[0.4, 0.0], [0.2, 1.0]]\n",
+    )
+    .unwrap();
+    let lightness = &config.curves.lightness;
+    assert_eq!(lightness.curve_type, CurveType::CubicBezier);
+    assert_eq!(lightness.bezier_points(), [(0.4, 0.0), (0.2, 1.0)]);
+    assert!(evaluate_curve(lightness, 0.5) > 0.5);
+}
+
+#[test]
+fn test_manual_curve_samples_its_stops() {
+    let stops = vec![0.0, 0.06, 0.14, 0.3, 0.62, 0.78, 0.9, 1.0];
+    let config = CurveConfig {
+        curve_type: CurveType::Manual,
+        stops: Some(stops.clone()),
+        ..Default::default()
+    };
+    assert_eq!(compute_sample_positions(8, &config), stops);
+    let y = evaluate_curve(&config, 0.5);
+    assert!((invert(&config, y) - 0.5).abs() < 1e-4);
+
+    // Without exactly eight stops it falls back to evenly spaced ones
+    let unset = curve(CurveType::Manual);
+    for (i, t) in compute_sample_positions(8, &unset).iter().enumerate() {
+        assert!((t - i as f32 / 7.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_manual_curve_reads_from_config() {
+    let config: ThemeConfig = toml::from_str(
+        "[curves.chroma]\ntype = \"manual\"\nstops = [0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 1.5]\n",
+    )
+    .unwrap();
+    let chroma = &config.curves.chroma;
+    assert_eq!(chroma.curve_type, CurveType::Manual);
+    assert_eq!(chroma.manual_stops()[7], 1.0);
+    assert!((evaluate_curve(chroma, 3.0 / 7.0) - 0.3).abs() < 1e-6);
+}
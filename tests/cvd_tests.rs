@@ -0,0 +1,68 @@
+//! Tests for colorblindness simulation and validation.
+
+use palette::Srgb;
+use themalingadingdong::cvd::{CvdKind, check_cvd, simulate, simulate_scheme};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::HellwigJmh;
+
+fn delta_e(a: Srgb<u8>, b: Srgb<u8>) -> f32 {
+    HellwigJmh::from_srgb_u8(a).delta_e(&HellwigJmh::from_srgb_u8(b))
+}
+
+#[test]
+fn simulation_preserves_black_and_white() {
+    for kind in CvdKind::ALL {
+        assert_eq!(simulate(Srgb::new(0, 0, 0), kind), Srgb::new(0, 0, 0));
+        let white = simulate(Srgb::new(255, 255, 255), kind);
+        assert!(delta_e(white, Srgb::new(255, 255, 255)) < 1.0, "{kind}");
+    }
+}
+
+#[test]
+fn red_green_collapse_for_deuteranopia_not_tritanopia() {
+    let red = Srgb::new(200u8, 80, 70);
+    let green = Srgb::new(110u8, 150, 60);
+
+    let deutan = delta_e(
+        simulate(red, CvdKind::Deuteranopia),
+        simulate(green, CvdKind::Deuteranopia),
+    );
+    let tritan = delta_e(
+        simulate(red, CvdKind::Tritanopia),
+        simulate(green, CvdKind::Tritanopia),
+    );
+    assert!(deutan < tritan, "deutan {deutan:.1} vs tritan {tritan:.1}");
+}
+
+#[test]
+fn check_cvd_flags_only_simulated_collisions() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+
+    let conflicts = check_cvd(&scheme, 5.0);
+    for c in &conflicts {
+        assert!(c.delta_e < 5.0);
+        assert!(c.normal_delta_e >= 5.0);
+        assert_ne!(c.first, c.second);
+    }
+    assert!(
+        conflicts
+            .iter()
+            .any(|c| c.first == "base08" && c.second == "base0B"),
+        "red/green accents should collide for some deficiency"
+    );
+
+    assert!(check_cvd(&scheme, 0.0).is_empty());
+}
+
+#[test]
+fn simulate_scheme_keeps_slots_and_metadata() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let simulated = simulate_scheme(&scheme, CvdKind::Protanopia);
+
+    assert_eq!(simulated.palette.len(), scheme.palette.len());
+    assert_eq!(simulated.slug, scheme.slug);
+    assert_ne!(
+        simulated.palette["base08"].rgb,
+        scheme.palette["base08"].rgb
+    );
+}
@@ -0,0 +1,92 @@
+//! Tests for `--daemon` outputs and terminal recoloring.
+
+use std::path::PathBuf;
+
+use themalingadingdong::config::{ColorConfig, ThemeConfig, validate_config};
+use themalingadingdong::daemon::{DaemonConfig, osc_sequences, rebuild};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-daemon-{}-{}",
+        std::process::id(),
+        name
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn gruvbox_config() -> ThemeConfig {
+    ThemeConfig {
+        colors: ColorConfig {
+            background: Some("#1d2021".into()),
+            foreground: Some("#ebdbb2".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_rebuild_writes_every_output_and_terminal() {
+    let dir = temp_dir("outputs");
+    // A regular file stands in for a terminal device
+    let tty = dir.join("tty");
+    std::fs::write(&tty, "").unwrap();
+    let daemon = DaemonConfig {
+        outputs: vec![dir.join("nested/scheme.yaml"), dir.join("scheme.css")],
+        terminals: vec![tty.clone(), dir.join("missing/tty")],
+    };
+
+    let outcome = rebuild(&gruvbox_config(), &daemon).unwrap();
+
+    assert_eq!(outcome.written, daemon.outputs);
+    let yaml = std::fs::read_to_string(dir.join("nested/scheme.yaml")).unwrap();
+    assert!(yaml.contains("base00"));
+    let css = std::fs::read_to_string(dir.join("scheme.css")).unwrap();
+    assert!(css.contains("--base00"));
+
+    assert_eq!(outcome.applied, vec![tty.clone()]);
+    assert_eq!(
+        std::fs::read_to_string(&tty).unwrap(),
+        osc_sequences(&outcome.scheme)
+    );
+    assert!(outcome.warnings.iter().any(|w| w.contains("missing/tty")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_osc_uses_base24_brights() {
+    let dir = temp_dir("osc");
+    let daemon = DaemonConfig {
+        outputs: vec![dir.join("scheme.json")],
+        terminals: vec![],
+    };
+    let scheme = rebuild(&gruvbox_config(), &daemon).unwrap().scheme;
+    let osc = osc_sequences(&scheme);
+
+    assert_eq!(osc.matches("\x1b]4;").count(), 16);
+    let base12 = scheme.palette["base12"].rgb;
+    assert!(osc.contains(&format!(
+        "\x1b]4;9;rgb:{:02x}/{:02x}/{:02x}\x1b\\",
+        base12.0, base12.1, base12.2
+    )));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_daemon_config_rejects_unknown_extension() {
+    let mut config = gruvbox_config();
+    config.daemon = Some(DaemonConfig {
+        outputs: vec!["theme.txt".into()],
+        terminals: vec![],
+    });
+    assert!(validate_config(&config).is_err());
+
+    config.daemon = Some(DaemonConfig {
+        outputs: vec!["theme.yml".into()],
+        terminals: vec![],
+    });
+    assert!(validate_config(&config).is_ok());
+}
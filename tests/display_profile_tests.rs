@@ -0,0 +1,127 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::config::{ThemeConfig, validate_config};
+use themalingadingdong::config_schema::check_config;
+use themalingadingdong::display_profile::DisplayProfile;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::viewing::WhitePoint;
+
+const BLACK: Srgb<u8> = Srgb::new(0, 0, 0);
+const WHITE: Srgb<u8> = Srgb::new(255, 255, 255);
+const GRAY: Srgb<u8> = Srgb::new(128, 128, 128);
+
+fn display(gamma: f32, white_point: WhitePoint) -> DisplayProfile {
+    DisplayProfile { gamma, white_point }
+}
+
+#[test]
+fn test_gamma_compensation() {
+    let srgb_like = DisplayProfile::default();
+    assert_eq!(srgb_like.calibrate(BLACK), BLACK);
+    assert_eq!(srgb_like.calibrate(WHITE), WHITE);
+
+    // A steeper curve darkens midtones, so they are sent brighter
+    let steep = display(2.4, WhitePoint::D65).calibrate(GRAY);
+    let shallow = display(1.8, WhitePoint::D65).calibrate(GRAY);
+    assert!(
+        steep.red > 128 && shallow.red < 128,
+        "{steep:?} {shallow:?}"
+    );
+    assert_eq!(steep.red, steep.blue);
+}
+
+#[test]
+fn test_white_point_compensation() {
+    // Warm displays get red cut back, cool ones blue; the strongest
+    // channel stays at full output
+    let warm = display(2.2, WhitePoint::D50).calibrate(WHITE);
+    assert!(warm.red < warm.green && warm.green < warm.blue);
+    assert_eq!(warm.blue, 255);
+
+    let cool = display(2.2, WhitePoint::D75).calibrate(WHITE);
+    assert!(cool.blue < cool.red);
+    assert_eq!(cool.red, 255);
+}
+
+#[test]
+fn test_calibrated_scheme_metadata() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let calibrated = display(2.4, WhitePoint::D50).calibrate_scheme(&scheme, "external");
+    assert_eq!(calibrated.slug, format!("{}-external", scheme.slug));
+    assert!(
+        calibrated
+            .description
+            .unwrap()
+            .ends_with("; calibrated for external (gamma 2.4, D50 white)")
+    );
+    assert_eq!(calibrated.name, scheme.name);
+    assert_ne!(
+        calibrated.palette["base07"].rgb,
+        scheme.palette["base07"].rgb
+    );
+}
+
+#[test]
+fn test_display_config() {
+    let source = r##"
+[colors]
+background = "#1a1a2e"
+foreground = "#eaeaea"
+
+[displays.macbook]
+gamma = 2.2
+
+[displays.external]
+gamma = 2.4
+white_point = "d50"
+"##;
+    assert_eq!(check_config(source), vec![]);
+    let config: ThemeConfig = toml::from_str(source).unwrap();
+    assert_eq!(config.displays["external"], display(2.4, WhitePoint::D50));
+    assert_eq!(config.displays["macbook"], DisplayProfile::default());
+    validate_config(&config).unwrap();
+
+    let bad: ThemeConfig = toml::from_str(&source.replace("2.4", "4.0")).unwrap();
+    assert!(
+        validate_config(&bad)
+            .unwrap_err()
+            .to_string()
+            .contains("gamma")
+    );
+    let bad: ThemeConfig = toml::from_str(&source.replace("macbook", "MacBook")).unwrap();
+    assert!(validate_config(&bad).is_err());
+
+    let typo = check_config(&source.replace("white_point", "whitepoint"));
+    assert_eq!(typo[0].key, "displays.external.whitepoint");
+}
+
+#[test]
+fn test_per_display_writes_one_file_per_display() {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-per-display-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("theme.toml");
+    std::fs::write(
+        &config,
+        "[theme]\nname = \"My Theme\"\n[colors]\nbackground = \"#1a1a2e\"\nforeground = \"#eaeaea\"\n\
+         [displays.macbook]\n[displays.external]\ngamma = 2.4\nwhite_point = \"d50\"\n",
+    )
+    .unwrap();
+    let output = dir.join("mytheme.yaml");
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("--config")
+        .arg(&config)
+        .arg("-o")
+        .arg(&output)
+        .arg("--per-display")
+        .assert()
+        .success();
+
+    let external = std::fs::read_to_string(dir.join("mytheme-external.yaml")).unwrap();
+    assert!(external.contains("slug: my-theme-dark-external"));
+    assert!(dir.join("mytheme-macbook.yaml").exists());
+    assert!(!output.exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,88 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use predicates::prelude::*;
+use themalingadingdong::apca::apca_contrast;
+use themalingadingdong::display_sim::DisplaySimulation;
+use themalingadingdong::generate::{GenerateConfig, generate};
+
+const BLACK: Srgb<u8> = Srgb::new(0, 0, 0);
+const WHITE: Srgb<u8> = Srgb::new(255, 255, 255);
+const GRAY: Srgb<u8> = Srgb::new(128, 128, 128);
+
+#[test]
+fn test_cycle_visits_every_simulation() {
+    let mut seen = Vec::new();
+    let mut current = DisplaySimulation::cycle(None);
+    while let Some(simulation) = current {
+        seen.push(simulation);
+        current = DisplaySimulation::cycle(current);
+    }
+    assert_eq!(seen, DisplaySimulation::ALL);
+}
+
+#[test]
+fn test_dim_darkens_and_crushes_shadows() {
+    let dim = DisplaySimulation::Dim;
+    assert!(dim.apply(WHITE).red < 255);
+    assert!(dim.apply(GRAY).red < 128);
+    assert_eq!(dim.apply(Srgb::new(20, 20, 20)), BLACK);
+}
+
+#[test]
+fn test_bright_glare_reduces_contrast() {
+    let bright = DisplaySimulation::Bright;
+    assert!(bright.apply(BLACK).red > 0);
+    assert_eq!(bright.apply(WHITE), WHITE);
+
+    let (text, background) = (Srgb::new(200u8, 200, 200), Srgb::new(30u8, 30, 40));
+    let normal = apca_contrast(text, background).abs();
+    let glare = apca_contrast(bright.apply(text), bright.apply(background)).abs();
+    assert!(glare < normal, "{glare} vs {normal}");
+}
+
+#[test]
+fn test_gamma_24_darkens_midtones_only() {
+    let gamma = DisplaySimulation::SrgbGamma24;
+    assert_eq!(gamma.apply(BLACK), BLACK);
+    assert_eq!(gamma.apply(WHITE), WHITE);
+    assert!(gamma.apply(GRAY).red < 128);
+}
+
+#[test]
+fn test_apply_scheme_transforms_every_slot() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let simulated = DisplaySimulation::Dim.apply_scheme(&scheme);
+
+    assert_eq!(simulated.palette.len(), scheme.palette.len());
+    assert_eq!(simulated.name, scheme.name);
+    assert_ne!(
+        simulated.palette["base07"].rgb,
+        scheme.palette["base07"].rgb
+    );
+}
+
+#[test]
+fn test_cli_simulate_only_changes_preview() {
+    let args = ["-b", "#1a1a2e", "-f", "#eaeaea", "--name", "Sim"];
+    let plain = cargo_bin_cmd!("themalingadingdong")
+        .args(args)
+        .output()
+        .unwrap();
+    let simulated = cargo_bin_cmd!("themalingadingdong")
+        .args(args)
+        .args(["--simulate", "bright"])
+        .output()
+        .unwrap();
+    assert_eq!(plain.stdout, simulated.stdout);
+
+    cargo_bin_cmd!("themalingadingdong")
+        .args(args)
+        .args(["--simulate", "srgb-gamma-2.4", "preview"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "Simulating srgb-gamma-2.4 display",
+        ))
+        // base00 #1a1a2e as a gamma 2.4 display shows it
+        .stdout(predicate::str::contains("0d0d23"));
+}
@@ -0,0 +1,147 @@
+//! Tests for environment variable and `~` expansion in config values.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::config_schema::check_config;
+use themalingadingdong::env_expand::{expand_with, is_verbatim};
+
+fn env(name: &str) -> Option<String> {
+    match name {
+        "HOME" => Some("/home/me".to_string()),
+        "EMPTY" => Some(String::new()),
+        "NAME" => Some("dusk".to_string()),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_expansion_rules() {
+    for (text, expected) in [
+        ("plain #1d2021", "plain #1d2021"),
+        ("${NAME}-light", "dusk-light"),
+        ("$NAME.yaml", "dusk.yaml"),
+        ("${EMPTY:-fallback}", "fallback"),
+        ("${EMPTY}", ""),
+        ("${MISSING:-}", ""),
+        ("~", "/home/me"),
+        ("~/themes", "/home/me/themes"),
+        // Only a leading `~` before a slash is the home directory
+        ("~user/themes", "~user/themes"),
+        ("themes/~", "themes/~"),
+        // A '$' without a name stays
+        ("$", "$"),
+        ("US$5", "US$5"),
+        ("$$NAME", "$NAME"),
+    ] {
+        assert_eq!(expand_with(text, env).unwrap(), expected, "{text}");
+    }
+
+    for (text, expected) in [
+        ("$MISSING", "MISSING is not set"),
+        ("${MISSING}", "MISSING is not set"),
+        ("${NAME", "unclosed"),
+        ("${1NAME}", "invalid variable name"),
+    ] {
+        let error = expand_with(text, env).unwrap_err();
+        assert!(error.contains(expected), "{text}: {error}");
+    }
+    assert!(expand_with("~/x", |_| None).is_err());
+
+    assert!(is_verbatim("hooks.post_generate"));
+    assert!(is_verbatim("profile.night.hooks.post_generate"));
+    assert!(!is_verbatim("colors.background"));
+}
+
+#[test]
+fn test_check_reports_unset_variables() {
+    let diagnostics = check_config(
+        "[colors]\nbackground = \"${THEMALINGADINGDONG_TEST_UNSET}\"\nforeground = \"${THEMALINGADINGDONG_TEST_UNSET:-#ebdbb2}\"\n\n[hooks]\npost_generate = \"echo $THEMALINGADINGDONG_OUTPUT\"\n",
+    );
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(diagnostics[0].key, "colors.background");
+    assert!(diagnostics[0].message.contains("is not set"));
+
+    // The expanded color is what gets checked
+    let diagnostics =
+        check_config("[colors]\nbackground = \"${THEMALINGADINGDONG_TEST_UNSET:-nope}\"\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].key, "colors.background");
+}
+
+#[test]
+fn test_config_values_read_the_environment() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-env-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("theme.toml");
+    std::fs::write(
+        &config,
+        r##"
+[theme]
+name = "${THEME_NAME:-Fallback}"
+
+[colors]
+background = "$TERM_BG"
+foreground = "#ebdbb2"
+"##,
+    )
+    .unwrap();
+    let output = dir.join("env.yaml");
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("--config")
+        .arg(&config)
+        .arg("-o")
+        .arg(&output)
+        .env("TERM_BG", "#282828")
+        .env_remove("THEME_NAME")
+        .assert()
+        .success();
+    let scheme = std::fs::read_to_string(&output).unwrap();
+    assert!(scheme.contains("Fallback"), "{scheme}");
+    assert!(scheme.to_lowercase().contains("282828"), "{scheme}");
+
+    // An unset variable without a fallback is an error
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("--config")
+        .arg(&config)
+        .arg("-o")
+        .arg(&output)
+        .env_remove("TERM_BG")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("TERM_BG is not set"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_saved_config_keeps_references() {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-env-save-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("theme.toml");
+    std::fs::write(
+        &config,
+        "[colors]\nbackground = \"${TERM_BG}\"\nforeground = \"${TERM_FG:-#ebdbb2}\"\n",
+    )
+    .unwrap();
+    let saved = dir.join("saved.toml");
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("--config")
+        .arg(&config)
+        .args(["--name", "Env", "-f", "#fbf1c7", "--save-config"])
+        .arg(&saved)
+        .env("TERM_BG", "#102030")
+        .env_remove("TERM_FG")
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&saved).unwrap();
+    assert!(content.contains("background = \"${TERM_BG}\""), "{content}");
+    // A value overridden on the command line is saved as given
+    assert!(content.contains("foreground = \"#fbf1c7\""), "{content}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
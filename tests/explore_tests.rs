@@ -0,0 +1,147 @@
+//! Tests for parameter exploration (`--explore` and the TUI's shuffle).
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::config::{ConfigError, ThemeConfig};
+use themalingadingdong::config_schema::check_config;
+use themalingadingdong::curves::CurveType;
+use themalingadingdong::explore::{ExploreConfig, explore};
+use themalingadingdong::generate::GenerateConfig;
+use themalingadingdong::validation::ValidationConfig;
+
+fn distance(a: f32, b: f32) -> f32 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
+
+#[test]
+fn test_same_seed_gives_same_ranked_candidates() {
+    let base = GenerateConfig::default();
+    let validation = ValidationConfig::default();
+    let bounds = ExploreConfig {
+        hue_jitter: 10.0,
+        min_target_m: 20.0,
+        max_target_m: 25.0,
+        curve_types: vec![CurveType::Linear, CurveType::Sigmoid],
+        ..Default::default()
+    };
+    let first = explore(&base, &validation, &bounds, 6, 42);
+    let second = explore(&base, &validation, &bounds, 6, 42);
+    assert_eq!(first.len(), 6);
+    for (a, b) in first.iter().zip(&second) {
+        assert_eq!(a.params, b.params);
+        assert_eq!(a.score.score, b.score.score);
+    }
+    assert!(
+        first
+            .windows(2)
+            .all(|w| w[0].score.score >= w[1].score.score)
+    );
+
+    // Every candidate stays within the bounds around the generated hues
+    let hues: Vec<f32> = themalingadingdong::generate::generate(&base)
+        .base_accent_results
+        .iter()
+        .map(|r| r.hue)
+        .collect();
+    for candidate in &first {
+        let params = &candidate.params;
+        assert!((20.0..=25.0).contains(&params.target_m));
+        assert!(bounds.curve_types.contains(&params.lightness_curve));
+        assert!(bounds.curve_types.contains(&params.chroma_curve));
+        for (hue, base_hue) in params.hues.iter().zip(&hues) {
+            assert!(distance(*hue, *base_hue) <= 10.01, "{hue} vs {base_hue}");
+        }
+    }
+
+    let other = explore(&base, &validation, &bounds, 6, 43);
+    assert!(first.iter().zip(&other).any(|(a, b)| a.params != b.params));
+}
+
+#[test]
+fn test_bounds_are_validated() {
+    assert!(ExploreConfig::default().validate().is_ok());
+    for (bounds, expected) in [
+        (
+            ExploreConfig {
+                hue_jitter: 200.0,
+                ..Default::default()
+            },
+            "explore.hue_jitter",
+        ),
+        (
+            ExploreConfig {
+                min_target_m: 40.0,
+                ..Default::default()
+            },
+            "explore.min_target_m",
+        ),
+        (
+            ExploreConfig {
+                curve_types: vec![CurveType::BSpline],
+                ..Default::default()
+            },
+            "b_spline",
+        ),
+        (
+            ExploreConfig {
+                count: 0,
+                ..Default::default()
+            },
+            "explore.count",
+        ),
+    ] {
+        assert!(matches!(
+            bounds.validate(),
+            Err(ConfigError::InvalidValue(ref msg)) if msg.contains(expected)
+        ));
+    }
+
+    // Default bounds are left out of written configs
+    let written = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!written.contains("[explore]"));
+
+    let diagnostics = check_config("[explore]\ncurve_types = [\"linear\", \"wobbly\"]\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 2);
+    assert!(diagnostics[0].key.starts_with("explore.curve_types"));
+}
+
+#[test]
+fn test_cli_lists_candidates_and_picks_a_rank() {
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Explored"])
+        .args([
+            "--explore",
+            "6",
+            "--explore-seed",
+            "1",
+            "--explore-pick",
+            "2",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(
+        stderr.contains("Explored 6 candidates (seed 1)"),
+        "{stderr}"
+    );
+    assert!(stderr.contains(">  2."), "{stderr}");
+    assert!(stderr.contains("--explore-seed 1"), "{stderr}");
+
+    // A pick past the listed candidates fails
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Explored"])
+        .args([
+            "--explore",
+            "3",
+            "--explore-seed",
+            "1",
+            "--explore-pick",
+            "4",
+        ])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,130 @@
+use palette::Srgb;
+use themalingadingdong::extended_accents::{ExtendedAccentMode, ExtendedAccents};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::interpolation::AccentResult;
+
+fn gruvbox(extended_accents: ExtendedAccents) -> GenerateConfig {
+    GenerateConfig {
+        background: Srgb::new(0x1d, 0x20, 0x21),
+        foreground: Srgb::new(0xeb, 0xdb, 0xb2),
+        extended_accents,
+        ..Default::default()
+    }
+}
+
+fn hue_distance(a: f32, b: f32) -> f32 {
+    ((a - b + 180.0).rem_euclid(360.0) - 180.0).abs()
+}
+
+#[test]
+fn test_darker_keeps_hue_and_lowers_lightness() {
+    let result = generate(&gruvbox(ExtendedAccents {
+        mode: ExtendedAccentMode::Darker,
+        amount: Some(10.0),
+    }));
+
+    for (base, extended) in result
+        .base_accent_results
+        .iter()
+        .zip(&result.extended_accent_results)
+    {
+        assert!(hue_distance(base.jmh.hue, extended.jmh.hue) < 1.0);
+        let drop = base.jmh.lightness - extended.jmh.lightness;
+        assert!((drop - 10.0).abs() < 0.5, "J' dropped by {drop}");
+    }
+}
+
+#[test]
+fn test_desaturated_reduces_colorfulness() {
+    let result = generate(&gruvbox(ExtendedAccents {
+        mode: ExtendedAccentMode::Desaturated,
+        amount: None,
+    }));
+
+    for (i, (base, extended)) in result
+        .base_accent_results
+        .iter()
+        .zip(&result.extended_accent_results)
+        .enumerate()
+    {
+        let slot = |prefix: &str, offset: usize| {
+            let c = &result.scheme.palette[&format!("{prefix}{:X}", offset + i)];
+            HellwigJmh::from_srgb_u8(Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2))
+        };
+        assert!(hue_distance(base.jmh.hue, extended.jmh.hue) < 1.0);
+        assert!((base.jmh.lightness - extended.jmh.lightness).abs() < 0.5);
+        assert!(slot("base1", 0).colorfulness < slot("base0", 8).colorfulness);
+    }
+}
+
+#[test]
+fn test_independent_mode_is_the_default() {
+    let default = generate(&GenerateConfig::default()).scheme;
+    let independent = generate(&GenerateConfig {
+        extended_accents: ExtendedAccents {
+            mode: ExtendedAccentMode::Independent,
+            amount: Some(25.0),
+        },
+        ..Default::default()
+    })
+    .scheme;
+    for (slot, color) in &default.palette {
+        assert_eq!(color.hex, independent.palette[slot].hex, "{slot}");
+    }
+}
+
+#[test]
+fn test_modes_cycle() {
+    let mut mode = ExtendedAccentMode::Independent;
+    for _ in 0..ExtendedAccentMode::ALL.len() {
+        assert_eq!(mode.next().prev(), mode);
+        mode = mode.next();
+    }
+    assert_eq!(mode, ExtendedAccentMode::Independent);
+}
+
+#[test]
+fn test_paired_keeps_offset_toward_background() {
+    let result = generate(&gruvbox(ExtendedAccents {
+        mode: ExtendedAccentMode::Paired,
+        amount: None,
+    }));
+
+    for (base, extended) in result
+        .base_accent_results
+        .iter()
+        .zip(&result.extended_accent_results)
+    {
+        assert!(hue_distance(base.jmh.hue, extended.jmh.hue) < 1.0);
+        let offset = base.jmh.lightness - extended.jmh.lightness;
+        assert!((offset - 10.0).abs() < 0.5, "offset {offset}");
+    }
+}
+
+#[test]
+fn test_paired_raises_shared_lightness_to_meet_floor() {
+    let settings = |mode| ExtendedAccents {
+        mode,
+        amount: Some(20.0),
+    };
+    let config = |mode| GenerateConfig {
+        extended_min_contrast: 70.0,
+        ..gruvbox(settings(mode))
+    };
+    let darker = generate(&config(ExtendedAccentMode::Darker));
+    let paired = generate(&config(ExtendedAccentMode::Paired));
+
+    assert!(
+        darker
+            .extended_accent_results
+            .iter()
+            .any(|r| !r.met_minimum)
+    );
+    assert!(paired.extended_accent_results.iter().all(|r| r.met_minimum));
+
+    let mean_j = |results: &[AccentResult]| {
+        results.iter().map(|r| r.jmh.lightness).sum::<f32>() / results.len() as f32
+    };
+    assert!(mean_j(&paired.base_accent_results) > mean_j(&darker.base_accent_results));
+}
@@ -0,0 +1,118 @@
+//! Tests for configs that extend an existing scheme.
+
+use std::path::{Path, PathBuf};
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::config::{ConfigOverrides, load_config, load_config_with_profile};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::import::resolve_extends;
+
+/// A fresh directory holding `parent.yaml`, a generated scheme.
+fn scheme_dir(name: &str) -> (PathBuf, tinted_builder::Base16Scheme) {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-extends-{}-{name}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    std::fs::write(
+        dir.join("parent.yaml"),
+        OutputFormat::Yaml.serialize(&scheme).unwrap(),
+    )
+    .unwrap();
+    (dir, scheme)
+}
+
+fn hex(scheme: &tinted_builder::Base16Scheme, slot: &str) -> String {
+    let (r, g, b) = scheme.palette[slot].rgb;
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+#[test]
+fn test_config_overrides_the_extended_scheme() {
+    let (dir, scheme) = scheme_dir("overrides");
+    let path = dir.join("child.toml");
+    std::fs::write(
+        &path,
+        r#"
+extends = "parent.yaml"
+
+[theme]
+name = "Child"
+
+[contrast]
+minimum = 60.0
+"#,
+    )
+    .unwrap();
+
+    let config = load_config(Some(&path), &ConfigOverrides::default()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(config.theme.name, "Child");
+    assert_eq!(config.contrast.minimum, 60.0);
+    assert_eq!(config.colors.background, Some(hex(&scheme, "base00")));
+    assert_eq!(config.colors.foreground, Some(hex(&scheme, "base07")));
+    assert!(config.colors.hue_overrides.is_some());
+}
+
+#[test]
+fn test_extends_by_slug_in_profile() {
+    let (dir, scheme) = scheme_dir("slug");
+    let path = dir.join("family.toml");
+    std::fs::write(
+        &path,
+        r##"
+[colors]
+background = "#101010"
+foreground = "#f0f0f0"
+
+[profile.child]
+extends = "parent"
+"##,
+    )
+    .unwrap();
+
+    let shared = load_config(Some(&path), &ConfigOverrides::default()).unwrap();
+    let child =
+        load_config_with_profile(Some(&path), Some("child"), &ConfigOverrides::default()).unwrap();
+    assert_eq!(shared.colors.background.as_deref(), Some("#101010"));
+    // The shared colors still override the extended scheme's
+    assert_eq!(child.colors.background.as_deref(), Some("#101010"));
+    assert_eq!(child.theme.name, scheme.name);
+
+    assert_eq!(
+        resolve_extends("parent", &dir, None).unwrap(),
+        dir.join("parent.yaml")
+    );
+    let err = resolve_extends("missing", &dir, None)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no scheme named 'missing'"), "{err}");
+    let err = resolve_extends("missing.yaml", &dir, None)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not found"), "{err}");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_extends_by_slug_next_to_relative_config() {
+    let (dir, scheme) = scheme_dir("relative");
+    std::fs::write(dir.join("child.toml"), "extends = \"parent\"\n").unwrap();
+
+    cargo_bin_cmd!("themalingadingdong")
+        .current_dir(&dir)
+        .args(["--config", "child.toml", "-o", "child.yaml"])
+        .assert()
+        .success();
+    let child = std::fs::read_to_string(dir.join("child.yaml")).unwrap();
+    assert!(child.contains(&scheme.name), "{child}");
+
+    // An empty directory is the working directory, not an unreadable one
+    let err = resolve_extends("missing", Path::new(""), None)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no scheme named 'missing' in ."), "{err}");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,163 @@
+//! Tests for the C ABI (`ffi` feature).
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use themalingadingdong::builder::ThemeBuilder;
+use themalingadingdong::ffi::{
+    TMD_SLOTS, TmdPalette, TmdStatus, TmdValidation, tmd_generate, tmd_generate_config,
+    tmd_last_error, tmd_validate, tmd_validation_free,
+};
+use themalingadingdong::validation::validate;
+use tinted_builder::Base16Scheme;
+
+fn hex(palette: &TmdPalette, index: usize) -> String {
+    unsafe { CStr::from_ptr(palette.colors[index].as_ptr()) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+fn last_error() -> String {
+    let message = tmd_last_error();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn theme_scheme() -> Base16Scheme {
+    ThemeBuilder::new()
+        .background("#1a1a2e")
+        .foreground("#eaeaea")
+        .build()
+        .unwrap()
+        .into_scheme()
+}
+
+#[test]
+fn test_generate_matches_builder() {
+    let bg = CString::new("#1a1a2e").unwrap();
+    let fg = CString::new("#eaeaea").unwrap();
+    let mut palette = TmdPalette::default();
+    assert_eq!(
+        unsafe { tmd_generate(bg.as_ptr(), fg.as_ptr(), &mut palette) },
+        TmdStatus::Ok
+    );
+
+    let theme = ThemeBuilder::new()
+        .background("#1a1a2e")
+        .foreground("#eaeaea")
+        .build()
+        .unwrap();
+    for index in 0..TMD_SLOTS {
+        let slot = format!("base{index:02X}");
+        assert_eq!(Some(hex(&palette, index)), theme.hex(&slot), "{slot}");
+    }
+    assert!(!palette.is_light);
+    assert_eq!(palette.warning_count as usize, theme.warnings().len());
+}
+
+#[test]
+fn test_generate_from_config() {
+    let config = CString::new(
+        r##"
+[colors]
+background = "#faf4ed"
+foreground = "#282430"
+
+[colors.hue_overrides]
+base08 = 20.0
+"##,
+    )
+    .unwrap();
+    let mut palette = TmdPalette::default();
+    assert_eq!(
+        unsafe { tmd_generate_config(config.as_ptr(), &mut palette) },
+        TmdStatus::Ok
+    );
+    assert_eq!(hex(&palette, 0), "faf4ed");
+    assert!(palette.is_light);
+}
+
+#[test]
+fn test_validate_reports_checks() {
+    let bg = CString::new("#1a1a2e").unwrap();
+    let fg = CString::new("#eaeaea").unwrap();
+    let mut palette = TmdPalette::default();
+    unsafe { tmd_generate(bg.as_ptr(), fg.as_ptr(), &mut palette) };
+
+    let mut validation = TmdValidation::default();
+    assert_eq!(
+        unsafe { tmd_validate(&palette, &mut validation) },
+        TmdStatus::Ok
+    );
+    let checks = unsafe { std::slice::from_raw_parts(validation.checks, validation.len) };
+    assert!(validation.required > 0 && validation.required <= validation.len);
+    let expected = validate(&theme_scheme());
+    assert_eq!(
+        validation.len,
+        expected.required.len() + expected.reference.len()
+    );
+    assert_eq!(
+        validation.required_passed,
+        expected.required.iter().filter(|r| r.passes).count()
+    );
+    assert!(checks[..validation.required].iter().all(|c| c.required));
+    assert!(checks[validation.required..].iter().all(|c| !c.required));
+    // base07 on base00 is among the required checks
+    let body = checks
+        .iter()
+        .find(|c| c.foreground == 7 && c.background == 0)
+        .unwrap();
+    assert!(body.passes && body.contrast.abs() >= body.min_contrast);
+    assert!(body.wcag_ratio > 4.5);
+
+    unsafe { tmd_validation_free(&mut validation) };
+    assert!(validation.checks.is_null());
+    unsafe { tmd_validation_free(&mut validation) };
+    unsafe { tmd_validation_free(ptr::null_mut()) };
+}
+
+#[test]
+fn test_errors_are_reported() {
+    let bg = CString::new("not a color").unwrap();
+    let fg = CString::new("#eaeaea").unwrap();
+    let mut palette = TmdPalette::default();
+    assert_eq!(
+        unsafe { tmd_generate(bg.as_ptr(), fg.as_ptr(), &mut palette) },
+        TmdStatus::InvalidConfig
+    );
+    assert!(last_error().contains("not a color"));
+
+    assert_eq!(
+        unsafe { tmd_generate(ptr::null(), fg.as_ptr(), &mut palette) },
+        TmdStatus::NullArgument
+    );
+    assert!(last_error().contains("background"));
+
+    let bad_utf8 = [0xffu8 as std::ffi::c_char, 0];
+    assert_eq!(
+        unsafe { tmd_generate(bad_utf8.as_ptr(), fg.as_ptr(), &mut palette) },
+        TmdStatus::InvalidUtf8
+    );
+
+    let config = CString::new("[colors]\nbackground = 3").unwrap();
+    assert_eq!(
+        unsafe { tmd_generate_config(config.as_ptr(), &mut palette) },
+        TmdStatus::InvalidConfig
+    );
+
+    let valid = CString::new("#1a1a2e").unwrap();
+    unsafe { tmd_generate(valid.as_ptr(), fg.as_ptr(), &mut palette) };
+    let mut validation = TmdValidation::default();
+    palette.colors[3] = [b'z' as std::ffi::c_char; 7];
+    palette.colors[3][6] = 0;
+    assert_eq!(
+        unsafe { tmd_validate(&palette, &mut validation) },
+        TmdStatus::InvalidConfig
+    );
+    let error = last_error();
+    assert!(error.starts_with("base03"), "{error}");
+}
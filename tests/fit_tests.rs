@@ -0,0 +1,45 @@
+//! Tests for fitting generator parameters to existing schemes.
+
+use palette::Srgb;
+use themalingadingdong::curves::CurveType;
+use themalingadingdong::fit::fit_scheme;
+use themalingadingdong::generate::{GenerateConfig, generate};
+
+#[test]
+fn fit_recovers_generated_scheme() {
+    let mut original = GenerateConfig {
+        background: Srgb::new(29, 32, 33),
+        foreground: Srgb::new(235, 219, 178),
+        name: "Round Trip".to_string(),
+        ..Default::default()
+    };
+    original.interpolation.lightness.curve_type = CurveType::SmoothEnd;
+    original.accent_opt.target_j = 70.0;
+    let target = generate(&original).scheme;
+
+    let fit = fit_scheme(&target).unwrap();
+
+    assert_eq!(fit.residuals.len(), 24);
+    assert_eq!(fit.config.background, original.background);
+    assert_eq!(fit.config.foreground, original.foreground);
+    assert_eq!(
+        fit.config.interpolation.lightness.curve_type,
+        CurveType::SmoothEnd
+    );
+    assert!(
+        fit.mean_error() < 3.0,
+        "mean delta E {:.2} too large",
+        fit.mean_error()
+    );
+}
+
+#[test]
+fn fit_reports_residual_per_slot() {
+    let target = generate(&GenerateConfig::default()).scheme;
+    let fit = fit_scheme(&target).unwrap();
+
+    let base00 = fit.residuals.iter().find(|r| r.slot == "base00").unwrap();
+    assert_eq!(base00.target, base00.fitted);
+    assert!(base00.delta_e < 1e-3);
+    assert!(fit.max_error() >= fit.mean_error());
+}
@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use palette::Srgb;
 use themalingadingdong::curves::InterpolationConfig;
-use themalingadingdong::generate::{GenerateConfig, generate, parse_color};
+use themalingadingdong::generate::{GenerateConfig, generate, generate_for_variant, parse_color};
 use themalingadingdong::interpolation::{DEFAULT_BASE16_HUES, build_hues_with_overrides};
+use tinted_builder::SchemeVariant;
 
 #[test]
 fn test_parse_hex_with_hash() {
@@ -124,3 +127,302 @@ fn test_generate_light_variant() {
         "Expected Light variant for light background"
     );
 }
+
+#[test]
+fn test_comment_contrast_lands_in_range() {
+    use themalingadingdong::apca::apca_contrast;
+    use themalingadingdong::config::CommentContrast;
+
+    let range = CommentContrast {
+        min: 40.0,
+        max: 55.0,
+    };
+    for (bg, fg) in [
+        (Srgb::new(26u8, 26, 46), Srgb::new(234u8, 234, 234)),
+        (Srgb::new(250u8, 250, 250), Srgb::new(30u8, 30, 30)),
+    ] {
+        let config = GenerateConfig {
+            background: bg,
+            foreground: fg,
+            comment_contrast: Some(range),
+            ..Default::default()
+        };
+
+        let result = generate(&config);
+        let get = |name: &str| {
+            let c = &result.scheme.palette[name];
+            Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2)
+        };
+        let lc = apca_contrast(get("base03"), get("base00")).abs();
+        assert!(
+            lc >= range.min - 0.5 && lc <= range.max + 0.5,
+            "base03 Lc {lc:.1} outside {:.0}-{:.0}",
+            range.min,
+            range.max
+        );
+        assert!(!result.warnings.iter().any(|w| w.starts_with("base03")));
+    }
+}
+
+#[test]
+fn test_comment_contrast_keeps_surfaces_in_order() {
+    use themalingadingdong::config::CommentContrast;
+    use themalingadingdong::hellwig::HellwigJmh;
+
+    // Past base04's contrast, so base03 can't reach the range
+    let config = GenerateConfig {
+        background: Srgb::new(0x1d, 0x20, 0x21),
+        foreground: Srgb::new(0xeb, 0xdb, 0xb2),
+        comment_contrast: Some(CommentContrast {
+            min: 65.0,
+            max: 70.0,
+        }),
+        ..Default::default()
+    };
+
+    let result = generate(&config);
+    let lightness = |name: &str| {
+        let c = &result.scheme.palette[name];
+        HellwigJmh::from_srgb_u8(Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2)).lightness
+    };
+    assert!(lightness("base02") <= lightness("base03"));
+    assert!(lightness("base03") <= lightness("base04"));
+    assert!(result.warnings.iter().any(|w| w.starts_with("base03")));
+}
+
+#[test]
+fn test_foreground_headroom_extends_ramp() {
+    use themalingadingdong::hellwig::hellwig_lightness;
+
+    let lightness = |config: &GenerateConfig, slot: &str| {
+        let (r, g, b) = generate(config).scheme.palette[slot].rgb;
+        hellwig_lightness(Srgb::new(r, g, b))
+    };
+
+    for (bg, fg) in [
+        (Srgb::new(29u8, 32, 33), Srgb::new(168u8, 153, 132)),
+        (Srgb::new(251u8, 241, 199), Srgb::new(102u8, 92, 84)),
+    ] {
+        let plain = GenerateConfig {
+            background: bg,
+            foreground: fg,
+            ..Default::default()
+        };
+        let extended = GenerateConfig {
+            foreground_headroom: 15.0,
+            ..plain.clone()
+        };
+
+        // base07 moves ~15 J' further from base00 than the foreground
+        let gained = (lightness(&extended, "base07") - lightness(&plain, "base07")).abs();
+        assert!((gained - 15.0).abs() < 1.5, "gained {gained:.1} J'");
+        assert!(
+            generate(&extended)
+                .warnings
+                .iter()
+                .all(|w| !w.contains("headroom"))
+        );
+    }
+
+    // Near-white foregrounds cannot gain the full amount and say so
+    let capped = GenerateConfig {
+        foreground: Srgb::new(245u8, 245, 245),
+        foreground_headroom: 20.0,
+        ..Default::default()
+    };
+    assert!(
+        generate(&capped)
+            .warnings
+            .iter()
+            .any(|w| w.contains("headroom"))
+    );
+}
+
+#[test]
+fn test_pinned_slots_are_kept_exactly() {
+    use themalingadingdong::hellwig::HellwigJmh;
+    use themalingadingdong::hue_spacing::HueSpacing;
+
+    let pin = Srgb::new(0x82u8, 0xaa, 0xff);
+    let config = GenerateConfig {
+        background: Srgb::new(0x1du8, 0x20, 0x21),
+        foreground: Srgb::new(0xebu8, 0xdb, 0xb2),
+        hue_spacing: Some(HueSpacing::default()),
+        pins: [
+            ("base0D".to_string(), pin),
+            ("base02".to_string(), Srgb::new(0x40, 0x40, 0x40)),
+        ]
+        .into(),
+        ..Default::default()
+    };
+    let result = generate(&config);
+
+    assert_eq!(
+        result.scheme.palette["base0D"].to_hex().to_lowercase(),
+        "82aaff"
+    );
+    assert_eq!(
+        result.scheme.palette["base02"].to_hex().to_lowercase(),
+        "404040"
+    );
+
+    // The pinned hue is not moved by spacing, and its bright variant follows it
+    let pinned_hue = HellwigJmh::from_srgb_u8(pin).hue;
+    assert!((result.base_accent_results[5].hue - pinned_hue).abs() < 0.01);
+    assert!((result.extended_accent_results[5].hue - pinned_hue).abs() < 1.0);
+}
+
+#[test]
+fn test_pinned_accent_below_minimum_warns() {
+    let config = GenerateConfig {
+        background: Srgb::new(0x1du8, 0x20, 0x21),
+        foreground: Srgb::new(0xebu8, 0xdb, 0xb2),
+        pins: [("base08".to_string(), Srgb::new(0x30u8, 0x10, 0x10))].into(),
+        ..Default::default()
+    };
+    let result = generate(&config);
+
+    assert!(!result.base_accent_results[0].met_minimum);
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|w| w.starts_with("base08 (pinned)"))
+    );
+}
+
+#[test]
+fn test_anchored_accent_stays_near_brand_color() {
+    use themalingadingdong::config::{AccentAnchor, AccentOptSettings};
+    use themalingadingdong::hellwig::HellwigJmh;
+
+    let brand = HellwigJmh::from_srgb_u8(Srgb::new(0x09u8, 0x69, 0xda));
+    let config = GenerateConfig {
+        background: Srgb::new(0xffu8, 0xff, 0xff),
+        foreground: Srgb::new(0x1fu8, 0x23, 0x28),
+        accent_opt: AccentOptSettings {
+            anchors: [("base0D".to_string(), AccentAnchor::new("#0969da"))].into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result = generate(&config);
+
+    let accent = &result.base_accent_results[5];
+    assert!((accent.hue - brand.hue).abs() < 0.01);
+    assert!(accent.jmh.delta_e(&brand) <= 5.5);
+}
+
+#[test]
+fn test_adaptive_hues_avoid_tinted_background() {
+    // #1a1a2e is tinted at the purple accent's hue
+    let config = GenerateConfig {
+        background: Srgb::new(0x1au8, 0x1a, 0x2e),
+        ..Default::default()
+    };
+    let adapted = generate(&config);
+    assert!((adapted.base_accent_results[6].hue - DEFAULT_BASE16_HUES[6]).abs() > 10.0);
+    // Pushed toward magenta, which has more room than blue
+    assert!(adapted.base_accent_results[6].hue > DEFAULT_BASE16_HUES[6]);
+
+    // Both variants avoid the configured background, so they share hues
+    let light = generate_for_variant(&config, Some(SchemeVariant::Light));
+    for (dark, light) in adapted
+        .base_accent_results
+        .iter()
+        .zip(&light.base_accent_results)
+    {
+        assert_eq!(dark.hue, light.hue);
+    }
+
+    let fixed = generate(&GenerateConfig {
+        adaptive_hues: false,
+        ..config.clone()
+    });
+    assert_eq!(fixed.base_accent_results[6].hue, DEFAULT_BASE16_HUES[6]);
+
+    // Any explicit override keeps every hue as given
+    let mut hue_overrides = [None; 8];
+    hue_overrides[0] = Some(30.0);
+    let overridden = generate(&GenerateConfig {
+        hue_overrides,
+        ..config
+    });
+    assert_eq!(
+        overridden.base_accent_results[6].hue,
+        DEFAULT_BASE16_HUES[6]
+    );
+}
+
+#[test]
+fn test_generate_reports_stage_timings() {
+    let timings = generate(&GenerateConfig::default()).timings;
+
+    assert!(timings.accents > Duration::ZERO);
+    assert!(timings.extended_accents > Duration::ZERO);
+    let sum: Duration = timings.stages().iter().map(|(_, d)| *d).sum();
+    assert_eq!(timings.total(), sum);
+    assert_eq!(timings.stages()[2], ("accents", timings.accents));
+}
+
+#[test]
+fn test_generation_cache_recomputes_only_downstream_stages() {
+    use themalingadingdong::extended_accents::{ExtendedAccentMode, ExtendedAccents};
+    use themalingadingdong::generate::GenerationCache;
+    use themalingadingdong::ramp::LightnessRamp;
+
+    let mut cache = GenerationCache::new();
+    let mut config = GenerateConfig::default();
+    cache.generate(&config);
+    assert_eq!(cache.recomputed().len(), 4);
+
+    cache.generate(&config);
+    assert!(cache.recomputed().is_empty());
+
+    config.ramp = LightnessRamp::from_targets([None, Some(20.0), None, None, None, None]);
+    cache.generate(&config);
+    assert_eq!(cache.recomputed(), ["ui ramp"]);
+
+    // Independent extended accents don't depend on the base accents
+    config.min_contrast = 70.0;
+    cache.generate(&config);
+    assert_eq!(cache.recomputed(), ["accents"]);
+
+    // Derived ones do
+    config.extended_accents = ExtendedAccents {
+        mode: ExtendedAccentMode::Darker,
+        amount: None,
+    };
+    cache.generate(&config);
+    assert_eq!(cache.recomputed(), ["extended accents"]);
+    config.min_contrast = 65.0;
+    cache.generate(&config);
+    assert_eq!(cache.recomputed(), ["accents", "extended accents"]);
+
+    config.background = Srgb::new(0x1d, 0x20, 0x21);
+    cache.generate(&config);
+    assert_eq!(cache.recomputed().len(), 4);
+}
+
+#[test]
+fn test_generation_cache_matches_uncached_generation() {
+    use themalingadingdong::generate::GenerationCache;
+
+    let mut cache = GenerationCache::new();
+    let mut config = GenerateConfig::default();
+    let edits: [fn(&mut GenerateConfig); 4] = [
+        |c| c.extended_min_contrast = 45.0,
+        |c| c.hue_overrides[2] = Some(100.0),
+        |c| c.accent_opt.target_m = 30.0,
+        |c| c.foreground = Srgb::new(0xdd, 0xdd, 0xcc),
+    ];
+    for edit in edits {
+        edit(&mut config);
+        let cached = cache.generate(&config);
+        let fresh = generate(&config);
+        for (slot, color) in &fresh.scheme.palette {
+            assert_eq!(cached.scheme.palette[slot].rgb, color.rgb, "{slot}");
+        }
+        assert_eq!(cached.warnings, fresh.warnings);
+    }
+}
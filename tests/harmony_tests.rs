@@ -0,0 +1,115 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::config::{ThemeConfig, validate_config};
+use themalingadingdong::harmony::{Harmony, HarmonyConfig};
+use themalingadingdong::interpolation::DEFAULT_BASE16_HUES;
+
+fn distance(a: f32, b: f32) -> f32 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
+
+#[test]
+fn test_rules_cluster_around_their_centers() {
+    let cases: [(Harmony, &[f32], f32); 5] = [
+        (Harmony::Analogous, &[0.0], 52.5),
+        (Harmony::Complementary, &[0.0, 180.0], 30.0),
+        (Harmony::SplitComplementary, &[0.0, 150.0, 210.0], 20.0),
+        (Harmony::Triadic, &[0.0, 120.0, 240.0], 20.0),
+        (Harmony::Tetradic, &[0.0, 90.0, 180.0, 270.0], 12.5),
+    ];
+    for (rule, centers, reach) in cases {
+        let hues = rule.hues(200.0);
+        for hue in hues {
+            assert!(
+                centers
+                    .iter()
+                    .any(|c| distance(hue, 200.0 + c) <= reach + 1e-3),
+                "{rule}: {hue}"
+            );
+        }
+        // No two accents share a hue
+        for i in 0..8 {
+            for j in i + 1..8 {
+                assert!(distance(hues[i], hues[j]) >= 10.0, "{rule}: {hues:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_hues_keep_wheel_order_near_defaults() {
+    let hues = Harmony::Triadic.hues(DEFAULT_BASE16_HUES[0]);
+    // base08 takes the hue nearest red
+    assert!(
+        distance(hues[0], DEFAULT_BASE16_HUES[0]) <= 20.0,
+        "{hues:?}"
+    );
+    // Going around the wheel from base08 passes the slots in order
+    let turns: Vec<f32> = hues
+        .iter()
+        .map(|h| (h - hues[0]).rem_euclid(360.0))
+        .collect();
+    assert!(turns.windows(2).all(|p| p[0] < p[1]), "{turns:?}");
+}
+
+#[test]
+fn test_cycle_visits_every_rule() {
+    let mut rule = Harmony::cycle(None);
+    let mut seen = Vec::new();
+    while let Some(r) = rule {
+        seen.push(r);
+        rule = Harmony::cycle(rule);
+    }
+    assert_eq!(seen, Harmony::ALL);
+}
+
+#[test]
+fn test_harmony_config() {
+    let mut config: ThemeConfig = toml::from_str(
+        r##"
+[colors]
+background = "#1d2021"
+foreground = "#ebdbb2"
+
+[colors.harmony]
+rule = "split-complementary"
+seed_hue = 220.0
+
+[colors.hue_overrides]
+base0b = 140.0
+"##,
+    )
+    .unwrap();
+    assert!(validate_config(&config).is_ok());
+    let harmony = config.colors.harmony.unwrap();
+    assert_eq!(harmony.rule, Harmony::SplitComplementary);
+
+    let hues = config.to_generate_config().unwrap().hue_overrides;
+    let expected = harmony.hues();
+    for (i, hue) in hues.iter().enumerate() {
+        // Explicit overrides win over the harmony
+        let want = if i == 3 { 140.0 } else { expected[i] };
+        assert_eq!(*hue, Some(want));
+    }
+
+    config.colors.harmony = Some(HarmonyConfig {
+        rule: Harmony::Triadic,
+        seed_hue: Some(400.0),
+    });
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn test_harmony_flags() {
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Harmony"])
+        .args(["--harmony", "triadic", "--seed-hue", "220"])
+        .assert()
+        .success();
+
+    // A seed needs a rule
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--seed-hue", "220"])
+        .assert()
+        .failure();
+}
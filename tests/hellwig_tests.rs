@@ -1,8 +1,12 @@
 use approx::assert_relative_eq;
-use palette::Srgb;
+use palette::{LinSrgb, Srgb};
 use themalingadingdong::gamut_map::cusp_at_hue;
 use themalingadingdong::generated::{ECCENTRICITY_CAM16_LUT, ECCENTRICITY_LUT, HK_HUE_LUT};
-use themalingadingdong::hellwig::{HellwigJmh, eccentricity, hue_angle_dependency};
+use themalingadingdong::hellwig::{
+    HellwigJmh, batch_from_srgb, batch_into_linear_srgb, batch_into_srgb, eccentricity,
+    hue_angle_dependency,
+};
+use themalingadingdong::viewing::{ViewingConditions, with_viewing_conditions};
 
 #[test]
 fn eccentricity_stays_in_range() {
@@ -437,3 +441,86 @@ fn cusp_lut_interpolation_is_smooth() {
         worst_hue_m
     );
 }
+
+#[test]
+fn delta_e_ignores_hue_at_zero_colorfulness() {
+    let a = HellwigJmh::new(50.0, 0.0, 10.0);
+    let b = HellwigJmh::new(50.0, 0.0, 200.0);
+    assert!(a.delta_e(&b) < 1e-4);
+
+    // Opposite hues at equal M are 2M apart
+    let c = HellwigJmh::new(50.0, 20.0, 0.0);
+    let d = HellwigJmh::new(50.0, 20.0, 180.0);
+    assert!((c.delta_e(&d) - 40.0).abs() < 1e-3);
+}
+
+fn batch_test_colors() -> Vec<Srgb<f32>> {
+    // 37 colors: not a multiple of the SIMD lane count, so the tail is covered
+    (0u8..37)
+        .map(|i| {
+            Srgb::new(i.wrapping_mul(7), i.wrapping_mul(97), i.wrapping_mul(193)).into_format()
+        })
+        .collect()
+}
+
+#[test]
+fn batch_from_srgb_matches_scalar() {
+    let colors = batch_test_colors();
+    let mut batch = vec![HellwigJmh::new(0.0, 0.0, 0.0); colors.len()];
+    batch_from_srgb(&colors, &mut batch);
+
+    for (color, jmh) in colors.iter().zip(&batch) {
+        let scalar = HellwigJmh::from_srgb(*color);
+        assert_relative_eq!(jmh.lightness, scalar.lightness, epsilon = 1e-3);
+        assert_relative_eq!(jmh.colorfulness, scalar.colorfulness, epsilon = 1e-3);
+        if scalar.colorfulness > 0.1 {
+            assert_relative_eq!(jmh.hue, scalar.hue, epsilon = 1e-2);
+        }
+    }
+}
+
+#[test]
+fn batch_into_srgb_matches_scalar() {
+    let jmh: Vec<HellwigJmh> = batch_test_colors()
+        .into_iter()
+        .map(HellwigJmh::from_srgb)
+        .collect();
+    let mut linear = vec![LinSrgb::new(0.0, 0.0, 0.0); jmh.len()];
+    let mut srgb = vec![Srgb::new(0.0, 0.0, 0.0); jmh.len()];
+    batch_into_linear_srgb(&jmh, &mut linear);
+    batch_into_srgb(&jmh, &mut srgb);
+
+    for ((color, lin), rgb) in jmh.iter().zip(&linear).zip(&srgb) {
+        let scalar = color.into_linear_srgb_unclamped();
+        assert_relative_eq!(lin.red, scalar.red, epsilon = 1e-4);
+        assert_relative_eq!(lin.green, scalar.green, epsilon = 1e-4);
+        assert_relative_eq!(lin.blue, scalar.blue, epsilon = 1e-4);
+        let scalar = color.into_srgb();
+        assert_relative_eq!(rgb.red, scalar.red, epsilon = 1e-4);
+        assert_relative_eq!(rgb.green, scalar.green, epsilon = 1e-4);
+        assert_relative_eq!(rgb.blue, scalar.blue, epsilon = 1e-4);
+    }
+}
+
+#[test]
+fn batch_conversions_follow_viewing_conditions() {
+    let conditions = ViewingConditions {
+        adapting_luminance: 200.0,
+        ..ViewingConditions::default()
+    };
+    let colors = batch_test_colors();
+    with_viewing_conditions(&conditions, || {
+        let mut batch = vec![HellwigJmh::new(0.0, 0.0, 0.0); colors.len()];
+        batch_from_srgb(&colors, &mut batch);
+        for (color, jmh) in colors.iter().zip(&batch) {
+            assert_eq!(*jmh, HellwigJmh::from_srgb(*color));
+        }
+    });
+}
+
+#[test]
+#[should_panic]
+fn batch_rejects_mismatched_lengths() {
+    let mut out = vec![HellwigJmh::new(0.0, 0.0, 0.0); 2];
+    batch_from_srgb(&batch_test_colors(), &mut out);
+}
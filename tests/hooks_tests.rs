@@ -0,0 +1,80 @@
+//! Tests for post-generation hooks.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::config::{ConfigError, ThemeConfig};
+use themalingadingdong::hooks::HooksConfig;
+
+#[test]
+fn test_empty_hook_command_is_rejected() {
+    let hooks = HooksConfig {
+        post_generate: Some("  ".to_string()),
+    };
+    assert!(matches!(
+        hooks.validate(),
+        Err(ConfigError::InvalidValue(msg)) if msg.contains("hooks.post_generate")
+    ));
+    assert!(HooksConfig::default().validate().is_ok());
+
+    let config: ThemeConfig = toml::from_str("[hooks]\npost_generate = \"true\"").unwrap();
+    assert_eq!(config.hooks.post_generate.as_deref(), Some("true"));
+    // Unset hooks are left out of written configs
+    let written = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!written.contains("hooks"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_post_generate_hook_gets_path_and_summary() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-hooks-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("theme.toml");
+    std::fs::write(
+        &config,
+        r##"
+[theme]
+name = "Hooked"
+
+[colors]
+background = "#1d2021"
+foreground = "#ebdbb2"
+
+[hooks]
+post_generate = "echo \"$THEMALINGADINGDONG_OUTPUT\" > \"$(dirname \"$THEMALINGADINGDONG_OUTPUT\")/path.txt\" && cat > \"$(dirname \"$THEMALINGADINGDONG_OUTPUT\")/summary.json\""
+"##,
+    )
+    .unwrap();
+    let output = dir.join("hooked.yaml");
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("--config")
+        .arg(&config)
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .success();
+
+    let path = std::fs::read_to_string(dir.join("path.txt")).unwrap();
+    assert_eq!(path.trim(), output.to_str().unwrap());
+    let summary: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("summary.json")).unwrap()).unwrap();
+    assert_eq!(summary["event"], "post_generate");
+    assert_eq!(summary["name"], "Hooked");
+    assert_eq!(summary["output"], output.to_str().unwrap());
+    assert!(summary["passed"].is_boolean());
+
+    // A failing hook fails the run
+    std::fs::write(
+        &config,
+        "[colors]\nbackground = \"#1d2021\"\nforeground = \"#ebdbb2\"\n\n[hooks]\npost_generate = \"exit 3\"\n",
+    )
+    .unwrap();
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("--config")
+        .arg(&config)
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("post_generate hook"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,69 @@
+//! Tests for the standalone HTML accessibility report.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::cvd::DEFAULT_CVD_THRESHOLD;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::html_report::render_html;
+use themalingadingdong::provenance::Provenance;
+use themalingadingdong::validation::validate;
+
+#[test]
+fn test_report_covers_every_section() {
+    let config = GenerateConfig {
+        background: Srgb::new(0x1d, 0x20, 0x21),
+        foreground: Srgb::new(0xeb, 0xdb, 0xb2),
+        name: "<Tricky & Name>".to_string(),
+        ..Default::default()
+    };
+    let scheme = generate(&config).scheme;
+    let validation = validate(&scheme);
+    let provenance = Provenance::new(&config, None, OutputFormat::Yaml);
+    let html = render_html(
+        &scheme,
+        &validation,
+        DEFAULT_CVD_THRESHOLD,
+        Some(&provenance),
+    )
+    .unwrap();
+
+    assert!(html.contains("&lt;Tricky &amp; Name&gt; accessibility report"));
+    assert!(!html.contains("<Tricky"));
+    assert!(html.contains("background: #1d2021"));
+    // One row per check
+    let rows = validation.required.len() + validation.reference.len();
+    assert_eq!(html.matches("Sample text").count(), rows);
+    for section in [
+        "Contrast matrix",
+        "protanopia",
+        "deuteranopia",
+        "tritanopia",
+    ] {
+        assert!(html.contains(section), "missing {section}");
+    }
+    assert!(html.contains("<pre id=\"provenance\">"));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn test_report_html_written_next_to_output() {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-html-report-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("theme.yaml");
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "theme"])
+        .args(["--report", "html", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+
+    assert!(output.exists());
+    let html = std::fs::read_to_string(dir.join("theme.report.html")).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("&quot;version&quot;"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+
+use themalingadingdong::config::{ThemeConfig, validate_config};
+use themalingadingdong::config_schema::check_config;
+use themalingadingdong::hue_links::{HueLink, HueLinks, parse_link};
+use themalingadingdong::interpolation::DEFAULT_BASE16_HUES;
+
+fn links(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+        .iter()
+        .map(|(slot, link)| (slot.to_string(), link.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_parse_link() {
+    assert_eq!(
+        parse_link("base08 + 30"),
+        Ok(HueLink {
+            source: 0,
+            offset: 30.0
+        })
+    );
+    assert_eq!(
+        parse_link("base0d-45.5°"),
+        Ok(HueLink {
+            source: 5,
+            offset: -45.5
+        })
+    );
+    assert_eq!(parse_link("base0F").map(|l| l.offset), Ok(0.0));
+    assert!(parse_link("base05 + 30").is_err());
+    assert!(parse_link("base08 + lots").is_err());
+}
+
+#[test]
+fn test_parse_rejects_bad_links() {
+    for entries in [
+        [("base07", "base08 + 30")].as_slice(),
+        &[("base08", "base08 + 30")],
+        &[
+            ("base08", "base09 + 10"),
+            ("base09", "base0A"),
+            ("base0A", "base08"),
+        ],
+    ] {
+        assert!(HueLinks::parse(&links(entries)).is_err(), "{entries:?}");
+    }
+}
+
+#[test]
+fn test_resolve_chains_and_wraps() {
+    let links = HueLinks::parse(&links(&[
+        ("base0F", "base08 + 30"),
+        ("base0C", "base08 + 180"),
+        ("base0E", "base0F - 45"),
+    ]))
+    .unwrap();
+    let mut overrides = [None; 8];
+    overrides[0] = Some(350.0);
+    // base0F's own override is replaced by the link
+    overrides[7] = Some(100.0);
+
+    let hues = links.resolve(overrides);
+    assert_eq!(hues[0], Some(350.0));
+    assert_eq!(hues[7], Some(20.0));
+    assert_eq!(hues[4], Some(170.0));
+    assert_eq!(hues[6], Some(335.0));
+    assert_eq!(hues[1], None);
+
+    // An unset source starts from its default hue
+    let defaults = links.resolve([None; 8]);
+    assert_eq!(defaults[7], Some(DEFAULT_BASE16_HUES[0] + 30.0));
+}
+
+#[test]
+fn test_editing_either_end_keeps_offsets() {
+    let links = HueLinks::parse(&links(&[
+        ("base0F", "base08 + 30"),
+        ("base0C", "base08 + 180"),
+    ]))
+    .unwrap();
+    let mut hues = links.resolve([Some(20.0), None, None, None, None, None, None, None]);
+
+    // Editing the target moves its source, and the source's other links
+    hues[7] = Some(60.0);
+    links.propagate(&mut hues, 7);
+    assert_eq!(hues[0], Some(30.0));
+    assert_eq!(hues[4], Some(210.0));
+    assert_eq!(hues[7], Some(60.0));
+
+    // Editing the source moves everything linked to it
+    hues[0] = Some(0.0);
+    links.propagate(&mut hues, 0);
+    assert_eq!(hues[7], Some(30.0));
+    assert_eq!(hues[4], Some(180.0));
+
+    // Unlinked hues are untouched
+    hues[3] = Some(140.0);
+    links.propagate(&mut hues, 3);
+    assert_eq!(hues[3], Some(140.0));
+    assert_eq!(hues[0], Some(0.0));
+}
+
+#[test]
+fn test_hue_links_config() {
+    let source = r##"
+[colors]
+background = "#1d2021"
+foreground = "#ebdbb2"
+
+[colors.hue_overrides]
+base08 = 10.0
+
+[colors.hue_links]
+base0C = "base08 + 180"
+"##;
+    let mut config: ThemeConfig = toml::from_str(source).unwrap();
+    assert!(validate_config(&config).is_ok());
+    assert_eq!(check_config(source), vec![]);
+    let generate = config.to_generate_config().unwrap();
+    assert_eq!(generate.hue_overrides[4], Some(190.0));
+
+    config.colors.hue_links = Some(links(&[("base0C", "base0C + 10")]));
+    assert!(validate_config(&config).is_err());
+    assert!(config.to_generate_config().is_err());
+
+    let diagnostics =
+        check_config("[colors.hue_links]\nbase05 = \"base08 + 30\"\nbase0F = \"red + 30\"\n");
+    let keys: Vec<&str> = diagnostics.iter().map(|d| d.key.as_str()).collect();
+    assert_eq!(keys, ["colors.hue_links.base05", "colors.hue_links.base0F"]);
+}
@@ -0,0 +1,65 @@
+use palette::Srgb;
+use themalingadingdong::builder::ThemeBuilder;
+use themalingadingdong::gamut_map::Gamut;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::hue_spacing::{HueSpacing, min_pairwise_delta_e, optimize_hues};
+use themalingadingdong::interpolation::DEFAULT_BASE16_HUES;
+
+#[test]
+fn test_zero_shift_keeps_anchors() {
+    let anchors = [10.0, 50.0, 95.0, 140.0, 190.0, 240.0, 290.0, 330.0];
+    let hues = optimize_hues(
+        &anchors,
+        65.0,
+        35.0,
+        Gamut::Srgb,
+        &HueSpacing { max_shift: 0.0 },
+    );
+    assert_eq!(hues, anchors);
+}
+
+#[test]
+fn test_perceptual_hues_widen_closest_pair() {
+    let spacing = HueSpacing { max_shift: 20.0 };
+    let hues = optimize_hues(&DEFAULT_BASE16_HUES, 65.0, 35.0, Gamut::Srgb, &spacing);
+
+    let before = min_pairwise_delta_e(&DEFAULT_BASE16_HUES, 65.0, 35.0, Gamut::Srgb);
+    let after = min_pairwise_delta_e(&hues, 65.0, 35.0, Gamut::Srgb);
+    assert!(after > before, "min ΔE {after:.2} should beat {before:.2}");
+
+    for (hue, anchor) in hues.iter().zip(DEFAULT_BASE16_HUES) {
+        let shift = (hue - anchor + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(shift.abs() <= 20.0, "{anchor} moved to {hue}");
+    }
+}
+
+#[test]
+fn test_generate_applies_hue_spacing() {
+    let base = GenerateConfig {
+        background: Srgb::new(0x1au8, 0x1a, 0x2e),
+        foreground: Srgb::new(0xe0u8, 0xe0, 0xe0),
+        ..Default::default()
+    };
+    let spaced = GenerateConfig {
+        hue_spacing: Some(HueSpacing::default()),
+        ..base.clone()
+    };
+
+    let accent_hue = |config: &GenerateConfig, slot: &str| {
+        let scheme = generate(config).scheme;
+        let (r, g, b) = scheme.palette[slot].rgb;
+        HellwigJmh::from_srgb_u8(Srgb::new(r, g, b)).hue
+    };
+    let moved = ["base08", "base0D", "base0F"]
+        .iter()
+        .any(|slot| (accent_hue(&base, slot) - accent_hue(&spaced, slot)).abs() > 1.0);
+    assert!(moved);
+
+    assert!(
+        ThemeBuilder::new()
+            .perceptual_hues(60.0)
+            .to_config()
+            .is_err()
+    );
+}
@@ -0,0 +1,197 @@
+//! Tests for colors picked from an image (`--from-image`).
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::image_palette::{decode_png, dominant_colors, image_theme};
+use themalingadingdong::interpolation::DEFAULT_BASE16_HUES;
+use tinted_builder::{Base16Scheme, SchemeVariant};
+
+/// A PNG with the given IHDR color type and bit depth, one filter per row.
+fn png(width: u32, color_type: u8, depth: u8, rows: &[(u8, Vec<u8>)], plte: &[u8]) -> Vec<u8> {
+    fn chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+        out.extend((data.len() as u32).to_be_bytes());
+        out.extend(kind);
+        out.extend(data);
+        // The decoder doesn't check CRCs
+        out.extend([0; 4]);
+    }
+    let mut header = Vec::new();
+    header.extend(width.to_be_bytes());
+    header.extend((rows.len() as u32).to_be_bytes());
+    header.extend([depth, color_type, 0, 0, 0]);
+    let raw: Vec<u8> = rows
+        .iter()
+        .flat_map(|(filter, row)| std::iter::once(*filter).chain(row.iter().copied()))
+        .collect();
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut out, b"IHDR", &header);
+    if !plte.is_empty() {
+        chunk(&mut out, b"PLTE", plte);
+    }
+    chunk(
+        &mut out,
+        b"IDAT",
+        &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6),
+    );
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+#[test]
+fn test_png_filters_and_color_types_decode() {
+    // RGBA: Sub then Up filtered rows; the transparent pixel is dropped
+    let image = decode_png(&png(
+        2,
+        6,
+        8,
+        &[
+            (1, vec![10, 20, 30, 255, 5, 5, 5, 255]),
+            (2, vec![0, 0, 0, 0, 1, 1, 1, 2]),
+        ],
+        &[],
+    ))
+    .unwrap();
+    assert_eq!((image.width, image.height), (2, 2));
+    assert_eq!(
+        image.pixels,
+        vec![
+            Srgb::new(10, 20, 30),
+            Srgb::new(15, 25, 35),
+            Srgb::new(10, 20, 30),
+        ]
+    );
+
+    // Palette indices packed two bits per pixel, Paeth filtered
+    let plte = [255, 0, 0, 0, 255, 0, 0, 0, 255, 9, 9, 9];
+    let image = decode_png(&png(4, 3, 2, &[(4, vec![0b00_01_10_11])], &plte)).unwrap();
+    assert_eq!(
+        image.pixels,
+        vec![
+            Srgb::new(255, 0, 0),
+            Srgb::new(0, 255, 0),
+            Srgb::new(0, 0, 255),
+            Srgb::new(9, 9, 9),
+        ]
+    );
+
+    // 16-bit grayscale keeps the high byte, Average filtered
+    let image = decode_png(&png(2, 0, 16, &[(3, vec![0x80, 0x00, 0x40, 0xff])], &[])).unwrap();
+    assert_eq!(
+        image.pixels,
+        vec![Srgb::new(0x80, 0x80, 0x80), Srgb::new(0x80, 0x80, 0x80)]
+    );
+}
+
+#[test]
+fn test_malformed_png_headers_are_errors() {
+    // Bit depth 0, and a depth the color type doesn't allow
+    for (color_type, depth) in [(0, 0), (2, 4), (3, 16), (6, 3)] {
+        let error = decode_png(&png(2, color_type, depth, &[(0, vec![0; 8])], &[1, 2, 3]))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("bit depth"), "{error}");
+    }
+
+    // Dimensions whose data size overflows
+    let error = decode_png(&png(u32::MAX, 6, 16, &[(0, vec![0; 8])], &[]))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("too large") || error.contains("truncated"),
+        "{error}"
+    );
+
+    // `--from-image` reports it instead of panicking
+    let path = std::env::temp_dir().join(format!(
+        "themalingadingdong-bad-depth-{}.png",
+        std::process::id()
+    ));
+    std::fs::write(&path, png(2, 0, 0, &[(0, vec![0]), (0, vec![0])], &[])).unwrap();
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .arg("--from-image")
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(!output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("panicked"));
+}
+
+#[test]
+fn test_clusters_and_theme_follow_the_image() {
+    // Mostly deep navy, with a teal and an orange highlight
+    let mut pixels = vec![Srgb::new(16u8, 22, 48); 700];
+    pixels.extend(vec![Srgb::new(40u8, 170, 160); 200]);
+    pixels.extend(vec![Srgb::new(235u8, 120, 40); 100]);
+    let clusters = dominant_colors(&pixels, 3);
+    assert_eq!(clusters.len(), 3);
+    assert!((clusters[0].share - 0.7).abs() < 0.01);
+    assert_eq!(clusters[0].color, Srgb::new(16, 22, 48));
+    assert!((clusters.iter().map(|c| c.share).sum::<f32>() - 1.0).abs() < 1e-4);
+
+    let theme = image_theme(&clusters, None);
+    assert!(matches!(theme.variant, SchemeVariant::Dark));
+    // The background keeps the navy's hue, toned down
+    let navy = HellwigJmh::from_srgb_u8(Srgb::new(16, 22, 48));
+    let background = HellwigJmh::from_srgb_u8(theme.background);
+    assert!(background.lightness <= 18.5);
+    assert!((background.hue - navy.hue).abs() < 10.0);
+    assert!(HellwigJmh::from_srgb_u8(theme.foreground).lightness > 85.0);
+
+    // Each highlight moves the nearest default hue to its own
+    for cluster in &clusters[1..] {
+        let moved = theme.hues.iter().flatten().any(|&h| h == cluster.jmh.hue);
+        let near_default = DEFAULT_BASE16_HUES.iter().any(|&d| {
+            let diff = (d - cluster.jmh.hue).rem_euclid(360.0);
+            diff.min(360.0 - diff) <= 20.0
+        });
+        assert_eq!(moved, near_default, "{cluster:?}");
+    }
+
+    // A forced light theme starts from the lightest sizable cluster
+    let light = image_theme(&clusters, Some(SchemeVariant::Light));
+    assert!(HellwigJmh::from_srgb_u8(light.background).lightness >= 93.5);
+    assert!(HellwigJmh::from_srgb_u8(light.foreground).lightness < 25.0);
+}
+
+#[test]
+fn test_from_image_generates_a_scheme() {
+    let path = std::env::temp_dir().join(format!(
+        "themalingadingdong-from-image-{}.ppm",
+        std::process::id()
+    ));
+    let mut ppm = b"P6\n4 4\n255\n".to_vec();
+    for i in 0..16 {
+        ppm.extend(if i < 12 { [24, 18, 30] } else { [220, 70, 60] });
+    }
+    std::fs::write(&path, ppm).unwrap();
+
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .arg("--from-image")
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Dominant colors of"));
+
+    let scheme: Base16Scheme = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert_eq!(scheme.name, "Wallpaper");
+    assert!(matches!(scheme.variant, SchemeVariant::Dark));
+    let base00 = &scheme.palette["base00"].rgb;
+    let background = HellwigJmh::from_srgb_u8(Srgb::new(base00.0, base00.1, base00.2));
+    assert!(background.lightness < 19.0);
+
+    // A missing image is reported by path
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["--from-image", "/nonexistent.png"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("/nonexistent.png"));
+}
@@ -0,0 +1,194 @@
+//! Tests for scheme and terminal theme import.
+
+use std::path::PathBuf;
+
+use themalingadingdong::import::import_scheme;
+use tinted_builder::{SchemeSystem, SchemeVariant};
+
+/// Write `content` to a uniquely named temp file and return its path.
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-import-{}-{}",
+        std::process::id(),
+        name.replace('.', "_")
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn hex(result: &themalingadingdong::import::ImportResult, slot: &str) -> String {
+    result.scheme.palette[slot].to_hex().to_lowercase()
+}
+
+#[test]
+fn test_import_alacritty_toml() {
+    let path = temp_file(
+        "gruvbox.toml",
+        r##"
+[colors.primary]
+background = "#282828"
+foreground = "0xebdbb2"
+
+[colors.normal]
+black = "#282828"
+red = "#cc241d"
+green = "#98971a"
+yellow = "#d79921"
+blue = "#458588"
+magenta = "#b16286"
+cyan = "#689d6a"
+white = "#a89984"
+
+[colors.bright]
+black = "#928374"
+red = "#fb4934"
+green = "#b8bb26"
+yellow = "#fabd2f"
+blue = "#83a598"
+magenta = "#d3869b"
+cyan = "#8ec07c"
+white = "#ebdbb2"
+"##,
+    );
+    let result = import_scheme(&path).unwrap();
+
+    assert!(matches!(result.scheme.system, SchemeSystem::Base24));
+    assert!(matches!(result.scheme.variant, SchemeVariant::Dark));
+    assert_eq!(result.scheme.name, "gruvbox");
+    assert_eq!(hex(&result, "base00"), "282828");
+    assert_eq!(hex(&result, "base05"), "ebdbb2");
+    assert_eq!(hex(&result, "base03"), "928374");
+    assert_eq!(hex(&result, "base08"), "cc241d");
+    assert_eq!(hex(&result, "base0D"), "458588");
+    // Bright colors fill base10-base17 in the same order as base08-base0F
+    assert_eq!(hex(&result, "base10"), "fb4934");
+    assert_eq!(hex(&result, "base12"), "fabd2f");
+    assert_eq!(hex(&result, "base13"), "b8bb26");
+    assert_eq!(hex(&result, "base15"), "83a598");
+    assert_eq!(hex(&result, "base16"), "d3869b");
+    assert_eq!(result.scheme.palette.len(), 24);
+    assert!(result.warnings.is_empty());
+    assert_eq!(result.config.colors.background.as_deref(), Some("#282828"));
+}
+
+#[test]
+fn test_import_kitty_conf() {
+    let path = temp_file(
+        "kitty-theme.conf",
+        "# Kitty theme\nbackground #fdf6e3\nforeground   #657b83\ncolor0 #073642\ncolor1 #dc322f\ncolor2 #859900\ncolor3 #b58900\ncolor4 #268bd2\ncolor5 #d33682\ncolor6 #2aa198\ncolor7 #eee8d5\n",
+    );
+    let result = import_scheme(&path).unwrap();
+
+    assert!(matches!(result.scheme.variant, SchemeVariant::Light));
+    assert_eq!(hex(&result, "base00"), "fdf6e3");
+    assert_eq!(hex(&result, "base0B"), "859900");
+    // Bright colors fall back to their normal counterparts
+    assert_eq!(hex(&result, "base13"), "859900");
+    assert!(
+        result
+            .warnings
+            .contains(&"color 10 (bright green) is missing; using color 2 (green)".to_string())
+    );
+    let hue = result.config.colors.hue_overrides.unwrap().to_array()[0];
+    assert!(hue.is_some(), "red hue should be extracted");
+}
+
+#[test]
+fn test_import_xresources() {
+    let path = temp_file(
+        ".Xresources",
+        "! comment\n#define nord_red #bf616a\n*.background: #2e3440\n*foreground:  #d8dee9\nURxvt*color1: nord_red\n*.color4: #81a1c1\n*.color8: #4c566a\n",
+    );
+    let result = import_scheme(&path).unwrap();
+
+    assert_eq!(hex(&result, "base00"), "2e3440");
+    assert_eq!(hex(&result, "base08"), "bf616a");
+    assert_eq!(hex(&result, "base0D"), "81a1c1");
+    assert_eq!(hex(&result, "base03"), "4c566a");
+}
+
+#[test]
+fn test_import_vscode_json() {
+    let path = temp_file(
+        "theme.json",
+        r##"{
+  // JSONC comment
+  "name": "Test Dark",
+  "type": "dark",
+  "colors": {
+    "editor.background": "#1e1e2e",
+    "editor.foreground": "#cdd6f4",
+    "terminal.ansiRed": "#f38ba8",
+    "terminal.ansiGreen": "#a6e3a1",
+    "terminal.ansiBrightBlue": "#89b4faff"
+  }
+}"##,
+    );
+    let result = import_scheme(&path).unwrap();
+
+    assert_eq!(result.scheme.name, "Test Dark");
+    assert_eq!(result.scheme.slug, "test-dark");
+    assert_eq!(hex(&result, "base00"), "1e1e2e");
+    assert_eq!(hex(&result, "base08"), "f38ba8");
+    // Normal blue falls back to bright blue (alpha dropped)
+    assert_eq!(hex(&result, "base0D"), "89b4fa");
+    // Colors missing in both variants fall back to the foreground, reported
+    assert_eq!(hex(&result, "base0A"), "cdd6f4");
+    assert!(
+        result
+            .warnings
+            .contains(&"color 3 (yellow) is missing; using the foreground".to_string())
+    );
+}
+
+#[test]
+fn test_import_terminal_without_background_fails() {
+    let path = temp_file("empty.conf", "# nothing here\nfont_size 12\n");
+    assert!(import_scheme(&path).is_err());
+}
+
+#[test]
+fn test_import_recovers_lightness_curve() {
+    use themalingadingdong::curves::{CurveType, InterpolationConfig};
+    use themalingadingdong::generate::{GenerateConfig, generate};
+
+    let mut interpolation = InterpolationConfig::default();
+    interpolation.lightness.curve_type = CurveType::SmoothEnd;
+    let scheme = generate(&GenerateConfig {
+        interpolation,
+        ..Default::default()
+    })
+    .scheme;
+    let path = temp_file("ease-out.yaml", &serde_yaml::to_string(&scheme).unwrap());
+
+    let result = import_scheme(&path).unwrap();
+    assert_eq!(
+        result.config.curves.lightness.curve_type,
+        CurveType::SmoothEnd
+    );
+}
+
+#[test]
+fn test_scheme_files_lists_schemes_recursively() {
+    use themalingadingdong::import::{read_scheme, scheme_files};
+
+    let dir =
+        std::env::temp_dir().join(format!("themalingadingdong-gallery-{}", std::process::id()));
+    let scheme =
+        serde_yaml::to_string(&themalingadingdong::generate::generate(&Default::default()).scheme)
+            .unwrap();
+    for relative in ["base24/b.yaml", "base16/a.yml", ".git/c.yaml", "README.md"] {
+        let path = dir.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &scheme).unwrap();
+    }
+
+    let files = scheme_files(&dir);
+    assert_eq!(
+        files,
+        vec![dir.join("base16/a.yml"), dir.join("base24/b.yaml")]
+    );
+    assert_eq!(read_scheme(&files[0]).unwrap().palette.len(), 24);
+}
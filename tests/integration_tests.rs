@@ -214,3 +214,626 @@ fn test_cli_variant_both_requires_output() {
         .failure()
         .stderr(predicate::str::contains("--variant both requires --output"));
 }
+
+#[test]
+fn test_cli_batch_writes_all_schemes() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-batch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let manifest = dir.join("manifest.toml");
+    std::fs::write(
+        &manifest,
+        r##"
+output_dir = "out"
+
+[defaults.contrast]
+minimum = 60.0
+
+[[scheme]]
+theme.name = "Batch One"
+colors.background = "#1a1a2e"
+colors.foreground = "#eaeaea"
+
+[[scheme]]
+theme.name = "Batch Two"
+colors.background = "#000000"
+colors.foreground = "#ffffff"
+colors.hue_overrides.base08 = 340.0
+"##,
+    )
+    .unwrap();
+
+    cmd()
+        .args(["--batch", manifest.to_str().unwrap(), "--format", "json"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Generated 2 schemes"));
+
+    for slug in ["batch-one-dark", "batch-two-dark"] {
+        let path = dir.join("out").join(format!("{slug}.json"));
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"base17\""), "{} missing base17", slug);
+    }
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_cli_batch_rejects_duplicate_slugs() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-dup-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let manifest = dir.join("manifest.toml");
+    std::fs::write(
+        &manifest,
+        r##"
+[[scheme]]
+theme.name = "Same"
+colors.background = "#000000"
+colors.foreground = "#ffffff"
+
+[[scheme]]
+theme.name = "Same"
+colors.background = "#101010"
+colors.foreground = "#f0f0f0"
+"##,
+    )
+    .unwrap();
+
+    cmd()
+        .args(["--batch", manifest.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Duplicate scheme slug"));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_cli_record_requires_interactive() {
+    cmd()
+        .args([
+            "-b",
+            "#000000",
+            "-f",
+            "#ffffff",
+            "--name",
+            "Rec",
+            "--record",
+            "session.jsonl",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--interactive"));
+}
+
+#[test]
+fn test_cli_replay_step_conflicts_with_speed() {
+    cmd()
+        .args([
+            "--replay",
+            "session.jsonl",
+            "--replay-step",
+            "--replay-speed",
+            "2",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_resume_without_saved_session() {
+    let dir = std::env::temp_dir().join("themalingadingdong_resume_test");
+    std::fs::remove_dir_all(&dir).ok();
+
+    cmd()
+        .env("XDG_STATE_HOME", &dir)
+        .arg("--resume")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No saved session"));
+
+    cmd()
+        .args(["--resume", "--config", "theme.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_max_contrast_raises_floors() {
+    let dir = std::env::temp_dir().join("themalingadingdong_max_contrast_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("theme.toml");
+
+    cmd()
+        .args([
+            "-b",
+            "#1a1a2e",
+            "-f",
+            "#e0e0e0",
+            "--name",
+            "Test",
+            "--max-contrast",
+            "--save-config",
+        ])
+        .arg(&config_path)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Max feasible min_contrast"));
+
+    let saved: toml::Value =
+        toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+    let minimum = saved["contrast"]["minimum"].as_float().unwrap();
+    assert!(
+        minimum > 45.0,
+        "ceiling {minimum} should beat the default floor"
+    );
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_cli_fit_requires_input() {
+    cmd()
+        .args(["-b", "#000000", "-f", "#ffffff", "--name", "Fit", "--fit"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--input"));
+}
+
+#[test]
+fn test_cli_check_cvd_reports() {
+    cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "CVD",
+            "--check-cvd",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("CVD check"));
+}
+
+#[test]
+fn test_cli_xterm256_comment_block() {
+    cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "Xterm",
+            "--xterm256",
+            "comment",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# xterm-256 approximations"))
+        .stdout(predicate::str::contains("# base08:"));
+}
+
+#[test]
+fn test_cli_xterm256_json_writes_table() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-xterm-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("theme.yaml");
+
+    cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "Xterm",
+            "--xterm256",
+            "json",
+            "-o",
+            output.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let table: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("theme.xterm256.json")).unwrap())
+            .unwrap();
+    assert_eq!(table.as_array().unwrap().len(), 24);
+    assert!(table[0]["index"].as_u64().unwrap() >= 16);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_cli_xterm256_json_requires_output() {
+    cmd()
+        .args([
+            "-b",
+            "#000000",
+            "-f",
+            "#ffffff",
+            "--name",
+            "X",
+            "--xterm256",
+            "json",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--xterm256 json requires --output",
+        ));
+}
+
+#[test]
+fn test_cli_contrast_standard_wcag() {
+    cmd()
+        .args([
+            "-b",
+            "#777777",
+            "-f",
+            "#ffffff",
+            "--name",
+            "Gray",
+            "--contrast-standard",
+            "wcag",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("for WCAG AA normal text"));
+}
+
+#[test]
+fn test_cli_fix_metadata() {
+    cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "  Spaced   Name ",
+            "--author",
+            "Someone",
+            "--fix-metadata",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Fixed metadata: name"))
+        .stdout(predicate::str::contains("name: Spaced Name"))
+        .stdout(predicate::str::contains("slug: spaced-name-dark"));
+}
+
+#[test]
+fn test_cli_empty_author_only_warns_when_given() {
+    cmd()
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Quiet"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("author is empty").not());
+    cmd()
+        .args([
+            "-b", "#1d2021", "-f", "#ebdbb2", "--name", "Quiet", "--author", " ",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Metadata warning: author is empty",
+        ));
+}
+
+#[test]
+fn test_cli_css_display_p3() {
+    cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "Wide",
+            "--gamut",
+            "display-p3",
+            "--format",
+            "css",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--base08: #"))
+        .stdout(predicate::str::contains(
+            "@supports (color: color(display-p3 0 0 0))",
+        ))
+        .stdout(predicate::str::contains("--base08: color(display-p3 "));
+}
+
+#[test]
+fn test_cli_convert_subcommand() {
+    cmd()
+        .args(["convert", "#ff0000"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hex     #ff0000"))
+        .stdout(predicate::str::contains("oklch   oklch(62.80% "))
+        .stdout(predicate::str::contains("max M   sRGB "));
+}
+
+#[test]
+fn test_cli_watch_requires_config_and_output() {
+    cmd()
+        .args(["-b", "#000000", "-f", "#ffffff", "--name", "W", "--watch"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--config"));
+}
+
+#[test]
+fn test_cli_batch_progress_json() {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-batch-progress-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let manifest = dir.join("manifest.toml");
+    std::fs::write(
+        &manifest,
+        r##"
+[[scheme]]
+theme.name = "Progress One"
+colors.background = "#1a1a2e"
+colors.foreground = "#eaeaea"
+
+[[scheme]]
+theme.name = "Progress Two"
+colors.background = "#000000"
+colors.foreground = "#ffffff"
+"##,
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            "--batch",
+            manifest.to_str().unwrap(),
+            "--progress-json",
+            "-o",
+        ])
+        .arg(dir.join("out"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // Every stderr line is a standalone JSON event
+    let events: Vec<serde_json::Value> = String::from_utf8(output.stderr)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let count = |kind: &str| events.iter().filter(|e| e["event"] == kind).count();
+    assert_eq!(count("started"), 2);
+    assert_eq!(count("finished"), 2);
+    let finished = events.iter().find(|e| e["event"] == "finished").unwrap();
+    assert_eq!(finished["total"], 2);
+    assert!(finished["warnings"].is_u64());
+    assert!(finished["duration_ms"].is_u64());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_cli_roles_section() {
+    let output = cmd()
+        .args([
+            "-b", "#1d2021", "-f", "#ebdbb2", "--name", "Roles", "--roles", "--format", "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let roles = json["roles"].as_object().unwrap();
+    assert_eq!(roles.len(), 8);
+    assert_eq!(roles["error"], json["palette"]["base08"]);
+    assert_eq!(roles["selection_bg"], json["palette"]["base02"]);
+}
+
+#[test]
+fn test_preview_subcommand() {
+    let output = cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--variant",
+            "both",
+            "preview",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // base00 swatch and sample background, and both variants
+    assert!(stdout.contains("\x1b[48;2;29;32;33m"));
+    assert!(stdout.contains("(dark)"));
+    assert!(stdout.contains("(light)"));
+    assert!(stdout.contains("$ git diff"));
+    assert_eq!(stdout.matches("ANSI 0-15:").count(), 2);
+}
+
+#[test]
+fn test_report_contrast_matrix() {
+    let output = cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "Matrix",
+            "--report",
+            "contrast-matrix",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // The report goes to stderr so stdout stays a valid scheme
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("Contrast matrix"));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Contrast matrix"));
+    assert_eq!(stderr.lines().filter(|l| l.starts_with("base")).count(), 24);
+}
+
+#[test]
+fn test_profile_requires_existing_table() {
+    let path = std::env::temp_dir().join(format!(
+        "themalingadingdong-profile-cli-{}.toml",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        "[theme]\nname = \"Base\"\n\n[colors]\nbackground = \"#1d2021\"\nforeground = \"#ebdbb2\"\n\n[profile.alt.theme]\nname = \"Alt\"\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args(["--config", path.to_str().unwrap(), "--profile", "alt"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .contains("name: Alt")
+    );
+
+    let output = cmd()
+        .args(["--config", path.to_str().unwrap(), "--profile", "missing"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("available: alt")
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_flag_at_default_overrides_profile() {
+    let path = std::env::temp_dir().join(format!(
+        "themalingadingdong-profile-flag-{}.toml",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        "[colors]\nbackground = \"#1d2021\"\nforeground = \"#ebdbb2\"\n\n[profile.strict.contrast]\nminimum = 90.0\n",
+    )
+    .unwrap();
+
+    // 75 is the built-in default, but given explicitly it still wins
+    cmd()
+        .args(["--config", path.to_str().unwrap(), "--profile", "strict"])
+        .args(["--min-contrast", "75"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("< 90.0").not());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_render_subcommand() {
+    let root = std::env::temp_dir().join(format!(
+        "themalingadingdong-render-cli-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&root);
+    let templates = root.join("base16-demo/templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(
+        templates.join("config.yaml"),
+        "default:\n  filename: \"{{ scheme-slug }}.txt\"\n  supported-systems: [base24]\n",
+    )
+    .unwrap();
+    std::fs::write(templates.join("default.mustache"), "{{scheme-variant}}\n").unwrap();
+    let out = root.join("out");
+
+    let output = cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "Rendered",
+            "--variant",
+            "both",
+            "render",
+            "--template-glob",
+            root.join("base16-*").to_str().unwrap(),
+            "--output-dir",
+            out.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let rendered = |slug: &str| std::fs::read_to_string(out.join("base16-demo").join(slug));
+    assert_eq!(rendered("rendered-dark.txt").unwrap(), "dark\n");
+    assert_eq!(rendered("rendered-light.txt").unwrap(), "light\n");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_reproduce_subcommand() {
+    let path = std::env::temp_dir().join(format!(
+        "themalingadingdong-provenance-{}.yaml",
+        std::process::id()
+    ));
+
+    cmd()
+        .args([
+            "-b",
+            "#1d2021",
+            "-f",
+            "#ebdbb2",
+            "--name",
+            "Reproducible",
+            "--emit-provenance",
+            "-o",
+            path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("provenance:"));
+
+    cmd()
+        .args(["reproduce", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Output: byte-identical"));
+
+    std::fs::write(&path, content.replace("Reproducible", "Edited")).unwrap();
+    cmd()
+        .args(["reproduce", path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Output: differs from line"));
+
+    std::fs::remove_file(&path).unwrap();
+}
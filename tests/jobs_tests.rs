@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use themalingadingdong::jobs::{map_jobs, queue_bound, run_jobs};
+
+#[test]
+fn test_map_jobs_keeps_input_order() {
+    let inputs: Vec<u64> = (0..64).collect();
+    let results = map_jobs(inputs, |index, n| {
+        // Later jobs finish first
+        std::thread::sleep(std::time::Duration::from_micros(64 - n));
+        (index, n * 2)
+    });
+    assert_eq!(results.len(), 64);
+    for (i, finished) in results.iter().enumerate() {
+        assert_eq!(finished.index, i);
+        assert_eq!(finished.output, (i, i as u64 * 2));
+    }
+    assert!(queue_bound() >= 1);
+}
+
+#[test]
+fn test_consumer_error_stops_pending_jobs() {
+    let ran = AtomicUsize::new(0);
+    let mut consumed = 0;
+    let outcome = run_jobs(
+        vec![(); 10_000],
+        |_, ()| {
+            ran.fetch_add(1, Ordering::Relaxed);
+            std::thread::sleep(std::time::Duration::from_micros(50));
+        },
+        |_| {
+            consumed += 1;
+            Err("stop")
+        },
+    );
+    assert_eq!(outcome, Err("stop"));
+    assert_eq!(consumed, 1);
+    assert!(ran.load(Ordering::Relaxed) < 10_000);
+}
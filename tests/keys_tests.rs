@@ -0,0 +1,101 @@
+//! Tests for TUI key rebinding.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::config::{ConfigError, ThemeConfig};
+use themalingadingdong::config_schema::check_config;
+
+#[test]
+fn test_invalid_bindings_are_rejected() {
+    let config: ThemeConfig =
+        toml::from_str("[keys]\nexport = [\"e\", \"ctrl+s\"]\nquit = \"q\"\n").unwrap();
+    assert!(config.keys.validate().is_ok());
+    let bindings = config.keys.bindings().unwrap();
+    assert_eq!(bindings[0].0, "export");
+    assert_eq!(bindings[0].1.len(), 2);
+    assert!(bindings[0].1[1].ctrl);
+
+    for (source, expected) in [
+        ("[keys]\nexprot = \"e\"\n", "keys.exprot: unknown action"),
+        (
+            "[keys]\nexport = \"hyper+e\"\n",
+            "'hyper' is not a modifier",
+        ),
+        (
+            "[keys]\nexport = \"e\"\nquit = [\"q\", \"e\"]\n",
+            "'e' is already bound to export",
+        ),
+    ] {
+        let config: ThemeConfig = toml::from_str(source).unwrap();
+        assert!(
+            matches!(
+                config.keys.validate(),
+                Err(ConfigError::InvalidValue(ref msg)) if msg.contains(expected)
+            ),
+            "{source}: {:?}",
+            config.keys.validate()
+        );
+    }
+
+    // Unset bindings are left out of written configs
+    let written = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!written.contains("[keys]"));
+}
+
+#[test]
+fn test_keys_table_is_checked() {
+    let diagnostics = check_config("[keys]\nexprt = \"e\"\ngallery = 3\nquit = [\"q\", \"f13\"]\n");
+    let summary: Vec<(usize, &str)> = diagnostics
+        .iter()
+        .map(|d| (d.line, d.key.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(2, "keys.exprt"), (3, "keys.gallery"), (4, "keys.quit")]
+    );
+    assert!(diagnostics[0].message.contains("did you mean 'export'"));
+    assert_eq!(
+        check_config("[keys]\nexport = [\"e\", \"ctrl+s\"]\n"),
+        vec![]
+    );
+}
+
+#[test]
+fn test_print_keys_applies_rebindings() {
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .arg("--print-keys")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let defaults = String::from_utf8(output).unwrap();
+    assert!(defaults.starts_with("[keys]\n"));
+    // Every entry parses back, though some defaults share a key
+    let table: ThemeConfig = toml::from_str(&defaults).unwrap();
+    for (action, keys) in &table.keys.0 {
+        let mut single = ThemeConfig::default();
+        single.keys.0.insert(action.clone(), keys.clone());
+        assert!(single.keys.validate().is_ok(), "{action}");
+    }
+    assert!(defaults.contains("code_preview = \"c\""));
+
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-keys-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("keys.toml");
+    std::fs::write(&config, "[keys]\nexport = \"c\"\ngallery = []\n").unwrap();
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .arg("--print-keys")
+        .arg("--config")
+        .arg(&config)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let rebound = String::from_utf8(output).unwrap();
+    assert!(rebound.contains("export = \"c\""));
+    // "c" moved from the code preview to export
+    assert!(rebound.contains("code_preview = []"));
+    assert!(rebound.contains("gallery = []"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
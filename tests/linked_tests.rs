@@ -0,0 +1,106 @@
+//! Tests for linked dark/light generation.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::autotune::max_feasible_linked_contrast;
+use themalingadingdong::generate::{GenerateConfig, generate_for_variant};
+use themalingadingdong::linked::generate_linked;
+use tinted_builder::SchemeVariant;
+
+fn hue_distance(a: f32, b: f32) -> f32 {
+    ((a - b + 180.0).rem_euclid(360.0) - 180.0).abs()
+}
+
+#[test]
+fn test_linked_variants_share_hues_and_mirror_lightness() {
+    let config = GenerateConfig::default();
+    let linked = generate_linked(&config);
+    assert!(matches!(linked.dark.scheme.variant, SchemeVariant::Dark));
+    assert!(matches!(linked.light.scheme.variant, SchemeVariant::Light));
+
+    for (dark, light) in linked
+        .dark
+        .base_accent_results
+        .iter()
+        .zip(&linked.light.base_accent_results)
+    {
+        assert!(hue_distance(dark.hue, light.hue) < 0.5);
+        let mirrored = 100.0 - dark.lightness;
+        assert!(
+            (light.lightness - mirrored).abs() <= 2.0 * config.accent_opt.delta_j,
+            "J' {:.1} vs mirrored {:.1}",
+            light.lightness,
+            mirrored
+        );
+    }
+    assert_eq!(linked.light_config.accent_opt.target_j, 20.0);
+    assert_eq!(linked.light_config.extended_accent_opt.target_j, 30.0);
+
+    // Generating each variant independently leaves light accents unreadable
+    let independent = generate_for_variant(&config, Some(SchemeVariant::Light));
+    let met = |results: &[themalingadingdong::interpolation::AccentResult]| {
+        results.iter().filter(|r| r.met_minimum).count()
+    };
+    assert_eq!(met(&linked.light.base_accent_results), 8);
+    assert!(met(&independent.base_accent_results) < 8);
+}
+
+#[test]
+fn test_light_config_is_kept_for_light_backgrounds() {
+    let mut config = GenerateConfig {
+        background: Srgb::new(0xfa, 0xf4, 0xed),
+        foreground: Srgb::new(0x28, 0x24, 0x30),
+        ..GenerateConfig::default()
+    };
+    config.accent_opt.target_j = 25.0;
+    config.extended_accent_opt.target_j = 35.0;
+
+    let linked = generate_linked(&config);
+    assert_eq!(linked.light_config.accent_opt.target_j, 25.0);
+    assert_eq!(linked.dark_config.accent_opt.target_j, 75.0);
+    assert_eq!(linked.dark.scheme.palette["base00"].to_hex(), "282430");
+}
+
+#[test]
+fn test_linked_ceiling_holds_for_both_variants() {
+    let config = GenerateConfig::default();
+    let ceiling = max_feasible_linked_contrast(&config);
+    let linked = generate_linked(&ceiling.apply(&config));
+    assert!(
+        linked
+            .dark
+            .base_accent_results
+            .iter()
+            .chain(&linked.light.base_accent_results)
+            .all(|r| r.met_minimum)
+    );
+}
+
+#[test]
+fn test_cli_variant_linked_writes_both_files() {
+    let dir =
+        std::env::temp_dir().join(format!("themalingadingdong-linked-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    cargo_bin_cmd!("themalingadingdong")
+        .current_dir(&dir)
+        .args([
+            "--background",
+            "#1a1a2e",
+            "--foreground",
+            "#eaeaea",
+            "--name",
+            "Linked",
+            "--variant",
+            "linked",
+            "-o",
+            "linked",
+        ])
+        .assert()
+        .success();
+
+    let dark = std::fs::read_to_string(dir.join("linked-dark.yaml")).unwrap();
+    let light = std::fs::read_to_string(dir.join("linked-light.yaml")).unwrap();
+    assert!(dark.contains("variant: dark"));
+    assert!(light.contains("variant: light"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,121 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::ansi::AnsiConfig;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::lint::{LintCode, fix_scheme, lint_scheme};
+use themalingadingdong::validation::ValidationConfig;
+use tinted_builder::Base16Scheme;
+
+fn scheme() -> Base16Scheme {
+    generate(&GenerateConfig::default()).scheme
+}
+
+fn codes(scheme: &Base16Scheme) -> Vec<(LintCode, String)> {
+    lint_scheme(scheme)
+        .into_iter()
+        .map(|f| (f.code, f.slot))
+        .collect()
+}
+
+#[test]
+fn test_generated_scheme_lints_clean() {
+    // Contrast is the generator's own validation; the rest holds by construction
+    let findings = lint_scheme(&scheme());
+    assert!(
+        findings.iter().all(|f| f.code == LintCode::LowContrast),
+        "{findings:?}"
+    );
+}
+
+#[test]
+fn test_surface_checks() {
+    let mut scheme = scheme();
+    let base02 = scheme.palette["base02"].clone();
+    let base03 = scheme.palette["base03"].clone();
+    scheme.palette.insert("base02".to_string(), base03);
+    scheme.palette.insert("base03".to_string(), base02);
+    assert!(codes(&scheme).contains(&(LintCode::SurfaceOrder, "base03".to_string())));
+
+    let mut scheme = self::scheme();
+    let base01 = scheme.palette["base01"].clone();
+    scheme.palette.insert("base02".to_string(), base01);
+    let found = codes(&scheme);
+    assert!(found.contains(&(LintCode::FlatSurfaces, "base02".to_string())));
+    // Equal surfaces are a flat step, not a duplicate
+    assert!(
+        !found
+            .iter()
+            .any(|(code, _)| *code == LintCode::DuplicateColor)
+    );
+}
+
+#[test]
+fn test_fix_scheme() {
+    let mut scheme = scheme();
+    let base01 = scheme.palette["base01"].clone();
+    scheme.palette.insert("base02".to_string(), base01);
+    let mut config = GenerateConfig::default();
+    config.hue_overrides[0] = Some(190.0); // base08 turned teal
+    let teal = generate(&config).scheme.palette["base08"].clone();
+    scheme.palette.insert("base08".to_string(), teal.clone());
+    scheme.palette.insert("base0F".to_string(), teal);
+
+    let before = codes(&scheme);
+    for code in [
+        LintCode::FlatSurfaces,
+        LintCode::DuplicateColor,
+        LintCode::AnsiHue,
+    ] {
+        assert!(before.iter().any(|(c, _)| *c == code), "{before:?}");
+    }
+
+    let fixed = fix_scheme(
+        &mut scheme,
+        &ValidationConfig::default(),
+        &AnsiConfig::default(),
+    );
+    assert!(!fixed.is_empty());
+    let after = codes(&scheme);
+    assert!(
+        after.iter().all(|(code, _)| *code == LintCode::LowContrast),
+        "{after:?}"
+    );
+}
+
+#[test]
+fn test_lint_command() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-lint-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("theme.yaml");
+    let mut scheme = scheme();
+    let red = scheme.palette["base08"].clone();
+    scheme.palette.insert("base09".to_string(), red);
+    std::fs::write(&path, serde_yaml::to_string(&scheme).unwrap()).unwrap();
+
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .args(["lint", "--json"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let findings = json[0]["findings"].as_array().unwrap();
+    assert!(
+        findings
+            .iter()
+            .any(|f| f["code"] == "duplicate-color" && f["slot"] == "base09"),
+        "{json}"
+    );
+
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["lint", "--fix"])
+        .arg(&path)
+        .assert()
+        .success();
+    assert!(dir.join("theme.yaml.bak").exists());
+    let fixed = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let fixed: Base16Scheme = serde_yaml::from_str(&fixed).unwrap();
+    assert_ne!(fixed.palette["base09"].rgb, fixed.palette["base08"].rgb);
+}
@@ -0,0 +1,55 @@
+//! Tests for the in-memory log buffer behind the TUI log panel.
+
+use themalingadingdong::logging::{LogBuffer, LogBufferLayer, LogEntry};
+use tracing::Level;
+use tracing_subscriber::prelude::*;
+
+fn entry(level: Level, message: &str) -> LogEntry {
+    LogEntry {
+        level,
+        target: "test".to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn test_full_buffer_drops_oldest() {
+    let buffer = LogBuffer::new(3);
+    assert!(buffer.is_empty());
+    for (i, level) in [Level::WARN, Level::INFO, Level::ERROR, Level::DEBUG]
+        .into_iter()
+        .enumerate()
+    {
+        buffer.push(entry(level, &i.to_string()));
+    }
+    let messages: Vec<String> = buffer.tail(10).into_iter().map(|e| e.message).collect();
+    assert_eq!(messages, ["1", "2", "3"]);
+    assert_eq!(buffer.tail(1)[0].message, "3");
+    // The dropped warning still counts
+    assert_eq!(buffer.warning_count(), 2);
+
+    // Clones share the events
+    buffer.clone().push(entry(Level::INFO, "4"));
+    assert_eq!(buffer.tail(1)[0].message, "4");
+}
+
+#[test]
+fn test_layer_records_filtered_events() {
+    let buffer = LogBuffer::new(10);
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_subscriber::filter::LevelFilter::INFO)
+        .with(LogBufferLayer::new(buffer.clone()));
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::debug!("filtered out");
+        tracing::warn!(hue = 25.0, method = "cobyla", "Solver failed");
+        tracing::info!(count = 3, "batch complete");
+    });
+
+    let entries = buffer.tail(10);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].level, Level::WARN);
+    assert_eq!(entries[0].target, "logging_tests");
+    assert_eq!(entries[0].message, "Solver failed hue=25.0 method=cobyla");
+    assert_eq!(entries[1].message, "batch complete count=3");
+    assert_eq!(buffer.warning_count(), 1);
+}
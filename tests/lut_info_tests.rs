@@ -0,0 +1,31 @@
+//! Tests for the build-time lookup table summary.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::generated::{BAKED_CUSP_LUTS, CUSP_LUT_HASH};
+use themalingadingdong::provenance::{lut_checksums, lut_info};
+
+#[test]
+fn test_lut_info_lists_every_table() {
+    let info = lut_info();
+    for (name, sum) in lut_checksums() {
+        assert!(info.contains(&name), "{info}");
+        assert!(info.contains(&sum), "{info}");
+    }
+    assert!(info.contains(CUSP_LUT_HASH));
+    for lut in BAKED_CUSP_LUTS {
+        assert!(info.contains(lut.hash), "{info}");
+    }
+}
+
+#[test]
+fn test_print_lut_info_needs_no_colors() {
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .arg("--print-lut-info")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    assert_eq!(stdout, lut_info());
+}
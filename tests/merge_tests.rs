@@ -0,0 +1,122 @@
+//! Tests for merging two schemes.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::merge::{MergePlan, Side, merge_schemes};
+use themalingadingdong::validation::{ContrastStandard, ValidationConfig};
+use tinted_builder::Base16Scheme;
+
+fn schemes() -> (Base16Scheme, Base16Scheme) {
+    let a = generate(&GenerateConfig::default()).scheme;
+    let b = generate(&GenerateConfig {
+        background: Srgb::new(0, 43, 54),
+        foreground: Srgb::new(238, 232, 213),
+        ..Default::default()
+    })
+    .scheme;
+    (a, b)
+}
+
+fn merge(a: &Base16Scheme, b: &Base16Scheme, plan: &MergePlan) -> Vec<String> {
+    merge_schemes(
+        a,
+        b,
+        plan,
+        Some("Merged"),
+        ContrastStandard::Apca,
+        &ValidationConfig::default(),
+    )
+    .unwrap()
+    .breakages
+}
+
+#[test]
+fn test_groups_and_picks() {
+    let (a, b) = schemes();
+    let mut plan = MergePlan {
+        extended_accents: Side::A,
+        ..Default::default()
+    };
+    plan.picks.insert("base0D".to_string(), Side::A);
+    let merged = merge_schemes(
+        &a,
+        &b,
+        &plan,
+        Some("Merged"),
+        ContrastStandard::Apca,
+        &ValidationConfig::default(),
+    )
+    .unwrap();
+
+    let rgb = |scheme: &Base16Scheme, slot: &str| scheme.palette[slot].rgb;
+    assert_eq!(rgb(&merged.scheme, "base03"), rgb(&a, "base03"));
+    assert_eq!(rgb(&merged.scheme, "base08"), rgb(&b, "base08"));
+    assert_eq!(rgb(&merged.scheme, "base0D"), rgb(&a, "base0D"));
+    assert_eq!(rgb(&merged.scheme, "base12"), rgb(&a, "base12"));
+    assert_eq!(merged.sources["base0D"], Side::A);
+    assert_eq!(merged.scheme.slug, "merged");
+    assert!(merged.breakages.is_empty(), "{:?}", merged.breakages);
+}
+
+#[test]
+fn test_breakage_is_reported() {
+    let (a, _) = schemes();
+    // The same scheme with base00 as bright as its text
+    let mut bright = a.clone();
+    let base07 = bright.palette["base07"].clone();
+    bright.palette.insert("base00".to_string(), base07);
+
+    let plan = MergePlan {
+        surfaces: Side::B,
+        accents: Side::A,
+        extended_accents: Side::A,
+        ..Default::default()
+    };
+    let breakages = merge(&a, &bright, &plan);
+    assert!(
+        breakages
+            .iter()
+            .any(|b| b.starts_with("base08 (from A) on base00 (from B)")),
+        "{breakages:?}"
+    );
+
+    // Failures the source scheme already had aren't blamed on the merge
+    assert!(merge(&bright, &bright, &plan).is_empty());
+}
+
+#[test]
+fn test_merge_command() {
+    let dir = std::env::temp_dir().join(format!("merge-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (a, b) = schemes();
+    let (path_a, path_b) = (dir.join("a.yaml"), dir.join("b.yaml"));
+    std::fs::write(&path_a, serde_yaml::to_string(&a).unwrap()).unwrap();
+    std::fs::write(&path_b, serde_yaml::to_string(&b).unwrap()).unwrap();
+
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .args(["--format", "json", "--name", "Mixed", "merge"])
+        .args([&path_a, &path_b])
+        .args(["--pick", "base0E=a"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["name"], "Mixed");
+    let hex = |scheme: &Base16Scheme, slot: &str| {
+        let (r, g, b) = scheme.palette[slot].rgb;
+        format!("{r:02x}{g:02x}{b:02x}")
+    };
+    assert_eq!(json["palette"]["base0E"], hex(&a, "base0E"));
+    assert_eq!(json["palette"]["base0B"], hex(&b, "base0B"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("required checks pass"));
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("merge")
+        .args([&path_a, &path_b])
+        .args(["--pick", "base0E"])
+        .assert()
+        .failure();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
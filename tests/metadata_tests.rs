@@ -0,0 +1,163 @@
+//! Tests for gallery metadata validation.
+
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::metadata::{
+    MetadataIssue, PublishIssue, check_generated_metadata, check_metadata, check_publishable,
+    fix_metadata, slugify,
+};
+use tinted_builder::SchemeVariant;
+
+fn scheme() -> tinted_builder::Base16Scheme {
+    generate(&GenerateConfig {
+        name: "Test Scheme".to_string(),
+        author: Some("Jane Doe <jane@example.com>".to_string()),
+        ..Default::default()
+    })
+    .scheme
+}
+
+#[test]
+fn generated_scheme_is_clean() {
+    assert_eq!(check_metadata(&scheme()), vec![]);
+}
+
+#[test]
+fn slugify_is_filename_safe() {
+    assert_eq!(slugify("Tokyo Night / Storm"), "tokyo-night-storm");
+    assert_eq!(slugify("--a__b--"), "a-b");
+    assert_eq!(slugify("日本"), "");
+}
+
+#[test]
+fn slugify_strips_accents() {
+    assert_eq!(slugify("Rosé Pine Dark"), "rose-pine-dark");
+    assert_eq!(slugify("Ünïcode"), "unicode");
+    assert_eq!(slugify("Ørsted Straße"), "orsted-strasse");
+
+    let scheme = generate(&GenerateConfig {
+        name: "Rosé Pine".to_string(),
+        ..Default::default()
+    })
+    .scheme;
+    assert_eq!(scheme.slug, "rose-pine-dark");
+}
+
+#[test]
+fn generated_empty_author_is_only_reported_on_request() {
+    let unset = generate(&GenerateConfig::default()).scheme;
+    assert_eq!(check_generated_metadata(&unset, false), vec![]);
+    assert_eq!(
+        check_generated_metadata(&unset, true),
+        vec![MetadataIssue::EmptyAuthor]
+    );
+    assert_eq!(check_metadata(&unset), vec![MetadataIssue::EmptyAuthor]);
+}
+
+#[test]
+fn detects_each_issue() {
+    let mut s = scheme();
+    s.name = "  ".to_string();
+    s.slug = "Test Scheme!".to_string();
+    s.author = "Jane <jane.example.com>".to_string();
+    s.variant = SchemeVariant::Light;
+
+    let issues = check_metadata(&s);
+    assert!(issues.contains(&MetadataIssue::EmptyName));
+    assert!(issues.contains(&MetadataIssue::UnsafeSlug {
+        slug: "Test Scheme!".to_string(),
+        expected: "test-scheme".to_string(),
+    }));
+    assert!(
+        issues
+            .iter()
+            .any(|i| matches!(i, MetadataIssue::AuthorFormat(r) if r.contains('@')))
+    );
+    assert!(issues.contains(&MetadataIssue::VariantMismatch {
+        declared: "light",
+        detected: "dark",
+    }));
+}
+
+#[test]
+fn fix_normalizes_what_it_can() {
+    let mut s = scheme();
+    s.name = " Test   Scheme ".to_string();
+    s.slug = "Test_Scheme".to_string();
+    s.author = String::new();
+    s.variant = SchemeVariant::Light;
+
+    let fixed = fix_metadata(&mut s);
+    assert_eq!(fixed.len(), 3);
+    assert_eq!(s.name, "Test Scheme");
+    assert_eq!(s.slug, "test-scheme");
+    assert!(matches!(s.variant, SchemeVariant::Dark));
+
+    // An empty author cannot be derived
+    assert_eq!(check_metadata(&s), vec![MetadataIssue::EmptyAuthor]);
+}
+
+#[test]
+fn description_summarizes_parameters_unless_set() {
+    let summary = scheme().description.unwrap();
+    assert!(summary.starts_with("Dark Base24 scheme on #"), "{summary}");
+    assert!(summary.contains("APCA Lc 75+"), "{summary}");
+
+    let described = generate(&GenerateConfig {
+        description: Some("Soft and blue".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(
+        described.scheme.description.as_deref(),
+        Some("Soft and blue")
+    );
+}
+
+#[test]
+fn generated_yaml_is_publishable() {
+    let yaml = serde_yaml::to_string(&scheme()).unwrap();
+    assert_eq!(check_publishable(&yaml, Some("test-scheme-dark")), vec![]);
+
+    // The file must be named after the slug
+    assert_eq!(
+        check_publishable(&yaml, Some("test")),
+        vec![PublishIssue::FileName {
+            stem: "test".to_string(),
+            expected: "test-scheme-dark".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn publish_check_reports_spec_violations() {
+    let yaml = serde_yaml::to_string(&scheme())
+        .unwrap()
+        .replace("base0A: ", "base0A: '#abc' #")
+        .replace("base17: ", "base18: ")
+        .replace("variant: dark", "variant: dim")
+        .replace("author: Jane Doe <jane@example.com>\n", "")
+        + "roles: {}\n";
+
+    let issues = check_publishable(&yaml, None);
+    for expected in [
+        PublishIssue::MissingKey("author"),
+        PublishIssue::UnknownKey("roles".to_string()),
+        PublishIssue::UnknownVariant("dim".to_string()),
+        PublishIssue::MissingSlot("base17".to_string()),
+        PublishIssue::ExtraSlot("base18".to_string()),
+        PublishIssue::BadHex {
+            slot: "base0A".to_string(),
+            value: "#abc".to_string(),
+        },
+    ] {
+        assert!(issues.contains(&expected), "{expected} not in {issues:?}");
+    }
+
+    // Metadata rules apply to the slug as written
+    let yaml = serde_yaml::to_string(&scheme())
+        .unwrap()
+        .replace("slug: test-scheme-dark", "slug: Test_Scheme");
+    assert!(matches!(
+        check_publishable(&yaml, None)[..],
+        [PublishIssue::Metadata(MetadataIssue::UnsafeSlug { .. })]
+    ));
+}
@@ -0,0 +1,152 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::config::{ConfigOverrides, load_config, load_config_with_profile};
+use themalingadingdong::migrate::{CONFIG_VERSION, migrate_config};
+
+const VERSION_1: &str = r##"
+[colors]
+background = "#1d2021"
+
+[contrast]
+target = 80.0
+extended = 65.0
+contrast_standard = "both"
+
+[profile.strict.contrast]
+target = 90.0
+minimum = 85.0
+"##;
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-migrate-{}-{name}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_migration_renames_keys() {
+    let migrated = migrate_config(VERSION_1).unwrap();
+    assert_eq!(migrated.from_version, 1);
+    assert!(!migrated.is_current());
+    assert_eq!(
+        migrated.changes,
+        [
+            "contrast.target -> contrast.minimum",
+            "profile.strict.contrast.target dropped (profile.strict.contrast.minimum is already set)",
+            "contrast.extended -> contrast.extended_minimum",
+            "contrast.contrast_standard -> contrast.standard",
+        ]
+    );
+
+    let contrast = migrated.document["contrast"].as_table().unwrap();
+    assert_eq!(contrast["minimum"].as_float(), Some(80.0));
+    assert_eq!(contrast["standard"].as_str(), Some("both"));
+    assert!(!contrast.contains_key("target"));
+    assert_eq!(
+        migrated.document["config_version"].as_integer(),
+        Some(CONFIG_VERSION.into())
+    );
+}
+
+#[test]
+fn test_migration_keeps_comments_and_order() {
+    let source = "[contrast]\n# my comment\nzeta = 1.0\ntarget = 80.0 # keep\nalpha = 2.0\n";
+    let toml = migrate_config(source).unwrap().to_toml();
+    assert!(
+        toml.contains("# my comment\nzeta = 1.0\nminimum = 80.0 # keep\nalpha = 2.0\n"),
+        "{toml}"
+    );
+}
+
+#[test]
+fn test_current_and_newer_versions() {
+    let current = format!("config_version = {CONFIG_VERSION}\n\n[contrast]\nminimum = 80.0\n");
+    let migrated = migrate_config(&current).unwrap();
+    assert!(migrated.is_current());
+    assert!(migrated.changes.is_empty());
+
+    let err = migrate_config("config_version = 99\n").unwrap_err();
+    assert!(err.to_string().contains("newer"), "{err}");
+    assert!(migrate_config("config_version = \"two\"\n").is_err());
+}
+
+#[test]
+fn test_old_configs_load_upgraded() {
+    let dir = temp_dir("load");
+    let path = dir.join("theme.toml");
+    std::fs::write(&path, VERSION_1).unwrap();
+
+    let config = load_config(Some(&path), &ConfigOverrides::default()).unwrap();
+    assert_eq!(config.contrast.minimum, 80.0);
+    assert_eq!(config.contrast.extended_minimum, 65.0);
+    assert_eq!(config.config_version, CONFIG_VERSION);
+    let strict =
+        load_config_with_profile(Some(&path), Some("strict"), &ConfigOverrides::default()).unwrap();
+    assert_eq!(strict.contrast.minimum, 85.0);
+
+    // Saved configs record the current version
+    let saved = dir.join("saved.toml");
+    config.save(&saved).unwrap();
+    let content = std::fs::read_to_string(&saved).unwrap();
+    assert!(content.starts_with(&format!("config_version = {CONFIG_VERSION}\n")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_migrate_config_command() {
+    let dir = temp_dir("command");
+    let path = dir.join("theme.toml");
+    std::fs::write(&path, VERSION_1).unwrap();
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("migrate-config")
+        .arg(&path)
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "contrast.target -> contrast.minimum",
+        ));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("theme.toml.bak")).unwrap(),
+        VERSION_1
+    );
+    let upgraded = std::fs::read_to_string(&path).unwrap();
+    assert!(migrate_config(&upgraded).unwrap().is_current());
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("migrate-config")
+        .arg(&path)
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("already at config version"));
+
+    // --output and --dry-run belong to the subcommand
+    let old = dir.join("old.toml");
+    std::fs::write(&old, VERSION_1).unwrap();
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("migrate-config")
+        .arg(&old)
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("minimum = 80.0"));
+    assert_eq!(std::fs::read_to_string(&old).unwrap(), VERSION_1);
+    let new = dir.join("new.toml");
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("migrate-config")
+        .arg(&old)
+        .arg("--output")
+        .arg(&new)
+        .assert()
+        .success();
+    assert!(
+        migrate_config(&std::fs::read_to_string(&new).unwrap())
+            .unwrap()
+            .is_current()
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,75 @@
+//! Tests for embedding provenance and reproducing output from it.
+
+use palette::Srgb;
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::provenance::{Provenance, reproduce};
+use themalingadingdong::roles::RolesConfig;
+use tinted_builder::SchemeVariant;
+
+fn config() -> GenerateConfig {
+    GenerateConfig {
+        background: Srgb::new(0x1d, 0x20, 0x21),
+        foreground: Srgb::new(0xeb, 0xdb, 0xb2),
+        name: "Provenance Test".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_every_format_reproduces_byte_identical() {
+    for format in [OutputFormat::Yaml, OutputFormat::Json, OutputFormat::Css] {
+        let mut provenance = Provenance::new(&config(), Some(SchemeVariant::Light), format);
+        provenance.config.roles = Some(RolesConfig::default());
+        let output = provenance.regenerate().unwrap();
+
+        let extracted = Provenance::extract(&output).unwrap();
+        assert_eq!(extracted.format, format);
+        assert_eq!(extracted.config.theme.variant.as_deref(), Some("light"));
+
+        let report = reproduce(&output).unwrap();
+        assert!(report.is_identical(), "{format:?}: {report}");
+        assert!(report.lut_mismatches.is_empty());
+    }
+}
+
+#[test]
+fn test_json_provenance_keeps_scheme_valid_json() {
+    let output = Provenance::new(&config(), None, OutputFormat::Json)
+        .regenerate()
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(value["name"], "Provenance Test");
+    assert_eq!(value["provenance"]["format"], "json");
+    assert_eq!(
+        value["provenance"]["config"]["colors"]["background"],
+        "#1d2021"
+    );
+}
+
+#[test]
+fn test_reproduce_reports_changed_output_and_tables() {
+    let output = Provenance::new(&config(), None, OutputFormat::Yaml)
+        .regenerate()
+        .unwrap();
+    let line = output
+        .lines()
+        .position(|l| l.trim_start().starts_with("base0D:"))
+        .unwrap();
+    let tampered = output
+        .replacen("base0D:", "base0D: '000000' #", 1)
+        .replacen("CUSP_LUT: ", "CUSP_LUT: 0", 1);
+
+    let report = reproduce(&tampered).unwrap();
+    assert_eq!(report.first_difference, Some(line + 1));
+    assert_eq!(report.lut_mismatches, vec!["CUSP_LUT".to_string()]);
+}
+
+#[test]
+fn test_extract_without_provenance_fails() {
+    let plain = OutputFormat::Yaml
+        .serialize(&generate(&config()).scheme)
+        .unwrap();
+    assert!(Provenance::extract(&plain).is_err());
+}
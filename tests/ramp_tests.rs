@@ -0,0 +1,107 @@
+//! Tests for the lightness ramp designer.
+
+use palette::Srgb;
+use themalingadingdong::apca::apca_contrast;
+use themalingadingdong::builder::ThemeBuilder;
+use themalingadingdong::config::ThemeConfig;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::ramp::LightnessRamp;
+
+fn slot_contrast(config: &GenerateConfig, slot: &str) -> f64 {
+    let scheme = generate(config).scheme;
+    let rgb = |slot: &str| {
+        let c = &scheme.palette[slot];
+        Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2)
+    };
+    apca_contrast(rgb(slot), rgb("base00")).abs()
+}
+
+#[test]
+fn test_ramp_places_targeted_slots() {
+    let config = GenerateConfig {
+        ramp: LightnessRamp {
+            base01: Some(10.0),
+            base02: Some(25.0),
+            base04: Some(60.0),
+            ..LightnessRamp::default()
+        },
+        ..GenerateConfig::default()
+    };
+    for (slot, target) in [("base01", 10.0), ("base02", 25.0), ("base04", 60.0)] {
+        let lc = slot_contrast(&config, slot);
+        assert!(
+            (lc - target).abs() <= 0.5,
+            "{slot}: Lc {lc:.1}, target {target}"
+        );
+    }
+
+    // Untargeted slots stay on the curve
+    let curve = GenerateConfig::default();
+    assert_eq!(
+        generate(&config).scheme.palette["base03"].to_hex(),
+        generate(&curve).scheme.palette["base03"].to_hex()
+    );
+}
+
+#[test]
+fn test_ramp_warns_past_foreground() {
+    let config = GenerateConfig {
+        ramp: LightnessRamp {
+            base06: Some(106.0),
+            ..LightnessRamp::default()
+        },
+        ..GenerateConfig::default()
+    };
+    let result = generate(&config);
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|w| w.starts_with("base06") && w.contains("ramp target")),
+        "{:?}",
+        result.warnings
+    );
+}
+
+#[test]
+fn test_ramp_validation() {
+    let ramp = |targets| LightnessRamp::from_targets(targets);
+    assert!(
+        ramp([Some(10.0), None, Some(40.0), None, None, Some(90.0)])
+            .validate()
+            .is_ok()
+    );
+
+    let err = ramp([Some(30.0), Some(20.0), None, None, None, None])
+        .validate()
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("ramp.base02"), "{err}");
+
+    assert!(
+        ramp([Some(120.0), None, None, None, None, None])
+            .validate()
+            .is_err()
+    );
+    assert!(
+        ramp([Some(0.0), None, None, None, None, None])
+            .validate()
+            .is_err()
+    );
+
+    let built = ThemeBuilder::new()
+        .ramp(ramp([None, Some(50.0), Some(50.0), None, None, None]))
+        .build();
+    assert!(built.is_err());
+}
+
+#[test]
+fn test_ramp_config_roundtrip() {
+    let config: ThemeConfig = toml::from_str("[ramp]\nbase01 = 8\nbase02 = 20\n").unwrap();
+    assert_eq!(config.ramp.targets()[..3], [Some(8.0), Some(20.0), None]);
+    assert_eq!(config.to_generate_config().unwrap().ramp, config.ramp);
+
+    // An empty ramp is omitted when saving
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("ramp"));
+}
@@ -0,0 +1,129 @@
+//! Tests for rendering tinted-theming template repositories.
+
+use std::path::{Path, PathBuf};
+
+use palette::Srgb;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::render::{render_template_dir, template_dirs};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-render-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A template repository with one Base24 template and one legacy Base16 template.
+fn write_repo(dir: &Path) {
+    let templates = dir.join("templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(
+        templates.join("config.yaml"),
+        r#"
+default:
+  filename: "colors/{{ scheme-system }}-{{ scheme-slug }}.conf"
+  supported-systems: [base16, base24]
+legacy:
+  extension: .vim
+  output: vim
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("default.mustache"),
+        "{{scheme-name}} bg={{base00-hex}} bright-red={{base12-hex}}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("legacy.mustache"),
+        "\" {{scheme-system}} {{scheme-name}} red={{base08-hex}}\n",
+    )
+    .unwrap();
+}
+
+fn scheme() -> tinted_builder::Base16Scheme {
+    let mut scheme = generate(&GenerateConfig {
+        background: Srgb::new(0x1d, 0x20, 0x21),
+        foreground: Srgb::new(0xeb, 0xdb, 0xb2),
+        ..Default::default()
+    })
+    .scheme;
+    scheme.name = "Render Test".into();
+    scheme.slug = "render-test".into();
+    scheme
+}
+
+#[test]
+fn test_render_base24_and_legacy_templates() {
+    let dir = temp_dir("repo");
+    write_repo(&dir);
+    let scheme = scheme();
+
+    let mut written = render_template_dir(&dir, &scheme, &dir).unwrap();
+    written.sort();
+    assert_eq!(
+        written,
+        vec![
+            dir.join("colors/base24-render-test.conf"),
+            dir.join("vim/base16-render-test.vim"),
+        ]
+    );
+
+    let hex = |slot: &str| scheme.palette[slot].to_hex();
+    let default = std::fs::read_to_string(&written[0]).unwrap();
+    assert_eq!(
+        default,
+        format!(
+            "Render Test bg={} bright-red={}\n",
+            hex("base00"),
+            hex("base12")
+        )
+    );
+    let legacy = std::fs::read_to_string(&written[1]).unwrap();
+    assert_eq!(
+        legacy,
+        format!("\" base16 Render Test red={}\n", hex("base08"))
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_render_rejects_escaping_filename() {
+    let dir = temp_dir("escape");
+    let templates = dir.join("templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(
+        templates.join("config.yaml"),
+        "default:\n  filename: \"../{{ scheme-slug }}.conf\"\n",
+    )
+    .unwrap();
+    std::fs::write(templates.join("default.mustache"), "x").unwrap();
+
+    let err = render_template_dir(&dir, &scheme(), &dir).unwrap_err();
+    assert!(err.to_string().contains("escapes"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_template_glob_finds_repositories() {
+    let root = temp_dir("glob");
+    write_repo(&root.join("base16-vim"));
+    write_repo(&root.join("base16-kitty"));
+    write_repo(&root.join("tinted-shell"));
+    // Not a template repository
+    std::fs::create_dir_all(root.join("base16-empty")).unwrap();
+
+    let pattern = root.join("base16-*");
+    assert_eq!(
+        template_dirs(pattern.to_str().unwrap()),
+        vec![root.join("base16-kitty"), root.join("base16-vim")]
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
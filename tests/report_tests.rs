@@ -0,0 +1,134 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::report::{REPORT_SCHEMA_VERSION, SolverReport, ValidationReport};
+use themalingadingdong::validation::validate;
+
+fn run_report(args: &[&str], stdin: Option<&str>) -> (bool, serde_json::Value) {
+    let mut cmd = cargo_bin_cmd!("themalingadingdong");
+    cmd.args(args).arg("--json-report");
+    if let Some(stdin) = stdin {
+        cmd.write_stdin(stdin);
+    }
+    let output = cmd.output().unwrap();
+    let report = serde_json::from_slice(&output.stdout).expect("stdout is one JSON document");
+    (output.status.success(), report)
+}
+
+#[test]
+fn test_solver_report_covers_every_accent() {
+    let result = generate(&GenerateConfig::default());
+    let solver = SolverReport::new(&result);
+
+    let slots: Vec<&str> = solver.accents.iter().map(|a| a.slot.as_str()).collect();
+    assert_eq!(slots.len(), 16);
+    assert_eq!(slots[0], "base08");
+    assert_eq!(slots[7], "base0F");
+    assert_eq!(slots[15], "base17");
+    assert_eq!(solver.timings.len(), 5);
+}
+
+#[test]
+fn test_validation_report_lists_required_checks_first() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let report = ValidationReport::new(&validate(&scheme));
+
+    assert!(report.checks[..report.required].iter().all(|c| c.required));
+    assert!(report.checks[report.required..].iter().all(|c| !c.required));
+    assert!(report.required_passed <= report.required);
+}
+
+#[test]
+fn test_json_report_from_cli_args() {
+    let (success, report) = run_report(
+        &["-b", "#1a1a2e", "-f", "#eaeaea", "--name", "Report"],
+        None,
+    );
+    assert!(success);
+    assert_eq!(report["schema_version"], REPORT_SCHEMA_VERSION);
+
+    let schemes = report["schemes"].as_array().unwrap();
+    assert_eq!(schemes.len(), 1);
+    let entry = &schemes[0];
+    assert_eq!(entry["scheme"]["name"], "Report");
+    assert_eq!(entry["scheme"]["palette"]["base00"], "1a1a2e");
+    assert!(entry["validation"]["checks"].as_array().unwrap().len() > 20);
+    assert!(entry["warnings"]["generation"].is_array());
+    assert_eq!(entry["solver"]["accents"].as_array().unwrap().len(), 16);
+}
+
+#[test]
+fn test_json_report_reads_config_json_from_stdin() {
+    let config = r##"{
+        "colors": { "background": "#fdf6e3", "foreground": "#073642" },
+        "theme": { "name": "Piped", "author": "CI" }
+    }"##;
+    let (success, report) = run_report(&["--config", "-"], Some(config));
+    assert!(success);
+
+    let scheme = &report["schemes"][0]["scheme"];
+    assert_eq!(scheme["name"], "Piped");
+    assert_eq!(scheme["variant"], "light");
+    assert_eq!(scheme["palette"]["base00"], "fdf6e3");
+}
+
+#[test]
+fn test_json_report_both_variants_without_output() {
+    let (success, report) = run_report(
+        &[
+            "-b",
+            "#1a1a2e",
+            "-f",
+            "#eaeaea",
+            "--name",
+            "Both",
+            "--variant",
+            "both",
+        ],
+        None,
+    );
+    assert!(success);
+
+    let variants: Vec<&str> = report["schemes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["scheme"]["variant"].as_str().unwrap())
+        .collect();
+    assert_eq!(variants, ["dark", "light"]);
+}
+
+#[test]
+fn test_json_report_no_adjust_still_reports_failures() {
+    let (success, report) = run_report(
+        &[
+            "-b",
+            "#1a1a2e",
+            "-f",
+            "#6a6a7a",
+            "--name",
+            "Dim",
+            "--no-adjust",
+        ],
+        None,
+    );
+    assert!(!success);
+
+    let entry = &report["schemes"][0];
+    assert_eq!(entry["passed"], false);
+    assert!(
+        !entry["warnings"]["validation"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_stdin_config_rejected_for_watch() {
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["--config", "-", "--watch", "-o", "/dev/null"])
+        .write_stdin("{}")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("not standard input"));
+}
@@ -0,0 +1,89 @@
+use palette::Srgb;
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::config::{ThemeConfig, validate_config};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::roles::{RolesConfig, derive_roles};
+use tinted_builder::Base16Scheme;
+
+fn gruvbox_dark() -> Base16Scheme {
+    generate(&GenerateConfig {
+        background: Srgb::new(0x1du8, 0x20, 0x21),
+        foreground: Srgb::new(0xebu8, 0xdb, 0xb2),
+        ..Default::default()
+    })
+    .scheme
+}
+
+#[test]
+fn test_roles_fall_back_to_next_candidate() {
+    let scheme = gruvbox_dark();
+    // base01 barely differs from base00, so it cannot carry error text
+    let config = RolesConfig {
+        error: vec!["base01".into(), "base08".into()],
+        ..Default::default()
+    };
+    let roles = derive_roles(&scheme, &config);
+    let error = roles.get("error").unwrap();
+    assert_eq!(error.slot, "base08");
+    assert!(error.passes);
+    assert!(roles.warnings().is_empty());
+
+    // With no passing candidate, the best one is used and reported
+    let config = RolesConfig {
+        error: vec!["base01".into()],
+        ..Default::default()
+    };
+    let roles = derive_roles(&scheme, &config);
+    assert_eq!(roles.get("error").unwrap().slot, "base01");
+    assert!(!roles.get("error").unwrap().passes);
+    assert_eq!(roles.warnings().len(), 1);
+}
+
+#[test]
+fn test_roles_config_validation() {
+    let toml_str = r##"
+[colors]
+background = "#1a1a2e"
+foreground = "#eaeaea"
+
+[roles]
+cursor = ["base07"]
+"##;
+
+    let mut config: ThemeConfig = toml::from_str(toml_str).unwrap();
+    let roles = config.roles.clone().unwrap();
+    assert_eq!(roles.cursor, vec!["base07".to_string()]);
+    assert_eq!(roles.error, RolesConfig::default().error);
+    assert!(validate_config(&config).is_ok());
+
+    for bad in [
+        vec![],
+        vec!["base18".to_string()],
+        vec!["base0a".to_string()],
+    ] {
+        config.roles = Some(RolesConfig {
+            info: bad,
+            ..Default::default()
+        });
+        assert!(validate_config(&config).is_err());
+    }
+}
+
+#[test]
+fn test_roles_section_keeps_scheme_readable() {
+    let scheme = gruvbox_dark();
+    let roles = derive_roles(&scheme, &RolesConfig::default());
+    let yaml = OutputFormat::Yaml
+        .serialize_with(&scheme, None, Some(&roles), None, None)
+        .unwrap();
+
+    assert!(yaml.contains("\nroles:\n  error: "));
+    // Template tools ignore the extra section when reading the scheme
+    let parsed: Base16Scheme = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(parsed.palette.len(), scheme.palette.len());
+
+    let css = OutputFormat::Css
+        .serialize_with(&scheme, None, Some(&roles), None, None)
+        .unwrap();
+    assert!(css.contains("--selection-bg: var(--base02);"));
+}
@@ -0,0 +1,106 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::generate::GenerateConfig;
+use themalingadingdong::hellwig::hellwig_lightness;
+use themalingadingdong::schedule::{ScheduleManifest, generate_schedule};
+use tinted_builder::SchemeVariant;
+
+fn config() -> GenerateConfig {
+    GenerateConfig {
+        name: "Dusk".to_string(),
+        background: Srgb::new(26, 26, 46),
+        foreground: Srgb::new(234, 234, 234),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_schedule_runs_light_to_dark() {
+    let schedule = generate_schedule(&config(), 5);
+    assert_eq!(schedule.len(), 5);
+
+    let backgrounds: Vec<f32> = schedule
+        .iter()
+        .map(|step| {
+            let (r, g, b) = step.result.scheme.palette["base00"].rgb;
+            hellwig_lightness(Srgb::new(r, g, b))
+        })
+        .collect();
+    assert!(
+        backgrounds.windows(2).all(|pair| pair[0] > pair[1]),
+        "{backgrounds:?}"
+    );
+
+    let variants: Vec<bool> = schedule
+        .iter()
+        .map(|step| matches!(step.result.scheme.variant, SchemeVariant::Light))
+        .collect();
+    assert_eq!(variants, [true, true, false, false, false]);
+
+    // Every step solves its accents at the same hues
+    let hues = |i: usize| -> Vec<f32> {
+        schedule[i]
+            .result
+            .base_accent_results
+            .iter()
+            .map(|r| r.hue)
+            .collect()
+    };
+    for i in 1..schedule.len() {
+        for (a, b) in hues(0).iter().zip(hues(i)) {
+            assert!((a - b).abs() < 0.5, "step {i}: {a} vs {b}");
+        }
+    }
+
+    let slugs: Vec<&str> = schedule
+        .iter()
+        .map(|step| step.result.scheme.slug.as_str())
+        .collect();
+    assert_eq!(slugs, ["dusk-1", "dusk-2", "dusk-3", "dusk-4", "dusk-5"]);
+    assert_eq!(schedule[1].result.scheme.name, "Dusk (2 of 5)");
+}
+
+#[test]
+fn test_manifest_lists_steps_in_order() {
+    let schedule = generate_schedule(&config(), 3);
+    let times = ["12:00", "18:00", "22:00"].map(String::from);
+    let manifest = ScheduleManifest::new(&schedule, "yaml", &times);
+
+    let toml = toml::to_string_pretty(&manifest).unwrap();
+    assert_eq!(toml.matches("[[steps]]").count(), 3);
+    assert!(toml.contains("time = \"18:00\""), "{toml}");
+    assert!(toml.contains("scheme = \"base24-dusk-2\""), "{toml}");
+    assert!(toml.contains("file = \"dusk-3.yaml\""), "{toml}");
+
+    // Times are optional
+    let untimed = ScheduleManifest::new(&schedule, "json", &[]);
+    assert!(untimed.steps.iter().all(|step| step.time.is_none()));
+}
+
+#[test]
+fn test_schedule_command() {
+    let dir = std::env::temp_dir().join(format!(
+        "themalingadingdong-schedule-{}",
+        std::process::id()
+    ));
+
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1a1a2e", "-f", "#eaeaea", "--name", "Dusk"])
+        .args(["schedule", "--times", "12:00,18:00,22:00", "--output-dir"])
+        .arg(&dir)
+        .assert()
+        .success();
+    for file in ["dusk-1.yaml", "dusk-2.yaml", "dusk-3.yaml", "schedule.toml"] {
+        assert!(dir.join(file).exists(), "missing {file}");
+    }
+
+    for times in ["12:00", "25:00,26:00"] {
+        cargo_bin_cmd!("themalingadingdong")
+            .args(["schedule", "--times", times, "--output-dir"])
+            .arg(&dir)
+            .assert()
+            .failure();
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,109 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::score::{ScoreWeights, score_scheme, score_scheme_with};
+use themalingadingdong::validation::ValidationConfig;
+use tinted_builder::{Base16Scheme, Color};
+
+fn default_scheme() -> Base16Scheme {
+    generate(&GenerateConfig::default()).scheme
+}
+
+#[test]
+fn test_scores_are_normalized() {
+    let score = score_scheme(&default_scheme());
+
+    assert!((0.0..=100.0).contains(&score.score));
+    for metric in [
+        score.metrics.contrast_margin_mean,
+        score.metrics.contrast_margin_min,
+        score.metrics.lightness_uniformity,
+        score.metrics.chroma_variance,
+        score.metrics.hue_spacing,
+        score.metrics.cvd_separability,
+    ] {
+        assert!((0.0..=1.0).contains(&metric.score), "{metric:?}");
+    }
+    // The solver holds accents at a uniform lightness
+    assert!(score.metrics.lightness_uniformity.score > 0.9);
+}
+
+#[test]
+fn test_single_weight_scores_that_metric() {
+    let weights = ScoreWeights {
+        contrast_margin_mean: 0.0,
+        contrast_margin_min: 0.0,
+        lightness_uniformity: 0.0,
+        chroma_variance: 0.0,
+        hue_spacing: 1.0,
+        cvd_separability: 0.0,
+    };
+    let score = score_scheme_with(&default_scheme(), &ValidationConfig::default(), &weights);
+
+    assert!((score.score - 100.0 * score.metrics.hue_spacing.score).abs() < 1e-9);
+}
+
+#[test]
+fn test_collapsed_accents_score_lower() {
+    let scheme = default_scheme();
+    let mut collapsed = scheme.clone();
+    let red = scheme.palette["base08"].clone();
+    for slot in ["base09", "base0A", "base0B"] {
+        collapsed.palette.insert(slot.to_string(), red.clone());
+    }
+    // A muddy, low-contrast accent
+    let (r, g, b) = scheme.palette["base00"].rgb;
+    let muddy = Srgb::new(
+        r.saturating_add(30),
+        g.saturating_add(30),
+        b.saturating_add(30),
+    );
+    collapsed.palette.insert(
+        "base0C".to_string(),
+        Color::new(format!(
+            "{:02x}{:02x}{:02x}",
+            muddy.red, muddy.green, muddy.blue
+        ))
+        .unwrap(),
+    );
+
+    let original = score_scheme(&scheme);
+    let worse = score_scheme(&collapsed);
+    assert!(worse.score < original.score);
+    assert_eq!(worse.metrics.hue_spacing.value, 0.0);
+    assert!(worse.metrics.contrast_margin_min.value < original.metrics.contrast_margin_min.value);
+    assert!(worse.metrics.cvd_separability.score < original.metrics.cvd_separability.score);
+}
+
+#[test]
+fn test_cli_score_ranks_files() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-score-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let good = dir.join("good.yaml");
+    let weak = dir.join("weak.yaml");
+    // A dim foreground fails the body-text checks
+    for (path, foreground) in [(&weak, "#6a6a7a"), (&good, "#eaeaea")] {
+        cargo_bin_cmd!("themalingadingdong")
+            .args(["-b", "#1a1a2e", "-f", foreground, "--name", "Scored", "-o"])
+            .arg(path)
+            .assert()
+            .success();
+    }
+
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .arg("score")
+        .args([&weak, &good])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let ranked: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let ranked = ranked.as_array().unwrap();
+
+    assert_eq!(ranked.len(), 2);
+    assert_eq!(ranked[0]["rank"], 1);
+    assert_eq!(ranked[0]["source"], good.display().to_string());
+    assert!(ranked[0]["score"].as_f64() > ranked[1]["score"].as_f64());
+    assert!(ranked[1]["metrics"]["hue_spacing"]["weight"].is_number());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,41 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "themalingadingdong-screenshot-{}-{name}",
+        std::process::id()
+    ))
+}
+
+#[test]
+fn test_screenshot_svg() {
+    let path = temp_path("shot.svg");
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Shot"])
+        .arg("--screenshot")
+        .arg(&path)
+        .assert()
+        .success();
+
+    let svg = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(svg.starts_with("<svg"), "{svg}");
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains("<title>Shot</title>"));
+    // The scheme's background fills the image, and text is drawn in its
+    // foreground
+    assert!(svg.contains(r##"fill="#1d2021""##));
+    assert!(svg.contains("<text"));
+}
+
+#[test]
+fn test_screenshot_rejects_other_formats() {
+    let path = temp_path("shot.jpg");
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Shot"])
+        .arg("--screenshot")
+        .arg(&path)
+        .assert()
+        .failure();
+    assert!(!path.exists());
+}
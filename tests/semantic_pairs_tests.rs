@@ -0,0 +1,145 @@
+//! Tests for semantic accent pairs.
+
+use palette::Srgb;
+use themalingadingdong::accent_solver::optimize_accents;
+use themalingadingdong::config::{AccentOptSettings, ConfigError, ThemeConfig};
+use themalingadingdong::config_schema::check_config;
+use themalingadingdong::cvd::CvdKind;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::semantic_pairs::{SemanticPairs, SemanticSeparation, Separation};
+use themalingadingdong::validation::{
+    ContrastStandard, ValidationConfig, validate_with_config, validation_warnings,
+};
+
+#[test]
+fn test_semantic_table_is_validated() {
+    let config: ThemeConfig = toml::from_str(
+        "[validation.semantic]\npairs = [\"base0B/base08\", \"base0a/base08\"]\nmin_hue = 40\n",
+    )
+    .unwrap();
+    let semantic = &config.validation.semantic;
+    assert!(semantic.validate().is_ok());
+    assert_eq!(
+        semantic.slots(),
+        vec![("base0B", "base08"), ("base0A", "base08")]
+    );
+    assert_eq!(semantic.min_hue, 40.0);
+    assert_eq!(semantic.min_lightness, 8.0);
+
+    for (pairs, expected) in [
+        (vec!["base0B"], "missing '/'"),
+        (vec!["base05/base08"], "not an accent"),
+        (vec!["base08/BASE08"], "paired with itself"),
+    ] {
+        let semantic = SemanticPairs {
+            pairs: pairs.into_iter().map(String::from).collect(),
+            ..Default::default()
+        };
+        assert!(
+            matches!(
+                semantic.validate(),
+                Err(ConfigError::InvalidValue(ref msg)) if msg.contains(expected)
+            ),
+            "{:?}",
+            semantic.validate()
+        );
+    }
+    let semantic = SemanticPairs {
+        min_hue: 0.0,
+        ..Default::default()
+    };
+    assert!(semantic.validate().is_err());
+
+    // Unset pairs are left out of written configs
+    let written = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!written.contains("semantic"));
+
+    let diagnostics = check_config("[validation.semantic]\npairs = [\"base0B/base05\"]\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 2);
+    assert!(diagnostics[0].key.starts_with("validation.semantic.pairs"));
+}
+
+#[test]
+fn test_apply_maps_pairs_into_accent_groups() {
+    let semantic = SemanticPairs {
+        pairs: vec!["base0B/base08".into(), "base13/base10".into()],
+        ..Default::default()
+    };
+    let settings = AccentOptSettings::default();
+    let base = semantic.apply(&settings, 0).semantic.unwrap();
+    assert_eq!(base.pairs, vec![(3, 0)]);
+    let extended = semantic.apply(&settings, 1).semantic.unwrap();
+    assert_eq!(extended.pairs, vec![(3, 0)]);
+    assert!(
+        SemanticPairs::default()
+            .apply(&settings, 0)
+            .semantic
+            .is_none()
+    );
+}
+
+#[test]
+fn test_failing_pairs_are_required_checks() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let plain = validate_with_config(
+        &scheme,
+        &[],
+        &[],
+        ContrastStandard::default(),
+        &ValidationConfig::default(),
+    );
+    // base08 and base09 are neighbors, so a wide hue requirement can't be
+    // met and lightness alone is unlikely to cover 50 J'
+    let config = ValidationConfig {
+        semantic: SemanticPairs {
+            pairs: vec!["base08/base09".into()],
+            min_hue: 180.0,
+            min_lightness: 50.0,
+        },
+        ..Default::default()
+    };
+    let results = validate_with_config(&scheme, &[], &[], ContrastStandard::default(), &config);
+    assert_eq!(results.semantic.len(), 1);
+    assert!(!results.semantic[0].passes);
+    assert_eq!(results.required_count(), plain.required_count() + 1);
+    assert!(!results.passes());
+
+    let warnings = validation_warnings(&scheme, ContrastStandard::default(), &config);
+    let warning = warnings
+        .iter()
+        .find(|w| w.starts_with("Semantic pair base08 vs base09"))
+        .unwrap();
+    assert!(warning.contains("required: hue 180° or lightness 50.0"));
+}
+
+#[test]
+fn test_solver_separates_semantic_pairs() {
+    let hues = [25.0, 55.0, 90.0, 145.0, 180.0, 250.0, 285.0, 335.0];
+    let background = Srgb::new(0, 0, 0);
+    let score = |settings: &AccentOptSettings| {
+        let result = optimize_accents(background, &hues, settings, 75.0);
+        let color = |i: usize| {
+            let c = result.hue_results[i].color;
+            Srgb::new(c.red, c.green, c.blue).into_format::<u8>()
+        };
+        CvdKind::ALL
+            .map(Some)
+            .into_iter()
+            .chain([None])
+            .map(|vision| Separation::between(color(0), color(3), vision).score(30.0, 8.0))
+            .fold(f32::INFINITY, f32::min)
+    };
+
+    let mut settings = AccentOptSettings::default();
+    let before = score(&settings);
+    settings.semantic = Some(SemanticSeparation {
+        pairs: vec![(0, 3)],
+        min_hue: 30.0,
+        min_lightness: 8.0,
+    });
+    let after = score(&settings);
+    // Red and green collapse under deuteranopia at equal lightness
+    assert!(before < 1.0, "{before}");
+    assert!(after >= 1.0, "{after}");
+}
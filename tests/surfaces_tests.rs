@@ -0,0 +1,104 @@
+//! Tests for the extended surface ramp.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::config::{ThemeConfig, validate_config};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::hellwig_lightness;
+use themalingadingdong::surfaces::{SurfaceRamp, SurfacesConfig};
+use tinted_builder::Base16Scheme;
+
+fn hex(scheme: &Base16Scheme, slot: &str) -> String {
+    let (r, g, b) = scheme.palette[slot].rgb;
+    format!("{r:02x}{g:02x}{b:02x}")
+}
+
+fn lightness(hex: &str) -> f32 {
+    let value = u32::from_str_radix(hex, 16).unwrap();
+    hellwig_lightness(palette::Srgb::new(
+        (value >> 16) as u8,
+        (value >> 8) as u8,
+        value as u8,
+    ))
+}
+
+#[test]
+fn test_half_steps_keep_the_surfaces() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let ramp = SurfaceRamp::new(&scheme, 15);
+    assert_eq!(ramp.shades.len(), 15);
+    for (index, slot) in [
+        "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07",
+    ]
+    .iter()
+    .enumerate()
+    {
+        assert_eq!(ramp.shades[index * 2], hex(&scheme, slot), "{slot}");
+    }
+}
+
+#[test]
+fn test_shades_step_monotonically() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let ramp = SurfaceRamp::new(&scheme, 16);
+    assert_eq!(ramp.shades.first(), Some(&hex(&scheme, "base00")));
+    assert_eq!(ramp.shades.last(), Some(&hex(&scheme, "base07")));
+
+    let lightness: Vec<f32> = ramp.shades.iter().map(|h| lightness(h)).collect();
+    for pair in lightness.windows(2) {
+        assert!(pair[1] > pair[0] - 0.5, "{lightness:?}");
+    }
+}
+
+#[test]
+fn test_surfaces_config_validation() {
+    let mut config = ThemeConfig::default();
+    config.colors.background = Some("#1d2021".to_string());
+    config.colors.foreground = Some("#ebdbb2".to_string());
+    config.surfaces = Some(SurfacesConfig::default());
+    assert!(validate_config(&config).is_ok());
+
+    config.surfaces = Some(SurfacesConfig { steps: 1 });
+    let err = validate_config(&config).unwrap_err().to_string();
+    assert!(err.contains("surfaces.steps"), "{err}");
+}
+
+#[test]
+fn test_surfaces_section_in_output() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let ramp = SurfaceRamp::new(&scheme, 4);
+    let yaml = OutputFormat::Yaml
+        .serialize_with(&scheme, None, None, None, Some(&ramp))
+        .unwrap();
+    assert!(
+        yaml.contains(&format!("\nsurfaces:\n- {}\n", ramp.shades[0])),
+        "{yaml}"
+    );
+    let parsed: Base16Scheme = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(parsed.palette.len(), scheme.palette.len());
+
+    let css = OutputFormat::Css
+        .serialize_prefixed(&scheme, None, None, None, Some(&ramp), "theme-")
+        .unwrap();
+    assert!(css.contains(&format!("  --theme-surface-3: #{};", ramp.shades[3])));
+
+    let json: serde_json::Value = serde_json::from_slice(
+        &cargo_bin_cmd!("themalingadingdong")
+            .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Shades"])
+            .args(["--format", "json", "--surface-steps", "15"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout,
+    )
+    .unwrap();
+    let surfaces = json["surfaces"].as_array().unwrap();
+    assert_eq!(surfaces.len(), 15);
+    assert_eq!(surfaces[14], json["palette"]["base07"]);
+
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Shades"])
+        .args(["--surface-steps", "1"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,79 @@
+//! Tests for terminal color depth detection and preview downsampling.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use palette::Srgb;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::preview::render_preview_at;
+use themalingadingdong::term_color::{ColorDepth, TermColor, TermPalette};
+
+#[test]
+fn test_depth_from_env() {
+    for (colorterm, term, depth) in [
+        (Some("24bit"), Some("screen"), ColorDepth::TrueColor),
+        (None, Some("xterm-direct"), ColorDepth::TrueColor),
+        (None, Some("tmux-256color"), ColorDepth::Ansi256),
+        (Some(""), Some("xterm"), ColorDepth::Ansi16),
+        (None, Some("dumb"), ColorDepth::Ansi16),
+    ] {
+        assert_eq!(ColorDepth::from_env(colorterm, term), depth, "{term:?}");
+    }
+}
+
+#[test]
+fn test_escape_codes() {
+    assert_eq!(TermColor::Indexed(1).fg(), "\x1b[31m");
+    assert_eq!(TermColor::Indexed(9).bg(), "\x1b[101m");
+    assert_eq!(TermColor::Indexed(196).fg(), "\x1b[38;5;196m");
+    assert_eq!(TermColor::Rgb(Srgb::new(1, 2, 3)).bg(), "\x1b[48;2;1;2;3m");
+}
+
+#[test]
+fn test_ansi16_uses_the_schemes_ansi_colors() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let mut palette = TermPalette::new(ColorDepth::Ansi16, Some(&scheme));
+    let (r, g, b) = scheme.palette["base08"].rgb;
+    assert_eq!(palette.map(Srgb::new(r, g, b)), TermColor::Indexed(1));
+    let (r, g, b) = scheme.palette["base0D"].rgb;
+    assert_eq!(palette.map(Srgb::new(r, g, b)), TermColor::Indexed(4));
+}
+
+#[test]
+fn test_preview_at_256_colors() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let preview = render_preview_at(&scheme, ColorDepth::Ansi256);
+    assert!(preview.contains("\x1b[48;5;"));
+    assert!(!preview.contains(";2;"));
+    assert!(preview.contains("ANSI 0-15:"));
+}
+
+#[test]
+fn test_color_depth_flag() {
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--color-depth", "16"])
+        .arg("preview")
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stdout.contains("\x1b[40m"), "{stdout}");
+    assert!(!stdout.contains("\x1b[48;2;"));
+    assert!(stderr.contains("terminal shows 16 colors"), "{stderr}");
+
+    // Piped output isn't downsampled from the environment alone
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .env("TERM", "xterm-256color")
+        .env_remove("COLORTERM")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "preview"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .contains("\x1b[48;2;29;32;33m")
+    );
+    assert!(output.stderr.is_empty());
+}
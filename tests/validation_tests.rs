@@ -1,6 +1,13 @@
 use palette::Srgb;
+use themalingadingdong::config::TerminalAttributes;
+use themalingadingdong::config_schema::check_config;
 use themalingadingdong::generate::{GenerateConfig, generate};
-use themalingadingdong::validation::{validate, validate_with_warnings};
+use themalingadingdong::validation::{
+    ContrastStandard, TextAttribute, TextUsage, ValidationConfig, parse_pair_rule,
+    terminal_attribute_warnings, validate, validate_terminal_attributes, validate_with_config,
+    validate_with_standard, validate_with_warnings, validate_with_warnings_for,
+    validation_warnings,
+};
 
 #[test]
 fn test_high_contrast_scheme_passes() {
@@ -78,3 +85,231 @@ fn test_accent_colors_meet_target_contrast() {
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_results_report_both_metrics() {
+    let config = GenerateConfig {
+        background: Srgb::new(0u8, 0, 0),
+        foreground: Srgb::new(255u8, 255, 255),
+        ..Default::default()
+    };
+    let scheme = generate(&config).scheme;
+
+    let results = validate(&scheme);
+    assert_eq!(results.standard, ContrastStandard::Apca);
+    for r in results.required.iter().chain(&results.reference) {
+        assert!(r.wcag_ratio >= 1.0 && r.wcag_ratio <= 21.0);
+        assert_eq!(r.passes, r.apca_passes);
+    }
+}
+
+#[test]
+fn test_standard_selects_pass_criteria() {
+    // Gray background: UI text misses both APCA Lc 75 and WCAG AA 4.5:1
+    let config = GenerateConfig {
+        background: Srgb::new(0x77u8, 0x77, 0x77),
+        foreground: Srgb::new(255u8, 255, 255),
+        ..Default::default()
+    };
+    let scheme = generate(&config).scheme;
+
+    for standard in [
+        ContrastStandard::Apca,
+        ContrastStandard::Wcag,
+        ContrastStandard::Both,
+    ] {
+        let results = validate_with_standard(&scheme, standard);
+        for r in &results.required {
+            let expected = (r.apca_passes || !standard.checks_apca())
+                && (r.wcag_passes || !standard.checks_wcag());
+            assert_eq!(r.passes, expected, "{standard}: {}", r.pair.foreground);
+        }
+    }
+
+    let wcag = validate_with_warnings_for(&scheme, ContrastStandard::Wcag);
+    assert!(!wcag.is_empty());
+    assert!(wcag.iter().all(|w| w.contains("WCAG AA")));
+}
+
+#[test]
+fn test_dim_text_loses_contrast_on_dark_background() {
+    let config = GenerateConfig {
+        background: Srgb::new(0x1du8, 0x20, 0x21),
+        foreground: Srgb::new(0xebu8, 0xdb, 0xb2),
+        ..Default::default()
+    };
+    let scheme = generate(&config).scheme;
+    let plain = validate(&scheme);
+    let attrs = TerminalAttributes::default();
+    let results = validate_terminal_attributes(&scheme, &attrs);
+
+    // 16 accents, each checked dim and bold
+    assert_eq!(results.len(), 32);
+    for r in results.iter().filter(|r| r.attribute == TextAttribute::Dim) {
+        let base = plain
+            .reference
+            .iter()
+            .find(|p| p.pair.foreground == r.foreground && p.pair.background == "base00")
+            .unwrap();
+        assert!(
+            r.contrast.abs() < base.contrast.abs(),
+            "{} should lose contrast when dimmed",
+            r.foreground
+        );
+        assert_eq!(r.min_lc, attrs.dim_min_contrast);
+    }
+
+    // An aggressive dim pushes accents below the readable floor
+    let harsh = TerminalAttributes { dim: 0.2, ..attrs };
+    let warnings = terminal_attribute_warnings(&scheme, &harsh);
+    assert!(!warnings.is_empty());
+    assert!(warnings.iter().all(|w| w.contains("as dim text on base00")));
+
+    // Multipliers of 1 render accents unchanged
+    let neutral = TerminalAttributes {
+        dim: 1.0,
+        bold: 1.0,
+        ..attrs
+    };
+    for r in validate_terminal_attributes(&scheme, &neutral) {
+        let base = plain
+            .reference
+            .iter()
+            .find(|p| p.pair.foreground == r.foreground && p.pair.background == "base00")
+            .unwrap();
+        assert!((r.contrast - base.contrast).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_accents_report_worst_background() {
+    use themalingadingdong::validation::{ValidationConfig, validate_with_config};
+
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let config = ValidationConfig::default();
+    let results = validate_with_config(&scheme, &[], &[], ContrastStandard::Apca, &config);
+
+    // Every accent is measured on base00, base01, and base02
+    assert_eq!(results.reference.len(), 48);
+    for worst in results.required.iter().skip(4) {
+        let checks: Vec<_> = results
+            .reference
+            .iter()
+            .filter(|r| r.pair.foreground == worst.pair.foreground)
+            .collect();
+        assert_eq!(checks.len(), 3);
+        assert!(checks.iter().all(|r| worst.margin() <= r.margin()));
+    }
+
+    // Raising the base02 threshold makes it the worst case for every accent
+    let strict = ValidationConfig {
+        base02: 100.0,
+        ..config
+    };
+    let results = validate_with_config(&scheme, &[], &[], ContrastStandard::Apca, &strict);
+    for worst in results.required.iter().skip(4) {
+        assert_eq!(worst.pair.background, "base02");
+        assert!(!worst.passes);
+    }
+}
+
+#[test]
+fn test_polarity_adjusts_thresholds() {
+    use themalingadingdong::validation::{Polarity, ValidationConfig, validate_with_config};
+
+    // Dark scheme: every accent is light text on a dark background
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let config = ValidationConfig {
+        light_on_dark: 10.0,
+        dark_on_light: -10.0,
+        ..Default::default()
+    };
+    let results = validate_with_config(&scheme, &[], &[], ContrastStandard::Apca, &config);
+    for r in &results.reference {
+        assert_eq!(r.polarity, Polarity::LightOnDark);
+        let base = config.accent_threshold(r.pair.background).unwrap();
+        assert_eq!(r.pair.threshold.min_lc, base + 10.0);
+    }
+    assert_eq!(Polarity::of(42.0), Polarity::DarkOnLight);
+}
+
+#[test]
+fn test_results_carry_text_usage() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let results = validate(&scheme);
+
+    for r in results.required.iter().chain(&results.reference) {
+        assert_eq!(r.usage, TextUsage::of(r.contrast), "{}", r.pair.foreground);
+    }
+    let base07 = results
+        .required
+        .iter()
+        .find(|r| r.pair.foreground == "base07")
+        .unwrap();
+    assert_eq!(base07.usage, TextUsage::BodyText);
+    assert_eq!(base07.usage.to_string(), "ok for body text");
+}
+
+#[test]
+fn test_text_usage_follows_font_lookup() {
+    assert_eq!(TextUsage::of(75.0), TextUsage::BodyText);
+    assert_eq!(TextUsage::of(60.0), TextUsage::ContentText);
+    assert_eq!(TextUsage::of(-55.0), TextUsage::LargeText);
+    assert_eq!(TextUsage::of(25.0), TextUsage::LargeText);
+    assert_eq!(TextUsage::of(15.0), TextUsage::NonText);
+    assert_eq!(TextUsage::of(14.9), TextUsage::NotReadable);
+    assert!(TextUsage::LargeText < TextUsage::ContentText);
+}
+
+#[test]
+fn test_custom_pairs_are_required() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let config = ValidationConfig {
+        pairs: vec![
+            "base03 on base00 >= 100".to_string(),
+            "base05 on base01 ≥ 30".to_string(),
+        ],
+        ..Default::default()
+    };
+    let results = validate_with_config(&scheme, &[], &[], ContrastStandard::Apca, &config);
+    let default = validate(&scheme);
+    assert_eq!(results.required.len(), default.required.len() + 2);
+
+    let custom: Vec<_> = results
+        .required
+        .iter()
+        .filter(|r| r.pair.is_custom())
+        .collect();
+    assert_eq!(
+        (custom[0].pair.foreground, custom[0].pair.background),
+        ("base03", "base00")
+    );
+    assert!(!custom[0].passes);
+    assert!(custom[1].passes);
+
+    let warnings = validation_warnings(&scheme, ContrastStandard::Apca, &config);
+    assert!(
+        warnings.iter().any(|w| w.starts_with("base03 on base00")),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_pair_rules_are_checked() {
+    assert!(parse_pair_rule("base08 on base02 >= 45").is_ok());
+    assert!(parse_pair_rule("base18 on base02 >= 45").is_err());
+    assert!(parse_pair_rule("base08 on base02 >= 120").is_err());
+    assert!(parse_pair_rule("base08 on base02").is_err());
+
+    let config = ValidationConfig {
+        pairs: vec!["base08 over base02 >= 45".to_string()],
+        ..Default::default()
+    };
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("validation.pairs"), "{err}");
+
+    let diagnostics =
+        check_config("[validation]\npairs = [\"base08 on base02 >= 45\", \"base08 on\"]\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].key, "validation.pairs");
+}
@@ -0,0 +1,68 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use themalingadingdong::config::ThemeConfig;
+use themalingadingdong::config_schema::check_config;
+use themalingadingdong::generate::{GenerateConfig, generate_for_variant};
+use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::interpolation::srgb_to_hex;
+use themalingadingdong::variant_match::{VariantMatch, compare_variants};
+use tinted_builder::Color;
+
+#[test]
+fn test_flags_hue_and_colorfulness_drift() {
+    let dark = generate_for_variant(&GenerateConfig::default(), None).scheme;
+    let same = compare_variants(&dark, &dark, &VariantMatch::default());
+    assert_eq!(same.len(), 16);
+    assert!(same.iter().all(|slot| !slot.diverges()));
+
+    // Turn base0D 40° and drain half its colorfulness
+    let mut light = dark.clone();
+    let rgb = light.palette["base0D"].rgb;
+    let jmh = HellwigJmh::from_srgb_u8(palette::Srgb::new(rgb.0, rgb.1, rgb.2));
+    let shifted = HellwigJmh::new(jmh.lightness, jmh.colorfulness / 2.0, jmh.hue + 40.0);
+    light.palette.insert(
+        "base0D".to_string(),
+        Color::new(srgb_to_hex(shifted.into_srgb_u8_gamut_mapped())).unwrap(),
+    );
+
+    let slots = compare_variants(&dark, &light, &VariantMatch::default());
+    let base0d = slots.iter().find(|slot| slot.slot == "base0D").unwrap();
+    assert!(
+        base0d.hue_diverges && base0d.colorfulness_diverges,
+        "{base0d}"
+    );
+    assert!((base0d.hue_shift.unwrap() - 40.0).abs() < 3.0);
+    assert_eq!(slots.iter().filter(|slot| slot.diverges()).count(), 1);
+
+    // Looser thresholds let it through
+    let loose = VariantMatch {
+        max_hue_shift: 60.0,
+        max_colorfulness_shift: 50.0,
+    };
+    assert!(
+        compare_variants(&dark, &light, &loose)
+            .iter()
+            .all(|slot| !slot.diverges())
+    );
+}
+
+#[test]
+fn test_thresholds_read_from_config() {
+    let config: ThemeConfig = toml::from_str("[validation.variants]\nmax_hue_shift = 5\n").unwrap();
+    assert_eq!(config.validation.variants.max_hue_shift, 5.0);
+    assert_eq!(config.validation.variants.max_colorfulness_shift, 8.0);
+    assert!(config.validation.validate().is_ok());
+
+    let diagnostics = check_config("[validation.variants]\nmax_hue_shift = 0\n");
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_cli_compares_linked_variants() {
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Pair"])
+        .args(["--variant", "linked", "match-variants", "--json"])
+        .assert()
+        .stdout(predicate::str::contains("\"slot\": \"base08\""))
+        .stdout(predicate::str::contains("\"hue_shift\""));
+}
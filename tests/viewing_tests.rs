@@ -0,0 +1,142 @@
+//! Tests for Hellwig viewing conditions.
+
+use palette::Srgb;
+use themalingadingdong::config::{ThemeConfig, validate_config};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::HellwigJmh;
+use themalingadingdong::interpolation::DEFAULT_BASE16_HUES;
+use themalingadingdong::viewing::{
+    MODEL_CAPACITY, Surround, ViewingConditions, WhitePoint, active_conditions,
+    with_viewing_conditions,
+};
+
+fn dim_tungsten() -> ViewingConditions {
+    ViewingConditions {
+        white_point: WhitePoint::A,
+        adapting_luminance: 20.0,
+        surround: Surround::Dim,
+    }
+}
+
+fn hue_distance(a: f32, b: f32) -> f32 {
+    ((a - b + 180.0).rem_euclid(360.0) - 180.0).abs()
+}
+
+#[test]
+fn test_default_conditions_match_reference() {
+    let color = Srgb::new(0.8f32, 0.3, 0.2);
+    let reference = HellwigJmh::from_srgb(color);
+    let scoped = with_viewing_conditions(&ViewingConditions::default(), || {
+        HellwigJmh::from_srgb(color)
+    });
+    assert_eq!(reference, scoped);
+
+    let config = GenerateConfig::default();
+    let explicit = GenerateConfig {
+        viewing: ViewingConditions::default(),
+        ..GenerateConfig::default()
+    };
+    assert_eq!(
+        generate(&config).scheme.palette["base0D"].to_hex(),
+        generate(&explicit).scheme.palette["base0D"].to_hex()
+    );
+}
+
+#[test]
+fn test_conditions_round_trip_and_restore() {
+    let conditions = dim_tungsten();
+    let color = Srgb::new(0.2f32, 0.5, 0.7);
+
+    with_viewing_conditions(&conditions, || {
+        assert_eq!(active_conditions(), conditions);
+        let jmh = HellwigJmh::from_srgb(color);
+        let back = jmh.into_srgb();
+        assert!((back.red - color.red).abs() < 1e-3);
+        assert!((back.green - color.green).abs() < 1e-3);
+        assert!((back.blue - color.blue).abs() < 1e-3);
+    });
+    assert!(active_conditions().is_default());
+}
+
+#[test]
+fn test_models_past_capacity_are_rebuilt() {
+    let color = Srgb::new(0.3f32, 0.6, 0.4);
+    let at = |adapting_luminance: f32| ViewingConditions {
+        adapting_luminance,
+        ..dim_tungsten()
+    };
+    let first = with_viewing_conditions(&at(5.0), || HellwigJmh::from_srgb(color));
+
+    with_viewing_conditions(&at(5.0), || {
+        // Enough other conditions to evict the one in effect
+        for i in 0..MODEL_CAPACITY * 2 {
+            let other = at(100.0 + i as f32);
+            with_viewing_conditions(&other, || {
+                assert_eq!(active_conditions(), other);
+                HellwigJmh::from_srgb(color).into_srgb_gamut_mapped();
+            });
+        }
+        // ...which stays usable while it is
+        assert_eq!(active_conditions(), at(5.0));
+        assert_eq!(HellwigJmh::from_srgb(color), first);
+    });
+    let rebuilt = with_viewing_conditions(&at(5.0), || HellwigJmh::from_srgb(color));
+    assert_eq!(rebuilt, first);
+}
+
+#[test]
+fn test_accents_are_solved_under_conditions() {
+    let config = GenerateConfig {
+        viewing: dim_tungsten(),
+        ..GenerateConfig::default()
+    };
+    let scheme = generate(&config).scheme;
+    let reference = generate(&GenerateConfig::default()).scheme;
+    assert_ne!(
+        scheme.palette["base08"].to_hex(),
+        reference.palette["base08"].to_hex()
+    );
+
+    // base08 sits at its hue as seen under the configured conditions
+    let c = &scheme.palette["base08"];
+    let rgb = Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2);
+    let seen = with_viewing_conditions(&config.viewing, || HellwigJmh::from_srgb_u8(rgb));
+    let plain = HellwigJmh::from_srgb_u8(rgb);
+    assert!(
+        hue_distance(seen.hue, DEFAULT_BASE16_HUES[0]) < 2.0,
+        "hue {:.1} under the conditions",
+        seen.hue
+    );
+    assert!(hue_distance(plain.hue, DEFAULT_BASE16_HUES[0]) > 2.0);
+}
+
+#[test]
+fn test_viewing_table_round_trips() {
+    let config: ThemeConfig = toml::from_str(
+        r#"
+[viewing]
+white_point = "a"
+adapting_luminance = 20.0
+surround = "dim"
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.viewing, dim_tungsten());
+    assert_eq!(config.to_generate_config().unwrap().viewing, dim_tungsten());
+
+    let saved = toml::to_string(&config).unwrap();
+    let reloaded: ThemeConfig = toml::from_str(&saved).unwrap();
+    assert_eq!(reloaded.viewing, dim_tungsten());
+
+    // Default conditions are left out of saved configs
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("[viewing]"));
+}
+
+#[test]
+fn test_rejects_nonpositive_adapting_luminance() {
+    let mut config = ThemeConfig::default();
+    config.viewing.adapting_luminance = 0.0;
+    let err = validate_config(&config).unwrap_err().to_string();
+    assert!(err.contains("viewing.adapting_luminance"), "{err}");
+}
@@ -0,0 +1,66 @@
+//! Tests for the JavaScript bindings (`wasm` feature), run natively.
+#![cfg(feature = "wasm")]
+
+use serde_json::Value;
+use themalingadingdong::builder::ThemeBuilder;
+use themalingadingdong::wasm::{gamut_map, generate, validate};
+
+const CONFIG: &str = r##"
+[colors]
+background = "#1a1a2e"
+foreground = "#eaeaea"
+"##;
+
+fn parse(json: String) -> Value {
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_generate_matches_builder() {
+    let theme = parse(generate(CONFIG).unwrap());
+    let expected = ThemeBuilder::new()
+        .background("#1a1a2e")
+        .foreground("#eaeaea")
+        .build()
+        .unwrap();
+    for (slot, color) in &expected.scheme().palette {
+        assert_eq!(theme["scheme"]["palette"][slot], color.to_hex(), "{slot}");
+    }
+    assert_eq!(
+        theme["warnings"].as_array().unwrap().len(),
+        expected.warnings().len()
+    );
+}
+
+#[test]
+fn test_validate_round_trips_generated_scheme() {
+    let theme = parse(generate(CONFIG).unwrap());
+    let report = parse(validate(&theme["scheme"].to_string()).unwrap());
+    assert_eq!(report, theme["validation"]);
+    assert_eq!(report["standard"], "apca");
+
+    let checks = report["checks"].as_array().unwrap();
+    let required = report["required"].as_u64().unwrap() as usize;
+    assert!(checks[..required].iter().all(|c| c["required"] == true));
+    assert!(
+        checks
+            .iter()
+            .any(|c| c["foreground"] == "base07" && c["background"] == "base00")
+    );
+}
+
+#[test]
+fn test_gamut_map_respects_target_gamut() {
+    let colorfulness = |gamut| {
+        parse(gamut_map(60.0, 120.0, 145.0, gamut).unwrap())["colorfulness"]
+            .as_f64()
+            .unwrap()
+    };
+    let srgb = colorfulness("srgb");
+    let p3 = colorfulness("display-p3");
+    assert!(srgb < p3 && p3 < 120.0, "sRGB {srgb}, P3 {p3}");
+
+    let inside = parse(gamut_map(50.0, 10.0, 30.0, "srgb").unwrap());
+    assert_eq!(inside["colorfulness"], 10.0);
+    assert!(inside["hex"].as_str().unwrap().starts_with('#'));
+}
@@ -0,0 +1,39 @@
+use palette::Srgb;
+use themalingadingdong::wcag::{TextSize, WcagLevel, contrast_ratio, relative_luminance};
+
+#[test]
+fn test_luminance_endpoints() {
+    assert!(relative_luminance(Srgb::new(0u8, 0, 0)).abs() < 1e-12);
+    assert!((relative_luminance(Srgb::new(255u8, 255, 255)) - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_known_gray_ratio() {
+    // #777777 on white is the classic "just misses AA" example (4.48:1)
+    let ratio = contrast_ratio(Srgb::new(0x77u8, 0x77, 0x77), Srgb::new(255u8, 255, 255));
+    assert!((ratio - 4.48).abs() < 0.01, "ratio {ratio}");
+    assert_eq!(WcagLevel::achieved(ratio, TextSize::Normal), None);
+    assert_eq!(
+        WcagLevel::achieved(ratio, TextSize::Large),
+        Some(WcagLevel::Aa)
+    );
+}
+
+#[test]
+fn test_ratio_is_symmetric() {
+    let a = Srgb::new(200u8, 80, 70);
+    let b = Srgb::new(30u8, 30, 40);
+    assert!((contrast_ratio(a, b) - contrast_ratio(b, a)).abs() < 1e-12);
+}
+
+#[test]
+fn test_level_thresholds() {
+    assert_eq!(WcagLevel::Aa.min_ratio(TextSize::Normal), 4.5);
+    assert_eq!(WcagLevel::Aa.min_ratio(TextSize::Large), 3.0);
+    assert_eq!(WcagLevel::Aaa.min_ratio(TextSize::Normal), 7.0);
+    assert_eq!(WcagLevel::Aaa.min_ratio(TextSize::Large), 4.5);
+    assert_eq!(
+        WcagLevel::achieved(21.0, TextSize::Normal),
+        Some(WcagLevel::Aaa)
+    );
+}
@@ -0,0 +1,84 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::ansi::AnsiMap;
+use themalingadingdong::cli::OutputFormat;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::provenance::Provenance;
+use themalingadingdong::roles::{RolesConfig, derive_roles};
+use themalingadingdong::web_vars::{to_scss, to_tailwind};
+
+#[test]
+fn test_scss_variables() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let roles = derive_roles(&scheme, &RolesConfig::default());
+    let map = AnsiMap::new(&scheme);
+    let scss = to_scss(&scheme, Some(&roles), Some(&map), None, "theme-");
+
+    let (r, g, b) = scheme.palette["base00"].rgb;
+    assert!(scss.contains(&format!("$theme-base00: #{r:02x}{g:02x}{b:02x};\n")));
+    assert!(scss.contains("$theme-base17: #"));
+    assert!(
+        scss.contains("$theme-selection-bg: $theme-base02;"),
+        "{scss}"
+    );
+    assert!(scss.contains("$theme-ansi-1: $theme-base08;"));
+}
+
+#[test]
+fn test_tailwind_colors() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let roles = derive_roles(&scheme, &RolesConfig::default());
+    let map = AnsiMap::new(&scheme);
+    let js = to_tailwind(&scheme, Some(&roles), Some(&map), None, "tm-");
+
+    let (r, g, b) = scheme.palette["base08"].rgb;
+    let red = format!("\"#{r:02x}{g:02x}{b:02x}\"");
+    assert!(js.contains(&format!("\"tm-base08\": {red},")), "{js}");
+    // Roles and ANSI colors repeat their slot's value
+    assert!(js.contains(&format!("\"tm-ansi-1\": {red},")));
+    assert!(js.contains("\"tm-selection-bg\": \"#"));
+    assert!(js.trim_end().ends_with("};"));
+}
+
+#[test]
+fn test_css_prefix() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let roles = derive_roles(&scheme, &RolesConfig::default());
+    let css = OutputFormat::Css
+        .serialize_prefixed(&scheme, None, Some(&roles), None, None, "theme-")
+        .unwrap();
+    assert!(css.contains("  --theme-base00: #"));
+    assert!(css.contains("--theme-selection-bg: var(--theme-base02);"));
+    assert!(!css.contains("  --base00"));
+}
+
+#[test]
+fn test_prefixed_provenance_reproduces() {
+    let mut provenance = Provenance::new(&GenerateConfig::default(), None, OutputFormat::Scss);
+    provenance.var_prefix = "theme-".to_string();
+    let output = provenance.regenerate().unwrap();
+    assert!(output.contains("$theme-base00: #"));
+    let extracted = Provenance::extract(&output).unwrap();
+    assert_eq!(extracted.var_prefix, "theme-");
+    assert_eq!(extracted.regenerate().unwrap(), output);
+}
+
+#[test]
+fn test_format_flags() {
+    let output = cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Web"])
+        .args(["--format", "tailwind", "--var-prefix", "web-"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let js = String::from_utf8(output).unwrap();
+    assert!(js.starts_with("// Web (dark)\n"), "{js}");
+    assert!(js.contains("\"web-base0d\": \"#"));
+
+    cargo_bin_cmd!("themalingadingdong")
+        .args(["-b", "#1d2021", "-f", "#ebdbb2", "--name", "Web"])
+        .args(["--format", "scss", "--var-prefix", "not a name"])
+        .assert()
+        .failure();
+}
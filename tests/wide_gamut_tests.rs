@@ -0,0 +1,130 @@
+use palette::Srgb;
+use themalingadingdong::config::ThemeConfig;
+use themalingadingdong::gamut_map::{
+    Gamut, gamut_cache_stats, gamut_map_to, max_colorfulness_in, max_colorfulness_many,
+};
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::hellwig::HellwigJmh;
+
+#[test]
+fn test_srgb_encode_matches_input() {
+    for (r, g, b) in [(26u8, 26, 46), (255, 0, 0), (120, 200, 90), (255, 255, 255)] {
+        let color = HellwigJmh::from_srgb_u8(Srgb::new(r, g, b));
+        let [er, eg, eb] = Gamut::Srgb.encode(color);
+        for (encoded, channel) in [(er, r), (eg, g), (eb, b)] {
+            assert!(
+                (encoded - channel as f32 / 255.0).abs() < 0.002,
+                "{encoded} vs {channel}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_wide_gamuts_nest() {
+    for hue in [25.0, 90.0, 145.0, 250.0, 335.0] {
+        for j in [40.0, 60.0, 80.0] {
+            let srgb = gamut_map_to(HellwigJmh::new(j, 150.0, hue), Gamut::Srgb);
+            assert!(Gamut::DisplayP3.contains(srgb));
+            assert!(Gamut::Rec2020.contains(srgb));
+
+            let p3 = max_colorfulness_in(j, hue, Gamut::DisplayP3);
+            let rec2020 = max_colorfulness_in(j, hue, Gamut::Rec2020);
+            assert!(
+                p3 >= srgb.colorfulness - 0.1,
+                "h={hue} J'={j}: P3 {p3} < sRGB {}",
+                srgb.colorfulness
+            );
+            assert!(
+                rec2020 >= p3 - 0.1,
+                "h={hue} J'={j}: 2020 {rec2020} < P3 {p3}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_gamut_map_to_lands_in_target() {
+    let vivid = HellwigJmh::new(60.0, 90.0, 145.0);
+    for gamut in [Gamut::Srgb, Gamut::DisplayP3, Gamut::Rec2020] {
+        let mapped = gamut_map_to(vivid, gamut);
+        assert!(gamut.contains(mapped), "{gamut}: {mapped:?}");
+        assert!((mapped.hue - vivid.hue).abs() < 0.01);
+    }
+}
+
+#[test]
+fn test_generate_display_p3_palette() {
+    let config = GenerateConfig {
+        gamut: Gamut::DisplayP3,
+        ..Default::default()
+    };
+    let result = generate(&config);
+
+    assert_eq!(result.wide_palette.gamut, Gamut::DisplayP3);
+    assert_eq!(result.wide_palette.colors.len(), 24);
+    for (slot, channels) in &result.wide_palette.colors {
+        assert!(
+            channels.iter().all(|c| (-0.001..=1.001).contains(c)),
+            "{slot} out of range: {channels:?}"
+        );
+    }
+    let base08 = result.wide_palette.css_color("base08").unwrap();
+    assert!(base08.starts_with("color(display-p3 "), "{base08}");
+}
+
+#[test]
+fn test_gamut_config_key() {
+    let config: ThemeConfig = toml::from_str("[colors]\ngamut = \"display-p3\"\n").unwrap();
+    assert_eq!(config.colors.gamut, Gamut::DisplayP3);
+    assert_eq!(config.to_generate_config().unwrap().gamut, Gamut::DisplayP3);
+
+    let config: ThemeConfig = toml::from_str("[colors]\ngamut = \"rec2020\"\n").unwrap();
+    assert_eq!(config.colors.gamut, Gamut::Rec2020);
+
+    // The sRGB default is omitted when saving
+    let saved = toml::to_string(&ThemeConfig::default()).unwrap();
+    assert!(!saved.contains("gamut"));
+}
+
+#[test]
+fn test_boundary_cache_is_shared_across_threads() {
+    let (j, hue) = (47.35, 123.45);
+    let computed = std::thread::spawn(move || max_colorfulness_in(j, hue, Gamut::Rec2020))
+        .join()
+        .unwrap();
+
+    let before = gamut_cache_stats();
+    assert_eq!(max_colorfulness_in(j, hue, Gamut::Rec2020), computed);
+    let after = gamut_cache_stats();
+    assert!(after.hits > before.hits);
+    assert!(after.bytes > 0);
+}
+
+#[test]
+fn test_max_colorfulness_many_finds_boundaries() {
+    let points: Vec<(f32, f32)> = (0..19)
+        .map(|i| (12.3 + i as f32 * 4.1, 7.7 + i as f32 * 18.9))
+        .chain([(0.0, 40.0), (100.5, 40.0)])
+        .collect();
+
+    for gamut in [Gamut::DisplayP3, Gamut::Rec2020] {
+        let mut boundaries = vec![0.0; points.len()];
+        max_colorfulness_many(&points, gamut, &mut boundaries);
+
+        for (&(j, hue), &m) in points.iter().zip(&boundaries) {
+            assert_eq!(m, max_colorfulness_in(j, hue, gamut));
+            // Boundaries are searched at bucket centers, so allow some slack
+            if m > 0.0 {
+                assert!(
+                    gamut.contains(HellwigJmh::new(j, m - 0.5, hue)),
+                    "{j} {hue}"
+                );
+                assert!(
+                    !gamut.contains(HellwigJmh::new(j, m + 0.5, hue)),
+                    "{j} {hue}"
+                );
+            }
+        }
+    }
+}
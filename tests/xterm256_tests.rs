@@ -0,0 +1,127 @@
+//! Tests for xterm-256 approximations.
+
+use palette::Srgb;
+use themalingadingdong::generate::{GenerateConfig, generate};
+use themalingadingdong::wcag::relative_luminance;
+use themalingadingdong::xterm256::{
+    Quantization, Xterm256Entry, ansi16_color, approximation_table, approximation_table_with,
+    comment_block, nearest_xterm, xterm_color,
+};
+use tinted_builder::Base16Scheme;
+
+#[test]
+fn cube_and_gray_ramp_values() {
+    assert_eq!(xterm_color(16), Srgb::new(0, 0, 0));
+    assert_eq!(xterm_color(231), Srgb::new(255, 255, 255));
+    assert_eq!(xterm_color(67), Srgb::new(95, 135, 175));
+    assert_eq!(xterm_color(255), Srgb::new(238, 238, 238));
+}
+
+#[test]
+fn exact_palette_colors_match_themselves() {
+    for index in [21u8, 67, 196, 244] {
+        let (found, delta_e) = nearest_xterm(xterm_color(index));
+        assert_eq!(xterm_color(found), xterm_color(index));
+        assert!(delta_e < 1e-3, "index {index}: {delta_e}");
+    }
+}
+
+#[test]
+fn table_covers_every_slot_in_order() {
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let table = approximation_table(&scheme);
+
+    assert_eq!(table.len(), 24);
+    assert_eq!(table[0].slot, "base00");
+    assert_eq!(table[23].slot, "base17");
+    assert!(table.iter().all(|e| e.index >= 16 && e.delta_e >= 0.0));
+
+    let block = comment_block(&table);
+    assert_eq!(block.lines().count(), 25);
+    assert!(block.lines().all(|l| l.starts_with('#')));
+}
+
+#[test]
+fn ansi16_matches_primaries_and_grays() {
+    use themalingadingdong::xterm256::nearest_ansi16;
+
+    for index in 0..16u8 {
+        let (found, delta_e) = nearest_ansi16(ansi16_color(index));
+        assert_eq!(found, index);
+        assert!(delta_e < 1e-3, "index {index}: {delta_e}");
+    }
+    assert_eq!(nearest_ansi16(Srgb::new(200, 20, 20)).0, 1);
+    assert_eq!(nearest_ansi16(Srgb::new(10, 10, 10)).0, 0);
+
+    let scheme = generate(&GenerateConfig::default()).scheme;
+    let table = approximation_table(&scheme);
+    assert!(
+        table
+            .iter()
+            .all(|e| e.ansi16 < 16 && e.ansi16_delta_e >= 0.0)
+    );
+}
+
+/// Slot pairs whose approximations swap their luminance order, and distinct
+/// slots sharing an index.
+fn order_errors(
+    scheme: &Base16Scheme,
+    indices: &[(String, u8)],
+    color: fn(u8) -> Srgb<u8>,
+) -> usize {
+    let rgb = |slot: &str| {
+        let c = &scheme.palette[slot];
+        Srgb::new(c.rgb.0, c.rgb.1, c.rgb.2)
+    };
+    let mut errors = 0;
+    for (i, (a, ia)) in indices.iter().enumerate() {
+        for (b, ib) in &indices[i + 1..] {
+            if rgb(a) == rgb(b) {
+                continue;
+            }
+            let before = relative_luminance(rgb(a)).total_cmp(&relative_luminance(rgb(b)));
+            let after = relative_luminance(color(*ia)).total_cmp(&relative_luminance(color(*ib)));
+            if ia == ib || (before.is_ne() && after.is_ne() && before != after) {
+                errors += 1;
+            }
+        }
+    }
+    errors
+}
+
+#[test]
+fn joint_quantization_keeps_order_better_than_nearest() {
+    for (bg, fg) in [
+        (Srgb::new(0x1d, 0x20, 0x21), Srgb::new(0xeb, 0xdb, 0xb2)),
+        (Srgb::new(0x00, 0x2b, 0x36), Srgb::new(0x83, 0x94, 0x96)),
+        (Srgb::new(0xfb, 0xf1, 0xc7), Srgb::new(0x3c, 0x38, 0x36)),
+    ] {
+        let scheme = generate(&GenerateConfig {
+            background: bg,
+            foreground: fg,
+            ..Default::default()
+        })
+        .scheme;
+        let nearest = approximation_table_with(&scheme, Quantization::Nearest);
+        let joint = approximation_table_with(&scheme, Quantization::Joint);
+        let xterm = |t: &[Xterm256Entry]| {
+            t.iter()
+                .map(|e| (e.slot.clone(), e.index))
+                .collect::<Vec<_>>()
+        };
+        let ansi = |t: &[Xterm256Entry]| {
+            t.iter()
+                .map(|e| (e.slot.clone(), e.ansi16))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(
+            order_errors(&scheme, &xterm(&joint), xterm_color)
+                < order_errors(&scheme, &xterm(&nearest), xterm_color)
+        );
+        assert!(
+            order_errors(&scheme, &ansi(&joint), ansi16_color)
+                < order_errors(&scheme, &ansi(&nearest), ansi16_color)
+        );
+    }
+}
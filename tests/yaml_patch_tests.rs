@@ -0,0 +1,132 @@
+//! Tests for re-exporting imported YAML schemes with their comments kept.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use themalingadingdong::yaml_patch::patch_scheme_yaml;
+use tinted_builder::{Base16Scheme, Color, SchemeSystem};
+
+const GRUVBOX: &str = r##"# Gruvbox dark, hard
+# Upstream: https://github.com/morhetz/gruvbox
+system: "base16"
+name: "Gruvbox dark, hard"
+author: "Dawid Kurek (dawikur@gmail.com), morhetz (https://github.com/morhetz/gruvbox)"
+variant: "dark"
+palette:
+  base00: "#1d2021" # ----
+  base01: "#3c3836" # ---
+  base02: "#504945" # --
+  base03: "#665c54" # -
+  base04: "#bdae93" # +
+  base05: "#d5c4a1" # ++
+  base06: "#ebdbb2" # +++
+  base07: "#fbf1c7" # ++++
+  base08: "#fb4934" # red
+  base09: "#fe8019" # orange
+  base0A: "#fabd2f" # yellow
+  base0B: "#b8bb26" # green
+  base0C: "#8ec07c" # aqua/cyan
+  base0D: "#83a598" # blue
+  base0E: "#d3869b" # purple
+  base0F: "#d65d0e" # brown
+"##;
+
+fn changed_lines(before: &str, after: &str) -> Vec<String> {
+    after
+        .lines()
+        .filter(|line| !before.lines().any(|l| l == *line))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn test_unchanged_scheme_round_trips_exactly() {
+    let scheme: Base16Scheme = serde_yaml::from_str(GRUVBOX).unwrap();
+    let patched = patch_scheme_yaml(GRUVBOX, &scheme).unwrap();
+    // The slug the importer derives is added; nothing else changes
+    assert_eq!(
+        changed_lines(GRUVBOX, &patched),
+        ["slug: gruvbox-dark-hard"]
+    );
+    assert!(
+        patched
+            .lines()
+            .position(|l| l.starts_with("slug:"))
+            .unwrap()
+            < patched.lines().position(|l| l == "palette:").unwrap()
+    );
+}
+
+#[test]
+fn test_edits_keep_comments_and_style() {
+    let mut scheme: Base16Scheme = serde_yaml::from_str(GRUVBOX).unwrap();
+    let original = patch_scheme_yaml(GRUVBOX, &scheme).unwrap();
+
+    scheme.system = SchemeSystem::Base24;
+    scheme.name = "Gruvbox it's mine".to_string();
+    scheme
+        .palette
+        .insert("base08".into(), Color::new("ff5544".into()).unwrap());
+    for slot in 0x10..=0x17 {
+        scheme.palette.insert(
+            format!("base{slot:X}"),
+            Color::new("ff0000".into()).unwrap(),
+        );
+    }
+
+    let patched = patch_scheme_yaml(&original, &scheme).unwrap();
+    let changed = changed_lines(&original, &patched);
+    assert_eq!(changed[0], "system: \"base24\"");
+    assert_eq!(changed[1], "name: \"Gruvbox it's mine\"");
+    assert_eq!(changed[2], "  base08: \"#ff5544\" # red");
+    // New slots follow base0F in its style
+    assert_eq!(changed[3], "  base10: \"#ff0000\"");
+    assert_eq!(changed.len(), 3 + 8);
+    assert!(patched.starts_with("# Gruvbox dark, hard\n# Upstream"));
+
+    let read_back: Base16Scheme = serde_yaml::from_str(&patched).unwrap();
+    assert_eq!(read_back.name, scheme.name);
+    assert_eq!(read_back.palette.len(), 24);
+}
+
+#[test]
+fn test_unsupported_layouts_are_left_to_serialization() {
+    let scheme: Base16Scheme = serde_yaml::from_str(GRUVBOX).unwrap();
+    let flow =
+        "system: base16\nname: Flow\nauthor: me\nvariant: dark\npalette: {base00: \"1d2021\"}\n";
+    assert!(patch_scheme_yaml(flow, &scheme).is_none());
+
+    let legacy = "scheme: \"Legacy\"\nauthor: \"me\"\nbase00: \"1d2021\"\n";
+    assert!(patch_scheme_yaml(legacy, &scheme).is_none());
+
+    let folded = GRUVBOX.replace(
+        "variant: \"dark\"",
+        "description: >\n  A folded\n  description\nvariant: \"dark\"",
+    );
+    assert!(patch_scheme_yaml(&folded, &scheme).is_none());
+}
+
+#[test]
+fn test_input_output_keeps_the_file_layout() {
+    let dir = std::env::temp_dir().join(format!("themalingadingdong-patch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("gruvbox-dark-hard.yaml");
+    std::fs::write(
+        &input,
+        GRUVBOX.replace("\"base16\"", "\"base16\"\nslug: \"gruvbox-dark-hard\""),
+    )
+    .unwrap();
+    let output = dir.join("out.yaml");
+
+    cargo_bin_cmd!("themalingadingdong")
+        .arg("--input")
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(&output).unwrap(),
+        std::fs::read_to_string(&input).unwrap()
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}